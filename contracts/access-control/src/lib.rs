@@ -1,6 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Vec,
+};
 
 mod test;
 
@@ -52,6 +54,9 @@ pub enum DataKey {
     ResourceAccess(String), // Resource -> Vec<Address> (authorized parties)
 }
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct AccessControl;
 
@@ -67,6 +72,7 @@ impl AccessControl {
         }
         admin.require_auth();
         env.storage().persistent().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
 
         env.events()
             .publish((symbol_short!("init"), admin), symbol_short!("success"));
@@ -395,4 +401,34 @@ impl AccessControl {
         env.events()
             .publish((symbol_short!("deact"), wallet), symbol_short!("success"));
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> u32 {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::migrate(&env, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Admin)
+            .expect("Contract not initialized");
+
+        if *admin != stored_admin {
+            panic!("Only admin can upgrade the contract");
+        }
+    }
 }
@@ -0,0 +1,443 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contract]
+pub struct AdmissionDischargeTransferContract;
+
+#[contractimpl]
+impl AdmissionDischargeTransferContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Grant the registration role used for bed assignment and patient movements.
+    pub fn add_staff(env: Env, admin: Address, staff: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Staff(staff), &true);
+        Ok(())
+    }
+
+    pub fn remove_staff(env: Env, admin: Address, staff: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage().persistent().remove(&DataKey::Staff(staff));
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Units and beds
+    // -------------------------------------------------------------------
+
+    pub fn register_unit(
+        env: Env,
+        admin: Address,
+        unit_id: Symbol,
+        name: String,
+        unit_type: UnitType,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if unit_exists(&env, &unit_id) {
+            return Err(Error::UnitAlreadyExists);
+        }
+        let unit = CareUnit {
+            unit_id: unit_id.clone(),
+            name,
+            unit_type,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Unit(unit_id), &unit);
+        Ok(())
+    }
+
+    pub fn add_bed(env: Env, admin: Address, unit_id: Symbol, bed_id: Symbol) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        if !unit_exists(&env, &unit_id) {
+            return Err(Error::UnitNotFound);
+        }
+        if load_bed(&env, &unit_id, &bed_id).is_some() {
+            return Err(Error::BedAlreadyExists);
+        }
+
+        save_bed(
+            &env,
+            &Bed {
+                unit_id: unit_id.clone(),
+                bed_id: bed_id.clone(),
+                status: BedStatus::Available,
+                encounter_id: None,
+            },
+        );
+        let mut beds = unit_beds(&env, &unit_id);
+        beds.push_back(bed_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::UnitBeds(unit_id), &beds);
+        Ok(())
+    }
+
+    /// Update housekeeping status of an unoccupied bed. Occupancy itself is
+    /// only changed through admit, transfer and discharge.
+    pub fn set_bed_status(
+        env: Env,
+        staff: Address,
+        unit_id: Symbol,
+        bed_id: Symbol,
+        status: BedStatus,
+    ) -> Result<(), Error> {
+        require_staff(&env, &staff)?;
+        let mut bed = load_bed(&env, &unit_id, &bed_id).ok_or(Error::BedNotFound)?;
+        if status == BedStatus::Occupied || bed.status == BedStatus::Occupied {
+            return Err(Error::BedUnavailable);
+        }
+        bed.status = status;
+        save_bed(&env, &bed);
+        Ok(())
+    }
+
+    pub fn get_unit(env: Env, unit_id: Symbol) -> Result<CareUnit, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Unit(unit_id))
+            .ok_or(Error::UnitNotFound)
+    }
+
+    pub fn get_bed(env: Env, unit_id: Symbol, bed_id: Symbol) -> Result<Bed, Error> {
+        load_bed(&env, &unit_id, &bed_id).ok_or(Error::BedNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Movements
+    // -------------------------------------------------------------------
+
+    pub fn admit_patient(
+        env: Env,
+        staff: Address,
+        patient_id: Address,
+        unit_id: Symbol,
+        bed_id: Symbol,
+    ) -> Result<u64, Error> {
+        require_staff(&env, &staff)?;
+        let active_key = DataKey::ActiveEncounter(patient_id.clone());
+        if env.storage().persistent().has(&active_key) {
+            return Err(Error::PatientAlreadyAdmitted);
+        }
+
+        let now = env.ledger().timestamp();
+        let encounter_id = next_encounter_id(&env);
+        occupy_bed(&env, &unit_id, &bed_id, encounter_id)?;
+
+        save_encounter(
+            &env,
+            &Encounter {
+                encounter_id,
+                patient_id: patient_id.clone(),
+                unit_id: unit_id.clone(),
+                bed_id: bed_id.clone(),
+                admitted_at: now,
+                discharged_at: 0,
+                disposition: Disposition::Pending,
+            },
+        );
+        env.storage().persistent().set(&active_key, &encounter_id);
+        open_stay(&env, &unit_id, encounter_id, now);
+        push_movement(
+            &env,
+            encounter_id,
+            Movement {
+                kind: MovementKind::Admit,
+                unit_id: unit_id.clone(),
+                bed_id,
+                recorded_by: staff,
+                timestamp: now,
+            },
+        );
+
+        PatientMoved {
+            encounter_id,
+            kind: MovementKind::Admit,
+            patient_id,
+            from_unit: None,
+            to_unit: Some(unit_id),
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(encounter_id)
+    }
+
+    /// Move an admitted patient to another bed, on the same or another unit.
+    pub fn transfer_patient(
+        env: Env,
+        staff: Address,
+        encounter_id: u64,
+        to_unit: Symbol,
+        to_bed: Symbol,
+    ) -> Result<(), Error> {
+        require_staff(&env, &staff)?;
+        let mut encounter = load_open_encounter(&env, encounter_id)?;
+        if encounter.unit_id == to_unit && encounter.bed_id == to_bed {
+            return Err(Error::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        occupy_bed(&env, &to_unit, &to_bed, encounter_id)?;
+        vacate_bed(&env, &encounter.unit_id, &encounter.bed_id);
+
+        let from_unit = encounter.unit_id.clone();
+        if from_unit != to_unit {
+            close_stay(&env, &from_unit, encounter_id, now);
+            open_stay(&env, &to_unit, encounter_id, now);
+        }
+
+        encounter.unit_id = to_unit.clone();
+        encounter.bed_id = to_bed.clone();
+        save_encounter(&env, &encounter);
+        push_movement(
+            &env,
+            encounter_id,
+            Movement {
+                kind: MovementKind::Transfer,
+                unit_id: to_unit.clone(),
+                bed_id: to_bed,
+                recorded_by: staff,
+                timestamp: now,
+            },
+        );
+
+        PatientMoved {
+            encounter_id,
+            kind: MovementKind::Transfer,
+            patient_id: encounter.patient_id,
+            from_unit: Some(from_unit),
+            to_unit: Some(to_unit),
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn discharge_patient(
+        env: Env,
+        staff: Address,
+        encounter_id: u64,
+        disposition: Disposition,
+    ) -> Result<(), Error> {
+        require_staff(&env, &staff)?;
+        if disposition == Disposition::Pending {
+            return Err(Error::InvalidInput);
+        }
+        let mut encounter = load_open_encounter(&env, encounter_id)?;
+        let now = env.ledger().timestamp();
+
+        vacate_bed(&env, &encounter.unit_id, &encounter.bed_id);
+        close_stay(&env, &encounter.unit_id, encounter_id, now);
+        env.storage()
+            .persistent()
+            .remove(&DataKey::ActiveEncounter(encounter.patient_id.clone()));
+
+        encounter.discharged_at = now;
+        encounter.disposition = disposition;
+        save_encounter(&env, &encounter);
+        push_movement(
+            &env,
+            encounter_id,
+            Movement {
+                kind: MovementKind::Discharge,
+                unit_id: encounter.unit_id.clone(),
+                bed_id: encounter.bed_id.clone(),
+                recorded_by: staff,
+                timestamp: now,
+            },
+        );
+
+        PatientMoved {
+            encounter_id,
+            kind: MovementKind::Discharge,
+            patient_id: encounter.patient_id,
+            from_unit: Some(encounter.unit_id),
+            to_unit: None,
+            timestamp: now,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_encounter(env: Env, encounter_id: u64) -> Result<Encounter, Error> {
+        load_encounter(&env, encounter_id).ok_or(Error::EncounterNotFound)
+    }
+
+    pub fn get_active_encounter(env: Env, patient_id: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ActiveEncounter(patient_id))
+    }
+
+    pub fn get_movements(env: Env, encounter_id: u64, offset: u32, limit: u32) -> Vec<Movement> {
+        let history = movements(&env, encounter_id);
+        shared_pagination::paginate(&history, offset, limit)
+    }
+
+    // -------------------------------------------------------------------
+    // Census
+    // -------------------------------------------------------------------
+
+    pub fn get_unit_census(env: Env, unit_id: Symbol) -> Result<UnitCensus, Error> {
+        if !unit_exists(&env, &unit_id) {
+            return Err(Error::UnitNotFound);
+        }
+        let mut census = UnitCensus {
+            unit_id: unit_id.clone(),
+            total_beds: 0,
+            occupied: 0,
+            available: 0,
+            cleaning: 0,
+            out_of_service: 0,
+        };
+        for bed_id in unit_beds(&env, &unit_id).iter() {
+            if let Some(bed) = load_bed(&env, &unit_id, &bed_id) {
+                census.total_beds += 1;
+                match bed.status {
+                    BedStatus::Occupied => census.occupied += 1,
+                    BedStatus::Available => census.available += 1,
+                    BedStatus::Cleaning => census.cleaning += 1,
+                    BedStatus::OutOfService => census.out_of_service += 1,
+                }
+            }
+        }
+        Ok(census)
+    }
+
+    /// Patients currently occupying a bed on the unit.
+    pub fn get_unit_patients(env: Env, unit_id: Symbol) -> Vec<Address> {
+        let mut patients = Vec::new(&env);
+        for bed_id in unit_beds(&env, &unit_id).iter() {
+            let encounter_id = load_bed(&env, &unit_id, &bed_id).and_then(|bed| bed.encounter_id);
+            if let Some(encounter) = encounter_id.and_then(|id| load_encounter(&env, id)) {
+                patients.push_back(encounter.patient_id);
+            }
+        }
+        patients
+    }
+
+    /// Whole patient-days spent on the unit within `[from, to)`, counting
+    /// open stays up to the current ledger time. Intended as the denominator
+    /// for infection and utilisation rates.
+    pub fn get_patient_days(env: Env, unit_id: Symbol, from: u64, to: u64) -> Result<u32, Error> {
+        if to <= from {
+            return Err(Error::InvalidInput);
+        }
+        let now = env.ledger().timestamp();
+        let mut seconds: u64 = 0;
+        for stay in unit_stays(&env, &unit_id).iter() {
+            let end = if stay.end == 0 { now } else { stay.end };
+            let start = stay.start.max(from);
+            let end = end.min(to);
+            if end > start {
+                seconds += end - start;
+            }
+        }
+        Ok((seconds / SECONDS_PER_DAY) as u32)
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_staff(env: &Env, staff: &Address) -> Result<(), Error> {
+    staff.require_auth();
+    if !is_staff(env, staff) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_open_encounter(env: &Env, encounter_id: u64) -> Result<Encounter, Error> {
+    let encounter = load_encounter(env, encounter_id).ok_or(Error::EncounterNotFound)?;
+    if encounter.discharged_at != 0 {
+        return Err(Error::EncounterClosed);
+    }
+    Ok(encounter)
+}
+
+fn occupy_bed(
+    env: &Env,
+    unit_id: &Symbol,
+    bed_id: &Symbol,
+    encounter_id: u64,
+) -> Result<(), Error> {
+    let mut bed = load_bed(env, unit_id, bed_id).ok_or(Error::BedNotFound)?;
+    if bed.status != BedStatus::Available {
+        return Err(Error::BedUnavailable);
+    }
+    bed.status = BedStatus::Occupied;
+    bed.encounter_id = Some(encounter_id);
+    save_bed(env, &bed);
+    Ok(())
+}
+
+/// A vacated bed goes to cleaning before it can be assigned again.
+fn vacate_bed(env: &Env, unit_id: &Symbol, bed_id: &Symbol) {
+    if let Some(mut bed) = load_bed(env, unit_id, bed_id) {
+        bed.status = BedStatus::Cleaning;
+        bed.encounter_id = None;
+        save_bed(env, &bed);
+    }
+}
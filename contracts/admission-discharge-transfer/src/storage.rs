@@ -0,0 +1,133 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{Bed, DataKey, Encounter, Movement, UnitStay};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_staff(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Staff(who.clone()))
+        .unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+pub fn next_encounter_id(env: &Env) -> u64 {
+    let next = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::EncounterCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::EncounterCounter, &next);
+    next
+}
+
+// -----------------------------------------------------------------------
+// Units and beds
+// -----------------------------------------------------------------------
+
+pub fn unit_exists(env: &Env, unit_id: &Symbol) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Unit(unit_id.clone()))
+}
+
+pub fn unit_beds(env: &Env, unit_id: &Symbol) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitBeds(unit_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_bed(env: &Env, bed: &Bed) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Bed(bed.unit_id.clone(), bed.bed_id.clone()), bed);
+}
+
+pub fn load_bed(env: &Env, unit_id: &Symbol, bed_id: &Symbol) -> Option<Bed> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Bed(unit_id.clone(), bed_id.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Encounters
+// -----------------------------------------------------------------------
+
+pub fn save_encounter(env: &Env, encounter: &Encounter) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Encounter(encounter.encounter_id), encounter);
+}
+
+pub fn load_encounter(env: &Env, encounter_id: u64) -> Option<Encounter> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Encounter(encounter_id))
+}
+
+pub fn movements(env: &Env, encounter_id: u64) -> Vec<Movement> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Movements(encounter_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn push_movement(env: &Env, encounter_id: u64, movement: Movement) {
+    let mut list = movements(env, encounter_id);
+    list.push_back(movement);
+    env.storage()
+        .persistent()
+        .set(&DataKey::Movements(encounter_id), &list);
+}
+
+// -----------------------------------------------------------------------
+// Unit stays
+// -----------------------------------------------------------------------
+
+pub fn unit_stays(env: &Env, unit_id: &Symbol) -> Vec<UnitStay> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitStays(unit_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn open_stay(env: &Env, unit_id: &Symbol, encounter_id: u64, start: u64) {
+    let mut stays = unit_stays(env, unit_id);
+    stays.push_back(UnitStay {
+        encounter_id,
+        start,
+        end: 0,
+    });
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnitStays(unit_id.clone()), &stays);
+}
+
+pub fn close_stay(env: &Env, unit_id: &Symbol, encounter_id: u64, end: u64) {
+    let mut stays = unit_stays(env, unit_id);
+    for i in (0..stays.len()).rev() {
+        let mut stay = stays.get(i).unwrap();
+        if stay.encounter_id == encounter_id && stay.end == 0 {
+            stay.end = end;
+            stays.set(i, stay);
+            break;
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnitStays(unit_id.clone()), &stays);
+}
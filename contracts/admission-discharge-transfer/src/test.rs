@@ -0,0 +1,351 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String, Symbol,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    AdmissionDischargeTransferContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(100 * DAY);
+    let contract_id = env.register(AdmissionDischargeTransferContract, ());
+    let client = AdmissionDischargeTransferContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let staff = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_staff(&admin, &staff);
+
+    client.register_unit(
+        &admin,
+        &symbol_short!("4W"),
+        &String::from_str(&env, "4 West Med/Surg"),
+        &UnitType::MedicalSurgical,
+    );
+    client.register_unit(
+        &admin,
+        &symbol_short!("ICU"),
+        &String::from_str(&env, "Medical ICU"),
+        &UnitType::IntensiveCare,
+    );
+    for bed in [symbol_short!("401"), symbol_short!("402")] {
+        client.add_bed(&admin, &symbol_short!("4W"), &bed);
+    }
+    client.add_bed(&admin, &symbol_short!("ICU"), &symbol_short!("I1"));
+
+    (env, admin, staff, client)
+}
+
+fn advance(env: &Env, seconds: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + seconds);
+}
+
+fn ward() -> Symbol {
+    symbol_short!("4W")
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_admit_transfer_discharge() {
+    let (env, _, staff, client) = setup();
+    let patient = Address::generate(&env);
+
+    let encounter_id = client.admit_patient(&staff, &patient, &ward(), &symbol_short!("401"));
+    assert_eq!(client.get_active_encounter(&patient), Some(encounter_id));
+
+    advance(&env, DAY);
+    client.transfer_patient(
+        &staff,
+        &encounter_id,
+        &symbol_short!("ICU"),
+        &symbol_short!("I1"),
+    );
+    assert_eq!(
+        client.get_bed(&ward(), &symbol_short!("401")).status,
+        BedStatus::Cleaning
+    );
+
+    advance(&env, DAY);
+    client.discharge_patient(&staff, &encounter_id, &Disposition::SkilledNursing);
+
+    let encounter = client.get_encounter(&encounter_id);
+    assert_eq!(encounter.unit_id, symbol_short!("ICU"));
+    assert_eq!(encounter.disposition, Disposition::SkilledNursing);
+    assert_eq!(client.get_active_encounter(&patient), None);
+
+    let movements = client.get_movements(&encounter_id, &0, &10);
+    assert_eq!(movements.len(), 3);
+    assert_eq!(movements.get(2).unwrap().kind, MovementKind::Discharge);
+    assert_eq!(
+        client.try_discharge_patient(&staff, &encounter_id, &Disposition::Home),
+        Err(Ok(Error::EncounterClosed))
+    );
+}
+
+#[test]
+fn test_bed_assignment_rules() {
+    let (env, _, staff, client) = setup();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    let encounter_id = client.admit_patient(&staff, &first, &ward(), &symbol_short!("401"));
+    assert_eq!(
+        client.try_admit_patient(&staff, &second, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::BedUnavailable))
+    );
+    assert_eq!(
+        client.try_admit_patient(&staff, &first, &ward(), &symbol_short!("402")),
+        Err(Ok(Error::PatientAlreadyAdmitted))
+    );
+
+    // Vacated beds must be cleaned before reuse.
+    client.discharge_patient(&staff, &encounter_id, &Disposition::Home);
+    assert_eq!(
+        client.try_admit_patient(&staff, &second, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::BedUnavailable))
+    );
+    client.set_bed_status(
+        &staff,
+        &ward(),
+        &symbol_short!("401"),
+        &BedStatus::Available,
+    );
+    client.admit_patient(&staff, &second, &ward(), &symbol_short!("401"));
+}
+
+#[test]
+fn test_unit_census() {
+    let (env, _, staff, client) = setup();
+    let patient = Address::generate(&env);
+    client.admit_patient(&staff, &patient, &ward(), &symbol_short!("402"));
+    client.set_bed_status(
+        &staff,
+        &ward(),
+        &symbol_short!("401"),
+        &BedStatus::OutOfService,
+    );
+
+    let census = client.get_unit_census(&ward());
+    assert_eq!(census.total_beds, 2);
+    assert_eq!(census.occupied, 1);
+    assert_eq!(census.out_of_service, 1);
+    assert_eq!(census.available, 0);
+    assert_eq!(client.get_unit_patients(&ward()).len(), 1);
+    assert_eq!(client.get_unit_patients(&symbol_short!("ICU")).len(), 0);
+}
+
+#[test]
+fn test_patient_days_per_unit() {
+    let (env, _, staff, client) = setup();
+    let start = env.ledger().timestamp();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    let first_encounter = client.admit_patient(&staff, &first, &ward(), &symbol_short!("401"));
+    client.admit_patient(&staff, &second, &ward(), &symbol_short!("402"));
+
+    advance(&env, 2 * DAY);
+    client.transfer_patient(
+        &staff,
+        &first_encounter,
+        &symbol_short!("ICU"),
+        &symbol_short!("I1"),
+    );
+    advance(&env, 3 * DAY);
+
+    // Ward: 2 days for the first patient, 5 (still open) for the second.
+    let end = env.ledger().timestamp();
+    assert_eq!(client.get_patient_days(&ward(), &start, &end), 7);
+    assert_eq!(
+        client.get_patient_days(&symbol_short!("ICU"), &start, &end),
+        3
+    );
+    // Window clipping.
+    assert_eq!(
+        client.get_patient_days(&ward(), &(start + DAY), &(start + 2 * DAY)),
+        2
+    );
+}
+
+#[test]
+fn test_only_staff_can_move_patients() {
+    let (env, _, staff, client) = setup();
+    let outsider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    assert_eq!(
+        client.try_admit_patient(&outsider, &patient, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_admit_patient(&staff, &patient, &ward(), &symbol_short!("999")),
+        Err(Ok(Error::BedNotFound))
+    );
+}
+
+#[test]
+fn test_configuration_requires_admin() {
+    let (env, admin, staff, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_add_staff(&staff, &outsider),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_register_unit(
+            &staff,
+            &symbol_short!("5E"),
+            &String::from_str(&env, "5 East Telemetry"),
+            &UnitType::MedicalSurgical,
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_bed(&staff, &ward(), &symbol_short!("403")),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Removed staff lose access to patient movement
+    client.remove_staff(&admin, &staff);
+    assert_eq!(
+        client.try_admit_patient(&staff, &outsider, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_duplicate_units_and_beds_rejected() {
+    let (env, admin, _, client) = setup();
+    assert_eq!(
+        client.try_register_unit(
+            &admin,
+            &ward(),
+            &String::from_str(&env, "4 West again"),
+            &UnitType::MedicalSurgical,
+        ),
+        Err(Ok(Error::UnitAlreadyExists))
+    );
+    assert_eq!(
+        client.try_add_bed(&admin, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::BedAlreadyExists))
+    );
+    assert_eq!(
+        client.try_add_bed(&admin, &symbol_short!("NONE"), &symbol_short!("1")),
+        Err(Ok(Error::UnitNotFound))
+    );
+}
+
+#[test]
+fn test_invalid_movements_rejected() {
+    let (env, _, staff, client) = setup();
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let first_encounter = client.admit_patient(&staff, &first, &ward(), &symbol_short!("401"));
+    let second_encounter = client.admit_patient(&staff, &second, &ward(), &symbol_short!("402"));
+
+    assert_eq!(
+        client.try_transfer_patient(&staff, &first_encounter, &ward(), &symbol_short!("401")),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_transfer_patient(&staff, &first_encounter, &ward(), &symbol_short!("402")),
+        Err(Ok(Error::BedUnavailable))
+    );
+    assert_eq!(
+        client.try_discharge_patient(&staff, &second_encounter, &Disposition::Pending),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    // Occupancy is only changed through patient movement
+    assert_eq!(
+        client.try_set_bed_status(
+            &staff,
+            &ward(),
+            &symbol_short!("401"),
+            &BedStatus::Available
+        ),
+        Err(Ok(Error::BedUnavailable))
+    );
+    assert_eq!(
+        client.try_set_bed_status(
+            &staff,
+            &symbol_short!("ICU"),
+            &symbol_short!("I1"),
+            &BedStatus::Occupied,
+        ),
+        Err(Ok(Error::BedUnavailable))
+    );
+
+    client.discharge_patient(&staff, &first_encounter, &Disposition::Home);
+    assert_eq!(
+        client.try_transfer_patient(
+            &staff,
+            &first_encounter,
+            &symbol_short!("ICU"),
+            &symbol_short!("I1"),
+        ),
+        Err(Ok(Error::EncounterClosed))
+    );
+    assert_eq!(
+        client.try_discharge_patient(&staff, &99, &Disposition::Home),
+        Err(Ok(Error::EncounterNotFound))
+    );
+}
+
+#[test]
+fn test_lookup_errors() {
+    let (_, _, _, client) = setup();
+    let unknown = symbol_short!("NONE");
+
+    assert_eq!(client.try_get_unit(&unknown), Err(Ok(Error::UnitNotFound)));
+    assert_eq!(
+        client.try_get_bed(&ward(), &symbol_short!("999")),
+        Err(Ok(Error::BedNotFound))
+    );
+    assert_eq!(
+        client.try_get_unit_census(&unknown),
+        Err(Ok(Error::UnitNotFound))
+    );
+    assert_eq!(
+        client.try_get_encounter(&99),
+        Err(Ok(Error::EncounterNotFound))
+    );
+    assert_eq!(
+        client.try_get_patient_days(&ward(), &DAY, &DAY),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, staff, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&staff), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&staff, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,184 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, String, Symbol};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    UnitNotFound = 4,
+    UnitAlreadyExists = 5,
+    BedNotFound = 6,
+    BedAlreadyExists = 7,
+    BedUnavailable = 8,
+    PatientAlreadyAdmitted = 9,
+    EncounterNotFound = 10,
+    EncounterClosed = 11,
+    InvalidInput = 12,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum UnitType {
+    MedicalSurgical,
+    IntensiveCare,
+    StepDown,
+    Pediatric,
+    Maternity,
+    Psychiatric,
+    Observation,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BedStatus {
+    Available,
+    Occupied,
+    /// Vacated and awaiting environmental services.
+    Cleaning,
+    OutOfService,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Disposition {
+    /// Patient is still in house.
+    Pending,
+    Home,
+    HomeHealth,
+    SkilledNursing,
+    InpatientRehab,
+    TransferredToFacility,
+    AgainstMedicalAdvice,
+    Hospice,
+    Expired,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MovementKind {
+    Admit,
+    Transfer,
+    Discharge,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareUnit {
+    pub unit_id: Symbol,
+    pub name: String,
+    pub unit_type: UnitType,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bed {
+    pub unit_id: Symbol,
+    pub bed_id: Symbol,
+    pub status: BedStatus,
+    pub encounter_id: Option<u64>,
+}
+
+/// One inpatient stay, from admission to discharge.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Encounter {
+    pub encounter_id: u64,
+    pub patient_id: Address,
+    pub unit_id: Symbol,
+    pub bed_id: Symbol,
+    pub admitted_at: u64,
+    /// 0 while the patient is still in house.
+    pub discharged_at: u64,
+    pub disposition: Disposition,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Movement {
+    pub kind: MovementKind,
+    pub unit_id: Symbol,
+    pub bed_id: Symbol,
+    pub recorded_by: Address,
+    pub timestamp: u64,
+}
+
+/// Time an encounter spent on one unit; the unit-level denominator for
+/// patient-day based rates.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitStay {
+    pub encounter_id: u64,
+    pub start: u64,
+    /// 0 while the stay is open.
+    pub end: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitCensus {
+    pub unit_id: Symbol,
+    pub total_beds: u32,
+    pub occupied: u32,
+    pub available: u32,
+    pub cleaning: u32,
+    pub out_of_service: u32,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+/// Published for every admission, transfer and discharge.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientMoved {
+    #[topic]
+    pub encounter_id: u64,
+    #[topic]
+    pub kind: MovementKind,
+    pub patient_id: Address,
+    pub from_unit: Option<Symbol>,
+    pub to_unit: Option<Symbol>,
+    pub timestamp: u64,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// staff -> bool
+    Staff(Address),
+    /// unit_id -> CareUnit
+    Unit(Symbol),
+    /// unit_id -> Vec<Symbol> (bed ids)
+    UnitBeds(Symbol),
+    /// (unit_id, bed_id) -> Bed
+    Bed(Symbol, Symbol),
+    EncounterCounter,
+    /// encounter_id -> Encounter
+    Encounter(u64),
+    /// patient -> encounter_id of the open stay
+    ActiveEncounter(Address),
+    /// encounter_id -> Vec<Movement>
+    Movements(u64),
+    /// unit_id -> Vec<UnitStay>
+    UnitStays(Symbol),
+}
@@ -1,8 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracterror, contractevent, symbol_short, Address, Env, String,
-    Symbol, Vec,
+    contract, contractimpl, contracterror, contractevent, symbol_short, xdr::ToXdr, Address,
+    BytesN, Env, String, Symbol, Vec,
 };
 
 mod types;
@@ -25,12 +25,46 @@ pub struct AllergyUpdated {
     pub new_severity: Symbol,
 }
 
+#[contractevent]
+pub struct SeverityDowngradeRequested {
+    pub allergy_id: u64,
+    pub current_severity: Symbol,
+    pub requested_severity: Symbol,
+}
+
+#[contractevent]
+pub struct AllergiesReconciled {
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub context: Symbol,
+    pub active_count: u32,
+}
+
 #[contractevent]
 pub struct AllergyResolved {
     pub allergy_id: u64,
     pub resolution_date: u64,
 }
 
+#[contractevent]
+pub struct ReactionRecorded {
+    pub allergy_id: u64,
+    pub severity: Symbol,
+}
+
+#[contractevent]
+pub struct SeverityEscalationSuggested {
+    pub allergy_id: u64,
+    pub current_severity: Symbol,
+    pub suggested_severity: Symbol,
+}
+
+#[contractevent]
+pub struct AllergyStatusChanged {
+    pub patient_id: Address,
+    pub status: PatientAllergyStatus,
+}
+
 #[contractevent]
 pub struct AccessGranted {
     pub patient_id: Address,
@@ -43,6 +77,32 @@ pub struct AccessRevoked {
     pub provider_id: Address,
 }
 
+#[contractevent]
+pub struct OrganizationAccessGranted {
+    pub patient_id: Address,
+    pub facility_id: Address,
+}
+
+#[contractevent]
+pub struct OrganizationAccessRevoked {
+    pub patient_id: Address,
+    pub facility_id: Address,
+}
+
+#[contractevent]
+pub struct RosterUpdated {
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub provider_id: Address,
+    pub added: bool,
+}
+
+#[contractevent]
+pub struct PediatricWeightRecorded {
+    pub patient_id: Address,
+    pub weight_grams: u32,
+}
+
 /// Error codes for allergy management operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -56,8 +116,31 @@ pub enum Error {
     InvalidDate = 6,
     DuplicateAllergy = 7,
     AccessDenied = 8,
+    AllergyStatusConflict = 9,
+    BatchTooLarge = 10,
+    OrganizationGrantNotFound = 11,
+    RosterFull = 12,
+    NoPendingDowngrade = 13,
+    ConfirmationRequired = 14,
+    InvalidReconciliationContext = 15,
+    WeightNotRecorded = 16,
+    StaleWeight = 17,
+    InvalidWeight = 18,
+    InvalidDose = 19,
 }
 
+/// Maximum number of allergies accepted by a single import call
+const MAX_IMPORT_BATCH: u32 = 50;
+
+/// Maximum number of providers a facility may place on a patient's roster
+const MAX_ORG_ROSTER: u32 = 25;
+
+/// A pediatric weight older than this is too stale to dose against
+const PEDIATRIC_WEIGHT_MAX_AGE_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct AllergyManagement;
 
@@ -73,6 +156,7 @@ impl AllergyManagement {
         
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::AllergyCounter, &0u64);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
     }
 
     /// Record a new allergy for a patient
@@ -83,51 +167,50 @@ impl AllergyManagement {
         request: RecordAllergyRequest,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
+        Self::store_allergy(&env, &patient_id, &provider_id, &request)
+    }
 
-        // Validate inputs
-        validation::validate_allergen_type(&request.allergen_type)?;
-        validation::validate_severity(&request.severity)?;
+    /// Import a batch of allergies, e.g. when onboarding a patient from another system.
+    ///
+    /// Each item is validated and duplicate-checked on its own; valid items are
+    /// recorded and the rest are reported back with a rejection reason.
+    pub fn import_allergies(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        requests: Vec<RecordAllergyRequest>,
+    ) -> Result<ImportReconciliation, Error> {
+        provider_id.require_auth();
 
-        // Check for duplicate allergy
-        if storage::check_duplicate_allergy(&env, &patient_id, &request.allergen, &request.allergen_type) {
-            return Err(Error::DuplicateAllergy);
+        if requests.len() > MAX_IMPORT_BATCH {
+            return Err(Error::BatchTooLarge);
         }
 
-        // Generate unique allergy ID
-        let allergy_id = storage::get_next_allergy_id(&env);
-
-        let allergy = AllergyRecord {
-            allergy_id,
-            patient_id: patient_id.clone(),
-            provider_id: provider_id.clone(),
-            allergen: request.allergen.clone(),
-            allergen_type: request.allergen_type.clone(),
-            reaction_type: request.reaction_type.clone(),
-            severity: request.severity.clone(),
-            onset_date: request.onset_date,
-            recorded_date: env.ledger().timestamp(),
-            verified: request.verified,
-            status: AllergyStatus::Active,
-            resolution_date: None,
-            resolution_reason: None,
-            severity_history: Vec::new(&env),
-        };
-
-        // Store allergy record
-        storage::save_allergy(&env, &allergy);
-        storage::add_patient_allergy(&env, &patient_id, allergy_id);
+        let mut imported = Vec::new(&env);
+        let mut rejected = Vec::new(&env);
 
-        // Emit event
-        AllergyRecorded {
-            patient_id: patient_id.clone(),
-            allergy_id,
+        for (index, request) in requests.iter().enumerate() {
+            match Self::store_allergy(&env, &patient_id, &provider_id, &request) {
+                Ok(allergy_id) => imported.push_back(allergy_id),
+                Err(err) => rejected.push_back(RejectedAllergyImport {
+                    index: index as u32,
+                    allergen: request.allergen.clone(),
+                    reason: validation::rejection_reason(err),
+                }),
+            }
         }
-        .publish(&env);
 
-        Ok(allergy_id)
+        Ok(ImportReconciliation {
+            total: requests.len(),
+            imported,
+            rejected,
+        })
     }
 
-    /// Update the severity of an existing allergy
+    /// Update the severity of an existing allergy.
+    ///
+    /// Downgrades from severe or critical are held until a second provider or
+    /// the patient confirms them with `confirm_severity_downgrade`.
     pub fn update_allergy_severity(
         env: Env,
         allergy_id: u64,
@@ -141,39 +224,95 @@ impl AllergyManagement {
         validation::validate_severity(&new_severity)?;
 
         // Load allergy record
-        let mut allergy = storage::get_allergy(&env, allergy_id)?;
+        let allergy = storage::get_allergy(&env, allergy_id)?;
 
         // Check if already resolved
         if allergy.status == AllergyStatus::Resolved {
             return Err(Error::AlreadyResolved);
         }
 
-        // Create severity update entry
-        let update = SeverityUpdate {
-            previous_severity: allergy.severity.clone(),
-            new_severity: new_severity.clone(),
-            updated_by: provider_id.clone(),
-            updated_at: env.ledger().timestamp(),
-            reason: reason.clone(),
-        };
+        if validation::requires_downgrade_confirmation(&allergy.severity, &new_severity) {
+            storage::save_pending_downgrade(
+                &env,
+                &PendingSeverityDowngrade {
+                    allergy_id,
+                    new_severity: new_severity.clone(),
+                    requested_by: provider_id,
+                    requested_at: env.ledger().timestamp(),
+                    reason,
+                },
+            );
 
-        // Update severity and add to history
-        allergy.severity = new_severity.clone();
-        allergy.severity_history.push_back(update);
+            SeverityDowngradeRequested {
+                allergy_id,
+                current_severity: allergy.severity,
+                requested_severity: new_severity,
+            }
+            .publish(&env);
 
-        // Save updated record
-        storage::save_allergy(&env, &allergy);
+            return Ok(());
+        }
 
-        // Emit event
-        AllergyUpdated {
-            allergy_id,
-            new_severity: new_severity.clone(),
+        // A held downgrade is superseded by any update that takes effect directly
+        storage::remove_pending_downgrade(&env, allergy_id);
+        Self::apply_severity_update(&env, allergy, provider_id, new_severity, reason, None);
+
+        Ok(())
+    }
+
+    /// Confirm a pending severity downgrade. The confirming party must be the
+    /// patient or a provider with access other than the one who requested it.
+    pub fn confirm_severity_downgrade(
+        env: Env,
+        allergy_id: u64,
+        confirmer: Address,
+    ) -> Result<(), Error> {
+        confirmer.require_auth();
+
+        let pending = storage::get_pending_downgrade(&env, allergy_id)?;
+        let allergy = storage::get_allergy(&env, allergy_id)?;
+
+        if confirmer == pending.requested_by {
+            return Err(Error::ConfirmationRequired);
         }
-        .publish(&env);
+        if !storage::check_access_permission(&env, &allergy.patient_id, &confirmer) {
+            return Err(Error::AccessDenied);
+        }
+        if allergy.status == AllergyStatus::Resolved {
+            return Err(Error::AlreadyResolved);
+        }
+
+        storage::remove_pending_downgrade(&env, allergy_id);
+        Self::apply_severity_update(
+            &env,
+            allergy,
+            pending.requested_by,
+            pending.new_severity,
+            pending.reason,
+            Some(confirmer),
+        );
 
         Ok(())
     }
 
+    /// Get the severity downgrade awaiting confirmation (requires access)
+    pub fn get_pending_severity_downgrade(
+        env: Env,
+        allergy_id: u64,
+        requester: Address,
+    ) -> Result<PendingSeverityDowngrade, Error> {
+        requester.require_auth();
+
+        let allergy = storage::get_allergy(&env, allergy_id)?;
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &allergy.patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        storage::get_pending_downgrade(&env, allergy_id)
+    }
+
     /// Resolve an allergy (mark as no longer active)
     pub fn resolve_allergy(
         env: Env,
@@ -215,45 +354,295 @@ impl AllergyManagement {
         Ok(())
     }
 
+    /// Assert a patient's overall allergy status (NKDA, NKA, unknown, has allergies)
+    pub fn set_allergy_status(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        status: PatientAllergyStatus,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        // NKA/NKDA cannot be asserted while contradicting allergies are active
+        let conflict = match status {
+            PatientAllergyStatus::Nka => storage::has_active_allergy(&env, &patient_id, None),
+            PatientAllergyStatus::Nkda => {
+                storage::has_active_allergy(&env, &patient_id, Some(symbol_short!("med")))
+            }
+            _ => false,
+        };
+        if conflict {
+            return Err(Error::AllergyStatusConflict);
+        }
+
+        Self::apply_allergy_status(&env, &patient_id, &provider_id, status);
+
+        Ok(())
+    }
+
+    /// Get a patient's overall allergy status (requires access)
+    pub fn get_allergy_status(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+    ) -> Result<PatientAllergyStatusRecord, Error> {
+        requester.require_auth();
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        Ok(storage::get_patient_allergy_status(&env, &patient_id))
+    }
+
+    /// Record an actual reaction occurrence for an existing allergy.
+    ///
+    /// Returns the suggested severity when the observed reaction is more
+    /// severe than the severity currently recorded on the allergy.
+    pub fn record_reaction_event(
+        env: Env,
+        allergy_id: u64,
+        provider_id: Address,
+        request: ReactionEventRequest,
+    ) -> Result<Option<Symbol>, Error> {
+        provider_id.require_auth();
+
+        // Validate inputs
+        validation::validate_severity(&request.severity)?;
+        if request.reaction_date > env.ledger().timestamp() {
+            return Err(Error::InvalidDate);
+        }
+
+        // Load allergy record
+        let allergy = storage::get_allergy(&env, allergy_id)?;
+
+        // Check if already resolved
+        if allergy.status == AllergyStatus::Resolved {
+            return Err(Error::AlreadyResolved);
+        }
+
+        let suggested_severity = if validation::severity_rank(&request.severity)
+            > validation::severity_rank(&allergy.severity)
+        {
+            Some(request.severity.clone())
+        } else {
+            None
+        };
+
+        let event = ReactionEvent {
+            allergy_id,
+            reported_by: provider_id.clone(),
+            reaction_date: request.reaction_date,
+            exposure_source: request.exposure_source,
+            severity: request.severity.clone(),
+            treatment_given: request.treatment_given,
+            outcome: request.outcome,
+            recorded_at: env.ledger().timestamp(),
+            suggested_severity: suggested_severity.clone(),
+        };
+
+        storage::add_reaction_event(&env, &event);
+
+        // Emit events
+        ReactionRecorded {
+            allergy_id,
+            severity: request.severity,
+        }
+        .publish(&env);
+
+        if let Some(suggested) = suggested_severity.clone() {
+            SeverityEscalationSuggested {
+                allergy_id,
+                current_severity: allergy.severity.clone(),
+                suggested_severity: suggested,
+            }
+            .publish(&env);
+        }
+
+        Ok(suggested_severity)
+    }
+
+    /// Get the reaction history for an allergy (requires access)
+    pub fn get_reaction_history(
+        env: Env,
+        allergy_id: u64,
+        requester: Address,
+    ) -> Result<Vec<ReactionEvent>, Error> {
+        requester.require_auth();
+
+        let allergy = storage::get_allergy(&env, allergy_id)?;
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &allergy.patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        Ok(storage::get_reaction_history(&env, allergy_id))
+    }
+
     /// Check for potential drug-allergy interactions
     pub fn check_drug_allergy_interaction(
         env: Env,
         patient_id: Address,
         drug_name: String,
     ) -> Result<Vec<AllergyInteraction>, Error> {
-        let mut interactions = Vec::new(&env);
+        Ok(Self::find_interactions(&env, &patient_id, &drug_name))
+    }
 
-        // Get all active allergies for patient
-        let allergy_ids = storage::get_patient_allergies(&env, &patient_id);
+    /// Record a pediatric patient's current weight (requires access)
+    pub fn record_pediatric_weight(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        weight_grams: u32,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
 
-        for allergy_id in allergy_ids.iter() {
+        validation::validate_pediatric_weight(weight_grams)?;
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &provider_id) {
+            return Err(Error::AccessDenied);
+        }
+
+        storage::save_pediatric_weight(
+            &env,
+            &PediatricWeight {
+                patient_id: patient_id.clone(),
+                weight_grams,
+                recorded_by: provider_id,
+                recorded_at: env.ledger().timestamp(),
+            },
+        );
+
+        PediatricWeightRecorded {
+            patient_id,
+            weight_grams,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Check a drug against a pediatric patient's allergies and express the
+    /// proposed dose per kilogram of the latest recorded weight. Fails when no
+    /// weight has been recorded within the last 30 days. Requires access.
+    pub fn check_pediatric_interaction(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        drug_name: String,
+        dose_mcg: u64,
+    ) -> Result<PediatricInteractionCheck, Error> {
+        requester.require_auth();
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        let weight = storage::get_pediatric_weight(&env, &patient_id)?;
+        if env.ledger().timestamp().saturating_sub(weight.recorded_at)
+            > PEDIATRIC_WEIGHT_MAX_AGE_SECS
+        {
+            return Err(Error::StaleWeight);
+        }
+
+        let dose_mcg_per_kg = dose_mcg
+            .checked_mul(1000)
+            .and_then(|dose| dose.checked_div(u64::from(weight.weight_grams)))
+            .ok_or(Error::InvalidDose)?;
+
+        Ok(PediatricInteractionCheck {
+            interactions: Self::find_interactions(&env, &patient_id, &drug_name),
+            weight_grams: weight.weight_grams,
+            weight_recorded_at: weight.recorded_at,
+            dose_mcg,
+            dose_mcg_per_kg,
+        })
+    }
+
+    /// Get a pediatric patient's latest recorded weight (requires access)
+    pub fn get_pediatric_weight(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+    ) -> Result<PediatricWeight, Error> {
+        requester.require_auth();
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        storage::get_pediatric_weight(&env, &patient_id)
+    }
+
+    /// Record that a provider reconciled the patient's allergy list at a
+    /// transition of care, capturing a hash of the active allergies at that moment
+    pub fn create_reconciliation_snapshot(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        context: Symbol,
+    ) -> Result<ReconciliationSnapshot, Error> {
+        provider_id.require_auth();
+
+        validation::validate_reconciliation_context(&env, &context)?;
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &provider_id) {
+            return Err(Error::AccessDenied);
+        }
+
+        let mut active_allergies: Vec<AllergyRecord> = Vec::new(&env);
+        for allergy_id in storage::get_patient_allergies(&env, &patient_id).iter() {
             if let Ok(allergy) = storage::get_allergy(&env, allergy_id) {
                 if allergy.status == AllergyStatus::Active {
-                    // Check for medication allergies
-                    if allergy.allergen_type == symbol_short!("med") {
-                        // Direct match or cross-sensitivity check
-                        if validation::check_drug_match(&allergy.allergen, &drug_name)
-                            || validation::check_cross_sensitivity(&env, &allergy.allergen, &drug_name)
-                        {
-                            let interaction = AllergyInteraction {
-                                allergy_id: allergy.allergy_id,
-                                allergen: allergy.allergen.clone(),
-                                severity: allergy.severity.clone(),
-                                reaction_type: allergy.reaction_type.clone(),
-                                interaction_type: if validation::check_drug_match(&allergy.allergen, &drug_name) {
-                                    symbol_short!("direct")
-                                } else {
-                                    symbol_short!("cross")
-                                },
-                            };
-                            interactions.push_back(interaction);
-                        }
-                    }
+                    active_allergies.push_back(allergy);
                 }
             }
         }
 
-        Ok(interactions)
+        let snapshot = ReconciliationSnapshot {
+            patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
+            context: context.clone(),
+            active_count: active_allergies.len(),
+            allergies_hash: env.crypto().sha256(&active_allergies.to_xdr(&env)).into(),
+            reconciled_at: env.ledger().timestamp(),
+        };
+        storage::add_reconciliation_snapshot(&env, &snapshot);
+
+        AllergiesReconciled {
+            patient_id,
+            provider_id,
+            context,
+            active_count: snapshot.active_count,
+        }
+        .publish(&env);
+
+        Ok(snapshot)
+    }
+
+    /// Get one page of a patient's reconciliation snapshots, oldest first (requires access)
+    pub fn get_reconciliation_snapshots(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<ReconciliationSnapshot>, Error> {
+        requester.require_auth();
+
+        // Check access permissions
+        if !storage::check_access_permission(&env, &patient_id, &requester) {
+            return Err(Error::AccessDenied);
+        }
+
+        let snapshots = storage::get_reconciliation_snapshots(&env, &patient_id);
+        Ok(shared_pagination::paginate(&snapshots, offset, limit))
     }
 
     /// Get all active allergies for a patient
@@ -283,11 +672,13 @@ impl AllergyManagement {
         Ok(active_allergies)
     }
 
-    /// Get all allergies (active and resolved) for a patient
+    /// Get one page of all allergies (active and resolved) for a patient
     pub fn get_all_allergies(
         env: Env,
         patient_id: Address,
         requester: Address,
+        offset: u32,
+        limit: u32,
     ) -> Result<Vec<AllergyRecord>, Error> {
         requester.require_auth();
 
@@ -296,16 +687,15 @@ impl AllergyManagement {
             return Err(Error::AccessDenied);
         }
 
-        let mut all_allergies = Vec::new(&env);
         let allergy_ids = storage::get_patient_allergies(&env, &patient_id);
 
-        for allergy_id in allergy_ids.iter() {
-            if let Ok(allergy) = storage::get_allergy(&env, allergy_id) {
-                all_allergies.push_back(allergy);
-            }
-        }
-
-        Ok(all_allergies)
+        Ok(shared_pagination::paginate_map(
+            &env,
+            &allergy_ids,
+            offset,
+            limit,
+            |allergy_id| storage::get_allergy(&env, allergy_id).ok(),
+        ))
     }
 
     /// Grant access to view patient allergies
@@ -332,6 +722,139 @@ impl AllergyManagement {
         .publish(&env);
     }
 
+    /// Authorize a care organization to manage provider access on the patient's behalf
+    pub fn grant_organization_access(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        facility_admin: Address,
+    ) {
+        patient_id.require_auth();
+
+        // Re-granting keeps the existing roster but may change the admin
+        let roster = match storage::get_organization_grant(&env, &patient_id, &facility_id) {
+            Ok(existing) => existing.roster,
+            Err(_) => Vec::new(&env),
+        };
+
+        let grant = OrganizationGrant {
+            patient_id: patient_id.clone(),
+            facility_id: facility_id.clone(),
+            facility_admin,
+            roster,
+            granted_at: env.ledger().timestamp(),
+        };
+        storage::save_organization_grant(&env, &grant);
+
+        OrganizationAccessGranted {
+            patient_id,
+            facility_id,
+        }
+        .publish(&env);
+    }
+
+    /// Revoke a care organization's grant, removing every provider on its roster
+    pub fn revoke_organization_access(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        storage::get_organization_grant(&env, &patient_id, &facility_id)?;
+        storage::remove_organization_grant(&env, &patient_id, &facility_id);
+
+        OrganizationAccessRevoked {
+            patient_id,
+            facility_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Add a provider to a facility's roster for a patient (facility admin only)
+    pub fn add_roster_provider(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        facility_admin: Address,
+        provider_id: Address,
+    ) -> Result<(), Error> {
+        facility_admin.require_auth();
+
+        let mut grant = storage::get_organization_grant(&env, &patient_id, &facility_id)?;
+        if grant.facility_admin != facility_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if !grant.roster.contains(&provider_id) {
+            if grant.roster.len() >= MAX_ORG_ROSTER {
+                return Err(Error::RosterFull);
+            }
+            grant.roster.push_back(provider_id.clone());
+            storage::save_organization_grant(&env, &grant);
+        }
+
+        RosterUpdated {
+            patient_id,
+            facility_id,
+            provider_id,
+            added: true,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Remove a provider from a facility's roster for a patient (facility admin only)
+    pub fn remove_roster_provider(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        facility_admin: Address,
+        provider_id: Address,
+    ) -> Result<(), Error> {
+        facility_admin.require_auth();
+
+        let mut grant = storage::get_organization_grant(&env, &patient_id, &facility_id)?;
+        if grant.facility_admin != facility_admin {
+            return Err(Error::Unauthorized);
+        }
+
+        if let Some(index) = grant.roster.first_index_of(&provider_id) {
+            grant.roster.remove(index);
+            storage::save_organization_grant(&env, &grant);
+        }
+
+        RosterUpdated {
+            patient_id,
+            facility_id,
+            provider_id,
+            added: false,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a patient's grant to a care organization (patient or facility admin)
+    pub fn get_organization_grant(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        requester: Address,
+    ) -> Result<OrganizationGrant, Error> {
+        requester.require_auth();
+
+        let grant = storage::get_organization_grant(&env, &patient_id, &facility_id)?;
+        if requester != patient_id && requester != grant.facility_admin {
+            return Err(Error::AccessDenied);
+        }
+
+        Ok(grant)
+    }
+
     /// Get allergy by ID (requires access)
     pub fn get_allergy(
         env: Env,
@@ -349,6 +872,191 @@ impl AllergyManagement {
 
         Ok(allergy)
     }
+
+    /// Extend the TTL of a patient's allergy records and reaction history.
+    /// Anyone may call this to keep resolved allergies readable.
+    pub fn extend_allergy_ttl(env: Env, patient_id: Address) -> Result<(), Error> {
+        if !storage::extend_patient_allergy_entries(&env, &patient_id) {
+            return Err(Error::AllergyNotFound);
+        }
+        shared_ttl::extend_instance(&env, shared_ttl::INSTANCE);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        shared_access::require_admin(env, admin, &DataKey::Admin).map_err(|_| Error::Unauthorized)
+    }
+
+    /// Active medication allergies that match `drug_name` directly or by cross-sensitivity
+    fn find_interactions(
+        env: &Env,
+        patient_id: &Address,
+        drug_name: &String,
+    ) -> Vec<AllergyInteraction> {
+        let mut interactions = Vec::new(env);
+
+        // Get all active allergies for patient
+        let allergy_ids = storage::get_patient_allergies(env, patient_id);
+
+        for allergy_id in allergy_ids.iter() {
+            if let Ok(allergy) = storage::get_allergy(env, allergy_id) {
+                if allergy.status == AllergyStatus::Active {
+                    // Check for medication allergies
+                    if allergy.allergen_type == symbol_short!("med") {
+                        // Direct match or cross-sensitivity check
+                        if validation::check_drug_match(&allergy.allergen, drug_name)
+                            || validation::check_cross_sensitivity(env, &allergy.allergen, drug_name)
+                        {
+                            let interaction = AllergyInteraction {
+                                allergy_id: allergy.allergy_id,
+                                allergen: allergy.allergen.clone(),
+                                severity: allergy.severity.clone(),
+                                reaction_type: allergy.reaction_type.clone(),
+                                interaction_type: if validation::check_drug_match(&allergy.allergen, drug_name) {
+                                    symbol_short!("direct")
+                                } else {
+                                    symbol_short!("cross")
+                                },
+                            };
+                            interactions.push_back(interaction);
+                        }
+                    }
+                }
+            }
+        }
+
+        interactions
+    }
+
+    fn store_allergy(
+        env: &Env,
+        patient_id: &Address,
+        provider_id: &Address,
+        request: &RecordAllergyRequest,
+    ) -> Result<u64, Error> {
+        // Validate inputs
+        validation::validate_allergen_type(&request.allergen_type)?;
+        validation::validate_severity(&request.severity)?;
+
+        // Check for duplicate allergy
+        if storage::check_duplicate_allergy(env, patient_id, &request.allergen, &request.allergen_type) {
+            return Err(Error::DuplicateAllergy);
+        }
+
+        // Generate unique allergy ID
+        let allergy_id = storage::get_next_allergy_id(env);
+
+        let allergy = AllergyRecord {
+            allergy_id,
+            patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
+            allergen: request.allergen.clone(),
+            allergen_type: request.allergen_type.clone(),
+            reaction_type: request.reaction_type.clone(),
+            severity: request.severity.clone(),
+            onset_date: request.onset_date,
+            recorded_date: env.ledger().timestamp(),
+            verified: request.verified,
+            status: AllergyStatus::Active,
+            resolution_date: None,
+            resolution_reason: None,
+            severity_history: Vec::new(env),
+        };
+
+        // Store allergy record
+        storage::save_allergy(env, &allergy);
+        storage::add_patient_allergy(env, patient_id, allergy_id);
+
+        // First recorded allergy replaces any NKDA/NKA/unknown assertion
+        let status = storage::get_patient_allergy_status(env, patient_id);
+        if status.status != PatientAllergyStatus::HasAllergies {
+            Self::apply_allergy_status(
+                env,
+                patient_id,
+                provider_id,
+                PatientAllergyStatus::HasAllergies,
+            );
+        }
+
+        // Emit event
+        AllergyRecorded {
+            patient_id: patient_id.clone(),
+            allergy_id,
+        }
+        .publish(env);
+
+        Ok(allergy_id)
+    }
+
+    fn apply_severity_update(
+        env: &Env,
+        mut allergy: AllergyRecord,
+        provider_id: Address,
+        new_severity: Symbol,
+        reason: String,
+        confirmed_by: Option<Address>,
+    ) {
+        // Create severity update entry
+        let update = SeverityUpdate {
+            previous_severity: allergy.severity.clone(),
+            new_severity: new_severity.clone(),
+            updated_by: provider_id,
+            updated_at: env.ledger().timestamp(),
+            reason,
+            confirmed_by,
+        };
+
+        // Update severity and add to history
+        allergy.severity = new_severity.clone();
+        allergy.severity_history.push_back(update);
+
+        // Save updated record
+        storage::save_allergy(env, &allergy);
+
+        // Emit event
+        AllergyUpdated {
+            allergy_id: allergy.allergy_id,
+            new_severity,
+        }
+        .publish(env);
+    }
+
+    fn apply_allergy_status(
+        env: &Env,
+        patient_id: &Address,
+        provider_id: &Address,
+        status: PatientAllergyStatus,
+    ) {
+        let record = PatientAllergyStatusRecord {
+            status: status.clone(),
+            set_by: Some(provider_id.clone()),
+            set_at: env.ledger().timestamp(),
+        };
+        storage::set_patient_allergy_status(env, patient_id, &record);
+
+        AllergyStatusChanged {
+            patient_id: patient_id.clone(),
+            status,
+        }
+        .publish(env);
+    }
 }
 
 #[cfg(test)]
@@ -1,6 +1,10 @@
 use soroban_sdk::{Address, Env, String, Symbol, Vec};
 
-use crate::{AllergyRecord, DataKey, Error};
+use crate::{
+    AllergyRecord, DataKey, Error, OrganizationGrant, PatientAllergyStatus,
+    PatientAllergyStatusRecord, PediatricWeight, PendingSeverityDowngrade, ReactionEvent,
+    ReconciliationSnapshot,
+};
 
 /// Get the next allergy ID and increment counter
 pub fn get_next_allergy_id(env: &Env) -> u64 {
@@ -21,15 +25,19 @@ pub fn get_next_allergy_id(env: &Env) -> u64 {
 pub fn save_allergy(env: &Env, allergy: &AllergyRecord) {
     let key = DataKey::Allergy(allergy.allergy_id);
     env.storage().persistent().set(&key, allergy);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 /// Get an allergy record by ID
 pub fn get_allergy(env: &Env, allergy_id: u64) -> Result<AllergyRecord, Error> {
     let key = DataKey::Allergy(allergy_id);
-    env.storage()
+    let allergy = env
+        .storage()
         .persistent()
         .get(&key)
-        .ok_or(Error::AllergyNotFound)
+        .ok_or(Error::AllergyNotFound)?;
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+    Ok(allergy)
 }
 
 /// Add an allergy ID to a patient's allergy list
@@ -43,15 +51,46 @@ pub fn add_patient_allergy(env: &Env, patient_id: &Address, allergy_id: u64) {
     
     allergies.push_back(allergy_id);
     env.storage().persistent().set(&key, &allergies);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 /// Get all allergy IDs for a patient
 pub fn get_patient_allergies(env: &Env, patient_id: &Address) -> Vec<u64> {
     let key = DataKey::PatientAllergies(patient_id.clone());
-    env.storage()
-        .persistent()
-        .get(&key)
-        .unwrap_or(Vec::new(env))
+    match env.storage().persistent().get(&key) {
+        Some(allergies) => {
+            shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+            allergies
+        }
+        None => Vec::new(env),
+    }
+}
+
+/// Extend a patient's allergy list, every record on it, their reaction
+/// histories and the patient's allergy status with the archive policy.
+/// Returns false if the patient has no allergy list.
+pub fn extend_patient_allergy_entries(env: &Env, patient_id: &Address) -> bool {
+    let key = DataKey::PatientAllergies(patient_id.clone());
+    let allergies: Vec<u64> = match env.storage().persistent().get(&key) {
+        Some(allergies) => allergies,
+        None => return false,
+    };
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ARCHIVE);
+
+    for allergy_id in allergies.iter() {
+        shared_ttl::bump_persistent(env, &DataKey::Allergy(allergy_id), shared_ttl::ARCHIVE);
+        shared_ttl::bump_persistent(
+            env,
+            &DataKey::ReactionHistory(allergy_id),
+            shared_ttl::ARCHIVE,
+        );
+    }
+    shared_ttl::bump_persistent(
+        env,
+        &DataKey::PatientAllergyStatus(patient_id.clone()),
+        shared_ttl::ARCHIVE,
+    );
+    true
 }
 
 /// Check if a patient already has a specific allergy recorded
@@ -78,20 +117,18 @@ pub fn check_duplicate_allergy(
 
 /// Grant access to a provider to view patient allergies
 pub fn grant_access(env: &Env, patient_id: &Address, provider_id: &Address) {
-    let key = DataKey::AccessControl(patient_id.clone(), provider_id.clone());
-    env.storage().persistent().set(&key, &true);
+    shared_access::grant_access(env, patient_id, provider_id, None);
 }
 
 /// Revoke access from a provider
 pub fn revoke_access(env: &Env, patient_id: &Address, provider_id: &Address) {
-    let key = DataKey::AccessControl(patient_id.clone(), provider_id.clone());
-    env.storage().persistent().remove(&key);
+    shared_access::revoke_access(env, patient_id, provider_id);
 }
 
 /// Check if a requester has access to patient allergies
 pub fn check_access_permission(env: &Env, patient_id: &Address, requester: &Address) -> bool {
-    // Patient always has access to their own data
-    if patient_id == requester {
+    // Patient always has access to their own data, plus explicit grants
+    if shared_access::has_access(env, patient_id, requester) {
         return true;
     }
     
@@ -101,10 +138,70 @@ pub fn check_access_permission(env: &Env, patient_id: &Address, requester: &Addr
             return true;
         }
     }
-    
-    // Check explicit access grant
-    let key = DataKey::AccessControl(patient_id.clone(), requester.clone());
-    env.storage().persistent().has(&key)
+
+    // Check organization rosters authorized by the patient
+    for facility_id in get_patient_organizations(env, patient_id).iter() {
+        if let Ok(grant) = get_organization_grant(env, patient_id, &facility_id) {
+            if grant.roster.contains(requester) {
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Save an organization grant, registering the facility under the patient
+pub fn save_organization_grant(env: &Env, grant: &OrganizationGrant) {
+    let key = DataKey::OrganizationGrant(grant.patient_id.clone(), grant.facility_id.clone());
+    env.storage().persistent().set(&key, grant);
+
+    let mut facilities = get_patient_organizations(env, &grant.patient_id);
+    if !facilities.contains(&grant.facility_id) {
+        facilities.push_back(grant.facility_id.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::PatientOrganizations(grant.patient_id.clone()), &facilities);
+    }
+}
+
+/// Get an organization grant for a patient and facility
+pub fn get_organization_grant(
+    env: &Env,
+    patient_id: &Address,
+    facility_id: &Address,
+) -> Result<OrganizationGrant, Error> {
+    let key = DataKey::OrganizationGrant(patient_id.clone(), facility_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::OrganizationGrantNotFound)
+}
+
+/// Remove an organization grant and its provider roster
+pub fn remove_organization_grant(env: &Env, patient_id: &Address, facility_id: &Address) {
+    let key = DataKey::OrganizationGrant(patient_id.clone(), facility_id.clone());
+    env.storage().persistent().remove(&key);
+
+    let facilities = get_patient_organizations(env, patient_id);
+    let mut remaining = Vec::new(env);
+    for f in facilities.iter() {
+        if f != *facility_id {
+            remaining.push_back(f);
+        }
+    }
+    env.storage()
+        .persistent()
+        .set(&DataKey::PatientOrganizations(patient_id.clone()), &remaining);
+}
+
+/// Get all facilities a patient has authorized
+pub fn get_patient_organizations(env: &Env, patient_id: &Address) -> Vec<Address> {
+    let key = DataKey::PatientOrganizations(patient_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
 }
 
 /// Store cross-sensitivity relationship between allergens
@@ -121,3 +218,117 @@ pub fn has_cross_sensitivity(env: &Env, allergen1: &String, allergen2: &String)
     let key = DataKey::CrossSensitivity(allergen1.clone(), allergen2.clone());
     env.storage().persistent().has(&key)
 }
+
+/// Append a reaction event to an allergy's reaction history
+pub fn add_reaction_event(env: &Env, event: &ReactionEvent) {
+    let key = DataKey::ReactionHistory(event.allergy_id);
+    let mut history: Vec<ReactionEvent> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    history.push_back(event.clone());
+    env.storage().persistent().set(&key, &history);
+}
+
+/// Get the reaction history for an allergy
+pub fn get_reaction_history(env: &Env, allergy_id: u64) -> Vec<ReactionEvent> {
+    let key = DataKey::ReactionHistory(allergy_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Save a severity downgrade awaiting confirmation, replacing any earlier request
+pub fn save_pending_downgrade(env: &Env, pending: &PendingSeverityDowngrade) {
+    let key = DataKey::PendingSeverityDowngrade(pending.allergy_id);
+    env.storage().persistent().set(&key, pending);
+}
+
+/// Get the severity downgrade awaiting confirmation for an allergy
+pub fn get_pending_downgrade(env: &Env, allergy_id: u64) -> Result<PendingSeverityDowngrade, Error> {
+    let key = DataKey::PendingSeverityDowngrade(allergy_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::NoPendingDowngrade)
+}
+
+/// Clear the severity downgrade awaiting confirmation for an allergy
+pub fn remove_pending_downgrade(env: &Env, allergy_id: u64) {
+    let key = DataKey::PendingSeverityDowngrade(allergy_id);
+    env.storage().persistent().remove(&key);
+}
+
+/// Append a reconciliation snapshot to a patient's list
+pub fn add_reconciliation_snapshot(env: &Env, snapshot: &ReconciliationSnapshot) {
+    let key = DataKey::ReconciliationSnapshots(snapshot.patient_id.clone());
+    let mut snapshots = get_reconciliation_snapshots(env, &snapshot.patient_id);
+    snapshots.push_back(snapshot.clone());
+    env.storage().persistent().set(&key, &snapshots);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ARCHIVE);
+}
+
+/// Get all reconciliation snapshots for a patient, oldest first
+pub fn get_reconciliation_snapshots(env: &Env, patient_id: &Address) -> Vec<ReconciliationSnapshot> {
+    let key = DataKey::ReconciliationSnapshots(patient_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Save a pediatric patient's latest weight, replacing any earlier one
+pub fn save_pediatric_weight(env: &Env, weight: &PediatricWeight) {
+    let key = DataKey::PediatricWeight(weight.patient_id.clone());
+    env.storage().persistent().set(&key, weight);
+}
+
+/// Get a pediatric patient's latest weight
+pub fn get_pediatric_weight(env: &Env, patient_id: &Address) -> Result<PediatricWeight, Error> {
+    let key = DataKey::PediatricWeight(patient_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::WeightNotRecorded)
+}
+
+/// Save the allergy status assertion for a patient
+pub fn set_patient_allergy_status(env: &Env, patient_id: &Address, record: &PatientAllergyStatusRecord) {
+    let key = DataKey::PatientAllergyStatus(patient_id.clone());
+    env.storage().persistent().set(&key, record);
+}
+
+/// Get the allergy status for a patient, defaulting to unknown when never assessed
+pub fn get_patient_allergy_status(env: &Env, patient_id: &Address) -> PatientAllergyStatusRecord {
+    let key = DataKey::PatientAllergyStatus(patient_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(PatientAllergyStatusRecord {
+            status: PatientAllergyStatus::Unknown,
+            set_by: None,
+            set_at: 0,
+        })
+}
+
+/// Check if a patient has any active allergy, optionally restricted to one allergen type
+pub fn has_active_allergy(env: &Env, patient_id: &Address, allergen_type: Option<Symbol>) -> bool {
+    let allergy_ids = get_patient_allergies(env, patient_id);
+
+    for allergy_id in allergy_ids.iter() {
+        if let Ok(allergy) = get_allergy(env, allergy_id) {
+            if allergy.status != crate::AllergyStatus::Active {
+                continue;
+            }
+            match &allergen_type {
+                Some(t) if allergy.allergen_type != *t => continue,
+                _ => return true,
+            }
+        }
+    }
+
+    false
+}
@@ -1,8 +1,15 @@
 #![cfg(test)]
 
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{storage::Persistent as _, Address as _, Ledger},
+    Address, Env, String, Symbol, Vec,
+};
 
-use crate::{AllergyManagement, AllergyManagementClient, AllergyStatus, RecordAllergyRequest};
+use crate::{
+    AllergyManagement, AllergyManagementClient, AllergyStatus, DataKey, Error,
+    PatientAllergyStatus, ReactionEventRequest, RecordAllergyRequest,
+};
 
 fn create_test_env() -> (Env, Address, Address, Address, AllergyManagementClient<'static>) {
     let env = Env::default();
@@ -339,6 +346,66 @@ fn test_check_drug_no_interaction() {
     assert_eq!(interactions.len(), 0);
 }
 
+#[test]
+fn test_pediatric_interaction_uses_latest_weight() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "hives"));
+    let request = create_allergy_request(
+        &env,
+        "Amoxicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("moderate"),
+        None,
+        true,
+    );
+    client.record_allergy(&patient, &provider, &request);
+
+    let drug = String::from_str(&env, "Amoxicillin");
+    let result = client.try_check_pediatric_interaction(&patient, &provider, &drug, &250_000);
+    assert!(matches!(result, Err(Ok(Error::WeightNotRecorded))));
+
+    let result = client.try_record_pediatric_weight(&patient, &provider, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidWeight)));
+    let result = client.try_record_pediatric_weight(&patient, &Address::generate(&env), &12_500);
+    assert_eq!(result, Err(Ok(Error::AccessDenied)));
+
+    // 250 mg for a 12.5 kg child is 20 mg/kg
+    client.record_pediatric_weight(&patient, &provider, &12_500);
+    let check = client.check_pediatric_interaction(&patient, &provider, &drug, &250_000);
+    assert_eq!(check.interactions.len(), 1);
+    assert_eq!(
+        check.interactions.get(0).unwrap().severity,
+        symbol_short!("moderate")
+    );
+    assert_eq!(check.weight_grams, 12_500);
+    assert_eq!(check.dose_mcg_per_kg, 20_000);
+
+    // A month-old weight is too stale to dose against
+    env.ledger().with_mut(|l| l.timestamp += 31 * 24 * 60 * 60);
+    let result = client.try_check_pediatric_interaction(&patient, &provider, &drug, &250_000);
+    assert!(matches!(result, Err(Ok(Error::StaleWeight))));
+
+    client.record_pediatric_weight(&patient, &provider, &13_000);
+    let check = client.check_pediatric_interaction(&patient, &provider, &drug, &260_000);
+    assert_eq!(check.weight_recorded_at, env.ledger().timestamp());
+    assert_eq!(check.dose_mcg_per_kg, 20_000);
+    assert_eq!(client.get_pediatric_weight(&patient, &provider).recorded_by, provider);
+
+    // Reads require access, and a dose too large to scale is rejected
+    let outsider = Address::generate(&env);
+    let result = client.try_check_pediatric_interaction(&patient, &outsider, &drug, &250_000);
+    assert!(matches!(result, Err(Ok(Error::AccessDenied))));
+    let result = client.try_get_pediatric_weight(&patient, &outsider);
+    assert!(matches!(result, Err(Ok(Error::AccessDenied))));
+    let result = client.try_check_pediatric_interaction(&patient, &provider, &drug, &u64::MAX);
+    assert!(matches!(result, Err(Ok(Error::InvalidDose))));
+}
+
 #[test]
 fn test_get_active_allergies() {
     let (env, _, patient, provider, client) = create_test_env();
@@ -580,13 +647,46 @@ fn test_get_all_allergies() {
         &String::from_str(&env, "Test"),
     );
 
-    let all = client.get_all_allergies(&patient, &provider);
+    let all = client.get_all_allergies(&patient, &provider, &0, &10);
     assert_eq!(all.len(), 2);
 
     let active = client.get_active_allergies(&patient, &provider);
     assert_eq!(active.len(), 1);
 }
 
+#[test]
+fn test_get_all_allergies_pages() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    for allergen in ["Penicillin", "Peanuts", "Latex"] {
+        let request = create_allergy_request(
+            &env,
+            allergen,
+            symbol_short!("med"),
+            reactions.clone(),
+            symbol_short!("mild"),
+            None,
+            true,
+        );
+        client.record_allergy(&patient, &provider, &request);
+    }
+
+    let first = client.get_all_allergies(&patient, &provider, &0, &2);
+    assert_eq!(first.len(), 2);
+    assert_eq!(first.get(0).unwrap().allergen, String::from_str(&env, "Penicillin"));
+
+    let second = client.get_all_allergies(&patient, &provider, &2, &2);
+    assert_eq!(second.len(), 1);
+    assert_eq!(second.get(0).unwrap().allergen, String::from_str(&env, "Latex"));
+
+    assert!(client.get_all_allergies(&patient, &provider, &3, &2).is_empty());
+}
+
 #[test]
 fn test_multiple_severity_updates() {
     let (env, _, patient, provider, client) = create_test_env();
@@ -627,6 +727,197 @@ fn test_multiple_severity_updates() {
     assert_eq!(allergy.severity_history.len(), 2);
 }
 
+#[test]
+fn test_severe_downgrade_requires_second_provider() {
+    let (env, _, patient, provider, client) = create_test_env();
+    let second_provider = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    client.grant_access(&patient, &provider);
+    client.grant_access(&patient, &second_provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "anaphylaxis"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("critical"),
+        None,
+        true,
+    );
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    // Downgrade is held rather than applied
+    client.update_allergy_severity(
+        &allergy_id,
+        &provider,
+        &symbol_short!("mild"),
+        &String::from_str(&env, "Tolerated graded challenge"),
+    );
+    let allergy = client.get_allergy(&allergy_id, &provider);
+    assert_eq!(allergy.severity, symbol_short!("critical"));
+    assert_eq!(allergy.severity_history.len(), 0);
+    let pending = client.get_pending_severity_downgrade(&allergy_id, &second_provider);
+    assert_eq!(pending.new_severity, symbol_short!("mild"));
+
+    // The requesting provider cannot confirm their own downgrade
+    let result = client.try_confirm_severity_downgrade(&allergy_id, &provider);
+    assert_eq!(result, Err(Ok(Error::ConfirmationRequired)));
+    let result = client.try_confirm_severity_downgrade(&allergy_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::AccessDenied)));
+
+    client.confirm_severity_downgrade(&allergy_id, &second_provider);
+
+    let allergy = client.get_allergy(&allergy_id, &provider);
+    assert_eq!(allergy.severity, symbol_short!("mild"));
+    let update = allergy.severity_history.get(0).unwrap();
+    assert_eq!(update.updated_by, provider);
+    assert_eq!(update.confirmed_by, Some(second_provider));
+
+    let result = client.try_get_pending_severity_downgrade(&allergy_id, &provider);
+    assert!(matches!(result, Err(Ok(Error::NoPendingDowngrade))));
+}
+
+#[test]
+fn test_direct_update_clears_pending_downgrade() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "anaphylaxis"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("severe"),
+        None,
+        true,
+    );
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    client.update_allergy_severity(
+        &allergy_id,
+        &provider,
+        &symbol_short!("mild"),
+        &String::from_str(&env, "Tolerated graded challenge"),
+    );
+    client.update_allergy_severity(
+        &allergy_id,
+        &provider,
+        &symbol_short!("critical"),
+        &String::from_str(&env, "Repeat anaphylaxis"),
+    );
+
+    // The held downgrade can no longer be confirmed over the escalation
+    let result = client.try_get_pending_severity_downgrade(&allergy_id, &provider);
+    assert!(matches!(result, Err(Ok(Error::NoPendingDowngrade))));
+    let result = client.try_confirm_severity_downgrade(&allergy_id, &patient);
+    assert!(matches!(result, Err(Ok(Error::NoPendingDowngrade))));
+    assert_eq!(
+        client.get_allergy(&allergy_id, &provider).severity,
+        symbol_short!("critical")
+    );
+}
+
+#[test]
+fn test_severe_downgrade_patient_cosign() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "angioedema"));
+
+    let request = create_allergy_request(
+        &env,
+        "Shellfish",
+        symbol_short!("food"),
+        reactions,
+        symbol_short!("severe"),
+        None,
+        true,
+    );
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    client.update_allergy_severity(
+        &allergy_id,
+        &provider,
+        &symbol_short!("moderate"),
+        &String::from_str(&env, "Reaction was hives only"),
+    );
+    client.confirm_severity_downgrade(&allergy_id, &patient);
+
+    let allergy = client.get_allergy(&allergy_id, &provider);
+    assert_eq!(allergy.severity, symbol_short!("moderate"));
+    assert_eq!(
+        allergy.severity_history.get(0).unwrap().confirmed_by,
+        Some(patient)
+    );
+}
+
+#[test]
+fn test_reconciliation_snapshots() {
+    let (env, _, patient, provider, client) = create_test_env();
+    let auditor = Address::generate(&env);
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "hives"));
+    let request = create_allergy_request(
+        &env,
+        "Sulfa",
+        symbol_short!("med"),
+        reactions.clone(),
+        symbol_short!("moderate"),
+        None,
+        true,
+    );
+    client.record_allergy(&patient, &provider, &request);
+
+    let admission =
+        client.create_reconciliation_snapshot(&patient, &provider, &Symbol::new(&env, "admission"));
+    assert_eq!(admission.active_count, 1);
+
+    // Reconciling again with no changes yields the same hash
+    let visit = client.create_reconciliation_snapshot(&patient, &provider, &symbol_short!("visit"));
+    assert_eq!(visit.allergies_hash, admission.allergies_hash);
+
+    let request = create_allergy_request(
+        &env,
+        "Latex",
+        symbol_short!("env"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        false,
+    );
+    client.record_allergy(&patient, &provider, &request);
+    let discharge =
+        client.create_reconciliation_snapshot(&patient, &provider, &Symbol::new(&env, "discharge"));
+    assert_eq!(discharge.active_count, 2);
+    assert_ne!(discharge.allergies_hash, admission.allergies_hash);
+
+    let result =
+        client.try_create_reconciliation_snapshot(&patient, &provider, &symbol_short!("transfer"));
+    assert_eq!(result.err(), Some(Ok(Error::InvalidReconciliationContext)));
+    let result =
+        client.try_create_reconciliation_snapshot(&patient, &auditor, &symbol_short!("visit"));
+    assert_eq!(result.err(), Some(Ok(Error::AccessDenied)));
+
+    client.grant_access(&patient, &auditor);
+    let snapshots = client.get_reconciliation_snapshots(&patient, &auditor, &0, &10);
+    assert_eq!(snapshots.len(), 3);
+    assert_eq!(snapshots.get(0).unwrap().context, Symbol::new(&env, "admission"));
+    assert_eq!(snapshots.get(2).unwrap().provider_id, provider);
+}
+
 #[test]
 fn test_environmental_allergy() {
     let (env, _, patient, provider, client) = create_test_env();
@@ -680,3 +971,354 @@ fn test_food_allergy() {
     assert_eq!(allergy.severity, symbol_short!("critical"));
     assert_eq!(allergy.onset_date, Some(500u64));
 }
+
+fn create_reaction_request(env: &Env, severity: Symbol) -> ReactionEventRequest {
+    ReactionEventRequest {
+        reaction_date: env.ledger().timestamp(),
+        exposure_source: String::from_str(env, "Amoxicillin 500mg"),
+        severity,
+        treatment_given: String::from_str(env, "Epinephrine IM"),
+        outcome: String::from_str(env, "Recovered"),
+    }
+}
+
+#[test]
+fn test_record_reaction_event() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("severe"),
+        None,
+        true,
+    );
+
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    let suggestion = client.record_reaction_event(
+        &allergy_id,
+        &provider,
+        &create_reaction_request(&env, symbol_short!("moderate")),
+    );
+    assert_eq!(suggestion, None);
+
+    let history = client.get_reaction_history(&allergy_id, &provider);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().severity, symbol_short!("moderate"));
+    assert_eq!(history.get(0).unwrap().suggested_severity, None);
+}
+
+#[test]
+fn test_reaction_event_suggests_escalation() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    let suggestion = client.record_reaction_event(
+        &allergy_id,
+        &provider,
+        &create_reaction_request(&env, symbol_short!("critical")),
+    );
+    assert_eq!(suggestion, Some(symbol_short!("critical")));
+
+    // Recorded severity is unchanged until a provider updates it
+    let allergy = client.get_allergy(&allergy_id, &provider);
+    assert_eq!(allergy.severity, symbol_short!("mild"));
+
+    let history = client.get_reaction_history(&allergy_id, &patient);
+    assert_eq!(
+        history.get(0).unwrap().suggested_severity,
+        Some(symbol_short!("critical"))
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_reaction_event_on_resolved_allergy() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    client.resolve_allergy(
+        &allergy_id,
+        &provider,
+        &env.ledger().timestamp(),
+        &String::from_str(&env, "Desensitized"),
+    );
+
+    // Reaction on resolved allergy should panic
+    client.record_reaction_event(
+        &allergy_id,
+        &provider,
+        &create_reaction_request(&env, symbol_short!("mild")),
+    );
+}
+
+#[test]
+fn test_allergy_status_defaults_to_unknown() {
+    let (_, _, patient, _, client) = create_test_env();
+
+    let status = client.get_allergy_status(&patient, &patient);
+    assert_eq!(status.status, PatientAllergyStatus::Unknown);
+    assert_eq!(status.set_by, None);
+}
+
+#[test]
+fn test_set_nkda_then_first_allergy_flips_status() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+    client.set_allergy_status(&patient, &provider, &PatientAllergyStatus::Nkda);
+
+    let status = client.get_allergy_status(&patient, &provider);
+    assert_eq!(status.status, PatientAllergyStatus::Nkda);
+    assert_eq!(status.set_by, Some(provider.clone()));
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "hives"));
+
+    let request = create_allergy_request(
+        &env,
+        "Peanuts",
+        symbol_short!("food"),
+        reactions,
+        symbol_short!("severe"),
+        None,
+        true,
+    );
+    client.record_allergy(&patient, &provider, &request);
+
+    let status = client.get_allergy_status(&patient, &provider);
+    assert_eq!(status.status, PatientAllergyStatus::HasAllergies);
+}
+
+#[test]
+fn test_set_nkda_conflicts_with_active_drug_allergy() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+    client.record_allergy(&patient, &provider, &request);
+
+    let result = client.try_set_allergy_status(&patient, &provider, &PatientAllergyStatus::Nkda);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_import_allergies_reconciliation() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    client.grant_access(&patient, &provider);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let mut batch = Vec::new(&env);
+    batch.push_back(create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions.clone(),
+        symbol_short!("severe"),
+        None,
+        true,
+    ));
+    batch.push_back(create_allergy_request(
+        &env,
+        "Latex",
+        symbol_short!("invalid"),
+        reactions.clone(),
+        symbol_short!("mild"),
+        None,
+        true,
+    ));
+    batch.push_back(create_allergy_request(
+        &env,
+        "Peanuts",
+        symbol_short!("food"),
+        reactions.clone(),
+        symbol_short!("critical"),
+        None,
+        true,
+    ));
+    // Duplicate of the first item in the same batch
+    batch.push_back(create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("severe"),
+        None,
+        true,
+    ));
+
+    let report = client.import_allergies(&patient, &provider, &batch);
+
+    assert_eq!(report.total, 4);
+    assert_eq!(report.imported.len(), 2);
+    assert_eq!(report.rejected.len(), 2);
+
+    let first = report.rejected.get(0).unwrap();
+    assert_eq!(first.index, 1);
+    assert_eq!(first.reason, symbol_short!("bad_type"));
+
+    let second = report.rejected.get(1).unwrap();
+    assert_eq!(second.index, 3);
+    assert_eq!(second.reason, symbol_short!("dup"));
+
+    let all = client.get_all_allergies(&patient, &provider, &0, &10);
+    assert_eq!(all.len(), 2);
+}
+
+#[test]
+fn test_organization_roster_access() {
+    let (env, _, patient, provider, client) = create_test_env();
+    let facility = Address::generate(&env);
+    let facility_admin = Address::generate(&env);
+    let staff = Address::generate(&env);
+
+    let mut reactions = Vec::new(&env);
+    reactions.push_back(String::from_str(&env, "rash"));
+
+    let request = create_allergy_request(
+        &env,
+        "Penicillin",
+        symbol_short!("med"),
+        reactions,
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+    client.record_allergy(&patient, &provider, &request);
+
+    client.grant_organization_access(&patient, &facility, &facility_admin);
+    assert!(client.try_get_active_allergies(&patient, &staff).is_err());
+
+    client.add_roster_provider(&patient, &facility, &facility_admin, &staff);
+    let active = client.get_active_allergies(&patient, &staff);
+    assert_eq!(active.len(), 1);
+
+    let grant = client.get_organization_grant(&patient, &facility, &patient);
+    assert_eq!(grant.roster.len(), 1);
+
+    // Patient revokes the whole organization in one call
+    client.revoke_organization_access(&patient, &facility);
+    assert!(client.try_get_active_allergies(&patient, &staff).is_err());
+}
+
+#[test]
+fn test_roster_requires_facility_admin() {
+    let (env, _, patient, provider, client) = create_test_env();
+    let facility = Address::generate(&env);
+    let facility_admin = Address::generate(&env);
+
+    client.grant_organization_access(&patient, &facility, &facility_admin);
+
+    let result = client.try_add_roster_provider(&patient, &facility, &provider, &provider);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_roster_is_bounded() {
+    let (env, _, patient, _, client) = create_test_env();
+    let facility = Address::generate(&env);
+    let facility_admin = Address::generate(&env);
+
+    client.grant_organization_access(&patient, &facility, &facility_admin);
+
+    for _ in 0..25 {
+        client.add_roster_provider(&patient, &facility, &facility_admin, &Address::generate(&env));
+    }
+
+    let result = client.try_add_roster_provider(
+        &patient,
+        &facility,
+        &facility_admin,
+        &Address::generate(&env),
+    );
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_extend_allergy_ttl() {
+    let (env, _, patient, provider, client) = create_test_env();
+
+    let request = create_allergy_request(
+        &env,
+        "Latex",
+        symbol_short!("env"),
+        Vec::new(&env),
+        symbol_short!("mild"),
+        None,
+        true,
+    );
+    let allergy_id = client.record_allergy(&patient, &provider, &request);
+
+    let ttl = |key: DataKey| {
+        env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key))
+    };
+    assert_eq!(ttl(DataKey::Allergy(allergy_id)), shared_ttl::ACTIVE.extend_to);
+
+    client.extend_allergy_ttl(&patient);
+    assert_eq!(ttl(DataKey::Allergy(allergy_id)), shared_ttl::ARCHIVE.extend_to);
+    assert_eq!(
+        ttl(DataKey::PatientAllergies(patient.clone())),
+        shared_ttl::ARCHIVE.extend_to
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_extend_allergy_ttl(&stranger),
+        Err(Ok(Error::AllergyNotFound))
+    );
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, String, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
 
 /// Allergy status enumeration
 #[contracttype]
@@ -8,6 +8,25 @@ pub enum AllergyStatus {
     Resolved,
 }
 
+/// Patient-level allergy status, distinguishing "no known allergies" from "never assessed"
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PatientAllergyStatus {
+    Unknown,
+    Nkda, // no known drug allergies
+    Nka,  // no known allergies of any kind
+    HasAllergies,
+}
+
+/// Allergy status assertion for a patient
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PatientAllergyStatusRecord {
+    pub status: PatientAllergyStatus,
+    pub set_by: Option<Address>,
+    pub set_at: u64,
+}
+
 /// Parameters for recording a new allergy
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -20,6 +39,24 @@ pub struct RecordAllergyRequest {
     pub verified: bool,
 }
 
+/// Batch import item that could not be recorded
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct RejectedAllergyImport {
+    pub index: u32,       // position in the submitted batch
+    pub allergen: String,
+    pub reason: Symbol,   // "dup", "bad_type", "bad_sev", ...
+}
+
+/// Reconciliation report returned by a batch import
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ImportReconciliation {
+    pub total: u32,
+    pub imported: Vec<u64>,
+    pub rejected: Vec<RejectedAllergyImport>,
+}
+
 /// Complete allergy record
 #[contracttype]
 #[derive(Clone, Debug)]
@@ -49,6 +86,18 @@ pub struct SeverityUpdate {
     pub updated_by: Address,
     pub updated_at: u64,
     pub reason: String,
+    pub confirmed_by: Option<Address>, // second provider or patient for downgrades from severe levels
+}
+
+/// Downgrade from a severe level awaiting a second provider or the patient
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PendingSeverityDowngrade {
+    pub allergy_id: u64,
+    pub new_severity: Symbol,
+    pub requested_by: Address,
+    pub requested_at: u64,
+    pub reason: String,
 }
 
 /// Drug-allergy interaction result
@@ -62,6 +111,78 @@ pub struct AllergyInteraction {
     pub interaction_type: Symbol, // "direct" or "cross" (cross-sensitivity)
 }
 
+/// Most recent weight for a pediatric patient, used to put a proposed dose in
+/// weight-based context
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PediatricWeight {
+    pub patient_id: Address,
+    pub weight_grams: u32,
+    pub recorded_by: Address,
+    pub recorded_at: u64,
+}
+
+/// Drug-allergy check for a pediatric patient, with the proposed dose
+/// expressed against the patient's latest recorded weight
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct PediatricInteractionCheck {
+    pub interactions: Vec<AllergyInteraction>,
+    pub weight_grams: u32,
+    pub weight_recorded_at: u64,
+    pub dose_mcg: u64,
+    pub dose_mcg_per_kg: u64,
+}
+
+/// Parameters for recording a reaction occurrence
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReactionEventRequest {
+    pub reaction_date: u64,
+    pub exposure_source: String,
+    pub severity: Symbol,
+    pub treatment_given: String,
+    pub outcome: String,
+}
+
+/// Documented reaction occurrence tied to an allergy record
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReactionEvent {
+    pub allergy_id: u64,
+    pub reported_by: Address,
+    pub reaction_date: u64,
+    pub exposure_source: String, // e.g., "amoxicillin 500mg PO"
+    pub severity: Symbol,
+    pub treatment_given: String,
+    pub outcome: String,
+    pub recorded_at: u64,
+    pub suggested_severity: Option<Symbol>, // set when the reaction exceeds the recorded severity
+}
+
+/// Patient authorization for a care organization to manage provider access
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct OrganizationGrant {
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub facility_admin: Address,
+    pub roster: Vec<Address>, // providers granted access under this facility
+    pub granted_at: u64,
+}
+
+/// Active allergy list as reconciled by a provider at a transition of care
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ReconciliationSnapshot {
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub context: Symbol, // "admission", "discharge", "visit"
+    pub active_count: u32,
+    pub allergies_hash: BytesN<32>, // sha256 of the active allergy records at reconciliation
+    pub reconciled_at: u64,
+}
+
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone)]
@@ -70,6 +191,12 @@ pub enum DataKey {
     AllergyCounter,
     Allergy(u64),
     PatientAllergies(Address),
-    AccessControl(Address, Address), // (patient, provider)
     CrossSensitivity(String, String), // (allergen1, allergen2)
+    ReactionHistory(u64),
+    PatientAllergyStatus(Address),
+    OrganizationGrant(Address, Address), // (patient, facility)
+    PatientOrganizations(Address),
+    PendingSeverityDowngrade(u64),
+    ReconciliationSnapshots(Address),
+    PediatricWeight(Address),
 }
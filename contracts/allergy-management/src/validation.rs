@@ -33,6 +33,41 @@ pub fn validate_severity(severity: &Symbol) -> Result<(), Error> {
     }
 }
 
+/// Validate a transition-of-care context for reconciliation
+pub fn validate_reconciliation_context(env: &Env, context: &Symbol) -> Result<(), Error> {
+    let valid_contexts = [
+        Symbol::new(env, "admission"),
+        Symbol::new(env, "discharge"),
+        symbol_short!("visit"),
+    ];
+
+    if valid_contexts.contains(context) {
+        Ok(())
+    } else {
+        Err(Error::InvalidReconciliationContext)
+    }
+}
+
+/// Rank a severity level so levels can be compared (higher is more severe)
+pub fn severity_rank(severity: &Symbol) -> u32 {
+    if *severity == symbol_short!("critical") {
+        4
+    } else if *severity == symbol_short!("severe") {
+        3
+    } else if *severity == symbol_short!("moderate") {
+        2
+    } else if *severity == symbol_short!("mild") {
+        1
+    } else {
+        0
+    }
+}
+
+/// Lowering severity from one of these levels needs a confirming party
+pub fn requires_downgrade_confirmation(current: &Symbol, new: &Symbol) -> bool {
+    severity_rank(current) >= 3 && severity_rank(new) < severity_rank(current)
+}
+
 /// Check if drug name matches allergen (case-insensitive comparison)
 pub fn check_drug_match(allergen: &String, drug_name: &String) -> bool {
     // Simple string comparison - in production, this would use more sophisticated matching
@@ -43,3 +78,22 @@ pub fn check_drug_match(allergen: &String, drug_name: &String) -> bool {
 pub fn check_cross_sensitivity(env: &Env, allergen: &String, drug_name: &String) -> bool {
     storage::has_cross_sensitivity(env, allergen, drug_name)
 }
+
+/// Map a recording error to a short reason code for import reconciliation reports
+pub fn rejection_reason(err: Error) -> Symbol {
+    match err {
+        Error::DuplicateAllergy => symbol_short!("dup"),
+        Error::InvalidAllergenType => symbol_short!("bad_type"),
+        Error::InvalidSeverity => symbol_short!("bad_sev"),
+        Error::InvalidDate => symbol_short!("bad_date"),
+        _ => symbol_short!("rejected"),
+    }
+}
+
+/// Validate a pediatric weight in grams (0 < weight <= 150 kg)
+pub fn validate_pediatric_weight(weight_grams: u32) -> Result<(), Error> {
+    if weight_grams == 0 || weight_grams > 150_000 {
+        return Err(Error::InvalidWeight);
+    }
+    Ok(())
+}
@@ -1,8 +1,8 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, String,
-    Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    String, Symbol, Vec,
 };
 
 /// Error codes for allergy tracking operations
@@ -21,6 +21,8 @@ pub enum Error {
     AllergenTooLong = 9,
     InvalidTimestamp = 10,
     ReasonTooLong = 11,
+    AlreadyInitialized = 12,
+    NotInitialized = 13,
 }
 
 /// Allergen types supported by the system
@@ -97,6 +99,7 @@ pub struct InteractionWarning {
 /// Storage keys for the contract
 #[contracttype]
 pub enum DataKey {
+    Admin,
     AllergyCounter,
     Allergy(u64),
     PatientAllergies(Address),
@@ -110,11 +113,25 @@ const MIN_ALLERGEN_LENGTH: u32 = 1;
 const MAX_REASON_LENGTH: u32 = 500;
 const MAX_REACTION_LENGTH: u32 = 200;
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct AllergyTrackingContract;
 
 #[contractimpl]
 impl AllergyTrackingContract {
+    /// Initialize the contract with an admin address
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     /// Record a new allergy for a patient
     pub fn record_allergy(
         env: Env,
@@ -440,7 +457,7 @@ impl AllergyTrackingContract {
         drug1: String,
         drug2: String,
     ) -> Result<(), Error> {
-        admin.require_auth();
+        Self::require_admin(&env, &admin)?;
 
         let key1 = DataKey::DrugCrossSensitivity(drug1.clone());
         let mut related1: Vec<String> = env
@@ -469,8 +486,38 @@ impl AllergyTrackingContract {
         Ok(())
     }
 
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
     // ==================== Helper Functions ====================
 
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
     fn validate_allergen(allergen: &String) -> Result<(), Error> {
         let len = allergen.len();
         if len < MIN_ALLERGEN_LENGTH {
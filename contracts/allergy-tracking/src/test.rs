@@ -3,7 +3,7 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as _, Ledger as _},
-    Address, Env, String, Symbol, Vec,
+    Address, BytesN, Env, String, Symbol, Vec,
 };
 
 fn create_test_env() -> (Env, Address, Address, Address, Address) {
@@ -19,6 +19,7 @@ fn create_test_env() -> (Env, Address, Address, Address, Address) {
     let patient = Address::generate(&env);
     let provider = Address::generate(&env);
     let admin = Address::generate(&env);
+    AllergyTrackingContractClient::new(&env, &contract_id).initialize(&admin);
 
     (env, contract_id, patient, provider, admin)
 }
@@ -692,3 +693,33 @@ fn test_valid_allergen_length_accepted() {
     );
     assert_eq!(allergy_id2, 1);
 }
+
+#[test]
+fn test_cross_sensitivity_requires_admin() {
+    let (env, contract_id, _, provider, _) = create_test_env();
+    let client = AllergyTrackingContractClient::new(&env, &contract_id);
+
+    let result = client.try_register_cross_sensitivity(
+        &provider,
+        &String::from_str(&env, "Penicillin"),
+        &String::from_str(&env, "Amoxicillin"),
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, contract_id, _, provider, admin) = create_test_env();
+    let client = AllergyTrackingContractClient::new(&env, &contract_id);
+
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    assert_eq!(client.try_migrate(&provider), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&provider, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,425 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct BloodBankContract;
+
+#[contractimpl]
+impl BloodBankContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(
+        env: Env,
+        admin: Address,
+        facility_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if get_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Facility(facility_id), &name);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Inventory
+    // -------------------------------------------------------------------
+
+    pub fn register_unit(
+        env: Env,
+        facility_id: Address,
+        request: UnitRequest,
+    ) -> Result<u64, Error> {
+        require_facility(&env, &facility_id)?;
+        if request.expires_at <= request.collected_at
+            || request.expires_at <= env.ledger().timestamp()
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        let unit = BloodUnit {
+            unit_id: next_id(&env, DataKey::UnitCounter),
+            facility_id: facility_id.clone(),
+            unit_number: request.unit_number,
+            abo: request.abo,
+            rh_positive: request.rh_positive,
+            component: request.component,
+            collected_at: request.collected_at,
+            expires_at: request.expires_at,
+            status: UnitStatus::Available,
+            assigned_patient: None,
+        };
+        save_unit(&env, &unit);
+        add_facility_unit(&env, &facility_id, unit.unit_id);
+        publish_status(&env, &unit);
+
+        Ok(unit.unit_id)
+    }
+
+    /// Remove a unit from inventory (expired, damaged, returned out of temperature).
+    pub fn discard_unit(env: Env, facility_id: Address, unit_id: u64) -> Result<(), Error> {
+        let mut unit = load_facility_unit(&env, &facility_id, unit_id)?;
+        if matches!(unit.status, UnitStatus::Transfused | UnitStatus::Discarded) {
+            return Err(Error::UnitUnavailable);
+        }
+        unit.status = UnitStatus::Discarded;
+        unit.assigned_patient = None;
+        save_unit(&env, &unit);
+        publish_status(&env, &unit);
+        Ok(())
+    }
+
+    pub fn get_unit(env: Env, unit_id: u64) -> Result<BloodUnit, Error> {
+        load_unit(&env, unit_id).ok_or(Error::UnitNotFound)
+    }
+
+    /// Units still in the blood bank (available or reserved) that expire
+    /// within `within_seconds` from now.
+    pub fn get_expiring_inventory(
+        env: Env,
+        facility_id: Address,
+        within_seconds: u64,
+    ) -> Vec<BloodUnit> {
+        let now = env.ledger().timestamp();
+        let cutoff = now.saturating_add(within_seconds);
+        let mut expiring = Vec::new(&env);
+        for id in facility_units(&env, &facility_id).iter() {
+            if let Some(unit) = load_unit(&env, id) {
+                let in_stock = matches!(
+                    unit.status,
+                    UnitStatus::Available | UnitStatus::Crossmatched
+                );
+                if in_stock && unit.expires_at <= cutoff {
+                    expiring.push_back(unit);
+                }
+            }
+        }
+        expiring
+    }
+
+    // -------------------------------------------------------------------
+    // Pre-transfusion testing
+    // -------------------------------------------------------------------
+
+    pub fn record_type_and_screen(
+        env: Env,
+        facility_id: Address,
+        patient_id: Address,
+        abo: AboGroup,
+        rh_positive: bool,
+        antibody_screen_positive: bool,
+        valid_until: u64,
+    ) -> Result<(), Error> {
+        require_facility(&env, &facility_id)?;
+        let now = env.ledger().timestamp();
+        if valid_until <= now {
+            return Err(Error::InvalidInput);
+        }
+
+        let record = TypeAndScreen {
+            patient_id: patient_id.clone(),
+            facility_id,
+            abo,
+            rh_positive,
+            antibody_screen_positive,
+            collected_at: now,
+            valid_until,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::TypeAndScreen(patient_id), &record);
+        Ok(())
+    }
+
+    pub fn get_type_and_screen(env: Env, patient_id: Address) -> Option<TypeAndScreen> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::TypeAndScreen(patient_id))
+    }
+
+    /// Crossmatch a unit against the patient's current type and screen and
+    /// reserve it for the patient when compatible.
+    pub fn request_crossmatch(
+        env: Env,
+        facility_id: Address,
+        requested_by: Address,
+        patient_id: Address,
+        unit_id: u64,
+    ) -> Result<u64, Error> {
+        requested_by.require_auth();
+        let mut unit = load_facility_unit(&env, &facility_id, unit_id)?;
+        let now = env.ledger().timestamp();
+
+        if unit.status != UnitStatus::Available {
+            return Err(Error::UnitUnavailable);
+        }
+        if unit.expires_at <= now {
+            return Err(Error::UnitExpired);
+        }
+
+        let screen: TypeAndScreen = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TypeAndScreen(patient_id.clone()))
+            .ok_or(Error::TypeAndScreenMissing)?;
+        if screen.valid_until < now {
+            return Err(Error::TypeAndScreenMissing);
+        }
+        if !is_compatible(&unit, &screen) {
+            return Err(Error::Incompatible);
+        }
+
+        let crossmatch = Crossmatch {
+            crossmatch_id: next_id(&env, DataKey::CrossmatchCounter),
+            unit_id,
+            patient_id: patient_id.clone(),
+            requested_by,
+            performed_at: now,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Crossmatch(crossmatch.crossmatch_id), &crossmatch);
+
+        unit.status = UnitStatus::Crossmatched;
+        unit.assigned_patient = Some(patient_id);
+        save_unit(&env, &unit);
+        publish_status(&env, &unit);
+
+        Ok(crossmatch.crossmatch_id)
+    }
+
+    pub fn get_crossmatch(env: Env, crossmatch_id: u64) -> Result<Crossmatch, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Crossmatch(crossmatch_id))
+            .ok_or(Error::CrossmatchNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Issue and administration
+    // -------------------------------------------------------------------
+
+    /// Release a crossmatched unit to the bedside for its reserved patient.
+    pub fn issue_unit(
+        env: Env,
+        facility_id: Address,
+        unit_id: u64,
+        patient_id: Address,
+    ) -> Result<(), Error> {
+        let mut unit = load_facility_unit(&env, &facility_id, unit_id)?;
+        if unit.status != UnitStatus::Crossmatched || unit.assigned_patient != Some(patient_id) {
+            return Err(Error::UnitUnavailable);
+        }
+        if unit.expires_at <= env.ledger().timestamp() {
+            return Err(Error::UnitExpired);
+        }
+
+        unit.status = UnitStatus::Issued;
+        save_unit(&env, &unit);
+        publish_status(&env, &unit);
+        Ok(())
+    }
+
+    /// Record the start of a transfusion. Two different clinicians must sign:
+    /// the one administering and the one independently verifying patient
+    /// and unit identity at the bedside.
+    pub fn record_transfusion(
+        env: Env,
+        unit_id: u64,
+        patient_id: Address,
+        administered_by: Address,
+        verified_by: Address,
+        volume_ml: u32,
+    ) -> Result<u64, Error> {
+        if administered_by == verified_by {
+            return Err(Error::DualSignatureRequired);
+        }
+        administered_by.require_auth();
+        verified_by.require_auth();
+
+        let mut unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.status != UnitStatus::Issued || unit.assigned_patient != Some(patient_id.clone()) {
+            return Err(Error::UnitUnavailable);
+        }
+
+        let transfusion = Transfusion {
+            transfusion_id: next_id(&env, DataKey::TransfusionCounter),
+            unit_id,
+            patient_id,
+            administered_by,
+            verified_by,
+            started_at: env.ledger().timestamp(),
+            volume_ml,
+        };
+        save_transfusion(&env, &transfusion);
+
+        unit.status = UnitStatus::Transfused;
+        save_unit(&env, &unit);
+        publish_status(&env, &unit);
+
+        Ok(transfusion.transfusion_id)
+    }
+
+    pub fn get_transfusion(env: Env, transfusion_id: u64) -> Result<Transfusion, Error> {
+        load_transfusion(&env, transfusion_id).ok_or(Error::TransfusionNotFound)
+    }
+
+    /// Report an adverse reaction. Either signing clinician may report.
+    pub fn report_reaction(
+        env: Env,
+        transfusion_id: u64,
+        reporter: Address,
+        reaction_type: Symbol,
+        severity: Symbol,
+        description: String,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+        let transfusion =
+            load_transfusion(&env, transfusion_id).ok_or(Error::TransfusionNotFound)?;
+        if reporter != transfusion.administered_by && reporter != transfusion.verified_by {
+            return Err(Error::Unauthorized);
+        }
+
+        let key = DataKey::Reactions(transfusion_id);
+        let mut reports: Vec<ReactionReport> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        reports.push_back(ReactionReport {
+            transfusion_id,
+            reported_by: reporter,
+            reaction_type,
+            severity: severity.clone(),
+            description,
+            reported_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &reports);
+
+        TransfusionReaction {
+            transfusion_id,
+            patient_id: transfusion.patient_id,
+            severity,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_reactions(env: Env, transfusion_id: u64) -> Vec<ReactionReport> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Reactions(transfusion_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_facility(env: &Env, facility_id: &Address) -> Result<(), Error> {
+    facility_id.require_auth();
+    if !is_facility(env, facility_id) {
+        return Err(Error::FacilityNotRegistered);
+    }
+    Ok(())
+}
+
+fn load_facility_unit(env: &Env, facility_id: &Address, unit_id: u64) -> Result<BloodUnit, Error> {
+    require_facility(env, facility_id)?;
+    let unit = load_unit(env, unit_id).ok_or(Error::UnitNotFound)?;
+    if unit.facility_id != *facility_id {
+        return Err(Error::Unauthorized);
+    }
+    Ok(unit)
+}
+
+fn publish_status(env: &Env, unit: &BloodUnit) {
+    UnitStatusChanged {
+        unit_id: unit.unit_id,
+        status: unit.status.clone(),
+    }
+    .publish(env);
+}
+
+/// Whether donor red cells carrying `donor` antigens can be given to `recipient`.
+fn red_cells_compatible(donor: AboGroup, recipient: AboGroup) -> bool {
+    match recipient {
+        AboGroup::AB => true,
+        AboGroup::A => matches!(donor, AboGroup::A | AboGroup::O),
+        AboGroup::B => matches!(donor, AboGroup::B | AboGroup::O),
+        AboGroup::O => donor == AboGroup::O,
+    }
+}
+
+fn is_compatible(unit: &BloodUnit, screen: &TypeAndScreen) -> bool {
+    match unit.component {
+        Component::WholeBlood => {
+            unit.abo == screen.abo && (screen.rh_positive || !unit.rh_positive)
+        }
+        Component::RedCells => {
+            red_cells_compatible(unit.abo, screen.abo) && (screen.rh_positive || !unit.rh_positive)
+        }
+        // Plasma antibodies run the other way: the recipient's red cells must
+        // not carry antigens the donor plasma reacts against.
+        Component::Plasma => red_cells_compatible(screen.abo, unit.abo),
+        Component::Platelets | Component::Cryoprecipitate => true,
+    }
+}
@@ -0,0 +1,76 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::{BloodUnit, DataKey, Transfusion};
+
+// -----------------------------------------------------------------------
+// Admin and facilities
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_facility(env: &Env, facility_id: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Facility(facility_id.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+pub fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+// -----------------------------------------------------------------------
+// Units
+// -----------------------------------------------------------------------
+
+pub fn save_unit(env: &Env, unit: &BloodUnit) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Unit(unit.unit_id), unit);
+}
+
+pub fn load_unit(env: &Env, unit_id: u64) -> Option<BloodUnit> {
+    env.storage().persistent().get(&DataKey::Unit(unit_id))
+}
+
+pub fn add_facility_unit(env: &Env, facility_id: &Address, unit_id: u64) {
+    let key = DataKey::FacilityUnits(facility_id.clone());
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(unit_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn facility_units(env: &Env, facility_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FacilityUnits(facility_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+// -----------------------------------------------------------------------
+// Transfusions
+// -----------------------------------------------------------------------
+
+pub fn save_transfusion(env: &Env, transfusion: &Transfusion) {
+    env.storage().persistent().set(
+        &DataKey::Transfusion(transfusion.transfusion_id),
+        transfusion,
+    );
+}
+
+pub fn load_transfusion(env: &Env, transfusion_id: u64) -> Option<Transfusion> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Transfusion(transfusion_id))
+}
@@ -0,0 +1,514 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (Env, Address, Address, BloodBankContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10 * DAY);
+    let contract_id = env.register(BloodBankContract, ());
+    let client = BloodBankContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(
+        &admin,
+        &facility,
+        &String::from_str(&env, "Central Blood Bank"),
+    );
+
+    (env, admin, facility, client)
+}
+
+fn add_unit(
+    env: &Env,
+    client: &BloodBankContractClient,
+    facility: &Address,
+    abo: AboGroup,
+    rh_positive: bool,
+    component: Component,
+    shelf_days: u64,
+) -> u64 {
+    let now = env.ledger().timestamp();
+    client.register_unit(
+        facility,
+        &UnitRequest {
+            unit_number: String::from_str(env, "W0000 26 000001"),
+            abo,
+            rh_positive,
+            component,
+            collected_at: now - DAY,
+            expires_at: now + shelf_days * DAY,
+        },
+    )
+}
+
+fn typed_patient(
+    env: &Env,
+    client: &BloodBankContractClient,
+    facility: &Address,
+    abo: AboGroup,
+    rh_positive: bool,
+) -> Address {
+    let patient = Address::generate(env);
+    let valid_until = env.ledger().timestamp() + 3 * DAY;
+    client.record_type_and_screen(facility, &patient, &abo, &rh_positive, &false, &valid_until);
+    patient
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_full_transfusion_workflow() {
+    let (env, _, facility, client) = setup();
+    let patient = typed_patient(&env, &client, &facility, AboGroup::A, true);
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        false,
+        Component::RedCells,
+        30,
+    );
+    let tech = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let second_nurse = Address::generate(&env);
+
+    let crossmatch_id = client.request_crossmatch(&facility, &tech, &patient, &unit_id);
+    assert_eq!(client.get_crossmatch(&crossmatch_id).unit_id, unit_id);
+    assert_eq!(client.get_unit(&unit_id).status, UnitStatus::Crossmatched);
+
+    client.issue_unit(&facility, &unit_id, &patient);
+    let transfusion_id = client.record_transfusion(&unit_id, &patient, &nurse, &second_nurse, &300);
+
+    let unit = client.get_unit(&unit_id);
+    assert_eq!(unit.status, UnitStatus::Transfused);
+    assert_eq!(unit.assigned_patient, Some(patient.clone()));
+    assert_eq!(
+        client.get_transfusion(&transfusion_id).verified_by,
+        second_nurse
+    );
+
+    client.report_reaction(
+        &transfusion_id,
+        &nurse,
+        &symbol_short!("febrile"),
+        &symbol_short!("mild"),
+        &String::from_str(&env, "Temp rise 1.2C at 20 minutes"),
+    );
+    assert_eq!(client.get_reactions(&transfusion_id).len(), 1);
+}
+
+#[test]
+fn test_abo_and_rh_compatibility() {
+    let (env, _, facility, client) = setup();
+    let tech = Address::generate(&env);
+
+    let o_neg = typed_patient(&env, &client, &facility, AboGroup::O, false);
+    let a_red_cells = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::A,
+        false,
+        Component::RedCells,
+        30,
+    );
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &o_neg, &a_red_cells),
+        Err(Ok(Error::Incompatible))
+    );
+    let o_pos_red_cells = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        true,
+        Component::RedCells,
+        30,
+    );
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &o_neg, &o_pos_red_cells),
+        Err(Ok(Error::Incompatible))
+    );
+
+    // AB plasma is the universal plasma donor; O plasma only suits O recipients.
+    let ab_pos = typed_patient(&env, &client, &facility, AboGroup::AB, true);
+    let o_plasma = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        true,
+        Component::Plasma,
+        365,
+    );
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &ab_pos, &o_plasma),
+        Err(Ok(Error::Incompatible))
+    );
+    let ab_plasma = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::AB,
+        true,
+        Component::Plasma,
+        365,
+    );
+    client.request_crossmatch(&facility, &tech, &o_neg, &ab_plasma);
+}
+
+#[test]
+fn test_crossmatch_requires_current_type_and_screen() {
+    let (env, _, facility, client) = setup();
+    let tech = Address::generate(&env);
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        false,
+        Component::RedCells,
+        30,
+    );
+
+    let untyped = Address::generate(&env);
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &untyped, &unit_id),
+        Err(Ok(Error::TypeAndScreenMissing))
+    );
+
+    let patient = typed_patient(&env, &client, &facility, AboGroup::B, true);
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 4 * DAY);
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &patient, &unit_id),
+        Err(Ok(Error::TypeAndScreenMissing))
+    );
+}
+
+#[test]
+fn test_transfusion_requires_two_distinct_signers() {
+    let (env, _, facility, client) = setup();
+    let patient = typed_patient(&env, &client, &facility, AboGroup::B, true);
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::B,
+        true,
+        Component::RedCells,
+        30,
+    );
+    let tech = Address::generate(&env);
+    let nurse = Address::generate(&env);
+
+    client.request_crossmatch(&facility, &tech, &patient, &unit_id);
+    assert_eq!(
+        client.try_record_transfusion(&unit_id, &patient, &nurse, &nurse, &250),
+        Err(Ok(Error::DualSignatureRequired))
+    );
+
+    client.issue_unit(&facility, &unit_id, &patient);
+    let other_patient = Address::generate(&env);
+    let second_nurse = Address::generate(&env);
+    assert_eq!(
+        client.try_record_transfusion(&unit_id, &other_patient, &nurse, &second_nurse, &250),
+        Err(Ok(Error::UnitUnavailable))
+    );
+}
+
+#[test]
+fn test_expiring_inventory_excludes_issued_and_discarded_units() {
+    let (env, _, facility, client) = setup();
+    let patient = typed_patient(&env, &client, &facility, AboGroup::AB, true);
+    let tech = Address::generate(&env);
+
+    let platelets = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::A,
+        true,
+        Component::Platelets,
+        2,
+    );
+    let issued = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::A,
+        true,
+        Component::Platelets,
+        2,
+    );
+    let discarded = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        true,
+        Component::RedCells,
+        1,
+    );
+    add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        true,
+        Component::RedCells,
+        30,
+    );
+
+    client.request_crossmatch(&facility, &tech, &patient, &issued);
+    client.issue_unit(&facility, &issued, &patient);
+    client.discard_unit(&facility, &discarded);
+
+    let expiring = client.get_expiring_inventory(&facility, &(3 * DAY));
+    assert_eq!(expiring.len(), 1);
+    assert_eq!(expiring.get(0).unwrap().unit_id, platelets);
+
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 3 * DAY);
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &patient, &platelets),
+        Err(Ok(Error::UnitExpired))
+    );
+}
+
+#[test]
+fn test_admin_only_setup() {
+    let (env, admin, facility, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&facility, &other, &String::from_str(&env, "Satellite")),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_facility_scoping() {
+    let (env, admin, facility, client) = setup();
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        false,
+        Component::RedCells,
+        30,
+    );
+
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_register_unit(
+            &unregistered,
+            &UnitRequest {
+                unit_number: String::from_str(&env, "W0000 26 000002"),
+                abo: AboGroup::A,
+                rh_positive: true,
+                component: Component::RedCells,
+                collected_at: env.ledger().timestamp(),
+                expires_at: env.ledger().timestamp() + DAY,
+            },
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+    assert_eq!(
+        client.try_record_type_and_screen(
+            &unregistered,
+            &Address::generate(&env),
+            &AboGroup::A,
+            &true,
+            &false,
+            &(env.ledger().timestamp() + DAY),
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+
+    // A registered facility still cannot touch another facility's units
+    let other_facility = Address::generate(&env);
+    client.register_facility(
+        &admin,
+        &other_facility,
+        &String::from_str(&env, "Satellite Blood Bank"),
+    );
+    assert_eq!(
+        client.try_discard_unit(&other_facility, &unit_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    let patient = typed_patient(&env, &client, &other_facility, AboGroup::O, false);
+    let tech = Address::generate(&env);
+    assert_eq!(
+        client.try_request_crossmatch(&other_facility, &tech, &patient, &unit_id),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_invalid_dates_rejected() {
+    let (env, _, facility, client) = setup();
+    let now = env.ledger().timestamp();
+
+    assert_eq!(
+        client.try_register_unit(
+            &facility,
+            &UnitRequest {
+                unit_number: String::from_str(&env, "W0000 26 000003"),
+                abo: AboGroup::B,
+                rh_positive: false,
+                component: Component::Plasma,
+                collected_at: now - 2 * DAY,
+                expires_at: now - DAY,
+            },
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_record_type_and_screen(
+            &facility,
+            &Address::generate(&env),
+            &AboGroup::B,
+            &false,
+            &false,
+            &now,
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_invalid_unit_transitions() {
+    let (env, _, facility, client) = setup();
+    let patient = typed_patient(&env, &client, &facility, AboGroup::O, true);
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::O,
+        true,
+        Component::RedCells,
+        30,
+    );
+    let tech = Address::generate(&env);
+
+    // Units must be crossmatched before issue, and only once
+    assert_eq!(
+        client.try_issue_unit(&facility, &unit_id, &patient),
+        Err(Ok(Error::UnitUnavailable))
+    );
+    client.request_crossmatch(&facility, &tech, &patient, &unit_id);
+    let other_patient = typed_patient(&env, &client, &facility, AboGroup::O, true);
+    assert_eq!(
+        client.try_request_crossmatch(&facility, &tech, &other_patient, &unit_id),
+        Err(Ok(Error::UnitUnavailable))
+    );
+    assert_eq!(
+        client.try_issue_unit(&facility, &unit_id, &other_patient),
+        Err(Ok(Error::UnitUnavailable))
+    );
+
+    client.issue_unit(&facility, &unit_id, &patient);
+    let nurse = Address::generate(&env);
+    let second_nurse = Address::generate(&env);
+    client.record_transfusion(&unit_id, &patient, &nurse, &second_nurse, &300);
+
+    assert_eq!(
+        client.try_record_transfusion(&unit_id, &patient, &nurse, &second_nurse, &300),
+        Err(Ok(Error::UnitUnavailable))
+    );
+    assert_eq!(
+        client.try_discard_unit(&facility, &unit_id),
+        Err(Ok(Error::UnitUnavailable))
+    );
+}
+
+#[test]
+fn test_lookup_and_reaction_errors() {
+    let (env, _, facility, client) = setup();
+    let patient = typed_patient(&env, &client, &facility, AboGroup::A, true);
+    let unit_id = add_unit(
+        &env,
+        &client,
+        &facility,
+        AboGroup::A,
+        true,
+        Component::RedCells,
+        30,
+    );
+    let tech = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let second_nurse = Address::generate(&env);
+
+    client.request_crossmatch(&facility, &tech, &patient, &unit_id);
+    client.issue_unit(&facility, &unit_id, &patient);
+    let transfusion_id = client.record_transfusion(&unit_id, &patient, &nurse, &second_nurse, &300);
+
+    // Only the two signing clinicians may report a reaction
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_report_reaction(
+            &transfusion_id,
+            &stranger,
+            &symbol_short!("febrile"),
+            &symbol_short!("mild"),
+            &String::from_str(&env, "Reported second hand"),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_report_reaction(
+            &99,
+            &nurse,
+            &symbol_short!("febrile"),
+            &symbol_short!("mild"),
+            &String::from_str(&env, "Unknown transfusion"),
+        ),
+        Err(Ok(Error::TransfusionNotFound))
+    );
+
+    assert_eq!(client.try_get_unit(&99), Err(Ok(Error::UnitNotFound)));
+    assert_eq!(
+        client.try_get_crossmatch(&99),
+        Err(Ok(Error::CrossmatchNotFound))
+    );
+    assert_eq!(
+        client.try_get_transfusion(&99),
+        Err(Ok(Error::TransfusionNotFound))
+    );
+    assert!(client.get_reactions(&transfusion_id).is_empty());
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, facility, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&facility), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&facility, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,184 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, String, Symbol};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    FacilityNotRegistered = 4,
+    UnitNotFound = 5,
+    UnitUnavailable = 6,
+    UnitExpired = 7,
+    TypeAndScreenMissing = 8,
+    Incompatible = 9,
+    CrossmatchNotFound = 10,
+    DualSignatureRequired = 11,
+    TransfusionNotFound = 12,
+    InvalidInput = 13,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AboGroup {
+    O,
+    A,
+    B,
+    AB,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Component {
+    WholeBlood,
+    RedCells,
+    Plasma,
+    Platelets,
+    Cryoprecipitate,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UnitStatus {
+    /// In inventory and unassigned.
+    Available,
+    /// Reserved for a patient after a compatible crossmatch.
+    Crossmatched,
+    /// Released from the blood bank to the bedside.
+    Issued,
+    Transfused,
+    Discarded,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitRequest {
+    pub unit_number: String,
+    pub abo: AboGroup,
+    pub rh_positive: bool,
+    pub component: Component,
+    pub collected_at: u64,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BloodUnit {
+    pub unit_id: u64,
+    pub facility_id: Address,
+    pub unit_number: String,
+    pub abo: AboGroup,
+    pub rh_positive: bool,
+    pub component: Component,
+    pub collected_at: u64,
+    pub expires_at: u64,
+    pub status: UnitStatus,
+    pub assigned_patient: Option<Address>,
+}
+
+/// Patient blood type and antibody screen, required before crossmatching.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TypeAndScreen {
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub abo: AboGroup,
+    pub rh_positive: bool,
+    pub antibody_screen_positive: bool,
+    pub collected_at: u64,
+    pub valid_until: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Crossmatch {
+    pub crossmatch_id: u64,
+    pub unit_id: u64,
+    pub patient_id: Address,
+    pub requested_by: Address,
+    pub performed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transfusion {
+    pub transfusion_id: u64,
+    pub unit_id: u64,
+    pub patient_id: Address,
+    pub administered_by: Address,
+    pub verified_by: Address,
+    pub started_at: u64,
+    pub volume_ml: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReactionReport {
+    pub transfusion_id: u64,
+    pub reported_by: Address,
+    pub reaction_type: Symbol,
+    pub severity: Symbol,
+    pub description: String,
+    pub reported_at: u64,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UnitStatusChanged {
+    #[topic]
+    pub unit_id: u64,
+    pub status: UnitStatus,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransfusionReaction {
+    #[topic]
+    pub transfusion_id: u64,
+    pub patient_id: Address,
+    pub severity: Symbol,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// facility_id -> facility name
+    Facility(Address),
+    UnitCounter,
+    /// unit_id -> BloodUnit
+    Unit(u64),
+    /// facility_id -> Vec<u64> (unit ids)
+    FacilityUnits(Address),
+    /// patient_id -> TypeAndScreen
+    TypeAndScreen(Address),
+    CrossmatchCounter,
+    /// crossmatch_id -> Crossmatch
+    Crossmatch(u64),
+    TransfusionCounter,
+    /// transfusion_id -> Transfusion
+    Transfusion(u64),
+    /// transfusion_id -> Vec<ReactionReport>
+    Reactions(u64),
+}
@@ -10,11 +10,25 @@ use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol,
 use storage::*;
 use types::*;
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct CarePlanContract;
 
 #[contractimpl]
 impl CarePlanContract {
+    /// Set the admin allowed to upgrade the contract. Can only be called once.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if has_admin(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        save_admin(&env, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     /// Create a new care plan for a patient.
     pub fn create_care_plan(
         env: Env,
@@ -457,4 +471,29 @@ impl CarePlanContract {
             next_review_date: plan.next_review_date,
         })
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
 }
\ No newline at end of file
@@ -3,9 +3,28 @@
 use soroban_sdk::{Address, Env, Vec};
 
 use crate::types::{
-    Barrier, CarePlan, CareReview, CareTeamMember, CareGoal, DataKey, Intervention,
+    Barrier, CarePlan, CareReview, CareTeamMember, CareGoal, DataKey, Error, Intervention,
 };
 
+// -----------------------------------------------------------------------
+// Admin
+// -----------------------------------------------------------------------
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+pub fn save_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn get_admin(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)
+}
+
 // -----------------------------------------------------------------------
 // Counter helpers
 // -----------------------------------------------------------------------
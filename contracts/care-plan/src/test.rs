@@ -1149,3 +1149,29 @@ fn test_full_care_plan_workflow() {
     assert!(summary.barriers.get(0).unwrap().resolved);
     assert!(summary.last_review_date.is_some());
 }
+
+// -----------------------------------------------------------------------
+// upgrade / migrate
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, provider, _patient) = setup();
+    let contract_id = env.register(CarePlanContract, ());
+    let client = CarePlanContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&provider), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&provider, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -20,6 +20,8 @@ pub enum Error {
     GoalDiscontinued = 8,
     BarrierAlreadyResolved = 9,
     ReviewAlreadyConducted = 10,
+    AlreadyInitialized = 11,
+    NotInitialized = 12,
 }
 
 // -----------------------------------------------------------------------
@@ -190,6 +192,8 @@ pub struct CarePlanSummary {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    /// Address allowed to upgrade the contract.
+    Admin,
     /// Auto-increment counter for care plans.
     CarePlanCounter,
     /// Auto-increment counter for goals.
@@ -0,0 +1,507 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contract]
+pub struct ChronicCareManagementContract;
+
+#[contractimpl]
+impl ChronicCareManagementContract {
+    // -------------------------------------------------------------------
+    // Programs
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn create_program(
+        env: Env,
+        admin: Address,
+        condition: Condition,
+        name: String,
+        manager: Address,
+    ) -> Result<u64, Error> {
+        admin.require_auth();
+        if get_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+        let program = Program {
+            program_id: next_id(&env, DataKey::ProgramCounter),
+            condition,
+            name,
+            manager,
+            active: true,
+            created_at: env.ledger().timestamp(),
+        };
+        save_program(&env, &program);
+        Ok(program.program_id)
+    }
+
+    pub fn set_program_active(
+        env: Env,
+        manager: Address,
+        program_id: u64,
+        active: bool,
+    ) -> Result<(), Error> {
+        let mut program = load_managed_program(&env, &manager, program_id)?;
+        program.active = active;
+        save_program(&env, &program);
+        Ok(())
+    }
+
+    /// Replace the program's care-gap rules.
+    pub fn set_care_gap_rules(
+        env: Env,
+        manager: Address,
+        program_id: u64,
+        rules: Vec<CareGapRule>,
+    ) -> Result<(), Error> {
+        load_managed_program(&env, &manager, program_id)?;
+        for rule in rules.iter() {
+            if rule.interval_days == 0 {
+                return Err(Error::InvalidInput);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::GapRules(program_id), &rules);
+        Ok(())
+    }
+
+    pub fn add_care_manager(
+        env: Env,
+        manager: Address,
+        program_id: u64,
+        care_manager: Address,
+    ) -> Result<(), Error> {
+        load_managed_program(&env, &manager, program_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::CareManager(program_id, care_manager), &true);
+        Ok(())
+    }
+
+    pub fn get_program(env: Env, program_id: u64) -> Result<Program, Error> {
+        load_program(&env, program_id).ok_or(Error::ProgramNotFound)
+    }
+
+    pub fn get_care_gap_rules(env: Env, program_id: u64) -> Vec<CareGapRule> {
+        gap_rules(&env, program_id)
+    }
+
+    // -------------------------------------------------------------------
+    // Enrollment
+    // -------------------------------------------------------------------
+
+    /// Enroll a patient and assign their care manager. Re-enrolling a
+    /// previously disenrolled patient reopens the enrollment.
+    pub fn enroll_patient(
+        env: Env,
+        manager: Address,
+        program_id: u64,
+        patient_id: Address,
+        care_manager: Address,
+    ) -> Result<(), Error> {
+        let program = load_managed_program(&env, &manager, program_id)?;
+        if !program.active {
+            return Err(Error::ProgramInactive);
+        }
+        if !is_care_manager(&env, program_id, &care_manager) {
+            return Err(Error::InvalidInput);
+        }
+
+        match load_enrollment(&env, program_id, &patient_id) {
+            Some(existing) if existing.active => return Err(Error::AlreadyEnrolled),
+            Some(_) => {}
+            None => {
+                let mut patients = program_patients(&env, program_id);
+                patients.push_back(patient_id.clone());
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::ProgramPatients(program_id), &patients);
+            }
+        }
+
+        save_enrollment(
+            &env,
+            &Enrollment {
+                program_id,
+                patient_id: patient_id.clone(),
+                care_manager: care_manager.clone(),
+                enrolled_at: env.ledger().timestamp(),
+                active: true,
+                disenrolled_at: 0,
+            },
+        );
+
+        PatientEnrolled {
+            program_id,
+            patient_id,
+            care_manager,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Leave the program. Either the patient or the program manager may disenroll.
+    pub fn disenroll_patient(
+        env: Env,
+        caller: Address,
+        program_id: u64,
+        patient_id: Address,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+        let program = load_program(&env, program_id).ok_or(Error::ProgramNotFound)?;
+        if caller != patient_id && caller != program.manager {
+            return Err(Error::Unauthorized);
+        }
+        let mut enrollment = load_active_enrollment(&env, program_id, &patient_id)?;
+        enrollment.active = false;
+        enrollment.disenrolled_at = env.ledger().timestamp();
+        save_enrollment(&env, &enrollment);
+        Ok(())
+    }
+
+    pub fn get_enrollment(
+        env: Env,
+        program_id: u64,
+        patient_id: Address,
+    ) -> Result<Enrollment, Error> {
+        load_enrollment(&env, program_id, &patient_id).ok_or(Error::NotEnrolled)
+    }
+
+    // -------------------------------------------------------------------
+    // Metrics and care gaps
+    // -------------------------------------------------------------------
+
+    /// Record a reading. Patients may self-report; otherwise the recorder
+    /// must be a care manager on the program.
+    pub fn record_metric(
+        env: Env,
+        recorder: Address,
+        program_id: u64,
+        patient_id: Address,
+        metric: MetricType,
+        value_x100: i64,
+    ) -> Result<(), Error> {
+        recorder.require_auth();
+        load_active_enrollment(&env, program_id, &patient_id)?;
+        if recorder != patient_id && !is_care_manager(&env, program_id, &recorder) {
+            return Err(Error::Unauthorized);
+        }
+        if value_x100 <= 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut list = readings(&env, program_id, &patient_id);
+        list.push_back(MetricReading {
+            metric,
+            value_x100,
+            recorded_by: recorder,
+            recorded_at: env.ledger().timestamp(),
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::Readings(program_id, patient_id), &list);
+        Ok(())
+    }
+
+    pub fn get_metric_history(
+        env: Env,
+        program_id: u64,
+        patient_id: Address,
+        metric: MetricType,
+    ) -> Vec<MetricReading> {
+        let mut history = Vec::new(&env);
+        for reading in readings(&env, program_id, &patient_id).iter() {
+            if reading.metric == metric {
+                history.push_back(reading);
+            }
+        }
+        history
+    }
+
+    /// Evaluate the program's care-gap rules for one enrolled patient.
+    pub fn get_care_gaps(
+        env: Env,
+        program_id: u64,
+        patient_id: Address,
+    ) -> Result<Vec<CareGap>, Error> {
+        load_active_enrollment(&env, program_id, &patient_id)?;
+        let now = env.ledger().timestamp();
+        let mut gaps = Vec::new(&env);
+
+        for rule in gap_rules(&env, program_id).iter() {
+            let interval = u64::from(rule.interval_days) * SECONDS_PER_DAY;
+            match latest_reading(&env, program_id, &patient_id, rule.metric) {
+                None => gaps.push_back(CareGap {
+                    metric: rule.metric,
+                    kind: GapKind::Overdue,
+                    last_recorded_at: 0,
+                    last_value_x100: 0,
+                }),
+                Some(latest) => {
+                    let kind = if now.saturating_sub(latest.recorded_at) > interval {
+                        Some(GapKind::Overdue)
+                    } else if rule.target_max_x100 > 0 && latest.value_x100 > rule.target_max_x100 {
+                        Some(GapKind::AboveTarget)
+                    } else {
+                        None
+                    };
+                    if let Some(kind) = kind {
+                        gaps.push_back(CareGap {
+                            metric: rule.metric,
+                            kind,
+                            last_recorded_at: latest.recorded_at,
+                            last_value_x100: latest.value_x100,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(gaps)
+    }
+
+    // -------------------------------------------------------------------
+    // Outreach
+    // -------------------------------------------------------------------
+
+    /// Assign an outreach task for an enrolled patient to a care manager on
+    /// the program. The program manager or any of its care managers may assign.
+    pub fn assign_outreach_task(
+        env: Env,
+        assigner: Address,
+        program_id: u64,
+        patient_id: Address,
+        care_manager: Address,
+        reason: String,
+        due_at: u64,
+    ) -> Result<u64, Error> {
+        assigner.require_auth();
+        let program = load_program(&env, program_id).ok_or(Error::ProgramNotFound)?;
+        if assigner != program.manager && !is_care_manager(&env, program_id, &assigner) {
+            return Err(Error::Unauthorized);
+        }
+        if !is_care_manager(&env, program_id, &care_manager) {
+            return Err(Error::InvalidInput);
+        }
+        load_active_enrollment(&env, program_id, &patient_id)?;
+        let now = env.ledger().timestamp();
+        if due_at <= now {
+            return Err(Error::InvalidInput);
+        }
+
+        let task = OutreachTask {
+            task_id: next_id(&env, DataKey::TaskCounter),
+            program_id,
+            patient_id: patient_id.clone(),
+            care_manager: care_manager.clone(),
+            reason,
+            due_at,
+            status: TaskStatus::Open,
+            created_at: now,
+            closed_at: 0,
+            outcome_note: None,
+        };
+        save_task(&env, &task);
+        push_id(
+            &env,
+            DataKey::ManagerTasks(care_manager.clone()),
+            task.task_id,
+        );
+        push_id(&env, DataKey::ProgramTasks(program_id), task.task_id);
+
+        OutreachAssigned {
+            task_id: task.task_id,
+            care_manager,
+            patient_id,
+            due_at,
+        }
+        .publish(&env);
+        Ok(task.task_id)
+    }
+
+    pub fn complete_outreach_task(
+        env: Env,
+        care_manager: Address,
+        task_id: u64,
+        outcome_note: String,
+    ) -> Result<(), Error> {
+        care_manager.require_auth();
+        let mut task = load_task(&env, task_id).ok_or(Error::TaskNotFound)?;
+        if task.care_manager != care_manager {
+            return Err(Error::Unauthorized);
+        }
+        if task.status != TaskStatus::Open {
+            return Err(Error::InvalidState);
+        }
+        task.status = TaskStatus::Completed;
+        task.closed_at = env.ledger().timestamp();
+        task.outcome_note = Some(outcome_note);
+        save_task(&env, &task);
+        Ok(())
+    }
+
+    pub fn cancel_outreach_task(env: Env, manager: Address, task_id: u64) -> Result<(), Error> {
+        let mut task = load_task(&env, task_id).ok_or(Error::TaskNotFound)?;
+        load_managed_program(&env, &manager, task.program_id)?;
+        if task.status != TaskStatus::Open {
+            return Err(Error::InvalidState);
+        }
+        task.status = TaskStatus::Cancelled;
+        task.closed_at = env.ledger().timestamp();
+        save_task(&env, &task);
+        Ok(())
+    }
+
+    pub fn get_task(env: Env, task_id: u64) -> Result<OutreachTask, Error> {
+        load_task(&env, task_id).ok_or(Error::TaskNotFound)
+    }
+
+    /// Open tasks assigned to a care manager.
+    pub fn get_open_tasks(env: Env, care_manager: Address) -> Vec<OutreachTask> {
+        let mut open = Vec::new(&env);
+        for id in id_list(&env, &DataKey::ManagerTasks(care_manager)).iter() {
+            if let Some(task) = load_task(&env, id) {
+                if task.status == TaskStatus::Open {
+                    open.push_back(task);
+                }
+            }
+        }
+        open
+    }
+
+    // -------------------------------------------------------------------
+    // Outcomes
+    // -------------------------------------------------------------------
+
+    /// Roll up the latest `metric` value across active enrollees. A patient
+    /// is at target when their latest reading is within the program's rule
+    /// for the metric (any reading counts when no target is configured).
+    pub fn get_program_outcomes(
+        env: Env,
+        program_id: u64,
+        metric: MetricType,
+    ) -> Result<ProgramOutcome, Error> {
+        load_program(&env, program_id).ok_or(Error::ProgramNotFound)?;
+        let target = gap_rules(&env, program_id)
+            .iter()
+            .find(|rule| rule.metric == metric)
+            .map(|rule| rule.target_max_x100)
+            .unwrap_or(0);
+
+        let mut outcome = ProgramOutcome {
+            program_id,
+            metric,
+            enrolled: 0,
+            measured: 0,
+            at_target: 0,
+            average_latest_x100: 0,
+            open_tasks: 0,
+        };
+        let mut total: i64 = 0;
+        for patient in program_patients(&env, program_id).iter() {
+            let active = load_enrollment(&env, program_id, &patient).is_some_and(|e| e.active);
+            if !active {
+                continue;
+            }
+            outcome.enrolled += 1;
+            if let Some(latest) = latest_reading(&env, program_id, &patient, metric) {
+                outcome.measured += 1;
+                total += latest.value_x100;
+                if target == 0 || latest.value_x100 <= target {
+                    outcome.at_target += 1;
+                }
+            }
+        }
+        if outcome.measured > 0 {
+            outcome.average_latest_x100 = total / i64::from(outcome.measured);
+        }
+        for id in id_list(&env, &DataKey::ProgramTasks(program_id)).iter() {
+            if load_task(&env, id).is_some_and(|t| t.status == TaskStatus::Open) {
+                outcome.open_tasks += 1;
+            }
+        }
+        Ok(outcome)
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_managed_program(env: &Env, manager: &Address, program_id: u64) -> Result<Program, Error> {
+    manager.require_auth();
+    let program = load_program(env, program_id).ok_or(Error::ProgramNotFound)?;
+    if program.manager != *manager {
+        return Err(Error::Unauthorized);
+    }
+    Ok(program)
+}
+
+fn load_active_enrollment(
+    env: &Env,
+    program_id: u64,
+    patient_id: &Address,
+) -> Result<Enrollment, Error> {
+    match load_enrollment(env, program_id, patient_id) {
+        Some(enrollment) if enrollment.active => Ok(enrollment),
+        _ => Err(Error::NotEnrolled),
+    }
+}
+
+fn gap_rules(env: &Env, program_id: u64) -> Vec<CareGapRule> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::GapRules(program_id))
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,119 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::{DataKey, Enrollment, MetricReading, MetricType, OutreachTask, Program};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_care_manager(env: &Env, program_id: u64, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CareManager(program_id, who.clone()))
+        .unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+pub fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+// -----------------------------------------------------------------------
+// Programs and enrollment
+// -----------------------------------------------------------------------
+
+pub fn save_program(env: &Env, program: &Program) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Program(program.program_id), program);
+}
+
+pub fn load_program(env: &Env, program_id: u64) -> Option<Program> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Program(program_id))
+}
+
+pub fn program_patients(env: &Env, program_id: u64) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProgramPatients(program_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_enrollment(env: &Env, enrollment: &Enrollment) {
+    env.storage().persistent().set(
+        &DataKey::Enrollment(enrollment.program_id, enrollment.patient_id.clone()),
+        enrollment,
+    );
+}
+
+pub fn load_enrollment(env: &Env, program_id: u64, patient_id: &Address) -> Option<Enrollment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Enrollment(program_id, patient_id.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Readings
+// -----------------------------------------------------------------------
+
+pub fn readings(env: &Env, program_id: u64, patient_id: &Address) -> Vec<MetricReading> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Readings(program_id, patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn latest_reading(
+    env: &Env,
+    program_id: u64,
+    patient_id: &Address,
+    metric: MetricType,
+) -> Option<MetricReading> {
+    let all = readings(env, program_id, patient_id);
+    let mut latest: Option<MetricReading> = None;
+    for reading in all.iter() {
+        if reading.metric == metric
+            && latest
+                .as_ref()
+                .is_none_or(|l| reading.recorded_at >= l.recorded_at)
+        {
+            latest = Some(reading);
+        }
+    }
+    latest
+}
+
+// -----------------------------------------------------------------------
+// Tasks
+// -----------------------------------------------------------------------
+
+pub fn save_task(env: &Env, task: &OutreachTask) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Task(task.task_id), task);
+}
+
+pub fn load_task(env: &Env, task_id: u64) -> Option<OutreachTask> {
+    env.storage().persistent().get(&DataKey::Task(task_id))
+}
+
+pub fn id_list(env: &Env, key: &DataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+pub fn push_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids = id_list(env, &key);
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
@@ -0,0 +1,411 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    u64,
+    ChronicCareManagementContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(400 * DAY);
+    let contract_id = env.register(ChronicCareManagementContract, ());
+    let client = ChronicCareManagementContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let manager = Address::generate(&env);
+    let care_manager = Address::generate(&env);
+    client.initialize(&admin);
+    let program_id = client.create_program(
+        &admin,
+        &Condition::Diabetes,
+        &String::from_str(&env, "Type 2 Diabetes Management"),
+        &manager,
+    );
+    client.add_care_manager(&manager, &program_id, &care_manager);
+    client.set_care_gap_rules(
+        &manager,
+        &program_id,
+        &vec![
+            &env,
+            CareGapRule {
+                metric: MetricType::HbA1c,
+                interval_days: 90,
+                target_max_x100: 700,
+            },
+            CareGapRule {
+                metric: MetricType::SystolicBp,
+                interval_days: 30,
+                target_max_x100: 13_000,
+            },
+        ],
+    );
+
+    (env, admin, manager, care_manager, program_id, client)
+}
+
+fn enroll(
+    env: &Env,
+    client: &ChronicCareManagementContractClient,
+    manager: &Address,
+    care_manager: &Address,
+    program_id: u64,
+) -> Address {
+    let patient = Address::generate(env);
+    client.enroll_patient(manager, &program_id, &patient, care_manager);
+    patient
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_enrollment_lifecycle() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+    assert_eq!(
+        client.try_enroll_patient(&manager, &program_id, &patient, &care_manager),
+        Err(Ok(Error::AlreadyEnrolled))
+    );
+
+    client.disenroll_patient(&patient, &program_id, &patient);
+    assert!(!client.get_enrollment(&program_id, &patient).active);
+    assert_eq!(
+        client.try_record_metric(&patient, &program_id, &patient, &MetricType::HbA1c, &720),
+        Err(Ok(Error::NotEnrolled))
+    );
+
+    client.enroll_patient(&manager, &program_id, &patient, &care_manager);
+    assert!(client.get_enrollment(&program_id, &patient).active);
+}
+
+#[test]
+fn test_care_gaps_overdue_and_above_target() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+
+    // Nothing recorded yet: both rules are overdue.
+    assert_eq!(client.get_care_gaps(&program_id, &patient).len(), 2);
+
+    client.record_metric(&patient, &program_id, &patient, &MetricType::HbA1c, &810);
+    client.record_metric(
+        &care_manager,
+        &program_id,
+        &patient,
+        &MetricType::SystolicBp,
+        &12_400,
+    );
+    let gaps = client.get_care_gaps(&program_id, &patient);
+    assert_eq!(gaps.len(), 1);
+    assert_eq!(gaps.get(0).unwrap().kind, GapKind::AboveTarget);
+    assert_eq!(gaps.get(0).unwrap().metric, MetricType::HbA1c);
+
+    // After 31 days the BP reading is stale.
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + 31 * DAY);
+    client.record_metric(&patient, &program_id, &patient, &MetricType::HbA1c, &690);
+    let gaps = client.get_care_gaps(&program_id, &patient);
+    assert_eq!(gaps.len(), 1);
+    assert_eq!(gaps.get(0).unwrap().metric, MetricType::SystolicBp);
+    assert_eq!(gaps.get(0).unwrap().kind, GapKind::Overdue);
+}
+
+#[test]
+fn test_only_patient_or_care_manager_records_metrics() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_record_metric(
+            &stranger,
+            &program_id,
+            &patient,
+            &MetricType::Weight,
+            &8_240
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client
+            .get_metric_history(&program_id, &patient, &MetricType::Weight)
+            .len(),
+        0
+    );
+}
+
+#[test]
+fn test_outreach_tasks() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+    let now = env.ledger().timestamp();
+
+    let task_id = client.assign_outreach_task(
+        &manager,
+        &program_id,
+        &patient,
+        &care_manager,
+        &String::from_str(&env, "A1c overdue - schedule lab"),
+        &(now + 7 * DAY),
+    );
+    assert_eq!(client.get_open_tasks(&care_manager).len(), 1);
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_complete_outreach_task(&other, &task_id, &String::from_str(&env, "done")),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.complete_outreach_task(
+        &care_manager,
+        &task_id,
+        &String::from_str(&env, "Lab booked for next week"),
+    );
+    assert_eq!(client.get_task(&task_id).status, TaskStatus::Completed);
+    assert_eq!(client.get_open_tasks(&care_manager).len(), 0);
+}
+
+#[test]
+fn test_program_outcome_rollup() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let controlled = enroll(&env, &client, &manager, &care_manager, program_id);
+    let uncontrolled = enroll(&env, &client, &manager, &care_manager, program_id);
+    let unmeasured = enroll(&env, &client, &manager, &care_manager, program_id);
+    let left = enroll(&env, &client, &manager, &care_manager, program_id);
+
+    for (patient, a1c) in [(&controlled, 650), (&uncontrolled, 910), (&left, 600)] {
+        client.record_metric(patient, &program_id, patient, &MetricType::HbA1c, &a1c);
+    }
+    client.disenroll_patient(&manager, &program_id, &left);
+    client.assign_outreach_task(
+        &care_manager,
+        &program_id,
+        &unmeasured,
+        &care_manager,
+        &String::from_str(&env, "Welcome call"),
+        &(env.ledger().timestamp() + DAY),
+    );
+
+    let outcome = client.get_program_outcomes(&program_id, &MetricType::HbA1c);
+    assert_eq!(outcome.enrolled, 3);
+    assert_eq!(outcome.measured, 2);
+    assert_eq!(outcome.at_target, 1);
+    assert_eq!(outcome.average_latest_x100, 780);
+    assert_eq!(outcome.open_tasks, 1);
+}
+
+#[test]
+fn test_program_administration_requires_roles() {
+    let (env, admin, manager, care_manager, program_id, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(
+        client.try_create_program(
+            &manager,
+            &Condition::Diabetes,
+            &String::from_str(&env, "Shadow program"),
+            &manager,
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Only the program's own manager can change it
+    assert_eq!(
+        client.try_set_program_active(&care_manager, &program_id, &false),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_care_manager(&care_manager, &program_id, &care_manager),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_care_gap_rules(&admin, &program_id, &vec![&env]),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_care_gap_rules(
+            &manager,
+            &program_id,
+            &vec![
+                &env,
+                CareGapRule {
+                    metric: MetricType::Weight,
+                    interval_days: 0,
+                    target_max_x100: 10_000,
+                },
+            ],
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_program_active(&manager, &99, &false),
+        Err(Ok(Error::ProgramNotFound))
+    );
+    assert_eq!(client.try_get_program(&99), Err(Ok(Error::ProgramNotFound)));
+}
+
+#[test]
+fn test_enrollment_errors() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = Address::generate(&env);
+
+    assert_eq!(
+        client.try_enroll_patient(&care_manager, &program_id, &patient, &care_manager),
+        Err(Ok(Error::Unauthorized))
+    );
+    let unassigned = Address::generate(&env);
+    assert_eq!(
+        client.try_enroll_patient(&manager, &program_id, &patient, &unassigned),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_get_enrollment(&program_id, &patient),
+        Err(Ok(Error::NotEnrolled))
+    );
+    assert_eq!(
+        client.try_disenroll_patient(&patient, &program_id, &patient),
+        Err(Ok(Error::NotEnrolled))
+    );
+
+    let enrolled = enroll(&env, &client, &manager, &care_manager, program_id);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_disenroll_patient(&stranger, &program_id, &enrolled),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // Paused programs take no new enrollments
+    client.set_program_active(&manager, &program_id, &false);
+    assert_eq!(
+        client.try_enroll_patient(&manager, &program_id, &patient, &care_manager),
+        Err(Ok(Error::ProgramInactive))
+    );
+}
+
+#[test]
+fn test_metric_values_must_be_positive() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+    assert_eq!(
+        client.try_record_metric(&patient, &program_id, &patient, &MetricType::HbA1c, &0),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_record_metric(
+            &care_manager,
+            &program_id,
+            &patient,
+            &MetricType::Weight,
+            &-5
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_outreach_task_errors() {
+    let (env, _, manager, care_manager, program_id, client) = setup();
+    let patient = enroll(&env, &client, &manager, &care_manager, program_id);
+    let now = env.ledger().timestamp();
+    let reason = String::from_str(&env, "BP overdue");
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_assign_outreach_task(
+            &stranger,
+            &program_id,
+            &patient,
+            &care_manager,
+            &reason,
+            &(now + DAY),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_assign_outreach_task(
+            &manager,
+            &program_id,
+            &patient,
+            &stranger,
+            &reason,
+            &(now + DAY),
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_assign_outreach_task(
+            &manager,
+            &program_id,
+            &patient,
+            &care_manager,
+            &reason,
+            &now,
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_assign_outreach_task(
+            &manager,
+            &program_id,
+            &stranger,
+            &care_manager,
+            &reason,
+            &(now + DAY),
+        ),
+        Err(Ok(Error::NotEnrolled))
+    );
+
+    let task_id = client.assign_outreach_task(
+        &manager,
+        &program_id,
+        &patient,
+        &care_manager,
+        &reason,
+        &(now + DAY),
+    );
+    assert_eq!(
+        client.try_cancel_outreach_task(&care_manager, &task_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.complete_outreach_task(&care_manager, &task_id, &String::from_str(&env, "Called"));
+    assert_eq!(
+        client.try_complete_outreach_task(
+            &care_manager,
+            &task_id,
+            &String::from_str(&env, "Again")
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_cancel_outreach_task(&manager, &task_id),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(client.try_get_task(&99), Err(Ok(Error::TaskNotFound)));
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, manager, _, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&manager), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&manager, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
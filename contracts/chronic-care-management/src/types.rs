@@ -0,0 +1,203 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, String};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    ProgramNotFound = 4,
+    ProgramInactive = 5,
+    AlreadyEnrolled = 6,
+    NotEnrolled = 7,
+    TaskNotFound = 8,
+    InvalidState = 9,
+    InvalidInput = 10,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Condition {
+    Diabetes,
+    HeartFailure,
+    Copd,
+    Hypertension,
+    ChronicKidneyDisease,
+}
+
+/// Tracked measurements. Values are stored ×100 (A1c 7.25% -> 725,
+/// weight 82.4 kg -> 8240, BP 132 mmHg -> 13200).
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetricType {
+    HbA1c,
+    SystolicBp,
+    DiastolicBp,
+    Weight,
+    Egfr,
+    OxygenSaturation,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GapKind {
+    /// No reading within the rule's interval.
+    Overdue,
+    /// Latest reading is above the rule's target.
+    AboveTarget,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TaskStatus {
+    Open,
+    Completed,
+    Cancelled,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Program {
+    pub program_id: u64,
+    pub condition: Condition,
+    pub name: String,
+    pub manager: Address,
+    pub active: bool,
+    pub created_at: u64,
+}
+
+/// A program-level rule: `metric` must be measured at least every
+/// `interval_days` and, when `target_max_x100` is non-zero, stay at or below it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareGapRule {
+    pub metric: MetricType,
+    pub interval_days: u32,
+    pub target_max_x100: i64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Enrollment {
+    pub program_id: u64,
+    pub patient_id: Address,
+    pub care_manager: Address,
+    pub enrolled_at: u64,
+    pub active: bool,
+    pub disenrolled_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricReading {
+    pub metric: MetricType,
+    pub value_x100: i64,
+    pub recorded_by: Address,
+    pub recorded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareGap {
+    pub metric: MetricType,
+    pub kind: GapKind,
+    /// Zero when the metric has never been recorded.
+    pub last_recorded_at: u64,
+    pub last_value_x100: i64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutreachTask {
+    pub task_id: u64,
+    pub program_id: u64,
+    pub patient_id: Address,
+    pub care_manager: Address,
+    pub reason: String,
+    pub due_at: u64,
+    pub status: TaskStatus,
+    pub created_at: u64,
+    pub closed_at: u64,
+    pub outcome_note: Option<String>,
+}
+
+/// Program rollup for one metric across active enrollees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgramOutcome {
+    pub program_id: u64,
+    pub metric: MetricType,
+    pub enrolled: u32,
+    pub measured: u32,
+    pub at_target: u32,
+    pub average_latest_x100: i64,
+    pub open_tasks: u32,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientEnrolled {
+    #[topic]
+    pub program_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub care_manager: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutreachAssigned {
+    #[topic]
+    pub task_id: u64,
+    #[topic]
+    pub care_manager: Address,
+    pub patient_id: Address,
+    pub due_at: u64,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    ProgramCounter,
+    /// program_id -> Program
+    Program(u64),
+    /// program_id -> Vec<CareGapRule>
+    GapRules(u64),
+    /// program_id -> Vec<Address> (every patient ever enrolled)
+    ProgramPatients(u64),
+    /// (program_id, care_manager) -> bool
+    CareManager(u64, Address),
+    /// (program_id, patient) -> Enrollment
+    Enrollment(u64, Address),
+    /// (program_id, patient) -> Vec<MetricReading>
+    Readings(u64, Address),
+    TaskCounter,
+    /// task_id -> OutreachTask
+    Task(u64),
+    /// care_manager -> Vec<u64> (task ids)
+    ManagerTasks(Address),
+    /// program_id -> Vec<u64> (task ids)
+    ProgramTasks(u64),
+}
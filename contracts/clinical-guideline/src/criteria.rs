@@ -0,0 +1,103 @@
+use soroban_sdk::{Env, String, Symbol, Vec, contracttype};
+
+/// Kind of applicability criterion attached to a guideline
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CriterionKind {
+    AgeRange,     // min_value..=max_value years
+    Diagnosis,    // code must be among the patient's diagnosis codes
+    LabThreshold, // lab `code` value within min_value..=max_value
+    Comorbidity,  // code present (required = true) or absent (required = false)
+}
+
+/// A single structured applicability criterion
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Criterion {
+    pub criterion_id: Symbol,
+    pub kind: CriterionKind,
+    pub code: Option<String>,
+    pub min_value: Option<i64>,
+    pub max_value: Option<i64>,
+    pub required: bool,
+}
+
+/// A lab result in the unit the guideline criteria are authored in
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LabValue {
+    pub code: String,
+    pub value: i64,
+}
+
+/// Patient parameters attested by the evaluating provider
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientParameters {
+    pub age_years: u32,
+    pub diagnosis_codes: Vec<String>,
+    pub lab_values: Vec<LabValue>,
+    pub comorbidities: Vec<String>,
+}
+
+fn in_range(value: i64, min: Option<i64>, max: Option<i64>) -> bool {
+    min.is_none_or(|m| value >= m) && max.is_none_or(|m| value <= m)
+}
+
+fn matches(criterion: &Criterion, params: &PatientParameters) -> bool {
+    match criterion.kind {
+        CriterionKind::AgeRange => in_range(
+            params.age_years as i64,
+            criterion.min_value,
+            criterion.max_value,
+        ),
+        CriterionKind::Diagnosis => match &criterion.code {
+            Some(code) => params.diagnosis_codes.contains(code),
+            None => false,
+        },
+        CriterionKind::LabThreshold => match &criterion.code {
+            Some(code) => params
+                .lab_values
+                .iter()
+                .find(|lab| lab.code == *code)
+                .is_some_and(|lab| in_range(lab.value, criterion.min_value, criterion.max_value)),
+            None => false,
+        },
+        CriterionKind::Comorbidity => match &criterion.code {
+            Some(code) => params.comorbidities.contains(code) == criterion.required,
+            None => false,
+        },
+    }
+}
+
+/// Evaluate criteria against patient parameters, returning (matched, failed) criterion ids
+pub fn evaluate(
+    env: &Env,
+    criteria: &Vec<Criterion>,
+    params: &PatientParameters,
+) -> (Vec<Symbol>, Vec<Symbol>) {
+    let mut matched = Vec::new(env);
+    let mut failed = Vec::new(env);
+
+    for criterion in criteria.iter() {
+        if matches(&criterion, params) {
+            matched.push_back(criterion.criterion_id);
+        } else {
+            failed.push_back(criterion.criterion_id);
+        }
+    }
+
+    (matched, failed)
+}
+
+/// Reject criteria that cannot be evaluated
+pub fn is_valid(criterion: &Criterion) -> bool {
+    match criterion.kind {
+        CriterionKind::AgeRange => criterion.min_value.is_some() || criterion.max_value.is_some(),
+        CriterionKind::Diagnosis | CriterionKind::Comorbidity => criterion.code.is_some(),
+        CriterionKind::LabThreshold => {
+            criterion.code.is_some()
+                && (criterion.min_value.is_some() || criterion.max_value.is_some())
+        }
+    }
+}
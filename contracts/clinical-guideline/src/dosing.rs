@@ -0,0 +1,68 @@
+use soroban_sdk::{Env, String, Symbol, Vec, contracttype};
+
+/// Renal tier applied when eGFR is below `below_egfr` (mL/min/1.73m2)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RenalTier {
+    pub below_egfr: u32,
+    pub dose_percent: u32,
+}
+
+/// Hepatic adjustment keyed by Child-Pugh class (A, B, C)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct HepaticTier {
+    pub child_pugh: Symbol,
+    pub dose_percent: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DosingRule {
+    pub medication: String,
+    pub dose_mcg_per_kg: u64,
+    pub max_dose_mcg: u64,
+    pub frequency: String,
+    pub route: Symbol,
+    pub duration_days: Option<u64>,
+    pub renal_tiers: Vec<RenalTier>,
+    pub hepatic_tiers: Vec<HepaticTier>,
+    pub monitoring_required: Vec<String>,
+}
+
+pub fn is_valid(rule: &DosingRule) -> bool {
+    rule.dose_mcg_per_kg > 0
+        && rule.max_dose_mcg > 0
+        && rule.renal_tiers.iter().all(|t| t.dose_percent <= 100)
+        && rule.hepatic_tiers.iter().all(|t| t.dose_percent <= 100)
+}
+
+/// Weight-based dose in micrograms, capped at the rule maximum
+pub fn base_dose_mcg(rule: &DosingRule, weight_grams: u64) -> u64 {
+    let dose = weight_grams.saturating_mul(rule.dose_mcg_per_kg) / 1000;
+    dose.min(rule.max_dose_mcg)
+}
+
+/// Most restrictive renal tier the eGFR falls into
+pub fn renal_percent(rule: &DosingRule, egfr: u32) -> Option<u32> {
+    rule.renal_tiers
+        .iter()
+        .filter(|t| egfr < t.below_egfr)
+        .min_by_key(|t| t.below_egfr)
+        .map(|t| t.dose_percent)
+}
+
+pub fn hepatic_percent(rule: &DosingRule, child_pugh: &Symbol) -> Option<u32> {
+    rule.hepatic_tiers
+        .iter()
+        .find(|t| t.child_pugh == *child_pugh)
+        .map(|t| t.dose_percent)
+}
+
+pub fn apply_percent(dose: u64, percent: u32) -> u64 {
+    dose * percent as u64 / 100
+}
+
+pub fn unit(env: &Env) -> Symbol {
+    Symbol::new(env, "mcg")
+}
@@ -0,0 +1,150 @@
+use soroban_sdk::{Address, Env, Symbol, contractevent, contracttype};
+
+/// Vital-sign snapshot. Measurements are fixed-point ×100
+/// (e.g. 37.25 °C is 3725, SpO2 96% is 9600).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VitalSigns {
+    pub respiratory_rate_x100: u32,
+    pub spo2_x100: u32,
+    pub on_supplemental_o2: bool,
+    pub temperature_x100: i32,
+    pub systolic_bp_x100: u32,
+    pub heart_rate_x100: u32,
+    pub consciousness: Symbol, // alert, confusion, voice, pain, unresponsive
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum EarlyWarningBand {
+    Low = 0,
+    LowMedium = 1, // a single parameter scored 3
+    Medium = 2,
+    High = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EarlyWarningScore {
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub recorded_at: u64,
+    pub news2_score: u32,
+    pub qsofa_score: u32,
+    pub band: EarlyWarningBand,
+    pub escalated: bool,
+}
+
+/// Escalate once `consecutive_scores` snapshots in a row reach either threshold
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscalationThresholds {
+    pub news2_threshold: u32,
+    pub qsofa_threshold: u32,
+    pub consecutive_scores: u32,
+}
+
+#[contractevent]
+pub struct EarlyWarningEscalation {
+    #[topic]
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub news2_score: u32,
+    pub qsofa_score: u32,
+    pub band: EarlyWarningBand,
+    pub consecutive_scores: u32,
+}
+
+pub fn default_thresholds() -> EscalationThresholds {
+    EscalationThresholds {
+        news2_threshold: 5,
+        qsofa_threshold: 2,
+        consecutive_scores: 2,
+    }
+}
+
+pub fn thresholds_valid(thresholds: &EscalationThresholds) -> bool {
+    thresholds.news2_threshold > 0
+        && thresholds.qsofa_threshold > 0
+        && thresholds.consecutive_scores > 0
+}
+
+fn is_alert(env: &Env, consciousness: &Symbol) -> Option<bool> {
+    if *consciousness == Symbol::new(env, "alert") {
+        return Some(true);
+    }
+    ["confusion", "voice", "pain", "unresponsive"]
+        .iter()
+        .any(|level| *consciousness == Symbol::new(env, level))
+        .then_some(false)
+}
+
+pub fn is_valid(env: &Env, vitals: &VitalSigns) -> bool {
+    vitals.spo2_x100 <= 10_000 && is_alert(env, &vitals.consciousness).is_some()
+}
+
+/// Score from the first band whose inclusive upper bound the value falls under
+fn banded<T: PartialOrd + Copy>(value: T, bands: &[(T, u32)], above: u32) -> u32 {
+    bands
+        .iter()
+        .find(|(upper, _)| value <= *upper)
+        .map(|(_, points)| *points)
+        .unwrap_or(above)
+}
+
+/// NEWS2 aggregate (SpO2 scale 1) and whether any single parameter scored 3
+pub fn news2(env: &Env, vitals: &VitalSigns) -> (u32, bool) {
+    let alert = is_alert(env, &vitals.consciousness).unwrap_or(false);
+    let parts = [
+        banded(
+            vitals.respiratory_rate_x100,
+            &[(800, 3), (1100, 1), (2000, 0), (2400, 2)],
+            3,
+        ),
+        banded(vitals.spo2_x100, &[(9100, 3), (9300, 2), (9500, 1)], 0),
+        if vitals.on_supplemental_o2 { 2 } else { 0 },
+        banded(
+            vitals.temperature_x100,
+            &[(3500, 3), (3600, 1), (3800, 0), (3900, 1)],
+            2,
+        ),
+        banded(
+            vitals.systolic_bp_x100,
+            &[(9000, 3), (10000, 2), (11000, 1), (21900, 0)],
+            3,
+        ),
+        banded(
+            vitals.heart_rate_x100,
+            &[(4000, 3), (5000, 1), (9000, 0), (11000, 1), (13000, 2)],
+            3,
+        ),
+        if alert { 0 } else { 3 },
+    ];
+    (parts.iter().sum(), parts.contains(&3))
+}
+
+/// qSOFA: RR ≥ 22, systolic BP ≤ 100, altered mentation
+pub fn qsofa(env: &Env, vitals: &VitalSigns) -> u32 {
+    let alert = is_alert(env, &vitals.consciousness).unwrap_or(false);
+    (vitals.respiratory_rate_x100 >= 2200) as u32
+        + (vitals.systolic_bp_x100 <= 10000) as u32
+        + (!alert) as u32
+}
+
+pub fn band(news2_score: u32, single_parameter_red: bool) -> EarlyWarningBand {
+    if news2_score >= 7 {
+        EarlyWarningBand::High
+    } else if news2_score >= 5 {
+        EarlyWarningBand::Medium
+    } else if single_parameter_red {
+        EarlyWarningBand::LowMedium
+    } else {
+        EarlyWarningBand::Low
+    }
+}
+
+pub fn crosses(score: &EarlyWarningScore, thresholds: &EscalationThresholds) -> bool {
+    score.news2_score >= thresholds.news2_threshold
+        || score.qsofa_score >= thresholds.qsofa_threshold
+}
@@ -0,0 +1,96 @@
+use soroban_sdk::{BytesN, Env, String, Symbol, Vec, contracttype};
+
+use crate::DataKey;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum InteractionSeverity {
+    Minor = 1,
+    Moderate = 2,
+    Major = 3,
+    Contraindicated = 4,
+}
+
+/// Interaction between two drug classes. The pair is unordered.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DrugInteraction {
+    pub class_a: Symbol,
+    pub class_b: Symbol,
+    pub severity: InteractionSeverity,
+    pub mechanism_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InteractionWarning {
+    pub medication_a: String,
+    pub medication_b: String,
+    pub class_a: Symbol,
+    pub class_b: Symbol,
+    pub severity: InteractionSeverity,
+    pub mechanism_hash: BytesN<32>,
+}
+
+/// Storage key for a class pair, independent of argument order
+pub fn key(class_a: &Symbol, class_b: &Symbol) -> DataKey {
+    if class_a <= class_b {
+        DataKey::DrugInteraction(class_a.clone(), class_b.clone())
+    } else {
+        DataKey::DrugInteraction(class_b.clone(), class_a.clone())
+    }
+}
+
+pub fn load(env: &Env, class_a: &Symbol, class_b: &Symbol) -> Option<DrugInteraction> {
+    env.storage().persistent().get(&key(class_a, class_b))
+}
+
+/// Insert keeping the most severe warnings first
+fn insert_ranked(warnings: &mut Vec<InteractionWarning>, warning: InteractionWarning) {
+    let index = warnings
+        .iter()
+        .position(|w| w.severity < warning.severity)
+        .unwrap_or(warnings.len() as usize);
+    warnings.insert(index as u32, warning);
+}
+
+/// Warnings for every pair of classified medications with a registered
+/// interaction, most severe first. Unclassified medications are skipped.
+pub fn check(env: &Env, medications: &Vec<String>) -> Vec<InteractionWarning> {
+    let mut classified: Vec<(String, Symbol)> = Vec::new(env);
+    for medication in medications.iter() {
+        let class: Option<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DrugClass(medication.clone()));
+        if let Some(class) = class {
+            classified.push_back((medication, class));
+        }
+    }
+
+    let mut warnings = Vec::new(env);
+    for i in 0..classified.len() {
+        let (medication_a, class_a) = classified.get_unchecked(i);
+        for j in (i + 1)..classified.len() {
+            let (medication_b, class_b) = classified.get_unchecked(j);
+            if medication_a == medication_b {
+                continue;
+            }
+            if let Some(interaction) = load(env, &class_a, &class_b) {
+                insert_ranked(
+                    &mut warnings,
+                    InteractionWarning {
+                        medication_a: medication_a.clone(),
+                        medication_b,
+                        class_a: class_a.clone(),
+                        class_b,
+                        severity: interaction.severity,
+                        mechanism_hash: interaction.mechanism_hash,
+                    },
+                );
+            }
+        }
+    }
+    warnings
+}
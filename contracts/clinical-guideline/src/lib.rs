@@ -3,6 +3,39 @@ use soroban_sdk::{
     Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
 };
 
+mod criteria;
+mod dosing;
+mod early_warning;
+mod interactions;
+mod order_sets;
+mod pathways;
+mod preventive;
+mod reminders;
+mod risk;
+mod subscriptions;
+
+pub use criteria::{Criterion, CriterionKind, LabValue, PatientParameters};
+pub use dosing::{DosingRule, HepaticTier, RenalTier};
+pub use early_warning::{
+    EarlyWarningBand, EarlyWarningEscalation, EarlyWarningScore, EscalationThresholds, VitalSigns,
+};
+pub use interactions::{DrugInteraction, InteractionSeverity, InteractionWarning};
+pub use order_sets::{
+    OrderChecklist, OrderChecklistItem, OrderItemKind, OrderSet, OrderSetCompliance, OrderSetItem,
+};
+pub use pathways::{
+    CarePathway, PathwayBranch, PathwayStep, PathwayVarianceReport, PatientPathway, StepCompletion,
+};
+pub use preventive::{PreventiveCareDue, PreventiveService, PreventiveStatus, RiskModifier};
+pub use reminders::{Reminder, ReminderStatus};
+pub use risk::{RiskAssessment, RiskBand, RiskCalculator, RiskComponent};
+pub use subscriptions::{
+    GuidelineChange, GuidelineUpdate, GuidelineUpdateNotice, SubscriptionTarget,
+};
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 // --- Custom Error Types ---
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -11,6 +44,19 @@ pub enum Error {
     NotAuthorized = 1,
     GuidelineNotFound = 2,
     InvalidInput = 3,
+    AlreadyInitialized = 4,
+    CalculatorNotFound = 5,
+    DrugNotFound = 6,
+    ReminderNotFound = 7,
+    InvalidReminderState = 8,
+    PathwayNotFound = 9,
+    PathwayStepMismatch = 10,
+    ServiceNotFound = 11,
+    GuidelineRetired = 12,
+    UpdateNotFound = 13,
+    InteractionNotFound = 14,
+    OrderSetNotFound = 15,
+    OrderItemNotFound = 16,
 }
 
 // --- Data Structures ---
@@ -23,76 +69,308 @@ pub struct GuidelineRecommendation {
     pub strength: Symbol,
     pub evidence_level: Symbol,
     pub alternative_options: Vec<String>,
+    pub matched_criteria: Vec<Symbol>,
+    pub failed_criteria: Vec<Symbol>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Guideline {
+    pub guideline_id: String,
+    pub condition: String,
+    pub criteria_hash: BytesN<32>,
+    pub recommendation_hash: BytesN<32>,
+    pub evidence_level: Symbol,
+    pub criteria: Vec<Criterion>,
+}
+
+// --- Storage Keys ---
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Admin,
+    Guideline(String),
+    RiskCalculator(Symbol),
+    DosingRule(String),
+    ReminderCounter,
+    Reminder(u64),
+    PatientReminders(Address),
+    ProviderReminders(Address),
+    CarePathway(Symbol),
+    ConditionPathways(String),
+    PathwayInstanceCounter,
+    PathwayInstance(u64),
+    PreventiveSchedule,
+    PreventiveHistory(Address, Symbol),
+    GuidelineVersion(String),
+    GuidelineRetired(String),
+    GuidelineSubscribers(String),
+    ConditionSubscribers(String),
+    ProviderUpdates(Address),
+    DrugClass(String),
+    DrugInteraction(Symbol, Symbol),
+    OrderSet(Symbol),
+    ConditionOrderSets(String),
+    OrderChecklistCounter,
+    OrderChecklist(u64),
+    OrderSetChecklists(Symbol),
+    EscalationThresholds,
+    EarlyWarningHistory(Address),
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DosageRecommendation {
     pub medication: String,
-    pub recommended_dose: String,
+    pub recommended_dose: u64,
+    pub dose_unit: Symbol,
     pub frequency: String,
     pub route: Symbol,
     pub duration: Option<u64>,
     pub renal_adjustment: bool,
+    pub hepatic_adjustment: bool,
     pub monitoring_required: Vec<String>,
 }
 
-// Placeholder for logic-heavy structures
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
-pub struct CarePathway {
-    pub condition: String,
-    pub steps: Vec<String>,
-}
-
 #[contract]
 pub struct ClinicalGuidelineContract;
 
 #[contractimpl]
 impl ClinicalGuidelineContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn register_clinical_guideline(
         env: Env,
         admin: Address,
         guideline_id: String,
-        _condition: String,
+        condition: String,
         criteria_hash: BytesN<32>,
-        _recommendation_hash: BytesN<32>,
-        _evidence_level: Symbol,
+        recommendation_hash: BytesN<32>,
+        evidence_level: Symbol,
     ) -> Result<(), Error> {
-        admin.require_auth();
-        // Use guideline_id as the storage key
+        Self::require_admin(&env, &admin)?;
+
+        // A new version keeps the structured criteria attached to the previous one
+        let criteria = Self::get_guideline(env.clone(), guideline_id.clone())
+            .map(|existing| existing.criteria)
+            .unwrap_or(Vec::new(&env));
+        let guideline = Guideline {
+            guideline_id: guideline_id.clone(),
+            condition: condition.clone(),
+            criteria_hash,
+            recommendation_hash,
+            evidence_level,
+            criteria,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Guideline(guideline_id.clone()), &guideline);
+
+        // Re-registering an id publishes a new version of that guideline
+        let version = Self::get_guideline_version(env.clone(), guideline_id.clone()) + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::GuidelineVersion(guideline_id.clone()), &version);
         env.storage()
             .persistent()
-            .set(&guideline_id, &criteria_hash);
+            .remove(&DataKey::GuidelineRetired(guideline_id.clone()));
+
+        subscriptions::notify(
+            &env,
+            &GuidelineUpdate {
+                guideline_id,
+                condition,
+                version,
+                change: GuidelineChange::Registered,
+                published_at: env.ledger().timestamp(),
+            },
+        );
         Ok(())
     }
 
+    /// Withdraw the current version of a guideline and notify subscribers
+    pub fn retire_guideline(env: Env, admin: Address, guideline_id: String) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let guideline = Self::get_guideline(env.clone(), guideline_id.clone())?;
+        if Self::is_guideline_retired(env.clone(), guideline_id.clone()) {
+            return Err(Error::GuidelineRetired);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::GuidelineRetired(guideline_id.clone()), &true);
+
+        subscriptions::notify(
+            &env,
+            &GuidelineUpdate {
+                version: Self::get_guideline_version(env.clone(), guideline_id.clone()),
+                guideline_id,
+                condition: guideline.condition,
+                change: GuidelineChange::Retired,
+                published_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    /// Current version of a guideline, or 0 if it was never registered
+    pub fn get_guideline_version(env: Env, guideline_id: String) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GuidelineVersion(guideline_id))
+            .unwrap_or(0)
+    }
+
+    pub fn is_guideline_retired(env: Env, guideline_id: String) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GuidelineRetired(guideline_id))
+            .unwrap_or(false)
+    }
+
+    /// Follow new versions and retirements of one guideline, or of every
+    /// guideline registered for a condition
+    pub fn subscribe_to_guideline(
+        env: Env,
+        provider: Address,
+        target: SubscriptionTarget,
+    ) -> Result<(), Error> {
+        provider.require_auth();
+        if let SubscriptionTarget::Guideline(guideline_id) = &target {
+            Self::get_guideline(env.clone(), guideline_id.clone())?;
+        }
+        subscriptions::subscribe(&env, &target, &provider);
+        Ok(())
+    }
+
+    pub fn unsubscribe_from_guideline(
+        env: Env,
+        provider: Address,
+        target: SubscriptionTarget,
+    ) -> Result<(), Error> {
+        provider.require_auth();
+        if !subscriptions::unsubscribe(&env, &target, &provider) {
+            return Err(Error::InvalidInput);
+        }
+        Ok(())
+    }
+
+    /// Guideline changes the provider has not yet acknowledged, oldest first
+    pub fn get_unacknowledged_updates(env: Env, provider: Address) -> Vec<GuidelineUpdate> {
+        subscriptions::unacknowledged(&env, &provider)
+    }
+
+    pub fn acknowledge_guideline_update(
+        env: Env,
+        provider: Address,
+        guideline_id: String,
+        version: u32,
+    ) -> Result<(), Error> {
+        provider.require_auth();
+        if !subscriptions::acknowledge(&env, &provider, &guideline_id, version) {
+            return Err(Error::UpdateNotFound);
+        }
+        Ok(())
+    }
+
+    /// Attach structured applicability criteria to a registered guideline
+    pub fn set_guideline_criteria(
+        env: Env,
+        admin: Address,
+        guideline_id: String,
+        criteria: Vec<Criterion>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let key = DataKey::Guideline(guideline_id);
+        let mut guideline: Guideline = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(Error::GuidelineNotFound)?;
+
+        for criterion in criteria.iter() {
+            if !criteria::is_valid(&criterion) {
+                return Err(Error::InvalidInput);
+            }
+        }
+
+        guideline.criteria = criteria;
+        env.storage().persistent().set(&key, &guideline);
+        Ok(())
+    }
+
+    pub fn get_guideline(env: Env, guideline_id: String) -> Result<Guideline, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Guideline(guideline_id))
+            .ok_or(Error::GuidelineNotFound)
+    }
+
+    /// Evaluate a guideline's criteria against provider-attested patient parameters.
+    /// The guideline applies only when every criterion matches.
     pub fn evaluate_guideline(
         env: Env,
         _patient_id: Address,
-        _provider_id: Address,
+        provider_id: Address,
         guideline_id: String,
-        patient_data_hash: BytesN<32>,
+        params: PatientParameters,
     ) -> Result<GuidelineRecommendation, Error> {
-        // Retrieve stored criteria
-        let stored_hash: BytesN<32> = env
+        provider_id.require_auth();
+
+        let guideline: Guideline = env
             .storage()
             .persistent()
-            .get(&guideline_id)
+            .get(&DataKey::Guideline(guideline_id.clone()))
             .ok_or(Error::GuidelineNotFound)?;
+        if Self::is_guideline_retired(env.clone(), guideline_id.clone()) {
+            return Err(Error::GuidelineRetired);
+        }
 
-        let is_applicable = stored_hash == patient_data_hash;
+        let (matched_criteria, failed_criteria) =
+            criteria::evaluate(&env, &guideline.criteria, &params);
+        let is_applicable = !guideline.criteria.is_empty() && failed_criteria.is_empty();
 
         Ok(GuidelineRecommendation {
             guideline_id,
             applicable: is_applicable,
             recommendation: String::from_str(&env, "Follow Standard Protocol"),
             strength: Symbol::new(&env, "High"),
-            evidence_level: Symbol::new(&env, "Level_A"),
+            evidence_level: guideline.evidence_level,
             alternative_options: Vec::new(&env),
+            matched_criteria,
+            failed_criteria,
         })
     }
 
+    pub fn register_dosing_rule(env: Env, admin: Address, rule: DosingRule) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !dosing::is_valid(&rule) {
+            return Err(Error::InvalidInput);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::DosingRule(rule.medication.clone()), &rule);
+        Ok(())
+    }
+
+    pub fn get_dosing_rule(env: Env, medication: String) -> Result<DosingRule, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DosingRule(medication))
+            .ok_or(Error::DrugNotFound)
+    }
+
+    /// Weight-based dose from the registry, reduced by the applicable renal
+    /// (eGFR) and hepatic (Child-Pugh) tiers.
     pub fn calculate_drug_dosage(
         env: Env,
         _patient_id: Address,
@@ -100,79 +378,799 @@ impl ClinicalGuidelineContract {
         weight_grams: u64, // Used u64 for fixed-point math instead of f32
         _age: u32,
         renal_function: Option<u32>,
+        hepatic_class: Option<Symbol>,
     ) -> Result<DosageRecommendation, Error> {
-        // Simple example: 5mg per kg (1000g)
-        let _dose_mg = (weight_grams * 5) / 1000;
-        let is_renal_impaired = renal_function.unwrap_or(100) < 60;
+        if weight_grams == 0 {
+            return Err(Error::InvalidInput);
+        }
+        let rule = Self::get_dosing_rule(env.clone(), medication)?;
+
+        let mut dose = dosing::base_dose_mcg(&rule, weight_grams);
+
+        let renal_percent = renal_function.and_then(|egfr| dosing::renal_percent(&rule, egfr));
+        if let Some(percent) = renal_percent {
+            dose = dosing::apply_percent(dose, percent);
+        }
+
+        let hepatic_percent = hepatic_class
+            .as_ref()
+            .and_then(|class| dosing::hepatic_percent(&rule, class));
+        if let Some(percent) = hepatic_percent {
+            dose = dosing::apply_percent(dose, percent);
+        }
 
         Ok(DosageRecommendation {
-            medication,
-            recommended_dose: String::from_str(&env, "5mg/kg"),
-            frequency: String::from_str(&env, "QD"),
-            route: Symbol::new(&env, "Oral"),
-            duration: Some(10),
-            renal_adjustment: is_renal_impaired,
-            monitoring_required: Vec::new(&env),
+            medication: rule.medication,
+            recommended_dose: dose,
+            dose_unit: dosing::unit(&env),
+            frequency: rule.frequency,
+            route: rule.route,
+            duration: rule.duration_days,
+            renal_adjustment: renal_percent.is_some(),
+            hepatic_adjustment: hepatic_percent.is_some(),
+            monitoring_required: rule.monitoring_required,
         })
     }
 
+    /// Assign a medication to the drug class used for interaction checks
+    pub fn register_drug_class(
+        env: Env,
+        admin: Address,
+        medication: String,
+        drug_class: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::DrugClass(medication), &drug_class);
+        Ok(())
+    }
+
+    pub fn get_drug_class(env: Env, medication: String) -> Option<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DrugClass(medication))
+    }
+
+    /// Add or replace the interaction between two drug classes
+    pub fn register_drug_interaction(
+        env: Env,
+        admin: Address,
+        interaction: DrugInteraction,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage().persistent().set(
+            &interactions::key(&interaction.class_a, &interaction.class_b),
+            &interaction,
+        );
+        Ok(())
+    }
+
+    pub fn remove_drug_interaction(
+        env: Env,
+        admin: Address,
+        class_a: Symbol,
+        class_b: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        let key = interactions::key(&class_a, &class_b);
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::InteractionNotFound);
+        }
+        env.storage().persistent().remove(&key);
+        Ok(())
+    }
+
+    pub fn get_drug_interaction(
+        env: Env,
+        class_a: Symbol,
+        class_b: Symbol,
+    ) -> Result<DrugInteraction, Error> {
+        interactions::load(&env, &class_a, &class_b).ok_or(Error::InteractionNotFound)
+    }
+
+    /// Interaction warnings across a medication list, most severe first
+    pub fn check_drug_interactions(env: Env, med_list: Vec<String>) -> Vec<InteractionWarning> {
+        interactions::check(&env, &med_list)
+    }
+
+    /// Register or replace a risk calculator; overrides a built-in of the same name
+    pub fn register_risk_calculator(
+        env: Env,
+        admin: Address,
+        calculator: RiskCalculator,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !risk::is_valid(&calculator) {
+            return Err(Error::InvalidInput);
+        }
+        env.storage().persistent().set(
+            &DataKey::RiskCalculator(calculator.name.clone()),
+            &calculator,
+        );
+        Ok(())
+    }
+
+    pub fn get_risk_calculator(env: Env, name: Symbol) -> Result<RiskCalculator, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RiskCalculator(name.clone()))
+            .or_else(|| risk::builtin(&env, &name))
+            .ok_or(Error::CalculatorNotFound)
+    }
+
+    /// Score a calculator from the components present for the patient
     pub fn assess_risk_score(
         env: Env,
         _patient_id: Address,
-        _risk_calculator: Symbol,
-        input_parameters: Vec<i32>,
-    ) -> Result<i32, Error> {
-        let mut total_score: i32 = 0;
-        for val in input_parameters.iter() {
-            total_score += val;
+        risk_calculator: Symbol,
+        present_components: Vec<Symbol>,
+    ) -> Result<RiskAssessment, Error> {
+        let calculator = Self::get_risk_calculator(env.clone(), risk_calculator)?;
+        risk::assess(&env, &calculator, &present_components).ok_or(Error::InvalidInput)
+    }
+
+    pub fn set_escalation_thresholds(
+        env: Env,
+        admin: Address,
+        thresholds: EscalationThresholds,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !early_warning::thresholds_valid(&thresholds) {
+            return Err(Error::InvalidInput);
         }
-        Ok(total_score)
+        env.storage()
+            .instance()
+            .set(&DataKey::EscalationThresholds, &thresholds);
+        Ok(())
     }
 
+    pub fn get_escalation_thresholds(env: Env) -> EscalationThresholds {
+        env.storage()
+            .instance()
+            .get(&DataKey::EscalationThresholds)
+            .unwrap_or(early_warning::default_thresholds())
+    }
+
+    /// Score a vital-sign snapshot (NEWS2 and qSOFA) and append it to the
+    /// patient's history. Escalates when the configured number of
+    /// consecutive scores reach a threshold.
+    pub fn submit_vital_signs(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        vitals: VitalSigns,
+    ) -> Result<EarlyWarningScore, Error> {
+        provider_id.require_auth();
+        if !early_warning::is_valid(&env, &vitals) {
+            return Err(Error::InvalidInput);
+        }
+
+        let (news2_score, single_parameter_red) = early_warning::news2(&env, &vitals);
+        let mut score = EarlyWarningScore {
+            patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
+            recorded_at: env.ledger().timestamp(),
+            news2_score,
+            qsofa_score: early_warning::qsofa(&env, &vitals),
+            band: early_warning::band(news2_score, single_parameter_red),
+            escalated: false,
+        };
+
+        let key = DataKey::EarlyWarningHistory(patient_id.clone());
+        let mut history: Vec<EarlyWarningScore> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+
+        // Escalate on the snapshot that completes the run, not every one after it
+        let thresholds = Self::get_escalation_thresholds(env.clone());
+        if early_warning::crosses(&score, &thresholds) {
+            let mut run = 1u32;
+            for previous in history.iter().rev() {
+                if !early_warning::crosses(&previous, &thresholds) {
+                    break;
+                }
+                run += 1;
+            }
+            if run == thresholds.consecutive_scores {
+                score.escalated = true;
+                EarlyWarningEscalation {
+                    patient_id,
+                    provider_id,
+                    news2_score: score.news2_score,
+                    qsofa_score: score.qsofa_score,
+                    band: score.band,
+                    consecutive_scores: run,
+                }
+                .publish(&env);
+            }
+        }
+
+        history.push_back(score.clone());
+        env.storage().persistent().set(&key, &history);
+        Ok(score)
+    }
+
+    pub fn get_early_warning_history(env: Env, patient_id: Address) -> Vec<EarlyWarningScore> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EarlyWarningHistory(patient_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Register or replace a named pathway and index it under its condition
+    pub fn register_care_pathway(
+        env: Env,
+        admin: Address,
+        pathway: CarePathway,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !pathways::is_valid(&pathway) {
+            return Err(Error::InvalidInput);
+        }
+
+        let index_key = DataKey::ConditionPathways(pathway.condition.clone());
+        let mut ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if !ids.contains(&pathway.pathway_id) {
+            ids.push_back(pathway.pathway_id.clone());
+            env.storage().persistent().set(&index_key, &ids);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::CarePathway(pathway.pathway_id.clone()), &pathway);
+        Ok(())
+    }
+
+    pub fn get_care_pathway(env: Env, pathway_id: Symbol) -> Result<CarePathway, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CarePathway(pathway_id))
+            .ok_or(Error::PathwayNotFound)
+    }
+
+    /// First pathway registered for the condition
     pub fn suggest_care_pathway(
         env: Env,
         _patient_id: Address,
         condition: String,
         _current_treatment: Vec<String>,
     ) -> Result<CarePathway, Error> {
-        let mut steps = Vec::new(&env);
-        steps.push_back(String::from_str(&env, "Initial Assessment"));
-        steps.push_back(String::from_str(&env, "Lab Tests"));
+        let ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ConditionPathways(condition))
+            .unwrap_or(Vec::new(&env));
+        let pathway_id = ids.get(0).ok_or(Error::PathwayNotFound)?;
+        Self::get_care_pathway(env, pathway_id)
+    }
+
+    /// Enroll a patient on a pathway; the first step becomes current
+    pub fn start_care_pathway(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        pathway_id: Symbol,
+    ) -> Result<u64, Error> {
+        provider_id.require_auth();
+        let pathway = Self::get_care_pathway(env.clone(), pathway_id.clone())?;
+        let first = pathway.steps.get(0).ok_or(Error::InvalidInput)?;
+
+        let instance_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::PathwayInstanceCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::PathwayInstanceCounter, &instance_id);
 
-        Ok(CarePathway { condition, steps })
+        let now = env.ledger().timestamp();
+        let instance = PatientPathway {
+            instance_id,
+            patient_id,
+            provider_id,
+            pathway_id,
+            started_at: now,
+            current_step: Some(first.step_id),
+            current_step_due: Some(now + first.expected_interval),
+            completions: Vec::new(&env),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::PathwayInstance(instance_id), &instance);
+        Ok(instance_id)
+    }
+
+    pub fn get_patient_pathway(env: Env, instance_id: u64) -> Result<PatientPathway, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PathwayInstance(instance_id))
+            .ok_or(Error::PathwayNotFound)
+    }
+
+    /// Complete the current step and advance along the matching branch
+    pub fn complete_pathway_step(
+        env: Env,
+        instance_id: u64,
+        provider_id: Address,
+        step_id: Symbol,
+        outcome: Option<Symbol>,
+    ) -> Result<Option<Symbol>, Error> {
+        provider_id.require_auth();
+        let mut instance = Self::get_patient_pathway(env.clone(), instance_id)?;
+        if provider_id != instance.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if instance.current_step != Some(step_id.clone()) {
+            return Err(Error::PathwayStepMismatch);
+        }
+
+        let pathway = Self::get_care_pathway(env.clone(), instance.pathway_id.clone())?;
+        let (index, step) =
+            pathways::find_step(&pathway, &step_id).ok_or(Error::PathwayStepMismatch)?;
+
+        let now = env.ledger().timestamp();
+        let expected_by = instance.current_step_due.unwrap_or(now);
+        instance.completions.push_back(StepCompletion {
+            step_id,
+            completed_by: provider_id,
+            completed_at: now,
+            expected_by,
+            variance_seconds: now as i64 - expected_by as i64,
+            outcome: outcome.clone(),
+        });
+
+        let next = pathways::next_step(&pathway, index, &step, &outcome);
+        instance.current_step_due = next.as_ref().and_then(|id| {
+            pathways::find_step(&pathway, id).map(|(_, s)| now + s.expected_interval)
+        });
+        instance.current_step = next.clone();
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::PathwayInstance(instance_id), &instance);
+        Ok(next)
+    }
+
+    pub fn get_pathway_variance(
+        env: Env,
+        instance_id: u64,
+    ) -> Result<PathwayVarianceReport, Error> {
+        let instance = Self::get_patient_pathway(env.clone(), instance_id)?;
+        Ok(pathways::variance_report(&env, &instance))
+    }
+
+    /// Register or replace a named order set and index it under its condition
+    pub fn register_order_set(env: Env, admin: Address, order_set: OrderSet) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !order_sets::is_valid(&order_set) {
+            return Err(Error::InvalidInput);
+        }
+        if let Some(guideline_id) = &order_set.guideline_id {
+            Self::get_guideline(env.clone(), guideline_id.clone())?;
+        }
+
+        let index_key = DataKey::ConditionOrderSets(order_set.condition.clone());
+        let mut ids: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        if !ids.contains(&order_set.order_set_id) {
+            ids.push_back(order_set.order_set_id.clone());
+            env.storage().persistent().set(&index_key, &ids);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::OrderSet(order_set.order_set_id.clone()),
+            &order_set,
+        );
+        Ok(())
+    }
+
+    pub fn get_order_set(env: Env, order_set_id: Symbol) -> Result<OrderSet, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OrderSet(order_set_id))
+            .ok_or(Error::OrderSetNotFound)
+    }
+
+    pub fn get_condition_order_sets(env: Env, condition: String) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConditionOrderSets(condition))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Start a checklist for the patient from an order set, optionally tied
+    /// to the patient's care pathway
+    pub fn instantiate_order_set(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        order_set_id: Symbol,
+        pathway_instance: Option<u64>,
+    ) -> Result<u64, Error> {
+        provider_id.require_auth();
+        let order_set = Self::get_order_set(env.clone(), order_set_id.clone())?;
+        if let Some(instance_id) = pathway_instance {
+            let pathway = Self::get_patient_pathway(env.clone(), instance_id)?;
+            if pathway.patient_id != patient_id {
+                return Err(Error::InvalidInput);
+            }
+        }
+
+        let checklist_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OrderChecklistCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::OrderChecklistCounter, &checklist_id);
+
+        let checklist = OrderChecklist {
+            checklist_id,
+            order_set_id: order_set_id.clone(),
+            patient_id,
+            provider_id,
+            pathway_instance,
+            created_at: env.ledger().timestamp(),
+            items: order_sets::checklist_items(&env, &order_set),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::OrderChecklist(checklist_id), &checklist);
+
+        let index_key = DataKey::OrderSetChecklists(order_set_id);
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(checklist_id);
+        env.storage().persistent().set(&index_key, &ids);
+        Ok(checklist_id)
+    }
+
+    pub fn get_order_checklist(env: Env, checklist_id: u64) -> Result<OrderChecklist, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OrderChecklist(checklist_id))
+            .ok_or(Error::OrderSetNotFound)
+    }
+
+    /// Mark a checklist item as done by the ordering provider
+    pub fn complete_order_item(
+        env: Env,
+        checklist_id: u64,
+        provider_id: Address,
+        item_id: Symbol,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut checklist = Self::get_order_checklist(env.clone(), checklist_id)?;
+        if provider_id != checklist.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+
+        let index = checklist
+            .items
+            .iter()
+            .position(|item| item.item_id == item_id)
+            .ok_or(Error::OrderItemNotFound)? as u32;
+        let mut item = checklist.items.get_unchecked(index);
+        if item.completed_at.is_some() {
+            return Err(Error::InvalidInput);
+        }
+        item.completed_by = Some(provider_id);
+        item.completed_at = Some(env.ledger().timestamp());
+        checklist.items.set(index, item);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::OrderChecklist(checklist_id), &checklist);
+        Ok(())
+    }
+
+    /// Item completion across every checklist started from the order set
+    pub fn get_order_set_compliance(
+        env: Env,
+        order_set_id: Symbol,
+    ) -> Result<OrderSetCompliance, Error> {
+        Self::get_order_set(env.clone(), order_set_id.clone())?;
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OrderSetChecklists(order_set_id.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        let mut checklists = Vec::new(&env);
+        for id in ids.iter() {
+            if let Ok(checklist) = Self::get_order_checklist(env.clone(), id) {
+                checklists.push_back(checklist);
+            }
+        }
+        Ok(order_sets::compliance(order_set_id, &checklists))
     }
 
     pub fn create_reminder(
         env: Env,
         patient_id: Address,
-        _provider_id: Address,
-        _reminder_type: Symbol,
+        provider_id: Address,
+        reminder_type: Symbol,
         due_date: u64,
-        _priority: Symbol,
+        priority: Symbol,
+        recurrence_interval: Option<u64>,
     ) -> Result<u64, Error> {
-        // Use ledger timestamp + patient address hash as a simple ID
-        let reminder_id = env.ledger().timestamp();
-        env.storage().temporary().set(&patient_id, &due_date);
-        Ok(reminder_id)
+        provider_id.require_auth();
+        if recurrence_interval == Some(0) {
+            return Err(Error::InvalidInput);
+        }
+
+        let reminder = Reminder {
+            reminder_id: reminders::next_id(&env),
+            patient_id,
+            provider_id,
+            reminder_type,
+            due_date,
+            priority,
+            recurrence_interval,
+            status: ReminderStatus::Pending,
+            created_at: env.ledger().timestamp(),
+            acknowledged_at: None,
+            closed_at: None,
+        };
+        reminders::insert(&env, &reminder);
+        Ok(reminder.reminder_id)
+    }
+
+    pub fn get_reminder(env: Env, reminder_id: u64) -> Result<Reminder, Error> {
+        reminders::load(&env, reminder_id).ok_or(Error::ReminderNotFound)
+    }
+
+    /// Patient or provider acknowledges they have seen the reminder
+    pub fn acknowledge_reminder(env: Env, reminder_id: u64, actor: Address) -> Result<(), Error> {
+        actor.require_auth();
+        let mut reminder = Self::get_reminder(env.clone(), reminder_id)?;
+        if actor != reminder.patient_id && actor != reminder.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if reminder.status != ReminderStatus::Pending {
+            return Err(Error::InvalidReminderState);
+        }
+
+        reminder.status = ReminderStatus::Acknowledged;
+        reminder.acknowledged_at = Some(env.ledger().timestamp());
+        reminders::save(&env, &reminder);
+        Ok(())
+    }
+
+    /// Complete a reminder; recurring reminders schedule their next occurrence,
+    /// whose id is returned.
+    pub fn complete_reminder(
+        env: Env,
+        reminder_id: u64,
+        provider_id: Address,
+    ) -> Result<Option<u64>, Error> {
+        provider_id.require_auth();
+        let mut reminder = Self::get_reminder(env.clone(), reminder_id)?;
+        if provider_id != reminder.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if !reminder.is_open() {
+            return Err(Error::InvalidReminderState);
+        }
+
+        let now = env.ledger().timestamp();
+        reminder.status = ReminderStatus::Completed;
+        reminder.closed_at = Some(now);
+        reminders::save(&env, &reminder);
+
+        let Some(interval) = reminder.recurrence_interval else {
+            return Ok(None);
+        };
+        let next = Reminder {
+            reminder_id: reminders::next_id(&env),
+            due_date: reminder.due_date.max(now) + interval,
+            status: ReminderStatus::Pending,
+            created_at: now,
+            acknowledged_at: None,
+            closed_at: None,
+            ..reminder
+        };
+        reminders::insert(&env, &next);
+        Ok(Some(next.reminder_id))
+    }
+
+    pub fn cancel_reminder(env: Env, reminder_id: u64, provider_id: Address) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut reminder = Self::get_reminder(env.clone(), reminder_id)?;
+        if provider_id != reminder.provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if !reminder.is_open() {
+            return Err(Error::InvalidReminderState);
+        }
+
+        reminder.status = ReminderStatus::Cancelled;
+        reminder.closed_at = Some(env.ledger().timestamp());
+        reminders::save(&env, &reminder);
+        Ok(())
+    }
+
+    /// Open reminders for a patient due within `horizon` seconds from now
+    pub fn get_patient_due_reminders(env: Env, patient_id: Address, horizon: u64) -> Vec<Reminder> {
+        let due_by = env.ledger().timestamp().saturating_add(horizon);
+        reminders::open_due(&env, &DataKey::PatientReminders(patient_id), due_by, false)
+    }
+
+    pub fn get_patient_overdue_reminders(env: Env, patient_id: Address) -> Vec<Reminder> {
+        let now = env.ledger().timestamp();
+        reminders::open_due(&env, &DataKey::PatientReminders(patient_id), now, true)
+    }
+
+    /// Open reminders owned by a provider due within `horizon` seconds from now
+    pub fn get_provider_due_reminders(
+        env: Env,
+        provider_id: Address,
+        horizon: u64,
+    ) -> Vec<Reminder> {
+        let due_by = env.ledger().timestamp().saturating_add(horizon);
+        reminders::open_due(
+            &env,
+            &DataKey::ProviderReminders(provider_id),
+            due_by,
+            false,
+        )
+    }
+
+    pub fn get_provider_overdue_reminders(env: Env, provider_id: Address) -> Vec<Reminder> {
+        let now = env.ledger().timestamp();
+        reminders::open_due(&env, &DataKey::ProviderReminders(provider_id), now, true)
+    }
+
+    /// Add or replace a service in the preventive-care schedule
+    pub fn set_preventive_service(
+        env: Env,
+        admin: Address,
+        service: PreventiveService,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        if !preventive::is_valid(&service) {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut schedule = Self::get_preventive_schedule(env.clone());
+        match schedule.iter().position(|s| s.service == service.service) {
+            Some(index) => schedule.set(index as u32, service),
+            None => schedule.push_back(service),
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::PreventiveSchedule, &schedule);
+        Ok(())
     }
 
+    pub fn remove_preventive_service(
+        env: Env,
+        admin: Address,
+        service: Symbol,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        let mut schedule = Self::get_preventive_schedule(env.clone());
+        let index = schedule
+            .iter()
+            .position(|s| s.service == service)
+            .ok_or(Error::ServiceNotFound)?;
+        schedule.remove(index as u32);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PreventiveSchedule, &schedule);
+        Ok(())
+    }
+
+    pub fn get_preventive_schedule(env: Env) -> Vec<PreventiveService> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PreventiveSchedule)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Record that a scheduled service was performed for the patient
+    pub fn record_preventive_service(
+        env: Env,
+        patient_id: Address,
+        provider_id: Address,
+        service: Symbol,
+        performed_at: u64,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        if performed_at > env.ledger().timestamp() {
+            return Err(Error::InvalidInput);
+        }
+        if !Self::get_preventive_schedule(env.clone())
+            .iter()
+            .any(|s| s.service == service)
+        {
+            return Err(Error::ServiceNotFound);
+        }
+
+        let key = DataKey::PreventiveHistory(patient_id, service);
+        let last: Option<u64> = env.storage().persistent().get(&key);
+        if last.is_none_or(|t| performed_at > t) {
+            env.storage().persistent().set(&key, &performed_at);
+        }
+        Ok(())
+    }
+
+    /// Scheduled services that are due or overdue for the patient
     pub fn check_preventive_care(
         env: Env,
-        _patient_id: Address,
+        patient_id: Address,
         age: u32,
-        _gender: Symbol,
-        _risk_factors: Vec<Symbol>,
-    ) -> Result<Vec<Symbol>, Error> {
-        let mut alerts = Vec::new(&env);
+        gender: Symbol,
+        risk_factors: Vec<Symbol>,
+    ) -> Result<Vec<PreventiveCareDue>, Error> {
+        let now = env.ledger().timestamp();
+        let mut due = Vec::new(&env);
 
-        if age > 45 {
-            alerts.push_back(Symbol::new(&env, "Cardiac_Screening"));
-        }
-        if age > 18 {
-            alerts.push_back(Symbol::new(&env, "Blood_Pressure_Check"));
+        for service in Self::get_preventive_schedule(env.clone()).iter() {
+            let (min_age, interval_days) = preventive::effective_rule(&service, &risk_factors);
+            if !preventive::applies(&service, min_age, age, &gender) {
+                continue;
+            }
+
+            let last_performed: Option<u64> = env.storage().persistent().get(
+                &DataKey::PreventiveHistory(patient_id.clone(), service.service.clone()),
+            );
+            if let Some(entry) =
+                preventive::due_entry(&service.service, interval_days, last_performed, now)
+            {
+                due.push_back(entry);
+            }
         }
 
-        Ok(alerts)
+        Ok(due)
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotAuthorized)?;
+        if stored != *admin {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
     }
 }
 
@@ -0,0 +1,126 @@
+use soroban_sdk::{Address, Env, String, Symbol, Vec, contracttype};
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrderItemKind {
+    Lab,
+    Medication,
+    Imaging,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderSetItem {
+    pub item_id: Symbol,
+    pub kind: OrderItemKind,
+    pub description: String,
+    pub required: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderSet {
+    pub order_set_id: Symbol,
+    pub name: String,
+    pub condition: String,
+    pub guideline_id: Option<String>,
+    pub items: Vec<OrderSetItem>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderChecklistItem {
+    pub item_id: Symbol,
+    pub kind: OrderItemKind,
+    pub required: bool,
+    pub completed_by: Option<Address>,
+    pub completed_at: Option<u64>,
+}
+
+/// A patient's copy of an order set, ticked off item by item
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderChecklist {
+    pub checklist_id: u64,
+    pub order_set_id: Symbol,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub pathway_instance: Option<u64>,
+    pub created_at: u64,
+    pub items: Vec<OrderChecklistItem>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrderSetCompliance {
+    pub order_set_id: Symbol,
+    pub checklists: u32,
+    pub checklists_complete: u32, // every required item done
+    pub items_total: u32,
+    pub items_completed: u32,
+    pub required_total: u32,
+    pub required_completed: u32,
+}
+
+/// Items must be non-empty with unique ids
+pub fn is_valid(order_set: &OrderSet) -> bool {
+    if order_set.items.is_empty() {
+        return false;
+    }
+    let mut seen: Vec<Symbol> = Vec::new(order_set.items.env());
+    for item in order_set.items.iter() {
+        if seen.contains(&item.item_id) {
+            return false;
+        }
+        seen.push_back(item.item_id);
+    }
+    true
+}
+
+pub fn checklist_items(env: &Env, order_set: &OrderSet) -> Vec<OrderChecklistItem> {
+    let mut items = Vec::new(env);
+    for item in order_set.items.iter() {
+        items.push_back(OrderChecklistItem {
+            item_id: item.item_id,
+            kind: item.kind,
+            required: item.required,
+            completed_by: None,
+            completed_at: None,
+        });
+    }
+    items
+}
+
+pub fn compliance(order_set_id: Symbol, checklists: &Vec<OrderChecklist>) -> OrderSetCompliance {
+    let mut report = OrderSetCompliance {
+        order_set_id,
+        checklists: checklists.len(),
+        checklists_complete: 0,
+        items_total: 0,
+        items_completed: 0,
+        required_total: 0,
+        required_completed: 0,
+    };
+    for checklist in checklists.iter() {
+        let mut required_open = false;
+        for item in checklist.items.iter() {
+            let done = item.completed_at.is_some();
+            report.items_total += 1;
+            if done {
+                report.items_completed += 1;
+            }
+            if item.required {
+                report.required_total += 1;
+                if done {
+                    report.required_completed += 1;
+                } else {
+                    required_open = true;
+                }
+            }
+        }
+        if !required_open {
+            report.checklists_complete += 1;
+        }
+    }
+    report
+}
@@ -0,0 +1,128 @@
+use soroban_sdk::{Address, Env, String, Symbol, Vec, contracttype};
+
+/// Jump to `next_step` when a step completes with `outcome`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathwayBranch {
+    pub outcome: Symbol,
+    pub next_step: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathwayStep {
+    pub step_id: Symbol,
+    pub description: String,
+    pub expected_interval: u64, // seconds after the previous step (or enrollment)
+    pub branches: Vec<PathwayBranch>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CarePathway {
+    pub pathway_id: Symbol,
+    pub condition: String,
+    pub steps: Vec<PathwayStep>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StepCompletion {
+    pub step_id: Symbol,
+    pub completed_by: Address,
+    pub completed_at: u64,
+    pub expected_by: u64,
+    pub variance_seconds: i64, // positive = late, negative = early
+    pub outcome: Option<Symbol>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientPathway {
+    pub instance_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub pathway_id: Symbol,
+    pub started_at: u64,
+    pub current_step: Option<Symbol>,
+    pub current_step_due: Option<u64>,
+    pub completions: Vec<StepCompletion>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PathwayVarianceReport {
+    pub instance_id: u64,
+    pub steps_completed: u32,
+    pub steps_late: u32,
+    pub total_delay_seconds: u64,
+    pub current_step: Option<Symbol>,
+    pub current_step_overdue_seconds: u64,
+}
+
+pub fn find_step(pathway: &CarePathway, step_id: &Symbol) -> Option<(u32, PathwayStep)> {
+    pathway
+        .steps
+        .iter()
+        .enumerate()
+        .find(|(_, step)| step.step_id == *step_id)
+        .map(|(index, step)| (index as u32, step))
+}
+
+/// Steps must be non-empty with unique ids, and every branch must target a known step
+pub fn is_valid(pathway: &CarePathway) -> bool {
+    if pathway.steps.is_empty() {
+        return false;
+    }
+    let mut seen: Vec<Symbol> = Vec::new(pathway.steps.env());
+    for step in pathway.steps.iter() {
+        if seen.contains(&step.step_id) {
+            return false;
+        }
+        seen.push_back(step.step_id);
+    }
+    pathway.steps.iter().all(|step| {
+        step.branches
+            .iter()
+            .all(|branch| seen.contains(&branch.next_step))
+    })
+}
+
+/// Branch matching the outcome, otherwise the next step in order
+pub fn next_step(
+    pathway: &CarePathway,
+    index: u32,
+    step: &PathwayStep,
+    outcome: &Option<Symbol>,
+) -> Option<Symbol> {
+    if let Some(outcome) = outcome
+        && let Some(branch) = step.branches.iter().find(|b| b.outcome == *outcome)
+    {
+        return Some(branch.next_step);
+    }
+    pathway.steps.get(index + 1).map(|s| s.step_id)
+}
+
+pub fn variance_report(env: &Env, instance: &PatientPathway) -> PathwayVarianceReport {
+    let now = env.ledger().timestamp();
+    let mut steps_late = 0u32;
+    let mut total_delay_seconds = 0u64;
+    for completion in instance.completions.iter() {
+        if completion.variance_seconds > 0 {
+            steps_late += 1;
+            total_delay_seconds += completion.variance_seconds as u64;
+        }
+    }
+
+    PathwayVarianceReport {
+        instance_id: instance.instance_id,
+        steps_completed: instance.completions.len(),
+        steps_late,
+        total_delay_seconds,
+        current_step: instance.current_step.clone(),
+        current_step_overdue_seconds: instance
+            .current_step_due
+            .map(|due| now.saturating_sub(due))
+            .unwrap_or(0),
+    }
+}
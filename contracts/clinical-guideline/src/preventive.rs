@@ -0,0 +1,96 @@
+use soroban_sdk::{Symbol, Vec, contracttype};
+
+pub const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Adjusts a service for patients carrying `risk_factor`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskModifier {
+    pub risk_factor: Symbol,
+    pub min_age_override: Option<u32>,
+    pub interval_days_override: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveService {
+    pub service: Symbol,
+    pub sex: Option<Symbol>, // None applies to all
+    pub min_age: u32,
+    pub max_age: u32,
+    pub interval_days: u64,
+    pub risk_modifiers: Vec<RiskModifier>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PreventiveStatus {
+    Due,     // never performed
+    Overdue, // interval elapsed since last performed
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveCareDue {
+    pub service: Symbol,
+    pub status: PreventiveStatus,
+    pub due_date: u64,
+    pub last_performed: Option<u64>,
+}
+
+pub fn is_valid(service: &PreventiveService) -> bool {
+    service.min_age <= service.max_age
+        && service.interval_days > 0
+        && service
+            .risk_modifiers
+            .iter()
+            .all(|m| m.interval_days_override != Some(0))
+}
+
+/// Effective (min_age, interval_days) once matching risk modifiers are applied.
+/// The earliest start age and shortest interval win.
+pub fn effective_rule(service: &PreventiveService, risk_factors: &Vec<Symbol>) -> (u32, u64) {
+    let mut min_age = service.min_age;
+    let mut interval_days = service.interval_days;
+    for modifier in service.risk_modifiers.iter() {
+        if !risk_factors.contains(&modifier.risk_factor) {
+            continue;
+        }
+        if let Some(age) = modifier.min_age_override {
+            min_age = min_age.min(age);
+        }
+        if let Some(days) = modifier.interval_days_override {
+            interval_days = interval_days.min(days);
+        }
+    }
+    (min_age, interval_days)
+}
+
+pub fn applies(service: &PreventiveService, min_age: u32, age: u32, sex: &Symbol) -> bool {
+    age >= min_age && age <= service.max_age && service.sex.as_ref().is_none_or(|s| s == sex)
+}
+
+/// Due entry for the service, or None while it is up to date
+pub fn due_entry(
+    service: &Symbol,
+    interval_days: u64,
+    last_performed: Option<u64>,
+    now: u64,
+) -> Option<PreventiveCareDue> {
+    let Some(last) = last_performed else {
+        return Some(PreventiveCareDue {
+            service: service.clone(),
+            status: PreventiveStatus::Due,
+            due_date: now,
+            last_performed: None,
+        });
+    };
+
+    let due_date = last.saturating_add(interval_days * SECONDS_PER_DAY);
+    (due_date <= now).then(|| PreventiveCareDue {
+        service: service.clone(),
+        status: PreventiveStatus::Overdue,
+        due_date,
+        last_performed,
+    })
+}
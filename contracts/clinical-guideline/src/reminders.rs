@@ -0,0 +1,102 @@
+use soroban_sdk::{Address, Env, Symbol, Vec, contracttype};
+
+use crate::DataKey;
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReminderStatus {
+    Pending,
+    Acknowledged,
+    Completed,
+    Cancelled,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Reminder {
+    pub reminder_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub reminder_type: Symbol,
+    pub due_date: u64,
+    pub priority: Symbol,
+    pub recurrence_interval: Option<u64>, // seconds between occurrences
+    pub status: ReminderStatus,
+    pub created_at: u64,
+    pub acknowledged_at: Option<u64>,
+    pub closed_at: Option<u64>,
+}
+
+impl Reminder {
+    pub fn is_open(&self) -> bool {
+        matches!(
+            self.status,
+            ReminderStatus::Pending | ReminderStatus::Acknowledged
+        )
+    }
+}
+
+pub fn next_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::ReminderCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage().instance().set(&DataKey::ReminderCounter, &id);
+    id
+}
+
+pub fn load(env: &Env, reminder_id: u64) -> Option<Reminder> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reminder(reminder_id))
+}
+
+pub fn save(env: &Env, reminder: &Reminder) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Reminder(reminder.reminder_id), reminder);
+}
+
+/// Store a new reminder and index it under its patient and provider
+pub fn insert(env: &Env, reminder: &Reminder) {
+    save(env, reminder);
+    for key in [
+        DataKey::PatientReminders(reminder.patient_id.clone()),
+        DataKey::ProviderReminders(reminder.provider_id.clone()),
+    ] {
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        ids.push_back(reminder.reminder_id);
+        env.storage().persistent().set(&key, &ids);
+    }
+}
+
+/// Open reminders under `index` whose due date is at or before `due_by`.
+/// With `strict`, only reminders due strictly before `due_by` are returned.
+pub fn open_due(env: &Env, index: &DataKey, due_by: u64, strict: bool) -> Vec<Reminder> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(index)
+        .unwrap_or(Vec::new(env));
+
+    let mut result = Vec::new(env);
+    for id in ids.iter() {
+        if let Some(reminder) = load(env, id) {
+            let due = if strict {
+                reminder.due_date < due_by
+            } else {
+                reminder.due_date <= due_by
+            };
+            if reminder.is_open() && due {
+                result.push_back(reminder);
+            }
+        }
+    }
+    result
+}
@@ -0,0 +1,231 @@
+use soroban_sdk::{Env, String, Symbol, Vec, contracttype};
+
+/// Weighted component of a risk calculator. Weights are in tenths of a point
+/// so half-point components (e.g. Wells) stay integral.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskComponent {
+    pub name: Symbol,
+    pub weight_tenths: u32,
+}
+
+/// Interpretation band, applying from `min_score_tenths` up to the next band
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskBand {
+    pub min_score_tenths: u32,
+    pub label: Symbol,
+    pub interpretation: String,
+    pub recommended_action: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskCalculator {
+    pub name: Symbol,
+    pub components: Vec<RiskComponent>,
+    pub bands: Vec<RiskBand>, // ascending by min_score_tenths
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RiskAssessment {
+    pub calculator: Symbol,
+    pub score_tenths: u32,
+    pub band: Symbol,
+    pub interpretation: String,
+    pub recommended_action: String,
+    pub contributing_components: Vec<Symbol>,
+}
+
+fn component(env: &Env, name: &str, weight_tenths: u32) -> RiskComponent {
+    RiskComponent {
+        name: Symbol::new(env, name),
+        weight_tenths,
+    }
+}
+
+fn band(
+    env: &Env,
+    min_score_tenths: u32,
+    label: &str,
+    interpretation: &str,
+    action: &str,
+) -> RiskBand {
+    RiskBand {
+        min_score_tenths,
+        label: Symbol::new(env, label),
+        interpretation: String::from_str(env, interpretation),
+        recommended_action: String::from_str(env, action),
+    }
+}
+
+fn cha2ds2_vasc(env: &Env) -> RiskCalculator {
+    let mut components = Vec::new(env);
+    components.push_back(component(env, "chf", 10));
+    components.push_back(component(env, "hypertension", 10));
+    components.push_back(component(env, "age_75_plus", 20));
+    components.push_back(component(env, "diabetes", 10));
+    components.push_back(component(env, "stroke_tia", 20));
+    components.push_back(component(env, "vascular_disease", 10));
+    components.push_back(component(env, "age_65_74", 10));
+    components.push_back(component(env, "female", 10));
+
+    let mut bands = Vec::new(env);
+    bands.push_back(band(
+        env,
+        0,
+        "low",
+        "Low stroke risk",
+        "No antithrombotic therapy",
+    ));
+    bands.push_back(band(
+        env,
+        10,
+        "moderate",
+        "Moderate stroke risk",
+        "Consider oral anticoagulation",
+    ));
+    bands.push_back(band(
+        env,
+        20,
+        "high",
+        "High stroke risk",
+        "Oral anticoagulation recommended",
+    ));
+
+    RiskCalculator {
+        name: Symbol::new(env, "cha2ds2_vasc"),
+        components,
+        bands,
+    }
+}
+
+fn wells_pe(env: &Env) -> RiskCalculator {
+    let mut components = Vec::new(env);
+    components.push_back(component(env, "dvt_signs", 30));
+    components.push_back(component(env, "pe_most_likely", 30));
+    components.push_back(component(env, "heart_rate_over_100", 15));
+    components.push_back(component(env, "immobilization_surgery", 15));
+    components.push_back(component(env, "previous_dvt_pe", 15));
+    components.push_back(component(env, "hemoptysis", 10));
+    components.push_back(component(env, "malignancy", 10));
+
+    let mut bands = Vec::new(env);
+    bands.push_back(band(
+        env,
+        0,
+        "pe_unlikely",
+        "PE unlikely",
+        "Obtain D-dimer; image if positive",
+    ));
+    bands.push_back(band(
+        env,
+        45,
+        "pe_likely",
+        "PE likely",
+        "Obtain CT pulmonary angiography",
+    ));
+
+    RiskCalculator {
+        name: Symbol::new(env, "wells_pe"),
+        components,
+        bands,
+    }
+}
+
+fn has_bled(env: &Env) -> RiskCalculator {
+    let mut components = Vec::new(env);
+    components.push_back(component(env, "hypertension", 10));
+    components.push_back(component(env, "abnormal_renal", 10));
+    components.push_back(component(env, "abnormal_liver", 10));
+    components.push_back(component(env, "stroke", 10));
+    components.push_back(component(env, "bleeding", 10));
+    components.push_back(component(env, "labile_inr", 10));
+    components.push_back(component(env, "elderly", 10));
+    components.push_back(component(env, "drugs", 10));
+    components.push_back(component(env, "alcohol", 10));
+
+    let mut bands = Vec::new(env);
+    bands.push_back(band(
+        env,
+        0,
+        "low",
+        "Low bleeding risk",
+        "Anticoagulation may proceed",
+    ));
+    bands.push_back(band(
+        env,
+        30,
+        "high",
+        "High bleeding risk",
+        "Address modifiable risk factors and review closely",
+    ));
+
+    RiskCalculator {
+        name: Symbol::new(env, "has_bled"),
+        components,
+        bands,
+    }
+}
+
+/// Built-in calculators available without registration
+pub fn builtin(env: &Env, name: &Symbol) -> Option<RiskCalculator> {
+    [cha2ds2_vasc(env), wells_pe(env), has_bled(env)]
+        .into_iter()
+        .find(|calculator| calculator.name == *name)
+}
+
+/// Bands must be non-empty, start at zero and be strictly ascending
+pub fn is_valid(calculator: &RiskCalculator) -> bool {
+    if calculator.components.is_empty() || calculator.bands.is_empty() {
+        return false;
+    }
+    let mut previous: Option<u32> = None;
+    for band in calculator.bands.iter() {
+        let ordered = match previous {
+            None => band.min_score_tenths == 0,
+            Some(prev) => band.min_score_tenths > prev,
+        };
+        if !ordered {
+            return false;
+        }
+        previous = Some(band.min_score_tenths);
+    }
+    true
+}
+
+/// Score the present components; returns None when a component is unknown
+pub fn assess(
+    env: &Env,
+    calculator: &RiskCalculator,
+    present: &Vec<Symbol>,
+) -> Option<RiskAssessment> {
+    let mut score_tenths = 0u32;
+    let mut contributing_components = Vec::new(env);
+
+    for name in present.iter() {
+        if contributing_components.contains(&name) {
+            continue;
+        }
+        let component = calculator.components.iter().find(|c| c.name == name)?;
+        score_tenths += component.weight_tenths;
+        contributing_components.push_back(name);
+    }
+
+    let mut selected = calculator.bands.get(0)?;
+    for band in calculator.bands.iter() {
+        if score_tenths >= band.min_score_tenths {
+            selected = band;
+        }
+    }
+
+    Some(RiskAssessment {
+        calculator: calculator.name.clone(),
+        score_tenths,
+        band: selected.label,
+        interpretation: selected.interpretation,
+        recommended_action: selected.recommended_action,
+        contributing_components,
+    })
+}
@@ -0,0 +1,141 @@
+use soroban_sdk::{Address, Env, String, Vec, contractevent, contracttype};
+
+use crate::DataKey;
+
+/// Most pending updates kept per provider; the oldest are dropped first
+pub const MAX_PENDING_UPDATES: u32 = 50;
+
+/// What a provider subscribes to: one guideline, or every guideline for a condition
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum SubscriptionTarget {
+    Guideline(String),
+    Condition(String),
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GuidelineChange {
+    Registered,
+    Retired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuidelineUpdate {
+    pub guideline_id: String,
+    pub condition: String,
+    pub version: u32,
+    pub change: GuidelineChange,
+    pub published_at: u64,
+}
+
+/// Emitted once per subscribed provider when a guideline version is
+/// registered or retired
+#[contractevent]
+pub struct GuidelineUpdateNotice {
+    #[topic]
+    pub provider: Address,
+    pub guideline_id: String,
+    pub version: u32,
+    pub change: GuidelineChange,
+}
+
+fn subscribers_key(target: &SubscriptionTarget) -> DataKey {
+    match target {
+        SubscriptionTarget::Guideline(id) => DataKey::GuidelineSubscribers(id.clone()),
+        SubscriptionTarget::Condition(condition) => {
+            DataKey::ConditionSubscribers(condition.clone())
+        }
+    }
+}
+
+fn subscribers(env: &Env, key: &DataKey) -> Vec<Address> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+pub fn subscribe(env: &Env, target: &SubscriptionTarget, provider: &Address) {
+    let key = subscribers_key(target);
+    let mut providers = subscribers(env, &key);
+    if !providers.contains(provider) {
+        providers.push_back(provider.clone());
+        env.storage().persistent().set(&key, &providers);
+    }
+}
+
+/// Returns false when the provider was not subscribed
+pub fn unsubscribe(env: &Env, target: &SubscriptionTarget, provider: &Address) -> bool {
+    let key = subscribers_key(target);
+    let mut providers = subscribers(env, &key);
+    match providers.first_index_of(provider) {
+        Some(index) => {
+            providers.remove(index);
+            env.storage().persistent().set(&key, &providers);
+            true
+        }
+        None => false,
+    }
+}
+
+pub fn unacknowledged(env: &Env, provider: &Address) -> Vec<GuidelineUpdate> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProviderUpdates(provider.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+/// Drop the provider's pending update for this guideline version.
+/// Returns false when there was nothing to acknowledge.
+pub fn acknowledge(env: &Env, provider: &Address, guideline_id: &String, version: u32) -> bool {
+    let mut updates = unacknowledged(env, provider);
+    let Some(index) = updates
+        .iter()
+        .position(|u| u.guideline_id == *guideline_id && u.version == version)
+    else {
+        return false;
+    };
+    updates.remove(index as u32);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProviderUpdates(provider.clone()), &updates);
+    true
+}
+
+/// Queue the update for everyone subscribed to the guideline or its
+/// condition, notifying each provider once. A provider's queue holds at most
+/// `MAX_PENDING_UPDATES` entries.
+pub fn notify(env: &Env, update: &GuidelineUpdate) {
+    let mut recipients = subscribers(
+        env,
+        &DataKey::GuidelineSubscribers(update.guideline_id.clone()),
+    );
+    for provider in subscribers(
+        env,
+        &DataKey::ConditionSubscribers(update.condition.clone()),
+    )
+    .iter()
+    {
+        if !recipients.contains(&provider) {
+            recipients.push_back(provider);
+        }
+    }
+
+    for provider in recipients.iter() {
+        let mut updates = unacknowledged(env, &provider);
+        if updates.len() >= MAX_PENDING_UPDATES {
+            updates.pop_front();
+        }
+        updates.push_back(update.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::ProviderUpdates(provider.clone()), &updates);
+
+        GuidelineUpdateNotice {
+            provider,
+            guideline_id: update.guideline_id.clone(),
+            version: update.version,
+            change: update.change,
+        }
+        .publish(env);
+    }
+}
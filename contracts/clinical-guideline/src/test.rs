@@ -1,11 +1,33 @@
 #![cfg(test)]
 use super::*;
-use soroban_sdk::{Address, BytesN, Env, String, Symbol, Vec, testutils::Address as _};
+use soroban_sdk::{
+    Address, BytesN, Env, String, Symbol, Vec,
+    testutils::{Address as _, Ledger},
+};
+
+fn criterion(
+    env: &Env,
+    id: &str,
+    kind: CriterionKind,
+    code: Option<&str>,
+    min_value: Option<i64>,
+    max_value: Option<i64>,
+    required: bool,
+) -> Criterion {
+    Criterion {
+        criterion_id: Symbol::new(env, id),
+        kind,
+        code: code.map(|c| String::from_str(env, c)),
+        min_value,
+        max_value,
+        required,
+    }
+}
 
 #[test]
 fn test_register_and_evaluate_guideline() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, ClinicalGuidelineContract);
+    let contract_id = env.register(ClinicalGuidelineContract, ());
     let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -14,77 +36,350 @@ fn test_register_and_evaluate_guideline() {
 
     // Register guideline (Mocking auth)
     env.mock_all_auths();
+    client.initialize(&admin);
     client.register_clinical_guideline(
         &admin,
         &guideline_id,
-        &String::from_str(&env, "Flu"),
+        &String::from_str(&env, "Diabetes"),
         &criteria_hash,
         &criteria_hash,
         &Symbol::new(&env, "A"),
     );
 
+    let mut criteria = Vec::new(&env);
+    criteria.push_back(criterion(
+        &env,
+        "adult",
+        CriterionKind::AgeRange,
+        None,
+        Some(18),
+        Some(75),
+        true,
+    ));
+    criteria.push_back(criterion(
+        &env,
+        "t2dm",
+        CriterionKind::Diagnosis,
+        Some("E11"),
+        None,
+        None,
+        true,
+    ));
+    // HbA1c in hundredths of a percent
+    criteria.push_back(criterion(
+        &env,
+        "a1c_high",
+        CriterionKind::LabThreshold,
+        Some("4548-4"),
+        Some(700),
+        None,
+        true,
+    ));
+    criteria.push_back(criterion(
+        &env,
+        "no_ckd",
+        CriterionKind::Comorbidity,
+        Some("CKD"),
+        None,
+        None,
+        false,
+    ));
+    client.set_guideline_criteria(&admin, &guideline_id, &criteria);
+
+    let mut lab_values = Vec::new(&env);
+    lab_values.push_back(LabValue {
+        code: String::from_str(&env, "4548-4"),
+        value: 820,
+    });
+    let params = PatientParameters {
+        age_years: 54,
+        diagnosis_codes: Vec::from_array(&env, [String::from_str(&env, "E11")]),
+        lab_values,
+        comorbidities: Vec::new(&env),
+    };
+
     // Evaluate: Match
     let result = client.evaluate_guideline(
         &Address::generate(&env),
         &Address::generate(&env),
         &guideline_id,
-        &criteria_hash,
+        &params,
     );
     assert!(result.applicable);
+    assert_eq!(result.matched_criteria.len(), 4);
+    assert_eq!(result.evidence_level, Symbol::new(&env, "A"));
 
-    // Evaluate: No Match (different hash)
-    let wrong_hash = BytesN::from_array(&env, &[1u8; 32]);
+    // Evaluate: No Match (CKD present, A1c at goal)
+    let mut lab_values = Vec::new(&env);
+    lab_values.push_back(LabValue {
+        code: String::from_str(&env, "4548-4"),
+        value: 650,
+    });
+    let params_fail = PatientParameters {
+        comorbidities: Vec::from_array(&env, [String::from_str(&env, "CKD")]),
+        lab_values,
+        ..params
+    };
     let result_fail = client.evaluate_guideline(
         &Address::generate(&env),
         &Address::generate(&env),
         &guideline_id,
-        &wrong_hash,
+        &params_fail,
     );
     assert!(!result_fail.applicable);
+    assert_eq!(
+        result_fail.failed_criteria,
+        Vec::from_array(
+            &env,
+            [Symbol::new(&env, "a1c_high"), Symbol::new(&env, "no_ckd")]
+        )
+    );
+
+    // Publishing a new version keeps the attached criteria
+    client.register_clinical_guideline(
+        &admin,
+        &guideline_id,
+        &String::from_str(&env, "Diabetes"),
+        &criteria_hash,
+        &criteria_hash,
+        &Symbol::new(&env, "B"),
+    );
+    let guideline = client.get_guideline(&guideline_id);
+    assert_eq!(guideline.criteria, criteria);
+    assert_eq!(guideline.evidence_level, Symbol::new(&env, "B"));
+    assert_eq!(client.get_guideline_version(&guideline_id), 2);
+}
+
+#[test]
+fn test_invalid_criterion_rejected() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let guideline_id = String::from_str(&env, "G1");
+    let hash = BytesN::from_array(&env, &[0u8; 32]);
+
+    // Only the admin can publish guidelines
+    let outsider = Address::generate(&env);
+    let result = client.try_register_clinical_guideline(
+        &outsider,
+        &guideline_id,
+        &String::from_str(&env, "HTN"),
+        &hash,
+        &hash,
+        &Symbol::new(&env, "B"),
+    );
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+
+    client.register_clinical_guideline(
+        &admin,
+        &guideline_id,
+        &String::from_str(&env, "HTN"),
+        &hash,
+        &hash,
+        &Symbol::new(&env, "B"),
+    );
+
+    let criteria = Vec::from_array(
+        &env,
+        [criterion(
+            &env,
+            "dx",
+            CriterionKind::Diagnosis,
+            None,
+            None,
+            None,
+            true,
+        )],
+    );
+    let result = client.try_set_guideline_criteria(&admin, &guideline_id, &criteria);
+    assert_eq!(result, Err(Ok(Error::InvalidInput)));
+
+    let result = client.try_set_guideline_criteria(&outsider, &guideline_id, &Vec::new(&env));
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+}
+
+fn amoxicillin_rule(env: &Env) -> DosingRule {
+    let mut renal_tiers = Vec::new(env);
+    renal_tiers.push_back(RenalTier {
+        below_egfr: 60,
+        dose_percent: 75,
+    });
+    renal_tiers.push_back(RenalTier {
+        below_egfr: 30,
+        dose_percent: 50,
+    });
+    let mut hepatic_tiers = Vec::new(env);
+    hepatic_tiers.push_back(HepaticTier {
+        child_pugh: Symbol::new(env, "C"),
+        dose_percent: 50,
+    });
+
+    DosingRule {
+        medication: String::from_str(env, "Amoxicillin"),
+        dose_mcg_per_kg: 25_000,
+        max_dose_mcg: 1_000_000,
+        frequency: String::from_str(env, "Q12H"),
+        route: Symbol::new(env, "Oral"),
+        duration_days: Some(10),
+        renal_tiers,
+        hepatic_tiers,
+        monitoring_required: Vec::from_array(env, [String::from_str(env, "Renal function")]),
+    }
 }
 
 #[test]
 fn test_drug_dosage_calculation() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, ClinicalGuidelineContract);
+    let contract_id = env.register(ClinicalGuidelineContract, ());
     let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_dosing_rule(&admin, &amoxicillin_rule(&env));
+
+    let patient = Address::generate(&env);
+    let medication = String::from_str(&env, "Amoxicillin");
 
+    // 20kg child: 500mg, below the 1g cap, no adjustments
+    let result = client.calculate_drug_dosage(&patient, &medication, &20000, &6, &None, &None);
+    assert_eq!(result.recommended_dose, 500_000);
+    assert_eq!(result.dose_unit, Symbol::new(&env, "mcg"));
+    assert!(!result.renal_adjustment);
+
+    // 70kg adult is capped at 1g, then reduced to 75% for eGFR 50
     let weight_grams = 70000; // 70kg
     let result = client.calculate_drug_dosage(
-        &Address::generate(&env),
-        &String::from_str(&env, "Amoxicillin"),
+        &patient,
+        &medication,
         &weight_grams,
         &30,
         &Some(50), // Renal impairment < 60
+        &None,
     );
-
     assert_eq!(result.renal_adjustment, true);
-    assert_eq!(result.medication, String::from_str(&env, "Amoxicillin"));
+    assert_eq!(result.recommended_dose, 750_000);
+    assert_eq!(result.duration, Some(10));
+    assert_eq!(result.monitoring_required.len(), 1);
+
+    // eGFR 20 with Child-Pugh C: 50% then 50%
+    let result = client.calculate_drug_dosage(
+        &patient,
+        &medication,
+        &weight_grams,
+        &30,
+        &Some(20),
+        &Some(Symbol::new(&env, "C")),
+    );
+    assert!(result.hepatic_adjustment);
+    assert_eq!(result.recommended_dose, 250_000);
+
+    let missing = client.try_calculate_drug_dosage(
+        &patient,
+        &String::from_str(&env, "Unknown"),
+        &weight_grams,
+        &30,
+        &None,
+        &None,
+    );
+    assert_eq!(missing, Err(Ok(Error::DrugNotFound)));
+}
+
+fn preventive_service(
+    env: &Env,
+    service: &str,
+    sex: Option<&str>,
+    min_age: u32,
+    max_age: u32,
+    interval_days: u64,
+) -> PreventiveService {
+    PreventiveService {
+        service: Symbol::new(env, service),
+        sex: sex.map(|s| Symbol::new(env, s)),
+        min_age,
+        max_age,
+        interval_days,
+        risk_modifiers: Vec::new(env),
+    }
 }
 
 #[test]
 fn test_preventive_care_logic() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, ClinicalGuidelineContract);
+    let contract_id = env.register(ClinicalGuidelineContract, ());
     let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let day = 86_400;
+    env.ledger().set_timestamp(1_000 * day);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let mut cardiac = preventive_service(&env, "Cardiac_Screening", None, 46, 120, 5 * 365);
+    cardiac.risk_modifiers.push_back(RiskModifier {
+        risk_factor: Symbol::new(&env, "diabetes"),
+        min_age_override: Some(30),
+        interval_days_override: Some(365),
+    });
+    client.set_preventive_service(&admin, &cardiac);
+    client.set_preventive_service(
+        &admin,
+        &preventive_service(&env, "Blood_Pressure_Check", None, 18, 120, 365),
+    );
+    client.set_preventive_service(
+        &admin,
+        &preventive_service(&env, "Mammogram", Some("F"), 40, 74, 2 * 365),
+    );
 
     // Test for older patient
-    let alerts = client.check_preventive_care(
+    let patient = Address::generate(&env);
+    let alerts =
+        client.check_preventive_care(&patient, &55, &Symbol::new(&env, "M"), &Vec::new(&env));
+
+    // Should have Cardiac and Blood Pressure checks but not the mammogram
+    assert_eq!(alerts.len(), 2);
+    assert_eq!(alerts.get(0).unwrap().status, PreventiveStatus::Due);
+
+    // Blood pressure checked 400 days ago is overdue; cardiac done last month is not
+    let provider = Address::generate(&env);
+    client.record_preventive_service(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "Blood_Pressure_Check"),
+        &(600 * day),
+    );
+    client.record_preventive_service(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "Cardiac_Screening"),
+        &(970 * day),
+    );
+    let alerts =
+        client.check_preventive_care(&patient, &55, &Symbol::new(&env, "M"), &Vec::new(&env));
+    assert_eq!(alerts.len(), 1);
+    let bp = alerts.get(0).unwrap();
+    assert_eq!(bp.status, PreventiveStatus::Overdue);
+    assert_eq!(bp.due_date, 965 * day);
+
+    // Diabetes lowers the cardiac start age for a younger patient
+    let young = client.check_preventive_care(
         &Address::generate(&env),
-        &55,
+        &35,
         &Symbol::new(&env, "M"),
-        &Vec::new(&env),
+        &Vec::from_array(&env, [Symbol::new(&env, "diabetes")]),
     );
-
-    // Should have at least Cardiac and Blood Pressure checks
-    assert!(alerts.len() >= 2);
+    assert_eq!(young.len(), 2);
 }
 
 #[test]
 #[should_panic(expected = "HostError: Error(Auth, InvalidAction)")]
 fn test_unauthorized_registration() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, ClinicalGuidelineContract);
+    let contract_id = env.register(ClinicalGuidelineContract, ());
     let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
 
     let admin = Address::generate(&env);
@@ -100,3 +395,665 @@ fn test_unauthorized_registration() {
         &Symbol::new(&env, "B"),
     );
 }
+
+#[test]
+fn test_builtin_risk_calculators() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    let patient = Address::generate(&env);
+
+    let chads = client.assess_risk_score(
+        &patient,
+        &Symbol::new(&env, "cha2ds2_vasc"),
+        &Vec::from_array(
+            &env,
+            [
+                Symbol::new(&env, "hypertension"),
+                Symbol::new(&env, "age_75_plus"),
+            ],
+        ),
+    );
+    assert_eq!(chads.score_tenths, 30);
+    assert_eq!(chads.band, Symbol::new(&env, "high"));
+
+    let wells = client.assess_risk_score(
+        &patient,
+        &Symbol::new(&env, "wells_pe"),
+        &Vec::from_array(
+            &env,
+            [
+                Symbol::new(&env, "heart_rate_over_100"),
+                Symbol::new(&env, "hemoptysis"),
+            ],
+        ),
+    );
+    assert_eq!(wells.score_tenths, 25);
+    assert_eq!(wells.band, Symbol::new(&env, "pe_unlikely"));
+
+    let unknown = client.try_assess_risk_score(
+        &patient,
+        &Symbol::new(&env, "has_bled"),
+        &Vec::from_array(&env, [Symbol::new(&env, "smoking")]),
+    );
+    assert_eq!(unknown, Err(Ok(Error::InvalidInput)));
+}
+
+#[test]
+fn test_register_custom_risk_calculator() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let name = Symbol::new(&env, "curb_65");
+    let mut components = Vec::new(&env);
+    for c in ["confusion", "urea", "resp_rate", "blood_pressure", "age_65"] {
+        components.push_back(RiskComponent {
+            name: Symbol::new(&env, c),
+            weight_tenths: 10,
+        });
+    }
+    let mut bands = Vec::new(&env);
+    bands.push_back(RiskBand {
+        min_score_tenths: 0,
+        label: Symbol::new(&env, "low"),
+        interpretation: String::from_str(&env, "Low mortality"),
+        recommended_action: String::from_str(&env, "Outpatient treatment"),
+    });
+    bands.push_back(RiskBand {
+        min_score_tenths: 20,
+        label: Symbol::new(&env, "admit"),
+        interpretation: String::from_str(&env, "Increased mortality"),
+        recommended_action: String::from_str(&env, "Hospital admission"),
+    });
+    let calculator = RiskCalculator {
+        name: name.clone(),
+        components,
+        bands,
+    };
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_register_risk_calculator(&outsider, &calculator),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    client.register_risk_calculator(&admin, &calculator);
+    let result = client.assess_risk_score(
+        &Address::generate(&env),
+        &name,
+        &Vec::from_array(
+            &env,
+            [Symbol::new(&env, "urea"), Symbol::new(&env, "age_65")],
+        ),
+    );
+    assert_eq!(result.band, Symbol::new(&env, "admit"));
+    assert_eq!(
+        result.recommended_action,
+        String::from_str(&env, "Hospital admission")
+    );
+}
+
+#[test]
+fn test_reminder_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let week = 7 * 24 * 60 * 60;
+
+    let recurring = client.create_reminder(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "inr_check"),
+        &2_000,
+        &Symbol::new(&env, "high"),
+        &Some(week),
+    );
+    let one_off = client.create_reminder(
+        &patient,
+        &provider,
+        &Symbol::new(&env, "flu_shot"),
+        &(1_000 + 2 * week),
+        &Symbol::new(&env, "low"),
+        &None,
+    );
+    assert_ne!(recurring, one_off);
+
+    // Both reminders survive and are indexed
+    assert_eq!(client.get_patient_due_reminders(&patient, &week).len(), 1);
+    assert_eq!(
+        client
+            .get_provider_due_reminders(&provider, &(3 * week))
+            .len(),
+        2
+    );
+
+    env.ledger().set_timestamp(3_000);
+    let overdue = client.get_patient_overdue_reminders(&patient);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue.get(0).unwrap().reminder_id, recurring);
+
+    client.acknowledge_reminder(&recurring, &patient);
+    assert_eq!(
+        client.get_reminder(&recurring).status,
+        ReminderStatus::Acknowledged
+    );
+
+    let next = client.complete_reminder(&recurring, &provider).unwrap();
+    let next_reminder = client.get_reminder(&next);
+    assert_eq!(next_reminder.due_date, 3_000 + week);
+    assert_eq!(next_reminder.status, ReminderStatus::Pending);
+    assert!(client.get_provider_overdue_reminders(&provider).is_empty());
+
+    client.cancel_reminder(&one_off, &provider);
+    assert_eq!(
+        client.try_complete_reminder(&one_off, &provider),
+        Err(Ok(Error::InvalidReminderState))
+    );
+    assert_eq!(
+        client.try_acknowledge_reminder(&next, &Address::generate(&env)),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+fn step(env: &Env, id: &str, interval: u64, branches: Vec<PathwayBranch>) -> PathwayStep {
+    PathwayStep {
+        step_id: Symbol::new(env, id),
+        description: String::from_str(env, id),
+        expected_interval: interval,
+        branches,
+    }
+}
+
+#[test]
+fn test_care_pathway_progress_and_variance() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    env.ledger().set_timestamp(0);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let day = 24 * 60 * 60;
+    let condition = String::from_str(&env, "Hypertension");
+    let mut steps = Vec::new(&env);
+    steps.push_back(step(
+        &env,
+        "assess",
+        day,
+        Vec::from_array(
+            &env,
+            [PathwayBranch {
+                outcome: Symbol::new(&env, "controlled"),
+                next_step: Symbol::new(&env, "follow_up"),
+            }],
+        ),
+    ));
+    steps.push_back(step(&env, "titrate", 14 * day, Vec::new(&env)));
+    steps.push_back(step(&env, "follow_up", 90 * day, Vec::new(&env)));
+    let pathway = CarePathway {
+        pathway_id: Symbol::new(&env, "htn_basic"),
+        condition: condition.clone(),
+        steps,
+    };
+    client.register_care_pathway(&admin, &pathway);
+
+    let patient = Address::generate(&env);
+    let suggested = client.suggest_care_pathway(&patient, &condition, &Vec::new(&env));
+    assert_eq!(suggested.pathway_id, Symbol::new(&env, "htn_basic"));
+
+    let provider = Address::generate(&env);
+    let instance = client.start_care_pathway(&patient, &provider, &pathway.pathway_id);
+
+    // Out-of-order completion is rejected
+    assert_eq!(
+        client.try_complete_pathway_step(
+            &instance,
+            &provider,
+            &Symbol::new(&env, "titrate"),
+            &None
+        ),
+        Err(Ok(Error::PathwayStepMismatch))
+    );
+
+    // Assessment two days late; uncontrolled falls through to titration
+    env.ledger().set_timestamp(3 * day);
+    let next = client.complete_pathway_step(
+        &instance,
+        &provider,
+        &Symbol::new(&env, "assess"),
+        &Some(Symbol::new(&env, "uncontrolled")),
+    );
+    assert_eq!(next, Some(Symbol::new(&env, "titrate")));
+
+    // Titration on time, then done early via the explicit order
+    env.ledger().set_timestamp(10 * day);
+    let next =
+        client.complete_pathway_step(&instance, &provider, &Symbol::new(&env, "titrate"), &None);
+    assert_eq!(next, Some(Symbol::new(&env, "follow_up")));
+
+    env.ledger().set_timestamp(110 * day);
+    let report = client.get_pathway_variance(&instance);
+    assert_eq!(report.steps_completed, 2);
+    assert_eq!(report.steps_late, 1);
+    assert_eq!(report.total_delay_seconds, 2 * day);
+    assert_eq!(report.current_step_overdue_seconds, 10 * day);
+}
+
+#[test]
+fn test_guideline_subscription_updates() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let by_guideline = Address::generate(&env);
+    let by_condition = Address::generate(&env);
+    let both = Address::generate(&env);
+
+    let guideline_id = String::from_str(&env, "G1");
+    let condition = String::from_str(&env, "HTN");
+    let hash = BytesN::from_array(&env, &[0u8; 32]);
+    let register = || {
+        client.register_clinical_guideline(
+            &admin,
+            &guideline_id,
+            &condition,
+            &hash,
+            &hash,
+            &Symbol::new(&env, "B"),
+        )
+    };
+
+    let unknown = SubscriptionTarget::Guideline(String::from_str(&env, "G404"));
+    assert_eq!(
+        client.try_subscribe_to_guideline(&by_guideline, &unknown),
+        Err(Ok(Error::GuidelineNotFound))
+    );
+
+    register();
+    assert_eq!(client.get_guideline_version(&guideline_id), 1);
+
+    let on_guideline = SubscriptionTarget::Guideline(guideline_id.clone());
+    let on_condition = SubscriptionTarget::Condition(condition.clone());
+    client.subscribe_to_guideline(&by_guideline, &on_guideline);
+    client.subscribe_to_guideline(&by_condition, &on_condition);
+    client.subscribe_to_guideline(&both, &on_guideline);
+    client.subscribe_to_guideline(&both, &on_condition);
+
+    env.ledger().set_timestamp(1_000);
+    register();
+    assert_eq!(client.get_guideline_version(&guideline_id), 2);
+
+    // One update per provider, even when subscribed through both targets
+    for provider in [&by_guideline, &by_condition, &both] {
+        let updates = client.get_unacknowledged_updates(provider);
+        assert_eq!(updates.len(), 1);
+        let update = updates.get(0).unwrap();
+        assert_eq!(update.version, 2);
+        assert_eq!(update.change, GuidelineChange::Registered);
+        assert_eq!(update.published_at, 1_000);
+    }
+
+    client.retire_guideline(&admin, &guideline_id);
+    assert!(client.is_guideline_retired(&guideline_id));
+    assert_eq!(
+        client.try_retire_guideline(&admin, &guideline_id),
+        Err(Ok(Error::GuidelineRetired))
+    );
+    let params = PatientParameters {
+        age_years: 50,
+        diagnosis_codes: Vec::new(&env),
+        lab_values: Vec::new(&env),
+        comorbidities: Vec::new(&env),
+    };
+    assert_eq!(
+        client.try_evaluate_guideline(&by_guideline, &by_guideline, &guideline_id, &params),
+        Err(Ok(Error::GuidelineRetired))
+    );
+
+    let updates = client.get_unacknowledged_updates(&by_condition);
+    assert_eq!(updates.len(), 2);
+    assert_eq!(updates.get(1).unwrap().change, GuidelineChange::Retired);
+
+    client.acknowledge_guideline_update(&by_condition, &guideline_id, &2);
+    client.acknowledge_guideline_update(&by_condition, &guideline_id, &2);
+    assert!(client.get_unacknowledged_updates(&by_condition).is_empty());
+    assert_eq!(
+        client.try_acknowledge_guideline_update(&by_condition, &guideline_id, &2),
+        Err(Ok(Error::UpdateNotFound))
+    );
+
+    // Unsubscribed providers stop receiving updates
+    client.unsubscribe_from_guideline(&by_guideline, &on_guideline);
+    register();
+    assert_eq!(client.get_unacknowledged_updates(&by_guideline).len(), 2);
+    assert_eq!(client.get_unacknowledged_updates(&by_condition).len(), 1);
+    assert_eq!(client.get_unacknowledged_updates(&both).len(), 3);
+    assert!(!client.is_guideline_retired(&guideline_id));
+
+    // The pending queue is capped; the oldest updates are dropped first
+    for _ in 0..subscriptions::MAX_PENDING_UPDATES {
+        register();
+    }
+    let updates = client.get_unacknowledged_updates(&both);
+    assert_eq!(updates.len(), subscriptions::MAX_PENDING_UPDATES);
+    assert_eq!(updates.get(0).unwrap().version, 4);
+    assert_eq!(updates.last().unwrap().version, 53);
+}
+
+#[test]
+fn test_drug_interaction_checks() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let warfarin = String::from_str(&env, "Warfarin");
+    let aspirin = String::from_str(&env, "Aspirin");
+    let lisinopril = String::from_str(&env, "Lisinopril");
+    let spironolactone = String::from_str(&env, "Spironolactone");
+    let unlisted = String::from_str(&env, "Acetaminophen");
+    for (medication, class) in [
+        (&warfarin, "anticoag"),
+        (&aspirin, "nsaid"),
+        (&lisinopril, "ace_inhib"),
+        (&spironolactone, "k_sparing"),
+    ] {
+        client.register_drug_class(&admin, medication, &Symbol::new(&env, class));
+    }
+    assert_eq!(
+        client.get_drug_class(&warfarin),
+        Some(Symbol::new(&env, "anticoag"))
+    );
+
+    let interaction = |a: &str, b: &str, severity: InteractionSeverity| DrugInteraction {
+        class_a: Symbol::new(&env, a),
+        class_b: Symbol::new(&env, b),
+        severity,
+        mechanism_hash: BytesN::from_array(&env, &[severity as u8; 32]),
+    };
+    client.register_drug_interaction(
+        &admin,
+        &interaction("nsaid", "ace_inhib", InteractionSeverity::Moderate),
+    );
+    client.register_drug_interaction(
+        &admin,
+        &interaction("anticoag", "nsaid", InteractionSeverity::Major),
+    );
+    client.register_drug_interaction(
+        &admin,
+        &interaction("ace_inhib", "k_sparing", InteractionSeverity::Minor),
+    );
+
+    // Lookup is independent of class order
+    let stored =
+        client.get_drug_interaction(&Symbol::new(&env, "nsaid"), &Symbol::new(&env, "anticoag"));
+    assert_eq!(stored.severity, InteractionSeverity::Major);
+
+    let meds = Vec::from_array(
+        &env,
+        [
+            lisinopril.clone(),
+            unlisted,
+            aspirin.clone(),
+            spironolactone,
+            warfarin.clone(),
+        ],
+    );
+    let warnings = client.check_drug_interactions(&meds);
+    assert_eq!(warnings.len(), 3);
+    assert_eq!(
+        warnings.get(0).unwrap().severity,
+        InteractionSeverity::Major
+    );
+    assert_eq!(
+        warnings.get(1).unwrap().severity,
+        InteractionSeverity::Moderate
+    );
+    assert_eq!(
+        warnings.get(2).unwrap().severity,
+        InteractionSeverity::Minor
+    );
+    let top = warnings.get(0).unwrap();
+    assert_eq!(top.medication_a, aspirin);
+    assert_eq!(top.medication_b, warfarin);
+
+    client.remove_drug_interaction(
+        &admin,
+        &Symbol::new(&env, "anticoag"),
+        &Symbol::new(&env, "nsaid"),
+    );
+    assert_eq!(client.check_drug_interactions(&meds).len(), 2);
+    assert_eq!(
+        client.try_remove_drug_interaction(
+            &admin,
+            &Symbol::new(&env, "anticoag"),
+            &Symbol::new(&env, "nsaid"),
+        ),
+        Err(Ok(Error::InteractionNotFound))
+    );
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_register_drug_interaction(
+            &outsider,
+            &interaction("anticoag", "nsaid", InteractionSeverity::Major),
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
+
+#[test]
+fn test_order_set_checklists_and_compliance() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let condition = String::from_str(&env, "CAP");
+
+    let item = |id: &str, kind: OrderItemKind, required: bool| OrderSetItem {
+        item_id: Symbol::new(&env, id),
+        kind,
+        description: String::from_str(&env, id),
+        required,
+    };
+    let mut order_set = OrderSet {
+        order_set_id: Symbol::new(&env, "cap_admit"),
+        name: String::from_str(&env, "CAP admission"),
+        condition: condition.clone(),
+        guideline_id: Some(String::from_str(&env, "CAP1")),
+        items: Vec::from_array(
+            &env,
+            [
+                item("cbc", OrderItemKind::Lab, true),
+                item("ceftriax", OrderItemKind::Medication, true),
+                item("cxr", OrderItemKind::Imaging, false),
+            ],
+        ),
+    };
+
+    // The linked guideline must exist
+    assert_eq!(
+        client.try_register_order_set(&admin, &order_set),
+        Err(Ok(Error::GuidelineNotFound))
+    );
+    let hash = BytesN::from_array(&env, &[0u8; 32]);
+    client.register_clinical_guideline(
+        &admin,
+        &String::from_str(&env, "CAP1"),
+        &condition,
+        &hash,
+        &hash,
+        &Symbol::new(&env, "A"),
+    );
+    client.register_order_set(&admin, &order_set);
+    assert_eq!(
+        client.get_condition_order_sets(&condition),
+        Vec::from_array(&env, [order_set.order_set_id.clone()])
+    );
+
+    let mut duplicate = order_set.clone();
+    duplicate
+        .items
+        .push_back(item("cbc", OrderItemKind::Lab, false));
+    assert_eq!(
+        client.try_register_order_set(&admin, &duplicate),
+        Err(Ok(Error::InvalidInput))
+    );
+    order_set.items = Vec::new(&env);
+    assert_eq!(
+        client.try_register_order_set(&admin, &order_set),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    let set_id = Symbol::new(&env, "cap_admit");
+    let first = client.instantiate_order_set(&patient, &provider, &set_id, &None);
+    let second = client.instantiate_order_set(&patient, &provider, &set_id, &None);
+    assert_eq!(client.get_order_checklist(&first).items.len(), 3);
+
+    env.ledger().set_timestamp(500);
+    client.complete_order_item(&first, &provider, &Symbol::new(&env, "cbc"));
+    client.complete_order_item(&first, &provider, &Symbol::new(&env, "ceftriax"));
+    client.complete_order_item(&second, &provider, &Symbol::new(&env, "cxr"));
+
+    let checklist = client.get_order_checklist(&first);
+    let cbc = checklist.items.get(0).unwrap();
+    assert_eq!(cbc.completed_by, Some(provider.clone()));
+    assert_eq!(cbc.completed_at, Some(500));
+
+    assert_eq!(
+        client.try_complete_order_item(&first, &provider, &Symbol::new(&env, "cbc")),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_complete_order_item(&first, &provider, &Symbol::new(&env, "mri")),
+        Err(Ok(Error::OrderItemNotFound))
+    );
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_complete_order_item(&second, &other, &Symbol::new(&env, "cbc")),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    let compliance = client.get_order_set_compliance(&set_id);
+    assert_eq!(compliance.checklists, 2);
+    assert_eq!(compliance.checklists_complete, 1);
+    assert_eq!(compliance.items_total, 6);
+    assert_eq!(compliance.items_completed, 3);
+    assert_eq!(compliance.required_total, 4);
+    assert_eq!(compliance.required_completed, 2);
+}
+
+fn vitals(env: &Env, rr: u32, spo2: u32, sbp: u32, hr: u32, consciousness: &str) -> VitalSigns {
+    VitalSigns {
+        respiratory_rate_x100: rr * 100,
+        spo2_x100: spo2 * 100,
+        on_supplemental_o2: false,
+        temperature_x100: 3700,
+        systolic_bp_x100: sbp * 100,
+        heart_rate_x100: hr * 100,
+        consciousness: Symbol::new(env, consciousness),
+    }
+}
+
+#[test]
+fn test_early_warning_scores_and_escalation() {
+    let env = Env::default();
+    let contract_id = env.register(ClinicalGuidelineContract, ());
+    let client = ClinicalGuidelineContractClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+
+    let normal =
+        client.submit_vital_signs(&patient, &provider, &vitals(&env, 16, 98, 120, 72, "alert"));
+    assert_eq!(normal.news2_score, 0);
+    assert_eq!(normal.qsofa_score, 0);
+    assert_eq!(normal.band, EarlyWarningBand::Low);
+
+    // A single red parameter (SpO2 90%) without a high aggregate
+    let red =
+        client.submit_vital_signs(&patient, &provider, &vitals(&env, 16, 90, 120, 72, "alert"));
+    assert_eq!(red.news2_score, 3);
+    assert_eq!(red.band, EarlyWarningBand::LowMedium);
+
+    // RR 23 (2), SpO2 93 (2), SBP 98 (2), HR 115 (2), confusion (3)
+    let septic = vitals(&env, 23, 93, 98, 115, "confusion");
+    let first = client.submit_vital_signs(&patient, &provider, &septic);
+    assert_eq!(first.news2_score, 11);
+    assert_eq!(first.qsofa_score, 3);
+    assert_eq!(first.band, EarlyWarningBand::High);
+    assert!(!first.escalated);
+
+    // The second consecutive high score escalates; later ones in the run do not
+    let second = client.submit_vital_signs(&patient, &provider, &septic);
+    assert!(second.escalated);
+    let third = client.submit_vital_signs(&patient, &provider, &septic);
+    assert!(!third.escalated);
+
+    client.set_escalation_thresholds(
+        &admin,
+        &EscalationThresholds {
+            news2_threshold: 3,
+            qsofa_threshold: 2,
+            consecutive_scores: 1,
+        },
+    );
+    // Still the same run of crossing scores, so no new escalation
+    let after_reset =
+        client.submit_vital_signs(&patient, &provider, &vitals(&env, 16, 90, 120, 72, "alert"));
+    assert!(!after_reset.escalated);
+    client.submit_vital_signs(&patient, &provider, &vitals(&env, 16, 98, 120, 72, "alert"));
+    let single =
+        client.submit_vital_signs(&patient, &provider, &vitals(&env, 16, 90, 120, 72, "alert"));
+    assert!(single.escalated);
+
+    let history = client.get_early_warning_history(&patient);
+    assert_eq!(history.len(), 8);
+    assert_eq!(history.get(3).unwrap(), second);
+
+    assert_eq!(
+        client.try_submit_vital_signs(
+            &patient,
+            &provider,
+            &vitals(&env, 16, 98, 120, 72, "drowsy")
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_escalation_thresholds(
+            &admin,
+            &EscalationThresholds {
+                news2_threshold: 5,
+                qsofa_threshold: 2,
+                consecutive_scores: 0,
+            },
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+}
@@ -1,17 +1,22 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracterror, contractevent, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, symbol_short, Address, Bytes, BytesN,
+    Env, Map, String, Symbol, Vec,
 };
 
+mod patient_registry;
 mod storage;
 mod types;
 mod validation;
 
+pub use patient_registry::{PatientRegistry, PatientRegistryClient};
 pub use storage::*;
 pub use types::*;
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 /// Events for clinical trial operations
 #[contractevent]
 pub struct TrialRegistered {
@@ -45,6 +50,54 @@ pub struct SafetyReportSubmitted {
     pub reporting_period: u64,
 }
 
+#[contractevent]
+pub struct BlindedAssessorRegistered {
+    pub trial_record_id: u64,
+    pub assessor: Address,
+}
+
+#[contractevent]
+pub struct BlindedAssessmentSubmitted {
+    pub assessment_id: u64,
+    pub enrollment_id: u64,
+    pub outcome_measure: Symbol,
+}
+
+#[contractevent]
+pub struct ParticipantScreened {
+    pub screening_id: u64,
+    pub trial_record_id: u64,
+    pub site_id: Symbol,
+    pub passed: bool,
+}
+
+#[contractevent]
+pub struct StipendAccrued {
+    pub enrollment_id: u64,
+    pub visit_number: u32,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct StipendPaid {
+    pub enrollment_id: u64,
+    pub amount: i128,
+}
+
+#[contractevent]
+pub struct BiospecimenRegistered {
+    pub specimen_id: u64,
+    pub enrollment_id: u64,
+    pub specimen_type: Symbol,
+}
+
+#[contractevent]
+pub struct CustodyTransferred {
+    pub specimen_id: u64,
+    pub to_role: Symbol,
+    pub transfer_time: u64,
+}
+
 /// Error codes for clinical trial operations
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -67,13 +120,32 @@ pub enum Error {
     VisitNotFound = 15,
     EventNotFound = 16,
     TrialNotActive = 17,
+    PatientNotRegistered = 18,
+    BlindedAssessorExists = 19,
+    NotBlindedAssessor = 20,
+    InvalidBlindedAssessor = 21,
+    BlindedAssessmentNotFound = 22,
+    ScreeningNotFound = 23,
+    InvalidScreenFailReason = 24,
+    ScreenFailed = 25,
+    ScreeningAlreadyLinked = 26,
+    ScreeningMismatch = 27,
+    InvalidStipendAmount = 28,
+    PaymentExceedsBalance = 29,
+    SpecimenNotFound = 30,
+    InvalidSpecimenType = 31,
+    InvalidAliquotCount = 32,
+    NotCustodian = 33,
+    InvalidCustodyRole = 34,
+    InvalidStorageCondition = 35,
+    DuplicatePayment = 36,
 }
 
 #[contract]
-pub struct ClinicalTrial;
+pub struct ClinicalTrialContract;
 
 #[contractimpl]
-impl ClinicalTrial {
+impl ClinicalTrialContract {
     /// Initialize the contract with an admin address
     pub fn initialize(env: Env, admin: Address) {
         admin.require_auth();
@@ -88,6 +160,30 @@ impl ClinicalTrial {
             .instance()
             .set(&DataKey::EnrollmentCounter, &0u64);
         env.storage().instance().set(&DataKey::EventCounter, &0u64);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+    }
+
+    /// Configure the patient-registry contract used to validate enrollments
+    pub fn set_patient_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        strict_mode: bool,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        storage::save_patient_registry(
+            &env,
+            &PatientRegistryConfig {
+                registry,
+                strict_mode,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_patient_registry(env: Env) -> Option<PatientRegistryConfig> {
+        storage::get_patient_registry(&env)
     }
 
     /// Register a new clinical trial
@@ -172,7 +268,7 @@ impl ClinicalTrial {
         env: Env,
         trial_record_id: u64,
         patient_id: Address,
-        patient_data_hash: BytesN<32>,
+        _patient_data_hash: BytesN<32>,
     ) -> Result<EligibilityResult, Error> {
         patient_id.require_auth();
 
@@ -225,6 +321,9 @@ impl ClinicalTrial {
         // Validate date
         validation::validate_date_not_future(&env, enrollment_date)?;
 
+        // Reject unregistered patients when strict mode is on
+        storage::require_registered_patient(&env, &patient_id)?;
+
         // Verify trial exists and is active
         let mut trial = storage::get_trial(&env, trial_record_id)?;
         if trial.status != TrialStatus::Active {
@@ -306,6 +405,21 @@ impl ClinicalTrial {
 
         storage::save_study_visit(&env, &visit);
 
+        // Accrue the scheduled stipend for this visit
+        if let Some(amount) = storage::accrue_visit_stipend(
+            &env,
+            enrollment.trial_record_id,
+            enrollment_id,
+            visit_number,
+        ) {
+            StipendAccrued {
+                enrollment_id,
+                visit_number,
+                amount,
+            }
+            .publish(&env);
+        }
+
         Ok(())
     }
 
@@ -402,6 +516,14 @@ impl ClinicalTrial {
         }
         storage::save_trial(&env, &trial);
 
+        // Count the withdrawal in the screening funnel
+        if let Some(screening_id) = storage::get_enrollment_screening(&env, enrollment_id) {
+            let screening = storage::get_screening(&env, screening_id)?;
+            storage::update_funnel(&env, trial.trial_record_id, &screening.site_id, |f| {
+                f.withdrawn += 1
+            });
+        }
+
         // Emit event
         ParticipantWithdrawn {
             enrollment_id,
@@ -521,9 +643,669 @@ impl ClinicalTrial {
 
         // Generate a hash representing the exported dataset
         // In production, this would be a hash of the actual de-identified data
-        let export_hash = env.crypto().sha256(&export_count.to_be_bytes().into());
+        let export_hash = env
+            .crypto()
+            .sha256(&Bytes::from_array(&env, &export_count.to_be_bytes()));
+
+        Ok(export_hash.into())
+    }
+
+    /// Record a screening attempt at a site. A `fail_reason` marks the
+    /// screen as failed and must be a known screen-fail category.
+    pub fn record_screening(
+        env: Env,
+        trial_record_id: u64,
+        principal_investigator: Address,
+        site_id: Symbol,
+        patient_id: Address,
+        screening_date: u64,
+        fail_reason: Option<Symbol>,
+    ) -> Result<u64, Error> {
+        principal_investigator.require_auth();
+
+        // Verify trial exists and PI is authorized
+        let trial = storage::get_trial(&env, trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        // Validate inputs
+        validation::validate_date_not_future(&env, screening_date)?;
+        if let Some(reason) = &fail_reason {
+            validation::validate_screen_fail_reason(reason)?;
+        }
+
+        // Generate unique screening ID
+        let screening_id = storage::get_next_screening_id(&env);
+        let passed = fail_reason.is_none();
+
+        let screening = ScreeningRecord {
+            screening_id,
+            trial_record_id,
+            site_id: site_id.clone(),
+            patient_id,
+            screening_date,
+            passed,
+            fail_reason: fail_reason.clone(),
+            enrollment_id: None,
+        };
+
+        storage::save_screening(&env, &screening);
+        storage::update_funnel(&env, trial_record_id, &site_id, |f| {
+            f.screened += 1;
+            if let Some(reason) = &fail_reason {
+                f.screen_failed += 1;
+                let count = f.failures_by_reason.get(reason.clone()).unwrap_or(0);
+                f.failures_by_reason.set(reason.clone(), count + 1);
+            }
+        });
+
+        // Emit event
+        ParticipantScreened {
+            screening_id,
+            trial_record_id,
+            site_id,
+            passed,
+        }
+        .publish(&env);
+
+        Ok(screening_id)
+    }
+
+    /// Link a successful screen to the enrollment it led to
+    pub fn link_screening_to_enrollment(
+        env: Env,
+        screening_id: u64,
+        principal_investigator: Address,
+        enrollment_id: u64,
+    ) -> Result<(), Error> {
+        principal_investigator.require_auth();
+
+        let mut screening = storage::get_screening(&env, screening_id)?;
+
+        // Verify trial exists and PI is authorized
+        let trial = storage::get_trial(&env, screening.trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        if !screening.passed {
+            return Err(Error::ScreenFailed);
+        }
+        if screening.enrollment_id.is_some()
+            || storage::get_enrollment_screening(&env, enrollment_id).is_some()
+        {
+            return Err(Error::ScreeningAlreadyLinked);
+        }
+
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+        if enrollment.trial_record_id != screening.trial_record_id
+            || enrollment.patient_id != screening.patient_id
+        {
+            return Err(Error::ScreeningMismatch);
+        }
+
+        screening.enrollment_id = Some(enrollment_id);
+        storage::save_screening(&env, &screening);
+        storage::save_enrollment_screening(&env, enrollment_id, screening_id);
+
+        let already_withdrawn = enrollment.status == EnrollmentStatus::Withdrawn;
+        storage::update_funnel(&env, screening.trial_record_id, &screening.site_id, |f| {
+            f.enrolled += 1;
+            if already_withdrawn {
+                f.withdrawn += 1;
+            }
+        });
+
+        Ok(())
+    }
+
+    /// Get a screening record (PI only)
+    pub fn get_screening(
+        env: Env,
+        screening_id: u64,
+        requester: Address,
+    ) -> Result<ScreeningRecord, Error> {
+        requester.require_auth();
+
+        let screening = storage::get_screening(&env, screening_id)?;
+        let trial = storage::get_trial(&env, screening.trial_record_id)?;
+
+        // Check authorization
+        if requester != trial.principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(screening)
+    }
+
+    /// Get enrollment funnel statistics for a trial. Enrolled and withdrawn
+    /// counts cover enrollments linked to a screening.
+    pub fn get_trial_funnel(env: Env, trial_record_id: u64) -> Result<FunnelStats, Error> {
+        storage::get_trial(&env, trial_record_id)?;
+        Ok(storage::get_trial_funnel(&env, trial_record_id))
+    }
+
+    /// Get enrollment funnel statistics for one site of a trial
+    pub fn get_site_funnel(
+        env: Env,
+        trial_record_id: u64,
+        site_id: Symbol,
+    ) -> Result<FunnelStats, Error> {
+        storage::get_trial(&env, trial_record_id)?;
+        Ok(storage::get_site_funnel(&env, trial_record_id, &site_id))
+    }
+
+    /// Set the stipend schedule for a trial. Amounts are in stroops and
+    /// accrue when the matching visit is recorded.
+    pub fn set_stipend_schedule(
+        env: Env,
+        trial_record_id: u64,
+        principal_investigator: Address,
+        entries: Vec<StipendEntry>,
+    ) -> Result<(), Error> {
+        principal_investigator.require_auth();
+
+        // Verify trial exists and PI is authorized
+        let trial = storage::get_trial(&env, trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut schedule = Map::new(&env);
+        for entry in entries.iter() {
+            if entry.amount <= 0 {
+                return Err(Error::InvalidStipendAmount);
+            }
+            schedule.set(entry.visit_number, entry.amount);
+        }
+
+        storage::save_stipend_schedule(&env, trial_record_id, &schedule);
+
+        Ok(())
+    }
+
+    /// Get the stipend schedule for a trial
+    pub fn get_stipend_schedule(env: Env, trial_record_id: u64) -> Result<Map<u32, i128>, Error> {
+        storage::get_trial(&env, trial_record_id)?;
+        Ok(storage::get_stipend_schedule(&env, trial_record_id))
+    }
+
+    /// Record a stipend disbursement against an enrollment's outstanding
+    /// balance. Each transaction reference can be recorded only once.
+    pub fn record_stipend_payment(
+        env: Env,
+        enrollment_id: u64,
+        principal_investigator: Address,
+        amount: i128,
+        transaction_ref: String,
+    ) -> Result<(), Error> {
+        principal_investigator.require_auth();
+
+        // Verify enrollment exists and PI is authorized
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        if amount <= 0 {
+            return Err(Error::InvalidStipendAmount);
+        }
+        // A transaction reference is recorded once per enrollment
+        if storage::get_stipend_payments(&env, enrollment_id)
+            .iter()
+            .any(|payment| payment.transaction_ref == transaction_ref)
+        {
+            return Err(Error::DuplicatePayment);
+        }
+        let mut balance = storage::get_stipend_balance(&env, enrollment_id);
+        if amount > balance.outstanding {
+            return Err(Error::PaymentExceedsBalance);
+        }
 
-        Ok(export_hash)
+        balance.paid += amount;
+        balance.outstanding -= amount;
+        storage::save_stipend_balance(&env, &balance);
+
+        let payment = StipendPayment {
+            enrollment_id,
+            amount,
+            transaction_ref,
+            paid_by: principal_investigator,
+            paid_at: env.ledger().timestamp(),
+        };
+        storage::add_stipend_payment(&env, &payment);
+
+        // Emit event
+        StipendPaid {
+            enrollment_id,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the stipend balance for an enrollment (patient or PI)
+    pub fn get_stipend_balance(
+        env: Env,
+        enrollment_id: u64,
+        requester: Address,
+    ) -> Result<StipendBalance, Error> {
+        requester.require_auth();
+
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+
+        // Check authorization (patient or PI)
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+        if requester != enrollment.patient_id && requester != trial.principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(storage::get_stipend_balance(&env, enrollment_id))
+    }
+
+    /// Get stipend payments for an enrollment (patient or PI)
+    pub fn get_stipend_payments(
+        env: Env,
+        enrollment_id: u64,
+        requester: Address,
+    ) -> Result<Vec<StipendPayment>, Error> {
+        requester.require_auth();
+
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+
+        // Check authorization (patient or PI)
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+        if requester != enrollment.patient_id && requester != trial.principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(storage::get_stipend_payments(&env, enrollment_id))
+    }
+
+    /// Register a biospecimen collected at a recorded study visit. The PI's
+    /// site holds initial custody.
+    pub fn register_biospecimen(
+        env: Env,
+        enrollment_id: u64,
+        principal_investigator: Address,
+        visit_number: u32,
+        specimen_type: Symbol,
+        collection_time: u64,
+        aliquot_count: u32,
+        storage_condition: Symbol,
+    ) -> Result<u64, Error> {
+        principal_investigator.require_auth();
+
+        // Verify enrollment exists and PI is authorized
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+        storage::get_study_visit(&env, enrollment_id, visit_number)?;
+
+        // Validate inputs
+        validation::validate_specimen_type(&specimen_type)?;
+        validation::validate_storage_condition(&storage_condition)?;
+        validation::validate_date_not_future(&env, collection_time)?;
+        if aliquot_count == 0 {
+            return Err(Error::InvalidAliquotCount);
+        }
+
+        // Generate unique specimen ID
+        let specimen_id = storage::get_next_specimen_id(&env);
+
+        let specimen = Biospecimen {
+            specimen_id,
+            enrollment_id,
+            visit_number,
+            specimen_type: specimen_type.clone(),
+            collection_time,
+            aliquot_count,
+            current_custodian: principal_investigator.clone(),
+            custodian_role: symbol_short!("site"),
+            storage_condition: storage_condition.clone(),
+        };
+
+        storage::save_specimen(&env, &specimen);
+        storage::add_custody_event(
+            &env,
+            &CustodyEvent {
+                specimen_id,
+                event_type: symbol_short!("collected"),
+                from_custodian: None,
+                custodian: principal_investigator,
+                custodian_role: symbol_short!("site"),
+                storage_condition,
+                condition_in_range: true,
+                occurred_at: collection_time,
+            },
+        );
+
+        // Emit event
+        BiospecimenRegistered {
+            specimen_id,
+            enrollment_id,
+            specimen_type,
+        }
+        .publish(&env);
+
+        Ok(specimen_id)
+    }
+
+    /// Transfer custody of a specimen. Both the current and the receiving
+    /// custodian sign the transfer.
+    pub fn transfer_specimen_custody(
+        env: Env,
+        specimen_id: u64,
+        from_custodian: Address,
+        to_custodian: Address,
+        to_role: Symbol,
+        transfer_time: u64,
+        storage_condition: Symbol,
+    ) -> Result<(), Error> {
+        from_custodian.require_auth();
+        to_custodian.require_auth();
+
+        let mut specimen = storage::get_specimen(&env, specimen_id)?;
+        if specimen.current_custodian != from_custodian {
+            return Err(Error::NotCustodian);
+        }
+
+        // Validate inputs
+        validation::validate_custody_role(&to_role)?;
+        validation::validate_storage_condition(&storage_condition)?;
+        validation::validate_date_not_future(&env, transfer_time)?;
+
+        specimen.current_custodian = to_custodian.clone();
+        specimen.custodian_role = to_role.clone();
+        specimen.storage_condition = storage_condition.clone();
+        storage::save_specimen(&env, &specimen);
+
+        storage::add_custody_event(
+            &env,
+            &CustodyEvent {
+                specimen_id,
+                event_type: symbol_short!("transfer"),
+                from_custodian: Some(from_custodian),
+                custodian: to_custodian,
+                custodian_role: to_role.clone(),
+                storage_condition,
+                condition_in_range: true,
+                occurred_at: transfer_time,
+            },
+        );
+
+        // Emit event
+        CustodyTransferred {
+            specimen_id,
+            to_role,
+            transfer_time,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Attest the storage condition of a specimen held by the custodian
+    pub fn attest_storage_condition(
+        env: Env,
+        specimen_id: u64,
+        custodian: Address,
+        storage_condition: Symbol,
+        condition_in_range: bool,
+        attested_at: u64,
+    ) -> Result<(), Error> {
+        custodian.require_auth();
+
+        let mut specimen = storage::get_specimen(&env, specimen_id)?;
+        if specimen.current_custodian != custodian {
+            return Err(Error::NotCustodian);
+        }
+
+        // Validate inputs
+        validation::validate_storage_condition(&storage_condition)?;
+        validation::validate_date_not_future(&env, attested_at)?;
+
+        specimen.storage_condition = storage_condition.clone();
+        storage::save_specimen(&env, &specimen);
+
+        storage::add_custody_event(
+            &env,
+            &CustodyEvent {
+                specimen_id,
+                event_type: symbol_short!("storage"),
+                from_custodian: None,
+                custodian,
+                custodian_role: specimen.custodian_role,
+                storage_condition,
+                condition_in_range,
+                occurred_at: attested_at,
+            },
+        );
+
+        Ok(())
+    }
+
+    /// Get a biospecimen record
+    pub fn get_biospecimen(env: Env, specimen_id: u64) -> Result<Biospecimen, Error> {
+        storage::get_specimen(&env, specimen_id)
+    }
+
+    /// Get the full chain of custody for a specimen (PI or any custodian in the chain)
+    pub fn get_chain_of_custody(
+        env: Env,
+        specimen_id: u64,
+        requester: Address,
+    ) -> Result<Vec<CustodyEvent>, Error> {
+        requester.require_auth();
+
+        let specimen = storage::get_specimen(&env, specimen_id)?;
+        let enrollment = storage::get_enrollment(&env, specimen.enrollment_id)?;
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+        let log = storage::get_custody_log(&env, specimen_id);
+
+        // Check authorization
+        if requester != trial.principal_investigator
+            && !log.iter().any(|event| event.custodian == requester)
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(log)
+    }
+
+    /// Register a blinded outcome assessor for a trial. The PI knows arm
+    /// assignments and so cannot act as a blinded assessor.
+    pub fn register_blinded_assessor(
+        env: Env,
+        trial_record_id: u64,
+        principal_investigator: Address,
+        assessor: Address,
+    ) -> Result<(), Error> {
+        principal_investigator.require_auth();
+
+        // Verify trial exists and PI is authorized
+        let trial = storage::get_trial(&env, trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+        if assessor == principal_investigator {
+            return Err(Error::InvalidBlindedAssessor);
+        }
+
+        let mut assessors = storage::get_trial_assessors(&env, trial_record_id);
+        if assessors.contains(&assessor) {
+            return Err(Error::BlindedAssessorExists);
+        }
+        assessors.push_back(assessor.clone());
+        storage::save_trial_assessors(&env, trial_record_id, &assessors);
+
+        // Emit event
+        BlindedAssessorRegistered {
+            trial_record_id,
+            assessor,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Remove a blinded outcome assessor from a trial
+    pub fn remove_blinded_assessor(
+        env: Env,
+        trial_record_id: u64,
+        principal_investigator: Address,
+        assessor: Address,
+    ) -> Result<(), Error> {
+        principal_investigator.require_auth();
+
+        // Verify trial exists and PI is authorized
+        let trial = storage::get_trial(&env, trial_record_id)?;
+        if trial.principal_investigator != principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut assessors = storage::get_trial_assessors(&env, trial_record_id);
+        let index = assessors
+            .first_index_of(&assessor)
+            .ok_or(Error::NotBlindedAssessor)?;
+        assessors.remove(index);
+        storage::save_trial_assessors(&env, trial_record_id, &assessors);
+
+        Ok(())
+    }
+
+    /// Get the blinded assessors registered for a trial
+    pub fn get_blinded_assessors(env: Env, trial_record_id: u64) -> Result<Vec<Address>, Error> {
+        storage::get_trial(&env, trial_record_id)?;
+        Ok(storage::get_trial_assessors(&env, trial_record_id))
+    }
+
+    /// List a trial's enrollments for a blinded assessor, without arm assignments
+    pub fn get_blinded_enrollments(
+        env: Env,
+        trial_record_id: u64,
+        assessor: Address,
+    ) -> Result<Vec<BlindedEnrollmentView>, Error> {
+        assessor.require_auth();
+
+        storage::get_trial(&env, trial_record_id)?;
+        if !storage::is_trial_assessor(&env, trial_record_id, &assessor) {
+            return Err(Error::NotBlindedAssessor);
+        }
+
+        let mut views = Vec::new(&env);
+        for enrollment_id in storage::get_trial_enrollments(&env, trial_record_id).iter() {
+            if let Ok(enrollment) = storage::get_enrollment(&env, enrollment_id) {
+                views.push_back(BlindedEnrollmentView {
+                    enrollment_id,
+                    participant_id: enrollment.participant_id,
+                    status: enrollment.status,
+                });
+            }
+        }
+
+        Ok(views)
+    }
+
+    /// Submit a blinded outcome assessment for an enrollment
+    pub fn submit_blinded_assessment(
+        env: Env,
+        enrollment_id: u64,
+        assessor: Address,
+        outcome_measure: Symbol,
+        assessment_date: u64,
+        outcome_data_hash: BytesN<32>,
+    ) -> Result<u64, Error> {
+        assessor.require_auth();
+
+        // Verify enrollment exists and assessor is registered for its trial
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+        if !storage::is_trial_assessor(&env, enrollment.trial_record_id, &assessor) {
+            return Err(Error::NotBlindedAssessor);
+        }
+
+        // Validate date
+        validation::validate_date_not_future(&env, assessment_date)?;
+
+        // Generate unique assessment ID
+        let assessment_id = storage::get_next_blinded_assessment_id(&env);
+
+        let assessment = BlindedOutcomeAssessment {
+            assessment_id,
+            enrollment_id,
+            trial_record_id: enrollment.trial_record_id,
+            participant_id: enrollment.participant_id,
+            assessor,
+            outcome_measure: outcome_measure.clone(),
+            assessment_date,
+            outcome_data_hash,
+            recorded_at: env.ledger().timestamp(),
+        };
+
+        storage::save_blinded_assessment(&env, &assessment);
+
+        // Emit event
+        BlindedAssessmentSubmitted {
+            assessment_id,
+            enrollment_id,
+            outcome_measure,
+        }
+        .publish(&env);
+
+        Ok(assessment_id)
+    }
+
+    /// Get a blinded outcome assessment (submitting assessor or PI)
+    pub fn get_blinded_assessment(
+        env: Env,
+        assessment_id: u64,
+        requester: Address,
+    ) -> Result<BlindedOutcomeAssessment, Error> {
+        requester.require_auth();
+
+        let assessment = storage::get_blinded_assessment(&env, assessment_id)?;
+        let trial = storage::get_trial(&env, assessment.trial_record_id)?;
+
+        // Check authorization
+        if requester != assessment.assessor && requester != trial.principal_investigator {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(assessment)
+    }
+
+    /// Get the blinded outcome assessments for an enrollment (trial assessors or PI)
+    pub fn get_enrollment_assessments(
+        env: Env,
+        enrollment_id: u64,
+        requester: Address,
+    ) -> Result<Vec<BlindedOutcomeAssessment>, Error> {
+        requester.require_auth();
+
+        let enrollment = storage::get_enrollment(&env, enrollment_id)?;
+        let trial = storage::get_trial(&env, enrollment.trial_record_id)?;
+
+        // Check authorization
+        if requester != trial.principal_investigator
+            && !storage::is_trial_assessor(&env, trial.trial_record_id, &requester)
+        {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut assessments = Vec::new(&env);
+        for assessment_id in storage::get_enrollment_blinded_assessments(&env, enrollment_id).iter()
+        {
+            if let Ok(assessment) = storage::get_blinded_assessment(&env, assessment_id) {
+                assessments.push_back(assessment);
+            }
+        }
+
+        Ok(assessments)
     }
 
     /// Get trial information
@@ -569,6 +1351,36 @@ impl ClinicalTrial {
 
         Ok(event)
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::Unauthorized)?;
+        if stored_admin != *admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
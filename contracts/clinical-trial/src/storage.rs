@@ -1,8 +1,10 @@
-use soroban_sdk::{Address, Env, Vec};
+use soroban_sdk::{Address, Env, Map, Symbol, Vec};
 
 use crate::{
-    AdverseEventReport, ClinicalTrial, DataKey, EligibilityCriteria, Error,
-    ParticipantEnrollment, ProtocolDeviation, SafetyReport, StudyVisit,
+    AdverseEventReport, Biospecimen, BlindedOutcomeAssessment, ClinicalTrial, CustodyEvent,
+    DataKey, EligibilityCriteria, Error, FunnelStats, ParticipantEnrollment, PatientRegistryClient,
+    PatientRegistryConfig, ProtocolDeviation, SafetyReport, ScreeningRecord, StipendBalance,
+    StipendPayment, StudyVisit,
 };
 
 /// Get the next trial record ID and increment counter
@@ -232,3 +234,319 @@ pub fn check_duplicate_enrollment(
 
     false
 }
+
+/// Save the patient-registry hook configuration
+pub fn save_patient_registry(env: &Env, config: &PatientRegistryConfig) {
+    env.storage()
+        .instance()
+        .set(&DataKey::PatientRegistry, config);
+}
+
+/// Get the patient-registry hook configuration, if set
+pub fn get_patient_registry(env: &Env) -> Option<PatientRegistryConfig> {
+    env.storage().instance().get(&DataKey::PatientRegistry)
+}
+
+/// In strict mode, verify the patient exists in the configured patient registry
+pub fn require_registered_patient(env: &Env, patient_id: &Address) -> Result<(), Error> {
+    match get_patient_registry(env) {
+        Some(config) if config.strict_mode => {
+            let client = PatientRegistryClient::new(env, &config.registry);
+            if client.is_patient_registered(patient_id) {
+                Ok(())
+            } else {
+                Err(Error::PatientNotRegistered)
+            }
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Get the next blinded assessment ID and increment counter
+pub fn get_next_blinded_assessment_id(env: &Env) -> u64 {
+    let current_id = env
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::BlindedAssessmentCounter)
+        .unwrap_or(0);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::BlindedAssessmentCounter, &(current_id + 1));
+
+    current_id
+}
+
+/// Get the blinded assessors registered for a trial
+pub fn get_trial_assessors(env: &Env, trial_record_id: u64) -> Vec<Address> {
+    let key = DataKey::TrialAssessors(trial_record_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Save the blinded assessors registered for a trial
+pub fn save_trial_assessors(env: &Env, trial_record_id: u64, assessors: &Vec<Address>) {
+    let key = DataKey::TrialAssessors(trial_record_id);
+    env.storage().persistent().set(&key, assessors);
+}
+
+/// Check if an address is a blinded assessor for a trial
+pub fn is_trial_assessor(env: &Env, trial_record_id: u64, assessor: &Address) -> bool {
+    get_trial_assessors(env, trial_record_id).contains(assessor)
+}
+
+/// Save a blinded outcome assessment and index it under its enrollment
+pub fn save_blinded_assessment(env: &Env, assessment: &BlindedOutcomeAssessment) {
+    let key = DataKey::BlindedAssessment(assessment.assessment_id);
+    env.storage().persistent().set(&key, assessment);
+
+    let index_key = DataKey::EnrollmentBlindedAssessments(assessment.enrollment_id);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&index_key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(assessment.assessment_id);
+    env.storage().persistent().set(&index_key, &ids);
+}
+
+/// Get a blinded outcome assessment
+pub fn get_blinded_assessment(
+    env: &Env,
+    assessment_id: u64,
+) -> Result<BlindedOutcomeAssessment, Error> {
+    let key = DataKey::BlindedAssessment(assessment_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::BlindedAssessmentNotFound)
+}
+
+/// Get all blinded assessment IDs for an enrollment
+pub fn get_enrollment_blinded_assessments(env: &Env, enrollment_id: u64) -> Vec<u64> {
+    let key = DataKey::EnrollmentBlindedAssessments(enrollment_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the next screening ID and increment counter
+pub fn get_next_screening_id(env: &Env) -> u64 {
+    let current_id = env
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::ScreeningCounter)
+        .unwrap_or(0);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::ScreeningCounter, &(current_id + 1));
+
+    current_id
+}
+
+/// Save a screening record
+pub fn save_screening(env: &Env, screening: &ScreeningRecord) {
+    let key = DataKey::Screening(screening.screening_id);
+    env.storage().persistent().set(&key, screening);
+}
+
+/// Get a screening record
+pub fn get_screening(env: &Env, screening_id: u64) -> Result<ScreeningRecord, Error> {
+    let key = DataKey::Screening(screening_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::ScreeningNotFound)
+}
+
+/// Link an enrollment back to the screening that led to it
+pub fn save_enrollment_screening(env: &Env, enrollment_id: u64, screening_id: u64) {
+    let key = DataKey::EnrollmentScreening(enrollment_id);
+    env.storage().persistent().set(&key, &screening_id);
+}
+
+/// Get the screening linked to an enrollment, if any
+pub fn get_enrollment_screening(env: &Env, enrollment_id: u64) -> Option<u64> {
+    let key = DataKey::EnrollmentScreening(enrollment_id);
+    env.storage().persistent().get(&key)
+}
+
+/// Get the funnel counts for a trial
+pub fn get_trial_funnel(env: &Env, trial_record_id: u64) -> FunnelStats {
+    let key = DataKey::TrialFunnel(trial_record_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(empty_funnel(env))
+}
+
+/// Get the funnel counts for one site of a trial
+pub fn get_site_funnel(env: &Env, trial_record_id: u64, site_id: &Symbol) -> FunnelStats {
+    let key = DataKey::SiteFunnel(trial_record_id, site_id.clone());
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(empty_funnel(env))
+}
+
+/// Apply the same update to a trial's funnel and its site's funnel
+pub fn update_funnel<F>(env: &Env, trial_record_id: u64, site_id: &Symbol, update: F)
+where
+    F: Fn(&mut FunnelStats),
+{
+    let mut trial_funnel = get_trial_funnel(env, trial_record_id);
+    update(&mut trial_funnel);
+    env.storage()
+        .persistent()
+        .set(&DataKey::TrialFunnel(trial_record_id), &trial_funnel);
+
+    let mut site_funnel = get_site_funnel(env, trial_record_id, site_id);
+    update(&mut site_funnel);
+    env.storage().persistent().set(
+        &DataKey::SiteFunnel(trial_record_id, site_id.clone()),
+        &site_funnel,
+    );
+}
+
+fn empty_funnel(env: &Env) -> FunnelStats {
+    FunnelStats {
+        screened: 0,
+        screen_failed: 0,
+        failures_by_reason: Map::new(env),
+        enrolled: 0,
+        withdrawn: 0,
+    }
+}
+
+/// Save the stipend schedule (visit number → amount) for a trial
+pub fn save_stipend_schedule(env: &Env, trial_record_id: u64, schedule: &Map<u32, i128>) {
+    let key = DataKey::StipendSchedule(trial_record_id);
+    env.storage().persistent().set(&key, schedule);
+}
+
+/// Get the stipend schedule for a trial
+pub fn get_stipend_schedule(env: &Env, trial_record_id: u64) -> Map<u32, i128> {
+    let key = DataKey::StipendSchedule(trial_record_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Map::new(env))
+}
+
+/// Get the stipend balance for an enrollment
+pub fn get_stipend_balance(env: &Env, enrollment_id: u64) -> StipendBalance {
+    let key = DataKey::StipendBalance(enrollment_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(StipendBalance {
+            enrollment_id,
+            ..Default::default()
+        })
+}
+
+/// Save the stipend balance for an enrollment
+pub fn save_stipend_balance(env: &Env, balance: &StipendBalance) {
+    let key = DataKey::StipendBalance(balance.enrollment_id);
+    env.storage().persistent().set(&key, balance);
+}
+
+/// Accrue the scheduled stipend for a visit once; returns the amount accrued
+pub fn accrue_visit_stipend(
+    env: &Env,
+    trial_record_id: u64,
+    enrollment_id: u64,
+    visit_number: u32,
+) -> Option<i128> {
+    let amount = get_stipend_schedule(env, trial_record_id).get(visit_number)?;
+    let accrued_key = DataKey::StipendAccrued(enrollment_id, visit_number);
+    if env.storage().persistent().has(&accrued_key) {
+        return None;
+    }
+    env.storage().persistent().set(&accrued_key, &amount);
+
+    let mut balance = get_stipend_balance(env, enrollment_id);
+    balance.accrued += amount;
+    balance.outstanding += amount;
+    save_stipend_balance(env, &balance);
+
+    Some(amount)
+}
+
+/// Append a stipend payment for an enrollment
+pub fn add_stipend_payment(env: &Env, payment: &StipendPayment) {
+    let key = DataKey::StipendPayments(payment.enrollment_id);
+    let mut payments: Vec<StipendPayment> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    payments.push_back(payment.clone());
+    env.storage().persistent().set(&key, &payments);
+}
+
+/// Get all stipend payments for an enrollment
+pub fn get_stipend_payments(env: &Env, enrollment_id: u64) -> Vec<StipendPayment> {
+    let key = DataKey::StipendPayments(enrollment_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Get the next specimen ID and increment counter
+pub fn get_next_specimen_id(env: &Env) -> u64 {
+    let current_id = env
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::SpecimenCounter)
+        .unwrap_or(0);
+
+    env.storage()
+        .instance()
+        .set(&DataKey::SpecimenCounter, &(current_id + 1));
+
+    current_id
+}
+
+/// Save a biospecimen record
+pub fn save_specimen(env: &Env, specimen: &Biospecimen) {
+    let key = DataKey::Biospecimen(specimen.specimen_id);
+    env.storage().persistent().set(&key, specimen);
+}
+
+/// Get a biospecimen record
+pub fn get_specimen(env: &Env, specimen_id: u64) -> Result<Biospecimen, Error> {
+    let key = DataKey::Biospecimen(specimen_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::SpecimenNotFound)
+}
+
+/// Append an entry to a specimen's chain of custody
+pub fn add_custody_event(env: &Env, event: &CustodyEvent) {
+    let key = DataKey::CustodyLog(event.specimen_id);
+    let mut log: Vec<CustodyEvent> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+
+    log.push_back(event.clone());
+    env.storage().persistent().set(&key, &log);
+}
+
+/// Get a specimen's chain of custody, oldest first
+pub fn get_custody_log(env: &Env, specimen_id: u64) -> Vec<CustodyEvent> {
+    let key = DataKey::CustodyLog(specimen_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
@@ -1,16 +1,42 @@
-use crate::{ClinicalTrialClient, DataFilters, AdverseEvent, CriteriaRule};
-use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, BytesN, Env, String, Vec};
+use crate::{ClinicalTrialContractClient, Error, StipendEntry};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String, Symbol,
+};
 
-fn create_test_env() -> (Env, Address, Address, Address, ClinicalTrialClient<'static>) {
+// Minimal stand-in for the patient-registry contract
+#[contract]
+struct MockPatientRegistry;
+
+#[contractimpl]
+impl MockPatientRegistry {
+    pub fn register_patient(env: Env, wallet: Address) {
+        env.storage().persistent().set(&wallet, &true);
+    }
+
+    pub fn is_patient_registered(env: Env, wallet: Address) -> bool {
+        env.storage().persistent().has(&wallet)
+    }
+}
+
+fn create_test_env() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    ClinicalTrialContractClient<'static>,
+) {
     let env = Env::default();
     env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
 
     let admin = Address::generate(&env);
     let pi = Address::generate(&env);
     let patient = Address::generate(&env);
 
-    let contract_id = env.register_contract(None, crate::ClinicalTrial);
-    let client = ClinicalTrialClient::new(&env, &contract_id);
+    let contract_id = env.register(crate::ClinicalTrialContract, ());
+    let client = ClinicalTrialContractClient::new(&env, &contract_id);
 
     client.initialize(&admin);
 
@@ -19,25 +45,56 @@ fn create_test_env() -> (Env, Address, Address, Address, ClinicalTrialClient<'st
 
 fn create_protocol_hash(env: &Env) -> BytesN<32> {
     let data = String::from_str(env, "protocol_v1");
-    env.crypto().sha256(&data.into())
+    env.crypto().sha256(&data.into()).into()
 }
 
 fn create_consent_hash(env: &Env) -> BytesN<32> {
     let data = String::from_str(env, "informed_consent");
-    env.crypto().sha256(&data.into())
+    env.crypto().sha256(&data.into()).into()
 }
 
 fn create_data_hash(env: &Env) -> BytesN<32> {
     let data = String::from_str(env, "patient_data");
-    env.crypto().sha256(&data.into())
+    env.crypto().sha256(&data.into()).into()
+}
+
+fn register_trial(env: &Env, client: &ClinicalTrialContractClient<'static>, pi: &Address) -> u64 {
+    client.register_clinical_trial(
+        pi,
+        &String::from_str(env, "TRIAL001"),
+        &String::from_str(env, "Diabetes Study"),
+        &symbol_short!("phase3"),
+        &create_protocol_hash(env),
+        &1000,
+        &50_000,
+        &200,
+        &String::from_str(env, "IRB-2024-002"),
+    )
+}
+
+fn enroll(
+    env: &Env,
+    client: &ClinicalTrialContractClient<'static>,
+    trial_record_id: u64,
+    patient: &Address,
+    participant_id: &str,
+) -> u64 {
+    client.enroll_participant(
+        &trial_record_id,
+        patient,
+        &symbol_short!("armA"),
+        &5_000,
+        &create_consent_hash(env),
+        &String::from_str(env, participant_id),
+    )
 }
 
 #[test]
 fn test_initialize() {
     let (env, admin, _, _, client) = create_test_env();
-    
+
     // Contract should be initialized successfully
-    let trial_id = client.register_clinical_trial(
+    let trial_record_id = client.register_clinical_trial(
         &admin,
         &String::from_str(&env, "TRIAL001"),
         &String::from_str(&env, "Cancer Treatment Study"),
@@ -48,8 +105,8 @@ fn test_initialize() {
         &100,
         &String::from_str(&env, "IRB-2024-001"),
     );
-    
-    assert!(trial_id.is_ok());
+
+    assert_eq!(trial_record_id, 0);
 }
 
 #[test]
@@ -59,8 +116,8 @@ fn test_double_initialize() {
     env.mock_all_auths();
 
     let admin = Address::generate(&env);
-    let contract_id = env.register_contract(None, crate::ClinicalTrial);
-    let client = ClinicalTrialClient::new(&env, &contract_id);
+    let contract_id = env.register(crate::ClinicalTrialContract, ());
+    let client = ClinicalTrialContractClient::new(&env, &contract_id);
 
     client.initialize(&admin);
     client.initialize(&admin); // Should panic
@@ -70,25 +127,9 @@ fn test_double_initialize() {
 fn test_register_clinical_trial() {
     let (env, _, pi, _, client) = create_test_env();
 
-    let trial_id = client.register_clinical_trial(
-        &pi,
-        &String::from_str(&env, "TRIAL001"),
-        &String::from_str(&env, "Diabetes Study"),
-        &symbol_short!("phase3"),
-        &create_protocol_hash(&env),
-        &1000,
-        &5000,
-        &200,
-        &String::from_str(&env, "IRB-2024-002"),
-    );
-
-    assert!(trial_id.is_ok());
-    let trial_record_id = trial_id.unwrap();
+    let trial_record_id = register_trial(&env, &client, &pi);
 
-    let trial = client.get_trial(&trial_record_id);
-    assert!(trial.is_ok());
-    
-    let trial_data = trial.unwrap();
+    let trial_data = client.get_trial(&trial_record_id);
     assert_eq!(trial_data.trial_record_id, trial_record_id);
     assert_eq!(trial_data.principal_investigator, pi);
     assert_eq!(trial_data.enrollment_target, 200);
@@ -110,7 +151,7 @@ fn test_invalid_study_phase() {
         &String::from_str(&env, "IRB-2024-003"),
     );
 
-    assert!(result.is_err());
+    assert_eq!(result, Err(Ok(Error::InvalidStudyPhase)));
 }
 
 #[test]
@@ -124,4 +165,391 @@ fn test_invalid_date_range() {
         &symbol_short!("phase1"),
         &create_protocol_hash(&env),
         &5000,
-        &1000, 
\ No newline at end of file
+        &1000,
+        &100,
+        &String::from_str(&env, "IRB-2024-004"),
+    );
+
+    assert_eq!(result, Err(Ok(Error::InvalidDateRange)));
+}
+
+#[test]
+fn test_blinded_assessor_sees_no_arm_assignments() {
+    let (env, _, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let enrollment_id = enroll(&env, &client, trial_record_id, &patient, "P-001");
+    let assessor = Address::generate(&env);
+
+    client.register_blinded_assessor(&trial_record_id, &pi, &assessor);
+    assert_eq!(client.get_blinded_assessors(&trial_record_id).len(), 1);
+
+    let views = client.get_blinded_enrollments(&trial_record_id, &assessor);
+    assert_eq!(views.len(), 1);
+    let view = views.get(0).unwrap();
+    assert_eq!(view.enrollment_id, enrollment_id);
+    assert_eq!(view.participant_id, String::from_str(&env, "P-001"));
+
+    let assessment_id = client.submit_blinded_assessment(
+        &enrollment_id,
+        &assessor,
+        &symbol_short!("hba1c"),
+        &9_000,
+        &create_data_hash(&env),
+    );
+    let assessment = client.get_blinded_assessment(&assessment_id, &pi);
+    assert_eq!(assessment.assessor, assessor);
+    assert_eq!(assessment.outcome_measure, symbol_short!("hba1c"));
+    assert_eq!(
+        client
+            .get_enrollment_assessments(&enrollment_id, &assessor)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_blinded_assessor_registration_rules() {
+    let (env, _, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let enrollment_id = enroll(&env, &client, trial_record_id, &patient, "P-001");
+    let assessor = Address::generate(&env);
+
+    // The PI knows arm assignments
+    let result = client.try_register_blinded_assessor(&trial_record_id, &pi, &pi);
+    assert_eq!(result, Err(Ok(Error::InvalidBlindedAssessor)));
+
+    let outsider = Address::generate(&env);
+    let result = client.try_register_blinded_assessor(&trial_record_id, &outsider, &assessor);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.register_blinded_assessor(&trial_record_id, &pi, &assessor);
+    let result = client.try_register_blinded_assessor(&trial_record_id, &pi, &assessor);
+    assert_eq!(result, Err(Ok(Error::BlindedAssessorExists)));
+
+    // Unregistered assessors cannot view enrollments or submit
+    let result = client.try_get_blinded_enrollments(&trial_record_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::NotBlindedAssessor)));
+    let result = client.try_submit_blinded_assessment(
+        &enrollment_id,
+        &outsider,
+        &symbol_short!("hba1c"),
+        &9_000,
+        &create_data_hash(&env),
+    );
+    assert_eq!(result, Err(Ok(Error::NotBlindedAssessor)));
+
+    client.remove_blinded_assessor(&trial_record_id, &pi, &assessor);
+    let result = client.try_get_blinded_enrollments(&trial_record_id, &assessor);
+    assert_eq!(result, Err(Ok(Error::NotBlindedAssessor)));
+}
+
+#[test]
+fn test_trial_and_site_funnel_counts() {
+    let (env, _, pi, _, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let north = symbol_short!("north");
+    let south = symbol_short!("south");
+
+    let screen = |site: &Symbol, patient: &Address, fail_reason: Option<Symbol>| {
+        client.record_screening(&trial_record_id, &pi, site, patient, &6_000, &fail_reason)
+    };
+
+    let enrolled = Address::generate(&env);
+    let withdrawn = Address::generate(&env);
+    let passed_north = screen(&north, &enrolled, None);
+    let passed_south = screen(&south, &withdrawn, None);
+    screen(
+        &north,
+        &Address::generate(&env),
+        Some(symbol_short!("labs")),
+    );
+    screen(
+        &south,
+        &Address::generate(&env),
+        Some(symbol_short!("labs")),
+    );
+    screen(
+        &south,
+        &Address::generate(&env),
+        Some(symbol_short!("consent")),
+    );
+
+    let first = enroll(&env, &client, trial_record_id, &enrolled, "P-001");
+    let second = enroll(&env, &client, trial_record_id, &withdrawn, "P-002");
+    client.link_screening_to_enrollment(&passed_north, &pi, &first);
+    client.link_screening_to_enrollment(&passed_south, &pi, &second);
+    client.withdraw_participant(&second, &7_000, &symbol_short!("consent"), &true);
+
+    let funnel = client.get_trial_funnel(&trial_record_id);
+    assert_eq!(funnel.screened, 5);
+    assert_eq!(funnel.screen_failed, 3);
+    assert_eq!(
+        funnel.failures_by_reason.get(symbol_short!("labs")),
+        Some(2)
+    );
+    assert_eq!(
+        funnel.failures_by_reason.get(symbol_short!("consent")),
+        Some(1)
+    );
+    assert_eq!(funnel.enrolled, 2);
+    assert_eq!(funnel.withdrawn, 1);
+
+    let south_funnel = client.get_site_funnel(&trial_record_id, &south);
+    assert_eq!(south_funnel.screened, 3);
+    assert_eq!(south_funnel.screen_failed, 2);
+    assert_eq!(south_funnel.enrolled, 1);
+    assert_eq!(south_funnel.withdrawn, 1);
+    let north_funnel = client.get_site_funnel(&trial_record_id, &north);
+    assert_eq!(north_funnel.screened, 2);
+    assert_eq!(north_funnel.enrolled, 1);
+    assert_eq!(north_funnel.withdrawn, 0);
+}
+
+#[test]
+fn test_screening_link_rules() {
+    let (env, _, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let enrollment_id = enroll(&env, &client, trial_record_id, &patient, "P-001");
+
+    let failed = client.record_screening(
+        &trial_record_id,
+        &pi,
+        &symbol_short!("north"),
+        &patient,
+        &6_000,
+        &Some(symbol_short!("labs")),
+    );
+    let result = client.try_link_screening_to_enrollment(&failed, &pi, &enrollment_id);
+    assert_eq!(result, Err(Ok(Error::ScreenFailed)));
+
+    let other_patient = client.record_screening(
+        &trial_record_id,
+        &pi,
+        &symbol_short!("north"),
+        &Address::generate(&env),
+        &6_000,
+        &None,
+    );
+    let result = client.try_link_screening_to_enrollment(&other_patient, &pi, &enrollment_id);
+    assert_eq!(result, Err(Ok(Error::ScreeningMismatch)));
+
+    let result = client.try_record_screening(
+        &trial_record_id,
+        &pi,
+        &symbol_short!("north"),
+        &patient,
+        &6_000,
+        &Some(symbol_short!("weather")),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidScreenFailReason)));
+}
+
+#[test]
+fn test_stipend_balance_and_payments() {
+    let (env, _, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let enrollment_id = enroll(&env, &client, trial_record_id, &patient, "P-001");
+
+    client.set_stipend_schedule(
+        &trial_record_id,
+        &pi,
+        &vec![
+            &env,
+            StipendEntry {
+                visit_number: 1,
+                amount: 500,
+            },
+            StipendEntry {
+                visit_number: 2,
+                amount: 750,
+            },
+        ],
+    );
+
+    let visit = |visit_number: u32| {
+        client.record_study_visit(
+            &enrollment_id,
+            &visit_number,
+            &8_000,
+            &symbol_short!("routine"),
+            &create_data_hash(&env),
+            &vec![&env],
+        )
+    };
+    visit(1);
+    visit(2);
+    // Re-recording a visit does not accrue twice
+    visit(2);
+    // Unscheduled visits accrue nothing
+    visit(3);
+
+    let balance = client.get_stipend_balance(&enrollment_id, &patient);
+    assert_eq!(balance.accrued, 1_250);
+    assert_eq!(balance.paid, 0);
+    assert_eq!(balance.outstanding, 1_250);
+
+    let tx = String::from_str(&env, "TX-1");
+    client.record_stipend_payment(&enrollment_id, &pi, &1_000, &tx);
+    let balance = client.get_stipend_balance(&enrollment_id, &pi);
+    assert_eq!(balance.accrued, 1_250);
+    assert_eq!(balance.paid, 1_000);
+    assert_eq!(balance.outstanding, 250);
+
+    let result = client.try_record_stipend_payment(&enrollment_id, &pi, &100, &tx);
+    assert_eq!(result, Err(Ok(Error::DuplicatePayment)));
+
+    let tx2 = String::from_str(&env, "TX-2");
+    let result = client.try_record_stipend_payment(&enrollment_id, &pi, &300, &tx2);
+    assert_eq!(result, Err(Ok(Error::PaymentExceedsBalance)));
+    let result = client.try_record_stipend_payment(&enrollment_id, &pi, &0, &tx2);
+    assert_eq!(result, Err(Ok(Error::InvalidStipendAmount)));
+
+    client.record_stipend_payment(&enrollment_id, &pi, &250, &tx2);
+    assert_eq!(
+        client
+            .get_stipend_balance(&enrollment_id, &patient)
+            .outstanding,
+        0
+    );
+    assert_eq!(
+        client.get_stipend_payments(&enrollment_id, &patient).len(),
+        2
+    );
+
+    let result = client.try_get_stipend_balance(&enrollment_id, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_specimen_custody_handoff() {
+    let (env, _, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+    let enrollment_id = enroll(&env, &client, trial_record_id, &patient, "P-001");
+    client.record_study_visit(
+        &enrollment_id,
+        &1,
+        &8_000,
+        &symbol_short!("routine"),
+        &create_data_hash(&env),
+        &vec![&env],
+    );
+    let specimen_id = client.register_biospecimen(
+        &enrollment_id,
+        &pi,
+        &1,
+        &symbol_short!("serum"),
+        &8_100,
+        &4,
+        &symbol_short!("refrig"),
+    );
+
+    let courier = Address::generate(&env);
+    let lab = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    // Only the current custodian can hand a specimen off
+    let result = client.try_transfer_specimen_custody(
+        &specimen_id,
+        &outsider,
+        &courier,
+        &symbol_short!("courier"),
+        &8_200,
+        &symbol_short!("refrig"),
+    );
+    assert_eq!(result, Err(Ok(Error::NotCustodian)));
+
+    client.transfer_specimen_custody(
+        &specimen_id,
+        &pi,
+        &courier,
+        &symbol_short!("courier"),
+        &8_200,
+        &symbol_short!("refrig"),
+    );
+    // Both sides of the handoff sign the transfer
+    let auths = env.auths();
+    assert!(auths.iter().any(|(signer, _)| *signer == pi));
+    assert!(auths.iter().any(|(signer, _)| *signer == courier));
+
+    // The previous custodian can no longer transfer the specimen
+    let result = client.try_transfer_specimen_custody(
+        &specimen_id,
+        &pi,
+        &lab,
+        &symbol_short!("lab"),
+        &8_300,
+        &symbol_short!("frozen80"),
+    );
+    assert_eq!(result, Err(Ok(Error::NotCustodian)));
+
+    client.transfer_specimen_custody(
+        &specimen_id,
+        &courier,
+        &lab,
+        &symbol_short!("lab"),
+        &8_300,
+        &symbol_short!("frozen80"),
+    );
+
+    let specimen = client.get_biospecimen(&specimen_id);
+    assert_eq!(specimen.current_custodian, lab);
+    assert_eq!(specimen.custodian_role, symbol_short!("lab"));
+
+    let chain = client.get_chain_of_custody(&specimen_id, &courier);
+    assert_eq!(chain.len(), 3);
+    let last = chain.get(2).unwrap();
+    assert_eq!(last.from_custodian, Some(courier.clone()));
+    assert_eq!(last.custodian, lab);
+
+    let result = client.try_get_chain_of_custody(&specimen_id, &outsider);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn test_strict_patient_registry_enrollment() {
+    let (env, admin, pi, patient, client) = create_test_env();
+    let trial_record_id = register_trial(&env, &client, &pi);
+
+    let registry_id = env.register(MockPatientRegistry, ());
+    let registry = MockPatientRegistryClient::new(&env, &registry_id);
+
+    // Only the admin can configure the registry hook
+    let result = client.try_set_patient_registry(&pi, &registry_id, &true);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    assert_eq!(client.get_patient_registry(), None);
+
+    // Without strict mode unregistered patients can still enroll
+    client.set_patient_registry(&admin, &registry_id, &false);
+    enroll(&env, &client, trial_record_id, &patient, "P-001");
+
+    client.set_patient_registry(&admin, &registry_id, &true);
+    let config = client.get_patient_registry().unwrap();
+    assert_eq!(config.registry, registry_id);
+    assert!(config.strict_mode);
+
+    let unregistered = Address::generate(&env);
+    let result = client.try_enroll_participant(
+        &trial_record_id,
+        &unregistered,
+        &symbol_short!("armA"),
+        &5_000,
+        &create_consent_hash(&env),
+        &String::from_str(&env, "P-002"),
+    );
+    assert_eq!(result, Err(Ok(Error::PatientNotRegistered)));
+
+    registry.register_patient(&unregistered);
+    enroll(&env, &client, trial_record_id, &unregistered, "P-002");
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _admin, pi, _patient, client) = create_test_env();
+    assert_eq!(client.schema_version(), crate::SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&pi), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&pi, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
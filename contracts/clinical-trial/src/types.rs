@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
+use soroban_sdk::{contracttype, Address, BytesN, Map, String, Symbol, Vec};
 
 /// Criteria rule for eligibility checking
 #[contracttype]
@@ -155,6 +155,123 @@ pub struct DataFilters {
     pub date_range_end: Option<u64>,
 }
 
+/// Outcome assessment submitted by a blinded assessor. Kept apart from
+/// study visits and never carries the study arm.
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BlindedOutcomeAssessment {
+    pub assessment_id: u64,
+    pub enrollment_id: u64,
+    pub trial_record_id: u64,
+    pub participant_id: String,
+    pub assessor: Address,
+    pub outcome_measure: Symbol,
+    pub assessment_date: u64,
+    pub outcome_data_hash: BytesN<32>,
+    pub recorded_at: u64,
+}
+
+/// Enrollment as seen by a blinded assessor, without the arm assignment
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct BlindedEnrollmentView {
+    pub enrollment_id: u64,
+    pub participant_id: String,
+    pub status: EnrollmentStatus,
+}
+
+/// Screening attempt for a trial at a site
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct ScreeningRecord {
+    pub screening_id: u64,
+    pub trial_record_id: u64,
+    pub site_id: Symbol,
+    pub patient_id: Address,
+    pub screening_date: u64,
+    pub passed: bool,
+    pub fail_reason: Option<Symbol>,
+    pub enrollment_id: Option<u64>,
+}
+
+/// Enrollment funnel counts for a trial or one of its sites
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunnelStats {
+    pub screened: u32,
+    pub screen_failed: u32,
+    pub failures_by_reason: Map<Symbol, u32>,
+    pub enrolled: u32,
+    pub withdrawn: u32,
+}
+
+/// Stipend owed for completing a study visit
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StipendEntry {
+    pub visit_number: u32,
+    pub amount: i128,
+}
+
+/// Stipend payment disbursed to a participant
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct StipendPayment {
+    pub enrollment_id: u64,
+    pub amount: i128,
+    pub transaction_ref: String,
+    pub paid_by: Address,
+    pub paid_at: u64,
+}
+
+/// Stipend balance for an enrollment, in stroops
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StipendBalance {
+    pub enrollment_id: u64,
+    pub accrued: i128,
+    pub paid: i128,
+    pub outstanding: i128,
+}
+
+/// Biospecimen collected at a study visit
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct Biospecimen {
+    pub specimen_id: u64,
+    pub enrollment_id: u64,
+    pub visit_number: u32,
+    pub specimen_type: Symbol,
+    pub collection_time: u64,
+    pub aliquot_count: u32,
+    pub current_custodian: Address,
+    pub custodian_role: Symbol,
+    pub storage_condition: Symbol,
+}
+
+/// Entry in a specimen's chain of custody: collection, a transfer signed by
+/// both custodians, or a storage condition attestation
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct CustodyEvent {
+    pub specimen_id: u64,
+    pub event_type: Symbol,
+    pub from_custodian: Option<Address>,
+    pub custodian: Address,
+    pub custodian_role: Symbol,
+    pub storage_condition: Symbol,
+    pub condition_in_range: bool,
+    pub occurred_at: u64,
+}
+
+/// Patient-registry hook; in strict mode unregistered patients are rejected
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientRegistryConfig {
+    pub registry: Address,
+    pub strict_mode: bool,
+}
+
 /// Storage keys for the contract
 #[contracttype]
 #[derive(Clone)]
@@ -172,4 +289,21 @@ pub enum DataKey {
     AdverseEvent(u64),
     ProtocolDeviation(u64, u64),
     SafetyReport(u64, u64),
+    PatientRegistry,
+    BlindedAssessmentCounter,
+    TrialAssessors(u64),
+    BlindedAssessment(u64),
+    EnrollmentBlindedAssessments(u64),
+    ScreeningCounter,
+    Screening(u64),
+    EnrollmentScreening(u64),
+    TrialFunnel(u64),
+    SiteFunnel(u64, Symbol),
+    StipendSchedule(u64),
+    StipendAccrued(u64, u32),
+    StipendBalance(u64),
+    StipendPayments(u64),
+    SpecimenCounter,
+    Biospecimen(u64),
+    CustodyLog(u64),
 }
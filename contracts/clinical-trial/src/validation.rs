@@ -87,3 +87,71 @@ pub fn validate_withdrawal_reason(reason: &Symbol) -> Result<(), Error> {
         Err(Error::InvalidWithdrawalReason)
     }
 }
+
+/// Validate screen-fail category
+pub fn validate_screen_fail_reason(reason: &Symbol) -> Result<(), Error> {
+    let valid_reasons = [
+        symbol_short!("inclusion"),
+        symbol_short!("exclusion"),
+        symbol_short!("consent"),
+        symbol_short!("labs"),
+        symbol_short!("other"),
+    ];
+
+    if valid_reasons.contains(reason) {
+        Ok(())
+    } else {
+        Err(Error::InvalidScreenFailReason)
+    }
+}
+
+/// Validate biospecimen type
+pub fn validate_specimen_type(specimen_type: &Symbol) -> Result<(), Error> {
+    let valid_types = [
+        symbol_short!("blood"),
+        symbol_short!("serum"),
+        symbol_short!("plasma"),
+        symbol_short!("urine"),
+        symbol_short!("tissue"),
+        symbol_short!("saliva"),
+        symbol_short!("csf"),
+    ];
+
+    if valid_types.contains(specimen_type) {
+        Ok(())
+    } else {
+        Err(Error::InvalidSpecimenType)
+    }
+}
+
+/// Validate custody role
+pub fn validate_custody_role(role: &Symbol) -> Result<(), Error> {
+    let valid_roles = [
+        symbol_short!("site"),
+        symbol_short!("courier"),
+        symbol_short!("lab"),
+    ];
+
+    if valid_roles.contains(role) {
+        Ok(())
+    } else {
+        Err(Error::InvalidCustodyRole)
+    }
+}
+
+/// Validate specimen storage condition
+pub fn validate_storage_condition(condition: &Symbol) -> Result<(), Error> {
+    let valid_conditions = [
+        symbol_short!("ambient"),
+        symbol_short!("refrig"),
+        symbol_short!("frozen20"),
+        symbol_short!("frozen80"),
+        symbol_short!("ln2"),
+    ];
+
+    if valid_conditions.contains(condition) {
+        Ok(())
+    } else {
+        Err(Error::InvalidStorageCondition)
+    }
+}
@@ -0,0 +1,198 @@
+use soroban_sdk::{contractevent, Address, BytesN, String, Symbol};
+
+use crate::types::ChartAccessLevel;
+
+// Every state-changing entrypoint publishes one of these. The first topic is
+// the record id where one exists, otherwise the patient.
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DentalChartCreated {
+    #[topic]
+    pub chart_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub dentist_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ToothConditionRecorded {
+    #[topic]
+    pub chart_id: u64,
+    pub tooth_number: String,
+    pub condition: Symbol,
+    pub recorded_by: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeriodontalAssessmentRecorded {
+    #[topic]
+    pub chart_id: u64,
+    pub tooth_number: String,
+    pub site: Symbol,
+    pub probing_depth: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerioExamRecorded {
+    #[topic]
+    pub exam_id: u64,
+    pub chart_id: u64,
+    pub examiner_id: Address,
+    pub site_count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartAccessGranted {
+    #[topic]
+    pub chart_id: u64,
+    #[topic]
+    pub grantee: Address,
+    pub level: ChartAccessLevel,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartAccessRevoked {
+    #[topic]
+    pub chart_id: u64,
+    #[topic]
+    pub grantee: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CariesRiskAssessed {
+    #[topic]
+    pub chart_id: u64,
+    pub risk_level: Symbol,
+    pub risk_score: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveServiceRecorded {
+    #[topic]
+    pub chart_id: u64,
+    pub service: Symbol,
+    pub performed_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartTransferred {
+    #[topic]
+    pub chart_id: u64,
+    pub from_dentist: Address,
+    pub to_dentist: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreatmentPlanCreated {
+    #[topic]
+    pub plan_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub dentist_id: Address,
+    pub estimated_cost: i128,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcedureScheduled {
+    #[topic]
+    pub appointment_id: u64,
+    pub treatment_plan_id: u64,
+    pub procedure_id: u64,
+    pub scheduled_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProcedurePerformed {
+    #[topic]
+    pub appointment_id: u64,
+    pub dentist_id: Address,
+    pub procedure_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RadiographRecorded {
+    #[topic]
+    pub radiograph_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub image_type: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrthoTreatmentStarted {
+    #[topic]
+    pub ortho_treatment_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub orthodontist_id: Address,
+    pub appliance_type: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrthoAdjustmentRecorded {
+    #[topic]
+    pub ortho_treatment_id: u64,
+    pub adjustment_date: u64,
+    pub next_adjustment_due: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OrthoTreatmentCompleted {
+    #[topic]
+    pub ortho_treatment_id: u64,
+    pub debond_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionPhaseStarted {
+    #[topic]
+    pub ortho_treatment_id: u64,
+    pub retainer_type: Symbol,
+    pub start_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionCheckRecorded {
+    #[topic]
+    pub ortho_treatment_id: u64,
+    pub check_date: u64,
+    pub compliant: bool,
+    pub relapse_noted: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DentalMedicationPrescribed {
+    #[topic]
+    pub prescription_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub dentist_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InformedConsentDocumented {
+    #[topic]
+    pub patient_id: Address,
+    pub consent_document_hash: BytesN<32>,
+    pub consent_date: u64,
+}
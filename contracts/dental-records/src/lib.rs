@@ -1,15 +1,42 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, String, Symbol, Vec};
 
+mod events;
 mod types;
+use events::*;
 use types::*;
 
+/// Attachment-loss increase (mm) between exams that counts as progression
+const PERIO_PROGRESSION_THRESHOLD_MM: u32 = 2;
+
+const SECONDS_PER_WEEK: u64 = 7 * 24 * 60 * 60;
+
+const SECONDS_PER_MONTH: u64 = 30 * 24 * 60 * 60;
+
+/// Caries risk score at or above which a patient is moderate / high risk
+const CARIES_MODERATE_SCORE: u32 = 2;
+const CARIES_HIGH_SCORE: u32 = 4;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct DentalRecordsContract;
 
 #[contractimpl]
 impl DentalRecordsContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn create_dental_chart(
         env: Env,
         patient_id: Address,
@@ -23,14 +50,22 @@ impl DentalRecordsContract {
         count += 1;
         
         let chart = DentalChart {
-            patient_id,
-            dentist_id,
+            patient_id: patient_id.clone(),
+            dentist_id: dentist_id.clone(),
             chart_date,
             tooth_notation_system,
         };
         
         env.storage().persistent().set(&DataKey::Chart(count), &chart);
         env.storage().instance().set(&DataKey::ChartCount, &count);
+        Self::index_dentist_chart(&env, &dentist_id, count, true);
+
+        DentalChartCreated {
+            chart_id: count,
+            patient_id,
+            dentist_id,
+        }
+        .publish(&env);
         
         Ok(count)
     }
@@ -38,28 +73,54 @@ impl DentalRecordsContract {
     pub fn record_tooth_condition(
         env: Env,
         chart_id: u64,
+        dentist_id: Address,
         tooth_number: String,
         surface: Option<Symbol>, // occlusal, mesial, distal, buccal, lingual
         condition: Symbol, // caries, filling, crown, missing, implant
         condition_details: Option<String>,
     ) -> Result<(), Error> {
-        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
-        chart.dentist_id.require_auth();
+        Self::require_chart_access(&env, chart_id, &dentist_id, true)?;
 
         let tooth_cond = ToothCondition {
             surface,
-            condition,
+            condition: condition.clone(),
             condition_details,
+            recorded_by: dentist_id.clone(),
+            recorded_at: env.ledger().timestamp(),
         };
-        
-        env.storage().persistent().set(&DataKey::ToothCond(chart_id, tooth_number), &tooth_cond);
+
+        // Conditions are appended so restorations and caries progression can be reconstructed
+        let key = DataKey::ToothHistory(chart_id, tooth_number.clone());
+        let mut history: Vec<ToothCondition> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        history.push_back(tooth_cond);
+        env.storage().persistent().set(&key, &history);
+
+        ToothConditionRecorded {
+            chart_id,
+            tooth_number,
+            condition,
+            recorded_by: dentist_id,
+        }
+        .publish(&env);
         
         Ok(())
     }
 
+    pub fn get_tooth_history(
+        env: Env,
+        chart_id: u64,
+        tooth_number: String,
+        requester: Address,
+    ) -> Result<Vec<ToothCondition>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+
+        Ok(env.storage().persistent().get(&DataKey::ToothHistory(chart_id, tooth_number)).unwrap_or(Vec::new(&env)))
+    }
+
     pub fn record_periodontal_assessment(
         env: Env,
         chart_id: u64,
+        dentist_id: Address,
         tooth_number: String,
         site: Symbol, // mb, b, db, ml, l, dl
         probing_depth: u32,
@@ -67,8 +128,7 @@ impl DentalRecordsContract {
         bleeding_on_probing: bool,
         mobility: Option<u32>,
     ) -> Result<(), Error> {
-        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
-        chart.dentist_id.require_auth();
+        Self::require_chart_access(&env, chart_id, &dentist_id, true)?;
 
         let assessment = PeriodontalAssessment {
             probing_depth,
@@ -77,11 +137,375 @@ impl DentalRecordsContract {
             mobility,
         };
         
-        env.storage().persistent().set(&DataKey::Perio(chart_id, tooth_number, site), &assessment);
+        env.storage().persistent().set(&DataKey::Perio(chart_id, tooth_number.clone(), site.clone()), &assessment);
+
+        PeriodontalAssessmentRecorded {
+            chart_id,
+            tooth_number,
+            site,
+            probing_depth,
+        }
+        .publish(&env);
         
         Ok(())
     }
 
+    pub fn record_perio_exam(
+        env: Env,
+        chart_id: u64,
+        dentist_id: Address,
+        exam_date: u64,
+        measurements: Vec<PerioSiteMeasurement>,
+    ) -> Result<u64, Error> {
+        Self::require_chart_access(&env, chart_id, &dentist_id, true)?;
+
+        if measurements.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut count: u64 = env.storage().instance().get(&DataKey::PerioExamCount).unwrap_or(0);
+        count += 1;
+
+        // Keep the per-site view current with the latest exam
+        for m in measurements.iter() {
+            let assessment = PeriodontalAssessment {
+                probing_depth: m.probing_depth,
+                recession: m.recession,
+                bleeding_on_probing: m.bleeding_on_probing,
+                mobility: m.mobility,
+            };
+            env.storage().persistent().set(&DataKey::Perio(chart_id, m.tooth_number, m.site), &assessment);
+        }
+
+        let site_count = measurements.len();
+        let exam = PerioExam {
+            chart_id,
+            exam_date,
+            examiner_id: dentist_id.clone(),
+            measurements,
+        };
+
+        env.storage().persistent().set(&DataKey::PerioExam(count), &exam);
+        env.storage().instance().set(&DataKey::PerioExamCount, &count);
+
+        let mut exams: Vec<u64> = env.storage().persistent().get(&DataKey::ChartPerioExams(chart_id)).unwrap_or(Vec::new(&env));
+        exams.push_back(count);
+        env.storage().persistent().set(&DataKey::ChartPerioExams(chart_id), &exams);
+
+        PerioExamRecorded {
+            exam_id: count,
+            chart_id,
+            examiner_id: dentist_id,
+            site_count,
+        }
+        .publish(&env);
+
+        Ok(count)
+    }
+
+    pub fn get_perio_exam(env: Env, exam_id: u64, requester: Address) -> Result<PerioExam, Error> {
+        let exam: PerioExam = env.storage().persistent().get(&DataKey::PerioExam(exam_id)).ok_or(Error::NotFound)?;
+        Self::require_chart_access(&env, exam.chart_id, &requester, false)?;
+        Ok(exam)
+    }
+
+    pub fn get_chart_perio_exams(env: Env, chart_id: u64, requester: Address) -> Result<Vec<u64>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+        Ok(env.storage().persistent().get(&DataKey::ChartPerioExams(chart_id)).unwrap_or(Vec::new(&env)))
+    }
+
+    pub fn compare_perio_exams(
+        env: Env,
+        chart_id: u64,
+        exam_a: u64,
+        exam_b: u64,
+        requester: Address,
+    ) -> Result<Vec<PerioSiteProgression>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+
+        let a: PerioExam = env.storage().persistent().get(&DataKey::PerioExam(exam_a)).ok_or(Error::NotFound)?;
+        let b: PerioExam = env.storage().persistent().get(&DataKey::PerioExam(exam_b)).ok_or(Error::NotFound)?;
+        if a.chart_id != chart_id || b.chart_id != chart_id {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut progressed = Vec::new(&env);
+        for site_b in b.measurements.iter() {
+            for site_a in a.measurements.iter() {
+                if site_a.tooth_number != site_b.tooth_number || site_a.site != site_b.site {
+                    continue;
+                }
+                let loss_a = site_a.probing_depth + site_a.recession;
+                let loss_b = site_b.probing_depth + site_b.recession;
+                if loss_b >= loss_a + PERIO_PROGRESSION_THRESHOLD_MM {
+                    progressed.push_back(PerioSiteProgression {
+                        tooth_number: site_b.tooth_number.clone(),
+                        site: site_b.site.clone(),
+                        attachment_loss_a: loss_a,
+                        attachment_loss_b: loss_b,
+                        change: loss_b - loss_a,
+                    });
+                }
+                break;
+            }
+        }
+
+        Ok(progressed)
+    }
+
+    /// Score caries risk from diet, fluoride, lesion history and saliva, then
+    /// replace the chart's recall schedule with the matching preventive plan
+    pub fn assess_caries_risk(
+        env: Env,
+        chart_id: u64,
+        dentist_id: Address,
+        factors: CariesRiskFactors,
+    ) -> Result<CariesRiskAssessment, Error> {
+        Self::require_chart_access(&env, chart_id, &dentist_id, true)?;
+
+        let mut score = 0;
+        if factors.frequent_sugar_intake {
+            score += 1;
+        }
+        if !factors.fluoridated_water {
+            score += 1;
+        }
+        if !factors.fluoride_toothpaste {
+            score += 1;
+        }
+        if factors.low_salivary_flow {
+            score += 2;
+        }
+        score += match factors.new_lesions_last_3_years {
+            0 => 0,
+            1..=2 => 2,
+            _ => 4,
+        };
+
+        let now = env.ledger().timestamp();
+        let mut recommendations = Vec::new(&env);
+        let risk_level = if score >= CARIES_HIGH_SCORE {
+            recommendations.push_back(Self::recommendation(symbol_short!("varnish"), 3, now));
+            recommendations.push_back(Self::recommendation(symbol_short!("sealants"), 0, now));
+            recommendations.push_back(Self::recommendation(symbol_short!("recall"), 3, now));
+            symbol_short!("high")
+        } else if score >= CARIES_MODERATE_SCORE {
+            recommendations.push_back(Self::recommendation(symbol_short!("varnish"), 6, now));
+            recommendations.push_back(Self::recommendation(symbol_short!("recall"), 6, now));
+            symbol_short!("moderate")
+        } else {
+            recommendations.push_back(Self::recommendation(symbol_short!("recall"), 12, now));
+            symbol_short!("low")
+        };
+
+        let assessment = CariesRiskAssessment {
+            chart_id,
+            assessed_by: dentist_id,
+            assessed_at: now,
+            factors,
+            risk_score: score,
+            risk_level: risk_level.clone(),
+            recommendations: recommendations.clone(),
+        };
+
+        let key = DataKey::CariesRisk(chart_id);
+        let mut history: Vec<CariesRiskAssessment> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        history.push_back(assessment.clone());
+        env.storage().persistent().set(&key, &history);
+        env.storage().persistent().set(&DataKey::Recalls(chart_id), &recommendations);
+
+        CariesRiskAssessed {
+            chart_id,
+            risk_level,
+            risk_score: score,
+        }
+        .publish(&env);
+
+        Ok(assessment)
+    }
+
+    pub fn get_caries_risk_history(
+        env: Env,
+        chart_id: u64,
+        requester: Address,
+    ) -> Result<Vec<CariesRiskAssessment>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+        Ok(env.storage().persistent().get(&DataKey::CariesRisk(chart_id)).unwrap_or(Vec::new(&env)))
+    }
+
+    /// Record a preventive service, rolling its recall forward by the plan
+    /// interval. One-time services drop off the schedule.
+    pub fn record_preventive_service(
+        env: Env,
+        chart_id: u64,
+        dentist_id: Address,
+        service: Symbol,
+        performed_date: u64,
+    ) -> Result<(), Error> {
+        Self::require_chart_access(&env, chart_id, &dentist_id, true)?;
+
+        let key = DataKey::Recalls(chart_id);
+        let recalls: Vec<PreventiveRecommendation> = env.storage().persistent().get(&key).ok_or(Error::NotFound)?;
+
+        let mut found = false;
+        let mut updated = Vec::new(&env);
+        for mut recall in recalls.iter() {
+            if recall.service == service {
+                found = true;
+                if recall.interval_months == 0 {
+                    continue;
+                }
+                recall.due_date = performed_date + recall.interval_months as u64 * SECONDS_PER_MONTH;
+            }
+            updated.push_back(recall);
+        }
+        if !found {
+            return Err(Error::NotFound);
+        }
+        env.storage().persistent().set(&key, &updated);
+
+        PreventiveServiceRecorded {
+            chart_id,
+            service,
+            performed_date,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_chart_recalls(
+        env: Env,
+        chart_id: u64,
+        requester: Address,
+    ) -> Result<Vec<PreventiveRecommendation>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+        Ok(env.storage().persistent().get(&DataKey::Recalls(chart_id)).unwrap_or(Vec::new(&env)))
+    }
+
+    /// Preventive services now due across the charts a dentist holds
+    pub fn get_due_recalls(env: Env, dentist_id: Address) -> Vec<RecallDue> {
+        let charts: Vec<u64> = env.storage().persistent().get(&DataKey::DentistCharts(dentist_id)).unwrap_or(Vec::new(&env));
+        let now = env.ledger().timestamp();
+
+        let mut due = Vec::new(&env);
+        for chart_id in charts.iter() {
+            let recalls: Vec<PreventiveRecommendation> = env.storage().persistent().get(&DataKey::Recalls(chart_id)).unwrap_or(Vec::new(&env));
+            for recall in recalls.iter() {
+                if recall.due_date <= now {
+                    due.push_back(RecallDue {
+                        chart_id,
+                        service: recall.service,
+                        due_date: recall.due_date,
+                    });
+                }
+            }
+        }
+
+        due
+    }
+
+    pub fn grant_chart_access(
+        env: Env,
+        chart_id: u64,
+        grantee: Address, // dental practice or referred dentist
+        level: ChartAccessLevel,
+    ) -> Result<(), Error> {
+        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
+        chart.patient_id.require_auth();
+
+        env.storage().persistent().set(&DataKey::ChartAccess(chart_id, grantee.clone()), &level);
+        Self::log_chart_access(&env, chart_id, &chart.patient_id, symbol_short!("grant"), Some(grantee.clone()));
+
+        ChartAccessGranted {
+            chart_id,
+            grantee,
+            level,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn revoke_chart_access(env: Env, chart_id: u64, grantee: Address) -> Result<(), Error> {
+        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
+        chart.patient_id.require_auth();
+
+        let key = DataKey::ChartAccess(chart_id, grantee.clone());
+        if !env.storage().persistent().has(&key) {
+            return Err(Error::NotFound);
+        }
+        env.storage().persistent().remove(&key);
+        Self::log_chart_access(&env, chart_id, &chart.patient_id, symbol_short!("revoke"), Some(grantee.clone()));
+
+        ChartAccessRevoked { chart_id, grantee }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Move a chart to a new dentist with the patient's co-signature. Entries
+    /// already on the chart stay attributed to whoever recorded them.
+    pub fn transfer_chart(env: Env, chart_id: u64, new_dentist: Address) -> Result<(), Error> {
+        let mut chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
+        chart.patient_id.require_auth();
+        new_dentist.require_auth();
+
+        if new_dentist == chart.dentist_id {
+            return Err(Error::InvalidInput);
+        }
+
+        let from_dentist = chart.dentist_id.clone();
+        chart.dentist_id = new_dentist.clone();
+        env.storage().persistent().set(&DataKey::Chart(chart_id), &chart);
+
+        Self::index_dentist_chart(&env, &from_dentist, chart_id, false);
+        Self::index_dentist_chart(&env, &new_dentist, chart_id, true);
+
+        let key = DataKey::ChartTransfers(chart_id);
+        let mut transfers: Vec<ChartTransfer> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        transfers.push_back(ChartTransfer {
+            from_dentist: from_dentist.clone(),
+            to_dentist: new_dentist.clone(),
+            transferred_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &transfers);
+        Self::log_chart_access(&env, chart_id, &chart.patient_id, symbol_short!("transfer"), Some(new_dentist.clone()));
+
+        ChartTransferred {
+            chart_id,
+            from_dentist,
+            to_dentist: new_dentist,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_chart_transfers(env: Env, chart_id: u64, requester: Address) -> Result<Vec<ChartTransfer>, Error> {
+        Self::require_chart_access(&env, chart_id, &requester, false)?;
+        Ok(env.storage().persistent().get(&DataKey::ChartTransfers(chart_id)).unwrap_or(Vec::new(&env)))
+    }
+
+    /// Charts a dentist currently holds write authority over
+    pub fn get_dentist_charts(env: Env, dentist_id: Address) -> Vec<u64> {
+        dentist_id.require_auth();
+        env.storage().persistent().get(&DataKey::DentistCharts(dentist_id)).unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_chart_access_log(
+        env: Env,
+        chart_id: u64,
+        requester: Address,
+    ) -> Result<Vec<ChartAccessLogEntry>, Error> {
+        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
+        requester.require_auth();
+        if requester != chart.patient_id && requester != chart.dentist_id {
+            return Err(Error::Unauthorized);
+        }
+
+        Ok(env.storage().persistent().get(&DataKey::ChartAccessLog(chart_id)).unwrap_or(Vec::new(&env)))
+    }
+
     pub fn create_treatment_plan(
         env: Env,
         patient_id: Address,
@@ -98,8 +522,8 @@ impl DentalRecordsContract {
         count += 1;
 
         let plan = TreatmentPlan {
-            patient_id,
-            dentist_id,
+            patient_id: patient_id.clone(),
+            dentist_id: dentist_id.clone(),
             plan_date,
             procedures,
             phased_treatment,
@@ -109,6 +533,14 @@ impl DentalRecordsContract {
         env.storage().persistent().set(&DataKey::Plan(count), &plan);
         env.storage().instance().set(&DataKey::PlanCount, &count);
 
+        TreatmentPlanCreated {
+            plan_id: count,
+            patient_id,
+            dentist_id,
+            estimated_cost,
+        }
+        .publish(&env);
+
         Ok(count)
     }
 
@@ -138,6 +570,14 @@ impl DentalRecordsContract {
         env.storage().persistent().set(&DataKey::Appt(count), &appt);
         env.storage().instance().set(&DataKey::AppointmentCount, &count);
 
+        ProcedureScheduled {
+            appointment_id: count,
+            treatment_plan_id,
+            procedure_id,
+            scheduled_date,
+        }
+        .publish(&env);
+
         Ok(count)
     }
 
@@ -158,7 +598,7 @@ impl DentalRecordsContract {
         env.storage().persistent().set(&DataKey::Appt(appointment_id), &appt);
 
         let log = ProcedureLog {
-            dentist_id,
+            dentist_id: dentist_id.clone(),
             procedure_date,
             procedures_completed,
             anesthesia_used,
@@ -168,6 +608,13 @@ impl DentalRecordsContract {
 
         env.storage().persistent().set(&DataKey::ProcedureLog(appointment_id), &log);
 
+        ProcedurePerformed {
+            appointment_id,
+            dentist_id,
+            procedure_date,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -186,8 +633,8 @@ impl DentalRecordsContract {
         count += 1;
 
         let radio = Radiograph {
-            patient_id,
-            image_type,
+            patient_id: patient_id.clone(),
+            image_type: image_type.clone(),
             image_date,
             teeth_included,
             findings,
@@ -197,6 +644,13 @@ impl DentalRecordsContract {
         env.storage().persistent().set(&DataKey::Radio(count), &radio);
         env.storage().instance().set(&DataKey::RadiographCount, &count);
 
+        RadiographRecorded {
+            radiograph_id: count,
+            patient_id,
+            image_type,
+        }
+        .publish(&env);
+
         Ok(count)
     }
 
@@ -216,16 +670,29 @@ impl DentalRecordsContract {
         count += 1;
 
         let ortho = OrthodonticTreatment {
-            patient_id,
-            orthodontist_id,
+            patient_id: patient_id.clone(),
+            orthodontist_id: orthodontist_id.clone(),
             treatment_start_date,
-            appliance_type,
+            appliance_type: appliance_type.clone(),
             treatment_plan_hash,
             estimated_duration_months,
+            next_adjustment_due: None,
+            phase: OrthoPhase::Active,
+            debond_date: None,
+            outcome_summary_hash: None,
         };
 
         env.storage().persistent().set(&DataKey::Ortho(count), &ortho);
         env.storage().instance().set(&DataKey::OrthoCount, &count);
+        Self::index_active_ortho(&env, &orthodontist_id, count, true);
+
+        OrthoTreatmentStarted {
+            ortho_treatment_id: count,
+            patient_id,
+            orthodontist_id,
+            appliance_type,
+        }
+        .publish(&env);
 
         Ok(count)
     }
@@ -238,9 +705,13 @@ impl DentalRecordsContract {
         arch_wire_change: bool,
         next_appointment_weeks: u32,
     ) -> Result<(), Error> {
-        let ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)?;
+        let mut ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)?;
         ortho.orthodontist_id.require_auth();
 
+        if ortho.phase != OrthoPhase::Active {
+            return Err(Error::InvalidInput);
+        }
+
         let adj = OrthoAdjustment {
             adjustment_date,
             adjustments_made,
@@ -250,9 +721,152 @@ impl DentalRecordsContract {
 
         env.storage().persistent().set(&DataKey::OrthoAdj(ortho_treatment_id, adjustment_date), &adj);
 
+        let next_adjustment_due = adjustment_date + next_appointment_weeks as u64 * SECONDS_PER_WEEK;
+        ortho.next_adjustment_due = Some(next_adjustment_due);
+        env.storage().persistent().set(&DataKey::Ortho(ortho_treatment_id), &ortho);
+
+        OrthoAdjustmentRecorded {
+            ortho_treatment_id,
+            adjustment_date,
+            next_adjustment_due,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn complete_ortho_treatment(
+        env: Env,
+        ortho_treatment_id: u64,
+        debond_date: u64,
+        outcome_summary_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)?;
+        ortho.orthodontist_id.require_auth();
+
+        if ortho.phase != OrthoPhase::Active || debond_date < ortho.treatment_start_date {
+            return Err(Error::InvalidInput);
+        }
+
+        ortho.phase = OrthoPhase::Completed;
+        ortho.debond_date = Some(debond_date);
+        ortho.outcome_summary_hash = Some(outcome_summary_hash);
+        ortho.next_adjustment_due = None;
+        env.storage().persistent().set(&DataKey::Ortho(ortho_treatment_id), &ortho);
+        Self::index_active_ortho(&env, &ortho.orthodontist_id, ortho_treatment_id, false);
+
+        OrthoTreatmentCompleted {
+            ortho_treatment_id,
+            debond_date,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
+    pub fn start_retention_phase(
+        env: Env,
+        ortho_treatment_id: u64,
+        retainer_type: Symbol,
+        wear_schedule: String,
+        start_date: u64,
+    ) -> Result<(), Error> {
+        let mut ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)?;
+        ortho.orthodontist_id.require_auth();
+
+        // Retention follows debonding
+        if ortho.phase != OrthoPhase::Completed {
+            return Err(Error::InvalidInput);
+        }
+
+        let plan = RetentionPlan {
+            retainer_type: retainer_type.clone(),
+            wear_schedule,
+            start_date,
+        };
+        env.storage().persistent().set(&DataKey::Retention(ortho_treatment_id), &plan);
+
+        ortho.phase = OrthoPhase::Retention;
+        env.storage().persistent().set(&DataKey::Ortho(ortho_treatment_id), &ortho);
+
+        RetentionPhaseStarted {
+            ortho_treatment_id,
+            retainer_type,
+            start_date,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn record_retention_check(
+        env: Env,
+        ortho_treatment_id: u64,
+        check_date: u64,
+        compliant: bool,
+        relapse_noted: bool,
+        notes: Option<String>,
+    ) -> Result<(), Error> {
+        let ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)?;
+        ortho.orthodontist_id.require_auth();
+
+        if ortho.phase != OrthoPhase::Retention {
+            return Err(Error::InvalidInput);
+        }
+
+        let key = DataKey::RetentionChecks(ortho_treatment_id);
+        let mut checks: Vec<RetentionCheck> = env.storage().persistent().get(&key).unwrap_or(Vec::new(&env));
+        checks.push_back(RetentionCheck {
+            check_date,
+            compliant,
+            relapse_noted,
+            notes,
+        });
+        env.storage().persistent().set(&key, &checks);
+
+        RetentionCheckRecorded {
+            ortho_treatment_id,
+            check_date,
+            compliant,
+            relapse_noted,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_ortho_treatment(env: Env, ortho_treatment_id: u64) -> Result<OrthodonticTreatment, Error> {
+        env.storage().persistent().get(&DataKey::Ortho(ortho_treatment_id)).ok_or(Error::NotFound)
+    }
+
+    pub fn get_retention_plan(env: Env, ortho_treatment_id: u64) -> Result<RetentionPlan, Error> {
+        env.storage().persistent().get(&DataKey::Retention(ortho_treatment_id)).ok_or(Error::NotFound)
+    }
+
+    pub fn get_retention_checks(env: Env, ortho_treatment_id: u64) -> Vec<RetentionCheck> {
+        env.storage().persistent().get(&DataKey::RetentionChecks(ortho_treatment_id)).unwrap_or(Vec::new(&env))
+    }
+
+    /// Active treatments for an orthodontist whose next adjustment is past due.
+    /// `offset` and `limit` page through the orthodontist's active treatments,
+    /// so a page holds at most `limit` ids and may hold fewer.
+    pub fn get_overdue_ortho_adjustments(env: Env, orthodontist_id: Address, offset: u32, limit: u32) -> Vec<u64> {
+        let active: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OrthodontistActive(orthodontist_id))
+            .unwrap_or(Vec::new(&env));
+        let now = env.ledger().timestamp();
+
+        shared_pagination::paginate_map(&env, &active, offset, limit, |id| {
+            let ortho: OrthodonticTreatment = env.storage().persistent().get(&DataKey::Ortho(id))?;
+            match ortho.next_adjustment_due {
+                Some(due) if due < now => Some(id),
+                _ => None,
+            }
+        })
+    }
+
     pub fn prescribe_dental_medication(
         env: Env,
         patient_id: Address,
@@ -267,8 +881,8 @@ impl DentalRecordsContract {
         count += 1;
 
         let rx = MedicationPrescription {
-            patient_id,
-            dentist_id,
+            patient_id: patient_id.clone(),
+            dentist_id: dentist_id.clone(),
             medication,
             indication,
             dosage_instructions,
@@ -277,6 +891,13 @@ impl DentalRecordsContract {
         env.storage().persistent().set(&DataKey::Rx(count), &rx);
         env.storage().instance().set(&DataKey::RxCount, &count);
 
+        DentalMedicationPrescribed {
+            prescription_id: count,
+            patient_id,
+            dentist_id,
+        }
+        .publish(&env);
+
         Ok(count)
     }
 
@@ -299,10 +920,120 @@ impl DentalRecordsContract {
             consent_document_hash: consent_document_hash.clone(),
         };
 
-        env.storage().persistent().set(&DataKey::Consent(consent_document_hash), &consent);
+        env.storage().persistent().set(&DataKey::Consent(consent_document_hash.clone()), &consent);
+
+        InformedConsentDocumented {
+            patient_id,
+            consent_document_hash,
+            consent_date,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Authorizes `actor` on a chart: the owning dentist always, the patient for reads,
+    /// and grantees according to their access level. Access by grantees is logged.
+    fn require_chart_access(env: &Env, chart_id: u64, actor: &Address, write: bool) -> Result<(), Error> {
+        let chart: DentalChart = env.storage().persistent().get(&DataKey::Chart(chart_id)).ok_or(Error::NotFound)?;
+        actor.require_auth();
+
+        if *actor == chart.dentist_id || (!write && *actor == chart.patient_id) {
+            return Ok(());
+        }
+
+        let level: ChartAccessLevel = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ChartAccess(chart_id, actor.clone()))
+            .ok_or(Error::Unauthorized)?;
+        if write && level != ChartAccessLevel::ReadWrite {
+            return Err(Error::Unauthorized);
+        }
+
+        let action = if write { symbol_short!("write") } else { symbol_short!("read") };
+        Self::log_chart_access(env, chart_id, actor, action, None);
 
         Ok(())
     }
+
+    /// Recurring services are next due one interval out; one-time services are due now
+    fn recommendation(service: Symbol, interval_months: u32, now: u64) -> PreventiveRecommendation {
+        let due_date = if interval_months == 0 {
+            now
+        } else {
+            now + interval_months as u64 * SECONDS_PER_MONTH
+        };
+        PreventiveRecommendation {
+            service,
+            interval_months,
+            due_date,
+        }
+    }
+
+    fn index_dentist_chart(env: &Env, dentist_id: &Address, chart_id: u64, add: bool) {
+        let key = DataKey::DentistCharts(dentist_id.clone());
+        let mut charts: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if add {
+            charts.push_back(chart_id);
+        } else if let Some(index) = charts.first_index_of(chart_id) {
+            charts.remove(index);
+        }
+        env.storage().persistent().set(&key, &charts);
+    }
+
+    fn index_active_ortho(env: &Env, orthodontist_id: &Address, ortho_treatment_id: u64, add: bool) {
+        let key = DataKey::OrthodontistActive(orthodontist_id.clone());
+        let mut active: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        if add {
+            active.push_back(ortho_treatment_id);
+        } else if let Some(index) = active.first_index_of(ortho_treatment_id) {
+            active.remove(index);
+        }
+        env.storage().persistent().set(&key, &active);
+    }
+
+    fn log_chart_access(env: &Env, chart_id: u64, actor: &Address, action: Symbol, subject: Option<Address>) {
+        let key = DataKey::ChartAccessLog(chart_id);
+        let mut log: Vec<ChartAccessLogEntry> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+        log.push_back(ChartAccessLogEntry {
+            actor: actor.clone(),
+            action,
+            subject,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &log);
+    }
 }
 
 mod test;
@@ -1,13 +1,13 @@
 #![cfg(test)]
 
-use crate::{DentalRecordsContract, DentalRecordsContractClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
+use crate::{DentalRecordsContract, DentalRecordsContractClient, SCHEMA_VERSION};
+use soroban_sdk::{testutils::{Address as _, Events, Ledger}, Address, BytesN, Env, String, Symbol, Vec};
 use crate::types::*;
 
 fn create_env() -> (Env, DentalRecordsContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
-    let contract_id = env.register_contract(None, DentalRecordsContract);
+    let contract_id = env.register(DentalRecordsContract, ());
     let client = DentalRecordsContractClient::new(&env, &contract_id);
     (env, client)
 }
@@ -31,6 +31,7 @@ fn test_tooth_charting_systems() {
     let tooth_num = String::from_str(&env, "8"); // Universal notation for maxillary right central incisor
     client.record_tooth_condition(
         &chart_id,
+        &dentist_id,
         &tooth_num,
         &Some(Symbol::new(&env, "occlusal")),
         &Symbol::new(&env, "caries"),
@@ -38,6 +39,196 @@ fn test_tooth_charting_systems() {
     );
 }
 
+#[test]
+fn test_tooth_history_timeline() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let dentist_id = Address::generate(&env);
+
+    let chart_id = client.create_dental_chart(&patient_id, &dentist_id, &1672531200, &Symbol::new(&env, "universal"));
+    let tooth_num = String::from_str(&env, "19");
+
+    env.ledger().set_timestamp(1672531200);
+    client.record_tooth_condition(
+        &chart_id,
+        &dentist_id,
+        &tooth_num,
+        &Some(Symbol::new(&env, "occlusal")),
+        &Symbol::new(&env, "caries"),
+        &None,
+    );
+
+    env.ledger().set_timestamp(1675209600);
+    client.record_tooth_condition(
+        &chart_id,
+        &dentist_id,
+        &tooth_num,
+        &Some(Symbol::new(&env, "occlusal")),
+        &Symbol::new(&env, "filling"),
+        &Some(String::from_str(&env, "composite")),
+    );
+
+    let history = client.get_tooth_history(&chart_id, &tooth_num, &patient_id);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().condition, Symbol::new(&env, "caries"));
+    assert_eq!(history.get(0).unwrap().recorded_at, 1672531200);
+    assert_eq!(history.get(1).unwrap().condition, Symbol::new(&env, "filling"));
+    assert_eq!(history.get(1).unwrap().recorded_by, dentist_id);
+
+    let untouched = client.get_tooth_history(&chart_id, &String::from_str(&env, "3"), &dentist_id);
+    assert_eq!(untouched.len(), 0);
+}
+
+#[test]
+fn test_chart_sharing_with_oral_surgeon() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let dentist_id = Address::generate(&env);
+    let surgeon_id = Address::generate(&env);
+
+    let chart_id = client.create_dental_chart(&patient_id, &dentist_id, &1672531200, &Symbol::new(&env, "universal"));
+    let tooth_num = String::from_str(&env, "32");
+
+    // No access before the patient grants it
+    assert!(client.try_get_tooth_history(&chart_id, &tooth_num, &surgeon_id).is_err());
+
+    client.grant_chart_access(&chart_id, &surgeon_id, &ChartAccessLevel::Read);
+    assert_eq!(env.events().all().len(), 1);
+    client.get_tooth_history(&chart_id, &tooth_num, &surgeon_id);
+    let write = client.try_record_tooth_condition(
+        &chart_id,
+        &surgeon_id,
+        &tooth_num,
+        &None,
+        &Symbol::new(&env, "missing"),
+        &None,
+    );
+    assert!(write.is_err());
+
+    client.grant_chart_access(&chart_id, &surgeon_id, &ChartAccessLevel::ReadWrite);
+    client.record_tooth_condition(
+        &chart_id,
+        &surgeon_id,
+        &tooth_num,
+        &None,
+        &Symbol::new(&env, "missing"),
+        &Some(String::from_str(&env, "surgical extraction")),
+    );
+    let history = client.get_tooth_history(&chart_id, &tooth_num, &dentist_id);
+    assert_eq!(history.get(0).unwrap().recorded_by, surgeon_id);
+
+    client.revoke_chart_access(&chart_id, &surgeon_id);
+    assert!(client.try_get_tooth_history(&chart_id, &tooth_num, &surgeon_id).is_err());
+
+    let log = client.get_chart_access_log(&chart_id, &patient_id);
+    assert_eq!(log.len(), 5);
+    assert_eq!(log.get(0).unwrap().action, Symbol::new(&env, "grant"));
+    assert_eq!(log.get(0).unwrap().subject, Some(surgeon_id.clone()));
+    assert_eq!(log.get(1).unwrap().action, Symbol::new(&env, "read"));
+    assert_eq!(log.get(3).unwrap().action, Symbol::new(&env, "write"));
+    assert_eq!(log.get(4).unwrap().action, Symbol::new(&env, "revoke"));
+}
+
+#[test]
+fn test_chart_transfer_to_new_dentist() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let old_dentist = Address::generate(&env);
+    let new_dentist = Address::generate(&env);
+
+    let chart_id = client.create_dental_chart(&patient_id, &old_dentist, &1672531200, &Symbol::new(&env, "universal"));
+    let other_chart = client.create_dental_chart(&Address::generate(&env), &old_dentist, &1672531200, &Symbol::new(&env, "fdi"));
+    let tooth_num = String::from_str(&env, "14");
+    client.record_tooth_condition(&chart_id, &old_dentist, &tooth_num, &None, &Symbol::new(&env, "filling"), &None);
+
+    assert!(client.try_transfer_chart(&chart_id, &old_dentist).is_err());
+
+    env.ledger().set_timestamp(1675209600);
+    client.transfer_chart(&chart_id, &new_dentist);
+    assert_eq!(env.events().all().len(), 1);
+
+    // Write authority moves; the prior dentist's entry stays attributed to them
+    let write = client.try_record_tooth_condition(&chart_id, &old_dentist, &tooth_num, &None, &Symbol::new(&env, "crown"), &None);
+    assert!(write.is_err());
+    client.record_tooth_condition(&chart_id, &new_dentist, &tooth_num, &None, &Symbol::new(&env, "crown"), &None);
+    let history = client.get_tooth_history(&chart_id, &tooth_num, &new_dentist);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().recorded_by, old_dentist);
+    assert_eq!(history.get(1).unwrap().recorded_by, new_dentist);
+
+    let transfers = client.get_chart_transfers(&chart_id, &patient_id);
+    assert_eq!(transfers.len(), 1);
+    assert_eq!(transfers.get(0).unwrap().from_dentist, old_dentist);
+    assert_eq!(transfers.get(0).unwrap().transferred_at, 1675209600);
+
+    assert_eq!(client.get_dentist_charts(&old_dentist), Vec::from_array(&env, [other_chart]));
+    assert_eq!(client.get_dentist_charts(&new_dentist), Vec::from_array(&env, [chart_id]));
+}
+
+#[test]
+fn test_caries_risk_and_recalls() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let dentist_id = Address::generate(&env);
+    let month = 30 * 24 * 60 * 60u64;
+
+    env.ledger().set_timestamp(1672531200);
+    let chart_id = client.create_dental_chart(&patient_id, &dentist_id, &1672531200, &Symbol::new(&env, "universal"));
+
+    let high = client.assess_caries_risk(
+        &chart_id,
+        &dentist_id,
+        &CariesRiskFactors {
+            frequent_sugar_intake: true,
+            fluoridated_water: false,
+            fluoride_toothpaste: true,
+            new_lesions_last_3_years: 3,
+            low_salivary_flow: false,
+        },
+    );
+    assert_eq!(high.risk_level, Symbol::new(&env, "high"));
+    assert_eq!(high.risk_score, 6);
+    assert_eq!(high.recommendations.len(), 3);
+
+    // Sealants are due immediately; varnish and recall exam in three months
+    let due = client.get_due_recalls(&dentist_id);
+    assert_eq!(due.len(), 1);
+    assert_eq!(due.get(0).unwrap().service, Symbol::new(&env, "sealants"));
+
+    client.record_preventive_service(&chart_id, &dentist_id, &Symbol::new(&env, "sealants"), &1672531200);
+    assert!(client.get_due_recalls(&dentist_id).is_empty());
+    assert_eq!(client.get_chart_recalls(&chart_id, &patient_id).len(), 2);
+
+    env.ledger().set_timestamp(1672531200 + 3 * month);
+    assert_eq!(client.get_due_recalls(&dentist_id).len(), 2);
+    client.record_preventive_service(&chart_id, &dentist_id, &Symbol::new(&env, "varnish"), &(1672531200 + 3 * month));
+    let due = client.get_due_recalls(&dentist_id);
+    assert_eq!(due.len(), 1);
+    assert_eq!(due.get(0).unwrap().service, Symbol::new(&env, "recall"));
+
+    // Improved risk replaces the preventive plan
+    let low = client.assess_caries_risk(
+        &chart_id,
+        &dentist_id,
+        &CariesRiskFactors {
+            frequent_sugar_intake: false,
+            fluoridated_water: true,
+            fluoride_toothpaste: true,
+            new_lesions_last_3_years: 0,
+            low_salivary_flow: false,
+        },
+    );
+    assert_eq!(low.risk_level, Symbol::new(&env, "low"));
+    let recalls = client.get_chart_recalls(&chart_id, &dentist_id);
+    assert_eq!(recalls.len(), 1);
+    assert_eq!(recalls.get(0).unwrap().interval_months, 12);
+    assert_eq!(client.get_caries_risk_history(&chart_id, &patient_id).len(), 2);
+
+    assert!(client
+        .try_record_preventive_service(&chart_id, &dentist_id, &Symbol::new(&env, "sealants"), &1672531200)
+        .is_err());
+}
+
 #[test]
 fn test_periodontal_tracking() {
     let (env, client) = create_env();
@@ -50,6 +241,7 @@ fn test_periodontal_tracking() {
     // Record periodontal assessment
     client.record_periodontal_assessment(
         &chart_id,
+        &dentist_id,
         &tooth_num,
         &Symbol::new(&env, "mb"),
         &4,
@@ -59,6 +251,58 @@ fn test_periodontal_tracking() {
     );
 }
 
+fn perio_site(env: &Env, tooth: &str, site: &str, probing_depth: u32, recession: u32) -> PerioSiteMeasurement {
+    PerioSiteMeasurement {
+        tooth_number: String::from_str(env, tooth),
+        site: Symbol::new(env, site),
+        probing_depth,
+        recession,
+        bleeding_on_probing: false,
+        mobility: None,
+    }
+}
+
+#[test]
+fn test_perio_exam_comparison() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let dentist_id = Address::generate(&env);
+
+    let chart_id = client.create_dental_chart(&patient_id, &dentist_id, &1672531200, &Symbol::new(&env, "universal"));
+
+    let baseline = client.record_perio_exam(
+        &chart_id,
+        &dentist_id,
+        &1672531200,
+        &Vec::from_array(&env, [
+            perio_site(&env, "3", "mb", 3, 0),
+            perio_site(&env, "3", "db", 3, 1),
+            perio_site(&env, "14", "mb", 4, 0),
+        ]),
+    );
+    let follow_up = client.record_perio_exam(
+        &chart_id,
+        &dentist_id,
+        &1688169600,
+        &Vec::from_array(&env, [
+            perio_site(&env, "3", "mb", 5, 1),
+            perio_site(&env, "3", "db", 4, 1),
+            perio_site(&env, "14", "mb", 4, 2),
+        ]),
+    );
+
+    assert_eq!(client.get_chart_perio_exams(&chart_id, &dentist_id).len(), 2);
+    assert_eq!(client.get_perio_exam(&follow_up, &dentist_id).measurements.len(), 3);
+
+    let progressed = client.compare_perio_exams(&chart_id, &baseline, &follow_up, &dentist_id);
+    assert_eq!(progressed.len(), 2);
+    let first = progressed.get(0).unwrap();
+    assert_eq!(first.tooth_number, String::from_str(&env, "3"));
+    assert_eq!(first.site, Symbol::new(&env, "mb"));
+    assert_eq!(first.change, 3);
+    assert_eq!(progressed.get(1).unwrap().tooth_number, String::from_str(&env, "14"));
+}
+
 #[test]
 fn test_treatment_planning_flow() {
     let (env, client) = create_env();
@@ -138,6 +382,83 @@ fn test_orthodontic_tracking_flow() {
     );
 }
 
+#[test]
+fn test_ortho_completion_and_retention() {
+    let (env, client) = create_env();
+    let patient_id = Address::generate(&env);
+    let orthodontist_id = Address::generate(&env);
+
+    let ortho_id = client.track_orthodontic_treatment(
+        &patient_id,
+        &orthodontist_id,
+        &1672531200,
+        &Symbol::new(&env, "braces"),
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &18,
+    );
+
+    // Adjustment due 4 weeks after 2023-02-01
+    client.record_ortho_adjustment(&ortho_id, &1675209600, &Vec::new(&env), &true, &4);
+    env.ledger().set_timestamp(1675209600 + 3 * 7 * 86400);
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &0, &10).len(), 0);
+    env.ledger().set_timestamp(1675209600 + 5 * 7 * 86400);
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &0, &10), Vec::from_array(&env, [ortho_id]));
+
+    client.complete_ortho_treatment(&ortho_id, &1720000000, &BytesN::from_array(&env, &[5u8; 32]));
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &0, &10).len(), 0);
+    assert!(client
+        .try_record_ortho_adjustment(&ortho_id, &1720000001, &Vec::new(&env), &false, &4)
+        .is_err());
+
+    client.start_retention_phase(
+        &ortho_id,
+        &Symbol::new(&env, "essix"),
+        &String::from_str(&env, "full-time 6 months, then nightly"),
+        &1720000000,
+    );
+    client.record_retention_check(&ortho_id, &1722600000, &true, &false, &None);
+
+    let ortho = client.get_ortho_treatment(&ortho_id);
+    assert_eq!(ortho.phase, OrthoPhase::Retention);
+    assert_eq!(ortho.debond_date, Some(1720000000));
+    assert_eq!(client.get_retention_plan(&ortho_id).retainer_type, Symbol::new(&env, "essix"));
+    assert_eq!(client.get_retention_checks(&ortho_id).len(), 1);
+}
+
+#[test]
+fn test_overdue_ortho_adjustments_paged_per_orthodontist() {
+    let (env, client) = create_env();
+    let orthodontist_id = Address::generate(&env);
+    let other_orthodontist = Address::generate(&env);
+
+    let start = |orthodontist: &Address| {
+        client.track_orthodontic_treatment(
+            &Address::generate(&env),
+            orthodontist,
+            &1672531200,
+            &Symbol::new(&env, "braces"),
+            &BytesN::from_array(&env, &[2u8; 32]),
+            &18,
+        )
+    };
+    let first = start(&orthodontist_id);
+    let second = start(&orthodontist_id);
+    let third = start(&orthodontist_id);
+    let elsewhere = start(&other_orthodontist);
+    for id in [first, second, third, elsewhere] {
+        client.record_ortho_adjustment(&id, &1675209600, &Vec::new(&env), &true, &4);
+    }
+    env.ledger().set_timestamp(1675209600 + 5 * 7 * 86400);
+
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &0, &2), Vec::from_array(&env, [first, second]));
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &2, &2), Vec::from_array(&env, [third]));
+
+    // Completed treatments leave the orthodontist's active index
+    client.complete_ortho_treatment(&second, &1720000000, &BytesN::from_array(&env, &[5u8; 32]));
+    assert_eq!(client.get_overdue_ortho_adjustments(&orthodontist_id, &0, &10), Vec::from_array(&env, [first, third]));
+    assert_eq!(client.get_overdue_ortho_adjustments(&other_orthodontist, &0, &10), Vec::from_array(&env, [elsewhere]));
+}
+
 #[test]
 fn test_procedure_documentation_flow() {
     let (env, client) = create_env();
@@ -203,15 +524,38 @@ fn test_procedure_documentation_flow() {
 }
 
 #[test]
-#[should_panic(expected = "NotFound")]
+#[should_panic(expected = "Error(Contract, #1)")]
 fn test_not_found() {
     let (env, client) = create_env();
+    let dentist_id = Address::generate(&env);
     let tooth_num = String::from_str(&env, "8");
     client.record_tooth_condition(
         &999,
+        &dentist_id,
         &tooth_num,
         &Some(Symbol::new(&env, "occlusal")),
         &Symbol::new(&env, "caries"),
         &Some(String::from_str(&env, "deep decay")),
     );
 }
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, client) = create_env();
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -1,6 +1,4 @@
-#![no_std]
-
-use soroban_sdk::{contracttype, Address, BytesN, String, Symbol, Vec};
+use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Symbol, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -33,12 +31,76 @@ pub struct DentalChart {
     pub tooth_notation_system: Symbol,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ChartAccessLevel {
+    Read,
+    ReadWrite,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartAccessLogEntry {
+    pub actor: Address,
+    pub action: Symbol, // grant, revoke, read, write
+    pub subject: Option<Address>, // grantee for grant/revoke entries
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChartTransfer {
+    pub from_dentist: Address,
+    pub to_dentist: Address,
+    pub transferred_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CariesRiskFactors {
+    pub frequent_sugar_intake: bool,
+    pub fluoridated_water: bool,
+    pub fluoride_toothpaste: bool,
+    pub new_lesions_last_3_years: u32,
+    pub low_salivary_flow: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreventiveRecommendation {
+    pub service: Symbol, // varnish, sealants, recall
+    pub interval_months: u32, // 0 for one-time services
+    pub due_date: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CariesRiskAssessment {
+    pub chart_id: u64,
+    pub assessed_by: Address,
+    pub assessed_at: u64,
+    pub factors: CariesRiskFactors,
+    pub risk_score: u32,
+    pub risk_level: Symbol, // low, moderate, high
+    pub recommendations: Vec<PreventiveRecommendation>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecallDue {
+    pub chart_id: u64,
+    pub service: Symbol,
+    pub due_date: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ToothCondition {
     pub surface: Option<Symbol>,
     pub condition: Symbol,
     pub condition_details: Option<String>,
+    pub recorded_by: Address,
+    pub recorded_at: u64,
 }
 
 #[contracttype]
@@ -50,6 +112,36 @@ pub struct PeriodontalAssessment {
     pub mobility: Option<u32>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerioSiteMeasurement {
+    pub tooth_number: String,
+    pub site: Symbol,
+    pub probing_depth: u32,
+    pub recession: u32,
+    pub bleeding_on_probing: bool,
+    pub mobility: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerioExam {
+    pub chart_id: u64,
+    pub exam_date: u64,
+    pub examiner_id: Address,
+    pub measurements: Vec<PerioSiteMeasurement>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PerioSiteProgression {
+    pub tooth_number: String,
+    pub site: Symbol,
+    pub attachment_loss_a: u32, // clinical attachment loss (probing depth + recession) in exam_a
+    pub attachment_loss_b: u32,
+    pub change: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TreatmentPlan {
@@ -92,6 +184,35 @@ pub struct OrthodonticTreatment {
     pub appliance_type: Symbol,
     pub treatment_plan_hash: BytesN<32>,
     pub estimated_duration_months: u32,
+    pub next_adjustment_due: Option<u64>,
+    pub phase: OrthoPhase,
+    pub debond_date: Option<u64>,
+    pub outcome_summary_hash: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum OrthoPhase {
+    Active,
+    Retention,
+    Completed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionPlan {
+    pub retainer_type: Symbol, // hawley, essix, bonded
+    pub wear_schedule: String, // e.g. "full-time 6 months, then nightly"
+    pub start_date: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RetentionCheck {
+    pub check_date: u64,
+    pub compliant: bool,
+    pub relapse_noted: bool,
+    pub notes: Option<String>,
 }
 
 #[contracttype]
@@ -106,10 +227,20 @@ pub struct OrthoAdjustment {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    Admin,
     ChartCount,
     Chart(u64),                     // chart_id
-    ToothCond(u64, String),         // chart_id, tooth_number
+    ToothHistory(u64, String),      // chart_id, tooth_number -> Vec<ToothCondition>
     Perio(u64, String, Symbol),     // chart_id, tooth_number, site
+    PerioExamCount,
+    PerioExam(u64),                 // exam_id
+    ChartPerioExams(u64),           // chart_id -> Vec<exam_id>
+    ChartAccess(u64, Address),      // chart_id, grantee -> ChartAccessLevel
+    ChartAccessLog(u64),            // chart_id -> Vec<ChartAccessLogEntry>
+    ChartTransfers(u64),            // chart_id -> Vec<ChartTransfer>
+    DentistCharts(Address),         // dentist_id -> Vec<chart_id>
+    CariesRisk(u64),                // chart_id -> Vec<CariesRiskAssessment>
+    Recalls(u64),                   // chart_id -> Vec<PreventiveRecommendation>
     PlanCount,
     Plan(u64),                      // treatment_plan_id
     AppointmentCount,
@@ -120,6 +251,9 @@ pub enum DataKey {
     OrthoCount,
     Ortho(u64),                     // ortho_treatment_id
     OrthoAdj(u64, u64),             // ortho_treatment_id, adjustment_date
+    OrthodontistActive(Address),    // orthodontist_id -> Vec<ortho_treatment_id> still in the active phase
+    Retention(u64),                 // ortho_treatment_id -> RetentionPlan
+    RetentionChecks(u64),           // ortho_treatment_id -> Vec<RetentionCheck>
     RxCount,
     Rx(u64),                        // rx_id
     Consent(BytesN<32>),
@@ -156,10 +290,13 @@ pub struct InformedConsent {
     pub consent_document_hash: BytesN<32>,
 }
 
-#[contracttype]
-#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
 pub enum Error {
     NotFound = 1,
     Unauthorized = 2,
     InvalidInput = 3,
+    AlreadyInitialized = 4,
+    NotInitialized = 5,
 }
@@ -161,3 +161,43 @@ fn test_multiple_doctors() {
         String::from_str(&env, "Orthopedics")
     );
 }
+
+#[test]
+fn test_initialize_records_schema_version() {
+    let env = Env::default();
+    let contract_id = env.register(DoctorRegistry, ());
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    assert_eq!(client.schema_version(), 0);
+    client.initialize(&Address::generate(&env));
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(DoctorRegistry, ());
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize(&admin);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized admin")]
+fn test_upgrade_by_stranger_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(DoctorRegistry, ());
+    let client = DoctorRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    client.initialize(&Address::generate(&env));
+    client.upgrade(
+        &Address::generate(&env),
+        &BytesN::from_array(&env, &[0u8; 32]),
+    );
+}
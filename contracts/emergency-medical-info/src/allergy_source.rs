@@ -0,0 +1,49 @@
+//! Client interface for the allergy-management contract, used to reconcile
+//! emergency profiles against the allergy source of truth.
+//!
+//! The record types mirror allergy-management's so values decode across the
+//! contract boundary; keep them in sync with that contract.
+
+use soroban_sdk::{contractclient, contracttype, Address, Env, String, Symbol, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AllergyStatus {
+    Active,
+    Resolved,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct SeverityUpdate {
+    pub previous_severity: Symbol,
+    pub new_severity: Symbol,
+    pub updated_by: Address,
+    pub updated_at: u64,
+    pub reason: String,
+}
+
+#[contracttype]
+#[derive(Clone, Debug)]
+pub struct AllergyRecord {
+    pub allergy_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub allergen: String,
+    pub allergen_type: Symbol,
+    pub reaction_type: Vec<String>,
+    pub severity: Symbol,
+    pub onset_date: Option<u64>,
+    pub recorded_date: u64,
+    pub verified: bool,
+    pub status: AllergyStatus,
+    pub resolution_date: Option<u64>,
+    pub resolution_reason: Option<String>,
+    pub severity_history: Vec<SeverityUpdate>,
+}
+
+#[contractclient(name = "AllergySourceClient")]
+pub trait AllergySource {
+    fn get_active_allergies(env: Env, patient_id: Address, requester: Address)
+        -> Vec<AllergyRecord>;
+}
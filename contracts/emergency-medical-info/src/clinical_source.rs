@@ -0,0 +1,14 @@
+//! Client interface for the medication and problem-list source of truth, used
+//! to reconcile an emergency profile's current medications and active
+//! conditions alongside its allergies.
+//!
+//! Any contract exposing these entrypoints can be configured as the source.
+
+use soroban_sdk::{contractclient, Address, Env, String, Vec};
+
+#[contractclient(name = "ClinicalSourceClient")]
+pub trait ClinicalSource {
+    fn get_active_medications(env: Env, patient_id: Address, requester: Address) -> Vec<String>;
+
+    fn get_active_conditions(env: Env, patient_id: Address, requester: Address) -> Vec<String>;
+}
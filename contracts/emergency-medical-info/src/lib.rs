@@ -1,9 +1,18 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
+use shared_access::AccessError;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contractevent, contractimpl, contracttype, symbol_short, xdr::ToXdr, Address, BytesN,
+    Env, String, Symbol, Vec,
 };
 
+mod allergy_source;
+mod clinical_source;
+
+pub use allergy_source::{AllergyRecord, AllergySource, AllergySourceClient};
+pub use clinical_source::{ClinicalSource, ClinicalSourceClient};
+
 /// --------------------
 /// Emergency Structures
 /// --------------------
@@ -26,6 +35,18 @@ pub struct EmergencyProfile {
     pub current_medications: Vec<String>,
     pub dnr_status: bool,
     pub emergency_contacts: Vec<EmergencyContact>,
+    pub version: u32,
+    pub last_updated: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileChange {
+    pub version: u32,
+    pub changed_by: Address,
+    pub change_type: Symbol,
+    pub detail: Option<String>,
+    pub timestamp: u64,
 }
 
 #[contracttype]
@@ -46,8 +67,61 @@ pub struct EmergencyAccessLog {
     pub justification: String,
     pub location: String,
     pub access_time: u64,
+    pub granted: bool, // false when the requester was not a registered emergency provider
+    pub disclosure_level: DisclosureLevel,
+    pub review_status: AccessReviewStatus,
+    pub review_note: Option<String>,
+    pub reviewed_at: Option<u64>,
+}
+
+/// How much of the profile a break-glass query may reveal
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DisclosureLevel {
+    Field,    // blood type, DNR status and critical allergies only
+    Hospital, // full profile; emergency department providers only
+}
+
+/// Minimum-necessary view returned to field (EMS) queries
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FieldEmergencyView {
+    pub blood_type: Symbol,
+    pub dnr_status: bool,
+    pub critical_allergies: Vec<String>,
+    pub profile_version: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccessReviewStatus {
+    Pending,
+    Reviewed,
+    Disputed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputedAccess {
+    pub patient_id: Address,
+    pub log_index: u32,
+    pub provider_id: Address,
+    pub access_time: u64,
+    pub note: Option<String>,
+    pub disputed_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyProvider {
+    pub provider_id: Address,
+    pub provider_type: Symbol, // EMS, ED
+    pub organization: String,
+    pub credential_ref: String,
+    pub registered_at: u64,
+}
+
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DNROrder {
@@ -55,6 +129,127 @@ pub struct DNROrder {
     pub dnr_document_hash: BytesN<32>,
     pub effective_date: u64,
     pub recorded_at: u64,
+    pub state: DNRState,
+    pub confirmed_by: Option<Address>,     // patient or healthcare agent
+    pub countersigned_by: Option<Address>, // second physician
+    pub rescinded_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DNRState {
+    Pending,   // awaiting patient/agent confirmation and physician countersignature
+    Effective,
+    Rescinded,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DNRHistoryEntry {
+    pub action: Symbol, // recorded, confirmed, countersigned, rescinded
+    pub actor: Address,
+    pub state: DNRState,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReconciliationReport {
+    pub source_allergies: Vec<String>,
+    pub missing_from_profile: Vec<String>, // active in the source, absent from critical_allergies
+    pub not_in_source: Vec<String>,        // listed on the profile, not active in the source
+    pub clinical_synced: bool, // false (and the lists below empty) without a clinical source
+    pub medications: ListReconciliation,
+    pub conditions: ListReconciliation,
+    pub generated_at: u64,
+}
+
+/// Divergence between one profile list and the active entries in its source
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListReconciliation {
+    pub source_items: Vec<String>,
+    pub missing_from_profile: Vec<String>,
+    pub not_in_source: Vec<String>,
+}
+
+/// Minimal emergency payload encoded on wallet cards / QR codes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyCardPayload {
+    pub patient_id: Address,
+    pub blood_type: Symbol,
+    pub top_allergies: Vec<String>,
+    pub dnr: bool,
+    pub emergency_contact_count: u32,
+    pub card_version: u32,
+    pub issued_at: u64,
+}
+
+/// On-chain commitment to the latest issued emergency card
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EmergencyCardCommitment {
+    pub card_version: u32,
+    pub content_hash: BytesN<32>, // sha256 of the XDR-encoded payload
+    pub profile_version: u32,
+    pub issued_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProfileProxy {
+    pub proxy: Address,
+    pub proxy_type: Symbol, // guardian, hc_agent
+    pub documentation_hash: BytesN<32>, // guardianship order or healthcare power of attorney
+    pub registered_by: Address,
+    pub registered_at: u64,
+    pub revoked_by: Option<Address>,
+    pub revoked_at: Option<u64>,
+}
+
+/// --------------------
+/// Events
+/// --------------------
+
+#[contractevent]
+pub struct EmergencyAccessed {
+    #[topic]
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub log_index: u32,
+    pub granted: bool,
+}
+
+#[contractevent]
+pub struct EmergencyCardIssued {
+    #[topic]
+    pub patient_id: Address,
+    pub card_version: u32,
+    pub content_hash: BytesN<32>,
+}
+
+#[contractevent]
+pub struct ProfileProxyRegistered {
+    #[topic]
+    pub patient_id: Address,
+    pub proxy: Address,
+    pub proxy_type: Symbol,
+}
+
+#[contractevent]
+pub struct ProfileProxyRevoked {
+    #[topic]
+    pub patient_id: Address,
+    pub proxy: Address,
+    pub revoked_by: Address,
+}
+
+#[contractevent]
+pub struct EmergencyAccessDisputed {
+    #[topic]
+    pub patient_id: Address,
+    pub log_index: u32,
 }
 
 /// --------------------
@@ -63,24 +258,102 @@ pub struct DNROrder {
 
 #[contracttype]
 pub enum DataKey {
+    Admin,
+    EmergencyProvider(Address),
     EmergencyProfile(Address),
     CriticalAlerts(Address),
     EmergencyAccessLog(Address),
     DNROrder(Address),
+    PendingDNROrder(Address),
+    DNRHistory(Address),
+    AllergySource,
+    ClinicalSource,
+    PendingReconciliation(Address),
     EmergencyNotifications(Address),
+    ProfileVersions(Address),
+    ProfileChangeLog(Address),
+    DisputedAccesses,
+    EmergencyCard(Address),
+    ProfileProxies(Address),
 }
 
+/// Number of prior profile versions retained per patient (oldest evicted first)
+const MAX_PROFILE_VERSIONS: u32 = 10;
+
+/// Number of allergies carried on an emergency card
+const CARD_TOP_ALLERGIES: u32 = 3;
+
+/// Role held by registered emergency providers; grants break-glass access
+const EMERGENCY_PROVIDER_ROLE: Symbol = symbol_short!("emergency");
+
+/// Provider type allowed hospital-level disclosure
+const HOSPITAL_PROVIDER_TYPE: Symbol = symbol_short!("ED");
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct EmergencyMedicalInfo;
 
 #[contractimpl]
 impl EmergencyMedicalInfo {
-    /// Set or update emergency profile for a patient
+    /// Initialize the contract with an admin who maintains the emergency provider registry
+    pub fn initialize(env: Env, admin: Address) {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Contract already initialized");
+        }
+
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+    }
+
+    /// Register a credentialed emergency provider (EMS agency, ED provider)
+    pub fn register_emergency_provider(
+        env: Env,
+        admin: Address,
+        provider_id: Address,
+        provider_type: Symbol,
+        organization: String,
+        credential_ref: String,
+    ) {
+        Self::require_admin(&env, &admin);
+
+        let provider = EmergencyProvider {
+            provider_id: provider_id.clone(),
+            provider_type,
+            organization,
+            credential_ref,
+            registered_at: env.ledger().timestamp(),
+        };
+
+        let key = DataKey::EmergencyProvider(provider_id.clone());
+        env.storage().persistent().set(&key, &provider);
+        shared_access::grant_role(&env, &EMERGENCY_PROVIDER_ROLE, &provider_id, &admin, None);
+    }
+
+    /// Remove a provider from the emergency provider registry
+    pub fn remove_emergency_provider(env: Env, admin: Address, provider_id: Address) {
+        Self::require_admin(&env, &admin);
+
+        let key = DataKey::EmergencyProvider(provider_id.clone());
+        env.storage().persistent().remove(&key);
+        shared_access::revoke_role(&env, &EMERGENCY_PROVIDER_ROLE, &provider_id);
+    }
+
+    /// Get a registered emergency provider
+    pub fn get_emergency_provider(env: Env, provider_id: Address) -> Option<EmergencyProvider> {
+        let key = DataKey::EmergencyProvider(provider_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Set or update emergency profile for a patient (patient or active profile proxy)
     /// Sub-second access optimized with persistent storage
-    #[allow(clippy::too_many_arguments)]
     pub fn set_emergency_profile(
         env: Env,
         patient_id: Address,
+        editor: Address,
         blood_type: Symbol,
         allergies_summary: String,
         critical_conditions: Vec<String>,
@@ -88,7 +361,7 @@ impl EmergencyMedicalInfo {
         emergency_contacts: Vec<EmergencyContact>,
         advance_directives_hash: Option<BytesN<32>>,
     ) {
-        patient_id.require_auth();
+        Self::require_patient_or_proxy(&env, &patient_id, &editor);
 
         let profile = EmergencyProfile {
             blood_type,
@@ -99,24 +372,186 @@ impl EmergencyMedicalInfo {
             },
             active_conditions: critical_conditions,
             current_medications,
-            dnr_status: false,
+            dnr_status: Self::dnr_in_effect(&env, &patient_id),
             emergency_contacts,
+            version: 0,
+            last_updated: 0,
         };
 
-        let key = DataKey::EmergencyProfile(patient_id.clone());
-        env.storage().persistent().set(&key, &profile);
+        Self::commit_profile(&env, &patient_id, &editor, profile, Symbol::new(&env, "set"), None);
 
-        // Store advance directives if provided
+        // Store advance directives if provided; the submitter's own entry
+        // counts as confirmation but still needs a physician countersignature
         if let Some(hash) = advance_directives_hash {
-            let dnr_key = DataKey::DNROrder(patient_id.clone());
             let dnr = DNROrder {
-                provider_id: patient_id.clone(),
+                provider_id: editor.clone(),
                 dnr_document_hash: hash,
                 effective_date: env.ledger().timestamp(),
                 recorded_at: env.ledger().timestamp(),
+                state: DNRState::Pending,
+                confirmed_by: Some(editor.clone()),
+                countersigned_by: None,
+                rescinded_at: None,
             };
-            env.storage().persistent().set(&dnr_key, &dnr);
+            Self::save_dnr(&env, &patient_id, &editor, dnr, Symbol::new(&env, "recorded"));
+        }
+    }
+
+    /// Add a medication to the emergency profile without resubmitting it
+    pub fn add_medication(env: Env, patient_id: Address, editor: Address, medication: String) {
+        Self::require_patient_or_proxy(&env, &patient_id, &editor);
+
+        let mut profile = Self::load_profile(&env, &patient_id);
+        profile.current_medications.push_back(medication.clone());
+
+        Self::commit_profile(&env, &patient_id, &editor, profile, Symbol::new(&env, "add_med"), Some(medication));
+    }
+
+    /// Remove an active condition from the emergency profile
+    pub fn remove_condition(env: Env, patient_id: Address, editor: Address, condition: String) {
+        Self::require_patient_or_proxy(&env, &patient_id, &editor);
+
+        let mut profile = Self::load_profile(&env, &patient_id);
+        let index = profile
+            .active_conditions
+            .first_index_of(&condition)
+            .expect("Condition not found");
+        profile.active_conditions.remove(index);
+
+        Self::commit_profile(&env, &patient_id, &editor, profile, Symbol::new(&env, "rm_cond"), Some(condition));
+    }
+
+    /// Replace the emergency contact with the same priority, or add it if none exists
+    pub fn update_contact(env: Env, patient_id: Address, editor: Address, contact: EmergencyContact) {
+        Self::require_patient_or_proxy(&env, &patient_id, &editor);
+
+        let mut profile = Self::load_profile(&env, &patient_id);
+        let existing = profile
+            .emergency_contacts
+            .iter()
+            .position(|c| c.priority == contact.priority);
+        match existing {
+            Some(i) => profile.emergency_contacts.set(i as u32, contact.clone()),
+            None => profile.emergency_contacts.push_back(contact.clone()),
+        }
+
+        Self::commit_profile(&env, &patient_id, &editor, profile, Symbol::new(&env, "upd_cont"), Some(contact.name));
+    }
+
+    /// Register a guardian or healthcare agent who may maintain the emergency
+    /// profile. The patient registers their own proxy; for minors and
+    /// incapacitated patients the admin registers one against the documentation.
+    pub fn register_profile_proxy(
+        env: Env,
+        patient_id: Address,
+        registrar: Address,
+        proxy: Address,
+        proxy_type: Symbol,
+        documentation_hash: BytesN<32>,
+    ) {
+        Self::require_patient_or_admin(&env, &patient_id, &registrar);
+
+        if proxy_type != symbol_short!("guardian") && proxy_type != symbol_short!("hc_agent") {
+            panic!("Invalid proxy type");
+        }
+        if proxy == patient_id || Self::is_active_proxy(&env, &patient_id, &proxy) {
+            panic!("Proxy already authorized");
+        }
+
+        let key = DataKey::ProfileProxies(patient_id.clone());
+        let mut proxies: Vec<ProfileProxy> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        proxies.push_back(ProfileProxy {
+            proxy: proxy.clone(),
+            proxy_type: proxy_type.clone(),
+            documentation_hash,
+            registered_by: registrar,
+            registered_at: env.ledger().timestamp(),
+            revoked_by: None,
+            revoked_at: None,
+        });
+        env.storage().persistent().set(&key, &proxies);
+
+        ProfileProxyRegistered {
+            patient_id,
+            proxy,
+            proxy_type,
         }
+        .publish(&env);
+    }
+
+    /// Revoke a profile proxy (patient or admin). The record is kept for attribution.
+    pub fn revoke_profile_proxy(env: Env, patient_id: Address, revoker: Address, proxy: Address) {
+        Self::require_patient_or_admin(&env, &patient_id, &revoker);
+
+        let key = DataKey::ProfileProxies(patient_id.clone());
+        let mut proxies: Vec<ProfileProxy> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let index = proxies
+            .iter()
+            .position(|p| p.proxy == proxy && p.revoked_at.is_none())
+            .expect("Proxy not found") as u32;
+
+        let mut record = proxies.get(index).unwrap();
+        record.revoked_by = Some(revoker.clone());
+        record.revoked_at = Some(env.ledger().timestamp());
+        proxies.set(index, record);
+        env.storage().persistent().set(&key, &proxies);
+
+        ProfileProxyRevoked {
+            patient_id,
+            proxy,
+            revoked_by: revoker,
+        }
+        .publish(&env);
+    }
+
+    /// Get every proxy registered for a patient, including revoked ones
+    /// (patient or active profile proxy)
+    pub fn get_profile_proxies(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+    ) -> Vec<ProfileProxy> {
+        Self::require_patient_or_proxy(&env, &patient_id, &requester);
+
+        let key = DataKey::ProfileProxies(patient_id);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get one page of prior versions of the emergency profile (oldest first)
+    pub fn get_profile_versions(env: Env, patient_id: Address, offset: u32, limit: u32) -> Vec<EmergencyProfile> {
+        patient_id.require_auth();
+
+        let key = DataKey::ProfileVersions(patient_id);
+        let items = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
+    }
+
+    /// Get one page of the change log for the emergency profile
+    pub fn get_profile_change_log(env: Env, patient_id: Address, offset: u32, limit: u32) -> Vec<ProfileChange> {
+        patient_id.require_auth();
+
+        let key = DataKey::ProfileChangeLog(patient_id);
+        let items = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
     /// Add critical alert to patient profile
@@ -149,8 +584,10 @@ impl EmergencyMedicalInfo {
         env.storage().persistent().set(&key, &alerts);
     }
 
-    /// Emergency access with break-glass protocol
-    /// Provides immediate access with full audit logging
+    /// Emergency access with break-glass protocol at hospital disclosure level
+    /// Registered emergency department providers get immediate access to the
+    /// full profile; every attempt, granted or not, is kept in the audit log.
+    /// Returns None when denied.
     pub fn emergency_access_request(
         env: Env,
         provider_id: Address,
@@ -158,34 +595,303 @@ impl EmergencyMedicalInfo {
         emergency_type: Symbol,
         justification: String,
         location: String,
-    ) -> EmergencyProfile {
+    ) -> Option<EmergencyProfile> {
         provider_id.require_auth();
 
-        // Log the emergency access (break-glass audit)
-        let access_log = EmergencyAccessLog {
-            provider_id: provider_id.clone(),
-            emergency_type: emergency_type.clone(),
+        // Retrieve emergency profile with the currently valid directive state
+        let mut profile = Self::load_profile(&env, &patient_id);
+        profile.dnr_status = Self::dnr_in_effect(&env, &patient_id);
+
+        let granted = Self::log_emergency_access(
+            &env,
+            &provider_id,
+            &patient_id,
+            emergency_type,
             justification,
             location,
-            access_time: env.ledger().timestamp(),
+            DisclosureLevel::Hospital,
+        );
+
+        if granted {
+            Some(profile)
+        } else {
+            None
+        }
+    }
+
+    /// Field-level break-glass query for EMS: returns only blood type, DNR
+    /// status and critical allergies. Logged like any other emergency access.
+    pub fn field_emergency_query(
+        env: Env,
+        provider_id: Address,
+        patient_id: Address,
+        emergency_type: Symbol,
+        justification: String,
+        location: String,
+    ) -> Option<FieldEmergencyView> {
+        provider_id.require_auth();
+
+        let profile = Self::load_profile(&env, &patient_id);
+        let view = FieldEmergencyView {
+            blood_type: profile.blood_type,
+            dnr_status: Self::dnr_in_effect(&env, &patient_id),
+            critical_allergies: profile.critical_allergies,
+            profile_version: profile.version,
         };
 
+        let granted = Self::log_emergency_access(
+            &env,
+            &provider_id,
+            &patient_id,
+            emergency_type,
+            justification,
+            location,
+            DisclosureLevel::Field,
+        );
+
+        if granted {
+            Some(view)
+        } else {
+            None
+        }
+    }
+
+    /// Mark an emergency access log entry as reviewed or disputed by the
+    /// patient or an active profile proxy
+    pub fn review_emergency_access(
+        env: Env,
+        patient_id: Address,
+        reviewer: Address,
+        log_index: u32,
+        status: AccessReviewStatus,
+        note: Option<String>,
+    ) {
+        Self::require_patient_or_proxy(&env, &patient_id, &reviewer);
+
+        if status == AccessReviewStatus::Pending {
+            panic!("Invalid review status");
+        }
+
         let log_key = DataKey::EmergencyAccessLog(patient_id.clone());
         let mut logs: Vec<EmergencyAccessLog> = env
             .storage()
             .persistent()
             .get(&log_key)
             .unwrap_or(Vec::new(&env));
+        let mut entry = logs.get(log_index).expect("Access log entry not found");
 
-        logs.push_back(access_log);
+        let newly_disputed =
+            status == AccessReviewStatus::Disputed && entry.review_status != AccessReviewStatus::Disputed;
+
+        entry.review_status = status;
+        entry.review_note = note.clone();
+        entry.reviewed_at = Some(env.ledger().timestamp());
+        logs.set(log_index, entry.clone());
         env.storage().persistent().set(&log_key, &logs);
 
-        // Retrieve emergency profile
-        let profile_key = DataKey::EmergencyProfile(patient_id.clone());
-        env.storage()
+        // Disputes are queued for compliance follow-up
+        if newly_disputed {
+            let mut disputes: Vec<DisputedAccess> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::DisputedAccesses)
+                .unwrap_or(Vec::new(&env));
+            disputes.push_back(DisputedAccess {
+                patient_id: patient_id.clone(),
+                log_index,
+                provider_id: entry.provider_id,
+                access_time: entry.access_time,
+                note,
+                disputed_at: env.ledger().timestamp(),
+            });
+            env.storage()
+                .persistent()
+                .set(&DataKey::DisputedAccesses, &disputes);
+
+            EmergencyAccessDisputed {
+                patient_id,
+                log_index,
+            }
+            .publish(&env);
+        }
+    }
+
+    /// Get one page of disputed emergency accesses for compliance follow-up (admin only)
+    pub fn get_disputed_accesses(env: Env, admin: Address, offset: u32, limit: u32) -> Vec<DisputedAccess> {
+        Self::require_admin(&env, &admin);
+
+        let disputes = env
+            .storage()
             .persistent()
-            .get(&profile_key)
-            .expect("Emergency profile not found")
+            .get(&DataKey::DisputedAccesses)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&disputes, offset, limit)
+    }
+
+    /// Configure the allergy-management contract used as the allergy source of truth
+    pub fn set_allergy_source(env: Env, admin: Address, source: Address) {
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::AllergySource, &source);
+    }
+
+    /// Configure the contract used as the medication and condition source of truth
+    pub fn set_clinical_source(env: Env, admin: Address, source: Address) {
+        Self::require_admin(&env, &admin);
+
+        env.storage().instance().set(&DataKey::ClinicalSource, &source);
+    }
+
+    /// Compare the profile's critical allergies against the active allergies in
+    /// allergy-management and, when a clinical source is configured, its
+    /// medications and conditions against that source. Stores a
+    /// reconciliation report for the patient to confirm.
+    pub fn sync_allergies(env: Env, patient_id: Address) -> ReconciliationReport {
+        patient_id.require_auth();
+
+        let source: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AllergySource)
+            .expect("Allergy source not configured");
+        let profile = Self::load_profile(&env, &patient_id);
+
+        let records = AllergySourceClient::new(&env, &source).get_active_allergies(&patient_id, &patient_id);
+        let mut source_allergies = Vec::new(&env);
+        for record in records.iter() {
+            source_allergies.push_back(record.allergen);
+        }
+        let allergies = Self::reconcile_list(&env, source_allergies, &profile.critical_allergies);
+
+        let clinical_source: Option<Address> = env.storage().instance().get(&DataKey::ClinicalSource);
+        let (medications, conditions) = match &clinical_source {
+            Some(source) => {
+                let client = ClinicalSourceClient::new(&env, source);
+                let medications = client.get_active_medications(&patient_id, &patient_id);
+                let conditions = client.get_active_conditions(&patient_id, &patient_id);
+                (
+                    Self::reconcile_list(&env, medications, &profile.current_medications),
+                    Self::reconcile_list(&env, conditions, &profile.active_conditions),
+                )
+            }
+            None => {
+                let empty = Vec::new(&env);
+                (
+                    Self::reconcile_list(&env, empty.clone(), &empty),
+                    Self::reconcile_list(&env, empty.clone(), &empty),
+                )
+            }
+        };
+
+        let report = ReconciliationReport {
+            source_allergies: allergies.source_items,
+            missing_from_profile: allergies.missing_from_profile,
+            not_in_source: allergies.not_in_source,
+            clinical_synced: clinical_source.is_some(),
+            medications,
+            conditions,
+            generated_at: env.ledger().timestamp(),
+        };
+
+        let key = DataKey::PendingReconciliation(patient_id);
+        env.storage().persistent().set(&key, &report);
+
+        report
+    }
+
+    /// Get the pending allergy reconciliation report for a patient
+    pub fn get_pending_reconciliation(env: Env, patient_id: Address) -> Option<ReconciliationReport> {
+        let key = DataKey::PendingReconciliation(patient_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Accept the pending reconciliation, replacing critical allergies (and
+    /// medications and conditions, when reconciled) with the source lists
+    pub fn confirm_reconciliation(env: Env, patient_id: Address) {
+        patient_id.require_auth();
+
+        let key = DataKey::PendingReconciliation(patient_id.clone());
+        let report: ReconciliationReport = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("No pending reconciliation");
+        env.storage().persistent().remove(&key);
+
+        let mut profile = Self::load_profile(&env, &patient_id);
+        profile.critical_allergies = report.source_allergies;
+        if report.clinical_synced {
+            profile.current_medications = report.medications.source_items;
+            profile.active_conditions = report.conditions.source_items;
+        }
+        Self::commit_profile(&env, &patient_id, &patient_id, profile, Symbol::new(&env, "sync"), None);
+    }
+
+    /// Assemble a minimal emergency card payload and commit its hash on-chain so
+    /// off-chain wallet cards / QR codes can be verified against the chain
+    pub fn generate_emergency_card(env: Env, patient_id: Address) -> EmergencyCardPayload {
+        patient_id.require_auth();
+
+        let profile = Self::load_profile(&env, &patient_id);
+
+        let mut top_allergies = Vec::new(&env);
+        for allergy in profile.critical_allergies.iter().take(CARD_TOP_ALLERGIES as usize) {
+            top_allergies.push_back(allergy);
+        }
+
+        let key = DataKey::EmergencyCard(patient_id.clone());
+        let card_version = env
+            .storage()
+            .persistent()
+            .get::<_, EmergencyCardCommitment>(&key)
+            .map(|c| c.card_version + 1)
+            .unwrap_or(1);
+
+        let payload = EmergencyCardPayload {
+            patient_id: patient_id.clone(),
+            blood_type: profile.blood_type,
+            top_allergies,
+            dnr: Self::dnr_in_effect(&env, &patient_id),
+            emergency_contact_count: profile.emergency_contacts.len(),
+            card_version,
+            issued_at: env.ledger().timestamp(),
+        };
+
+        let content_hash: BytesN<32> = env.crypto().sha256(&payload.clone().to_xdr(&env)).into();
+        let commitment = EmergencyCardCommitment {
+            card_version,
+            content_hash: content_hash.clone(),
+            profile_version: profile.version,
+            issued_at: payload.issued_at,
+        };
+        env.storage().persistent().set(&key, &commitment);
+
+        EmergencyCardIssued {
+            patient_id,
+            card_version,
+            content_hash,
+        }
+        .publish(&env);
+
+        payload
+    }
+
+    /// Get the latest emergency card commitment for a patient
+    pub fn get_emergency_card(env: Env, patient_id: Address) -> Option<EmergencyCardCommitment> {
+        let key = DataKey::EmergencyCard(patient_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Check whether a card payload matches the latest commitment on-chain
+    pub fn verify_emergency_card(env: Env, payload: EmergencyCardPayload) -> bool {
+        let key = DataKey::EmergencyCard(payload.patient_id.clone());
+        match env.storage().persistent().get::<_, EmergencyCardCommitment>(&key) {
+            Some(commitment) => {
+                let hash: BytesN<32> = env.crypto().sha256(&payload.to_xdr(&env)).into();
+                commitment.content_hash == hash
+            }
+            None => false,
+        }
     }
 
     /// Notify emergency contacts
@@ -196,12 +902,7 @@ impl EmergencyMedicalInfo {
         notification_time: u64,
     ) -> Vec<EmergencyContact> {
         // Get emergency profile
-        let profile_key = DataKey::EmergencyProfile(patient_id.clone());
-        let profile: EmergencyProfile = env
-            .storage()
-            .persistent()
-            .get(&profile_key)
-            .expect("Emergency profile not found");
+        let profile = Self::load_profile(&env, &patient_id);
 
         // Log notification
         let notif_key = DataKey::EmergencyNotifications(patient_id.clone());
@@ -218,54 +919,113 @@ impl EmergencyMedicalInfo {
     }
 
     /// Record DNR (Do Not Resuscitate) order
+    /// The ordering provider and the patient or their agent both sign; the
+    /// authorizer's signature counts as confirmation. The order stays pending
+    /// until countersigned by a second physician, and an effective order it
+    /// replaces stays in force until then.
     pub fn record_dnr_order(
         env: Env,
         patient_id: Address,
         provider_id: Address,
+        authorizer: Address,
         dnr_document_hash: BytesN<32>,
         effective_date: u64,
     ) {
         provider_id.require_auth();
+        Self::require_patient_or_agent(&env, &patient_id, &authorizer);
 
         let dnr = DNROrder {
             provider_id: provider_id.clone(),
             dnr_document_hash,
             effective_date,
             recorded_at: env.ledger().timestamp(),
+            state: DNRState::Pending,
+            confirmed_by: Some(authorizer),
+            countersigned_by: None,
+            rescinded_at: None,
         };
 
-        let dnr_key = DataKey::DNROrder(patient_id.clone());
-        env.storage().persistent().set(&dnr_key, &dnr);
+        Self::save_dnr(&env, &patient_id, &provider_id, dnr, Symbol::new(&env, "recorded"));
+    }
 
-        // Update profile DNR status
-        let profile_key = DataKey::EmergencyProfile(patient_id.clone());
-        if let Some(mut profile) = env
+    /// Confirm a pending DNR order (patient or healthcare agent)
+    pub fn confirm_dnr_order(env: Env, patient_id: Address, confirmer: Address) {
+        Self::require_patient_or_agent(&env, &patient_id, &confirmer);
+
+        let mut dnr = Self::load_working_dnr(&env, &patient_id);
+        if dnr.state == DNRState::Rescinded {
+            panic!("DNR order has been rescinded");
+        }
+
+        dnr.confirmed_by = Some(confirmer.clone());
+        Self::save_dnr(&env, &patient_id, &confirmer, dnr, Symbol::new(&env, "confirmed"));
+    }
+
+    /// Countersign a DNR order by a registered provider other than the ordering provider
+    pub fn countersign_dnr_order(env: Env, patient_id: Address, physician_id: Address) {
+        physician_id.require_auth();
+
+        let mut dnr = Self::load_working_dnr(&env, &patient_id);
+        if dnr.state == DNRState::Rescinded {
+            panic!("DNR order has been rescinded");
+        }
+        if dnr.provider_id == physician_id {
+            panic!("Countersignature requires a second physician");
+        }
+        if !shared_access::has_role(&env, &EMERGENCY_PROVIDER_ROLE, &physician_id) {
+            panic!("Countersignature requires a registered provider");
+        }
+
+        dnr.countersigned_by = Some(physician_id.clone());
+        Self::save_dnr(&env, &patient_id, &physician_id, dnr, Symbol::new(&env, "countersig"));
+    }
+
+    /// Revoke a DNR order (patient or healthcare agent). Any replacement still
+    /// awaiting countersignature is discarded with it.
+    pub fn revoke_dnr_order(env: Env, patient_id: Address, revoker: Address) {
+        Self::require_patient_or_agent(&env, &patient_id, &revoker);
+
+        let mut dnr = Self::load_dnr(&env, &patient_id);
+        if dnr.state == DNRState::Rescinded {
+            panic!("DNR order has been rescinded");
+        }
+
+        dnr.state = DNRState::Rescinded;
+        dnr.rescinded_at = Some(env.ledger().timestamp());
+        Self::save_dnr(&env, &patient_id, &revoker, dnr, Symbol::new(&env, "rescinded"));
+    }
+
+    /// Get one page of the DNR order history for a patient
+    pub fn get_dnr_history(env: Env, patient_id: Address, offset: u32, limit: u32) -> Vec<DNRHistoryEntry> {
+        let key = DataKey::DNRHistory(patient_id);
+        let items = env
             .storage()
             .persistent()
-            .get::<_, EmergencyProfile>(&profile_key)
-        {
-            profile.dnr_status = true;
-            env.storage().persistent().set(&profile_key, &profile);
-        }
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
-    /// Get emergency information (fast read access)
+    /// Get the full emergency profile (patient or active profile proxy).
+    /// Providers read it through the logged break-glass entrypoints instead.
     pub fn get_emergency_info(
         env: Env,
         patient_id: Address,
         requester: Address,
     ) -> EmergencyProfile {
-        requester.require_auth();
+        Self::require_patient_or_proxy(&env, &patient_id, &requester);
 
-        let key = DataKey::EmergencyProfile(patient_id.clone());
-        env.storage()
-            .persistent()
-            .get(&key)
-            .expect("Emergency profile not found")
+        Self::load_profile(&env, &patient_id)
     }
 
-    /// Get critical alerts for a patient
-    pub fn get_critical_alerts(env: Env, patient_id: Address) -> Vec<CriticalAlert> {
+    /// Get critical alerts for a patient (patient or active profile proxy)
+    pub fn get_critical_alerts(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+    ) -> Vec<CriticalAlert> {
+        Self::require_patient_or_proxy(&env, &patient_id, &requester);
+
         let key = DataKey::CriticalAlerts(patient_id);
         env.storage()
             .persistent()
@@ -273,26 +1033,360 @@ impl EmergencyMedicalInfo {
             .unwrap_or(Vec::new(&env))
     }
 
-    /// Get emergency access logs (audit trail)
-    pub fn get_emergency_access_logs(env: Env, patient_id: Address) -> Vec<EmergencyAccessLog> {
+    /// Get one page of emergency access logs (audit trail; patient or active profile proxy)
+    pub fn get_emergency_access_logs(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<EmergencyAccessLog> {
+        Self::require_patient_or_proxy(&env, &patient_id, &requester);
+
         let key = DataKey::EmergencyAccessLog(patient_id);
-        env.storage()
+        let items = env
+            .storage()
             .persistent()
             .get(&key)
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
-    /// Get DNR order details
-    pub fn get_dnr_order(env: Env, patient_id: Address) -> Option<DNROrder> {
+    /// Get DNR order details (patient or active profile proxy). Providers see
+    /// DNR status through the logged break-glass entrypoints.
+    pub fn get_dnr_order(env: Env, patient_id: Address, requester: Address) -> Option<DNROrder> {
+        Self::require_patient_or_proxy(&env, &patient_id, &requester);
+
         let key = DataKey::DNROrder(patient_id);
         env.storage().persistent().get(&key)
     }
 
+    /// Get a replacement DNR order awaiting countersignature while the current one stays effective
+    pub fn get_pending_dnr_order(env: Env, patient_id: Address) -> Option<DNROrder> {
+        let key = DataKey::PendingDNROrder(patient_id);
+        env.storage().persistent().get(&key)
+    }
+
     /// Check if patient has emergency profile
     pub fn has_emergency_profile(env: Env, patient_id: Address) -> bool {
         let key = DataKey::EmergencyProfile(patient_id);
         env.storage().persistent().has(&key)
     }
+
+    /// Extend the TTL of a patient's profile and the records kept alongside it
+    /// (alerts, DNR order and history, proxies, prior versions, change and
+    /// access logs, card). Anyone may call this.
+    pub fn extend_profile_ttl(env: Env, patient_id: Address) {
+        let profile_key = DataKey::EmergencyProfile(patient_id.clone());
+        if !shared_ttl::bump_persistent(&env, &profile_key, shared_ttl::ARCHIVE) {
+            panic!("Emergency profile not found");
+        }
+        for key in [
+            DataKey::CriticalAlerts(patient_id.clone()),
+            DataKey::DNROrder(patient_id.clone()),
+            DataKey::PendingDNROrder(patient_id.clone()),
+            DataKey::DNRHistory(patient_id.clone()),
+            DataKey::ProfileProxies(patient_id.clone()),
+            DataKey::ProfileVersions(patient_id.clone()),
+            DataKey::ProfileChangeLog(patient_id.clone()),
+            DataKey::EmergencyAccessLog(patient_id.clone()),
+            DataKey::EmergencyNotifications(patient_id.clone()),
+            DataKey::EmergencyCard(patient_id),
+        ] {
+            shared_ttl::bump_persistent(&env, &key, shared_ttl::ARCHIVE);
+        }
+        shared_ttl::extend_instance(&env, shared_ttl::INSTANCE);
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> u32 {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::migrate(&env, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn load_dnr(env: &Env, patient_id: &Address) -> DNROrder {
+        let key = DataKey::DNROrder(patient_id.clone());
+        env.storage()
+            .persistent()
+            .get(&key)
+            .expect("DNR order not found")
+    }
+
+    /// The order confirmations and countersignatures apply to: a pending
+    /// replacement if there is one, otherwise the current order
+    fn load_working_dnr(env: &Env, patient_id: &Address) -> DNROrder {
+        let key = DataKey::PendingDNROrder(patient_id.clone());
+        match env.storage().persistent().get(&key) {
+            Some(dnr) => dnr,
+            None => Self::load_dnr(env, patient_id),
+        }
+    }
+
+    /// Whether an effective (confirmed, countersigned, not rescinded) DNR applies now
+    fn dnr_in_effect(env: &Env, patient_id: &Address) -> bool {
+        let key = DataKey::DNROrder(patient_id.clone());
+        match env.storage().persistent().get::<_, DNROrder>(&key) {
+            Some(dnr) => {
+                dnr.state == DNRState::Effective && dnr.effective_date <= env.ledger().timestamp()
+            }
+            None => false,
+        }
+    }
+
+    /// Store a DNR order, advancing its state, recording history and syncing the profile flag.
+    /// A pending order is held aside while an effective one is in force, and
+    /// replaces it once it becomes effective itself.
+    fn save_dnr(env: &Env, patient_id: &Address, actor: &Address, mut dnr: DNROrder, action: Symbol) {
+        if dnr.state == DNRState::Pending && dnr.confirmed_by.is_some() && dnr.countersigned_by.is_some() {
+            dnr.state = DNRState::Effective;
+        }
+
+        let key = DataKey::DNROrder(patient_id.clone());
+        let pending_key = DataKey::PendingDNROrder(patient_id.clone());
+        let current: Option<DNROrder> = env.storage().persistent().get(&key);
+        let current_effective = current.is_some_and(|c| c.state == DNRState::Effective);
+        if dnr.state == DNRState::Pending && current_effective {
+            env.storage().persistent().set(&pending_key, &dnr);
+        } else {
+            env.storage().persistent().set(&key, &dnr);
+            env.storage().persistent().remove(&pending_key);
+        }
+
+        let history_key = DataKey::DNRHistory(patient_id.clone());
+        let mut history: Vec<DNRHistoryEntry> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(DNRHistoryEntry {
+            action,
+            actor: actor.clone(),
+            state: dnr.state.clone(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+
+        // Keep the profile DNR flag in line with the directive state
+        let profile_key = DataKey::EmergencyProfile(patient_id.clone());
+        if let Some(mut profile) = env
+            .storage()
+            .persistent()
+            .get::<_, EmergencyProfile>(&profile_key)
+        {
+            let in_effect = match dnr.state {
+                DNRState::Effective => true,
+                DNRState::Pending => current_effective,
+                DNRState::Rescinded => false,
+            };
+            if profile.dnr_status != in_effect {
+                profile.dnr_status = in_effect;
+                Self::commit_profile(env, patient_id, actor, profile, Symbol::new(env, "dnr"), None);
+            }
+        }
+    }
+
+    /// Split a source list against the profile's copy into what each side is missing
+    fn reconcile_list(env: &Env, source_items: Vec<String>, profile_items: &Vec<String>) -> ListReconciliation {
+        let mut missing_from_profile = Vec::new(env);
+        for item in source_items.iter() {
+            if !profile_items.contains(&item) {
+                missing_from_profile.push_back(item);
+            }
+        }
+
+        let mut not_in_source = Vec::new(env);
+        for item in profile_items.iter() {
+            if !source_items.contains(&item) {
+                not_in_source.push_back(item);
+            }
+        }
+
+        ListReconciliation {
+            source_items,
+            missing_from_profile,
+            not_in_source,
+        }
+    }
+
+    /// Directives are confirmed or revoked by the patient or an active
+    /// `hc_agent` profile proxy
+    fn require_patient_or_agent(env: &Env, patient_id: &Address, actor: &Address) {
+        actor.require_auth();
+
+        if actor == patient_id {
+            return;
+        }
+        let is_agent = Self::active_proxy(env, patient_id, actor)
+            .is_some_and(|p| p.proxy_type == symbol_short!("hc_agent"));
+        if !is_agent {
+            panic!("Unauthorized: patient or healthcare agent only");
+        }
+    }
+
+    /// Record a break-glass attempt at the requested disclosure level and
+    /// return whether it was granted. Hospital-level disclosure is limited to
+    /// registered emergency department providers.
+    fn log_emergency_access(
+        env: &Env,
+        provider_id: &Address,
+        patient_id: &Address,
+        emergency_type: Symbol,
+        justification: String,
+        location: String,
+        disclosure_level: DisclosureLevel,
+    ) -> bool {
+        let mut granted = shared_access::has_role(env, &EMERGENCY_PROVIDER_ROLE, provider_id);
+        if granted && disclosure_level == DisclosureLevel::Hospital {
+            let provider: Option<EmergencyProvider> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::EmergencyProvider(provider_id.clone()));
+            granted = provider.is_some_and(|p| p.provider_type == HOSPITAL_PROVIDER_TYPE);
+        }
+
+        // Log the emergency access (break-glass audit)
+        let access_log = EmergencyAccessLog {
+            provider_id: provider_id.clone(),
+            emergency_type,
+            justification,
+            location,
+            access_time: env.ledger().timestamp(),
+            granted,
+            disclosure_level,
+            review_status: AccessReviewStatus::Pending,
+            review_note: None,
+            reviewed_at: None,
+        };
+
+        let log_key = DataKey::EmergencyAccessLog(patient_id.clone());
+        let mut logs: Vec<EmergencyAccessLog> = env
+            .storage()
+            .persistent()
+            .get(&log_key)
+            .unwrap_or(Vec::new(env));
+
+        logs.push_back(access_log);
+        env.storage().persistent().set(&log_key, &logs);
+
+        // Notify the patient so the access can be reviewed
+        EmergencyAccessed {
+            patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
+            log_index: logs.len() - 1,
+            granted,
+        }
+        .publish(env);
+
+        granted
+    }
+
+    fn require_patient_or_proxy(env: &Env, patient_id: &Address, actor: &Address) {
+        actor.require_auth();
+
+        if actor != patient_id && !Self::is_active_proxy(env, patient_id, actor) {
+            panic!("Unauthorized: patient or profile proxy only");
+        }
+    }
+
+    fn require_patient_or_admin(env: &Env, patient_id: &Address, actor: &Address) {
+        if actor == patient_id {
+            actor.require_auth();
+        } else {
+            Self::require_admin(env, actor);
+        }
+    }
+
+    fn is_active_proxy(env: &Env, patient_id: &Address, actor: &Address) -> bool {
+        Self::active_proxy(env, patient_id, actor).is_some()
+    }
+
+    fn active_proxy(env: &Env, patient_id: &Address, actor: &Address) -> Option<ProfileProxy> {
+        let key = DataKey::ProfileProxies(patient_id.clone());
+        let proxies: Vec<ProfileProxy> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        proxies.iter().find(|p| p.proxy == *actor && p.revoked_at.is_none())
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        match shared_access::require_admin(env, admin, &DataKey::Admin) {
+            Ok(()) => {}
+            Err(AccessError::NotInitialized) => panic!("Contract not initialized"),
+            Err(_) => panic!("Unauthorized: admin only"),
+        }
+    }
+
+    fn load_profile(env: &Env, patient_id: &Address) -> EmergencyProfile {
+        let key = DataKey::EmergencyProfile(patient_id.clone());
+        let profile = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("Emergency profile not found");
+        shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+        profile
+    }
+
+    /// Store a new profile version, archiving the previous one and logging the change
+    fn commit_profile(
+        env: &Env,
+        patient_id: &Address,
+        changed_by: &Address,
+        mut profile: EmergencyProfile,
+        change_type: Symbol,
+        detail: Option<String>,
+    ) {
+        let key = DataKey::EmergencyProfile(patient_id.clone());
+        let previous: Option<EmergencyProfile> = env.storage().persistent().get(&key);
+
+        profile.version = match &previous {
+            Some(prev) => {
+                let versions_key = DataKey::ProfileVersions(patient_id.clone());
+                let mut versions: Vec<EmergencyProfile> = env
+                    .storage()
+                    .persistent()
+                    .get(&versions_key)
+                    .unwrap_or(Vec::new(env));
+                if versions.len() >= MAX_PROFILE_VERSIONS {
+                    versions.pop_front();
+                }
+                versions.push_back(prev.clone());
+                env.storage().persistent().set(&versions_key, &versions);
+                prev.version + 1
+            }
+            None => 1,
+        };
+        profile.last_updated = env.ledger().timestamp();
+        env.storage().persistent().set(&key, &profile);
+        shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+
+        let log_key = DataKey::ProfileChangeLog(patient_id.clone());
+        let mut log: Vec<ProfileChange> = env
+            .storage()
+            .persistent()
+            .get(&log_key)
+            .unwrap_or(Vec::new(env));
+        log.push_back(ProfileChange {
+            version: profile.version,
+            changed_by: changed_by.clone(),
+            change_type,
+            detail,
+            timestamp: profile.last_updated,
+        });
+        env.storage().persistent().set(&log_key, &log);
+    }
 }
 
 #[cfg(test)]
@@ -1,7 +1,69 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Env};
+use crate::allergy_source::{AllergyRecord, AllergyStatus};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{storage::Persistent as _, Address as _},
+    Env,
+};
+
+/// Stand-in for allergy-management that reports a fixed set of active allergies
+#[contract]
+struct MockAllergySource;
+
+#[contractimpl]
+impl MockAllergySource {
+    pub fn get_active_allergies(env: Env, patient_id: Address, _requester: Address) -> Vec<AllergyRecord> {
+        let mut records = Vec::new(&env);
+        for allergen in ["Penicillin", "Peanuts"] {
+            records.push_back(AllergyRecord {
+                allergy_id: records.len() as u64,
+                patient_id: patient_id.clone(),
+                provider_id: patient_id.clone(),
+                allergen: String::from_str(&env, allergen),
+                allergen_type: symbol_short!("med"),
+                reaction_type: Vec::new(&env),
+                severity: symbol_short!("severe"),
+                onset_date: None,
+                recorded_date: 0,
+                verified: true,
+                status: AllergyStatus::Active,
+                resolution_date: None,
+                resolution_reason: None,
+                severity_history: Vec::new(&env),
+            });
+        }
+        records
+    }
+}
+
+/// Stand-in for the medication and problem-list source
+#[contract]
+struct MockClinicalSource;
+
+#[contractimpl]
+impl MockClinicalSource {
+    pub fn get_active_medications(env: Env, _patient_id: Address, _requester: Address) -> Vec<String> {
+        Vec::from_array(&env, [String::from_str(&env, "Metformin"), String::from_str(&env, "Lisinopril")])
+    }
+
+    pub fn get_active_conditions(env: Env, _patient_id: Address, _requester: Address) -> Vec<String> {
+        Vec::from_array(&env, [String::from_str(&env, "Type 2 diabetes")])
+    }
+}
+
+fn register_provider(env: &Env, client: &EmergencyMedicalInfoClient, provider: &Address) {
+    let admin = Address::generate(env);
+    client.initialize(&admin);
+    client.register_emergency_provider(
+        &admin,
+        provider,
+        &Symbol::new(env, "ED"),
+        &String::from_str(env, "General Hospital ED"),
+        &String::from_str(env, "NPI-1234567890"),
+    );
+}
 
 fn create_test_emergency_contacts(env: &Env) -> Vec<EmergencyContact> {
     let mut contacts = Vec::new(env);
@@ -26,7 +88,7 @@ fn create_test_emergency_contacts(env: &Env) -> Vec<EmergencyContact> {
 #[test]
 fn test_set_emergency_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -46,6 +108,7 @@ fn test_set_emergency_profile() {
     let contacts = create_test_emergency_contacts(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -63,13 +126,13 @@ fn test_set_emergency_profile() {
     assert_eq!(profile.active_conditions.len(), 2);
     assert_eq!(profile.current_medications.len(), 2);
     assert_eq!(profile.emergency_contacts.len(), 2);
-    assert_eq!(profile.dnr_status, false);
+    assert!(!profile.dnr_status);
 }
 
 #[test]
 fn test_emergency_access_request() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -84,6 +147,7 @@ fn test_emergency_access_request() {
     let contacts = create_test_emergency_contacts(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -98,6 +162,8 @@ fn test_emergency_access_request() {
     let justification = String::from_str(&env, "Patient unconscious, cardiac arrest");
     let location = String::from_str(&env, "ER Room 3");
 
+    register_provider(&env, &client, &provider);
+
     let profile = client.emergency_access_request(
         &provider,
         &patient,
@@ -106,11 +172,12 @@ fn test_emergency_access_request() {
         &location,
     );
 
+    let profile = profile.unwrap();
     assert_eq!(profile.blood_type, blood_type);
     assert_eq!(profile.emergency_contacts.len(), 2);
 
     // Verify access was logged
-    let logs = client.get_emergency_access_logs(&patient);
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
     assert_eq!(logs.len(), 1);
     assert_eq!(logs.get(0).unwrap().provider_id, provider);
     assert_eq!(logs.get(0).unwrap().emergency_type, emergency_type);
@@ -119,7 +186,7 @@ fn test_emergency_access_request() {
 #[test]
 fn test_add_critical_alert() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -132,7 +199,7 @@ fn test_add_critical_alert() {
 
     client.add_critical_alert(&patient, &provider, &alert_type, &alert_text, &severity);
 
-    let alerts = client.get_critical_alerts(&patient);
+    let alerts = client.get_critical_alerts(&patient, &patient);
     assert_eq!(alerts.len(), 1);
     assert_eq!(alerts.get(0).unwrap().alert_type, alert_type);
     assert_eq!(alerts.get(0).unwrap().severity, severity);
@@ -141,7 +208,7 @@ fn test_add_critical_alert() {
 #[test]
 fn test_multiple_critical_alerts() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -165,14 +232,14 @@ fn test_multiple_critical_alerts() {
         &Symbol::new(&env, "CRITICAL"),
     );
 
-    let alerts = client.get_critical_alerts(&patient);
+    let alerts = client.get_critical_alerts(&patient, &patient);
     assert_eq!(alerts.len(), 2);
 }
 
 #[test]
 fn test_notify_emergency_contacts() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -186,6 +253,7 @@ fn test_notify_emergency_contacts() {
     let contacts = create_test_emergency_contacts(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -209,7 +277,7 @@ fn test_notify_emergency_contacts() {
 #[test]
 fn test_record_dnr_order() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -224,6 +292,7 @@ fn test_record_dnr_order() {
     let contacts = Vec::new(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -237,22 +306,30 @@ fn test_record_dnr_order() {
     let dnr_hash = BytesN::from_array(&env, &[42u8; 32]);
     let effective_date = env.ledger().timestamp();
 
-    client.record_dnr_order(&patient, &provider, &dnr_hash, &effective_date);
+    client.record_dnr_order(&patient, &provider, &patient, &dnr_hash, &effective_date);
 
-    // Verify DNR was recorded
-    let dnr = client.get_dnr_order(&patient);
+    // Verify DNR was recorded but is not yet in effect
+    let dnr = client.get_dnr_order(&patient, &patient);
     assert!(dnr.is_some());
     assert_eq!(dnr.unwrap().dnr_document_hash, dnr_hash);
+    assert!(!client.get_emergency_info(&patient, &patient).dnr_status);
+
+    // Patient confirmation and physician countersignature make it effective
+    let attending = Address::generate(&env);
+    register_provider(&env, &client, &attending);
+    client.confirm_dnr_order(&patient, &patient);
+    client.countersign_dnr_order(&patient, &attending);
+    assert_eq!(client.get_dnr_order(&patient, &patient).unwrap().state, DNRState::Effective);
 
     // Verify profile DNR status updated
     let profile = client.get_emergency_info(&patient, &patient);
-    assert_eq!(profile.dnr_status, true);
+    assert!(profile.dnr_status);
 }
 
 #[test]
 fn test_dnr_with_advance_directives() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -266,6 +343,7 @@ fn test_dnr_with_advance_directives() {
     let advance_directives = Some(BytesN::from_array(&env, &[99u8; 32]));
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -276,7 +354,7 @@ fn test_dnr_with_advance_directives() {
     );
 
     // Verify DNR was created with advance directives
-    let dnr = client.get_dnr_order(&patient);
+    let dnr = client.get_dnr_order(&patient, &patient);
     assert!(dnr.is_some());
 }
 
@@ -284,7 +362,7 @@ fn test_dnr_with_advance_directives() {
 #[should_panic(expected = "Emergency profile not found")]
 fn test_emergency_access_without_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -304,7 +382,7 @@ fn test_emergency_access_without_profile() {
 #[test]
 fn test_emergency_access_audit_trail() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -320,6 +398,7 @@ fn test_emergency_access_audit_trail() {
     let contacts = Vec::new(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -347,16 +426,22 @@ fn test_emergency_access_audit_trail() {
     );
 
     // Verify audit trail
-    let logs = client.get_emergency_access_logs(&patient);
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
     assert_eq!(logs.len(), 2);
     assert_eq!(logs.get(0).unwrap().provider_id, provider1);
     assert_eq!(logs.get(1).unwrap().provider_id, provider2);
+
+    // Audit trail can be read a page at a time
+    let page = client.get_emergency_access_logs(&patient, &patient, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().provider_id, provider2);
+    assert!(client.get_emergency_access_logs(&patient, &patient, &2, &10).is_empty());
 }
 
 #[test]
 fn test_has_emergency_profile() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -373,6 +458,7 @@ fn test_has_emergency_profile() {
     let contacts = Vec::new(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -389,7 +475,7 @@ fn test_has_emergency_profile() {
 #[test]
 fn test_comprehensive_emergency_scenario() {
     let env = Env::default();
-    let contract_id = env.register_contract(None, EmergencyMedicalInfo);
+    let contract_id = env.register(EmergencyMedicalInfo, ());
     let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
 
     let patient = Address::generate(&env);
@@ -411,6 +497,7 @@ fn test_comprehensive_emergency_scenario() {
     let contacts = create_test_emergency_contacts(&env);
 
     client.set_emergency_profile(
+        &patient,
         &patient,
         &blood_type,
         &allergies,
@@ -430,13 +517,16 @@ fn test_comprehensive_emergency_scenario() {
     );
 
     // 3. Emergency access
-    let profile = client.emergency_access_request(
-        &provider,
-        &patient,
-        &Symbol::new(&env, "RESP_DIST"),
-        &String::from_str(&env, "Severe asthma attack"),
-        &String::from_str(&env, "ER"),
-    );
+    register_provider(&env, &client, &provider);
+    let profile = client
+        .emergency_access_request(
+            &provider,
+            &patient,
+            &Symbol::new(&env, "RESP_DIST"),
+            &String::from_str(&env, "Severe asthma attack"),
+            &String::from_str(&env, "ER"),
+        )
+        .unwrap();
 
     // 4. Notify contacts
     let notified = client.notify_emergency_contacts(
@@ -450,9 +540,829 @@ fn test_comprehensive_emergency_scenario() {
     assert_eq!(profile.active_conditions.len(), 2);
     assert_eq!(notified.len(), 2);
 
-    let alerts = client.get_critical_alerts(&patient);
+    let alerts = client.get_critical_alerts(&patient, &patient);
     assert_eq!(alerts.len(), 1);
 
-    let logs = client.get_emergency_access_logs(&patient);
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
     assert_eq!(logs.len(), 1);
 }
+
+#[test]
+fn test_profile_versioning_and_granular_updates() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    env.mock_all_auths();
+
+    let mut conditions = Vec::new(&env);
+    conditions.push_back(String::from_str(&env, "Asthma"));
+    conditions.push_back(String::from_str(&env, "Hypertension"));
+
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "A_POS"),
+        &String::from_str(&env, "Sulfa"),
+        &conditions,
+        &Vec::new(&env),
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+    assert_eq!(client.get_emergency_info(&patient, &patient).version, 1);
+
+    client.add_medication(&patient, &patient, &String::from_str(&env, "Albuterol inhaler"));
+    client.remove_condition(&patient, &patient, &String::from_str(&env, "Hypertension"));
+    client.update_contact(
+        &patient,
+        &patient,
+        &EmergencyContact {
+            name: String::from_str(&env, "Sam Doe"),
+            relationship: String::from_str(&env, "Sibling"),
+            contact_hash: BytesN::from_array(&env, &[3u8; 32]),
+            priority: 2,
+        },
+    );
+
+    let profile = client.get_emergency_info(&patient, &patient);
+    assert_eq!(profile.version, 4);
+    assert_eq!(profile.current_medications.len(), 1);
+    assert_eq!(profile.active_conditions.len(), 1);
+    assert_eq!(profile.emergency_contacts.len(), 2);
+    assert_eq!(
+        profile.emergency_contacts.get(1).unwrap().name,
+        String::from_str(&env, "Sam Doe")
+    );
+
+    let versions = client.get_profile_versions(&patient, &0, &10);
+    assert_eq!(versions.len(), 3);
+    assert_eq!(versions.get(0).unwrap().version, 1);
+    assert_eq!(versions.get(0).unwrap().current_medications.len(), 0);
+
+    let log = client.get_profile_change_log(&patient, &0, &10);
+    assert_eq!(log.len(), 4);
+    assert_eq!(log.get(1).unwrap().change_type, Symbol::new(&env, "add_med"));
+    assert_eq!(log.get(2).unwrap().changed_by, patient);
+}
+
+#[test]
+fn test_guardian_proxy_maintains_minor_profile() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let child = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Admin registers the guardian against the guardianship documentation
+    let documentation = BytesN::from_array(&env, &[9u8; 32]);
+    client.register_profile_proxy(&child, &admin, &guardian, &symbol_short!("guardian"), &documentation);
+
+    client.set_emergency_profile(
+        &child,
+        &guardian,
+        &Symbol::new(&env, "B_NEG"),
+        &String::from_str(&env, "Peanuts"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+    client.add_medication(&child, &guardian, &String::from_str(&env, "EpiPen Jr"));
+
+    let log = client.get_profile_change_log(&child, &0, &10);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(0).unwrap().changed_by, guardian);
+    assert_eq!(log.get(1).unwrap().changed_by, guardian);
+    assert_eq!(client.get_emergency_info(&child, &guardian).blood_type, Symbol::new(&env, "B_NEG"));
+
+    client.revoke_profile_proxy(&child, &admin, &guardian);
+    let proxies = client.get_profile_proxies(&child, &child);
+    assert_eq!(proxies.len(), 1);
+    assert_eq!(proxies.get(0).unwrap().registered_by, admin);
+    assert_eq!(proxies.get(0).unwrap().revoked_by, Some(admin.clone()));
+    assert_eq!(proxies.get(0).unwrap().documentation_hash, documentation);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or profile proxy only")]
+fn test_provider_cannot_read_full_profile_outside_break_glass() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    register_provider(&env, &client, &provider);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "Latex"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+
+    client.get_emergency_info(&patient, &provider);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or profile proxy only")]
+fn test_provider_cannot_read_access_logs() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    register_provider(&env, &client, &provider);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "Latex"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+
+    client.get_emergency_access_logs(&patient, &provider, &0, &10);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or healthcare agent only")]
+fn test_guardian_proxy_cannot_revoke_dnr() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let provider = Address::generate(&env);
+    register_provider(&env, &client, &provider);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    client.register_profile_proxy(&patient, &patient, &guardian, &symbol_short!("guardian"), &BytesN::from_array(&env, &[4u8; 32]));
+    client.record_dnr_order(&patient, &provider, &patient, &BytesN::from_array(&env, &[7u8; 32]), &0);
+
+    client.revoke_dnr_order(&patient, &guardian);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or profile proxy only")]
+fn test_revoked_proxy_cannot_update_profile() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let agent = Address::generate(&env);
+
+    client.register_profile_proxy(&patient, &patient, &agent, &symbol_short!("hc_agent"), &BytesN::from_array(&env, &[4u8; 32]));
+    client.set_emergency_profile(
+        &patient,
+        &agent,
+        &Symbol::new(&env, "O_NEG"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    client.revoke_profile_proxy(&patient, &patient, &agent);
+
+    client.add_medication(&patient, &agent, &String::from_str(&env, "Warfarin"));
+}
+
+#[test]
+fn test_field_and_hospital_disclosure_levels() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let ed_provider = Address::generate(&env);
+    let paramedic = Address::generate(&env);
+    client.initialize(&admin);
+    for (provider, provider_type) in [(&ed_provider, "ED"), (&paramedic, "EMS")] {
+        client.register_emergency_provider(
+            &admin,
+            provider,
+            &Symbol::new(&env, provider_type),
+            &String::from_str(&env, "Regional EMS"),
+            &String::from_str(&env, "REG-1"),
+        );
+    }
+
+    let mut medications = Vec::new(&env);
+    medications.push_back(String::from_str(&env, "Metformin"));
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "AB_POS"),
+        &String::from_str(&env, "Penicillin"),
+        &Vec::new(&env),
+        &medications,
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+
+    // EMS gets only the minimum necessary in the field
+    let view = client
+        .field_emergency_query(
+            &paramedic,
+            &patient,
+            &Symbol::new(&env, "CARDIAC"),
+            &String::from_str(&env, "Unresponsive"),
+            &String::from_str(&env, "Residence"),
+        )
+        .unwrap();
+    assert_eq!(view.blood_type, Symbol::new(&env, "AB_POS"));
+    assert!(!view.dnr_status);
+    assert_eq!(view.critical_allergies.len(), 1);
+
+    // Full profile is reserved for the hospital tier
+    let denied = client.emergency_access_request(
+        &paramedic,
+        &patient,
+        &Symbol::new(&env, "CARDIAC"),
+        &String::from_str(&env, "Unresponsive"),
+        &String::from_str(&env, "Residence"),
+    );
+    assert!(denied.is_none());
+    let profile = client
+        .emergency_access_request(
+            &ed_provider,
+            &patient,
+            &Symbol::new(&env, "CARDIAC"),
+            &String::from_str(&env, "Arrival"),
+            &String::from_str(&env, "ED Bay 3"),
+        )
+        .unwrap();
+    assert_eq!(profile.current_medications.len(), 1);
+
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
+    assert_eq!(logs.len(), 3);
+    assert_eq!(logs.get(0).unwrap().disclosure_level, DisclosureLevel::Field);
+    assert!(logs.get(0).unwrap().granted);
+    assert_eq!(logs.get(1).unwrap().disclosure_level, DisclosureLevel::Hospital);
+    assert!(!logs.get(1).unwrap().granted);
+    assert!(logs.get(2).unwrap().granted);
+
+    // Providers cannot bypass the tiers through the untiered profile read
+    assert!(client.try_get_emergency_info(&patient, &paramedic).is_err());
+    assert!(client.try_get_emergency_info(&patient, &ed_provider).is_err());
+}
+
+#[test]
+fn test_profile_version_ring_buffer_is_bounded() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    for _ in 0..12 {
+        client.add_medication(&patient, &patient, &String::from_str(&env, "Aspirin 81mg"));
+    }
+
+    let versions = client.get_profile_versions(&patient, &0, &10);
+    assert_eq!(versions.len(), 10);
+    assert_eq!(versions.get(0).unwrap().version, 3);
+    assert_eq!(client.get_emergency_info(&patient, &patient).version, 13);
+}
+
+#[test]
+fn test_unregistered_emergency_access_is_denied_and_logged() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let registered = Address::generate(&env);
+    let unregistered = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    register_provider(&env, &client, &registered);
+    assert!(client.get_emergency_provider(&registered).is_some());
+
+    let denied = client.emergency_access_request(
+        &unregistered,
+        &patient,
+        &Symbol::new(&env, "TRAUMA"),
+        &String::from_str(&env, "Claims to be EMS"),
+        &String::from_str(&env, "Roadside"),
+    );
+    assert!(denied.is_none());
+
+    let granted = client.emergency_access_request(
+        &registered,
+        &patient,
+        &Symbol::new(&env, "TRAUMA"),
+        &String::from_str(&env, "MVA victim"),
+        &String::from_str(&env, "Trauma Bay 1"),
+    );
+    assert!(granted.is_some());
+
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
+    assert_eq!(logs.len(), 2);
+    assert!(!logs.get(0).unwrap().granted);
+    assert!(logs.get(1).unwrap().granted);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: admin only")]
+fn test_register_emergency_provider_non_admin() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let impostor = Address::generate(&env);
+    client.initialize(&admin);
+
+    client.register_emergency_provider(
+        &impostor,
+        &impostor,
+        &Symbol::new(&env, "EMS"),
+        &String::from_str(&env, "Fake EMS"),
+        &String::from_str(&env, "N/A"),
+    );
+}
+
+#[test]
+fn test_patient_reviews_and_disputes_emergency_access() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.register_emergency_provider(
+        &admin,
+        &provider,
+        &Symbol::new(&env, "EMS"),
+        &String::from_str(&env, "County EMS"),
+        &String::from_str(&env, "EMS-42"),
+    );
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+
+    for _ in 0..2 {
+        client.field_emergency_query(
+            &provider,
+            &patient,
+            &Symbol::new(&env, "TRAUMA"),
+            &String::from_str(&env, "MVA"),
+            &String::from_str(&env, "Roadside"),
+        );
+    }
+
+    // The patient's healthcare agent can act on the access log too
+    let agent = Address::generate(&env);
+    client.register_profile_proxy(&patient, &patient, &agent, &symbol_short!("hc_agent"), &BytesN::from_array(&env, &[4u8; 32]));
+
+    client.review_emergency_access(&patient, &patient, &0, &AccessReviewStatus::Reviewed, &None);
+    client.review_emergency_access(
+        &patient,
+        &agent,
+        &1,
+        &AccessReviewStatus::Disputed,
+        &Some(String::from_str(&env, "I was not in an accident that day")),
+    );
+
+    let logs = client.get_emergency_access_logs(&patient, &patient, &0, &10);
+    assert_eq!(logs.get(0).unwrap().review_status, AccessReviewStatus::Reviewed);
+    assert_eq!(logs.get(1).unwrap().review_status, AccessReviewStatus::Disputed);
+
+    let disputes = client.get_disputed_accesses(&admin, &0, &10);
+    assert_eq!(disputes.len(), 1);
+    assert_eq!(disputes.get(0).unwrap().log_index, 1);
+    assert_eq!(disputes.get(0).unwrap().provider_id, provider);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or profile proxy only")]
+fn test_provider_cannot_review_emergency_access() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    register_provider(&env, &client, &provider);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    client.field_emergency_query(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "TRAUMA"),
+        &String::from_str(&env, "MVA"),
+        &String::from_str(&env, "Roadside"),
+    );
+
+    client.review_emergency_access(&patient, &provider, &0, &AccessReviewStatus::Reviewed, &None);
+}
+
+#[test]
+fn test_dnr_revocation_by_agent() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let agent = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let attending = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "O_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    for physician in [&provider, &attending] {
+        client.register_emergency_provider(
+            &admin,
+            physician,
+            &Symbol::new(&env, "ED"),
+            &String::from_str(&env, "General Hospital ED"),
+            &String::from_str(&env, "NPI-1234567890"),
+        );
+    }
+    client.register_profile_proxy(&patient, &patient, &agent, &symbol_short!("hc_agent"), &BytesN::from_array(&env, &[4u8; 32]));
+
+    client.record_dnr_order(&patient, &provider, &agent, &BytesN::from_array(&env, &[7u8; 32]), &0);
+    client.confirm_dnr_order(&patient, &agent);
+    client.countersign_dnr_order(&patient, &attending);
+
+    let profile = client
+        .emergency_access_request(
+            &provider,
+            &patient,
+            &Symbol::new(&env, "CARDIAC"),
+            &String::from_str(&env, "Arrest"),
+            &String::from_str(&env, "Home"),
+        )
+        .unwrap();
+    assert!(profile.dnr_status);
+
+    client.revoke_dnr_order(&patient, &agent);
+    let profile = client
+        .emergency_access_request(
+            &provider,
+            &patient,
+            &Symbol::new(&env, "CARDIAC"),
+            &String::from_str(&env, "Arrest"),
+            &String::from_str(&env, "Home"),
+        )
+        .unwrap();
+    assert!(!profile.dnr_status);
+
+    // The agent is an ordinary hc_agent profile proxy
+    let proxies = client.get_profile_proxies(&patient, &agent);
+    assert_eq!(proxies.len(), 1);
+    assert_eq!(proxies.get(0).unwrap().proxy_type, symbol_short!("hc_agent"));
+
+    let history = client.get_dnr_history(&patient, &0, &10);
+    assert_eq!(history.len(), 4);
+    assert_eq!(history.get(2).unwrap().state, DNRState::Effective);
+    assert_eq!(history.get(3).unwrap().state, DNRState::Rescinded);
+}
+
+#[test]
+fn test_replacement_dnr_keeps_effective_order_until_countersigned() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let attending = Address::generate(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    for physician in [&provider, &attending] {
+        client.register_emergency_provider(
+            &admin,
+            physician,
+            &Symbol::new(&env, "ED"),
+            &String::from_str(&env, "General Hospital ED"),
+            &String::from_str(&env, "NPI-1234567890"),
+        );
+    }
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "A_POS"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+
+    let original = BytesN::from_array(&env, &[1u8; 32]);
+    client.record_dnr_order(&patient, &provider, &patient, &original, &0);
+    client.countersign_dnr_order(&patient, &attending);
+    assert_eq!(client.get_dnr_order(&patient, &patient).unwrap().state, DNRState::Effective);
+
+    // The replacement waits aside; the original stays in force
+    let replacement = BytesN::from_array(&env, &[2u8; 32]);
+    client.record_dnr_order(&patient, &provider, &patient, &replacement, &0);
+    let current = client.get_dnr_order(&patient, &patient).unwrap();
+    assert_eq!(current.dnr_document_hash, original);
+    assert_eq!(current.state, DNRState::Effective);
+    assert_eq!(client.get_pending_dnr_order(&patient).unwrap().dnr_document_hash, replacement);
+    assert!(client.get_emergency_info(&patient, &patient).dnr_status);
+
+    client.countersign_dnr_order(&patient, &attending);
+    let current = client.get_dnr_order(&patient, &patient).unwrap();
+    assert_eq!(current.dnr_document_hash, replacement);
+    assert_eq!(current.state, DNRState::Effective);
+    assert!(client.get_pending_dnr_order(&patient).is_none());
+    assert!(client.get_emergency_info(&patient, &patient).dnr_status);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: patient or healthcare agent only")]
+fn test_record_dnr_order_requires_patient_or_agent() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+
+    let stranger = Address::generate(&env);
+
+    client.record_dnr_order(&patient, &provider, &stranger, &BytesN::from_array(&env, &[7u8; 32]), &0);
+}
+
+#[test]
+#[should_panic(expected = "Countersignature requires a registered provider")]
+fn test_dnr_countersign_by_unregistered_physician() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    client.initialize(&Address::generate(&env));
+
+    client.record_dnr_order(&patient, &provider, &patient, &BytesN::from_array(&env, &[7u8; 32]), &0);
+    client.countersign_dnr_order(&patient, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Countersignature requires a second physician")]
+fn test_dnr_countersign_by_ordering_provider() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.record_dnr_order(&patient, &provider, &patient, &BytesN::from_array(&env, &[7u8; 32]), &0);
+    client.countersign_dnr_order(&patient, &provider);
+}
+
+#[test]
+fn test_sync_allergies_reconciliation() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    let source_id = env.register(MockAllergySource, ());
+
+    let patient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.set_allergy_source(&admin, &source_id);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "A_NEG"),
+        &String::from_str(&env, "Latex"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &None,
+    );
+
+    let report = client.sync_allergies(&patient);
+    assert_eq!(report.source_allergies.len(), 2);
+    assert_eq!(report.missing_from_profile.len(), 2);
+    assert_eq!(report.not_in_source, Vec::from_array(&env, [String::from_str(&env, "Latex")]));
+    assert!(client.get_pending_reconciliation(&patient).is_some());
+
+    client.confirm_reconciliation(&patient);
+
+    let profile = client.get_emergency_info(&patient, &patient);
+    assert_eq!(profile.critical_allergies, report.source_allergies);
+    assert!(client.get_pending_reconciliation(&patient).is_none());
+    assert!(!report.clinical_synced);
+    assert!(report.medications.source_items.is_empty());
+}
+
+#[test]
+fn test_sync_medications_and_conditions_reconciliation() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    let allergy_source_id = env.register(MockAllergySource, ());
+    let clinical_source_id = env.register(MockClinicalSource, ());
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_allergy_source(&admin, &allergy_source_id);
+    client.set_clinical_source(&admin, &clinical_source_id);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "A_NEG"),
+        &String::from_str(&env, "Penicillin"),
+        &Vec::from_array(&env, [String::from_str(&env, "Asthma")]),
+        &Vec::from_array(&env, [String::from_str(&env, "Metformin"), String::from_str(&env, "Warfarin")]),
+        &Vec::new(&env),
+        &None,
+    );
+
+    let report = client.sync_allergies(&patient);
+    assert!(report.clinical_synced);
+    let medications = report.medications.clone();
+    assert_eq!(medications.missing_from_profile, Vec::from_array(&env, [String::from_str(&env, "Lisinopril")]));
+    assert_eq!(medications.not_in_source, Vec::from_array(&env, [String::from_str(&env, "Warfarin")]));
+    let conditions = report.conditions.clone();
+    assert_eq!(conditions.missing_from_profile, Vec::from_array(&env, [String::from_str(&env, "Type 2 diabetes")]));
+    assert_eq!(conditions.not_in_source, Vec::from_array(&env, [String::from_str(&env, "Asthma")]));
+
+    client.confirm_reconciliation(&patient);
+
+    let profile = client.get_emergency_info(&patient, &patient);
+    assert_eq!(profile.current_medications, medications.source_items);
+    assert_eq!(profile.active_conditions, conditions.source_items);
+}
+
+#[test]
+fn test_generate_and_verify_emergency_card() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "B_NEG"),
+        &String::from_str(&env, "Penicillin"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+
+    let card = client.generate_emergency_card(&patient);
+    assert_eq!(card.card_version, 1);
+    assert_eq!(card.blood_type, Symbol::new(&env, "B_NEG"));
+    assert_eq!(card.top_allergies.len(), 1);
+    assert_eq!(card.emergency_contact_count, 2);
+    assert!(!card.dnr);
+
+    let commitment = client.get_emergency_card(&patient).unwrap();
+    assert_eq!(commitment.profile_version, 1);
+    assert!(client.verify_emergency_card(&card));
+
+    // A tampered payload no longer matches the commitment
+    let mut forged = card.clone();
+    forged.blood_type = Symbol::new(&env, "O_POS");
+    assert!(!client.verify_emergency_card(&forged));
+
+    // Re-issuing supersedes the previous card
+    let reissued = client.generate_emergency_card(&patient);
+    assert_eq!(reissued.card_version, 2);
+    assert!(!client.verify_emergency_card(&card));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized: admin only")]
+fn test_migrate_non_admin() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    assert_eq!(client.migrate(&admin), SCHEMA_VERSION);
+
+    client.migrate(&Address::generate(&env));
+}
+
+#[test]
+fn test_extend_profile_ttl() {
+    let env = Env::default();
+    let contract_id = env.register(EmergencyMedicalInfo, ());
+    let client = EmergencyMedicalInfoClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let patient = Address::generate(&env);
+    client.set_emergency_profile(
+        &patient,
+        &patient,
+        &Symbol::new(&env, "A_NEG"),
+        &String::from_str(&env, "None"),
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &create_test_emergency_contacts(&env),
+        &None,
+    );
+
+    let key = DataKey::EmergencyProfile(patient.clone());
+    let ttl = || env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key));
+    assert_eq!(ttl(), shared_ttl::ACTIVE.extend_to);
+
+    client.extend_profile_ttl(&patient);
+    assert_eq!(ttl(), shared_ttl::ARCHIVE.extend_to);
+}
@@ -1,5 +1,5 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, Env, String, Vec};
+use soroban_sdk::{contract, contractimpl, contracttype, vec, Address, BytesN, Env, String, Vec};
 
 #[contracttype]
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
@@ -23,16 +23,30 @@ pub struct FinancialRecord {
 
 #[contracttype]
 pub enum DataKey {
+    Admin,
     Record(Address, u32),     // (Owner, Index) -> FinancialRecord
     RecordCount(Address),     // Owner -> Number of records
     Access(Address, Address), // (Owner, Authorized) -> bool
 }
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct FinancialRecordContract;
 
 #[contractimpl]
 impl FinancialRecordContract {
+    /// Sets the admin allowed to upgrade the contract. Can only be called once.
+    pub fn initialize(e: Env, admin: Address) {
+        if e.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+        admin.require_auth();
+        e.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&e, SCHEMA_VERSION);
+    }
+
     /// Adds a new financial record for the caller.
     pub fn add_financial_record(
         e: Env,
@@ -66,9 +80,15 @@ impl FinancialRecordContract {
             .set(&DataKey::RecordCount(owner.clone()), &(count + 1));
     }
 
-    /// Retrieves all financial records for an owner.
+    /// Retrieves one page of financial records for an owner, oldest first.
     /// Access is allowed if the caller is the owner or has been granted access.
-    pub fn get_financial_records(e: Env, caller: Address, owner: Address) -> Vec<FinancialRecord> {
+    pub fn get_financial_records(
+        e: Env,
+        caller: Address,
+        owner: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FinancialRecord> {
         Self::check_access(&e, &caller, &owner);
 
         let count: u32 = e
@@ -78,7 +98,7 @@ impl FinancialRecordContract {
             .unwrap_or(0);
         let mut records = vec![&e];
 
-        for i in 0..count {
+        for i in shared_pagination::page_range(count, offset, limit) {
             if let Some(record) = e
                 .storage()
                 .persistent()
@@ -167,6 +187,35 @@ impl FinancialRecordContract {
             .remove(&DataKey::Access(owner, authorized));
     }
 
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(e: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&e, &admin);
+        shared_upgrade::upgrade(&e, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(e: Env, admin: Address) -> u32 {
+        Self::require_admin(&e, &admin);
+        shared_upgrade::migrate(&e, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(e: Env) -> u32 {
+        shared_upgrade::schema_version(&e)
+    }
+
+    /// Internal helper to check the caller is the stored admin.
+    fn require_admin(e: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored: Address = e
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Not initialized"));
+        if stored != *admin {
+            panic!("Unauthorized admin");
+        }
+    }
+
     /// Internal helper to check access.
     fn check_access(e: &Env, caller: &Address, owner: &Address) {
         if caller == owner {
@@ -1,7 +1,7 @@
 #![cfg(test)]
 use super::*;
 use soroban_sdk::testutils::{Address as _, Ledger};
-use soroban_sdk::Env;
+use soroban_sdk::{BytesN, Env};
 
 #[test]
 fn test_add_and_get_records() {
@@ -17,7 +17,7 @@ fn test_add_and_get_records() {
 
     client.add_financial_record(&owner, &RecordType::TaxDocument, &ipfs_hash, &description);
 
-    let records = client.get_financial_records(&owner, &owner);
+    let records = client.get_financial_records(&owner, &owner, &0, &10);
     assert_eq!(records.len(), 1);
     let record = records.get(0).unwrap();
     assert_eq!(record.owner, owner);
@@ -26,6 +26,37 @@ fn test_add_and_get_records() {
     assert_eq!(record.description, description);
 }
 
+#[test]
+fn test_get_records_pages() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, ());
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let owner = Address::generate(&e);
+    let ipfs_hash = String::from_str(&e, "QmXoypizj2Madv6NthR75ce451F33968F9e1XF3D8xS288");
+    for description in ["Jan", "Feb", "Mar"] {
+        let description = String::from_str(&e, description);
+        client.add_financial_record(&owner, &RecordType::TaxDocument, &ipfs_hash, &description);
+    }
+
+    let page = client.get_financial_records(&owner, &owner, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(
+        page.get(0).unwrap().description,
+        String::from_str(&e, "Feb")
+    );
+
+    assert_eq!(
+        client.get_financial_records(&owner, &owner, &2, &10).len(),
+        1
+    );
+    assert!(client
+        .get_financial_records(&owner, &owner, &3, &10)
+        .is_empty());
+}
+
 #[test]
 fn test_access_granted() {
     let e = Env::default();
@@ -46,7 +77,7 @@ fn test_access_granted() {
     client.grant_access(&owner, &auditor);
 
     // Auditor can see now
-    let records = client.get_financial_records(&auditor, &owner);
+    let records = client.get_financial_records(&auditor, &owner, &0, &10);
     assert_eq!(records.len(), 1);
 }
 
@@ -70,7 +101,7 @@ fn test_unauthorized_access() {
     );
 
     // Stranger cannot see
-    client.get_financial_records(&stranger, &owner);
+    client.get_financial_records(&stranger, &owner, &0, &10);
 }
 
 #[test]
@@ -93,10 +124,10 @@ fn test_revoked_access() {
     );
 
     client.grant_access(&owner, &auditor);
-    client.get_financial_records(&auditor, &owner); // Should be fine
+    client.get_financial_records(&auditor, &owner, &0, &10); // Should be fine
 
     client.revoke_access(&owner, &auditor);
-    client.get_financial_records(&auditor, &owner); // Should panic
+    client.get_financial_records(&auditor, &owner, &0, &10); // Should panic
 }
 
 #[test]
@@ -143,3 +174,43 @@ fn test_filtering() {
     assert_eq!(range_records.len(), 1);
     assert_eq!(range_records.get(0).unwrap().timestamp, 200);
 }
+
+#[test]
+fn test_initialize_records_schema_version() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, ());
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    assert_eq!(client.schema_version(), 0);
+    client.initialize(&Address::generate(&e));
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, ());
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    let admin = Address::generate(&e);
+    client.initialize(&admin);
+    client.initialize(&admin);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized admin")]
+fn test_upgrade_by_stranger_fails() {
+    let e = Env::default();
+    e.mock_all_auths();
+
+    let contract_id = e.register(FinancialRecordContract, ());
+    let client = FinancialRecordContractClient::new(&e, &contract_id);
+
+    client.initialize(&Address::generate(&e));
+    client.upgrade(&Address::generate(&e), &BytesN::from_array(&e, &[0u8; 32]));
+}
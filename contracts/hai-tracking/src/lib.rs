@@ -1,10 +1,17 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, xdr::ToXdr,
+    Address, BytesN, Env, String, Symbol, Vec,
 };
 
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Bundle compliance below this (percent x100) is flagged as low.
+const LOW_BUNDLE_COMPLIANCE_X100: i64 = 9_500;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -16,6 +23,10 @@ pub enum Error {
     InvalidPrecautionType = 5,
     InvalidPriority = 6,
     DivisionByZero = 7,
+    DeviceNotAttributable = 8,
+    AlreadyInitialized = 9,
+    NotInitialized = 10,
+    NotAuthorized = 11,
 }
 
 #[contracttype]
@@ -37,6 +48,27 @@ pub struct Organism {
     pub is_multidrug_resistant: bool,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DeviceType {
+    CentralLine,
+    UrinaryCatheter,
+    Ventilator,
+}
+
+/// One device in place on a patient, from insertion to removal.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeviceRecord {
+    pub device_record_id: u64,
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub device_type: DeviceType,
+    pub inserted_at: u64,
+    pub removed_at: Option<u64>,
+    pub inserted_by: Address,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InfectionCase {
@@ -62,6 +94,68 @@ pub struct InfectionRate {
     pub denominator: u32,
     pub rate_per_1000_days_x100: i64,
     pub sir_x100: Option<i64>,
+    /// Prevention-bundle compliance for the infection's device on the unit
+    /// over the same period, when the query names a unit and audits exist.
+    pub bundle_compliance_x100: Option<i64>,
+    pub low_bundle_compliance: bool,
+}
+
+/// One daily audit of a device's prevention bundle. The audit is compliant
+/// only when every element was met.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundleAudit {
+    pub audit_id: u64,
+    pub device_record_id: u64,
+    pub facility_id: Address,
+    pub unit: String,
+    pub device_type: DeviceType,
+    pub audit_date: u64,
+    pub elements_met: Vec<Symbol>,
+    pub elements_missed: Vec<Symbol>,
+    pub compliant: bool,
+    pub auditor: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BundleCompliance {
+    pub device_type: DeviceType,
+    pub audits: u32,
+    pub compliant_audits: u32,
+    pub compliance_rate_x100: i64,
+}
+
+/// A surgical procedure performed at the facility, the denominator for SSI
+/// rates. `wound_class` runs from 1 (clean) to 4 (dirty).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurgicalProcedure {
+    pub procedure_id: u64,
+    pub facility_id: Address,
+    pub procedure_category: Symbol,
+    pub procedure_date: u64,
+    pub wound_class: u32,
+    pub ssi_infection_id: Option<u64>,
+}
+
+/// Procedures and linked SSI cases in one category and quarter; `quarter`
+/// is encoded as `year * 10 + quarter`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SsiQuarterCounts {
+    pub procedures: u32,
+    pub ssi_cases: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SsiRate {
+    pub procedure_category: Symbol,
+    pub quarter: u32,
+    pub procedures: u32,
+    pub ssi_cases: u32,
+    pub rate_per_100_x100: i64,
 }
 
 #[contracttype]
@@ -76,6 +170,116 @@ pub struct OutbreakCluster {
     pub active: bool,
 }
 
+/// Fields supplied when recording an environmental sample; give either
+/// `outbreak_id` or `unit`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvironmentalSampleInput {
+    pub facility_id: Address,
+    pub outbreak_id: Option<u64>,
+    pub unit: Option<String>,
+    pub sample_site: String,
+    pub collection_date: u64,
+    pub result_hash: BytesN<32>,
+    pub organism_found: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EnvironmentalSample {
+    pub sample_id: u64,
+    pub facility_id: Address,
+    pub unit: String,
+    pub outbreak_id: Option<u64>,
+    pub sample_site: String,
+    pub collection_date: u64,
+    pub result_hash: BytesN<32>,
+    pub organism_found: Option<String>,
+    pub collected_by: Address,
+}
+
+/// One row of an outbreak line list.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineListEntry {
+    pub infection_id: u64,
+    pub onset_date: u64,
+    pub unit: String,
+    pub organisms: Vec<String>,
+}
+
+/// Commitment to a generated line list. `line_list_hash` is the sha256 of
+/// the XDR encoding of `(outbreak_id, entries)`, with entries in case id
+/// order, so an exported spreadsheet can be checked against it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineListExport {
+    pub outbreak_id: u64,
+    pub case_count: u32,
+    pub line_list_hash: BytesN<32>,
+    pub generated_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineListGenerated {
+    #[topic]
+    pub outbreak_id: u64,
+    pub line_list_hash: BytesN<32>,
+    pub case_count: u32,
+    pub generated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutbreakSummary {
+    pub cluster: OutbreakCluster,
+    pub environmental_samples: u32,
+    pub environmental_positives: Vec<EnvironmentalSample>,
+}
+
+/// Occupational-health records below are keyed by staff member and kept
+/// apart from patient infection cases; they carry the outbreak id when one
+/// applies.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffExposure {
+    pub exposure_id: u64,
+    pub staff_id: Address,
+    pub facility_id: Address,
+    pub infection_id: u64,
+    pub outbreak_id: Option<u64>,
+    pub exposure_date: u64,
+    pub exposure_type: Symbol,
+    pub reported_by: Address,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffSymptomReport {
+    pub report_id: u64,
+    pub staff_id: Address,
+    pub facility_id: Address,
+    pub outbreak_id: Option<u64>,
+    pub symptom_onset: u64,
+    pub symptoms: Vec<Symbol>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WorkRestriction {
+    pub restriction_id: u64,
+    pub staff_id: Address,
+    pub facility_id: Address,
+    pub outbreak_id: Option<u64>,
+    pub reason: String,
+    pub start_date: u64,
+    pub ordered_by: Address,
+    pub cleared_date: Option<u64>,
+    pub cleared_by: Option<Address>,
+    pub active: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct IsolationPrecaution {
@@ -126,15 +330,38 @@ pub struct NhsnReport {
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
+    Admin,
     InfectionCase(u64),
     OutbreakCluster(u64),
     IsolationPrecaution(u64),
     HandHygieneRecord(u64),
     StewardshipRecord(u64),
     NhsnReport(u64),
+    EnvironmentalSample(u64),
+    OutbreakSamples(u64),
+    OutbreakCases(u64),
+    LineListExports(u64),
+    StaffExposure(u64),
+    StaffSymptomReport(u64),
+    WorkRestriction(u64),
+    StaffExposureIds(Address),
+    StaffSymptomReportIds(Address),
+    OutbreakStaffExposures(u64),
+    FacilityRestrictions(Address),
+    SurgicalProcedure(u64),
+    InfectionProcedure(u64),
+    SsiQuarterCounts(Address, Symbol, u32),
+    DeviceRecord(u64),
+    PatientDevices(Address),
+    InfectionDevice(u64),
+    BundleElements(Address, DeviceType),
+    BundleAudit(u64),
+    DeviceAuditDay(u64, u64),
+    UnitBundleAudits(Address, String),
     InfectionIds,
     OutbreakIds,
     PrecautionIds,
+    EnvironmentalSampleIds,
 }
 
 #[contract]
@@ -142,6 +369,16 @@ pub struct HAITrackingContract;
 
 #[contractimpl]
 impl HAITrackingContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn report_infection(
         env: Env,
         patient_id: Address,
@@ -149,8 +386,7 @@ impl HAITrackingContract {
         infection_type: Symbol,
         onset_date: u64,
         location: String,
-        device_associated: bool,
-        device_days: Option<u32>,
+        device_record_id: Option<u64>,
         reported_by: Address,
     ) -> Result<u64, Error> {
         reported_by.require_auth();
@@ -159,9 +395,31 @@ impl HAITrackingContract {
             return Err(Error::InvalidInfectionType);
         }
 
-        if device_associated && device_days.is_none() {
-            return Err(Error::InvalidData);
-        }
+        // CLABSI, CAUTI and VAP are attributed to the device that was in
+        // place at onset; other infection types take no device.
+        let device_days = match (Self::attributed_device_type(&env, &infection_type), device_record_id) {
+            (Some(device_type), Some(device_record_id)) => {
+                let device = Self::get_device_internal(&env, device_record_id)
+                    .map_err(|_| Error::DeviceNotAttributable)?;
+                let in_place_at_onset = device.inserted_at <= onset_date
+                    && device
+                        .removed_at
+                        .is_none_or(|removed| onset_date <= removed.saturating_add(SECONDS_PER_DAY));
+                if device.device_type != device_type
+                    || device.patient_id != patient_id
+                    || device.facility_id != facility_id
+                    || !in_place_at_onset
+                {
+                    return Err(Error::DeviceNotAttributable);
+                }
+                let end = device.removed_at.map_or(onset_date, |removed| removed.min(onset_date));
+                Some(Self::device_days_between(device.inserted_at, end))
+            }
+            (Some(_), None) => return Err(Error::DeviceNotAttributable),
+            (None, Some(_)) => return Err(Error::InvalidData),
+            (None, None) => None,
+        };
+        let device_associated = device_days.is_some();
 
         let infection_id = Self::next_id(&env, symbol_short!("inf_ctr"));
         let case = InfectionCase {
@@ -183,10 +441,94 @@ impl HAITrackingContract {
             .persistent()
             .set(&DataKey::InfectionCase(infection_id), &case);
         Self::push_id(&env, DataKey::InfectionIds, infection_id);
+        if let Some(device_record_id) = device_record_id {
+            env.storage()
+                .persistent()
+                .set(&DataKey::InfectionDevice(infection_id), &device_record_id);
+        }
 
         Ok(infection_id)
     }
 
+    pub fn record_device_insertion(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        device_type: DeviceType,
+        inserted_at: u64,
+        inserted_by: Address,
+    ) -> Result<u64, Error> {
+        inserted_by.require_auth();
+
+        let device_record_id = Self::next_id(&env, symbol_short!("dev_ctr"));
+        let device = DeviceRecord {
+            device_record_id,
+            patient_id: patient_id.clone(),
+            facility_id,
+            device_type,
+            inserted_at,
+            removed_at: None,
+            inserted_by,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::DeviceRecord(device_record_id), &device);
+        Self::push_id(&env, DataKey::PatientDevices(patient_id), device_record_id);
+
+        Ok(device_record_id)
+    }
+
+    pub fn record_device_removal(
+        env: Env,
+        device_record_id: u64,
+        removed_at: u64,
+        removed_by: Address,
+    ) -> Result<(), Error> {
+        removed_by.require_auth();
+
+        let mut device = Self::get_device_internal(&env, device_record_id)?;
+        if device.removed_at.is_some() || removed_at < device.inserted_at {
+            return Err(Error::InvalidData);
+        }
+
+        device.removed_at = Some(removed_at);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DeviceRecord(device_record_id), &device);
+
+        Ok(())
+    }
+
+    pub fn get_device_record(env: Env, device_record_id: u64) -> Result<DeviceRecord, Error> {
+        Self::get_device_internal(&env, device_record_id)
+    }
+
+    pub fn get_patient_devices(env: Env, patient_id: Address) -> Vec<DeviceRecord> {
+        let device_ids = Self::get_ids(&env, DataKey::PatientDevices(patient_id));
+        let mut out = Vec::new(&env);
+
+        let mut i = 0u32;
+        while i < device_ids.len() {
+            if let Some(device_record_id) = device_ids.get(i) {
+                if let Ok(device) = Self::get_device_internal(&env, device_record_id) {
+                    out.push_back(device);
+                }
+            }
+            i += 1;
+        }
+
+        out
+    }
+
+    /// Calendar days the device has been in place, counting the insertion
+    /// day, up to its removal or to now if it is still in.
+    pub fn get_device_days(env: Env, device_record_id: u64) -> Result<u32, Error> {
+        let device = Self::get_device_internal(&env, device_record_id)?;
+        let end = device.removed_at.unwrap_or(env.ledger().timestamp());
+        Ok(Self::device_days_between(device.inserted_at, end))
+    }
+
     pub fn record_organism(
         env: Env,
         infection_id: u64,
@@ -268,6 +610,121 @@ impl HAITrackingContract {
         Ok(())
     }
 
+    pub fn register_procedure(
+        env: Env,
+        facility_id: Address,
+        procedure_category: Symbol,
+        procedure_date: u64,
+        wound_class: u32,
+    ) -> Result<u64, Error> {
+        facility_id.require_auth();
+
+        if !(1..=4).contains(&wound_class) {
+            return Err(Error::InvalidData);
+        }
+
+        let procedure_id = Self::next_id(&env, symbol_short!("proc_ctr"));
+        let procedure = SurgicalProcedure {
+            procedure_id,
+            facility_id: facility_id.clone(),
+            procedure_category: procedure_category.clone(),
+            procedure_date,
+            wound_class,
+            ssi_infection_id: None,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::SurgicalProcedure(procedure_id), &procedure);
+
+        let key = DataKey::SsiQuarterCounts(facility_id, procedure_category, Self::quarter_of(procedure_date));
+        let mut counts: SsiQuarterCounts = env.storage().persistent().get(&key).unwrap_or_default();
+        counts.procedures += 1;
+        env.storage().persistent().set(&key, &counts);
+
+        Ok(procedure_id)
+    }
+
+    /// Attribute an SSI case to the procedure it followed. Each procedure
+    /// and each case can be linked once.
+    pub fn link_ssi_to_procedure(
+        env: Env,
+        infection_id: u64,
+        procedure_id: u64,
+    ) -> Result<(), Error> {
+        let case = Self::get_infection_case_internal(&env, infection_id)?;
+        if case.infection_type != Symbol::new(&env, "ssi") {
+            return Err(Error::InvalidInfectionType);
+        }
+
+        let mut procedure = Self::get_procedure_internal(&env, procedure_id)?;
+        if procedure.facility_id != case.facility_id
+            || procedure.ssi_infection_id.is_some()
+            || env
+                .storage()
+                .persistent()
+                .has(&DataKey::InfectionProcedure(infection_id))
+        {
+            return Err(Error::InvalidData);
+        }
+
+        procedure.ssi_infection_id = Some(infection_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::SurgicalProcedure(procedure_id), &procedure);
+        env.storage()
+            .persistent()
+            .set(&DataKey::InfectionProcedure(infection_id), &procedure_id);
+
+        let key = DataKey::SsiQuarterCounts(
+            procedure.facility_id,
+            procedure.procedure_category,
+            Self::quarter_of(procedure.procedure_date),
+        );
+        let mut counts: SsiQuarterCounts = env.storage().persistent().get(&key).unwrap_or_default();
+        counts.ssi_cases += 1;
+        env.storage().persistent().set(&key, &counts);
+
+        Ok(())
+    }
+
+    pub fn get_procedure(env: Env, procedure_id: u64) -> Result<SurgicalProcedure, Error> {
+        Self::get_procedure_internal(&env, procedure_id)
+    }
+
+    /// SSI rate per 100 procedures for the category in the quarter the
+    /// procedures were performed (`year * 10 + quarter`).
+    pub fn calculate_ssi_rate(
+        env: Env,
+        facility_id: Address,
+        procedure_category: Symbol,
+        quarter: u32,
+    ) -> Result<SsiRate, Error> {
+        if !(1..=4).contains(&(quarter % 10)) {
+            return Err(Error::InvalidData);
+        }
+
+        let counts: SsiQuarterCounts = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SsiQuarterCounts(facility_id, procedure_category.clone(), quarter))
+            .unwrap_or_default();
+        if counts.procedures == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        let rate_per_100_x100 =
+            (i64::from(counts.ssi_cases) * 100 * 100) / i64::from(counts.procedures);
+
+        Ok(SsiRate {
+            procedure_category,
+            quarter,
+            procedures: counts.procedures,
+            ssi_cases: counts.ssi_cases,
+            rate_per_100_x100,
+        })
+    }
+
     pub fn identify_outbreak_cluster(
         env: Env,
         infection_type: Symbol,
@@ -286,6 +743,7 @@ impl HAITrackingContract {
 
         let infection_ids = Self::get_ids(&env, DataKey::InfectionIds);
         let mut case_count = 0u32;
+        let mut case_ids = Vec::new(&env);
 
         let mut i = 0u32;
         while i < infection_ids.len() {
@@ -297,6 +755,7 @@ impl HAITrackingContract {
                         && case.onset_date >= window_start
                     {
                         case_count += 1;
+                        case_ids.push_back(case_id);
                         case.outbreak_related = true;
                         env.storage()
                             .persistent()
@@ -326,53 +785,460 @@ impl HAITrackingContract {
             .persistent()
             .set(&DataKey::OutbreakCluster(outbreak_id), &cluster);
         Self::push_id(&env, DataKey::OutbreakIds, outbreak_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OutbreakCases(outbreak_id), &case_ids);
+
+        // Positive environmental samples already taken on the unit during
+        // the window belong to the new cluster.
+        let sample_ids = Self::get_ids(&env, DataKey::EnvironmentalSampleIds);
+        let mut j = 0u32;
+        while j < sample_ids.len() {
+            if let Some(sample_id) = sample_ids.get(j) {
+                let sample: Option<EnvironmentalSample> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::EnvironmentalSample(sample_id));
+                if let Some(mut sample) = sample {
+                    if sample.outbreak_id.is_none()
+                        && sample.organism_found.is_some()
+                        && sample.facility_id == cluster.facility_id
+                        && sample.unit == cluster.unit
+                        && sample.collection_date >= window_start
+                    {
+                        sample.outbreak_id = Some(outbreak_id);
+                        env.storage()
+                            .persistent()
+                            .set(&DataKey::EnvironmentalSample(sample_id), &sample);
+                        Self::push_id(&env, DataKey::OutbreakSamples(outbreak_id), sample_id);
+                    }
+                }
+            }
+            j += 1;
+        }
 
         Ok(Some(outbreak_id))
     }
 
-    pub fn initiate_outbreak_investigation(
+    /// Record an environmental culture taken during surveillance or an
+    /// outbreak investigation. Give either the outbreak it was taken for or
+    /// the unit it was taken on; a positive unit sample is linked to the
+    /// unit's active outbreak, if there is one.
+    pub fn record_environmental_sample(
         env: Env,
-        outbreak_id: u64,
-        lead_investigator: Address,
-        investigation_protocol: String,
-    ) -> Result<(), Error> {
-        lead_investigator.require_auth();
-        let outbreak: OutbreakCluster = env
-            .storage()
-            .persistent()
-            .get(&DataKey::OutbreakCluster(outbreak_id))
-            .ok_or(Error::NotFound)?;
+        collected_by: Address,
+        input: EnvironmentalSampleInput,
+    ) -> Result<u64, Error> {
+        collected_by.require_auth();
 
-        env.events().publish(
-            (Symbol::new(&env, "outbreak_investigation"), outbreak_id),
-            (lead_investigator, investigation_protocol, outbreak.unit),
-        );
+        let EnvironmentalSampleInput {
+            facility_id,
+            outbreak_id,
+            unit,
+            sample_site,
+            collection_date,
+            result_hash,
+            organism_found,
+        } = input;
+        let (outbreak_id, unit) = match (outbreak_id, unit) {
+            (Some(outbreak_id), _) => {
+                let outbreak: OutbreakCluster = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::OutbreakCluster(outbreak_id))
+                    .ok_or(Error::NotFound)?;
+                if outbreak.facility_id != facility_id {
+                    return Err(Error::InvalidData);
+                }
+                (Some(outbreak_id), outbreak.unit)
+            }
+            (None, Some(unit)) => {
+                let linked = if organism_found.is_some() {
+                    Self::active_outbreak_for_unit(&env, &facility_id, &unit)
+                } else {
+                    None
+                };
+                (linked, unit)
+            }
+            (None, None) => return Err(Error::InvalidData),
+        };
 
-        Ok(())
-    }
+        let sample_id = Self::next_id(&env, symbol_short!("env_ctr"));
+        let sample = EnvironmentalSample {
+            sample_id,
+            facility_id,
+            unit,
+            outbreak_id,
+            sample_site,
+            collection_date,
+            result_hash,
+            organism_found,
+            collected_by,
+        };
 
-    pub fn track_isolation_precaution(
-        env: Env,
-        patient_id: Address,
-        precaution_type: Symbol,
-        start_date: u64,
-        indication: String,
-        discontinuation_criteria: String,
-    ) -> Result<u64, Error> {
-        if !Self::is_valid_precaution(&env, &precaution_type) {
-            return Err(Error::InvalidPrecautionType);
+        env.storage()
+            .persistent()
+            .set(&DataKey::EnvironmentalSample(sample_id), &sample);
+        Self::push_id(&env, DataKey::EnvironmentalSampleIds, sample_id);
+        if let Some(outbreak_id) = outbreak_id {
+            Self::push_id(&env, DataKey::OutbreakSamples(outbreak_id), sample_id);
         }
 
-        let precaution_id = Self::next_id(&env, symbol_short!("iso_ctr"));
-        let record = IsolationPrecaution {
-            precaution_id,
-            patient_id,
-            precaution_type,
-            start_date,
-            indication,
-            discontinuation_criteria,
-            active: true,
-        };
+        Ok(sample_id)
+    }
+
+    pub fn get_environmental_sample(env: Env, sample_id: u64) -> Result<EnvironmentalSample, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::EnvironmentalSample(sample_id))
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn get_outbreak_summary(env: Env, outbreak_id: u64) -> Result<OutbreakSummary, Error> {
+        let cluster: OutbreakCluster = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OutbreakCluster(outbreak_id))
+            .ok_or(Error::NotFound)?;
+
+        let sample_ids = Self::get_ids(&env, DataKey::OutbreakSamples(outbreak_id));
+        let mut environmental_positives = Vec::new(&env);
+        let mut i = 0u32;
+        while i < sample_ids.len() {
+            if let Some(sample_id) = sample_ids.get(i) {
+                let sample: Option<EnvironmentalSample> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::EnvironmentalSample(sample_id));
+                if let Some(sample) = sample {
+                    if sample.organism_found.is_some() {
+                        environmental_positives.push_back(sample);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        Ok(OutbreakSummary {
+            cluster,
+            environmental_samples: sample_ids.len(),
+            environmental_positives,
+        })
+    }
+
+    /// The outbreak's line list: every case in the cluster with its onset
+    /// date, unit and organisms, in case id order.
+    pub fn get_line_list(env: Env, outbreak_id: u64) -> Result<Vec<LineListEntry>, Error> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::OutbreakCluster(outbreak_id))
+        {
+            return Err(Error::NotFound);
+        }
+
+        let case_ids = Self::get_ids(&env, DataKey::OutbreakCases(outbreak_id));
+        let mut entries = Vec::new(&env);
+        let mut i = 0u32;
+        while i < case_ids.len() {
+            if let Some(case_id) = case_ids.get(i) {
+                let case = Self::get_infection_case_internal(&env, case_id)?;
+                let mut organisms = Vec::new(&env);
+                for organism in case.organisms.iter() {
+                    organisms.push_back(organism.name);
+                }
+                entries.push_back(LineListEntry {
+                    infection_id: case.infection_id,
+                    onset_date: case.onset_date,
+                    unit: case.location,
+                    organisms,
+                });
+            }
+            i += 1;
+        }
+
+        Ok(entries)
+    }
+
+    /// Assemble the outbreak's line list and store a hash of it with the
+    /// time it was generated. The list is derived entirely from recorded
+    /// cases, so anyone may commit it; each call adds a new export.
+    pub fn generate_line_list(env: Env, outbreak_id: u64) -> Result<LineListExport, Error> {
+        let entries = Self::get_line_list(env.clone(), outbreak_id)?;
+        let line_list_hash: BytesN<32> = env
+            .crypto()
+            .sha256(&(outbreak_id, entries.clone()).to_xdr(&env))
+            .into();
+
+        let export = LineListExport {
+            outbreak_id,
+            case_count: entries.len(),
+            line_list_hash: line_list_hash.clone(),
+            generated_at: env.ledger().timestamp(),
+        };
+        let key = DataKey::LineListExports(outbreak_id);
+        let mut exports: Vec<LineListExport> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        exports.push_back(export.clone());
+        env.storage().persistent().set(&key, &exports);
+
+        LineListGenerated {
+            outbreak_id,
+            line_list_hash,
+            case_count: export.case_count,
+            generated_at: export.generated_at,
+        }
+        .publish(&env);
+
+        Ok(export)
+    }
+
+    pub fn get_line_list_exports(env: Env, outbreak_id: u64) -> Vec<LineListExport> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LineListExports(outbreak_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn initiate_outbreak_investigation(
+        env: Env,
+        outbreak_id: u64,
+        lead_investigator: Address,
+        investigation_protocol: String,
+    ) -> Result<(), Error> {
+        lead_investigator.require_auth();
+        let outbreak: OutbreakCluster = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OutbreakCluster(outbreak_id))
+            .ok_or(Error::NotFound)?;
+
+        env.events().publish(
+            (Symbol::new(&env, "outbreak_investigation"), outbreak_id),
+            (lead_investigator, investigation_protocol, outbreak.unit),
+        );
+
+        Ok(())
+    }
+
+    /// Record a staff member's exposure to an infection case. The exposure
+    /// is linked to the active outbreak on the case's unit when the case is
+    /// part of one.
+    pub fn record_staff_exposure(
+        env: Env,
+        staff_id: Address,
+        infection_id: u64,
+        exposure_date: u64,
+        exposure_type: Symbol,
+        reported_by: Address,
+    ) -> Result<u64, Error> {
+        reported_by.require_auth();
+
+        let case = Self::get_infection_case_internal(&env, infection_id)?;
+        let outbreak_id = if case.outbreak_related {
+            Self::active_outbreak_for_unit(&env, &case.facility_id, &case.location)
+        } else {
+            None
+        };
+
+        let exposure_id = Self::next_id(&env, symbol_short!("exp_ctr"));
+        let exposure = StaffExposure {
+            exposure_id,
+            staff_id: staff_id.clone(),
+            facility_id: case.facility_id,
+            infection_id,
+            outbreak_id,
+            exposure_date,
+            exposure_type,
+            reported_by,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::StaffExposure(exposure_id), &exposure);
+        Self::push_id(&env, DataKey::StaffExposureIds(staff_id), exposure_id);
+        if let Some(outbreak_id) = outbreak_id {
+            Self::push_id(&env, DataKey::OutbreakStaffExposures(outbreak_id), exposure_id);
+        }
+
+        Ok(exposure_id)
+    }
+
+    /// A staff member reports their own symptom onset.
+    pub fn report_staff_symptoms(
+        env: Env,
+        staff_id: Address,
+        facility_id: Address,
+        symptom_onset: u64,
+        symptoms: Vec<Symbol>,
+        outbreak_id: Option<u64>,
+    ) -> Result<u64, Error> {
+        staff_id.require_auth();
+
+        if symptoms.is_empty() {
+            return Err(Error::InvalidData);
+        }
+        Self::check_outbreak_facility(&env, outbreak_id, &facility_id)?;
+
+        let report_id = Self::next_id(&env, symbol_short!("sym_ctr"));
+        let report = StaffSymptomReport {
+            report_id,
+            staff_id: staff_id.clone(),
+            facility_id,
+            outbreak_id,
+            symptom_onset,
+            symptoms,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::StaffSymptomReport(report_id), &report);
+        Self::push_id(&env, DataKey::StaffSymptomReportIds(staff_id), report_id);
+
+        Ok(report_id)
+    }
+
+    pub fn start_work_restriction(
+        env: Env,
+        staff_id: Address,
+        facility_id: Address,
+        reason: String,
+        start_date: u64,
+        outbreak_id: Option<u64>,
+        ordered_by: Address,
+    ) -> Result<u64, Error> {
+        ordered_by.require_auth();
+        Self::check_outbreak_facility(&env, outbreak_id, &facility_id)?;
+
+        let restriction_id = Self::next_id(&env, symbol_short!("wr_ctr"));
+        let restriction = WorkRestriction {
+            restriction_id,
+            staff_id,
+            facility_id: facility_id.clone(),
+            outbreak_id,
+            reason,
+            start_date,
+            ordered_by,
+            cleared_date: None,
+            cleared_by: None,
+            active: true,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::WorkRestriction(restriction_id), &restriction);
+        Self::push_id(&env, DataKey::FacilityRestrictions(facility_id), restriction_id);
+
+        Ok(restriction_id)
+    }
+
+    pub fn clear_work_restriction(
+        env: Env,
+        restriction_id: u64,
+        cleared_date: u64,
+        cleared_by: Address,
+    ) -> Result<(), Error> {
+        cleared_by.require_auth();
+
+        let mut restriction: WorkRestriction = env
+            .storage()
+            .persistent()
+            .get(&DataKey::WorkRestriction(restriction_id))
+            .ok_or(Error::NotFound)?;
+        if !restriction.active || cleared_date < restriction.start_date {
+            return Err(Error::InvalidData);
+        }
+
+        restriction.cleared_date = Some(cleared_date);
+        restriction.cleared_by = Some(cleared_by);
+        restriction.active = false;
+        env.storage()
+            .persistent()
+            .set(&DataKey::WorkRestriction(restriction_id), &restriction);
+
+        Ok(())
+    }
+
+    pub fn get_staff_exposures(env: Env, staff_id: Address) -> Vec<StaffExposure> {
+        let exposure_ids = Self::get_ids(&env, DataKey::StaffExposureIds(staff_id));
+        Self::load_staff_exposures(&env, &exposure_ids)
+    }
+
+    pub fn get_outbreak_staff_exposures(env: Env, outbreak_id: u64) -> Vec<StaffExposure> {
+        let exposure_ids = Self::get_ids(&env, DataKey::OutbreakStaffExposures(outbreak_id));
+        Self::load_staff_exposures(&env, &exposure_ids)
+    }
+
+    pub fn get_staff_symptom_reports(env: Env, staff_id: Address) -> Vec<StaffSymptomReport> {
+        let report_ids = Self::get_ids(&env, DataKey::StaffSymptomReportIds(staff_id));
+        let mut out = Vec::new(&env);
+
+        let mut i = 0u32;
+        while i < report_ids.len() {
+            if let Some(report_id) = report_ids.get(i) {
+                let report: Option<StaffSymptomReport> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::StaffSymptomReport(report_id));
+                if let Some(report) = report {
+                    out.push_back(report);
+                }
+            }
+            i += 1;
+        }
+
+        out
+    }
+
+    /// Work restrictions at the facility that have not been cleared.
+    pub fn get_restricted_staff(env: Env, facility_id: Address) -> Vec<WorkRestriction> {
+        let restriction_ids = Self::get_ids(&env, DataKey::FacilityRestrictions(facility_id));
+        let mut out = Vec::new(&env);
+
+        let mut i = 0u32;
+        while i < restriction_ids.len() {
+            if let Some(restriction_id) = restriction_ids.get(i) {
+                let restriction: Option<WorkRestriction> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::WorkRestriction(restriction_id));
+                if let Some(restriction) = restriction {
+                    if restriction.active {
+                        out.push_back(restriction);
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        out
+    }
+
+    pub fn track_isolation_precaution(
+        env: Env,
+        patient_id: Address,
+        precaution_type: Symbol,
+        start_date: u64,
+        indication: String,
+        discontinuation_criteria: String,
+    ) -> Result<u64, Error> {
+        if !Self::is_valid_precaution(&env, &precaution_type) {
+            return Err(Error::InvalidPrecautionType);
+        }
+
+        let precaution_id = Self::next_id(&env, symbol_short!("iso_ctr"));
+        let record = IsolationPrecaution {
+            precaution_id,
+            patient_id,
+            precaution_type,
+            start_date,
+            indication,
+            discontinuation_criteria,
+            active: true,
+        };
 
         env.storage()
             .persistent()
@@ -465,15 +1331,169 @@ impl HAITrackingContract {
         let rate_per_1000_days_x100 =
             (i64::from(numerator) * 1000 * 100) / i64::from(denominator);
 
+        let bundle_compliance_x100 = match (&unit, Self::attributed_device_type(&env, &infection_type)) {
+            (Some(unit), Some(device_type)) => {
+                let compliance = Self::bundle_compliance(
+                    &env,
+                    &facility_id,
+                    unit,
+                    device_type,
+                    time_period_start,
+                    time_period_end,
+                );
+                if compliance.audits > 0 {
+                    Some(compliance.compliance_rate_x100)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
         Ok(InfectionRate {
             infection_type,
             numerator,
             denominator,
             rate_per_1000_days_x100,
             sir_x100: None,
+            bundle_compliance_x100,
+            low_bundle_compliance: bundle_compliance_x100
+                .is_some_and(|rate| rate < LOW_BUNDLE_COMPLIANCE_X100),
         })
     }
 
+    /// Set the prevention-bundle elements audited for a device type.
+    pub fn define_bundle(
+        env: Env,
+        facility_id: Address,
+        device_type: DeviceType,
+        elements: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+
+        if elements.is_empty() {
+            return Err(Error::InvalidData);
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BundleElements(facility_id, device_type), &elements);
+
+        Ok(())
+    }
+
+    pub fn get_bundle(env: Env, facility_id: Address, device_type: DeviceType) -> Result<Vec<Symbol>, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BundleElements(facility_id, device_type))
+            .ok_or(Error::NotFound)
+    }
+
+    /// Record the daily bundle audit for a device in place. Elements of the
+    /// facility's bundle not listed as met are recorded as missed; a device
+    /// is audited at most once per calendar day.
+    pub fn record_bundle_audit(
+        env: Env,
+        device_record_id: u64,
+        unit: String,
+        audit_date: u64,
+        elements_met: Vec<Symbol>,
+        auditor: Address,
+    ) -> Result<u64, Error> {
+        auditor.require_auth();
+
+        let device = Self::get_device_internal(&env, device_record_id)?;
+        let bundle: Vec<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::BundleElements(device.facility_id.clone(), device.device_type))
+            .ok_or(Error::NotFound)?;
+
+        let in_place = device.inserted_at <= audit_date
+            && device.removed_at.is_none_or(|removed| audit_date <= removed);
+        let day_key = DataKey::DeviceAuditDay(device_record_id, audit_date / SECONDS_PER_DAY);
+        if !in_place || env.storage().persistent().has(&day_key) {
+            return Err(Error::InvalidData);
+        }
+
+        let mut i = 0u32;
+        while i < elements_met.len() {
+            if let Some(element) = elements_met.get(i) {
+                if !bundle.contains(&element) {
+                    return Err(Error::InvalidData);
+                }
+            }
+            i += 1;
+        }
+
+        let mut elements_missed = Vec::new(&env);
+        let mut j = 0u32;
+        while j < bundle.len() {
+            if let Some(element) = bundle.get(j) {
+                if !elements_met.contains(&element) {
+                    elements_missed.push_back(element);
+                }
+            }
+            j += 1;
+        }
+
+        let audit_id = Self::next_id(&env, symbol_short!("bnd_ctr"));
+        let audit = BundleAudit {
+            audit_id,
+            device_record_id,
+            facility_id: device.facility_id.clone(),
+            unit: unit.clone(),
+            device_type: device.device_type,
+            audit_date,
+            elements_met,
+            compliant: elements_missed.is_empty(),
+            elements_missed,
+            auditor,
+        };
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::BundleAudit(audit_id), &audit);
+        env.storage().persistent().set(&day_key, &audit_id);
+        Self::push_id(&env, DataKey::UnitBundleAudits(device.facility_id, unit), audit_id);
+
+        Ok(audit_id)
+    }
+
+    pub fn get_bundle_audit(env: Env, audit_id: u64) -> Result<BundleAudit, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BundleAudit(audit_id))
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn calculate_bundle_compliance(
+        env: Env,
+        facility_id: Address,
+        unit: String,
+        device_type: DeviceType,
+        time_period_start: u64,
+        time_period_end: u64,
+    ) -> Result<BundleCompliance, Error> {
+        if time_period_start > time_period_end {
+            return Err(Error::InvalidData);
+        }
+
+        let compliance = Self::bundle_compliance(
+            &env,
+            &facility_id,
+            &unit,
+            device_type,
+            time_period_start,
+            time_period_end,
+        );
+        if compliance.audits == 0 {
+            return Err(Error::DivisionByZero);
+        }
+
+        Ok(compliance)
+    }
+
     pub fn report_to_nhsn(
         env: Env,
         facility_id: Address,
@@ -599,6 +1619,23 @@ impl HAITrackingContract {
         out
     }
 
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
     fn get_infection_case_internal(env: &Env, infection_id: u64) -> Result<InfectionCase, Error> {
         env.storage()
             .persistent()
@@ -606,6 +1643,156 @@ impl HAITrackingContract {
             .ok_or(Error::NotFound)
     }
 
+    fn check_outbreak_facility(
+        env: &Env,
+        outbreak_id: Option<u64>,
+        facility_id: &Address,
+    ) -> Result<(), Error> {
+        if let Some(outbreak_id) = outbreak_id {
+            let outbreak: OutbreakCluster = env
+                .storage()
+                .persistent()
+                .get(&DataKey::OutbreakCluster(outbreak_id))
+                .ok_or(Error::NotFound)?;
+            if outbreak.facility_id != *facility_id {
+                return Err(Error::InvalidData);
+            }
+        }
+        Ok(())
+    }
+
+    fn load_staff_exposures(env: &Env, exposure_ids: &Vec<u64>) -> Vec<StaffExposure> {
+        let mut out = Vec::new(env);
+
+        let mut i = 0u32;
+        while i < exposure_ids.len() {
+            if let Some(exposure_id) = exposure_ids.get(i) {
+                let exposure: Option<StaffExposure> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::StaffExposure(exposure_id));
+                if let Some(exposure) = exposure {
+                    out.push_back(exposure);
+                }
+            }
+            i += 1;
+        }
+
+        out
+    }
+
+    fn active_outbreak_for_unit(env: &Env, facility_id: &Address, unit: &String) -> Option<u64> {
+        let outbreak_ids = Self::get_ids(env, DataKey::OutbreakIds);
+        let mut i = 0u32;
+        while i < outbreak_ids.len() {
+            if let Some(outbreak_id) = outbreak_ids.get(i) {
+                let cluster: Option<OutbreakCluster> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::OutbreakCluster(outbreak_id));
+                if let Some(cluster) = cluster {
+                    if cluster.active && cluster.facility_id == *facility_id && cluster.unit == *unit {
+                        return Some(outbreak_id);
+                    }
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+
+    fn bundle_compliance(
+        env: &Env,
+        facility_id: &Address,
+        unit: &String,
+        device_type: DeviceType,
+        time_period_start: u64,
+        time_period_end: u64,
+    ) -> BundleCompliance {
+        let audit_ids = Self::get_ids(env, DataKey::UnitBundleAudits(facility_id.clone(), unit.clone()));
+        let mut audits = 0u32;
+        let mut compliant_audits = 0u32;
+
+        let mut i = 0u32;
+        while i < audit_ids.len() {
+            if let Some(audit_id) = audit_ids.get(i) {
+                let audit: Option<BundleAudit> = env
+                    .storage()
+                    .persistent()
+                    .get(&DataKey::BundleAudit(audit_id));
+                if let Some(audit) = audit {
+                    if audit.device_type == device_type
+                        && audit.audit_date >= time_period_start
+                        && audit.audit_date <= time_period_end
+                    {
+                        audits += 1;
+                        if audit.compliant {
+                            compliant_audits += 1;
+                        }
+                    }
+                }
+            }
+            i += 1;
+        }
+
+        let compliance_rate_x100 = if audits == 0 {
+            0
+        } else {
+            (i64::from(compliant_audits) * 10_000) / i64::from(audits)
+        };
+
+        BundleCompliance {
+            device_type,
+            audits,
+            compliant_audits,
+            compliance_rate_x100,
+        }
+    }
+
+    fn get_device_internal(env: &Env, device_record_id: u64) -> Result<DeviceRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::DeviceRecord(device_record_id))
+            .ok_or(Error::NotFound)
+    }
+
+    fn device_days_between(inserted_at: u64, end: u64) -> u32 {
+        ((end / SECONDS_PER_DAY).saturating_sub(inserted_at / SECONDS_PER_DAY) + 1) as u32
+    }
+
+    fn attributed_device_type(env: &Env, infection_type: &Symbol) -> Option<DeviceType> {
+        if *infection_type == Symbol::new(env, "clabsi") {
+            Some(DeviceType::CentralLine)
+        } else if *infection_type == Symbol::new(env, "cauti") {
+            Some(DeviceType::UrinaryCatheter)
+        } else if *infection_type == Symbol::new(env, "vap") {
+            Some(DeviceType::Ventilator)
+        } else {
+            None
+        }
+    }
+
+    fn get_procedure_internal(env: &Env, procedure_id: u64) -> Result<SurgicalProcedure, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::SurgicalProcedure(procedure_id))
+            .ok_or(Error::NotFound)
+    }
+
+    /// Calendar quarter of a Unix timestamp, encoded as `year * 10 + quarter`.
+    fn quarter_of(timestamp: u64) -> u32 {
+        // Civil-from-days conversion (proleptic Gregorian, UTC).
+        let z = timestamp / SECONDS_PER_DAY + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+        (year * 10 + (month - 1) / 3 + 1) as u32
+    }
+
     fn next_id(env: &Env, counter_key: Symbol) -> u64 {
         let next = env.storage().instance().get(&counter_key).unwrap_or(0u64) + 1;
         env.storage().instance().set(&counter_key, &next);
@@ -652,6 +1839,19 @@ impl HAITrackingContract {
             || *priority == Symbol::new(env, "high")
             || *priority == Symbol::new(env, "critical")
     }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(())
+    }
 }
 
 mod test;
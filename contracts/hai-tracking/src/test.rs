@@ -29,14 +29,23 @@ fn report_case(
     location: &str,
     reporter: &Address,
 ) -> u64 {
+    // Device-associated types need the device in place at onset.
+    let device_type = match infection_type {
+        "clabsi" => Some(DeviceType::CentralLine),
+        "cauti" => Some(DeviceType::UrinaryCatheter),
+        "vap" => Some(DeviceType::Ventilator),
+        _ => None,
+    };
+    let device_record_id = device_type.map(|device_type| {
+        client.record_device_insertion(patient, facility, &device_type, &(onset_date - 3 * 86_400), reporter)
+    });
     client.report_infection(
         patient,
         facility,
         &Symbol::new(env, infection_type),
         &onset_date,
         &String::from_str(env, location),
-        &false,
-        &None,
+        &device_record_id,
         reporter,
     )
 }
@@ -78,7 +87,6 @@ fn test_report_infection_invalid_type_fails() {
         &Symbol::new(&env, "unknown"),
         &1_799_900_000,
         &String::from_str(&env, "Ward A"),
-        &false,
         &None,
         &reporter,
     );
@@ -213,6 +221,373 @@ fn test_outbreak_detection_success() {
     assert_eq!(outbreaks.get(0).unwrap().case_count, 4);
 }
 
+#[test]
+fn test_generate_outbreak_line_list() {
+    let (env, client) = setup();
+    let facility = Address::generate(&env);
+    let reporter = Address::generate(&env);
+
+    let onsets = [1_799_900_000u64, 1_799_950_000, 1_799_990_000];
+    let case_ids = onsets.map(|onset| {
+        let patient = Address::generate(&env);
+        report_case(
+            &env, &client, &patient, &facility, "mrsa", onset, "Ward A", &reporter,
+        )
+    });
+    // Different unit, not part of the cluster
+    report_case(
+        &env,
+        &client,
+        &Address::generate(&env),
+        &facility,
+        "mrsa",
+        1_799_950_000,
+        "Ward B",
+        &reporter,
+    );
+    client.record_organism(
+        &case_ids[0],
+        &String::from_str(&env, "Staphylococcus aureus"),
+        &Symbol::new(&env, "blood"),
+        &1_799_910_000,
+        &BytesN::from_array(&env, &[4u8; 32]),
+    );
+
+    let outbreak_id = client
+        .identify_outbreak_cluster(
+            &Symbol::new(&env, "mrsa"),
+            &facility,
+            &String::from_str(&env, "Ward A"),
+            &30,
+            &3,
+        )
+        .unwrap();
+
+    let entries = client.get_line_list(&outbreak_id);
+    assert_eq!(entries.len(), 3);
+    let first = entries.get(0).unwrap();
+    assert_eq!(first.infection_id, case_ids[0]);
+    assert_eq!(first.onset_date, 1_799_900_000);
+    assert_eq!(first.unit, String::from_str(&env, "Ward A"));
+    assert_eq!(first.organisms.len(), 1);
+    assert_eq!(entries.get(2).unwrap().infection_id, case_ids[2]);
+
+    let export = client.generate_line_list(&outbreak_id);
+    assert_eq!(export.case_count, 3);
+    assert_eq!(export.generated_at, 1_800_000_000);
+    let expected: BytesN<32> = env
+        .crypto()
+        .sha256(&(outbreak_id, entries).to_xdr(&env))
+        .into();
+    assert_eq!(export.line_list_hash, expected);
+
+    // Same cases, same hash
+    let again = client.generate_line_list(&outbreak_id);
+    assert_eq!(again.line_list_hash, export.line_list_hash);
+    assert_eq!(client.get_line_list_exports(&outbreak_id).len(), 2);
+
+    assert_eq!(client.try_generate_line_list(&99), Err(Ok(Error::NotFound)));
+}
+
+#[test]
+fn test_environmental_samples_link_to_outbreak_summary() {
+    let (env, client) = setup();
+    let facility = Address::generate(&env);
+    let collector = Address::generate(&env);
+    let ward = String::from_str(&env, "Ward A");
+
+    let sample = |outbreak_id: Option<u64>, unit: Option<String>, organism: Option<&str>| {
+        client.record_environmental_sample(
+            &collector,
+            &EnvironmentalSampleInput {
+                facility_id: facility.clone(),
+                outbreak_id,
+                unit,
+                sample_site: String::from_str(&env, "sink drain"),
+                collection_date: 1_799_960_000,
+                result_hash: BytesN::from_array(&env, &[7; 32]),
+                organism_found: organism.map(|o| String::from_str(&env, o)),
+            },
+        )
+    };
+
+    // Taken on the unit before any cluster exists; the positive is picked up
+    // when the cluster is identified.
+    let early_positive = sample(None, Some(ward.clone()), Some("MRSA"));
+    sample(None, Some(ward.clone()), None);
+
+    for _ in 0..3 {
+        report_case(&env, &client, &Address::generate(&env), &facility, "mrsa", 1_799_950_000, "Ward A", &collector);
+    }
+    let outbreak_id = client
+        .identify_outbreak_cluster(&Symbol::new(&env, "mrsa"), &facility, &ward, &30, &3)
+        .unwrap();
+    assert_eq!(
+        client.get_environmental_sample(&early_positive).outbreak_id,
+        Some(outbreak_id)
+    );
+
+    // Positive unit samples now link to the active cluster directly.
+    let unit_positive = sample(None, Some(ward.clone()), Some("MRSA"));
+    assert_eq!(
+        client.get_environmental_sample(&unit_positive).outbreak_id,
+        Some(outbreak_id)
+    );
+    sample(Some(outbreak_id), None, None);
+
+    let res = client.try_record_environmental_sample(
+        &collector,
+        &EnvironmentalSampleInput {
+            facility_id: facility.clone(),
+            outbreak_id: None,
+            unit: None,
+            sample_site: String::from_str(&env, "bed rail"),
+            collection_date: 1_799_960_000,
+            result_hash: BytesN::from_array(&env, &[7; 32]),
+            organism_found: None,
+        },
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+
+    let summary = client.get_outbreak_summary(&outbreak_id);
+    assert_eq!(summary.cluster.case_count, 3);
+    assert_eq!(summary.environmental_samples, 3);
+    assert_eq!(summary.environmental_positives.len(), 2);
+    assert_eq!(summary.environmental_positives.get(0).unwrap().sample_id, early_positive);
+}
+
+#[test]
+fn test_staff_exposure_and_work_restrictions() {
+    let (env, client) = setup();
+    let facility = Address::generate(&env);
+    let occ_health = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let ward = String::from_str(&env, "Ward A");
+
+    let mut case_id = 0;
+    for _ in 0..3 {
+        case_id = report_case(&env, &client, &Address::generate(&env), &facility, "mrsa", 1_799_950_000, "Ward A", &occ_health);
+    }
+    let outbreak_id = client
+        .identify_outbreak_cluster(&Symbol::new(&env, "mrsa"), &facility, &ward, &30, &3)
+        .unwrap();
+
+    // The exposure picks up the outbreak of the case it came from.
+    let exposure_id = client.record_staff_exposure(
+        &nurse,
+        &case_id,
+        &1_799_960_000,
+        &Symbol::new(&env, "contact"),
+        &occ_health,
+    );
+    let exposures = client.get_staff_exposures(&nurse);
+    assert_eq!(exposures.len(), 1);
+    assert_eq!(exposures.get(0).unwrap().outbreak_id, Some(outbreak_id));
+    assert_eq!(
+        client.get_outbreak_staff_exposures(&outbreak_id).get(0).unwrap().exposure_id,
+        exposure_id
+    );
+
+    client.report_staff_symptoms(
+        &nurse,
+        &facility,
+        &1_799_970_000,
+        &soroban_sdk::vec![&env, Symbol::new(&env, "fever")],
+        &Some(outbreak_id),
+    );
+    assert_eq!(client.get_staff_symptom_reports(&nurse).len(), 1);
+    let res = client.try_report_staff_symptoms(
+        &nurse,
+        &Address::generate(&env),
+        &1_799_970_000,
+        &soroban_sdk::vec![&env, Symbol::new(&env, "fever")],
+        &Some(outbreak_id),
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+
+    let restriction_id = client.start_work_restriction(
+        &nurse,
+        &facility,
+        &String::from_str(&env, "febrile after MRSA exposure"),
+        &1_799_970_000,
+        &Some(outbreak_id),
+        &occ_health,
+    );
+    let restricted = client.get_restricted_staff(&facility);
+    assert_eq!(restricted.len(), 1);
+    assert_eq!(restricted.get(0).unwrap().staff_id, nurse);
+
+    let res = client.try_clear_work_restriction(&restriction_id, &1_799_960_000, &occ_health);
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+    client.clear_work_restriction(&restriction_id, &1_799_990_000, &occ_health);
+    assert!(client.get_restricted_staff(&facility).is_empty());
+}
+
+#[test]
+fn test_ssi_rate_per_procedure_category_and_quarter() {
+    let (env, client) = setup();
+    let facility = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let colon = Symbol::new(&env, "colo");
+
+    // Mid-January and mid-February 2027 fall in Q1; early April in Q2.
+    let first = client.register_procedure(&facility, &colon, &1_800_000_000, &2);
+    client.register_procedure(&facility, &colon, &1_803_000_000, &2);
+    let second_quarter = client.register_procedure(&facility, &colon, &1_807_000_000, &3);
+    let res = client.try_register_procedure(&facility, &colon, &1_800_000_000, &5);
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+
+    let ssi = report_case(&env, &client, &Address::generate(&env), &facility, "ssi", 1_800_500_000, "OR", &reporter);
+    let clabsi = report_case(&env, &client, &Address::generate(&env), &facility, "clabsi", 1_800_500_000, "ICU", &reporter);
+    assert_eq!(
+        client.try_link_ssi_to_procedure(&clabsi, &first),
+        Err(Ok(Error::InvalidInfectionType))
+    );
+    client.link_ssi_to_procedure(&ssi, &first);
+    assert_eq!(client.get_procedure(&first).ssi_infection_id, Some(ssi));
+    assert_eq!(
+        client.try_link_ssi_to_procedure(&ssi, &second_quarter),
+        Err(Ok(Error::InvalidData))
+    );
+
+    let rate = client.calculate_ssi_rate(&facility, &colon, &20271);
+    assert_eq!(rate.procedures, 2);
+    assert_eq!(rate.ssi_cases, 1);
+    assert_eq!(rate.rate_per_100_x100, 5_000);
+
+    let rate = client.calculate_ssi_rate(&facility, &colon, &20272);
+    assert_eq!(rate.procedures, 1);
+    assert_eq!(rate.ssi_cases, 0);
+    assert_eq!(
+        client.try_calculate_ssi_rate(&facility, &colon, &20273),
+        Err(Ok(Error::DivisionByZero))
+    );
+}
+
+#[test]
+fn test_device_registry_and_attribution() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let facility = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let ward = String::from_str(&env, "ICU");
+
+    // Inserted on day 0, removed on day 4: five calendar device days.
+    let line = client.record_device_insertion(&patient, &facility, &DeviceType::CentralLine, &1_799_600_000, &nurse);
+    let foley = client.record_device_insertion(&patient, &facility, &DeviceType::UrinaryCatheter, &1_799_600_000, &nurse);
+    client.record_device_removal(&line, &1_799_950_000, &nurse);
+    assert_eq!(client.get_device_days(&line), 5);
+    assert_eq!(
+        client.try_record_device_removal(&line, &1_799_960_000, &nurse),
+        Err(Ok(Error::InvalidData))
+    );
+    assert_eq!(client.get_patient_devices(&patient).len(), 2);
+
+    let report = |infection_type: &str, onset_date: u64, device: Option<u64>| {
+        client.try_report_infection(
+            &patient,
+            &facility,
+            &Symbol::new(&env, infection_type),
+            &onset_date,
+            &ward,
+            &device,
+            &nurse,
+        )
+    };
+
+    // No device, the wrong kind of device, or one removed well before onset.
+    assert_eq!(report("clabsi", 1_799_980_000, None), Err(Ok(Error::DeviceNotAttributable)));
+    assert_eq!(report("clabsi", 1_799_980_000, Some(foley)), Err(Ok(Error::DeviceNotAttributable)));
+    assert_eq!(report("clabsi", 1_800_200_000, Some(line)), Err(Ok(Error::DeviceNotAttributable)));
+    assert_eq!(report("mrsa", 1_799_980_000, Some(line)), Err(Ok(Error::InvalidData)));
+
+    // Onset the day after removal still counts against the line.
+    let case_id = report("clabsi", 1_799_980_000, Some(line)).unwrap().unwrap();
+    let case = client.get_infection_case(&case_id);
+    assert!(case.device_associated);
+    assert_eq!(case.device_days, Some(5));
+}
+
+#[test]
+fn test_bundle_audits_and_compliance_correlation() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+    let facility = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let icu = String::from_str(&env, "ICU");
+    let hand_hygiene = Symbol::new(&env, "hand_hygiene");
+    let chg_bath = Symbol::new(&env, "chg_bath");
+
+    client.define_bundle(
+        &facility,
+        &DeviceType::CentralLine,
+        &soroban_sdk::vec![&env, hand_hygiene.clone(), chg_bath.clone()],
+    );
+    let line = client.record_device_insertion(&patient, &facility, &DeviceType::CentralLine, &1_799_600_000, &nurse);
+
+    // Day one fully met, day two missed the CHG bath.
+    client.record_bundle_audit(
+        &line,
+        &icu,
+        &1_799_610_000,
+        &soroban_sdk::vec![&env, hand_hygiene.clone(), chg_bath.clone()],
+        &nurse,
+    );
+    let missed = client.record_bundle_audit(
+        &line,
+        &icu,
+        &1_799_700_000,
+        &soroban_sdk::vec![&env, hand_hygiene.clone()],
+        &nurse,
+    );
+    let audit = client.get_bundle_audit(&missed);
+    assert!(!audit.compliant);
+    assert_eq!(audit.elements_missed, soroban_sdk::vec![&env, chg_bath.clone()]);
+
+    // One audit per device per day, and only elements of the bundle.
+    let res = client.try_record_bundle_audit(&line, &icu, &1_799_700_100, &soroban_sdk::vec![&env], &nurse);
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+    let res = client.try_record_bundle_audit(
+        &line,
+        &icu,
+        &1_799_800_000,
+        &soroban_sdk::vec![&env, Symbol::new(&env, "other")],
+        &nurse,
+    );
+    assert_eq!(res, Err(Ok(Error::InvalidData)));
+
+    let compliance = client.calculate_bundle_compliance(
+        &facility,
+        &icu,
+        &DeviceType::CentralLine,
+        &1_799_600_000,
+        &1_799_900_000,
+    );
+    assert_eq!(compliance.audits, 2);
+    assert_eq!(compliance.compliant_audits, 1);
+    assert_eq!(compliance.compliance_rate_x100, 5_000);
+
+    client.report_infection(
+        &patient,
+        &facility,
+        &Symbol::new(&env, "clabsi"),
+        &1_799_800_000,
+        &icu,
+        &Some(line),
+        &nurse,
+    );
+    let rate = client.calculate_infection_rate(
+        &facility,
+        &Symbol::new(&env, "clabsi"),
+        &1_799_600_000,
+        &1_799_900_000,
+        &Some(icu.clone()),
+    );
+    assert_eq!(rate.numerator, 1);
+    assert_eq!(rate.bundle_compliance_x100, Some(5_000));
+    assert!(rate.low_bundle_compliance);
+}
+
 #[test]
 fn test_outbreak_detection_below_threshold() {
     let (env, client) = setup();
@@ -389,3 +764,24 @@ fn test_reporting_stewardship_and_alert_priority_validation() {
     );
     assert!(bad_dot.is_err());
 }
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
@@ -0,0 +1,425 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, BytesN, Env, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct HealthInformationExchangeContract;
+
+#[contractimpl]
+impl HealthInformationExchangeContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(env: Env, admin: Address, facility: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Facility(facility), &true);
+        Ok(())
+    }
+
+    /// Allow another contract (referral, PACS) to register the documents it exchanges.
+    pub fn add_integration(env: Env, admin: Address, contract_id: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Integration(contract_id), &true);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Registry
+    // -------------------------------------------------------------------
+
+    pub fn register_document(
+        env: Env,
+        facility: Address,
+        patient_id: Address,
+        document_type: Symbol,
+        content_hash: BytesN<32>,
+    ) -> Result<u64, Error> {
+        facility.require_auth();
+        if !has_flag(&env, &DataKey::Facility(facility.clone())) {
+            return Err(Error::FacilityNotRegistered);
+        }
+        let document_id = store_document(
+            &env,
+            patient_id,
+            facility.clone(),
+            document_type,
+            content_hash,
+            None,
+        );
+        audit(&env, document_id, &facility, symbol_short!("register"), 0);
+        Ok(document_id)
+    }
+
+    /// Hook for integration contracts: register a document that `source`
+    /// already sent to `recipient`. The exchange is recorded as a fulfilled
+    /// request so it shows in the document's audit trail.
+    pub fn register_exchanged_document(
+        env: Env,
+        integration: Address,
+        source: Address,
+        recipient: Address,
+        patient_id: Address,
+        document_type: Symbol,
+        content_hash: BytesN<32>,
+    ) -> Result<u64, Error> {
+        integration.require_auth();
+        if !has_flag(&env, &DataKey::Integration(integration.clone())) {
+            return Err(Error::Unauthorized);
+        }
+        let document_id = store_document(
+            &env,
+            patient_id,
+            source.clone(),
+            document_type.clone(),
+            content_hash,
+            Some(integration),
+        );
+        audit(&env, document_id, &source, symbol_short!("register"), 0);
+
+        let now = env.ledger().timestamp();
+        let request = DocumentRequest {
+            request_id: next_id(&env, DataKey::RequestCounter),
+            document_id,
+            requester: recipient.clone(),
+            purpose: document_type,
+            status: RequestStatus::Fulfilled,
+            requested_at: now,
+            responded_at: now,
+        };
+        save_request(&env, &request);
+        audit(
+            &env,
+            document_id,
+            &recipient,
+            symbol_short!("exchange"),
+            request.request_id,
+        );
+
+        Ok(document_id)
+    }
+
+    pub fn get_patient_documents(
+        env: Env,
+        patient_id: Address,
+    ) -> Result<Vec<ClinicalDocument>, Error> {
+        patient_id.require_auth();
+        let mut docs = Vec::new(&env);
+        for id in id_list(&env, &DataKey::PatientDocuments(patient_id)).iter() {
+            if let Some(doc) = load_document(&env, id) {
+                docs.push_back(doc);
+            }
+        }
+        Ok(docs)
+    }
+
+    // -------------------------------------------------------------------
+    // Patient access policies
+    // -------------------------------------------------------------------
+
+    /// Grant (or replace) standing consent for a grantee.
+    pub fn set_access_policy(
+        env: Env,
+        patient_id: Address,
+        policy: AccessPolicy,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+        if policy.expires_at != 0 && policy.expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidInput);
+        }
+        let mut list = without_grantee(&env, &patient_id, &policy.grantee);
+        list.push_back(policy);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Policies(patient_id), &list);
+        Ok(())
+    }
+
+    pub fn revoke_access_policy(
+        env: Env,
+        patient_id: Address,
+        grantee: Address,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+        let list = without_grantee(&env, &patient_id, &grantee);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Policies(patient_id), &list);
+        Ok(())
+    }
+
+    pub fn get_access_policies(env: Env, patient_id: Address) -> Vec<AccessPolicy> {
+        policies(&env, &patient_id)
+    }
+
+    // -------------------------------------------------------------------
+    // Retrieval handshake
+    // -------------------------------------------------------------------
+
+    /// Ask for a document. Approved immediately when a patient policy covers
+    /// the requester and document type; otherwise waits for the patient.
+    pub fn request_document(
+        env: Env,
+        requester: Address,
+        document_id: u64,
+        purpose: Symbol,
+    ) -> Result<u64, Error> {
+        requester.require_auth();
+        let doc = load_document(&env, document_id).ok_or(Error::DocumentNotFound)?;
+        let now = env.ledger().timestamp();
+        let status = if policy_allows(&env, &doc, &requester) {
+            RequestStatus::Approved
+        } else {
+            RequestStatus::Pending
+        };
+
+        let request = DocumentRequest {
+            request_id: next_id(&env, DataKey::RequestCounter),
+            document_id,
+            requester: requester.clone(),
+            purpose,
+            status,
+            requested_at: now,
+            responded_at: if status == RequestStatus::Approved {
+                now
+            } else {
+                0
+            },
+        };
+        save_request(&env, &request);
+        push_id(
+            &env,
+            DataKey::PatientRequests(doc.patient_id),
+            request.request_id,
+        );
+        audit(
+            &env,
+            document_id,
+            &requester,
+            symbol_short!("request"),
+            request.request_id,
+        );
+        publish_request(&env, &request);
+
+        Ok(request.request_id)
+    }
+
+    pub fn respond_to_request(
+        env: Env,
+        patient_id: Address,
+        request_id: u64,
+        approve: bool,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+        let mut request = load_request(&env, request_id).ok_or(Error::RequestNotFound)?;
+        let doc = load_document(&env, request.document_id).ok_or(Error::DocumentNotFound)?;
+        if doc.patient_id != patient_id {
+            return Err(Error::Unauthorized);
+        }
+        if request.status != RequestStatus::Pending {
+            return Err(Error::InvalidState);
+        }
+
+        request.status = if approve {
+            RequestStatus::Approved
+        } else {
+            RequestStatus::Denied
+        };
+        request.responded_at = env.ledger().timestamp();
+        save_request(&env, &request);
+        let action = if approve {
+            symbol_short!("approve")
+        } else {
+            symbol_short!("deny")
+        };
+        audit(&env, request.document_id, &patient_id, action, request_id);
+        publish_request(&env, &request);
+        Ok(())
+    }
+
+    /// Complete an approved request and return the document pointer.
+    pub fn retrieve_document(
+        env: Env,
+        requester: Address,
+        request_id: u64,
+    ) -> Result<ClinicalDocument, Error> {
+        requester.require_auth();
+        let mut request = load_request(&env, request_id).ok_or(Error::RequestNotFound)?;
+        if request.requester != requester {
+            return Err(Error::Unauthorized);
+        }
+        if request.status != RequestStatus::Approved {
+            return Err(Error::AccessDenied);
+        }
+        let doc = load_document(&env, request.document_id).ok_or(Error::DocumentNotFound)?;
+
+        request.status = RequestStatus::Fulfilled;
+        save_request(&env, &request);
+        audit(
+            &env,
+            request.document_id,
+            &requester,
+            symbol_short!("retrieve"),
+            request_id,
+        );
+        publish_request(&env, &request);
+        Ok(doc)
+    }
+
+    pub fn get_request(env: Env, request_id: u64) -> Result<DocumentRequest, Error> {
+        load_request(&env, request_id).ok_or(Error::RequestNotFound)
+    }
+
+    /// Requests awaiting the patient's decision.
+    pub fn get_pending_requests(env: Env, patient_id: Address) -> Vec<DocumentRequest> {
+        let mut pending = Vec::new(&env);
+        for id in id_list(&env, &DataKey::PatientRequests(patient_id)).iter() {
+            if let Some(request) = load_request(&env, id) {
+                if request.status == RequestStatus::Pending {
+                    pending.push_back(request);
+                }
+            }
+        }
+        pending
+    }
+
+    /// Full access history of a document, visible to its patient and source.
+    pub fn get_audit_log(
+        env: Env,
+        caller: Address,
+        document_id: u64,
+    ) -> Result<Vec<AuditEvent>, Error> {
+        caller.require_auth();
+        let doc = load_document(&env, document_id).ok_or(Error::DocumentNotFound)?;
+        if caller != doc.patient_id && caller != doc.source {
+            return Err(Error::Unauthorized);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::Audit(document_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn store_document(
+    env: &Env,
+    patient_id: Address,
+    source: Address,
+    document_type: Symbol,
+    content_hash: BytesN<32>,
+    registered_via: Option<Address>,
+) -> u64 {
+    let doc = ClinicalDocument {
+        document_id: next_id(env, DataKey::DocumentCounter),
+        patient_id: patient_id.clone(),
+        source: source.clone(),
+        document_type: document_type.clone(),
+        content_hash,
+        registered_at: env.ledger().timestamp(),
+        registered_via,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::Document(doc.document_id), &doc);
+    push_id(
+        env,
+        DataKey::PatientDocuments(patient_id.clone()),
+        doc.document_id,
+    );
+
+    DocumentRegistered {
+        document_id: doc.document_id,
+        patient_id,
+        document_type,
+        source,
+    }
+    .publish(env);
+    doc.document_id
+}
+
+fn policy_allows(env: &Env, doc: &ClinicalDocument, requester: &Address) -> bool {
+    let now = env.ledger().timestamp();
+    policies(env, &doc.patient_id).iter().any(|p| {
+        p.grantee == *requester
+            && (p.expires_at == 0 || p.expires_at > now)
+            && (p.document_types.is_empty() || p.document_types.contains(&doc.document_type))
+    })
+}
+
+fn without_grantee(env: &Env, patient_id: &Address, grantee: &Address) -> Vec<AccessPolicy> {
+    let mut kept = Vec::new(env);
+    for policy in policies(env, patient_id).iter() {
+        if policy.grantee != *grantee {
+            kept.push_back(policy);
+        }
+    }
+    kept
+}
+
+fn publish_request(env: &Env, request: &DocumentRequest) {
+    DocumentRequestUpdated {
+        request_id: request.request_id,
+        document_id: request.document_id,
+        status: request.status,
+    }
+    .publish(env);
+}
@@ -0,0 +1,84 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{AccessPolicy, AuditEvent, ClinicalDocument, DataKey, DocumentRequest};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn has_flag(env: &Env, key: &DataKey) -> bool {
+    env.storage().persistent().get(key).unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Counters and id lists
+// -----------------------------------------------------------------------
+
+pub fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+pub fn id_list(env: &Env, key: &DataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+pub fn push_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids = id_list(env, &key);
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+// -----------------------------------------------------------------------
+// Documents and requests
+// -----------------------------------------------------------------------
+
+pub fn load_document(env: &Env, document_id: u64) -> Option<ClinicalDocument> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Document(document_id))
+}
+
+pub fn save_request(env: &Env, request: &DocumentRequest) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Request(request.request_id), request);
+}
+
+pub fn load_request(env: &Env, request_id: u64) -> Option<DocumentRequest> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Request(request_id))
+}
+
+pub fn policies(env: &Env, patient_id: &Address) -> Vec<AccessPolicy> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Policies(patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+// -----------------------------------------------------------------------
+// Audit
+// -----------------------------------------------------------------------
+
+pub fn audit(env: &Env, document_id: u64, actor: &Address, action: Symbol, request_id: u64) {
+    let key = DataKey::Audit(document_id);
+    let mut log: Vec<AuditEvent> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    log.push_back(AuditEvent {
+        actor: actor.clone(),
+        action,
+        request_id,
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &log);
+}
@@ -0,0 +1,304 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    HealthInformationExchangeContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(HealthInformationExchangeContract, ());
+    let client = HealthInformationExchangeContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    let patient = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(&admin, &facility);
+
+    (env, admin, facility, patient, client)
+}
+
+fn register(
+    env: &Env,
+    client: &HealthInformationExchangeContractClient,
+    facility: &Address,
+    patient: &Address,
+    document_type: Symbol,
+) -> u64 {
+    client.register_document(
+        facility,
+        patient,
+        &document_type,
+        &BytesN::from_array(env, &[4u8; 32]),
+    )
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_request_approve_retrieve_handshake() {
+    let (env, _, facility, patient, client) = setup();
+    let doc_id = register(&env, &client, &facility, &patient, symbol_short!("ccd"));
+    let clinic = Address::generate(&env);
+
+    let request_id = client.request_document(&clinic, &doc_id, &symbol_short!("treat"));
+    assert_eq!(client.get_pending_requests(&patient).len(), 1);
+    assert_eq!(
+        client.try_retrieve_document(&clinic, &request_id),
+        Err(Ok(Error::AccessDenied))
+    );
+
+    client.respond_to_request(&patient, &request_id, &true);
+    let doc = client.retrieve_document(&clinic, &request_id);
+    assert_eq!(doc.document_id, doc_id);
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Fulfilled
+    );
+
+    let log = client.get_audit_log(&patient, &doc_id);
+    let expected = [
+        symbol_short!("register"),
+        symbol_short!("request"),
+        symbol_short!("approve"),
+        symbol_short!("retrieve"),
+    ];
+    assert_eq!(log.len(), expected.len() as u32);
+    for (i, action) in expected.iter().enumerate() {
+        assert_eq!(log.get(i as u32).unwrap().action, *action);
+    }
+}
+
+#[test]
+fn test_standing_policy_auto_approves_matching_types() {
+    let (env, _, facility, patient, client) = setup();
+    let labs = register(&env, &client, &facility, &patient, symbol_short!("lab"));
+    let notes = register(&env, &client, &facility, &patient, symbol_short!("psych"));
+    let pcp = Address::generate(&env);
+
+    client.set_access_policy(
+        &patient,
+        &AccessPolicy {
+            grantee: pcp.clone(),
+            document_types: vec![&env, symbol_short!("lab")],
+            expires_at: 0,
+        },
+    );
+    let lab_request = client.request_document(&pcp, &labs, &symbol_short!("treat"));
+    assert_eq!(
+        client.get_request(&lab_request).status,
+        RequestStatus::Approved
+    );
+    let note_request = client.request_document(&pcp, &notes, &symbol_short!("treat"));
+    assert_eq!(
+        client.get_request(&note_request).status,
+        RequestStatus::Pending
+    );
+
+    client.revoke_access_policy(&patient, &pcp);
+    let again = client.request_document(&pcp, &labs, &symbol_short!("treat"));
+    assert_eq!(client.get_request(&again).status, RequestStatus::Pending);
+}
+
+#[test]
+fn test_denied_request_cannot_be_retrieved() {
+    let (env, _, facility, patient, client) = setup();
+    let doc_id = register(&env, &client, &facility, &patient, symbol_short!("ccd"));
+    let insurer = Address::generate(&env);
+    let request_id = client.request_document(&insurer, &doc_id, &symbol_short!("payment"));
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_respond_to_request(&stranger, &request_id, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.respond_to_request(&patient, &request_id, &false);
+    assert_eq!(
+        client.try_retrieve_document(&insurer, &request_id),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_respond_to_request(&patient, &request_id, &true),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_integration_hook_registers_exchanged_document() {
+    let (env, admin, _, patient, client) = setup();
+    let integration = Address::generate(&env);
+    let sender = Address::generate(&env);
+    let recipient = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[8u8; 32]);
+
+    assert_eq!(
+        client.try_register_exchanged_document(
+            &integration,
+            &sender,
+            &recipient,
+            &patient,
+            &symbol_short!("referral"),
+            &hash,
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.add_integration(&admin, &integration);
+    let doc_id = client.register_exchanged_document(
+        &integration,
+        &sender,
+        &recipient,
+        &patient,
+        &symbol_short!("referral"),
+        &hash,
+    );
+    let docs = client.get_patient_documents(&patient);
+    assert_eq!(docs.len(), 1);
+    assert_eq!(docs.get(0).unwrap().registered_via, Some(integration));
+
+    let log = client.get_audit_log(&sender, &doc_id);
+    assert_eq!(log.len(), 2);
+    assert_eq!(log.get(1).unwrap().action, symbol_short!("exchange"));
+    assert_eq!(log.get(1).unwrap().actor, recipient);
+}
+
+#[test]
+fn test_only_registered_facilities_register_documents() {
+    let (env, _, _, patient, client) = setup();
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_document(
+            &other,
+            &patient,
+            &symbol_short!("ccd"),
+            &BytesN::from_array(&env, &[1u8; 32]),
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+}
+
+#[test]
+fn test_administration_requires_admin() {
+    let (env, admin, facility, _, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&facility, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_integration(&facility, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_handshake_errors() {
+    let (env, _, facility, patient, client) = setup();
+    let doc_id = register(&env, &client, &facility, &patient, symbol_short!("ccd"));
+    let clinic = Address::generate(&env);
+
+    assert_eq!(
+        client.try_request_document(&clinic, &99, &symbol_short!("treat")),
+        Err(Ok(Error::DocumentNotFound))
+    );
+    assert_eq!(
+        client.try_respond_to_request(&patient, &99, &true),
+        Err(Ok(Error::RequestNotFound))
+    );
+    assert_eq!(client.try_get_request(&99), Err(Ok(Error::RequestNotFound)));
+
+    // Approval only benefits the original requester, and only once
+    let request_id = client.request_document(&clinic, &doc_id, &symbol_short!("treat"));
+    client.respond_to_request(&patient, &request_id, &true);
+    let other_clinic = Address::generate(&env);
+    assert_eq!(
+        client.try_retrieve_document(&other_clinic, &request_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.retrieve_document(&clinic, &request_id);
+    assert_eq!(
+        client.try_retrieve_document(&clinic, &request_id),
+        Err(Ok(Error::AccessDenied))
+    );
+    assert_eq!(
+        client.try_respond_to_request(&patient, &request_id, &false),
+        Err(Ok(Error::InvalidState))
+    );
+
+    assert_eq!(
+        client.try_get_audit_log(&clinic, &doc_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_get_audit_log(&patient, &99),
+        Err(Ok(Error::DocumentNotFound))
+    );
+}
+
+#[test]
+fn test_expired_policies() {
+    let (env, _, facility, patient, client) = setup();
+    let doc_id = register(&env, &client, &facility, &patient, symbol_short!("lab"));
+    let pcp = Address::generate(&env);
+
+    assert_eq!(
+        client.try_set_access_policy(
+            &patient,
+            &AccessPolicy {
+                grantee: pcp.clone(),
+                document_types: vec![&env],
+                expires_at: 1_000,
+            },
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    client.set_access_policy(
+        &patient,
+        &AccessPolicy {
+            grantee: pcp.clone(),
+            document_types: vec![&env],
+            expires_at: 2_000,
+        },
+    );
+    env.ledger().set_timestamp(2_000);
+    let request_id = client.request_document(&pcp, &doc_id, &symbol_short!("treat"));
+    assert_eq!(
+        client.get_request(&request_id).status,
+        RequestStatus::Pending
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, facility, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&facility), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&facility, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,136 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, BytesN, Symbol, Vec};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    FacilityNotRegistered = 4,
+    DocumentNotFound = 5,
+    RequestNotFound = 6,
+    AccessDenied = 7,
+    InvalidState = 8,
+    InvalidInput = 9,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RequestStatus {
+    /// Waiting on the patient.
+    Pending,
+    Approved,
+    Denied,
+    /// The requester has retrieved the document.
+    Fulfilled,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClinicalDocument {
+    pub document_id: u64,
+    pub patient_id: Address,
+    /// Facility (or, for integration documents, provider) that produced the document.
+    pub source: Address,
+    pub document_type: Symbol,
+    pub content_hash: BytesN<32>,
+    pub registered_at: u64,
+    /// Integration contract that registered the document, if any.
+    pub registered_via: Option<Address>,
+}
+
+/// Patient-managed standing consent. An empty `document_types` list covers
+/// every type; `expires_at` of 0 never expires.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AccessPolicy {
+    pub grantee: Address,
+    pub document_types: Vec<Symbol>,
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentRequest {
+    pub request_id: u64,
+    pub document_id: u64,
+    pub requester: Address,
+    pub purpose: Symbol,
+    pub status: RequestStatus,
+    pub requested_at: u64,
+    pub responded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEvent {
+    pub actor: Address,
+    pub action: Symbol,
+    pub request_id: u64,
+    pub timestamp: u64,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentRegistered {
+    #[topic]
+    pub document_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub document_type: Symbol,
+    pub source: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DocumentRequestUpdated {
+    #[topic]
+    pub request_id: u64,
+    pub document_id: u64,
+    pub status: RequestStatus,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// facility -> bool
+    Facility(Address),
+    /// integration contract -> bool
+    Integration(Address),
+    DocumentCounter,
+    /// document_id -> ClinicalDocument
+    Document(u64),
+    /// patient -> Vec<u64> (document ids)
+    PatientDocuments(Address),
+    /// patient -> Vec<AccessPolicy>
+    Policies(Address),
+    RequestCounter,
+    /// request_id -> DocumentRequest
+    Request(u64),
+    /// patient -> Vec<u64> (request ids awaiting a decision or history)
+    PatientRequests(Address),
+    /// document_id -> Vec<AuditEvent>
+    Audit(u64),
+}
@@ -1,10 +1,17 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
-    String, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, symbol_short, Address,
+    BytesN, Env, Map, String, Symbol, Vec,
 };
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Fewest outcomes a treatment needs in a cohort before its success rate
+/// is published, until the admin sets another threshold.
+const DEFAULT_MIN_OUTCOME_COUNT: u32 = 10;
+
 /// --------------------
 /// Data Structures
 /// --------------------
@@ -43,18 +50,244 @@ pub struct QualityMetricRecord {
     pub period: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MetricDirection {
+    HigherIsBetter,
+    LowerIsBetter,
+}
+
+/// Catalog entry for a quality metric, keyed by `name` (the `metric_name`
+/// providers report under).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricDefinition {
+    pub name: String,
+    pub description: String,
+    pub target: i128,
+    pub direction: MetricDirection,
+    pub steward: String,
+}
+
+/// One line of a provider scorecard. `gap` is the distance from target,
+/// positive when better than target. `percentile` (×100) is the share of
+/// all providers' results for the metric and period that this result is at
+/// least as good as, itself included.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricScore {
+    pub metric_name: String,
+    pub value: i128,
+    pub target: i128,
+    pub direction: MetricDirection,
+    pub met_target: bool,
+    pub gap: i128,
+    pub percentile: u32,
+}
+
+/// A provider's panel risk for a period under a registered case-mix
+/// methodology. 100 is an average panel; 150 is expected to have 1.5× the
+/// adverse outcomes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PanelRiskScore {
+    pub provider_id: Address,
+    pub period: u64,
+    pub methodology: Symbol,
+    pub risk_score_x100: u32,
+}
+
+/// Where a provider's result ranks among peers for a metric and period.
+/// Percentiles are ×10000, as in `MetricScore`. Without a methodology, or
+/// for providers with no panel score under it, the adjustment factor is
+/// 100 and the adjusted figures equal the raw ones.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BenchmarkResult {
+    pub metric_name: String,
+    pub period: u64,
+    pub value: i128,
+    pub raw_percentile: u32,
+    pub methodology: Option<Symbol>,
+    pub adjustment_factor_x100: u32,
+    pub adjusted_value: i128,
+    pub adjusted_percentile: u32,
+}
+
+/// Weighted metric in the facility quality index. The metric must be in
+/// the catalog so its value can be scored against target.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexComponent {
+    pub metric_name: String,
+    pub weight: u32,
+}
+
+/// A component's contribution to one facility index. `score` (×10000) is
+/// the value's achievement of target, capped at 10000.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComponentScore {
+    pub metric_name: String,
+    pub value: i128,
+    pub weight: u32,
+    pub score: u32,
+}
+
+/// Composite quality index (×10000) for a facility and period. Components
+/// without a reported value are listed in `missing` and the remaining
+/// weights are rescaled.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FacilityQualityIndex {
+    pub facility_id: Address,
+    pub period: u64,
+    pub index: u32,
+    pub components: Vec<ComponentScore>,
+    pub missing: Vec<String>,
+    pub computed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexPoint {
+    pub period: u64,
+    pub index: u32,
+}
+
+/// The facility's most recent index periods, oldest first. `change` is the
+/// last point minus the first.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IndexTrend {
+    pub facility_id: Address,
+    pub points: Vec<IndexPoint>,
+    pub change: i64,
+}
+
+/// Registry entry for a condition. `code` is an ICD-10-style code; the
+/// display name and aliases resolve to it when outcomes are recorded.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionCode {
+    pub code: String,
+    pub display: String,
+}
+
+/// An anonymized treatment outcome. `cohort` is a coarse grouping chosen
+/// by the reporter (e.g. an age band); no patient identifiers are stored.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeRecord {
+    pub id: u64,
+    pub condition_code: String,
+    pub treatment: String,
+    pub cohort: Symbol,
+    pub successful: bool,
+    pub timestamp: u64,
+}
+
+/// Outcomes for one treatment in the filtered cohort. `success_rate`
+/// (×10000) is `None` when fewer than the minimum outcome count were
+/// recorded, so small groups cannot be singled out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreatmentOutcome {
+    pub treatment: String,
+    pub total: u32,
+    pub success_rate: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PopulationStatistics {
+    pub condition_code: String,
+    pub display: String,
+    pub cohort: Option<Symbol>,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub total_outcomes: u32,
+    pub min_outcome_count: u32,
+    pub treatments: Vec<TreatmentOutcome>,
+}
+
+/// --------------------
+/// Events
+/// --------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MetricDefinitionSet {
+    pub name: String,
+    pub target: i128,
+    pub direction: MetricDirection,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityMetricRecorded {
+    #[topic]
+    pub provider_id: Address,
+    pub metric_name: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityIndexConfigured {
+    pub components: Vec<IndexComponent>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QualityIndexComputed {
+    #[topic]
+    pub facility_id: Address,
+    pub period: u64,
+    pub index: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConditionCodeRegistered {
+    #[topic]
+    pub code: String,
+    pub display: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AnonymizedOutcomeRecorded {
+    #[topic]
+    pub condition_code: String,
+    pub outcome_id: u64,
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
 
 #[contracttype]
 pub enum DataKey {
+    Admin,
     MetricCounter,
     Metric(u64),
     MetricsByType(Symbol),
     QualityMetricCounter,
     QualityMetric(u64),
     QualityMetricsByProvider(Address),
+    QualityMetricsByName(String),
+    MetricDefinition(String),
+    QualityIndexComponents,
+    MetricFeed(Address, String),
+    FacilityIndexHistory(Address),
+    RiskMethodology(Symbol),
+    PanelRiskScore(Address, u64, Symbol),
+    OutcomeCounter,
+    Outcome(u64),
+    OutcomesByCondition(String),
+    MinOutcomeCount,
+    ConditionCode(String),
+    ConditionAlias(String),
 }
 
 /// --------------------
@@ -69,6 +302,19 @@ pub enum Error {
     NoDataFound = 2,
     Unauthorized = 3,
     InvalidValue = 4,
+    AlreadyInitialized = 5,
+    NotInitialized = 6,
+    IndexNotConfigured = 7,
+    UnknownCondition = 8,
+}
+
+impl From<shared_access::AccessError> for Error {
+    fn from(err: shared_access::AccessError) -> Self {
+        match err {
+            shared_access::AccessError::NotInitialized => Error::NotInitialized,
+            _ => Error::Unauthorized,
+        }
+    }
 }
 
 #[contract]
@@ -76,6 +322,16 @@ pub struct HealthcareAnalytics;
 
 #[contractimpl]
 impl HealthcareAnalytics {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     /// Record an anonymized metric for population health analytics.
     /// Privacy is preserved by accepting only pre-anonymized, aggregate-ready
     /// values with an optional metadata hash instead of raw patient data.
@@ -205,77 +461,880 @@ impl HealthcareAnalytics {
         period: u64,
     ) -> Result<(), Error> {
         provider_id.require_auth();
+        store_quality_metric(&env, &provider_id, &metric_name, value, period);
+        Ok(())
+    }
 
-        let id = env
+    /// Retrieve quality metrics for a provider filtered by reporting period.
+    pub fn get_quality_metrics(
+        env: Env,
+        provider_id: Address,
+        period: u64,
+    ) -> Result<Vec<QualityMetricRecord>, Error> {
+        let ids: Vec<u64> = env
             .storage()
-            .instance()
-            .get::<DataKey, u64>(&DataKey::QualityMetricCounter)
-            .unwrap_or(0);
+            .persistent()
+            .get(&DataKey::QualityMetricsByProvider(provider_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut results: Vec<QualityMetricRecord> = Vec::new(&env);
+
+        for i in 0..ids.len() {
+            let id = ids.get(i).unwrap();
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get::<DataKey, QualityMetricRecord>(&DataKey::QualityMetric(id))
+            {
+                if record.period == period {
+                    results.push_back(record);
+                }
+            }
+        }
+
+        if results.is_empty() {
+            return Err(Error::NoDataFound);
+        }
+
+        Ok(results)
+    }
+
+    /// Add or replace a quality metric in the catalog (admin only).
+    pub fn set_metric_definition(
+        env: Env,
+        admin: Address,
+        definition: MetricDefinition,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if definition.name.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+
         env.storage()
-            .instance()
-            .set(&DataKey::QualityMetricCounter, &(id + 1));
+            .persistent()
+            .set(&DataKey::MetricDefinition(definition.name.clone()), &definition);
 
-        let record = QualityMetricRecord {
-            id,
+        MetricDefinitionSet {
+            name: definition.name,
+            target: definition.target,
+            direction: definition.direction,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_metric_definition(env: Env, name: String) -> Option<MetricDefinition> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MetricDefinition(name))
+    }
+
+    /// Score a provider's quality metrics for a period against each metric's
+    /// catalog target and direction. Metrics missing from the catalog are
+    /// left out.
+    pub fn get_provider_scorecard(
+        env: Env,
+        provider_id: Address,
+        period: u64,
+    ) -> Result<Vec<MetricScore>, Error> {
+        let metrics = Self::get_quality_metrics(env.clone(), provider_id, period)?;
+        let mut scorecard: Vec<MetricScore> = Vec::new(&env);
+
+        for record in metrics.iter() {
+            let key = DataKey::MetricDefinition(record.metric_name.clone());
+            let definition: MetricDefinition = match env.storage().persistent().get(&key) {
+                Some(definition) => definition,
+                None => continue,
+            };
+
+            let gap = match definition.direction {
+                MetricDirection::HigherIsBetter => record.value - definition.target,
+                MetricDirection::LowerIsBetter => definition.target - record.value,
+            };
+
+            scorecard.push_back(MetricScore {
+                metric_name: record.metric_name.clone(),
+                value: record.value,
+                target: definition.target,
+                direction: definition.direction,
+                met_target: gap >= 0,
+                gap,
+                percentile: peer_percentile(&env, &record, definition.direction),
+            });
+        }
+
+        Ok(scorecard)
+    }
+
+    /// Register a case-mix methodology providers may score their panels
+    /// under (admin only).
+    pub fn register_risk_methodology(
+        env: Env,
+        admin: Address,
+        methodology: Symbol,
+        description: String,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RiskMethodology(methodology), &description);
+        Ok(())
+    }
+
+    pub fn get_risk_methodology(env: Env, methodology: Symbol) -> Option<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RiskMethodology(methodology))
+    }
+
+    /// Record the provider's panel risk score for a period. Resubmitting
+    /// under the same methodology replaces the score.
+    pub fn submit_panel_risk_score(
+        env: Env,
+        provider_id: Address,
+        period: u64,
+        methodology: Symbol,
+        risk_score_x100: u32,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        if risk_score_x100 == 0
+            || Self::get_risk_methodology(env.clone(), methodology.clone()).is_none()
+        {
+            return Err(Error::InvalidValue);
+        }
+
+        let score = PanelRiskScore {
             provider_id: provider_id.clone(),
-            metric_name: metric_name.clone(),
-            value,
             period,
+            methodology: methodology.clone(),
+            risk_score_x100,
         };
+        env.storage().persistent().set(
+            &DataKey::PanelRiskScore(provider_id, period, methodology),
+            &score,
+        );
+        Ok(())
+    }
 
+    pub fn get_panel_risk_score(
+        env: Env,
+        provider_id: Address,
+        period: u64,
+        methodology: Symbol,
+    ) -> Option<PanelRiskScore> {
         env.storage()
             .persistent()
-            .set(&DataKey::QualityMetric(id), &record);
+            .get(&DataKey::PanelRiskScore(provider_id, period, methodology))
+    }
 
-        let mut ids: Vec<u64> = env
+    /// Rank the provider's latest result for a cataloged metric and period
+    /// against all peers, both raw and, when `methodology` is given,
+    /// adjusted for each provider's panel risk under it.
+    pub fn benchmark_performance(
+        env: Env,
+        provider_id: Address,
+        metric_name: String,
+        period: u64,
+        methodology: Option<Symbol>,
+    ) -> Result<BenchmarkResult, Error> {
+        let definition = Self::get_metric_definition(env.clone(), metric_name.clone())
+            .ok_or(Error::NoDataFound)?;
+        let ids: Vec<u64> = env
             .storage()
             .persistent()
             .get(&DataKey::QualityMetricsByProvider(provider_id.clone()))
             .unwrap_or(Vec::new(&env));
-        ids.push_back(id);
+        let value = latest_value(&env, &ids, &metric_name, period).ok_or(Error::NoDataFound)?;
+
+        let factor = adjustment_factor(&env, &provider_id, period, &methodology);
+        let adjusted_value = risk_adjust(value, factor, definition.direction);
+        let direction = definition.direction;
+
+        Ok(BenchmarkResult {
+            raw_percentile: benchmark_percentile(
+                &env,
+                &metric_name,
+                period,
+                value,
+                direction,
+                &None,
+            ),
+            adjusted_percentile: benchmark_percentile(
+                &env,
+                &metric_name,
+                period,
+                adjusted_value,
+                direction,
+                &methodology,
+            ),
+            metric_name,
+            period,
+            value,
+            methodology,
+            adjustment_factor_x100: factor,
+            adjusted_value,
+        })
+    }
+
+    /// Replace the components and weights of the facility quality index
+    /// (admin only). Every component must be cataloged with a positive
+    /// target.
+    pub fn set_quality_index_components(
+        env: Env,
+        admin: Address,
+        components: Vec<IndexComponent>,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if components.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+        for component in components.iter() {
+            let definition = Self::get_metric_definition(env.clone(), component.metric_name)
+                .ok_or(Error::InvalidValue)?;
+            if component.weight == 0 || definition.target <= 0 {
+                return Err(Error::InvalidValue);
+            }
+        }
+
         env.storage()
             .persistent()
-            .set(&DataKey::QualityMetricsByProvider(provider_id.clone()), &ids);
+            .set(&DataKey::QualityIndexComponents, &components);
 
-        env.events()
-            .publish((symbol_short!("rec_qm"), provider_id), metric_name);
+        QualityIndexConfigured { components }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_quality_index_components(env: Env) -> Vec<IndexComponent> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::QualityIndexComponents)
+            .unwrap_or(Vec::new(&env))
+    }
 
+    /// Let `feed` (typically another contract, e.g. a hand-hygiene monitor)
+    /// report `metric_name` for any facility (admin only).
+    pub fn authorize_metric_feed(
+        env: Env,
+        admin: Address,
+        feed: Address,
+        metric_name: String,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MetricFeed(feed, metric_name), &true);
         Ok(())
     }
 
-    /// Retrieve quality metrics for a provider filtered by reporting period.
-    pub fn get_quality_metrics(
+    /// Record a facility-level quality metric on behalf of the facility from
+    /// an authorized feed.
+    pub fn feed_facility_metric(
         env: Env,
-        provider_id: Address,
+        feed: Address,
+        facility_id: Address,
+        metric_name: String,
+        value: i128,
         period: u64,
-    ) -> Result<Vec<QualityMetricRecord>, Error> {
+    ) -> Result<(), Error> {
+        feed.require_auth();
+        let authorized: bool = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MetricFeed(feed, metric_name.clone()))
+            .unwrap_or(false);
+        if !authorized {
+            return Err(Error::Unauthorized);
+        }
+        store_quality_metric(&env, &facility_id, &metric_name, value, period);
+        Ok(())
+    }
+
+    /// Compute the facility's composite quality index for a period from its
+    /// reported quality metrics and store it in the facility's history,
+    /// replacing any earlier result for the same period.
+    pub fn compute_quality_index(
+        env: Env,
+        facility_id: Address,
+        period: u64,
+    ) -> Result<FacilityQualityIndex, Error> {
+        let components = Self::get_quality_index_components(env.clone());
+        if components.is_empty() {
+            return Err(Error::IndexNotConfigured);
+        }
+
         let ids: Vec<u64> = env
             .storage()
             .persistent()
-            .get(&DataKey::QualityMetricsByProvider(provider_id))
+            .get(&DataKey::QualityMetricsByProvider(facility_id.clone()))
             .unwrap_or(Vec::new(&env));
 
-        let mut results: Vec<QualityMetricRecord> = Vec::new(&env);
+        let mut scores: Vec<ComponentScore> = Vec::new(&env);
+        let mut missing: Vec<String> = Vec::new(&env);
+        let mut weighted: u64 = 0;
+        let mut total_weight: u64 = 0;
+        for component in components.iter() {
+            let definition =
+                match Self::get_metric_definition(env.clone(), component.metric_name.clone()) {
+                    Some(definition) => definition,
+                    None => {
+                        missing.push_back(component.metric_name);
+                        continue;
+                    }
+                };
+            let value = match latest_value(&env, &ids, &component.metric_name, period) {
+                Some(value) => value,
+                None => {
+                    missing.push_back(component.metric_name);
+                    continue;
+                }
+            };
 
-        for i in 0..ids.len() {
-            let id = ids.get(i).unwrap();
-            if let Some(record) = env
+            let score = achievement(value, &definition);
+            weighted += u64::from(score) * u64::from(component.weight);
+            total_weight += u64::from(component.weight);
+            scores.push_back(ComponentScore {
+                metric_name: component.metric_name,
+                value,
+                weight: component.weight,
+                score,
+            });
+        }
+
+        if total_weight == 0 {
+            return Err(Error::NoDataFound);
+        }
+
+        let result = FacilityQualityIndex {
+            facility_id: facility_id.clone(),
+            period,
+            index: (weighted / total_weight) as u32,
+            components: scores,
+            missing,
+            computed_at: env.ledger().timestamp(),
+        };
+
+        let key = DataKey::FacilityIndexHistory(facility_id.clone());
+        let mut history: Vec<FacilityQualityIndex> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let mut idx = history.len();
+        while idx > 0 && history.get(idx - 1).unwrap().period > period {
+            idx -= 1;
+        }
+        if idx > 0 && history.get(idx - 1).unwrap().period == period {
+            history.set(idx - 1, result.clone());
+        } else {
+            history.insert(idx, result.clone());
+        }
+        env.storage().persistent().set(&key, &history);
+
+        QualityIndexComputed {
+            facility_id,
+            period,
+            index: result.index,
+        }
+        .publish(&env);
+
+        Ok(result)
+    }
+
+    /// Every computed index for the facility, ordered by period.
+    pub fn get_quality_index_history(env: Env, facility_id: Address) -> Vec<FacilityQualityIndex> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FacilityIndexHistory(facility_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The facility's index over its last `periods` computed periods.
+    pub fn get_quality_index_trend(
+        env: Env,
+        facility_id: Address,
+        periods: u32,
+    ) -> Result<IndexTrend, Error> {
+        let history = Self::get_quality_index_history(env.clone(), facility_id.clone());
+        if history.is_empty() || periods == 0 {
+            return Err(Error::NoDataFound);
+        }
+
+        let start = history.len().saturating_sub(periods);
+        let mut points: Vec<IndexPoint> = Vec::new(&env);
+        for i in start..history.len() {
+            let entry = history.get(i).unwrap();
+            points.push_back(IndexPoint {
+                period: entry.period,
+                index: entry.index,
+            });
+        }
+
+        let first = points.first().unwrap().index;
+        let last = points.last().unwrap().index;
+        Ok(IndexTrend {
+            facility_id,
+            points,
+            change: i64::from(last) - i64::from(first),
+        })
+    }
+
+    /// Add or update a condition in the registry (admin only). The display
+    /// name and each alias become accepted spellings of `code`; an alias
+    /// already bound to another code is rejected.
+    pub fn register_condition_code(
+        env: Env,
+        admin: Address,
+        code: String,
+        display: String,
+        aliases: Vec<String>,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if code.is_empty() || display.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+
+        let mut names = aliases;
+        names.push_back(display.clone());
+        for name in names.iter() {
+            if name.is_empty()
+                || (name != code && Self::get_condition_code(env.clone(), name.clone()).is_some())
+            {
+                return Err(Error::InvalidValue);
+            }
+            let bound: Option<String> = env
                 .storage()
                 .persistent()
-                .get::<DataKey, QualityMetricRecord>(&DataKey::QualityMetric(id))
+                .get(&DataKey::ConditionAlias(name.clone()));
+            if bound.is_some_and(|bound| bound != code) {
+                return Err(Error::InvalidValue);
+            }
+        }
+
+        env.storage().persistent().set(
+            &DataKey::ConditionCode(code.clone()),
+            &ConditionCode {
+                code: code.clone(),
+                display: display.clone(),
+            },
+        );
+        for name in names.iter() {
+            env.storage()
+                .persistent()
+                .set(&DataKey::ConditionAlias(name), &code);
+        }
+
+        ConditionCodeRegistered { code, display }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_condition_code(env: Env, code: String) -> Option<ConditionCode> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ConditionCode(code))
+    }
+
+    /// Record an anonymized treatment outcome for population statistics.
+    /// `condition` may be a registered code, display name or alias and is
+    /// stored as its code. Requires reporter authorization.
+    pub fn record_anonymized_outcome(
+        env: Env,
+        reporter: Address,
+        condition: String,
+        treatment: String,
+        cohort: Symbol,
+        successful: bool,
+    ) -> Result<u64, Error> {
+        reporter.require_auth();
+        if treatment.is_empty() {
+            return Err(Error::InvalidValue);
+        }
+        let condition_code = resolve_condition(&env, &condition)?.code;
+
+        let id = env
+            .storage()
+            .instance()
+            .get::<DataKey, u64>(&DataKey::OutcomeCounter)
+            .unwrap_or(0);
+        env.storage()
+            .instance()
+            .set(&DataKey::OutcomeCounter, &(id + 1));
+
+        let record = OutcomeRecord {
+            id,
+            condition_code: condition_code.clone(),
+            treatment,
+            cohort,
+            successful,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Outcome(id), &record);
+
+        let key = DataKey::OutcomesByCondition(condition_code.clone());
+        let mut ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        ids.push_back(id);
+        env.storage().persistent().set(&key, &ids);
+
+        AnonymizedOutcomeRecorded {
+            condition_code,
+            outcome_id: id,
+        }
+        .publish(&env);
+
+        Ok(id)
+    }
+
+    /// Set the fewest outcomes a treatment needs before its success rate is
+    /// published (admin only).
+    pub fn set_min_outcome_count(env: Env, admin: Address, count: u32) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if count == 0 {
+            return Err(Error::InvalidValue);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MinOutcomeCount, &count);
+        Ok(())
+    }
+
+    pub fn get_min_outcome_count(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MinOutcomeCount)
+            .unwrap_or(DEFAULT_MIN_OUTCOME_COUNT)
+    }
+
+    /// Per-treatment success rates for a registered condition over outcomes
+    /// recorded within the time range, optionally limited to one cohort.
+    /// `condition` is resolved to its code like in `record_anonymized_outcome`.
+    /// Treatments are ordered by name.
+    pub fn get_population_statistics(
+        env: Env,
+        condition: String,
+        start_time: u64,
+        end_time: u64,
+        cohort: Option<Symbol>,
+    ) -> Result<PopulationStatistics, Error> {
+        if start_time > end_time {
+            return Err(Error::InvalidTimeRange);
+        }
+        let entry = resolve_condition(&env, &condition)?;
+
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::OutcomesByCondition(entry.code.clone()))
+            .unwrap_or(Vec::new(&env));
+
+        // treatment -> (total, successful)
+        let mut tallies: Map<String, (u32, u32)> = Map::new(&env);
+        let mut total_outcomes: u32 = 0;
+        for id in ids.iter() {
+            let record: OutcomeRecord = match env.storage().persistent().get(&DataKey::Outcome(id))
             {
-                if record.period == period {
-                    results.push_back(record);
+                Some(record) => record,
+                None => continue,
+            };
+            if record.timestamp < start_time || record.timestamp > end_time {
+                continue;
+            }
+            if let Some(ref wanted) = cohort {
+                if record.cohort != *wanted {
+                    continue;
                 }
             }
+
+            let (total, successful) = tallies.get(record.treatment.clone()).unwrap_or((0, 0));
+            tallies.set(
+                record.treatment,
+                (total + 1, successful + u32::from(record.successful)),
+            );
+            total_outcomes += 1;
         }
 
-        if results.is_empty() {
+        if total_outcomes == 0 {
             return Err(Error::NoDataFound);
         }
 
-        Ok(results)
+        let min_outcome_count = Self::get_min_outcome_count(env.clone());
+        let mut treatments: Vec<TreatmentOutcome> = Vec::new(&env);
+        for (treatment, (total, successful)) in tallies.iter() {
+            let success_rate = if total >= min_outcome_count {
+                Some((u64::from(successful) * 10000 / u64::from(total)) as u32)
+            } else {
+                None
+            };
+            treatments.push_back(TreatmentOutcome {
+                treatment,
+                total,
+                success_rate,
+            });
+        }
+
+        Ok(PopulationStatistics {
+            condition_code: entry.code,
+            display: entry.display,
+            cohort,
+            period_start: start_time,
+            period_end: end_time,
+            total_outcomes,
+            min_outcome_count,
+            treatments,
+        })
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+/// Look up a condition by code, display name or alias.
+fn resolve_condition(env: &Env, condition: &String) -> Result<ConditionCode, Error> {
+    let code = env
+        .storage()
+        .persistent()
+        .get(&DataKey::ConditionAlias(condition.clone()))
+        .unwrap_or(condition.clone());
+    env.storage()
+        .persistent()
+        .get(&DataKey::ConditionCode(code))
+        .ok_or(Error::UnknownCondition)
+}
+
+/// Store a quality metric and index it by provider and metric name.
+fn store_quality_metric(
+    env: &Env,
+    provider_id: &Address,
+    metric_name: &String,
+    value: i128,
+    period: u64,
+) {
+    let id = env
+        .storage()
+        .instance()
+        .get::<DataKey, u64>(&DataKey::QualityMetricCounter)
+        .unwrap_or(0);
+    env.storage()
+        .instance()
+        .set(&DataKey::QualityMetricCounter, &(id + 1));
+
+    let record = QualityMetricRecord {
+        id,
+        provider_id: provider_id.clone(),
+        metric_name: metric_name.clone(),
+        value,
+        period,
+    };
+
+    env.storage()
+        .persistent()
+        .set(&DataKey::QualityMetric(id), &record);
+
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::QualityMetricsByProvider(provider_id.clone()))
+        .unwrap_or(Vec::new(env));
+    ids.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::QualityMetricsByProvider(provider_id.clone()), &ids);
+
+    let mut by_name: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::QualityMetricsByName(metric_name.clone()))
+        .unwrap_or(Vec::new(env));
+    by_name.push_back(id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::QualityMetricsByName(metric_name.clone()), &by_name);
+
+    QualityMetricRecorded {
+        provider_id: provider_id.clone(),
+        metric_name: metric_name.clone(),
+    }
+    .publish(env);
+}
+
+/// The provider's panel risk (×100) under `methodology`, or 100 when there
+/// is no methodology or no score.
+fn adjustment_factor(
+    env: &Env,
+    provider_id: &Address,
+    period: u64,
+    methodology: &Option<Symbol>,
+) -> u32 {
+    methodology
+        .as_ref()
+        .and_then(|methodology| {
+            env.storage()
+                .persistent()
+                .get::<DataKey, PanelRiskScore>(&DataKey::PanelRiskScore(
+                    provider_id.clone(),
+                    period,
+                    methodology.clone(),
+                ))
+        })
+        .map(|score| score.risk_score_x100)
+        .unwrap_or(100)
+}
+
+/// Scale a result to an average-risk panel. Sicker panels are expected to
+/// do worse, so lower-is-better results are divided by the risk factor and
+/// higher-is-better results multiplied by it.
+fn risk_adjust(value: i128, factor_x100: u32, direction: MetricDirection) -> i128 {
+    let factor = i128::from(factor_x100);
+    match direction {
+        MetricDirection::LowerIsBetter => value * 100 / factor,
+        MetricDirection::HigherIsBetter => value * factor / 100,
+    }
+}
+
+/// Share (×10000) of providers whose latest result for the metric and
+/// period `value` is at least as good as. Each peer's result is adjusted by
+/// its own panel score under `methodology`, if one is given.
+fn benchmark_percentile(
+    env: &Env,
+    metric_name: &String,
+    period: u64,
+    value: i128,
+    direction: MetricDirection,
+    methodology: &Option<Symbol>,
+) -> u32 {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::QualityMetricsByName(metric_name.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut seen: Vec<Address> = Vec::new(env);
+    let mut total: u32 = 0;
+    let mut matched_or_beaten: u32 = 0;
+    let mut idx = ids.len();
+    while idx > 0 {
+        idx -= 1;
+        let peer = match env
+            .storage()
+            .persistent()
+            .get::<DataKey, QualityMetricRecord>(&DataKey::QualityMetric(ids.get(idx).unwrap()))
+        {
+            Some(peer) => peer,
+            None => continue,
+        };
+        if peer.period != period || seen.contains(&peer.provider_id) {
+            continue;
+        }
+        seen.push_back(peer.provider_id.clone());
+
+        let factor = adjustment_factor(env, &peer.provider_id, peer.period, methodology);
+        let peer_adjusted = risk_adjust(peer.value, factor, direction);
+        total += 1;
+        let at_least_as_good = match direction {
+            MetricDirection::HigherIsBetter => value >= peer_adjusted,
+            MetricDirection::LowerIsBetter => value <= peer_adjusted,
+        };
+        if at_least_as_good {
+            matched_or_beaten += 1;
+        }
+    }
+
+    if total == 0 {
+        return 0;
+    }
+    (u64::from(matched_or_beaten) * 10_000 / u64::from(total)) as u32
+}
+
+/// Achievement (×10000) of a value against its metric target, capped at
+/// 10000. For lower-is-better metrics a value of zero scores in full.
+fn achievement(value: i128, definition: &MetricDefinition) -> u32 {
+    let value = value.max(0);
+    let score = match definition.direction {
+        MetricDirection::HigherIsBetter => value * 10_000 / definition.target,
+        MetricDirection::LowerIsBetter => {
+            if value == 0 {
+                10_000
+            } else {
+                definition.target * 10_000 / value
+            }
+        }
+    };
+    score.min(10_000) as u32
+}
+
+/// The most recently recorded value of `metric_name` for `period` among
+/// the given quality metric ids.
+fn latest_value(env: &Env, ids: &Vec<u64>, metric_name: &String, period: u64) -> Option<i128> {
+    let mut idx = ids.len();
+    while idx > 0 {
+        idx -= 1;
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, QualityMetricRecord>(&DataKey::QualityMetric(ids.get(idx).unwrap()))
+        {
+            if record.period == period && record.metric_name == *metric_name {
+                return Some(record.value);
+            }
+        }
+    }
+    None
+}
+
+/// Share (×10000) of results for the record's metric and period that the
+/// record is at least as good as.
+fn peer_percentile(env: &Env, record: &QualityMetricRecord, direction: MetricDirection) -> u32 {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::QualityMetricsByName(record.metric_name.clone()))
+        .unwrap_or(Vec::new(env));
+
+    let mut total: u32 = 0;
+    let mut matched_or_beaten: u32 = 0;
+    for id in ids.iter() {
+        if let Some(peer) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, QualityMetricRecord>(&DataKey::QualityMetric(id))
+        {
+            if peer.period != record.period {
+                continue;
+            }
+            total += 1;
+            let at_least_as_good = match direction {
+                MetricDirection::HigherIsBetter => record.value >= peer.value,
+                MetricDirection::LowerIsBetter => record.value <= peer.value,
+            };
+            if at_least_as_good {
+                matched_or_beaten += 1;
+            }
+        }
+    }
+
+    if total == 0 {
+        return 0;
     }
+    (u64::from(matched_or_beaten) * 10_000 / u64::from(total)) as u32
 }
 
 #[cfg(test)]
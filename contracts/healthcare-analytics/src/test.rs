@@ -1,7 +1,11 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{symbol_short, testutils::Address as _, Address, BytesN, Env, String};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
 
 fn setup() -> (Env, HealthcareAnalyticsClient<'static>) {
     let env = Env::default();
@@ -715,3 +719,505 @@ fn test_time_series_support() {
     assert_eq!(all.count, 4);
     assert_eq!(all.average, 127);
 }
+
+// ========================
+// Metric catalog and scorecard tests
+// ========================
+
+fn definition(env: &Env, name: &str, target: i128, direction: MetricDirection) -> MetricDefinition {
+    MetricDefinition {
+        name: String::from_str(env, name),
+        description: String::from_str(env, "Test metric"),
+        target,
+        direction,
+        steward: String::from_str(env, "NQF"),
+    }
+}
+
+#[test]
+fn test_set_metric_definition_admin_only() {
+    let (env, client) = setup();
+
+    let admin = Address::generate(&env);
+    let infection = definition(&env, "Infection Rate", 200, MetricDirection::LowerIsBetter);
+
+    assert_eq!(
+        client.try_set_metric_definition(&admin, &infection),
+        Err(Ok(Error::NotInitialized))
+    );
+
+    client.initialize(&admin);
+    assert_eq!(client.try_initialize(&admin), Err(Ok(Error::AlreadyInitialized)));
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    assert_eq!(
+        client.try_set_metric_definition(&Address::generate(&env), &infection),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_upgrade(&Address::generate(&env), &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.set_metric_definition(&admin, &infection);
+    assert_eq!(
+        client.get_metric_definition(&String::from_str(&env, "Infection Rate")),
+        Some(infection)
+    );
+    assert_eq!(
+        client.get_metric_definition(&String::from_str(&env, "Mortality Rate")),
+        None
+    );
+}
+
+#[test]
+fn test_provider_scorecard_uses_target_and_direction() {
+    let (env, client) = setup();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_metric_definition(
+        &admin,
+        &definition(&env, "Infection Rate", 200, MetricDirection::LowerIsBetter),
+    );
+    client.set_metric_definition(
+        &admin,
+        &definition(&env, "Hand Hygiene", 9000, MetricDirection::HigherIsBetter),
+    );
+
+    let provider = Address::generate(&env);
+    let peer = Address::generate(&env);
+    let infection = String::from_str(&env, "Infection Rate");
+    let hygiene = String::from_str(&env, "Hand Hygiene");
+
+    client.record_quality_metric(&provider, &infection, &150, &202401);
+    client.record_quality_metric(&provider, &hygiene, &8500, &202401);
+    client.record_quality_metric(&provider, &String::from_str(&env, "Uncataloged"), &1, &202401);
+    client.record_quality_metric(&peer, &infection, &300, &202401);
+    client.record_quality_metric(&peer, &hygiene, &9500, &202401);
+    // Other periods are not peers.
+    client.record_quality_metric(&peer, &infection, &100, &202402);
+
+    let scorecard = client.get_provider_scorecard(&provider, &202401);
+    assert_eq!(scorecard.len(), 2);
+
+    let infection_score = scorecard.get(0).unwrap();
+    assert_eq!(infection_score.metric_name, infection);
+    assert_eq!(infection_score.target, 200);
+    assert!(infection_score.met_target);
+    assert_eq!(infection_score.gap, 50);
+    assert_eq!(infection_score.percentile, 10_000);
+
+    let hygiene_score = scorecard.get(1).unwrap();
+    assert!(!hygiene_score.met_target);
+    assert_eq!(hygiene_score.gap, -500);
+    assert_eq!(hygiene_score.percentile, 5_000);
+
+    assert_eq!(
+        client.try_get_provider_scorecard(&provider, &202403),
+        Err(Ok(Error::NoDataFound))
+    );
+}
+
+fn component(env: &Env, name: &str, weight: u32) -> IndexComponent {
+    IndexComponent {
+        metric_name: String::from_str(env, name),
+        weight,
+    }
+}
+
+#[test]
+fn test_facility_quality_index_history_and_trend() {
+    let (env, client) = setup();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_metric_definition(
+        &admin,
+        &definition(
+            &env,
+            "Readmission Rate",
+            1500,
+            MetricDirection::LowerIsBetter,
+        ),
+    );
+    client.set_metric_definition(
+        &admin,
+        &definition(&env, "Satisfaction", 8000, MetricDirection::HigherIsBetter),
+    );
+    client.set_metric_definition(
+        &admin,
+        &definition(&env, "Hand Hygiene", 9000, MetricDirection::HigherIsBetter),
+    );
+
+    let facility = Address::generate(&env);
+    assert_eq!(
+        client.try_compute_quality_index(&facility, &202401),
+        Err(Ok(Error::IndexNotConfigured))
+    );
+
+    // Components must be cataloged and weighted
+    let uncataloged = Vec::from_array(&env, [component(&env, "Mortality", 1)]);
+    assert_eq!(
+        client.try_set_quality_index_components(&admin, &uncataloged),
+        Err(Ok(Error::InvalidValue))
+    );
+    let components = Vec::from_array(
+        &env,
+        [
+            component(&env, "Readmission Rate", 2),
+            component(&env, "Satisfaction", 1),
+            component(&env, "Hand Hygiene", 1),
+        ],
+    );
+    client.set_quality_index_components(&admin, &components);
+    assert_eq!(client.get_quality_index_components(), components);
+
+    let readmission = String::from_str(&env, "Readmission Rate");
+    let satisfaction = String::from_str(&env, "Satisfaction");
+    let hygiene = String::from_str(&env, "Hand Hygiene");
+
+    // Hand hygiene arrives from a monitoring contract
+    let feed = Address::generate(&env);
+    assert_eq!(
+        client.try_feed_facility_metric(&feed, &facility, &hygiene, &8100, &202401),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.authorize_metric_feed(&admin, &feed, &hygiene);
+    client.feed_facility_metric(&feed, &facility, &hygiene, &8100, &202401);
+    assert_eq!(
+        client.try_feed_facility_metric(&feed, &facility, &satisfaction, &9000, &202401),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.record_quality_metric(&facility, &readmission, &2000, &202401);
+    client.record_quality_metric(&facility, &satisfaction, &8000, &202401);
+
+    // Readmission 1500/2000 = 7500, satisfaction 10000, hygiene 8100/9000 = 9000
+    let january = client.compute_quality_index(&facility, &202401);
+    assert_eq!(january.index, (7500 * 2 + 10_000 + 9000) / 4);
+    assert_eq!(january.components.len(), 3);
+    assert!(january.missing.is_empty());
+
+    // A period missing hand hygiene rescales the remaining weights
+    client.record_quality_metric(&facility, &readmission, &1000, &202402);
+    client.record_quality_metric(&facility, &satisfaction, &7200, &202402);
+    let february = client.compute_quality_index(&facility, &202402);
+    assert_eq!(february.index, (10_000 * 2 + 9000) / 3);
+    assert_eq!(february.missing, Vec::from_array(&env, [hygiene.clone()]));
+
+    // Recomputing a period replaces its entry
+    client.feed_facility_metric(&feed, &facility, &hygiene, &9000, &202402);
+    let february = client.compute_quality_index(&facility, &202402);
+    assert_eq!(february.index, (10_000 * 2 + 9000 + 10_000) / 4);
+
+    let history = client.get_quality_index_history(&facility);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap(), february);
+
+    let trend = client.get_quality_index_trend(&facility, &5);
+    assert_eq!(trend.points.len(), 2);
+    assert_eq!(
+        trend.change,
+        i64::from(february.index) - i64::from(january.index)
+    );
+    let latest = client.get_quality_index_trend(&facility, &1);
+    assert_eq!(latest.points.len(), 1);
+    assert_eq!(latest.change, 0);
+
+    assert_eq!(
+        client.try_compute_quality_index(&facility, &202403),
+        Err(Ok(Error::NoDataFound))
+    );
+    assert_eq!(
+        client.try_get_quality_index_trend(&Address::generate(&env), &3),
+        Err(Ok(Error::NoDataFound))
+    );
+}
+
+#[test]
+fn test_benchmark_performance_with_case_mix_adjustment() {
+    let (env, client) = setup();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_metric_definition(
+        &admin,
+        &definition(&env, "Mortality", 300, MetricDirection::LowerIsBetter),
+    );
+    let mortality = String::from_str(&env, "Mortality");
+    let hcc = symbol_short!("hcc");
+
+    let sick_panel = Address::generate(&env);
+    let healthy_panel = Address::generate(&env);
+    let average_panel = Address::generate(&env);
+    client.record_quality_metric(&sick_panel, &mortality, &400, &202401);
+    client.record_quality_metric(&healthy_panel, &mortality, &250, &202401);
+    client.record_quality_metric(&average_panel, &mortality, &300, &202401);
+
+    // Only registered methodologies and positive scores are accepted
+    assert_eq!(
+        client.try_submit_panel_risk_score(&sick_panel, &202401, &hcc, &200),
+        Err(Ok(Error::InvalidValue))
+    );
+    client.register_risk_methodology(&admin, &hcc, &String::from_str(&env, "HCC v28"));
+    assert_eq!(
+        client.try_submit_panel_risk_score(&sick_panel, &202401, &hcc, &0),
+        Err(Ok(Error::InvalidValue))
+    );
+    client.submit_panel_risk_score(&sick_panel, &202401, &hcc, &200);
+    client.submit_panel_risk_score(&healthy_panel, &202401, &hcc, &50);
+
+    // Raw: the sick panel ranks last
+    let raw = client.benchmark_performance(&sick_panel, &mortality, &202401, &None);
+    assert_eq!(raw.raw_percentile, 3_333);
+    assert_eq!(raw.adjustment_factor_x100, 100);
+    assert_eq!(raw.adjusted_value, 400);
+    assert_eq!(raw.adjusted_percentile, raw.raw_percentile);
+
+    // Adjusted: 400 / 2.0 = 200 beats 250 / 0.5 = 500 and the unadjusted 300
+    let adjusted =
+        client.benchmark_performance(&sick_panel, &mortality, &202401, &Some(hcc.clone()));
+    assert_eq!(adjusted.raw_percentile, 3_333);
+    assert_eq!(adjusted.methodology, Some(hcc.clone()));
+    assert_eq!(adjusted.adjustment_factor_x100, 200);
+    assert_eq!(adjusted.adjusted_value, 200);
+    assert_eq!(adjusted.adjusted_percentile, 10_000);
+
+    let healthy = client.benchmark_performance(&healthy_panel, &mortality, &202401, &Some(hcc));
+    assert_eq!(healthy.raw_percentile, 10_000);
+    assert_eq!(healthy.adjusted_value, 500);
+    assert_eq!(healthy.adjusted_percentile, 3_333);
+
+    assert_eq!(
+        client.try_benchmark_performance(&sick_panel, &mortality, &202402, &None),
+        Err(Ok(Error::NoDataFound))
+    );
+    assert_eq!(
+        client.try_benchmark_performance(
+            &sick_panel,
+            &String::from_str(&env, "Uncataloged"),
+            &202401,
+            &None
+        ),
+        Err(Ok(Error::NoDataFound))
+    );
+}
+
+// ========================
+// Population statistics tests
+// ========================
+
+fn record_outcomes(
+    env: &Env,
+    client: &HealthcareAnalyticsClient,
+    condition: &String,
+    treatment: &str,
+    cohort: &str,
+    successful: u32,
+    failed: u32,
+) {
+    let reporter = Address::generate(env);
+    let treatment = String::from_str(env, treatment);
+    let cohort = Symbol::new(env, cohort);
+    for i in 0..successful + failed {
+        client.record_anonymized_outcome(
+            &reporter,
+            condition,
+            &treatment,
+            &cohort,
+            &(i < successful),
+        );
+    }
+}
+
+fn register_conditions(env: &Env, client: &HealthcareAnalyticsClient, admin: &Address) {
+    client.register_condition_code(
+        admin,
+        &String::from_str(env, "I50"),
+        &String::from_str(env, "Heart failure"),
+        &vec![
+            env,
+            String::from_str(env, "CHF"),
+            String::from_str(env, "Heart Failure"),
+        ],
+    );
+    client.register_condition_code(
+        admin,
+        &String::from_str(env, "J45"),
+        &String::from_str(env, "Asthma"),
+        &Vec::new(env),
+    );
+}
+
+#[test]
+fn test_population_statistics_success_rate_per_treatment() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_conditions(&env, &client, &admin);
+    env.ledger().set_timestamp(1000);
+
+    let condition = String::from_str(&env, "I50");
+    let asthma = String::from_str(&env, "J45");
+    record_outcomes(&env, &client, &condition, "ACE inhibitor", "adult", 9, 3);
+    record_outcomes(&env, &client, &condition, "Beta blocker", "adult", 5, 5);
+    record_outcomes(&env, &client, &condition, "Beta blocker", "senior", 10, 0);
+    record_outcomes(&env, &client, &asthma, "Inhaled steroid", "adult", 12, 0);
+
+    let stats = client.get_population_statistics(&condition, &0, &2000, &None);
+    assert_eq!(stats.condition_code, condition);
+    assert_eq!(stats.display, String::from_str(&env, "Heart failure"));
+    assert_eq!(stats.total_outcomes, 32);
+    assert_eq!(stats.min_outcome_count, 10);
+    assert_eq!(stats.treatments.len(), 2);
+
+    let ace = stats.treatments.get(0).unwrap();
+    assert_eq!(ace.treatment, String::from_str(&env, "ACE inhibitor"));
+    assert_eq!(ace.total, 12);
+    assert_eq!(ace.success_rate, Some(7500));
+
+    let beta = stats.treatments.get(1).unwrap();
+    assert_eq!(beta.total, 20);
+    assert_eq!(beta.success_rate, Some(7500));
+
+    let adults =
+        client.get_population_statistics(&condition, &0, &2000, &Some(symbol_short!("adult")));
+    assert_eq!(adults.total_outcomes, 22);
+    let beta = adults.treatments.get(1).unwrap();
+    assert_eq!(beta.total, 10);
+    assert_eq!(beta.success_rate, Some(5000));
+
+    assert_eq!(
+        client.try_get_population_statistics(&condition, &1001, &2000, &None),
+        Err(Ok(Error::NoDataFound))
+    );
+    assert_eq!(
+        client.try_get_population_statistics(&condition, &2000, &0, &None),
+        Err(Ok(Error::InvalidTimeRange))
+    );
+}
+
+#[test]
+fn test_population_statistics_suppresses_small_groups() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_conditions(&env, &client, &admin);
+
+    let condition = String::from_str(&env, "J45");
+    record_outcomes(&env, &client, &condition, "Inhaled steroid", "child", 3, 1);
+
+    let stats = client.get_population_statistics(&condition, &0, &u64::MAX, &None);
+    let steroid = stats.treatments.get(0).unwrap();
+    assert_eq!(steroid.total, 4);
+    assert_eq!(steroid.success_rate, None);
+
+    assert_eq!(
+        client.try_set_min_outcome_count(&Address::generate(&env), &4),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_min_outcome_count(&admin, &0),
+        Err(Ok(Error::InvalidValue))
+    );
+    client.set_min_outcome_count(&admin, &4);
+
+    let stats = client.get_population_statistics(&condition, &0, &u64::MAX, &None);
+    assert_eq!(stats.treatments.get(0).unwrap().success_rate, Some(7500));
+}
+
+#[test]
+fn test_condition_names_normalize_to_registered_code() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_conditions(&env, &client, &admin);
+    let reporter = Address::generate(&env);
+    let treatment = String::from_str(&env, "Diuretic");
+
+    for name in ["I50", "CHF", "Heart failure", "Heart Failure"] {
+        client.record_anonymized_outcome(
+            &reporter,
+            &String::from_str(&env, name),
+            &treatment,
+            &symbol_short!("adult"),
+            &true,
+        );
+    }
+    assert_eq!(
+        client.try_record_anonymized_outcome(
+            &reporter,
+            &String::from_str(&env, "heart trouble"),
+            &treatment,
+            &symbol_short!("adult"),
+            &true,
+        ),
+        Err(Ok(Error::UnknownCondition))
+    );
+
+    let stats =
+        client.get_population_statistics(&String::from_str(&env, "CHF"), &0, &u64::MAX, &None);
+    assert_eq!(stats.condition_code, String::from_str(&env, "I50"));
+    assert_eq!(stats.total_outcomes, 4);
+    assert_eq!(
+        client.try_get_population_statistics(
+            &String::from_str(&env, "E11"),
+            &0,
+            &u64::MAX,
+            &None
+        ),
+        Err(Ok(Error::UnknownCondition))
+    );
+}
+
+#[test]
+fn test_register_condition_code_validation() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_conditions(&env, &client, &admin);
+
+    let code = String::from_str(&env, "E11");
+    let display = String::from_str(&env, "Type 2 diabetes");
+    assert_eq!(
+        client.try_register_condition_code(
+            &Address::generate(&env),
+            &code,
+            &display,
+            &Vec::new(&env)
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    // An alias may not point at another code or shadow one.
+    assert_eq!(
+        client.try_register_condition_code(
+            &admin,
+            &code,
+            &display,
+            &vec![&env, String::from_str(&env, "CHF")]
+        ),
+        Err(Ok(Error::InvalidValue))
+    );
+    assert_eq!(
+        client.try_register_condition_code(
+            &admin,
+            &code,
+            &display,
+            &vec![&env, String::from_str(&env, "J45")]
+        ),
+        Err(Ok(Error::InvalidValue))
+    );
+
+    client.register_condition_code(&admin, &code, &display, &Vec::new(&env));
+    assert_eq!(
+        client.get_condition_code(&code),
+        Some(ConditionCode {
+            code: code.clone(),
+            display
+        })
+    );
+    // Re-registering updates the display name.
+    let renamed = String::from_str(&env, "Diabetes mellitus type 2");
+    client.register_condition_code(&admin, &code, &renamed, &Vec::new(&env));
+    assert_eq!(client.get_condition_code(&code).unwrap().display, renamed);
+}
@@ -0,0 +1,195 @@
+use soroban_sdk::{contractevent, Address, String, Symbol, Vec};
+
+// Every state-changing entrypoint publishes one of these. The first topic is
+// the record id where one exists, otherwise the provider.
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialingInitiated {
+    #[topic]
+    pub case_id: u64,
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialDocumentSubmitted {
+    #[topic]
+    pub case_id: u64,
+    pub document_type: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AcceptedMethodsSet {
+    #[topic]
+    pub credential_type: Symbol,
+    pub methods: Vec<Symbol>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierAuthorized {
+    #[topic]
+    pub verifier: Address,
+    pub credential_type: Symbol,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierRevoked {
+    #[topic]
+    pub verifier: Address,
+    pub credential_type: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitteeMemberAuthorized {
+    #[topic]
+    pub member: Address,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitteeMemberRevoked {
+    #[topic]
+    pub member: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CredentialVerified {
+    #[topic]
+    pub case_id: u64,
+    pub credential_type: Symbol,
+    pub verifier: Address,
+    pub verification_result: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SanctionsChecked {
+    #[topic]
+    pub case_id: u64,
+    pub checker: Address,
+    pub sanctions_found: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SanctionDeterminationRecorded {
+    #[topic]
+    pub case_id: u64,
+    pub committee: Address,
+    pub disqualifying: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PeerReferenceRecorded {
+    #[topic]
+    pub case_id: u64,
+    pub reference_provider: Address,
+    pub recommended: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivilegesGranted {
+    #[topic]
+    pub case_id: u64,
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub privilege_count: u32,
+    pub expiration_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProvisionalPrivilegesRequested {
+    #[topic]
+    pub request_id: u64,
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub privilege_category: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClinicalActivityTracked {
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub outcome: Symbol,
+    pub complications: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeThresholdSet {
+    #[topic]
+    pub facility_id: Address,
+    pub procedure_prefix: String,
+    pub max_complication_rate_x100: u32,
+    pub min_case_volume: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FocusedReviewTriggered {
+    #[topic]
+    pub review_id: u64,
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub trigger_reason: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecredentialingScheduled {
+    #[topic]
+    pub schedule_id: u64,
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub due_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivilegesSuspended {
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub suspension_authority: Address,
+    pub is_immediate: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrivilegesReinstated {
+    #[topic]
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub reinstatement_authority: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpirationDigestGenerated {
+    #[topic]
+    pub digest_id: u64,
+    #[topic]
+    pub facility_id: Address,
+    pub horizon_end: u64,
+    pub privileges_expiring: u32,
+    pub documents_expiring: u32,
+}
@@ -2,11 +2,28 @@
 #![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, BytesN, Env,
+    IntoVal, String, Symbol, TryFromVal, Val, Vec,
 };
 
+mod events;
+
+pub use events::*;
+
 const REQUIRED_CREDENTIALS: u32 = 5;
 
+/// Leading characters of a procedure code that OPPE statistics are grouped
+/// by, e.g. the CPT section "332" for pacemaker procedures.
+const PROCEDURE_PREFIX_LEN: u32 = 3;
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Shared-access role held by credentialing committee members, who rule on
+/// sanction reviews
+const COMMITTEE_ROLE: Symbol = symbol_short!("committee");
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -21,6 +38,20 @@ pub enum Error {
     PrivilegeNotFound = 8,
     AlreadySuspended = 9,
     NotSuspended = 10,
+    NotInitialized = 11,
+    AlreadyInitialized = 12,
+    VerificationMethodNotAccepted = 13,
+    VerifierNotQualified = 14,
+    DigestNotFound = 15,
+}
+
+impl From<shared_access::AccessError> for Error {
+    fn from(err: shared_access::AccessError) -> Self {
+        match err {
+            shared_access::AccessError::NotInitialized => Error::NotInitialized,
+            _ => Error::NotAuthorized,
+        }
+    }
 }
 
 #[contracttype]
@@ -32,6 +63,9 @@ pub enum CredentialingStatus {
     Approved,
     Denied,
     DeferredForMoreInfo,
+    /// A sanctions check found a hit; only a committee determination moves
+    /// the case on.
+    SanctionReview,
 }
 
 #[contracttype]
@@ -78,6 +112,7 @@ pub struct CredentialDocument {
     pub issuing_authority: String,
     pub issue_date: u64,
     pub expiration_date: Option<u64>,
+    pub submitted_at: u64,
 }
 
 #[contracttype]
@@ -107,6 +142,45 @@ pub struct PeerReferenceRecord {
     pub competency_ratings: Vec<CompetencyRating>,
     pub reference_notes_hash: BytesN<32>,
     pub recommended: bool,
+    pub recorded_at: u64,
+}
+
+/// Committee ruling on a sanctions hit. `rationale_hash` points at the
+/// off-chain minutes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SanctionDetermination {
+    pub committee: Address,
+    pub disqualifying: bool,
+    pub rationale_hash: BytesN<32>,
+    pub determined_at: u64,
+}
+
+/// A case status transition, recorded when it happens. The first entry of
+/// a case's history is its initial status.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusChange {
+    pub status: CredentialingStatus,
+    pub changed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum CaseTimelineEvent {
+    StatusChanged(StatusChange),
+    DocumentSubmitted(CredentialDocument),
+    CredentialVerified(VerificationRecord),
+    SanctionsChecked(SanctionCheckRecord),
+    PeerReference(PeerReferenceRecord),
+    SanctionDetermination(SanctionDetermination),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseTimelineEntry {
+    pub timestamp: u64,
+    pub event: CaseTimelineEvent,
 }
 
 #[contracttype]
@@ -129,6 +203,41 @@ pub struct ClinicalActivityRecord {
     pub activity_date: u64,
 }
 
+/// Facility limit on the complication rate for one procedure-code prefix.
+/// Rates are percentages ×100, so 250 is 2.5%.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeThreshold {
+    pub max_complication_rate_x100: u32,
+    pub min_case_volume: u32,
+}
+
+/// Case volume and complications for one provider, facility, procedure-code
+/// prefix and calendar quarter (encoded as `year * 10 + quarter`).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ActivityStats {
+    pub procedure_prefix: String,
+    pub quarter: u32,
+    pub case_volume: u32,
+    pub complications: u32,
+    pub complication_rate_x100: u32,
+    pub focused_review_id: Option<u64>,
+}
+
+/// Running verification totals for a verifier, overall or at one facility.
+/// Turnaround runs from the latest submission of the document to the
+/// verification date.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VerifierStats {
+    pub verifier: Address,
+    pub verified_count: u32,
+    pub failed_count: u32,
+    pub total_turnaround_seconds: u64,
+    pub avg_turnaround_days_x100: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct FocusedReviewRecord {
@@ -168,9 +277,44 @@ pub struct ReinstatementRecord {
     pub monitoring_requirements: Vec<String>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiringPrivilege {
+    pub privilege_id: u64,
+    pub provider_id: Address,
+    pub privilege_category: Symbol,
+    pub expiration_date: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpiringDocument {
+    pub case_id: u64,
+    pub provider_id: Address,
+    pub document_type: Symbol,
+    pub expiration_date: u64,
+}
+
+/// Privileges and credential documents at a facility that expire on or
+/// before `horizon_end`, including any that have already lapsed.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExpirationDigest {
+    pub digest_id: u64,
+    pub facility_id: Address,
+    pub generated_at: u64,
+    pub horizon_end: u64,
+    pub privileges_expiring: u32,
+    pub documents_expiring: u32,
+    pub privileges: Vec<ExpiringPrivilege>,
+    pub documents: Vec<ExpiringDocument>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    Admin,
+    AcceptedVerificationMethods(Symbol),
     CaseCounter,
     Case(u64),
     ProviderFacilityCase(Address, Address),
@@ -178,12 +322,17 @@ pub enum DataKey {
     CaseVerifications(u64),
     CaseSanctions(u64),
     CasePeerReferences(u64),
+    CaseStatusHistory(u64),
+    CaseSanctionDeterminations(u64),
     PrivilegeCounter,
     ProviderFacilityPrivileges(Address, Address),
     ProvisionalCounter,
     ProvisionalRequest(u64),
     ProviderFacilityProvisional(Address, Address),
     ProviderFacilityActivities(Address, Address),
+    ProviderFacilityActivityStats(Address, Address, String, u32),
+    ProviderFacilityStatPrefixes(Address, Address, u32),
+    FacilityOutcomeThreshold(Address, String),
     FocusedReviewCounter,
     FocusedReview(u64),
     RecredentialingCounter,
@@ -191,6 +340,13 @@ pub enum DataKey {
     ProviderFacilityRecredentialings(Address, Address),
     ProviderFacilitySuspensions(Address, Address),
     ProviderFacilityReinstatements(Address, Address),
+    FacilityCases(Address),
+    VerifierStats(Address),
+    FacilityVerifierStats(Address, Address),
+    FacilityVerifiers(Address),
+    DigestCounter,
+    ExpirationDigest(u64),
+    FacilityDigests(Address),
 }
 
 #[contract]
@@ -198,6 +354,125 @@ pub struct HealthcareCredentialingSystem;
 
 #[contractimpl]
 impl HealthcareCredentialingSystem {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Replace the verification methods accepted for a credential type,
+    /// e.g. `primary_source`, `ama_profile` or `npdb_query`. Verifications
+    /// using any other method are rejected.
+    pub fn set_accepted_methods(
+        env: Env,
+        admin: Address,
+        credential_type: Symbol,
+        methods: Vec<Symbol>,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if !is_supported_credential_type(&env, &credential_type) {
+            return Err(Error::InvalidCredentialType);
+        }
+        if methods.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+        env.storage().persistent().set(
+            &DataKey::AcceptedVerificationMethods(credential_type.clone()),
+            &methods,
+        );
+
+        AcceptedMethodsSet {
+            credential_type,
+            methods,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_accepted_methods(env: Env, credential_type: Symbol) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AcceptedVerificationMethods(credential_type))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Allow `verifier` to verify credentials of `credential_type` until
+    /// `expires_at`, or indefinitely when it is `None`. The tag is stored
+    /// as a shared-access role named after the credential type.
+    pub fn authorize_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+        credential_type: Symbol,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        if !is_supported_credential_type(&env, &credential_type) {
+            return Err(Error::InvalidCredentialType);
+        }
+        shared_access::grant_role(&env, &credential_type, &verifier, &admin, expires_at);
+
+        VerifierAuthorized {
+            verifier,
+            credential_type,
+            expires_at,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn revoke_verifier(
+        env: Env,
+        admin: Address,
+        verifier: Address,
+        credential_type: Symbol,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_access::revoke_role(&env, &credential_type, &verifier);
+
+        VerifierRevoked {
+            verifier,
+            credential_type,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn can_verify(env: Env, verifier: Address, credential_type: Symbol) -> bool {
+        shared_access::has_role(&env, &credential_type, &verifier)
+    }
+
+    /// Seat `member` on the credentialing committee until `expires_at`, or
+    /// indefinitely when it is `None`.
+    pub fn authorize_committee_member(
+        env: Env,
+        admin: Address,
+        member: Address,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_access::grant_role(&env, &COMMITTEE_ROLE, &member, &admin, expires_at);
+
+        CommitteeMemberAuthorized { member, expires_at }.publish(&env);
+        Ok(())
+    }
+
+    pub fn revoke_committee_member(env: Env, admin: Address, member: Address) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_access::revoke_role(&env, &COMMITTEE_ROLE, &member);
+
+        CommitteeMemberRevoked { member }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_committee_member(env: Env, member: Address) -> bool {
+        shared_access::has_role(&env, &COMMITTEE_ROLE, &member)
+    }
+
     pub fn initiate_credentialing(
         env: Env,
         provider_id: Address,
@@ -232,13 +507,26 @@ impl HealthcareCredentialingSystem {
             verifications_required: REQUIRED_CREDENTIALS,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Case(case_id), &case);
+        save_case(&env, case_id, &case);
+        record_status_change(&env, case_id, CredentialingStatus::Incomplete);
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityCase(provider_id, facility_id),
+            &DataKey::ProviderFacilityCase(provider_id.clone(), facility_id.clone()),
             &case_id,
         );
+        let mut facility_cases: Vec<u64> =
+            load_case_list(&env, DataKey::FacilityCases(facility_id.clone()));
+        facility_cases.push_back(case_id);
+        env.storage().persistent().set(
+            &DataKey::FacilityCases(facility_id.clone()),
+            &facility_cases,
+        );
+
+        CredentialingInitiated {
+            case_id,
+            provider_id,
+            facility_id,
+        }
+        .publish(&env);
 
         Ok(case_id)
     }
@@ -264,20 +552,25 @@ impl HealthcareCredentialingSystem {
             .unwrap_or(Vec::new(&env));
 
         docs.push_back(CredentialDocument {
-            document_type,
+            document_type: document_type.clone(),
             document_hash,
             issuing_authority,
             issue_date,
             expiration_date,
+            submitted_at: env.ledger().timestamp(),
         });
 
-        case.status = CredentialingStatus::InReview;
+        set_status(&env, &mut case, CredentialingStatus::InReview);
         env.storage()
             .persistent()
             .set(&DataKey::CaseDocuments(case_id), &docs);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Case(case_id), &case);
+        save_case(&env, case_id, &case);
+
+        CredentialDocumentSubmitted {
+            case_id,
+            document_type,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -299,8 +592,14 @@ impl HealthcareCredentialingSystem {
             .persistent()
             .get(&DataKey::CaseDocuments(case_id))
             .unwrap_or(Vec::new(&env));
-        if !document_exists_for_type(&docs, &credential_type) {
-            return Err(Error::DocumentNotFound);
+        let submitted_at =
+            latest_submission(&docs, &credential_type).ok_or(Error::DocumentNotFound)?;
+        let accepted = Self::get_accepted_methods(env.clone(), credential_type.clone());
+        if !accepted.contains(&verification_method) {
+            return Err(Error::VerificationMethodNotAccepted);
+        }
+        if !shared_access::has_role(&env, &credential_type, &verifier) {
+            return Err(Error::VerifierNotQualified);
         }
 
         let mut records: Vec<VerificationRecord> = env
@@ -309,8 +608,8 @@ impl HealthcareCredentialingSystem {
             .get(&DataKey::CaseVerifications(case_id))
             .unwrap_or(Vec::new(&env));
         records.push_back(VerificationRecord {
-            credential_type,
-            verifier,
+            credential_type: credential_type.clone(),
+            verifier: verifier.clone(),
             verification_method,
             verification_result,
             verification_date,
@@ -320,22 +619,52 @@ impl HealthcareCredentialingSystem {
             .persistent()
             .set(&DataKey::CaseVerifications(case_id), &records);
 
+        let turnaround = verification_date.saturating_sub(submitted_at);
+        record_verifier_stats(
+            &env,
+            DataKey::VerifierStats(verifier.clone()),
+            &verifier,
+            turnaround,
+            verification_result,
+        );
+        let facility_key =
+            DataKey::FacilityVerifierStats(case.facility_id.clone(), verifier.clone());
+        if !env.storage().persistent().has(&facility_key) {
+            let verifiers_key = DataKey::FacilityVerifiers(case.facility_id.clone());
+            let mut verifiers: Vec<Address> = load_case_list(&env, verifiers_key.clone());
+            verifiers.push_back(verifier.clone());
+            env.storage().persistent().set(&verifiers_key, &verifiers);
+        }
+        record_verifier_stats(
+            &env,
+            facility_key,
+            &verifier,
+            turnaround,
+            verification_result,
+        );
+
         if verification_result {
             if case.verifications_complete < case.verifications_required {
                 case.verifications_complete += 1;
             }
             if case.verifications_complete >= case.verifications_required {
-                case.status = CredentialingStatus::CommitteeReview;
+                set_status(&env, &mut case, CredentialingStatus::CommitteeReview);
             } else {
-                case.status = CredentialingStatus::InReview;
+                set_status(&env, &mut case, CredentialingStatus::InReview);
             }
         } else {
-            case.status = CredentialingStatus::DeferredForMoreInfo;
+            set_status(&env, &mut case, CredentialingStatus::DeferredForMoreInfo);
         }
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Case(case_id), &case);
+        save_case(&env, case_id, &case);
+
+        CredentialVerified {
+            case_id,
+            credential_type,
+            verifier,
+            verification_result,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -356,7 +685,7 @@ impl HealthcareCredentialingSystem {
             .get(&DataKey::CaseSanctions(case_id))
             .unwrap_or(Vec::new(&env));
         checks.push_back(SanctionCheckRecord {
-            checker,
+            checker: checker.clone(),
             databases_checked,
             sanctions_found,
             check_date,
@@ -366,14 +695,79 @@ impl HealthcareCredentialingSystem {
             .set(&DataKey::CaseSanctions(case_id), &checks);
 
         if sanctions_found {
-            case.status = CredentialingStatus::Denied;
+            set_status(&env, &mut case, CredentialingStatus::SanctionReview);
         }
-        env.storage()
-            .persistent()
-            .set(&DataKey::Case(case_id), &case);
+        save_case(&env, case_id, &case);
+
+        SanctionsChecked {
+            case_id,
+            checker,
+            sanctions_found,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Rule on a case under sanction review. A disqualifying finding denies
+    /// the case; otherwise it resumes where its verifications left it. Only
+    /// a seated committee member may rule.
+    pub fn record_sanction_determination(
+        env: Env,
+        case_id: u64,
+        credentialing_committee: Address,
+        disqualifying: bool,
+        rationale_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        credentialing_committee.require_auth();
+        if !shared_access::has_role(&env, &COMMITTEE_ROLE, &credentialing_committee) {
+            return Err(Error::NotAuthorized);
+        }
+        let mut case = get_case(&env, case_id)?;
+        if case.status != CredentialingStatus::SanctionReview {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let key = DataKey::CaseSanctionDeterminations(case_id);
+        let mut determinations: Vec<SanctionDetermination> = load_case_list(&env, key.clone());
+        determinations.push_back(SanctionDetermination {
+            committee: credentialing_committee.clone(),
+            disqualifying,
+            rationale_hash,
+            determined_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &determinations);
+
+        let status = if disqualifying {
+            CredentialingStatus::Denied
+        } else if case.verifications_complete >= case.verifications_required {
+            CredentialingStatus::CommitteeReview
+        } else {
+            CredentialingStatus::InReview
+        };
+        record_status_change(&env, case_id, status.clone());
+        case.status = status;
+        save_case(&env, case_id, &case);
+
+        SanctionDeterminationRecorded {
+            case_id,
+            committee: credentialing_committee,
+            disqualifying,
+        }
+        .publish(&env);
         Ok(())
     }
 
+    pub fn get_sanction_determinations(
+        env: Env,
+        case_id: u64,
+    ) -> Result<Vec<SanctionDetermination>, Error> {
+        get_case(&env, case_id)?;
+        Ok(load_case_list(
+            &env,
+            DataKey::CaseSanctionDeterminations(case_id),
+        ))
+    }
+
     pub fn conduct_peer_reference(
         env: Env,
         case_id: u64,
@@ -392,21 +786,27 @@ impl HealthcareCredentialingSystem {
             .get(&DataKey::CasePeerReferences(case_id))
             .unwrap_or(Vec::new(&env));
         refs.push_back(PeerReferenceRecord {
-            reference_provider,
+            reference_provider: reference_provider.clone(),
             competency_ratings,
             reference_notes_hash,
             recommended,
+            recorded_at: env.ledger().timestamp(),
         });
         env.storage()
             .persistent()
             .set(&DataKey::CasePeerReferences(case_id), &refs);
 
         if !recommended {
-            case.status = CredentialingStatus::DeferredForMoreInfo;
-            env.storage()
-                .persistent()
-                .set(&DataKey::Case(case_id), &case);
+            set_status(&env, &mut case, CredentialingStatus::DeferredForMoreInfo);
+            save_case(&env, case_id, &case);
+        }
+
+        PeerReferenceRecorded {
+            case_id,
+            reference_provider,
+            recommended,
         }
+        .publish(&env);
         Ok(())
     }
 
@@ -463,7 +863,7 @@ impl HealthcareCredentialingSystem {
             idx += 1;
         }
 
-        case.status = CredentialingStatus::Approved;
+        set_status(&env, &mut case, CredentialingStatus::Approved);
         env.storage()
             .instance()
             .set(&DataKey::PrivilegeCounter, &privilege_counter);
@@ -474,9 +874,16 @@ impl HealthcareCredentialingSystem {
             ),
             &current,
         );
-        env.storage()
-            .persistent()
-            .set(&DataKey::Case(case_id), &case);
+        save_case(&env, case_id, &case);
+
+        PrivilegesGranted {
+            case_id,
+            provider_id: case.provider_id,
+            facility_id: case.facility_id,
+            privilege_count: approved_privileges.len(),
+            expiration_date,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -505,7 +912,7 @@ impl HealthcareCredentialingSystem {
                 request_id,
                 provider_id: provider_id.clone(),
                 facility_id: facility_id.clone(),
-                privilege_category,
+                privilege_category: privilege_category.clone(),
                 supervising_provider,
                 justification,
             },
@@ -521,10 +928,18 @@ impl HealthcareCredentialingSystem {
             .unwrap_or(Vec::new(&env));
         ids.push_back(request_id);
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityProvisional(provider_id, facility_id),
+            &DataKey::ProviderFacilityProvisional(provider_id.clone(), facility_id.clone()),
             &ids,
         );
 
+        ProvisionalPrivilegesRequested {
+            request_id,
+            provider_id,
+            facility_id,
+            privilege_category,
+        }
+        .publish(&env);
+
         Ok(request_id)
     }
 
@@ -548,15 +963,72 @@ impl HealthcareCredentialingSystem {
             .unwrap_or(Vec::new(&env));
 
         records.push_back(ClinicalActivityRecord {
-            procedure_code,
-            outcome,
+            procedure_code: procedure_code.clone(),
+            outcome: outcome.clone(),
             complications,
             activity_date,
         });
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityActivities(provider_id, facility_id),
+            &DataKey::ProviderFacilityActivities(provider_id.clone(), facility_id.clone()),
             &records,
         );
+
+        ClinicalActivityTracked {
+            provider_id: provider_id.clone(),
+            facility_id: facility_id.clone(),
+            outcome,
+            complications,
+        }
+        .publish(&env);
+
+        let prefix = procedure_prefix(&env, &procedure_code);
+        let stats = record_activity_stats(
+            &env,
+            &provider_id,
+            &facility_id,
+            prefix,
+            quarter_of(activity_date),
+            complications,
+        );
+        review_if_over_threshold(&env, &provider_id, &facility_id, stats);
+        Ok(())
+    }
+
+    /// Set the facility's complication-rate limit for a procedure-code
+    /// prefix. Once a provider has at least `min_case_volume` cases under the
+    /// prefix in a quarter and their rate exceeds the limit, a focused review
+    /// is opened automatically.
+    pub fn set_outcome_threshold(
+        env: Env,
+        facility_id: Address,
+        procedure_prefix: String,
+        max_complication_rate_x100: u32,
+        min_case_volume: u32,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+        if procedure_prefix.is_empty()
+            || procedure_prefix.len() > PROCEDURE_PREFIX_LEN
+            || max_complication_rate_x100 > 10_000
+            || min_case_volume == 0
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::FacilityOutcomeThreshold(facility_id.clone(), procedure_prefix.clone()),
+            &OutcomeThreshold {
+                max_complication_rate_x100,
+                min_case_volume,
+            },
+        );
+
+        OutcomeThresholdSet {
+            facility_id,
+            procedure_prefix,
+            max_complication_rate_x100,
+            min_case_volume,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -569,28 +1041,14 @@ impl HealthcareCredentialingSystem {
         initiated_by: Address,
     ) -> Result<u64, Error> {
         initiated_by.require_auth();
-        let current: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::FocusedReviewCounter)
-            .unwrap_or(0);
-        let review_id = current + 1;
-        env.storage()
-            .instance()
-            .set(&DataKey::FocusedReviewCounter, &review_id);
-
-        env.storage().persistent().set(
-            &DataKey::FocusedReview(review_id),
-            &FocusedReviewRecord {
-                review_id,
-                provider_id,
-                facility_id,
-                trigger_reason,
-                review_type,
-                initiated_by,
-            },
-        );
-        Ok(review_id)
+        Ok(open_focused_review(
+            &env,
+            provider_id,
+            facility_id,
+            trigger_reason,
+            review_type,
+            initiated_by,
+        ))
     }
 
     pub fn schedule_recredentialing(
@@ -632,10 +1090,18 @@ impl HealthcareCredentialingSystem {
             .unwrap_or(Vec::new(&env));
         ids.push_back(schedule_id);
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityRecredentialings(provider_id, facility_id),
+            &DataKey::ProviderFacilityRecredentialings(provider_id.clone(), facility_id.clone()),
             &ids,
         );
 
+        RecredentialingScheduled {
+            schedule_id,
+            provider_id,
+            facility_id,
+            due_date,
+        }
+        .publish(&env);
+
         Ok(schedule_id)
     }
 
@@ -690,7 +1156,7 @@ impl HealthcareCredentialingSystem {
             ))
             .unwrap_or(Vec::new(&env));
         history.push_back(SuspensionRecord {
-            suspension_authority,
+            suspension_authority: suspension_authority.clone(),
             suspension_reason,
             suspension_date,
             is_immediate,
@@ -702,9 +1168,17 @@ impl HealthcareCredentialingSystem {
             &privileges,
         );
         env.storage().persistent().set(
-            &DataKey::ProviderFacilitySuspensions(provider_id, facility_id),
+            &DataKey::ProviderFacilitySuspensions(provider_id.clone(), facility_id.clone()),
             &history,
         );
+
+        PrivilegesSuspended {
+            provider_id,
+            facility_id,
+            suspension_authority,
+            is_immediate,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -752,7 +1226,7 @@ impl HealthcareCredentialingSystem {
             ))
             .unwrap_or(Vec::new(&env));
         history.push_back(ReinstatementRecord {
-            reinstatement_authority,
+            reinstatement_authority: reinstatement_authority.clone(),
             corrective_actions_completed,
             monitoring_requirements,
         });
@@ -762,22 +1236,223 @@ impl HealthcareCredentialingSystem {
             &privileges,
         );
         env.storage().persistent().set(
-            &DataKey::ProviderFacilityReinstatements(provider_id, facility_id),
+            &DataKey::ProviderFacilityReinstatements(provider_id.clone(), facility_id.clone()),
             &history,
         );
-        Ok(())
-    }
 
-    pub fn get_credentialing_case(env: Env, case_id: u64) -> Result<CredentialingCase, Error> {
-        get_case(&env, case_id)
+        PrivilegesReinstated {
+            provider_id,
+            facility_id,
+            reinstatement_authority,
+        }
+        .publish(&env);
+        Ok(())
     }
 
-    pub fn get_provider_privileges(
+    /// Snapshot the privileges and credential documents at the facility
+    /// that expire within `horizon_days`, for the medical staff office's
+    /// expirables report. The digest is stored and announced by event.
+    pub fn generate_expiration_digest(
         env: Env,
-        provider_id: Address,
         facility_id: Address,
-    ) -> Vec<Privilege> {
-        env.storage()
+        horizon_days: u32,
+    ) -> Result<u64, Error> {
+        facility_id.require_auth();
+        if horizon_days == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let horizon_end = now.saturating_add(horizon_days as u64 * SECONDS_PER_DAY);
+        let case_ids: Vec<u64> = load_case_list(&env, DataKey::FacilityCases(facility_id.clone()));
+
+        let mut providers: Vec<Address> = Vec::new(&env);
+        let mut documents = Vec::new(&env);
+        for case_id in case_ids.iter() {
+            let Ok(case) = get_case(&env, case_id) else {
+                continue;
+            };
+            let docs: Vec<CredentialDocument> =
+                load_case_list(&env, DataKey::CaseDocuments(case_id));
+            for doc in docs.iter() {
+                if let Some(expiration_date) = doc.expiration_date {
+                    if expiration_date <= horizon_end {
+                        documents.push_back(ExpiringDocument {
+                            case_id,
+                            provider_id: case.provider_id.clone(),
+                            document_type: doc.document_type,
+                            expiration_date,
+                        });
+                    }
+                }
+            }
+            if !providers.contains(&case.provider_id) {
+                providers.push_back(case.provider_id);
+            }
+        }
+
+        let mut privileges = Vec::new(&env);
+        for provider_id in providers.iter() {
+            let granted = Self::get_provider_privileges(
+                env.clone(),
+                provider_id.clone(),
+                facility_id.clone(),
+            );
+            for privilege in granted.iter() {
+                if privilege.expiration_date <= horizon_end {
+                    privileges.push_back(ExpiringPrivilege {
+                        privilege_id: privilege.privilege_id,
+                        provider_id: provider_id.clone(),
+                        privilege_category: privilege.privilege_category,
+                        expiration_date: privilege.expiration_date,
+                    });
+                }
+            }
+        }
+
+        let digest_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::DigestCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::DigestCounter, &digest_id);
+
+        let digest = ExpirationDigest {
+            digest_id,
+            facility_id: facility_id.clone(),
+            generated_at: now,
+            horizon_end,
+            privileges_expiring: privileges.len(),
+            documents_expiring: documents.len(),
+            privileges,
+            documents,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::ExpirationDigest(digest_id), &digest);
+
+        let mut digests: Vec<u64> =
+            load_case_list(&env, DataKey::FacilityDigests(facility_id.clone()));
+        digests.push_back(digest_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::FacilityDigests(facility_id.clone()), &digests);
+
+        ExpirationDigestGenerated {
+            digest_id,
+            facility_id,
+            horizon_end,
+            privileges_expiring: digest.privileges_expiring,
+            documents_expiring: digest.documents_expiring,
+        }
+        .publish(&env);
+
+        Ok(digest_id)
+    }
+
+    pub fn get_credentialing_case(env: Env, case_id: u64) -> Result<CredentialingCase, Error> {
+        get_case(&env, case_id)
+    }
+
+    /// Full chronology of a case for audit: status changes, documents,
+    /// verifications, sanctions checks and peer references, oldest first.
+    /// Documents and peer references are timed by when they were recorded,
+    /// verifications and sanctions checks by their own dates. On equal
+    /// timestamps a status change follows the record that caused it.
+    pub fn get_case_timeline(env: Env, case_id: u64) -> Result<Vec<CaseTimelineEntry>, Error> {
+        get_case(&env, case_id)?;
+        let mut timeline = Vec::new(&env);
+
+        for doc in
+            load_case_list::<CredentialDocument>(&env, DataKey::CaseDocuments(case_id)).iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                doc.submitted_at,
+                CaseTimelineEvent::DocumentSubmitted(doc),
+            );
+        }
+        for record in
+            load_case_list::<VerificationRecord>(&env, DataKey::CaseVerifications(case_id)).iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                record.verification_date,
+                CaseTimelineEvent::CredentialVerified(record),
+            );
+        }
+        for check in
+            load_case_list::<SanctionCheckRecord>(&env, DataKey::CaseSanctions(case_id)).iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                check.check_date,
+                CaseTimelineEvent::SanctionsChecked(check),
+            );
+        }
+        for reference in
+            load_case_list::<PeerReferenceRecord>(&env, DataKey::CasePeerReferences(case_id)).iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                reference.recorded_at,
+                CaseTimelineEvent::PeerReference(reference),
+            );
+        }
+        for determination in load_case_list::<SanctionDetermination>(
+            &env,
+            DataKey::CaseSanctionDeterminations(case_id),
+        )
+        .iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                determination.determined_at,
+                CaseTimelineEvent::SanctionDetermination(determination),
+            );
+        }
+        for change in
+            load_case_list::<StatusChange>(&env, DataKey::CaseStatusHistory(case_id)).iter()
+        {
+            insert_by_time(
+                &mut timeline,
+                change.changed_at,
+                CaseTimelineEvent::StatusChanged(change),
+            );
+        }
+        Ok(timeline)
+    }
+
+    /// Extend the TTL of a case and its documents, verifications, sanctions
+    /// checks and peer references. Anyone may call this to keep a closed
+    /// case readable for audit.
+    pub fn extend_case_ttl(env: Env, case_id: u64) -> Result<(), Error> {
+        if !shared_ttl::bump_persistent(&env, &DataKey::Case(case_id), shared_ttl::ARCHIVE) {
+            return Err(Error::CaseNotFound);
+        }
+        for key in [
+            DataKey::CaseDocuments(case_id),
+            DataKey::CaseVerifications(case_id),
+            DataKey::CaseSanctions(case_id),
+            DataKey::CasePeerReferences(case_id),
+            DataKey::CaseStatusHistory(case_id),
+            DataKey::CaseSanctionDeterminations(case_id),
+        ] {
+            shared_ttl::bump_persistent(&env, &key, shared_ttl::ARCHIVE);
+        }
+        shared_ttl::extend_instance(&env, shared_ttl::INSTANCE);
+        Ok(())
+    }
+
+    pub fn get_provider_privileges(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+    ) -> Vec<Privilege> {
+        env.storage()
             .persistent()
             .get(&DataKey::ProviderFacilityPrivileges(
                 provider_id,
@@ -786,26 +1461,438 @@ impl HealthcareCredentialingSystem {
             .unwrap_or(Vec::new(&env))
     }
 
+    /// True when the provider holds an unexpired, unsuspended privilege in
+    /// `privilege_category` at the facility.
+    pub fn has_active_privilege(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+        privilege_category: Symbol,
+    ) -> bool {
+        let privileges: Vec<Privilege> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderFacilityPrivileges(
+                provider_id,
+                facility_id,
+            ))
+            .unwrap_or(Vec::new(&env));
+        let now = env.ledger().timestamp();
+        let suspended_marker = String::from_str(&env, "SUSPENDED");
+
+        let mut idx: u32 = 0;
+        while idx < privileges.len() {
+            if let Some(p) = privileges.get(idx) {
+                if p.privilege_category == privilege_category
+                    && p.granted_date <= now
+                    && now < p.expiration_date
+                    && !marker_exists(&p.restrictions, &suspended_marker)
+                {
+                    return true;
+                }
+            }
+            idx += 1;
+        }
+        false
+    }
+
+    /// One page of a provider's clinical activity at a facility, oldest first.
     pub fn get_clinical_activities(
         env: Env,
         provider_id: Address,
         facility_id: Address,
+        offset: u32,
+        limit: u32,
     ) -> Vec<ClinicalActivityRecord> {
-        env.storage()
+        let activities = env
+            .storage()
             .persistent()
             .get(&DataKey::ProviderFacilityActivities(
                 provider_id,
                 facility_id,
             ))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&activities, offset, limit)
     }
+
+    pub fn get_expiration_digest(env: Env, digest_id: u64) -> Result<ExpirationDigest, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ExpirationDigest(digest_id))
+            .ok_or(Error::DigestNotFound)
+    }
+
+    /// One page of the facility's digest ids, oldest first.
+    pub fn get_facility_digests(
+        env: Env,
+        facility_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let digests = load_case_list(&env, DataKey::FacilityDigests(facility_id));
+        shared_pagination::paginate(&digests, offset, limit)
+    }
+
+    pub fn get_verifier_stats(env: Env, verifier: Address) -> Option<VerifierStats> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::VerifierStats(verifier))
+    }
+
+    /// Statistics of every verifier who has verified a credential for the
+    /// facility, fastest average turnaround first. Ties go to the verifier
+    /// with more verifications.
+    pub fn get_facility_verifier_ranking(env: Env, facility_id: Address) -> Vec<VerifierStats> {
+        let verifiers: Vec<Address> =
+            load_case_list(&env, DataKey::FacilityVerifiers(facility_id.clone()));
+
+        let mut ranking: Vec<VerifierStats> = Vec::new(&env);
+        for verifier in verifiers.iter() {
+            let Some(stats) = env.storage().persistent().get::<_, VerifierStats>(
+                &DataKey::FacilityVerifierStats(facility_id.clone(), verifier),
+            ) else {
+                continue;
+            };
+            let count = stats.verified_count + stats.failed_count;
+            let mut idx = ranking.len();
+            while idx > 0 {
+                let prev = ranking.get_unchecked(idx - 1);
+                let prev_count = prev.verified_count + prev.failed_count;
+                if prev.avg_turnaround_days_x100 < stats.avg_turnaround_days_x100
+                    || (prev.avg_turnaround_days_x100 == stats.avg_turnaround_days_x100
+                        && prev_count >= count)
+                {
+                    break;
+                }
+                idx -= 1;
+            }
+            ranking.insert(idx, stats);
+        }
+        ranking
+    }
+
+    pub fn get_outcome_threshold(
+        env: Env,
+        facility_id: Address,
+        procedure_prefix: String,
+    ) -> Option<OutcomeThreshold> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FacilityOutcomeThreshold(
+                facility_id,
+                procedure_prefix,
+            ))
+    }
+
+    /// OPPE scorecard: the provider's statistics at the facility for each
+    /// procedure-code prefix with activity in `quarter` (`year * 10 +
+    /// quarter`, e.g. 20253).
+    pub fn get_oppe_scorecard(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+        quarter: u32,
+    ) -> Vec<ActivityStats> {
+        let prefixes: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderFacilityStatPrefixes(
+                provider_id.clone(),
+                facility_id.clone(),
+                quarter,
+            ))
+            .unwrap_or(Vec::new(&env));
+
+        let mut scorecard = Vec::new(&env);
+        for prefix in prefixes.iter() {
+            if let Some(stats) =
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::ProviderFacilityActivityStats(
+                        provider_id.clone(),
+                        facility_id.clone(),
+                        prefix,
+                        quarter,
+                    ))
+            {
+                scorecard.push_back(stats);
+            }
+        }
+        scorecard
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn open_focused_review(
+    env: &Env,
+    provider_id: Address,
+    facility_id: Address,
+    trigger_reason: Symbol,
+    review_type: Symbol,
+    initiated_by: Address,
+) -> u64 {
+    let current: u64 = env
+        .storage()
+        .instance()
+        .get(&DataKey::FocusedReviewCounter)
+        .unwrap_or(0);
+    let review_id = current + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::FocusedReviewCounter, &review_id);
+
+    env.storage().persistent().set(
+        &DataKey::FocusedReview(review_id),
+        &FocusedReviewRecord {
+            review_id,
+            provider_id: provider_id.clone(),
+            facility_id: facility_id.clone(),
+            trigger_reason: trigger_reason.clone(),
+            review_type,
+            initiated_by,
+        },
+    );
+
+    FocusedReviewTriggered {
+        review_id,
+        provider_id,
+        facility_id,
+        trigger_reason,
+    }
+    .publish(env);
+    review_id
+}
+
+/// Add one case to the provider's statistics for `prefix` in `quarter`.
+fn record_activity_stats(
+    env: &Env,
+    provider_id: &Address,
+    facility_id: &Address,
+    prefix: String,
+    quarter: u32,
+    complications: bool,
+) -> ActivityStats {
+    let key = DataKey::ProviderFacilityActivityStats(
+        provider_id.clone(),
+        facility_id.clone(),
+        prefix.clone(),
+        quarter,
+    );
+    let mut stats: ActivityStats = match env.storage().persistent().get(&key) {
+        Some(stats) => stats,
+        None => {
+            let prefixes_key = DataKey::ProviderFacilityStatPrefixes(
+                provider_id.clone(),
+                facility_id.clone(),
+                quarter,
+            );
+            let mut prefixes: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&prefixes_key)
+                .unwrap_or(Vec::new(env));
+            prefixes.push_back(prefix.clone());
+            env.storage().persistent().set(&prefixes_key, &prefixes);
+            ActivityStats {
+                procedure_prefix: prefix,
+                quarter,
+                case_volume: 0,
+                complications: 0,
+                complication_rate_x100: 0,
+                focused_review_id: None,
+            }
+        }
+    };
+
+    stats.case_volume += 1;
+    if complications {
+        stats.complications += 1;
+    }
+    stats.complication_rate_x100 =
+        (u64::from(stats.complications) * 10_000 / u64::from(stats.case_volume)) as u32;
+    env.storage().persistent().set(&key, &stats);
+    stats
+}
+
+/// Add one verification to the statistics stored under `key`.
+fn record_verifier_stats(
+    env: &Env,
+    key: DataKey,
+    verifier: &Address,
+    turnaround_seconds: u64,
+    verified: bool,
+) {
+    let mut stats: VerifierStats = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(VerifierStats {
+            verifier: verifier.clone(),
+            verified_count: 0,
+            failed_count: 0,
+            total_turnaround_seconds: 0,
+            avg_turnaround_days_x100: 0,
+        });
+
+    if verified {
+        stats.verified_count += 1;
+    } else {
+        stats.failed_count += 1;
+    }
+    stats.total_turnaround_seconds += turnaround_seconds;
+    let total = u64::from(stats.verified_count + stats.failed_count);
+    stats.avg_turnaround_days_x100 =
+        (stats.total_turnaround_seconds * 100 / SECONDS_PER_DAY / total) as u32;
+    env.storage().persistent().set(&key, &stats);
+}
+
+/// Open a focused review the first time the provider's complication rate for
+/// a prefix and quarter exceeds the facility threshold.
+fn review_if_over_threshold(
+    env: &Env,
+    provider_id: &Address,
+    facility_id: &Address,
+    mut stats: ActivityStats,
+) {
+    if stats.focused_review_id.is_some() {
+        return;
+    }
+    let threshold: OutcomeThreshold =
+        match env
+            .storage()
+            .persistent()
+            .get(&DataKey::FacilityOutcomeThreshold(
+                facility_id.clone(),
+                stats.procedure_prefix.clone(),
+            )) {
+            Some(threshold) => threshold,
+            None => return,
+        };
+    if stats.case_volume < threshold.min_case_volume
+        || stats.complication_rate_x100 <= threshold.max_complication_rate_x100
+    {
+        return;
+    }
+
+    let review_id = open_focused_review(
+        env,
+        provider_id.clone(),
+        facility_id.clone(),
+        Symbol::new(env, "complication_rate"),
+        Symbol::new(env, "fppe"),
+        env.current_contract_address(),
+    );
+    stats.focused_review_id = Some(review_id);
+    env.storage().persistent().set(
+        &DataKey::ProviderFacilityActivityStats(
+            provider_id.clone(),
+            facility_id.clone(),
+            stats.procedure_prefix.clone(),
+            stats.quarter,
+        ),
+        &stats,
+    );
+}
+
+/// The leading `PROCEDURE_PREFIX_LEN` bytes of a procedure code.
+fn procedure_prefix(env: &Env, procedure_code: &String) -> String {
+    let len = procedure_code.len().min(PROCEDURE_PREFIX_LEN);
+    let mut buf = [0u8; PROCEDURE_PREFIX_LEN as usize];
+    procedure_code
+        .to_bytes()
+        .slice(0..len)
+        .copy_into_slice(&mut buf[..len as usize]);
+    String::from_bytes(env, &buf[..len as usize])
+}
+
+/// Calendar quarter of a Unix timestamp, encoded as `year * 10 + quarter`.
+fn quarter_of(timestamp: u64) -> u32 {
+    // Civil-from-days conversion (proleptic Gregorian, UTC).
+    let z = timestamp / SECONDS_PER_DAY + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year * 10 + (month - 1) / 3 + 1) as u32
 }
 
 fn get_case(env: &Env, case_id: u64) -> Result<CredentialingCase, Error> {
+    let key = DataKey::Case(case_id);
+    let case = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .ok_or(Error::CaseNotFound)?;
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+    Ok(case)
+}
+
+/// Move the case to `status`, recording the transition if it changes. A
+/// case under sanction review keeps that status until the committee's
+/// determination; records submitted meanwhile are still stored.
+fn set_status(env: &Env, case: &mut CredentialingCase, status: CredentialingStatus) {
+    if case.status == CredentialingStatus::SanctionReview {
+        return;
+    }
+    if case.status != status {
+        record_status_change(env, case.case_id, status.clone());
+        case.status = status;
+    }
+}
+
+fn record_status_change(env: &Env, case_id: u64, status: CredentialingStatus) {
+    let key = DataKey::CaseStatusHistory(case_id);
+    let mut history: Vec<StatusChange> = load_case_list(env, key.clone());
+    history.push_back(StatusChange {
+        status,
+        changed_at: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &history);
+}
+
+fn load_case_list<T>(env: &Env, key: DataKey) -> Vec<T>
+where
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+{
     env.storage()
         .persistent()
-        .get(&DataKey::Case(case_id))
-        .ok_or(Error::CaseNotFound)
+        .get(&key)
+        .unwrap_or(Vec::new(env))
+}
+
+/// Insert after every entry at or before `timestamp`, so merging lists that
+/// are each in order keeps ties in insertion order.
+fn insert_by_time(timeline: &mut Vec<CaseTimelineEntry>, timestamp: u64, event: CaseTimelineEvent) {
+    let mut idx = timeline.len();
+    while idx > 0 && timeline.get_unchecked(idx - 1).timestamp > timestamp {
+        idx -= 1;
+    }
+    timeline.insert(idx, CaseTimelineEntry { timestamp, event });
+}
+
+fn save_case(env: &Env, case_id: u64, case: &CredentialingCase) {
+    let key = DataKey::Case(case_id);
+    env.storage().persistent().set(&key, case);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 fn is_supported_credential_type(env: &Env, credential_type: &Symbol) -> bool {
@@ -816,17 +1903,18 @@ fn is_supported_credential_type(env: &Env, credential_type: &Symbol) -> bool {
         || *credential_type == Symbol::new(env, "references")
 }
 
-fn document_exists_for_type(docs: &Vec<CredentialDocument>, credential_type: &Symbol) -> bool {
-    let mut idx: u32 = 0;
-    while idx < docs.len() {
+/// When the most recent document of `credential_type` was submitted.
+fn latest_submission(docs: &Vec<CredentialDocument>, credential_type: &Symbol) -> Option<u64> {
+    let mut idx = docs.len();
+    while idx > 0 {
+        idx -= 1;
         if let Some(doc) = docs.get(idx) {
             if doc.document_type == *credential_type {
-                return true;
+                return Some(doc.submitted_at);
             }
         }
-        idx += 1;
     }
-    false
+    None
 }
 
 fn validate_competency_ratings(ratings: &Vec<CompetencyRating>) -> Result<(), Error> {
@@ -910,7 +1998,10 @@ fn all_privileges_have_marker(privileges: &Vec<Privilege>, marker: &String) -> b
 #[cfg(test)]
 mod test {
     use super::*;
-    use soroban_sdk::{testutils::Address as _, Address};
+    use soroban_sdk::{
+        testutils::{storage::Persistent as _, Address as _, Events, Ledger},
+        Address,
+    };
 
     fn create_case(
         env: &Env,
@@ -940,6 +2031,24 @@ mod test {
         }
     }
 
+    /// Initialize the contract, accept `primary_source` for every credential
+    /// type and let `verifier` verify all of them.
+    fn setup_verification(
+        env: &Env,
+        client: &HealthcareCredentialingSystemClient,
+        verifier: &Address,
+    ) -> Address {
+        let admin = Address::generate(env);
+        client.initialize(&admin);
+        let methods = Vec::from_array(env, [Symbol::new(env, "primary_source")]);
+        for credential_type in ["medical_license", "dea", "board_cert", "cv", "references"] {
+            let credential_type = Symbol::new(env, credential_type);
+            client.set_accepted_methods(&admin, &credential_type, &methods);
+            client.authorize_verifier(&admin, verifier, &credential_type, &None);
+        }
+        admin
+    }
+
     #[test]
     fn full_credentialing_lifecycle() {
         let env = Env::default();
@@ -954,6 +2063,7 @@ mod test {
         let checker = Address::generate(&env);
         let ref_provider = Address::generate(&env);
         let committee = Address::generate(&env);
+        setup_verification(&env, &client, &verifier);
 
         let case_id = create_case(&env, &client, &provider, &facility);
         submit_required_docs(&env, &client, case_id);
@@ -1040,6 +2150,102 @@ mod test {
         assert_eq!(res, Err(Ok(Error::DocumentNotFound)));
     }
 
+    #[test]
+    fn verification_requires_accepted_method_and_qualified_verifier() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let admin = Address::generate(&env);
+        let license = Symbol::new(&env, "medical_license");
+        let dea = Symbol::new(&env, "dea");
+        let ama_profile = Symbol::new(&env, "ama_profile");
+
+        client.initialize(&admin);
+        assert_eq!(
+            client.try_initialize(&admin),
+            Err(Ok(Error::AlreadyInitialized))
+        );
+        assert_eq!(
+            client.try_set_accepted_methods(
+                &admin,
+                &Symbol::new(&env, "diploma"),
+                &Vec::from_array(&env, [ama_profile.clone()]),
+            ),
+            Err(Ok(Error::InvalidCredentialType))
+        );
+        assert_eq!(
+            client.try_set_accepted_methods(
+                &Address::generate(&env),
+                &license,
+                &Vec::from_array(&env, [ama_profile.clone()]),
+            ),
+            Err(Ok(Error::NotAuthorized))
+        );
+        client.set_accepted_methods(
+            &admin,
+            &license,
+            &Vec::from_array(
+                &env,
+                [Symbol::new(&env, "primary_source"), ama_profile.clone()],
+            ),
+        );
+        assert_eq!(client.get_accepted_methods(&license).len(), 2);
+        assert!(client.get_accepted_methods(&dea).is_empty());
+
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+
+        let verify = |credential_type: &Symbol, method: &Symbol| {
+            client.try_verify_credential(
+                &case_id,
+                credential_type,
+                &verifier,
+                method,
+                &true,
+                &1_700_010_000,
+                &String::from_str(&env, "Verified"),
+            )
+        };
+
+        // Unknown method, and a type with no accepted methods configured.
+        assert_eq!(
+            verify(&license, &Symbol::new(&env, "phone_call")),
+            Err(Ok(Error::VerificationMethodNotAccepted))
+        );
+        assert_eq!(
+            verify(&dea, &ama_profile),
+            Err(Ok(Error::VerificationMethodNotAccepted))
+        );
+
+        // Accepted method, but the verifier is not tagged for licenses.
+        assert_eq!(
+            verify(&license, &ama_profile),
+            Err(Ok(Error::VerifierNotQualified))
+        );
+
+        env.ledger().set_timestamp(1_700_000_000);
+        client.authorize_verifier(&admin, &verifier, &license, &Some(1_700_100_000));
+        assert!(client.can_verify(&verifier, &license));
+        assert!(!client.can_verify(&verifier, &dea));
+        assert_eq!(verify(&license, &ama_profile), Ok(Ok(())));
+
+        // Tags expire and can be revoked.
+        env.ledger().set_timestamp(1_700_100_001);
+        assert_eq!(
+            verify(&license, &ama_profile),
+            Err(Ok(Error::VerifierNotQualified))
+        );
+        client.authorize_verifier(&admin, &verifier, &license, &None);
+        client.revoke_verifier(&admin, &verifier, &license);
+        assert!(!client.can_verify(&verifier, &license));
+    }
+
     #[test]
     fn invalid_peer_rating_fails() {
         let env = Env::default();
@@ -1069,6 +2275,128 @@ mod test {
         assert_eq!(res, Err(Ok(Error::InvalidRating)));
     }
 
+    #[test]
+    fn case_timeline_merges_records_in_order() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let checker = Address::generate(&env);
+        let ref_provider = Address::generate(&env);
+        setup_verification(&env, &client, &verifier);
+
+        env.ledger().set_timestamp(1_700_000_000);
+        let case_id = create_case(&env, &client, &provider, &facility);
+
+        env.ledger().set_timestamp(1_700_001_000);
+        let license = Symbol::new(&env, "medical_license");
+        client.submit_credential_document(
+            &case_id,
+            &license,
+            &BytesN::from_array(&env, &[1; 32]),
+            &String::from_str(&env, "Issuer"),
+            &1_600_000_000,
+            &None,
+        );
+
+        // Verified as of an earlier date than it was recorded.
+        env.ledger().set_timestamp(1_700_003_000);
+        client.verify_credential(
+            &case_id,
+            &license,
+            &verifier,
+            &Symbol::new(&env, "primary_source"),
+            &false,
+            &1_700_002_000,
+            &String::from_str(&env, "Lapsed"),
+        );
+
+        env.ledger().set_timestamp(1_700_004_000);
+        let mut ratings = Vec::new(&env);
+        ratings.push_back(CompetencyRating {
+            competency_area: Symbol::new(&env, "clinical_judgment"),
+            rating: 4,
+            clinical_examples: true,
+        });
+        client.conduct_peer_reference(
+            &case_id,
+            &ref_provider,
+            &ratings,
+            &BytesN::from_array(&env, &[9; 32]),
+            &true,
+        );
+
+        env.ledger().set_timestamp(1_700_005_000);
+        let mut dbs = Vec::new(&env);
+        dbs.push_back(Symbol::new(&env, "NPDB"));
+        client.check_sanctions(&case_id, &checker, &dbs, &true, &1_700_005_000);
+
+        let timeline = client.get_case_timeline(&case_id);
+        let mut timestamps = Vec::new(&env);
+        let mut statuses = Vec::new(&env);
+        for entry in timeline.iter() {
+            timestamps.push_back(entry.timestamp);
+            if let CaseTimelineEvent::StatusChanged(change) = entry.event {
+                statuses.push_back(change.status);
+            }
+        }
+        assert_eq!(
+            timestamps,
+            Vec::from_array(
+                &env,
+                [
+                    1_700_000_000,
+                    1_700_001_000,
+                    1_700_001_000,
+                    1_700_002_000,
+                    1_700_003_000,
+                    1_700_004_000,
+                    1_700_005_000,
+                    1_700_005_000,
+                ]
+            )
+        );
+        assert_eq!(
+            statuses,
+            Vec::from_array(
+                &env,
+                [
+                    CredentialingStatus::Incomplete,
+                    CredentialingStatus::InReview,
+                    CredentialingStatus::DeferredForMoreInfo,
+                    CredentialingStatus::SanctionReview,
+                ]
+            )
+        );
+        assert!(matches!(
+            timeline.get(1).unwrap().event,
+            CaseTimelineEvent::DocumentSubmitted(_)
+        ));
+        assert!(matches!(
+            timeline.get(3).unwrap().event,
+            CaseTimelineEvent::CredentialVerified(_)
+        ));
+        assert!(matches!(
+            timeline.get(5).unwrap().event,
+            CaseTimelineEvent::PeerReference(_)
+        ));
+        // The sanctions hit precedes the review it opened.
+        assert!(matches!(
+            timeline.get(6).unwrap().event,
+            CaseTimelineEvent::SanctionsChecked(_)
+        ));
+
+        assert_eq!(
+            client.try_get_case_timeline(&99),
+            Err(Ok(Error::CaseNotFound))
+        );
+    }
+
     #[test]
     fn sanctions_can_deny_case() {
         let env = Env::default();
@@ -1080,14 +2408,122 @@ mod test {
         let provider = Address::generate(&env);
         let facility = Address::generate(&env);
         let checker = Address::generate(&env);
+        let committee = Address::generate(&env);
+        let admin = Address::generate(&env);
+        client.initialize(&admin);
+        client.authorize_committee_member(&admin, &committee, &None);
         let case_id = create_case(&env, &client, &provider, &facility);
 
         let mut dbs = Vec::new(&env);
         dbs.push_back(Symbol::new(&env, "NPDB"));
         client.check_sanctions(&case_id, &checker, &dbs, &true, &1_700_020_000);
 
+        // A hit opens a review rather than denying outright
+        let case = client.get_credentialing_case(&case_id);
+        assert_eq!(case.status, CredentialingStatus::SanctionReview);
+
+        // Later records don't move the case out of review
+        client.submit_credential_document(
+            &case_id,
+            &Symbol::new(&env, "cv"),
+            &BytesN::from_array(&env, &[3; 32]),
+            &String::from_str(&env, "Issuer"),
+            &1_700_000_000,
+            &None,
+        );
+        let case = client.get_credentialing_case(&case_id);
+        assert_eq!(case.status, CredentialingStatus::SanctionReview);
+
+        env.ledger().set_timestamp(1_700_030_000);
+        let rationale = BytesN::from_array(&env, &[4; 32]);
+
+        // Only a seated committee member can rule on the review
+        let outsider = Address::generate(&env);
+        assert_eq!(
+            client.try_record_sanction_determination(&case_id, &outsider, &false, &rationale),
+            Err(Ok(Error::NotAuthorized))
+        );
+        assert_eq!(
+            client.try_authorize_committee_member(&outsider, &outsider, &None),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        client.record_sanction_determination(&case_id, &committee, &true, &rationale);
+
         let case = client.get_credentialing_case(&case_id);
         assert_eq!(case.status, CredentialingStatus::Denied);
+        let determinations = client.get_sanction_determinations(&case_id);
+        assert_eq!(
+            determinations,
+            Vec::from_array(
+                &env,
+                [SanctionDetermination {
+                    committee: committee.clone(),
+                    disqualifying: true,
+                    rationale_hash: rationale.clone(),
+                    determined_at: 1_700_030_000,
+                }]
+            )
+        );
+        assert_eq!(
+            client.try_record_sanction_determination(&case_id, &committee, &false, &rationale),
+            Err(Ok(Error::InvalidStatusTransition))
+        );
+    }
+
+    #[test]
+    fn non_disqualifying_sanction_resumes_case() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let checker = Address::generate(&env);
+        let committee = Address::generate(&env);
+        let admin = setup_verification(&env, &client, &verifier);
+        client.authorize_committee_member(&admin, &committee, &None);
+        assert!(client.is_committee_member(&committee));
+
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+
+        let dbs = Vec::from_array(&env, [Symbol::new(&env, "OIG")]);
+        client.check_sanctions(&case_id, &checker, &dbs, &true, &1_700_005_000);
+
+        // Verifications completed during the review are kept
+        for credential_type in ["medical_license", "dea", "board_cert", "cv", "references"] {
+            client.verify_credential(
+                &case_id,
+                &Symbol::new(&env, credential_type),
+                &verifier,
+                &Symbol::new(&env, "primary_source"),
+                &true,
+                &1_700_010_000,
+                &String::from_str(&env, "Verified"),
+            );
+        }
+        let case = client.get_credentialing_case(&case_id);
+        assert_eq!(case.status, CredentialingStatus::SanctionReview);
+        assert_eq!(case.verifications_complete, REQUIRED_CREDENTIALS);
+
+        client.record_sanction_determination(
+            &case_id,
+            &committee,
+            &false,
+            &BytesN::from_array(&env, &[5; 32]),
+        );
+        let case = client.get_credentialing_case(&case_id);
+        assert_eq!(case.status, CredentialingStatus::CommitteeReview);
+
+        let timeline = client.get_case_timeline(&case_id);
+        assert!(timeline.iter().any(|entry| matches!(
+            entry.event,
+            CaseTimelineEvent::SanctionDetermination(ref d) if !d.disqualifying
+        )));
     }
 
     #[test]
@@ -1134,10 +2570,133 @@ mod test {
             client.schedule_recredentialing(&provider, &facility, &1_800_000_000, &true);
         assert_eq!(schedule_id, 1);
 
-        let activities = client.get_clinical_activities(&provider, &facility);
+        let activities = client.get_clinical_activities(&provider, &facility, &0, &10);
         assert_eq!(activities.len(), 1);
     }
 
+    #[test]
+    fn quarter_and_prefix_helpers() {
+        let env = Env::default();
+
+        assert_eq!(quarter_of(1_700_050_000), 20234);
+        assert_eq!(quarter_of(1_704_067_199), 20234);
+        assert_eq!(quarter_of(1_704_067_200), 20241);
+        assert_eq!(quarter_of(1_719_792_000), 20243);
+
+        assert_eq!(
+            procedure_prefix(&env, &String::from_str(&env, "33206")),
+            String::from_str(&env, "332")
+        );
+        assert_eq!(
+            procedure_prefix(&env, &String::from_str(&env, "J1")),
+            String::from_str(&env, "J1")
+        );
+    }
+
+    #[test]
+    fn complication_threshold_opens_focused_review() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let pacemaker = String::from_str(&env, "332");
+        let successful = Symbol::new(&env, "successful");
+
+        assert_eq!(
+            client.try_set_outcome_threshold(&facility, &pacemaker, &10_001, &3),
+            Err(Ok(Error::InvalidInput))
+        );
+        client.set_outcome_threshold(&facility, &pacemaker, &2_000, &3);
+        assert_eq!(
+            client.get_outcome_threshold(&facility, &pacemaker),
+            Some(OutcomeThreshold {
+                max_complication_rate_x100: 2_000,
+                min_case_volume: 3,
+            })
+        );
+
+        // 1 of 2 cases is over the limit but below the minimum volume.
+        for (code, complications) in [("33206", false), ("33207", true)] {
+            client.track_clinical_activity(
+                &provider,
+                &facility,
+                &String::from_str(&env, code),
+                &successful,
+                &complications,
+                &1_700_050_000,
+            );
+        }
+        let scorecard = client.get_oppe_scorecard(&provider, &facility, &20234);
+        assert_eq!(scorecard.get(0).unwrap().focused_review_id, None);
+
+        // The third case brings the volume to the minimum at 33.33%.
+        client.track_clinical_activity(
+            &provider,
+            &facility,
+            &String::from_str(&env, "33208"),
+            &successful,
+            &false,
+            &1_700_060_000,
+        );
+        // Further complications in the same quarter do not reopen it.
+        client.track_clinical_activity(
+            &provider,
+            &facility,
+            &String::from_str(&env, "33206"),
+            &successful,
+            &true,
+            &1_700_070_000,
+        );
+        // Other prefixes and quarters are tracked separately.
+        client.track_clinical_activity(
+            &provider,
+            &facility,
+            &String::from_str(&env, "99291"),
+            &successful,
+            &true,
+            &1_700_070_000,
+        );
+        client.track_clinical_activity(
+            &provider,
+            &facility,
+            &String::from_str(&env, "33206"),
+            &successful,
+            &false,
+            &1_704_067_200,
+        );
+
+        let scorecard = client.get_oppe_scorecard(&provider, &facility, &20234);
+        assert_eq!(scorecard.len(), 2);
+        let pacemaker_stats = scorecard.get(0).unwrap();
+        assert_eq!(pacemaker_stats.procedure_prefix, pacemaker);
+        assert_eq!(pacemaker_stats.case_volume, 4);
+        assert_eq!(pacemaker_stats.complications, 2);
+        assert_eq!(pacemaker_stats.complication_rate_x100, 5_000);
+        assert_eq!(pacemaker_stats.focused_review_id, Some(1));
+
+        let critical_care = scorecard.get(1).unwrap();
+        assert_eq!(critical_care.complication_rate_x100, 10_000);
+        assert_eq!(critical_care.focused_review_id, None);
+
+        let next_quarter = client.get_oppe_scorecard(&provider, &facility, &20241);
+        assert_eq!(next_quarter.len(), 1);
+        assert_eq!(next_quarter.get(0).unwrap().case_volume, 1);
+
+        // The automatic review consumed id 1.
+        let review_id = client.trigger_focused_review(
+            &provider,
+            &facility,
+            &Symbol::new(&env, "complaint"),
+            &Symbol::new(&env, "fppe"),
+            &facility,
+        );
+        assert_eq!(review_id, 2);
+    }
+
     #[test]
     fn suspend_and_reinstate_privileges() {
         let env = Env::default();
@@ -1153,6 +2712,7 @@ mod test {
         let ref_provider = Address::generate(&env);
         let committee = Address::generate(&env);
         let authority = Address::generate(&env);
+        setup_verification(&env, &client, &verifier);
 
         let case_id = create_case(&env, &client, &provider, &facility);
         submit_required_docs(&env, &client, case_id);
@@ -1201,6 +2761,11 @@ mod test {
             &1_900_000_000,
         );
 
+        env.ledger().set_timestamp(1_700_035_000);
+        let icu = Symbol::new(&env, "icu");
+        assert!(client.has_active_privilege(&provider, &facility, &icu));
+        assert!(!client.has_active_privilege(&provider, &facility, &Symbol::new(&env, "surgery")));
+
         client.suspend_privileges(
             &provider,
             &facility,
@@ -1211,6 +2776,9 @@ mod test {
             &true,
         );
 
+        assert_eq!(env.events().all().len(), 1);
+        assert!(!client.has_active_privilege(&provider, &facility, &icu));
+
         let after_suspend = client.get_provider_privileges(&provider, &facility);
         assert!(marker_exists(
             &after_suspend.get(0).unwrap().restrictions,
@@ -1223,10 +2791,261 @@ mod test {
         monitoring.push_back(String::from_str(&env, "90-day review"));
         client.reinstate_privileges(&provider, &facility, &authority, &actions, &monitoring);
 
+        assert!(client.has_active_privilege(&provider, &facility, &icu));
+
         let after_reinstate = client.get_provider_privileges(&provider, &facility);
         assert!(!marker_exists(
             &after_reinstate.get(0).unwrap().restrictions,
             &String::from_str(&env, "SUSPENDED")
         ));
     }
+
+    #[test]
+    fn extend_case_ttl_keeps_closed_cases_live() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+
+        let ttl = |key: DataKey| {
+            env.as_contract(&contract_id, || env.storage().persistent().get_ttl(&key))
+        };
+        assert_eq!(ttl(DataKey::Case(case_id)), shared_ttl::ACTIVE.extend_to);
+
+        client.extend_case_ttl(&case_id);
+        assert_eq!(ttl(DataKey::Case(case_id)), shared_ttl::ARCHIVE.extend_to);
+        assert_eq!(
+            ttl(DataKey::CaseDocuments(case_id)),
+            shared_ttl::ARCHIVE.extend_to
+        );
+
+        assert_eq!(
+            client.try_extend_case_ttl(&(case_id + 1)),
+            Err(Ok(Error::CaseNotFound))
+        );
+    }
+
+    #[test]
+    fn expiration_digest_lists_items_within_horizon() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let other_facility = Address::generate(&env);
+        let verifier = Address::generate(&env);
+        let committee = Address::generate(&env);
+        setup_verification(&env, &client, &verifier);
+
+        let day = SECONDS_PER_DAY;
+        let now = 1_700_100_000;
+        env.ledger().set_timestamp(now);
+
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+        // A DEA registration renewed shortly before it lapses
+        client.submit_credential_document(
+            &case_id,
+            &Symbol::new(&env, "dea"),
+            &BytesN::from_array(&env, &[9; 32]),
+            &String::from_str(&env, "DEA"),
+            &1_700_000_000,
+            &Some(now + 20 * day),
+        );
+        let verify_types = ["medical_license", "dea", "board_cert", "cv", "references"];
+        for credential_type in verify_types {
+            client.verify_credential(
+                &case_id,
+                &Symbol::new(&env, credential_type),
+                &verifier,
+                &Symbol::new(&env, "primary_source"),
+                &true,
+                &now,
+                &String::from_str(&env, "Verified"),
+            );
+        }
+        let dbs = Vec::from_array(&env, [Symbol::new(&env, "NPDB")]);
+        client.check_sanctions(&case_id, &committee, &dbs, &false, &now);
+        client.conduct_peer_reference(
+            &case_id,
+            &committee,
+            &Vec::from_array(
+                &env,
+                [CompetencyRating {
+                    competency_area: Symbol::new(&env, "clinical_judgment"),
+                    rating: 4,
+                    clinical_examples: true,
+                }],
+            ),
+            &BytesN::from_array(&env, &[7; 32]),
+            &true,
+        );
+        client.grant_privileges(
+            &case_id,
+            &committee,
+            &Vec::from_array(
+                &env,
+                [Symbol::new(&env, "icu"), Symbol::new(&env, "surgery")],
+            ),
+            &None,
+            &now,
+            &(now + 45 * day),
+        );
+
+        // Another facility's cases stay out of this facility's digest
+        let elsewhere = create_case(&env, &client, &provider, &other_facility);
+        submit_required_docs(&env, &client, elsewhere);
+
+        let digest_id = client.generate_expiration_digest(&facility, &30);
+        assert_eq!(env.events().all().len(), 1);
+        let digest = client.get_expiration_digest(&digest_id);
+        assert_eq!(digest.horizon_end, now + 30 * day);
+        assert_eq!(digest.privileges_expiring, 0);
+        assert_eq!(digest.documents_expiring, 1);
+        let dea = digest.documents.get(0).unwrap();
+        assert_eq!(dea.case_id, case_id);
+        assert_eq!(dea.document_type, Symbol::new(&env, "dea"));
+        assert_eq!(dea.expiration_date, now + 20 * day);
+
+        let quarterly = client.generate_expiration_digest(&facility, &90);
+        let digest = client.get_expiration_digest(&quarterly);
+        assert_eq!(digest.privileges_expiring, 2);
+        assert_eq!(digest.documents_expiring, 1);
+        let privilege_ids: Vec<u64> = Vec::from_array(
+            &env,
+            [
+                digest.privileges.get(0).unwrap().privilege_id,
+                digest.privileges.get(1).unwrap().privilege_id,
+            ],
+        );
+        assert_eq!(privilege_ids, Vec::from_array(&env, [1, 2]));
+
+        assert_eq!(
+            client.get_facility_digests(&facility, &0, &10),
+            Vec::from_array(&env, [digest_id, quarterly])
+        );
+        assert_eq!(
+            client.get_facility_digests(&other_facility, &0, &10).len(),
+            0
+        );
+        assert_eq!(
+            client.try_generate_expiration_digest(&facility, &0),
+            Err(Ok(Error::InvalidInput))
+        );
+        assert_eq!(
+            client.try_get_expiration_digest(&99),
+            Err(Ok(Error::DigestNotFound))
+        );
+    }
+
+    #[test]
+    fn verifier_turnaround_stats_and_ranking() {
+        let env = Env::default();
+        env.mock_all_auths();
+
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+
+        let provider = Address::generate(&env);
+        let facility = Address::generate(&env);
+        let other_facility = Address::generate(&env);
+        let fast = Address::generate(&env);
+        let slow = Address::generate(&env);
+        let admin = setup_verification(&env, &client, &fast);
+        for credential_type in ["medical_license", "dea", "board_cert", "cv", "references"] {
+            client.authorize_verifier(&admin, &slow, &Symbol::new(&env, credential_type), &None);
+        }
+
+        let day = SECONDS_PER_DAY;
+        let submitted = 1_700_000_000;
+        env.ledger().set_timestamp(submitted);
+        let case_id = create_case(&env, &client, &provider, &facility);
+        submit_required_docs(&env, &client, case_id);
+
+        let verify =
+            |case_id: u64, credential_type: &str, verifier: &Address, ok: bool, days: u64| {
+                client.verify_credential(
+                    &case_id,
+                    &Symbol::new(&env, credential_type),
+                    verifier,
+                    &Symbol::new(&env, "primary_source"),
+                    &ok,
+                    &(submitted + days * day),
+                    &String::from_str(&env, "Checked"),
+                );
+            };
+        verify(case_id, "medical_license", &fast, true, 1);
+        verify(case_id, "dea", &fast, false, 2);
+        verify(case_id, "board_cert", &slow, true, 10);
+        verify(case_id, "cv", &slow, true, 5);
+
+        let stats = client.get_verifier_stats(&fast).unwrap();
+        assert_eq!(stats.verified_count, 1);
+        assert_eq!(stats.failed_count, 1);
+        assert_eq!(stats.total_turnaround_seconds, 3 * day);
+        assert_eq!(stats.avg_turnaround_days_x100, 150);
+
+        let ranking = client.get_facility_verifier_ranking(&facility);
+        assert_eq!(ranking.len(), 2);
+        assert_eq!(ranking.get(0).unwrap().verifier, fast);
+        assert_eq!(ranking.get(1).unwrap().verifier, slow);
+        assert_eq!(ranking.get(1).unwrap().avg_turnaround_days_x100, 750);
+
+        // Work at another facility counts toward the verifier's overall stats only
+        let elsewhere = create_case(&env, &client, &provider, &other_facility);
+        submit_required_docs(&env, &client, elsewhere);
+        verify(elsewhere, "medical_license", &slow, true, 0);
+        assert_eq!(
+            client
+                .get_verifier_stats(&slow)
+                .unwrap()
+                .avg_turnaround_days_x100,
+            500
+        );
+        assert_eq!(
+            client
+                .get_facility_verifier_ranking(&facility)
+                .get(1)
+                .unwrap(),
+            ranking.get(1).unwrap()
+        );
+        assert_eq!(
+            client.get_facility_verifier_ranking(&other_facility).len(),
+            1
+        );
+        assert_eq!(client.get_verifier_stats(&Address::generate(&env)), None);
+    }
+
+    #[test]
+    fn schema_version_and_migrate() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let contract_id = env.register(HealthcareCredentialingSystem, ());
+        let client = HealthcareCredentialingSystemClient::new(&env, &contract_id);
+        let admin = Address::generate(&env);
+        let stranger = Address::generate(&env);
+
+        assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+        client.initialize(&admin);
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+        assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::NotAuthorized)));
+        assert_eq!(
+            client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+            Err(Ok(Error::NotAuthorized))
+        );
+
+        // Re-running at the current version is a no-op.
+        assert_eq!(client.migrate(&admin), SCHEMA_VERSION);
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    }
 }
@@ -0,0 +1,9 @@
+use soroban_sdk::{contractevent, BytesN};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadmissionLinked {
+    #[topic]
+    pub readmission_episode: BytesN<32>,
+    pub index_episode: BytesN<32>,
+}
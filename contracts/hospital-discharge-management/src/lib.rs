@@ -2,6 +2,7 @@
 
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Symbol, Vec, String};
 
+mod events;
 mod storage;
 mod types;
 mod validation;
@@ -9,15 +10,31 @@ mod validation;
 #[cfg(test)]
 mod test;
 
+use events::*;
 use types::*;
 use storage::*;
 use validation::*;
 
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct HospitalDischargeContract;
 
 #[contractimpl]
 impl HospitalDischargeContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if has_admin(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        save_admin(&env, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     /// Initialize a new discharge planning process
     pub fn initiate_discharge_planning(
         env: Env,
@@ -401,6 +418,161 @@ impl HospitalDischargeContract {
         Ok(())
     }
 
+    /// Configure the readmission window for a hospital's index admissions
+    pub fn set_readmission_window(
+        env: Env,
+        caller: Address,
+        hospital_id: BytesN<32>,
+        window_days: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        if window_days == 0 {
+            return Err(Error::InvalidDates);
+        }
+        save_readmission_window(&env, &hospital_id, window_days);
+
+        Ok(())
+    }
+
+    /// Record an admission episode. If the patient was discharged from an
+    /// earlier episode within that hospital's readmission window, the new
+    /// episode is linked to it as a readmission.
+    pub fn record_admission_episode(
+        env: Env,
+        caller: Address,
+        episode_id: BytesN<32>,
+        patient_id: BytesN<32>,
+        hospital_id: BytesN<32>,
+        admission_date: u64,
+        discharge_date: Option<u64>,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        if episode_exists(&env, &episode_id) {
+            return Err(Error::EpisodeExists);
+        }
+        if let Some(discharged) = discharge_date {
+            validate_dates(&env, admission_date, discharged)?;
+        }
+
+        // Most recent earlier discharge of the same patient within its window
+        let mut index: Option<(AdmissionEpisode, u64)> = None;
+        for prior_id in get_patient_episodes(&env, &patient_id).iter() {
+            let prior = get_episode(&env, &prior_id)?;
+            let discharged = match prior.discharge_date {
+                Some(discharged) if discharged <= admission_date => discharged,
+                _ => continue,
+            };
+            let window = get_readmission_window(&env, &prior.hospital_id) * SECONDS_PER_DAY;
+            let is_later = match &index {
+                Some((_, best)) => discharged > *best,
+                None => true,
+            };
+            if admission_date - discharged <= window && is_later {
+                index = Some((prior, discharged));
+            }
+        }
+
+        let episode = AdmissionEpisode {
+            episode_id: episode_id.clone(),
+            patient_id: patient_id.clone(),
+            hospital_id: hospital_id.clone(),
+            admission_date,
+            discharge_date,
+            index_episode: index.as_ref().map(|(prior, _)| prior.episode_id.clone()),
+            readmissions: Vec::new(&env),
+            recorded_by: caller.clone(),
+            recorded_at: env.ledger().timestamp(),
+        };
+        save_episode(&env, &episode);
+        add_patient_episode(&env, &patient_id, &episode_id);
+        if discharge_date.is_some() {
+            count_index_admission(&env, &hospital_id);
+        }
+
+        if let Some((mut prior, discharged)) = index {
+            let (admissions, readmitted) = get_readmission_counts(&env, &prior.hospital_id);
+            if prior.readmissions.is_empty() {
+                save_readmission_counts(&env, &prior.hospital_id, (admissions, readmitted + 1));
+            }
+            prior.readmissions.push_back(episode_id.clone());
+            save_episode(&env, &prior);
+
+            add_readmission_link(
+                &env,
+                &prior.hospital_id,
+                &ReadmissionLink {
+                    index_episode: prior.episode_id.clone(),
+                    readmission_episode: episode_id.clone(),
+                    days_after_discharge: (admission_date - discharged) / SECONDS_PER_DAY,
+                },
+            );
+
+            // Emit event
+            ReadmissionLinked {
+                readmission_episode: episode_id,
+                index_episode: prior.episode_id,
+            }
+            .publish(&env);
+        }
+
+        Ok(())
+    }
+
+    /// Record the discharge of an open episode, making it an index admission
+    pub fn record_episode_discharge(
+        env: Env,
+        caller: Address,
+        episode_id: BytesN<32>,
+        discharge_date: u64,
+    ) -> Result<(), Error> {
+        caller.require_auth();
+
+        let mut episode = get_episode(&env, &episode_id)?;
+        if episode.discharge_date.is_some() {
+            return Err(Error::InvalidStatus);
+        }
+        validate_dates(&env, episode.admission_date, discharge_date)?;
+
+        episode.discharge_date = Some(discharge_date);
+        save_episode(&env, &episode);
+        count_index_admission(&env, &episode.hospital_id);
+
+        Ok(())
+    }
+
+    pub fn get_admission_episode(
+        env: Env,
+        episode_id: BytesN<32>,
+    ) -> Result<AdmissionEpisode, Error> {
+        get_episode(&env, &episode_id)
+    }
+
+    /// Aggregate readmissions attributed to the hospital's index admissions
+    pub fn get_readmission_stats(env: Env, hospital_id: BytesN<32>) -> ReadmissionStats {
+        let (index_admissions, readmitted_index_admissions) =
+            get_readmission_counts(&env, &hospital_id);
+        let readmission_rate = if index_admissions == 0 {
+            0
+        } else {
+            (u64::from(readmitted_index_admissions) * 10_000 / u64::from(index_admissions)) as u32
+        };
+
+        ReadmissionStats {
+            window_days: get_readmission_window(&env, &hospital_id),
+            hospital_id,
+            index_admissions,
+            readmitted_index_admissions,
+            readmission_rate,
+        }
+    }
+
+    /// Episodes behind the hospital's readmission stats, oldest link first
+    pub fn get_readmission_episodes(env: Env, hospital_id: BytesN<32>) -> Vec<ReadmissionLink> {
+        get_readmission_links(&env, &hospital_id)
+    }
+
     // Query functions
     pub fn get_discharge_plan(env: Env, discharge_plan_id: u64) -> Result<DischargePlan, Error> {
         get_discharge_plan(&env, discharge_plan_id)
@@ -412,4 +584,34 @@ impl HospitalDischargeContract {
     ) -> Result<ReadinessScore, Error> {
         get_readiness_assessment(&env, discharge_plan_id)
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn count_index_admission(env: &Env, hospital_id: &BytesN<32>) {
+    let (admissions, readmitted) = get_readmission_counts(env, hospital_id);
+    save_readmission_counts(env, hospital_id, (admissions + 1, readmitted));
 }
@@ -1,4 +1,4 @@
-use soroban_sdk::{Env, Symbol};
+use soroban_sdk::{symbol_short, Address, BytesN, Env, Symbol, Vec};
 
 use crate::types::*;
 
@@ -15,6 +15,31 @@ const EDU: Symbol = Symbol::short("EDU");
 const SNF: Symbol = Symbol::short("SNF");
 const COMPLETE: Symbol = Symbol::short("COMPLETE");
 const RISK: Symbol = Symbol::short("RISK");
+const EPISODE: Symbol = symbol_short!("EPISODE");
+const PAT_EPIS: Symbol = symbol_short!("PAT_EPIS");
+const RDM_WIN: Symbol = symbol_short!("RDM_WIN");
+const RDM_STAT: Symbol = symbol_short!("RDM_STAT");
+const RDM_LINK: Symbol = symbol_short!("RDM_LINK");
+const ADMIN: Symbol = symbol_short!("ADMIN");
+
+/// Readmission window when a hospital has not configured one
+pub const DEFAULT_READMISSION_WINDOW_DAYS: u64 = 30;
+
+// Admin
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&ADMIN)
+}
+
+pub fn save_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&ADMIN, admin);
+}
+
+pub fn get_admin(env: &Env) -> Result<Address, Error> {
+    env.storage()
+        .instance()
+        .get(&ADMIN)
+        .ok_or(Error::NotInitialized)
+}
 
 // Counter management
 pub fn get_and_increment_counter(env: &Env) -> u64 {
@@ -103,3 +128,81 @@ pub fn save_discharge_completion(env: &Env, plan_id: u64, completion: &Discharge
 pub fn save_readmission_risk(env: &Env, plan_id: u64, risk: &ReadmissionRisk) {
     env.storage().persistent().set(&(RISK, plan_id), risk);
 }
+
+// Admission episode storage
+pub fn save_episode(env: &Env, episode: &AdmissionEpisode) {
+    env.storage()
+        .persistent()
+        .set(&(EPISODE, episode.episode_id.clone()), episode);
+}
+
+pub fn get_episode(env: &Env, episode_id: &BytesN<32>) -> Result<AdmissionEpisode, Error> {
+    env.storage()
+        .persistent()
+        .get(&(EPISODE, episode_id.clone()))
+        .ok_or(Error::EpisodeNotFound)
+}
+
+pub fn episode_exists(env: &Env, episode_id: &BytesN<32>) -> bool {
+    env.storage()
+        .persistent()
+        .has(&(EPISODE, episode_id.clone()))
+}
+
+pub fn get_patient_episodes(env: &Env, patient_id: &BytesN<32>) -> Vec<BytesN<32>> {
+    env.storage()
+        .persistent()
+        .get(&(PAT_EPIS, patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_patient_episode(env: &Env, patient_id: &BytesN<32>, episode_id: &BytesN<32>) {
+    let mut episodes = get_patient_episodes(env, patient_id);
+    episodes.push_back(episode_id.clone());
+    env.storage()
+        .persistent()
+        .set(&(PAT_EPIS, patient_id.clone()), &episodes);
+}
+
+// Readmission window and statistics storage
+pub fn save_readmission_window(env: &Env, hospital_id: &BytesN<32>, window_days: u64) {
+    env.storage()
+        .persistent()
+        .set(&(RDM_WIN, hospital_id.clone()), &window_days);
+}
+
+pub fn get_readmission_window(env: &Env, hospital_id: &BytesN<32>) -> u64 {
+    env.storage()
+        .persistent()
+        .get(&(RDM_WIN, hospital_id.clone()))
+        .unwrap_or(DEFAULT_READMISSION_WINDOW_DAYS)
+}
+
+/// (index admissions, readmitted index admissions)
+pub fn get_readmission_counts(env: &Env, hospital_id: &BytesN<32>) -> (u32, u32) {
+    env.storage()
+        .persistent()
+        .get(&(RDM_STAT, hospital_id.clone()))
+        .unwrap_or((0, 0))
+}
+
+pub fn save_readmission_counts(env: &Env, hospital_id: &BytesN<32>, counts: (u32, u32)) {
+    env.storage()
+        .persistent()
+        .set(&(RDM_STAT, hospital_id.clone()), &counts);
+}
+
+pub fn get_readmission_links(env: &Env, hospital_id: &BytesN<32>) -> Vec<ReadmissionLink> {
+    env.storage()
+        .persistent()
+        .get(&(RDM_LINK, hospital_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_readmission_link(env: &Env, hospital_id: &BytesN<32>, link: &ReadmissionLink) {
+    let mut links = get_readmission_links(env, hospital_id);
+    links.push_back(link.clone());
+    env.storage()
+        .persistent()
+        .set(&(RDM_LINK, hospital_id.clone()), &links);
+}
@@ -527,3 +527,119 @@ fn test_full_discharge_workflow() {
     let plan = client.get_discharge_plan(&plan_id);
     assert_eq!(plan.status, DischargeStatus::Completed);
 }
+
+#[test]
+fn test_readmission_linked_to_index_admission() {
+    let (env, admin, _patient, patient_id, hospital_id) = create_test_env();
+    let contract_id = env.register(HospitalDischargeContract, ());
+    let client = HospitalDischargeContractClient::new(&env, &contract_id);
+
+    let day = 86_400u64;
+    let index_episode = BytesN::from_array(&env, &[10u8; 32]);
+    let readmission_episode = BytesN::from_array(&env, &[11u8; 32]);
+
+    client.record_admission_episode(
+        &admin,
+        &index_episode,
+        &patient_id,
+        &hospital_id,
+        &day,
+        &Some(5 * day),
+    );
+    // Readmitted 12 days after discharge, inside the default 30-day window
+    client.record_admission_episode(
+        &admin,
+        &readmission_episode,
+        &patient_id,
+        &hospital_id,
+        &(17 * day),
+        &None,
+    );
+
+    let episode = client.get_admission_episode(&readmission_episode);
+    assert_eq!(episode.index_episode, Some(index_episode.clone()));
+    let index = client.get_admission_episode(&index_episode);
+    assert_eq!(index.readmissions.len(), 1);
+    assert_eq!(index.readmissions.get(0).unwrap(), readmission_episode);
+
+    let stats = client.get_readmission_stats(&hospital_id);
+    assert_eq!(stats.window_days, 30);
+    assert_eq!(stats.index_admissions, 1);
+    assert_eq!(stats.readmitted_index_admissions, 1);
+    assert_eq!(stats.readmission_rate, 10_000);
+
+    let links = client.get_readmission_episodes(&hospital_id);
+    assert_eq!(links.len(), 1);
+    let link = links.get(0).unwrap();
+    assert_eq!(link.index_episode, index_episode);
+    assert_eq!(link.readmission_episode, readmission_episode);
+    assert_eq!(link.days_after_discharge, 12);
+
+    // Discharging the readmission makes it an index admission of its own
+    client.record_episode_discharge(&admin, &readmission_episode, &(20 * day));
+    let stats = client.get_readmission_stats(&hospital_id);
+    assert_eq!(stats.index_admissions, 2);
+    assert_eq!(stats.readmission_rate, 5_000);
+}
+
+#[test]
+fn test_admission_outside_window_not_linked() {
+    let (env, admin, _patient, patient_id, hospital_id) = create_test_env();
+    let contract_id = env.register(HospitalDischargeContract, ());
+    let client = HospitalDischargeContractClient::new(&env, &contract_id);
+
+    let day = 86_400u64;
+    let first = BytesN::from_array(&env, &[10u8; 32]);
+    let second = BytesN::from_array(&env, &[11u8; 32]);
+
+    client.set_readmission_window(&admin, &hospital_id, &7u64);
+    client.record_admission_episode(
+        &admin,
+        &first,
+        &patient_id,
+        &hospital_id,
+        &day,
+        &Some(2 * day),
+    );
+    client.record_admission_episode(
+        &admin,
+        &second,
+        &patient_id,
+        &hospital_id,
+        &(20 * day),
+        &None,
+    );
+
+    assert_eq!(client.get_admission_episode(&second).index_episode, None);
+    let stats = client.get_readmission_stats(&hospital_id);
+    assert_eq!(stats.window_days, 7);
+    assert_eq!(stats.index_admissions, 1);
+    assert_eq!(stats.readmitted_index_admissions, 0);
+    assert_eq!(client.get_readmission_episodes(&hospital_id).len(), 0);
+
+    let result =
+        client.try_record_admission_episode(&admin, &first, &patient_id, &hospital_id, &day, &None);
+    assert_eq!(result, Err(Ok(Error::EpisodeExists)));
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, admin, _patient, _patient_id, _hospital_id) = create_test_env();
+    let contract_id = env.register(HospitalDischargeContract, ());
+    let client = HospitalDischargeContractClient::new(&env, &contract_id);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
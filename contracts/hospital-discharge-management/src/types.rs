@@ -8,6 +8,10 @@ pub enum Error {
     PlanNotFound = 2,
     InvalidStatus = 3,
     Unauthorized = 4,
+    EpisodeNotFound = 5,
+    EpisodeExists = 6,
+    AlreadyInitialized = 7,
+    NotInitialized = 8,
 }
 
 #[contracttype]
@@ -161,3 +165,40 @@ pub struct ReadmissionRisk {
     pub tracked_by: Address,
     pub tracked_at: u64,
 }
+
+/// An inpatient admission, identified by an opaque episode id. An episode
+/// that began within the readmission window after an earlier discharge of
+/// the same patient links to that index episode.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdmissionEpisode {
+    pub episode_id: BytesN<32>,
+    pub patient_id: BytesN<32>,
+    pub hospital_id: BytesN<32>,
+    pub admission_date: u64,
+    pub discharge_date: Option<u64>,
+    pub index_episode: Option<BytesN<32>>,
+    pub readmissions: Vec<BytesN<32>>,
+    pub recorded_by: Address,
+    pub recorded_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadmissionLink {
+    pub index_episode: BytesN<32>,
+    pub readmission_episode: BytesN<32>,
+    pub days_after_discharge: u64,
+}
+
+/// Readmissions attributed to the hospital of the index episode.
+/// `readmission_rate` is ×10000 of discharged index admissions.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReadmissionStats {
+    pub hospital_id: BytesN<32>,
+    pub window_days: u64,
+    pub index_admissions: u32,
+    pub readmitted_index_admissions: u32,
+    pub readmission_rate: u32,
+}
@@ -1,9 +1,14 @@
 #![no_std]
+#![allow(clippy::too_many_arguments)]
 
 use soroban_sdk::{
     contract, contracterror, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
 };
 
+mod pacs;
+
+pub use pacs::{Pacs, PacsClient, PacsFilters, PacsStudy};
+
 /// --------------------
 /// Imaging Structures
 /// --------------------
@@ -64,6 +69,59 @@ pub struct FinalReport {
     pub submitted_at: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodedFinding {
+    pub finding_code: String, // RadLex or SNOMED CT code
+    pub laterality: Symbol,   // LEFT, RIGHT, BILATERAL, NONE
+    pub severity: Symbol,     // MILD, MODERATE, SEVERE, CRITICAL
+    pub incidental: bool,
+}
+
+/// Coded content submitted alongside a final report
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StructuredFindings {
+    pub findings: Vec<CodedFinding>,
+    pub bi_rads: Option<u32>,      // 0-6, breast imaging only
+    pub lung_rads: Option<Symbol>, // 0, 1, 2, 3, 4A, 4B, 4X, S; CT only
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StructuredReport {
+    pub order_id: u64,
+    pub findings: Vec<CodedFinding>,
+    pub bi_rads: Option<u32>,
+    pub lung_rads: Option<Symbol>,
+    pub has_incidental_findings: bool,
+    pub submitted_at: u64,
+}
+
+/// Closed-loop notification of an urgent finding to the care team
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalResultCommunication {
+    pub order_id: u64,
+    pub radiologist_id: Address,
+    pub notified_party: Address,
+    pub method: Symbol, // PHONE, IN_PERSON, SECURE_MESSAGE
+    pub communicated_at: u64,
+    pub read_back_confirmed: bool,
+    pub logged_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CriticalResultCompliance {
+    pub urgent_findings: u32,
+    pub confirmed: u32,
+    pub pending: u32,
+    /// Mean seconds from preliminary report to confirmed read-back
+    pub avg_seconds_to_confirmation: u64,
+    pub total_seconds_to_confirmation: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PeerReview {
@@ -74,6 +132,55 @@ pub struct PeerReview {
     pub status: Symbol, // PENDING, COMPLETED, DECLINED
 }
 
+/// Appropriateness rating an admin assigns to a study type for an
+/// indication category.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppropriatenessRule {
+    pub study_type: Symbol,
+    pub indication_category: Symbol,
+    pub rating: Symbol, // APPROPRIATE, MAYBE_APPROPRIATE, RARELY_APPROPRIATE
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppropriatenessScore {
+    pub order_id: u64,
+    pub indication_category: Symbol,
+    pub rating: Symbol,
+    pub override_justification: Option<String>,
+    pub scored_at: u64,
+}
+
+/// Daily slot layout an imaging center offers for one modality. Slots start
+/// `day_start_offset` seconds after midnight UTC and run back to back.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleTemplate {
+    pub imaging_center: Address,
+    pub modality: Symbol,
+    pub day_start_offset: u64,
+    pub slot_duration: u64,
+    pub daily_capacity: u32,
+    pub updated_at: u64,
+}
+
+/// One entry in a patient's imaging timeline. An order linked to a PACS
+/// study appears once, carrying both ids.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimelineEntry {
+    pub source: Symbol, // ORDER, PACS
+    pub order_id: Option<u64>,
+    pub pacs_study_id: Option<u64>,
+    pub occurred_at: u64,
+    pub modality: Symbol,
+    pub body_part: String,
+    pub status: Symbol, // order status, or ARCHIVED for PACS-only studies
+    pub report_available: bool,
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
@@ -89,6 +196,18 @@ pub enum DataKey {
     PeerReview(u64),
     PatientOrders(Address),
     ProviderOrders(Address),
+    Admin,
+    AppropriatenessRule(Symbol, Symbol),
+    OrderAppropriateness(u64),
+    ScheduleTemplate(Address, Symbol),
+    SlotBooking(Address, Symbol, u64),
+    PacsContract,
+    OrderPacsStudy(u64),
+    StructuredReport(u64),
+    PatientIncidentalFindings(Address),
+    CriticalCommunications(u64),
+    CriticalResultCompliance(Address),
+    PendingCriticalResults(Address),
 }
 
 /// --------------------
@@ -107,15 +226,107 @@ pub enum Error {
     PreliminaryReportExists = 6,
     FinalReportExists = 7,
     PeerReviewExists = 8,
+    AlreadyInitialized = 9,
+    NotInitialized = 10,
+    InvalidRating = 11,
+    OverrideJustificationRequired = 12,
+    InvalidTemplate = 13,
+    InvalidSlot = 14,
+    SlotUnavailable = 15,
+    PacsStudyAlreadyLinked = 16,
+    InvalidStructuredReport = 17,
+    CategoryNotApplicable = 18,
+    NoUrgentFindings = 19,
+    CriticalCommunicationRequired = 20,
+    InvalidCommunicationMethod = 21,
 }
 
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct ImagingRadiology;
 
 #[contractimpl]
 impl ImagingRadiology {
+    /// Set the admin who maintains appropriateness rules
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+
+        Ok(())
+    }
+
+    /// Configure the PACS contract whose studies are merged into timelines
+    pub fn set_pacs_contract(env: Env, admin: Address, pacs: Address) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        env.storage().instance().set(&DataKey::PacsContract, &pacs);
+
+        Ok(())
+    }
+
+    /// Register or replace the appropriateness rating for a study type and
+    /// indication category
+    pub fn set_appropriateness_rule(
+        env: Env,
+        admin: Address,
+        study_type: Symbol,
+        indication_category: Symbol,
+        rating: Symbol,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+
+        let stored_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored_admin != admin {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        if rating != Symbol::new(&env, "APPROPRIATE")
+            && rating != Symbol::new(&env, "MAYBE_APPROPRIATE")
+            && rating != Symbol::new(&env, "RARELY_APPROPRIATE")
+        {
+            return Err(Error::InvalidRating);
+        }
+
+        let rule = AppropriatenessRule {
+            study_type: study_type.clone(),
+            indication_category: indication_category.clone(),
+            rating,
+            updated_at: env.ledger().timestamp(),
+        };
+
+        let rule_key = DataKey::AppropriatenessRule(study_type, indication_category);
+        env.storage().persistent().set(&rule_key, &rule);
+
+        Ok(())
+    }
+
     /// Order a new imaging study
-    #[allow(clippy::too_many_arguments)]
+    ///
+    /// When an indication category is given and a rule exists for it, the
+    /// order is scored and the score stored. Rarely appropriate orders that
+    /// are not STAT need an override justification.
     pub fn order_imaging_study(
         env: Env,
         provider_id: Address,
@@ -125,9 +336,26 @@ impl ImagingRadiology {
         contrast_required: bool,
         clinical_indication: String,
         priority: Symbol,
+        indication_category: Option<Symbol>,
+        override_justification: Option<String>,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
 
+        // Score against the appropriateness rule, if any
+        let rule: Option<AppropriatenessRule> = indication_category.and_then(|category| {
+            env.storage()
+                .persistent()
+                .get(&DataKey::AppropriatenessRule(study_type.clone(), category))
+        });
+        if let Some(rule) = &rule {
+            if rule.rating == Symbol::new(&env, "RARELY_APPROPRIATE")
+                && priority != Symbol::new(&env, "STAT")
+                && override_justification.is_none()
+            {
+                return Err(Error::OverrideJustificationRequired);
+            }
+        }
+
         // Get next order ID
         let counter_key = DataKey::OrderCounter;
         let order_id: u64 = env.storage().persistent().get(&counter_key).unwrap_or(0) + 1;
@@ -175,6 +403,20 @@ impl ImagingRadiology {
             .persistent()
             .set(&provider_key, &provider_orders);
 
+        // Store appropriateness score
+        if let Some(rule) = rule {
+            let score = AppropriatenessScore {
+                order_id,
+                indication_category: rule.indication_category,
+                rating: rule.rating,
+                override_justification,
+                scored_at: env.ledger().timestamp(),
+            };
+            env.storage()
+                .persistent()
+                .set(&DataKey::OrderAppropriateness(order_id), &score);
+        }
+
         Ok(order_id)
     }
 
@@ -202,6 +444,15 @@ impl ImagingRadiology {
             return Err(Error::AlreadyScheduled);
         }
 
+        // Book the slot when the center runs a template for this modality
+        book_slot(
+            &env,
+            &imaging_center,
+            &order.study_type,
+            scheduled_time,
+            order_id,
+        )?;
+
         // Create schedule
         let schedule = ImagingSchedule {
             order_id,
@@ -220,6 +471,111 @@ impl ImagingRadiology {
         Ok(())
     }
 
+    /// Set the slot template an imaging center offers for a modality
+    pub fn set_schedule_template(
+        env: Env,
+        imaging_center: Address,
+        modality: Symbol,
+        day_start_offset: u64,
+        slot_duration: u64,
+        daily_capacity: u32,
+    ) -> Result<(), Error> {
+        imaging_center.require_auth();
+
+        if slot_duration == 0
+            || daily_capacity == 0
+            || day_start_offset + slot_duration * daily_capacity as u64 > SECONDS_PER_DAY
+        {
+            return Err(Error::InvalidTemplate);
+        }
+
+        let template = ScheduleTemplate {
+            imaging_center: imaging_center.clone(),
+            modality: modality.clone(),
+            day_start_offset,
+            slot_duration,
+            daily_capacity,
+            updated_at: env.ledger().timestamp(),
+        };
+
+        let template_key = DataKey::ScheduleTemplate(imaging_center, modality);
+        env.storage().persistent().set(&template_key, &template);
+
+        Ok(())
+    }
+
+    /// Move a scheduled study to another time at the same imaging center
+    pub fn reschedule_imaging(
+        env: Env,
+        order_id: u64,
+        imaging_center: Address,
+        new_time: u64,
+    ) -> Result<(), Error> {
+        imaging_center.require_auth();
+
+        // Verify order exists and is still awaiting acquisition
+        let order_key = DataKey::ImagingOrder(order_id);
+        let order: ImagingOrder = env
+            .storage()
+            .persistent()
+            .get(&order_key)
+            .ok_or(Error::OrderNotFound)?;
+        if order.status != Symbol::new(&env, "SCHEDULED") {
+            return Err(Error::InvalidStatus);
+        }
+
+        let schedule_key = DataKey::ImagingSchedule(order_id);
+        let mut schedule: ImagingSchedule = env
+            .storage()
+            .persistent()
+            .get(&schedule_key)
+            .ok_or(Error::InvalidStatus)?;
+        if schedule.imaging_center != imaging_center {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        // Book the new slot before releasing the old one
+        book_slot(&env, &imaging_center, &order.study_type, new_time, order_id)?;
+        let old_booking =
+            DataKey::SlotBooking(imaging_center, order.study_type, schedule.scheduled_time);
+        env.storage().persistent().remove(&old_booking);
+
+        schedule.scheduled_time = new_time;
+        schedule.scheduled_at = env.ledger().timestamp();
+        env.storage().persistent().set(&schedule_key, &schedule);
+
+        Ok(())
+    }
+
+    /// Link an order to the PACS study it produced
+    pub fn link_pacs_study(
+        env: Env,
+        order_id: u64,
+        provider_id: Address,
+        pacs_study_id: u64,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        // Verify order exists and belongs to the provider
+        let order_key = DataKey::ImagingOrder(order_id);
+        let order: ImagingOrder = env
+            .storage()
+            .persistent()
+            .get(&order_key)
+            .ok_or(Error::OrderNotFound)?;
+        if order.provider_id != provider_id {
+            return Err(Error::UnauthorizedAccess);
+        }
+
+        let link_key = DataKey::OrderPacsStudy(order_id);
+        if env.storage().persistent().has(&link_key) {
+            return Err(Error::PacsStudyAlreadyLinked);
+        }
+        env.storage().persistent().set(&link_key, &pacs_study_id);
+
+        Ok(())
+    }
+
     /// Upload DICOM images for a study
     pub fn upload_images(
         env: Env,
@@ -293,6 +649,23 @@ impl ImagingRadiology {
             return Err(Error::PreliminaryReportExists);
         }
 
+        // Track urgent findings until read-back is confirmed
+        if urgent_findings {
+            let mut compliance = load_critical_compliance(&env, &radiologist_id);
+            compliance.urgent_findings += 1;
+            compliance.pending += 1;
+            save_critical_compliance(&env, &radiologist_id, &compliance);
+
+            let pending_key = DataKey::PendingCriticalResults(radiologist_id.clone());
+            let mut pending: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&pending_key)
+                .unwrap_or(Vec::new(&env));
+            pending.push_back(order_id);
+            env.storage().persistent().set(&pending_key, &pending);
+        }
+
         // Create preliminary report
         let report = PreliminaryReport {
             order_id,
@@ -307,13 +680,15 @@ impl ImagingRadiology {
         Ok(())
     }
 
-    /// Submit final report
+    /// Submit final report, optionally with coded findings and a BI-RADS or
+    /// Lung-RADS category
     pub fn submit_final_report(
         env: Env,
         order_id: u64,
         radiologist_id: Address,
         final_report_hash: BytesN<32>,
         impression: String,
+        structured: Option<StructuredFindings>,
     ) -> Result<(), Error> {
         radiologist_id.require_auth();
 
@@ -337,6 +712,17 @@ impl ImagingRadiology {
             return Err(Error::FinalReportExists);
         }
 
+        // Urgent findings must be communicated with read-back first
+        if let Some(prelim) = env
+            .storage()
+            .persistent()
+            .get::<_, PreliminaryReport>(&DataKey::PreliminaryReport(order_id))
+        {
+            if prelim.urgent_findings && !read_back_confirmed(&env, order_id) {
+                return Err(Error::CriticalCommunicationRequired);
+            }
+        }
+
         // Create final report
         let report = FinalReport {
             order_id,
@@ -346,6 +732,26 @@ impl ImagingRadiology {
             submitted_at: env.ledger().timestamp(),
         };
 
+        // Validate and store coded content
+        if let Some(structured) = structured {
+            let structured_report = build_structured_report(&env, &order, structured)?;
+            if structured_report.has_incidental_findings {
+                let incidental_key = DataKey::PatientIncidentalFindings(order.patient_id.clone());
+                let mut incidental_orders: Vec<u64> = env
+                    .storage()
+                    .persistent()
+                    .get(&incidental_key)
+                    .unwrap_or(Vec::new(&env));
+                incidental_orders.push_back(order_id);
+                env.storage()
+                    .persistent()
+                    .set(&incidental_key, &incidental_orders);
+            }
+            env.storage()
+                .persistent()
+                .set(&DataKey::StructuredReport(order_id), &structured_report);
+        }
+
         env.storage().persistent().set(&final_key, &report);
 
         // Update order status to completed
@@ -355,6 +761,79 @@ impl ImagingRadiology {
         Ok(())
     }
 
+    /// Log communication of an urgent finding. The first entry with
+    /// read-back confirmed closes the loop for the order.
+    pub fn log_critical_communication(
+        env: Env,
+        order_id: u64,
+        radiologist_id: Address,
+        notified_party: Address,
+        method: Symbol,
+        communicated_at: u64,
+        read_back_confirmed: bool,
+    ) -> Result<(), Error> {
+        radiologist_id.require_auth();
+
+        // Only orders with urgent preliminary findings need the loop closed
+        let prelim: PreliminaryReport = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PreliminaryReport(order_id))
+            .ok_or(Error::NoUrgentFindings)?;
+        if !prelim.urgent_findings {
+            return Err(Error::NoUrgentFindings);
+        }
+
+        let methods = ["PHONE", "IN_PERSON", "SECURE_MESSAGE"];
+        if !methods.iter().any(|m| method == Symbol::new(&env, m)) {
+            return Err(Error::InvalidCommunicationMethod);
+        }
+
+        // Close the loop on the first confirmed read-back
+        if read_back_confirmed && !self::read_back_confirmed(&env, order_id) {
+            let mut compliance = load_critical_compliance(&env, &prelim.radiologist_id);
+            compliance.confirmed += 1;
+            compliance.pending = compliance.pending.saturating_sub(1);
+            compliance.total_seconds_to_confirmation +=
+                communicated_at.saturating_sub(prelim.submitted_at);
+            compliance.avg_seconds_to_confirmation =
+                compliance.total_seconds_to_confirmation / compliance.confirmed as u64;
+            save_critical_compliance(&env, &prelim.radiologist_id, &compliance);
+
+            let pending_key = DataKey::PendingCriticalResults(prelim.radiologist_id.clone());
+            let mut pending: Vec<u64> = env
+                .storage()
+                .persistent()
+                .get(&pending_key)
+                .unwrap_or(Vec::new(&env));
+            if let Some(index) = pending.first_index_of(order_id) {
+                pending.remove(index);
+            }
+            env.storage().persistent().set(&pending_key, &pending);
+        }
+
+        let communication = CriticalResultCommunication {
+            order_id,
+            radiologist_id,
+            notified_party,
+            method,
+            communicated_at,
+            read_back_confirmed,
+            logged_at: env.ledger().timestamp(),
+        };
+
+        let log_key = DataKey::CriticalCommunications(order_id);
+        let mut log: Vec<CriticalResultCommunication> = env
+            .storage()
+            .persistent()
+            .get(&log_key)
+            .unwrap_or(Vec::new(&env));
+        log.push_back(communication);
+        env.storage().persistent().set(&log_key, &log);
+
+        Ok(())
+    }
+
     /// Request peer review
     pub fn request_peer_review(
         env: Env,
@@ -397,6 +876,69 @@ impl ImagingRadiology {
         env.storage().persistent().get(&key)
     }
 
+    /// Get the appropriateness rule for a study type and indication category
+    pub fn get_appropriateness_rule(
+        env: Env,
+        study_type: Symbol,
+        indication_category: Symbol,
+    ) -> Option<AppropriatenessRule> {
+        let key = DataKey::AppropriatenessRule(study_type, indication_category);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get the appropriateness score stored for an order
+    pub fn get_order_appropriateness(env: Env, order_id: u64) -> Option<AppropriatenessScore> {
+        let key = DataKey::OrderAppropriateness(order_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get the slot template an imaging center offers for a modality
+    pub fn get_schedule_template(
+        env: Env,
+        imaging_center: Address,
+        modality: Symbol,
+    ) -> Option<ScheduleTemplate> {
+        let key = DataKey::ScheduleTemplate(imaging_center, modality);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get open slot start times in `[from, to)`, earliest first, up to one
+    /// page of results
+    pub fn get_available_slots(
+        env: Env,
+        imaging_center: Address,
+        modality: Symbol,
+        from: u64,
+        to: u64,
+    ) -> Vec<u64> {
+        let mut slots = Vec::new(&env);
+        let template_key = DataKey::ScheduleTemplate(imaging_center.clone(), modality.clone());
+        let template: ScheduleTemplate = match env.storage().persistent().get(&template_key) {
+            Some(template) => template,
+            None => return slots,
+        };
+
+        let mut day = from - from % SECONDS_PER_DAY;
+        while day < to {
+            for i in 0..template.daily_capacity as u64 {
+                let slot = day + template.day_start_offset + i * template.slot_duration;
+                if slot < from {
+                    continue;
+                }
+                if slot >= to || slots.len() >= shared_pagination::MAX_PAGE_SIZE {
+                    return slots;
+                }
+                let booking = DataKey::SlotBooking(imaging_center.clone(), modality.clone(), slot);
+                if !env.storage().persistent().has(&booking) {
+                    slots.push_back(slot);
+                }
+            }
+            day += SECONDS_PER_DAY;
+        }
+
+        slots
+    }
+
     /// Get imaging schedule
     pub fn get_imaging_schedule(env: Env, order_id: u64) -> Option<ImagingSchedule> {
         let key = DataKey::ImagingSchedule(order_id);
@@ -421,6 +963,64 @@ impl ImagingRadiology {
         env.storage().persistent().get(&key)
     }
 
+    /// Get the coded findings submitted with a final report
+    pub fn get_structured_report(env: Env, order_id: u64) -> Option<StructuredReport> {
+        let key = DataKey::StructuredReport(order_id);
+        env.storage().persistent().get(&key)
+    }
+
+    /// Get orders whose final report flagged incidental findings for a patient
+    pub fn get_patient_incidental_findings(
+        env: Env,
+        patient_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let key = DataKey::PatientIncidentalFindings(patient_id);
+        let orders = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
+    /// Get the critical result communication log for an order
+    pub fn get_critical_communications(
+        env: Env,
+        order_id: u64,
+    ) -> Vec<CriticalResultCommunication> {
+        let key = DataKey::CriticalCommunications(order_id);
+        env.storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Get a radiologist's critical result communication compliance
+    pub fn get_critical_result_compliance(
+        env: Env,
+        radiologist_id: Address,
+    ) -> CriticalResultCompliance {
+        load_critical_compliance(&env, &radiologist_id)
+    }
+
+    /// Get a radiologist's orders with urgent findings still awaiting read-back
+    pub fn get_pending_critical_results(
+        env: Env,
+        radiologist_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let key = DataKey::PendingCriticalResults(radiologist_id);
+        let orders = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
     /// Get peer review request
     pub fn get_peer_review(env: Env, order_id: u64) -> Option<PeerReview> {
         let key = DataKey::PeerReview(order_id);
@@ -428,22 +1028,306 @@ impl ImagingRadiology {
     }
 
     /// Get all orders for a patient
-    pub fn get_patient_orders(env: Env, patient_id: Address) -> Vec<u64> {
+    pub fn get_patient_orders(env: Env, patient_id: Address, offset: u32, limit: u32) -> Vec<u64> {
         let key = DataKey::PatientOrders(patient_id);
-        env.storage()
+        let orders = env
+            .storage()
             .persistent()
             .get(&key)
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
+    /// Get a patient's imaging history, oldest first: every order plus the
+    /// PACS studies the requester can see, with linked studies merged into
+    /// their order
+    pub fn get_patient_imaging_timeline(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<TimelineEntry> {
+        requester.require_auth();
+
+        let pacs_studies: Vec<PacsStudy> = match env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::PacsContract)
+        {
+            Some(pacs) => PacsClient::new(&env, &pacs).search_imaging_studies(
+                &patient_id,
+                &requester,
+                &PacsFilters {
+                    modality: None,
+                    body_part: None,
+                    start_date: None,
+                    end_date: None,
+                    has_critical_findings: None,
+                },
+            ),
+            None => Vec::new(&env),
+        };
+
+        let mut timeline = Vec::new(&env);
+        let mut linked_studies: Vec<u64> = Vec::new(&env);
+
+        let order_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientOrders(patient_id))
+            .unwrap_or(Vec::new(&env));
+        for order_id in order_ids.iter() {
+            let order: ImagingOrder = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::ImagingOrder(order_id))
+            {
+                Some(order) => order,
+                None => continue,
+            };
+
+            let pacs_study_id: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::OrderPacsStudy(order_id));
+            let pacs_study =
+                pacs_study_id.and_then(|id| pacs_studies.iter().find(|study| study.study_id == id));
+            if let Some(id) = pacs_study_id {
+                linked_studies.push_back(id);
+            }
+
+            let occurred_at = match env
+                .storage()
+                .persistent()
+                .get::<_, DicomImages>(&DataKey::DicomImages(order_id))
+            {
+                Some(images) => images.study_date,
+                None => match env
+                    .storage()
+                    .persistent()
+                    .get::<_, ImagingSchedule>(&DataKey::ImagingSchedule(order_id))
+                {
+                    Some(schedule) => schedule.scheduled_time,
+                    None => order.ordered_at,
+                },
+            };
+            let report_available = env
+                .storage()
+                .persistent()
+                .has(&DataKey::FinalReport(order_id))
+                || pacs_study.as_ref().is_some_and(|study| study.has_report);
+
+            insert_chronologically(
+                &mut timeline,
+                TimelineEntry {
+                    source: Symbol::new(&env, "ORDER"),
+                    order_id: Some(order_id),
+                    pacs_study_id,
+                    occurred_at,
+                    modality: order.study_type,
+                    body_part: order.body_part,
+                    status: order.status,
+                    report_available,
+                },
+            );
+        }
+
+        for study in pacs_studies.iter() {
+            if linked_studies.contains(study.study_id) {
+                continue;
+            }
+            insert_chronologically(
+                &mut timeline,
+                TimelineEntry {
+                    source: Symbol::new(&env, "PACS"),
+                    order_id: None,
+                    pacs_study_id: Some(study.study_id),
+                    occurred_at: study.study_date,
+                    modality: study.modality,
+                    body_part: study.body_part,
+                    status: Symbol::new(&env, "ARCHIVED"),
+                    report_available: study.has_report,
+                },
+            );
+        }
+
+        shared_pagination::paginate(&timeline, offset, limit)
     }
 
     /// Get all orders by a provider
-    pub fn get_provider_orders(env: Env, provider_id: Address) -> Vec<u64> {
+    pub fn get_provider_orders(
+        env: Env,
+        provider_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
         let key = DataKey::ProviderOrders(provider_id);
-        env.storage()
+        let orders = env
+            .storage()
             .persistent()
             .get(&key)
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
     }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if stored != *admin {
+        return Err(Error::UnauthorizedAccess);
+    }
+    Ok(())
+}
+
+fn read_back_confirmed(env: &Env, order_id: u64) -> bool {
+    let log: Vec<CriticalResultCommunication> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CriticalCommunications(order_id))
+        .unwrap_or(Vec::new(env));
+    log.iter().any(|entry| entry.read_back_confirmed)
+}
+
+fn load_critical_compliance(env: &Env, radiologist_id: &Address) -> CriticalResultCompliance {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CriticalResultCompliance(radiologist_id.clone()))
+        .unwrap_or_default()
+}
+
+fn save_critical_compliance(
+    env: &Env,
+    radiologist_id: &Address,
+    compliance: &CriticalResultCompliance,
+) {
+    env.storage().persistent().set(
+        &DataKey::CriticalResultCompliance(radiologist_id.clone()),
+        compliance,
+    );
+}
+
+/// Validate coded findings and categories against the order's modality
+fn build_structured_report(
+    env: &Env,
+    order: &ImagingOrder,
+    structured: StructuredFindings,
+) -> Result<StructuredReport, Error> {
+    let lateralities = ["LEFT", "RIGHT", "BILATERAL", "NONE"];
+    let severities = ["MILD", "MODERATE", "SEVERE", "CRITICAL"];
+
+    let mut has_incidental_findings = false;
+    for finding in structured.findings.iter() {
+        if finding.finding_code.is_empty()
+            || !lateralities
+                .iter()
+                .any(|l| finding.laterality == Symbol::new(env, l))
+            || !severities
+                .iter()
+                .any(|s| finding.severity == Symbol::new(env, s))
+        {
+            return Err(Error::InvalidStructuredReport);
+        }
+        has_incidental_findings |= finding.incidental;
+    }
+
+    if let Some(bi_rads) = structured.bi_rads {
+        if bi_rads > 6 {
+            return Err(Error::InvalidStructuredReport);
+        }
+        let breast_modalities = ["MAMMO", "ULTRASOUND", "MRI"];
+        if !breast_modalities
+            .iter()
+            .any(|m| order.study_type == Symbol::new(env, m))
+        {
+            return Err(Error::CategoryNotApplicable);
+        }
+    }
+
+    if let Some(lung_rads) = &structured.lung_rads {
+        let categories = ["0", "1", "2", "3", "4A", "4B", "4X", "S"];
+        if !categories.iter().any(|c| *lung_rads == Symbol::new(env, c)) {
+            return Err(Error::InvalidStructuredReport);
+        }
+        if order.study_type != Symbol::new(env, "CT") {
+            return Err(Error::CategoryNotApplicable);
+        }
+    }
+
+    Ok(StructuredReport {
+        order_id: order.order_id,
+        findings: structured.findings,
+        bi_rads: structured.bi_rads,
+        lung_rads: structured.lung_rads,
+        has_incidental_findings,
+        submitted_at: env.ledger().timestamp(),
+    })
+}
+
+/// Insert after any entries at the same time, keeping the timeline stable
+fn insert_chronologically(timeline: &mut Vec<TimelineEntry>, entry: TimelineEntry) {
+    let mut index = timeline.len();
+    while index > 0 && timeline.get_unchecked(index - 1).occurred_at > entry.occurred_at {
+        index -= 1;
+    }
+    timeline.insert(index, entry);
+}
+
+/// Reserve the slot starting at `slot` for an order. Centers without a
+/// template for the modality accept any time.
+fn book_slot(
+    env: &Env,
+    imaging_center: &Address,
+    modality: &Symbol,
+    slot: u64,
+    order_id: u64,
+) -> Result<(), Error> {
+    let template_key = DataKey::ScheduleTemplate(imaging_center.clone(), modality.clone());
+    let template: ScheduleTemplate = match env.storage().persistent().get(&template_key) {
+        Some(template) => template,
+        None => return Ok(()),
+    };
+
+    let offset = slot % SECONDS_PER_DAY;
+    if offset < template.day_start_offset {
+        return Err(Error::InvalidSlot);
+    }
+    let since_start = offset - template.day_start_offset;
+    if !since_start.is_multiple_of(template.slot_duration)
+        || since_start / template.slot_duration >= template.daily_capacity as u64
+    {
+        return Err(Error::InvalidSlot);
+    }
+
+    let booking_key = DataKey::SlotBooking(imaging_center.clone(), modality.clone(), slot);
+    if env.storage().persistent().has(&booking_key) {
+        return Err(Error::SlotUnavailable);
+    }
+    env.storage().persistent().set(&booking_key, &order_id);
+
+    Ok(())
 }
 
 #[cfg(test)]
@@ -0,0 +1,45 @@
+//! Client interface for the pacs-integration contract, used to merge archived
+//! studies into a patient's imaging timeline.
+
+use soroban_sdk::{contractclient, contracttype, Address, BytesN, Env, String, Symbol, Vec};
+
+/// Mirror of the PACS contract's `ImagingStudy`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PacsStudy {
+    pub study_id: u64,
+    pub patient_id: Address,
+    pub ordering_provider: Address,
+    pub study_uid: String,
+    pub modality: Symbol,
+    pub body_part: String,
+    pub study_date: u64,
+    pub study_description: String,
+    pub series_count: u32,
+    pub image_count: u32,
+    pub storage_location_hash: BytesN<32>,
+    pub has_report: bool,
+    pub critical_findings: bool,
+    pub registered_at: u64,
+}
+
+/// Mirror of the PACS contract's `ImagingFilters`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PacsFilters {
+    pub modality: Option<Symbol>,
+    pub body_part: Option<String>,
+    pub start_date: Option<u64>,
+    pub end_date: Option<u64>,
+    pub has_critical_findings: Option<bool>,
+}
+
+#[contractclient(name = "PacsClient")]
+pub trait Pacs {
+    fn search_imaging_studies(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        filters: PacsFilters,
+    ) -> Vec<PacsStudy>;
+}
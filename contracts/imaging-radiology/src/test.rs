@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec as svec, Env};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    vec as svec, Env,
+};
 
 #[test]
 fn test_order_imaging_study() {
@@ -26,6 +29,8 @@ fn test_order_imaging_study() {
         &true,
         &clinical_indication,
         &priority,
+        &None,
+        &None,
     );
 
     assert_eq!(order_id, 1);
@@ -58,6 +63,8 @@ fn test_multiple_imaging_orders() {
         &true,
         &String::from_str(&env, "Abdominal pain"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     // Order 2: X-Ray
@@ -69,13 +76,15 @@ fn test_multiple_imaging_orders() {
         &false,
         &String::from_str(&env, "Cough"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     assert_eq!(order_id1, 1);
     assert_eq!(order_id2, 2);
 
     // Verify patient has both orders
-    let patient_orders = client.get_patient_orders(&patient);
+    let patient_orders = client.get_patient_orders(&patient, &0, &10);
     assert_eq!(patient_orders.len(), 2);
 }
 
@@ -99,6 +108,8 @@ fn test_schedule_imaging() {
         &true,
         &String::from_str(&env, "Headaches"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     // Schedule imaging
@@ -137,6 +148,8 @@ fn test_schedule_imaging_already_scheduled() {
         &false,
         &String::from_str(&env, "Pain"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     let scheduled_time = env.ledger().timestamp() + 3600;
@@ -169,6 +182,8 @@ fn test_upload_images() {
         &true,
         &String::from_str(&env, "Trauma"),
         &Symbol::new(&env, "STAT"),
+        &None,
+        &None,
     );
 
     let scheduled_time = env.ledger().timestamp() + 1800;
@@ -218,6 +233,8 @@ fn test_upload_images_already_uploaded() {
         &false,
         &String::from_str(&env, "Fracture"),
         &Symbol::new(&env, "URGENT"),
+        &None,
+        &None,
     );
 
     let dicom_hash = BytesN::from_array(&env, &[10u8; 32]);
@@ -251,6 +268,8 @@ fn test_submit_preliminary_report() {
         &true,
         &String::from_str(&env, "Stroke workup"),
         &Symbol::new(&env, "STAT"),
+        &None,
+        &None,
     );
 
     let dicom_hash = BytesN::from_array(&env, &[20u8; 32]);
@@ -292,6 +311,8 @@ fn test_submit_preliminary_report_without_images() {
         &false,
         &String::from_str(&env, "Back pain"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     // Try to submit report without images
@@ -320,6 +341,8 @@ fn test_submit_final_report() {
         &false,
         &String::from_str(&env, "Screening"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     let dicom_hash = BytesN::from_array(&env, &[50u8; 32]);
@@ -335,7 +358,7 @@ fn test_submit_final_report() {
     let final_hash = BytesN::from_array(&env, &[60u8; 32]);
     let impression = String::from_str(&env, "No evidence of malignancy. BI-RADS 1.");
 
-    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression);
+    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
 
     // Verify final report
     let report = client.get_final_report(&order_id).unwrap();
@@ -368,6 +391,8 @@ fn test_submit_final_report_already_exists() {
         &false,
         &String::from_str(&env, "RUQ pain"),
         &Symbol::new(&env, "URGENT"),
+        &None,
+        &None,
     );
 
     let dicom_hash = BytesN::from_array(&env, &[70u8; 32]);
@@ -383,10 +408,10 @@ fn test_submit_final_report_already_exists() {
     let impression = String::from_str(&env, "Normal study");
 
     // First submission succeeds
-    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression);
+    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
 
     // Second submission fails
-    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression);
+    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
 }
 
 #[test]
@@ -410,6 +435,8 @@ fn test_request_peer_review() {
         &false,
         &String::from_str(&env, "Cancer staging"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     // Request peer review
@@ -443,6 +470,8 @@ fn test_request_peer_review_already_exists() {
         &true,
         &String::from_str(&env, "Complex case"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     // First request succeeds
@@ -471,6 +500,8 @@ fn test_get_patient_orders() {
         &false,
         &String::from_str(&env, "Cough"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     let order_id2 = client.order_imaging_study(
@@ -481,10 +512,12 @@ fn test_get_patient_orders() {
         &true,
         &String::from_str(&env, "Pain"),
         &Symbol::new(&env, "URGENT"),
+        &None,
+        &None,
     );
 
     // Get patient orders
-    let orders = client.get_patient_orders(&patient);
+    let orders = client.get_patient_orders(&patient, &0, &10);
     assert_eq!(orders.len(), 2);
     assert_eq!(orders.get(0).unwrap(), order_id1);
     assert_eq!(orders.get(1).unwrap(), order_id2);
@@ -510,6 +543,8 @@ fn test_get_provider_orders() {
         &true,
         &String::from_str(&env, "Headache"),
         &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
     );
 
     let order_id2 = client.order_imaging_study(
@@ -520,10 +555,12 @@ fn test_get_provider_orders() {
         &false,
         &String::from_str(&env, "Injury"),
         &Symbol::new(&env, "URGENT"),
+        &None,
+        &None,
     );
 
     // Get provider orders
-    let orders = client.get_provider_orders(&provider);
+    let orders = client.get_provider_orders(&provider, &0, &10);
     assert_eq!(orders.len(), 2);
     assert_eq!(orders.get(0).unwrap(), order_id1);
     assert_eq!(orders.get(1).unwrap(), order_id2);
@@ -551,6 +588,8 @@ fn test_complete_imaging_workflow() {
         &true,
         &String::from_str(&env, "Cancer staging"),
         &Symbol::new(&env, "URGENT"),
+        &None,
+        &None,
     );
 
     let order = client.get_imaging_order(&order_id).unwrap();
@@ -590,13 +629,23 @@ fn test_complete_imaging_workflow() {
     let peer_review = client.get_peer_review(&order_id).unwrap();
     assert_eq!(peer_review.status, Symbol::new(&env, "PENDING"));
 
-    // 6. Submit final report
+    // 6. Communicate the urgent findings with read-back
+    client.log_critical_communication(
+        &order_id,
+        &radiologist,
+        &provider,
+        &Symbol::new(&env, "PHONE"),
+        &env.ledger().timestamp(),
+        &true,
+    );
+
+    // 7. Submit final report
     let final_hash = BytesN::from_array(&env, &[4u8; 32]);
     let impression = String::from_str(
         &env,
         "Multiple pulmonary nodules. Recommend follow-up CT in 3 months.",
     );
-    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression);
+    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
 
     let order = client.get_imaging_order(&order_id).unwrap();
     assert_eq!(order.status, Symbol::new(&env, "COMPLETED"));
@@ -636,6 +685,8 @@ fn test_multi_modality_support() {
             &false,
             &String::from_str(&env, "Test indication"),
             &Symbol::new(&env, "ROUTINE"),
+            &None,
+            &None,
         );
 
         let order = client.get_imaging_order(&order_id).unwrap();
@@ -671,6 +722,8 @@ fn test_priority_levels() {
             &false,
             &String::from_str(&env, "Test"),
             &priority,
+            &None,
+            &None,
         );
 
         let order = client.get_imaging_order(&order_id).unwrap();
@@ -699,6 +752,8 @@ fn test_urgent_findings_notification() {
         &true,
         &String::from_str(&env, "Acute stroke"),
         &Symbol::new(&env, "STAT"),
+        &None,
+        &None,
     );
 
     // Upload images
@@ -723,3 +778,512 @@ fn test_urgent_findings_notification() {
     let order = client.get_imaging_order(&order_id).unwrap();
     assert_eq!(order.priority, Symbol::new(&env, "STAT"));
 }
+
+#[test]
+fn test_appropriateness_scoring() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+
+    let mri = Symbol::new(&env, "MRI");
+    let low_back_pain = Symbol::new(&env, "LBP_NO_RED_FLAGS");
+    client.set_appropriateness_rule(
+        &admin,
+        &mri,
+        &low_back_pain,
+        &Symbol::new(&env, "RARELY_APPROPRIATE"),
+    );
+
+    // Only the admin may register rules, with a known rating
+    let result = client.try_set_appropriateness_rule(
+        &provider,
+        &mri,
+        &low_back_pain,
+        &Symbol::new(&env, "APPROPRIATE"),
+    );
+    assert_eq!(result, Err(Ok(Error::UnauthorizedAccess)));
+    let result =
+        client.try_set_appropriateness_rule(&admin, &mri, &low_back_pain, &Symbol::new(&env, "OK"));
+    assert_eq!(result, Err(Ok(Error::InvalidRating)));
+
+    // A rarely appropriate routine order needs a justification
+    let result = client.try_order_imaging_study(
+        &provider,
+        &patient,
+        &mri,
+        &String::from_str(&env, "Lumbar spine"),
+        &false,
+        &String::from_str(&env, "Low back pain, 2 weeks"),
+        &Symbol::new(&env, "ROUTINE"),
+        &Some(low_back_pain.clone()),
+        &None,
+    );
+    assert_eq!(result, Err(Ok(Error::OverrideJustificationRequired)));
+
+    let justification = String::from_str(&env, "Progressive neurological deficit");
+    let order_id = client.order_imaging_study(
+        &provider,
+        &patient,
+        &mri,
+        &String::from_str(&env, "Lumbar spine"),
+        &false,
+        &String::from_str(&env, "Low back pain, 2 weeks"),
+        &Symbol::new(&env, "ROUTINE"),
+        &Some(low_back_pain.clone()),
+        &Some(justification.clone()),
+    );
+    let score = client.get_order_appropriateness(&order_id).unwrap();
+    assert_eq!(score.rating, Symbol::new(&env, "RARELY_APPROPRIATE"));
+    assert_eq!(score.override_justification, Some(justification));
+
+    // STAT orders skip the justification requirement but are still scored
+    let stat_order = client.order_imaging_study(
+        &provider,
+        &patient,
+        &mri,
+        &String::from_str(&env, "Lumbar spine"),
+        &false,
+        &String::from_str(&env, "Suspected cord compression"),
+        &Symbol::new(&env, "STAT"),
+        &Some(low_back_pain),
+        &None,
+    );
+    assert!(client.get_order_appropriateness(&stat_order).is_some());
+
+    // Orders without a matching rule are not scored
+    let unscored = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "XRAY"),
+        &String::from_str(&env, "Chest"),
+        &false,
+        &String::from_str(&env, "Cough"),
+        &Symbol::new(&env, "ROUTINE"),
+        &Some(Symbol::new(&env, "COUGH")),
+        &None,
+    );
+    assert!(client.get_order_appropriateness(&unscored).is_none());
+}
+
+#[test]
+fn test_schedule_template_slots() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    env.mock_all_auths();
+
+    let mri = Symbol::new(&env, "MRI");
+    let day = 20 * 86_400u64;
+    let eight_am = 8 * 3_600u64;
+
+    // Four 45-minute MRI slots from 08:00
+    client.set_schedule_template(&imaging_center, &mri, &eight_am, &2_700, &4);
+    let result = client.try_set_schedule_template(&imaging_center, &mri, &eight_am, &0, &4);
+    assert_eq!(result, Err(Ok(Error::InvalidTemplate)));
+
+    let slots = client.get_available_slots(&imaging_center, &mri, &day, &(day + 86_400));
+    assert_eq!(slots.len(), 4);
+    assert_eq!(slots.get(0).unwrap(), day + eight_am);
+    assert_eq!(slots.get(3).unwrap(), day + eight_am + 3 * 2_700);
+
+    let order_mri = |body_part: &str| {
+        client.order_imaging_study(
+            &provider,
+            &patient,
+            &mri,
+            &String::from_str(&env, body_part),
+            &false,
+            &String::from_str(&env, "Follow-up"),
+            &Symbol::new(&env, "ROUTINE"),
+            &None,
+            &None,
+        )
+    };
+    let first = order_mri("Knee");
+    let second = order_mri("Shoulder");
+    let prep_hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    // Times must fall on a free slot
+    let result =
+        client.try_schedule_imaging(&first, &imaging_center, &(day + eight_am + 60), &prep_hash);
+    assert_eq!(result, Err(Ok(Error::InvalidSlot)));
+    client.schedule_imaging(&first, &imaging_center, &(day + eight_am), &prep_hash);
+    let result =
+        client.try_schedule_imaging(&second, &imaging_center, &(day + eight_am), &prep_hash);
+    assert_eq!(result, Err(Ok(Error::SlotUnavailable)));
+    client.schedule_imaging(
+        &second,
+        &imaging_center,
+        &(day + eight_am + 2_700),
+        &prep_hash,
+    );
+
+    let slots = client.get_available_slots(&imaging_center, &mri, &day, &(day + 86_400));
+    assert_eq!(slots.len(), 2);
+
+    // Rescheduling frees the old slot
+    client.reschedule_imaging(&first, &imaging_center, &(day + 86_400 + eight_am));
+    let schedule = client.get_imaging_schedule(&first).unwrap();
+    assert_eq!(schedule.scheduled_time, day + 86_400 + eight_am);
+    let slots = client.get_available_slots(&imaging_center, &mri, &day, &(day + 86_400));
+    assert_eq!(slots.len(), 3);
+    assert_eq!(slots.get(0).unwrap(), day + eight_am);
+    let result =
+        client.try_reschedule_imaging(&second, &imaging_center, &(day + 86_400 + eight_am));
+    assert_eq!(result, Err(Ok(Error::SlotUnavailable)));
+}
+
+// Stand-in for the pacs-integration contract's study search
+#[soroban_sdk::contract]
+struct MockPacs;
+
+#[soroban_sdk::contractimpl]
+impl MockPacs {
+    pub fn add_study(env: Env, study: PacsStudy) {
+        let mut studies: Vec<PacsStudy> = env
+            .storage()
+            .instance()
+            .get(&Symbol::new(&env, "studies"))
+            .unwrap_or(Vec::new(&env));
+        studies.push_back(study);
+        env.storage()
+            .instance()
+            .set(&Symbol::new(&env, "studies"), &studies);
+    }
+
+    pub fn search_imaging_studies(
+        env: Env,
+        _patient_id: Address,
+        _requester: Address,
+        _filters: PacsFilters,
+    ) -> Vec<PacsStudy> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "studies"))
+            .unwrap_or(Vec::new(&env))
+    }
+}
+
+#[test]
+fn test_patient_imaging_timeline() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+    let pacs_id = env.register(MockPacs, ());
+    let pacs = MockPacsClient::new(&env, &pacs_id);
+
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    env.mock_all_auths();
+
+    client.initialize(&admin);
+    client.set_pacs_contract(&admin, &pacs_id);
+
+    let pacs_study = |study_id: u64, modality: &str, study_date: u64, has_report: bool| PacsStudy {
+        study_id,
+        patient_id: patient.clone(),
+        ordering_provider: provider.clone(),
+        study_uid: String::from_str(&env, "1.2.840.1"),
+        modality: Symbol::new(&env, modality),
+        body_part: String::from_str(&env, "Chest"),
+        study_date,
+        study_description: String::from_str(&env, "Prior study"),
+        series_count: 1,
+        image_count: 10,
+        storage_location_hash: BytesN::from_array(&env, &[2u8; 32]),
+        has_report,
+        critical_findings: false,
+        registered_at: study_date,
+    };
+    // An outside study from before any order, and the archive of order 1
+    pacs.add_study(&pacs_study(7, "XRAY", 1_000, true));
+    pacs.add_study(&pacs_study(8, "CT", 5_000, true));
+
+    env.ledger().set_timestamp(3_000);
+    let ct_order = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "CT"),
+        &String::from_str(&env, "Chest"),
+        &true,
+        &String::from_str(&env, "Nodule follow-up"),
+        &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
+    );
+    env.ledger().set_timestamp(4_000);
+    let mri_order = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "MRI"),
+        &String::from_str(&env, "Brain"),
+        &false,
+        &String::from_str(&env, "Headache"),
+        &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
+    );
+
+    let dicom_hash = BytesN::from_array(&env, &[3u8; 32]);
+    client.upload_images(&ct_order, &imaging_center, &dicom_hash, &120, &5_000);
+    client.link_pacs_study(&ct_order, &provider, &8);
+    let result = client.try_link_pacs_study(&ct_order, &provider, &9);
+    assert_eq!(result, Err(Ok(Error::PacsStudyAlreadyLinked)));
+
+    let timeline = client.get_patient_imaging_timeline(&patient, &provider, &0, &10);
+    assert_eq!(timeline.len(), 3);
+
+    let prior = timeline.get(0).unwrap();
+    assert_eq!(prior.source, Symbol::new(&env, "PACS"));
+    assert_eq!(prior.pacs_study_id, Some(7));
+    assert_eq!(prior.status, Symbol::new(&env, "ARCHIVED"));
+    assert!(prior.report_available);
+
+    let mri = timeline.get(1).unwrap();
+    assert_eq!(mri.order_id, Some(mri_order));
+    assert_eq!(mri.occurred_at, 4_000);
+    assert!(!mri.report_available);
+
+    // The CT order and its archived study appear once, dated by acquisition
+    let ct = timeline.get(2).unwrap();
+    assert_eq!(ct.order_id, Some(ct_order));
+    assert_eq!(ct.pacs_study_id, Some(8));
+    assert_eq!(ct.occurred_at, 5_000);
+    assert_eq!(ct.status, Symbol::new(&env, "IN_PROGRESS"));
+    assert!(ct.report_available);
+}
+
+#[test]
+fn test_structured_final_report() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    let radiologist = Address::generate(&env);
+    env.mock_all_auths();
+
+    let order_id = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "CT"),
+        &String::from_str(&env, "Chest"),
+        &false,
+        &String::from_str(&env, "Lung cancer screening"),
+        &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
+    );
+    let dicom_hash = BytesN::from_array(&env, &[5u8; 32]);
+    client.upload_images(&order_id, &imaging_center, &dicom_hash, &300, &1_000);
+
+    let finding = |code: &str, laterality: &str, incidental: bool| CodedFinding {
+        finding_code: String::from_str(&env, code),
+        laterality: Symbol::new(&env, laterality),
+        severity: Symbol::new(&env, "MILD"),
+        incidental,
+    };
+    let report_hash = BytesN::from_array(&env, &[6u8; 32]);
+    let impression = String::from_str(&env, "Solid nodule, Lung-RADS 3");
+
+    // BI-RADS does not apply to a chest CT
+    let result = client.try_submit_final_report(
+        &order_id,
+        &radiologist,
+        &report_hash,
+        &impression,
+        &Some(StructuredFindings {
+            findings: svec![&env, finding("RID50149", "RIGHT", false)],
+            bi_rads: Some(2),
+            lung_rads: None,
+        }),
+    );
+    assert_eq!(result, Err(Ok(Error::CategoryNotApplicable)));
+
+    let result = client.try_submit_final_report(
+        &order_id,
+        &radiologist,
+        &report_hash,
+        &impression,
+        &Some(StructuredFindings {
+            findings: svec![&env, finding("RID50149", "UPPER", false)],
+            bi_rads: None,
+            lung_rads: None,
+        }),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidStructuredReport)));
+
+    client.submit_final_report(
+        &order_id,
+        &radiologist,
+        &report_hash,
+        &impression,
+        &Some(StructuredFindings {
+            findings: svec![
+                &env,
+                finding("RID50149", "RIGHT", false),
+                finding("RID3890", "LEFT", true)
+            ],
+            bi_rads: None,
+            lung_rads: Some(Symbol::new(&env, "3")),
+        }),
+    );
+
+    let structured = client.get_structured_report(&order_id).unwrap();
+    assert_eq!(structured.findings.len(), 2);
+    assert_eq!(structured.lung_rads, Some(Symbol::new(&env, "3")));
+    assert!(structured.has_incidental_findings);
+    assert_eq!(
+        client.get_patient_incidental_findings(&patient, &0, &10),
+        svec![&env, order_id]
+    );
+}
+
+#[test]
+fn test_critical_result_communication_loop() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    let radiologist = Address::generate(&env);
+    env.mock_all_auths();
+
+    let order_id = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "CT"),
+        &String::from_str(&env, "Head"),
+        &false,
+        &String::from_str(&env, "Fall with head strike"),
+        &Symbol::new(&env, "STAT"),
+        &None,
+        &None,
+    );
+    let dicom_hash = BytesN::from_array(&env, &[7u8; 32]);
+    client.upload_images(&order_id, &imaging_center, &dicom_hash, &80, &1_000);
+
+    env.ledger().set_timestamp(2_000);
+    let report_hash = BytesN::from_array(&env, &[8u8; 32]);
+    client.submit_preliminary_report(&order_id, &radiologist, &report_hash, &true);
+    assert_eq!(
+        client.get_pending_critical_results(&radiologist, &0, &10),
+        svec![&env, order_id]
+    );
+
+    // Final sign-off is blocked until read-back is confirmed
+    let final_hash = BytesN::from_array(&env, &[9u8; 32]);
+    let impression = String::from_str(&env, "Acute subdural hematoma");
+    let result =
+        client.try_submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
+    assert_eq!(result, Err(Ok(Error::CriticalCommunicationRequired)));
+
+    let result = client.try_log_critical_communication(
+        &order_id,
+        &radiologist,
+        &provider,
+        &Symbol::new(&env, "FAX"),
+        &2_300,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidCommunicationMethod)));
+
+    // A voicemail without read-back does not close the loop
+    client.log_critical_communication(
+        &order_id,
+        &radiologist,
+        &provider,
+        &Symbol::new(&env, "PHONE"),
+        &2_300,
+        &false,
+    );
+    let result =
+        client.try_submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
+    assert_eq!(result, Err(Ok(Error::CriticalCommunicationRequired)));
+
+    client.log_critical_communication(
+        &order_id,
+        &radiologist,
+        &provider,
+        &Symbol::new(&env, "PHONE"),
+        &2_600,
+        &true,
+    );
+    client.submit_final_report(&order_id, &radiologist, &final_hash, &impression, &None);
+
+    assert_eq!(client.get_critical_communications(&order_id).len(), 2);
+    let compliance = client.get_critical_result_compliance(&radiologist);
+    assert_eq!(compliance.urgent_findings, 1);
+    assert_eq!(compliance.confirmed, 1);
+    assert_eq!(compliance.pending, 0);
+    assert_eq!(compliance.avg_seconds_to_confirmation, 600);
+    assert!(client
+        .get_pending_critical_results(&radiologist, &0, &10)
+        .is_empty());
+
+    // Orders without urgent findings have nothing to log
+    let routine = client.order_imaging_study(
+        &provider,
+        &patient,
+        &Symbol::new(&env, "XRAY"),
+        &String::from_str(&env, "Wrist"),
+        &false,
+        &String::from_str(&env, "Pain"),
+        &Symbol::new(&env, "ROUTINE"),
+        &None,
+        &None,
+    );
+    let result = client.try_log_critical_communication(
+        &routine,
+        &radiologist,
+        &provider,
+        &Symbol::new(&env, "PHONE"),
+        &2_700,
+        &true,
+    );
+    assert_eq!(result, Err(Ok(Error::NoUrgentFindings)));
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let env = Env::default();
+    let contract_id = env.register(ImagingRadiology, ());
+    let client = ImagingRadiologyClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(
+        client.try_migrate(&stranger),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::UnauthorizedAccess))
+    );
+}
@@ -3,8 +3,17 @@
 mod types;
 mod test;
 
-use soroban_sdk::{contract, contractimpl, Env, Address, String, Symbol, Vec, BytesN};
+use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Env, Address, String, Symbol, Vec, BytesN};
 use types::{DataKey, Error, VaccineRecord, AdverseEvent, VaccineSeries};
+pub use types::{
+    DoseForecast, ExemptionRecord, ForecastStatus, ScheduleDose, StatusShare, VaccinationStatus,
+    VaccineSchedule,
+};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
 
 #[contract]
 pub struct ImmunizationRegistry;
@@ -17,6 +26,10 @@ impl ImmunizationRegistry {
     ) -> Result<u64, Error> {
         record.provider_id.require_auth();
 
+        if record.dose_number == 0 {
+            return Err(Error::InvalidDoseNumber);
+        }
+
         let count: u64 = env
             .storage()
             .instance()
@@ -175,4 +188,360 @@ impl ImmunizationRegistry {
 
         Ok(due_series)
     }
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn get_adverse_events(env: Env, immunization_id: u64) -> Result<Vec<AdverseEvent>, Error> {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::ImmunizationRecord(immunization_id))
+        {
+            return Err(Error::RecordNotFound);
+        }
+        Ok(env
+            .storage()
+            .persistent()
+            .get(&DataKey::AdverseEvents(immunization_id))
+            .unwrap_or(Vec::new(&env)))
+    }
+
+    // -------------------------------------------------------------------------
+    // Schedules and forecasting
+    // -------------------------------------------------------------------------
+
+    /// Configure (or replace) the dose schedule for a vaccine
+    pub fn set_vaccine_schedule(
+        env: Env,
+        admin: Address,
+        schedule: VaccineSchedule,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+
+        if schedule.doses.is_empty() {
+            return Err(Error::InvalidSchedule);
+        }
+        for (i, dose) in schedule.doses.iter().enumerate() {
+            if dose.dose_number != i as u32 + 1 {
+                return Err(Error::InvalidSchedule);
+            }
+        }
+
+        let key = DataKey::Schedule(schedule.cvx_code.clone());
+        if !env.storage().persistent().has(&key) {
+            let mut codes: Vec<String> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::ScheduleCodes)
+                .unwrap_or(Vec::new(&env));
+            codes.push_back(schedule.cvx_code.clone());
+            env.storage()
+                .persistent()
+                .set(&DataKey::ScheduleCodes, &codes);
+        }
+        env.storage().persistent().set(&key, &schedule);
+
+        Ok(())
+    }
+
+    pub fn get_vaccine_schedule(env: Env, cvx_code: String) -> Option<VaccineSchedule> {
+        env.storage().persistent().get(&DataKey::Schedule(cvx_code))
+    }
+
+    /// Next outstanding dose of every configured schedule, skipping vaccines the
+    /// patient holds an active exemption for.
+    pub fn forecast_doses(
+        env: Env,
+        patient_id: Address,
+        birth_date: u64,
+        current_date: u64,
+    ) -> Vec<DoseForecast> {
+        let codes: Vec<String> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ScheduleCodes)
+            .unwrap_or(Vec::new(&env));
+        let history = patient_records(&env, &patient_id);
+
+        let mut forecast = Vec::new(&env);
+        for code in codes.iter() {
+            let schedule: VaccineSchedule = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Schedule(code.clone()))
+            {
+                Some(schedule) => schedule,
+                None => continue,
+            };
+            if has_active_exemption(&env, &patient_id, &code, current_date) {
+                continue;
+            }
+
+            let (received, last_dose_date) = doses_for(&history, &code);
+            let next = match schedule.doses.get(received) {
+                Some(dose) => dose,
+                None => continue, // series complete
+            };
+
+            let by_age = birth_date + next.min_age_days as u64 * SECONDS_PER_DAY;
+            let by_interval = if received > 0 {
+                last_dose_date + next.min_interval_days as u64 * SECONDS_PER_DAY
+            } else {
+                0
+            };
+            let due_date = by_age.max(by_interval);
+            let overdue_at = due_date + schedule.grace_period_days as u64 * SECONDS_PER_DAY;
+
+            let status = if current_date < due_date {
+                ForecastStatus::Upcoming
+            } else if current_date > overdue_at {
+                ForecastStatus::Overdue
+            } else {
+                ForecastStatus::Due
+            };
+
+            forecast.push_back(DoseForecast {
+                cvx_code: code,
+                vaccine_name: schedule.vaccine_name,
+                dose_number: next.dose_number,
+                due_date,
+                status,
+            });
+        }
+
+        forecast
+    }
+
+    // -------------------------------------------------------------------------
+    // Exemptions
+    // -------------------------------------------------------------------------
+
+    pub fn record_exemption(
+        env: Env,
+        patient_id: Address,
+        issuer: Address,
+        cvx_code: String,
+        exemption_type: Symbol,
+        reason: String,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        issuer.require_auth();
+
+        let now = env.ledger().timestamp();
+        let valid_type = exemption_type == Symbol::new(&env, "MEDICAL")
+            || exemption_type == Symbol::new(&env, "RELIGIOUS")
+            || exemption_type == Symbol::new(&env, "PERSONAL");
+        if !valid_type || expires_at.is_some_and(|expiry| expiry <= now) {
+            return Err(Error::InvalidExemption);
+        }
+        // Non-medical exemptions are claimed by the patient themselves
+        if exemption_type != Symbol::new(&env, "MEDICAL") && issuer != patient_id {
+            return Err(Error::NotAuthorized);
+        }
+
+        let key = DataKey::Exemptions(patient_id);
+        let mut exemptions: Vec<ExemptionRecord> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        exemptions.push_back(ExemptionRecord {
+            cvx_code,
+            exemption_type,
+            reason,
+            issued_by: issuer,
+            issued_at: now,
+            expires_at,
+        });
+        env.storage().persistent().set(&key, &exemptions);
+
+        Ok(())
+    }
+
+    pub fn get_exemptions(env: Env, patient_id: Address) -> Vec<ExemptionRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Exemptions(patient_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // -------------------------------------------------------------------------
+    // Patient-controlled status sharing
+    // -------------------------------------------------------------------------
+
+    pub fn share_vaccination_status(
+        env: Env,
+        patient_id: Address,
+        viewer: Address,
+        expires_at: u64,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            return Err(Error::NotAuthorized);
+        }
+        env.storage().persistent().set(
+            &DataKey::StatusAccess(patient_id, viewer),
+            &StatusShare {
+                granted_at: now,
+                expires_at,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn revoke_status_share(env: Env, patient_id: Address, viewer: Address) {
+        patient_id.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::StatusAccess(patient_id, viewer));
+    }
+
+    /// Status for one vaccine, readable by the patient or a viewer they shared with
+    pub fn get_vaccination_status(
+        env: Env,
+        patient_id: Address,
+        requester: Address,
+        cvx_code: String,
+    ) -> Result<VaccinationStatus, Error> {
+        requester.require_auth();
+
+        if requester != patient_id {
+            let share: Option<StatusShare> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StatusAccess(patient_id.clone(), requester));
+            match share {
+                Some(share) if share.expires_at > env.ledger().timestamp() => {}
+                _ => return Err(Error::NotAuthorized),
+            }
+        }
+
+        Ok(build_status(&env, &patient_id, &cvx_code))
+    }
+
+    /// Check a presented status hash against the registry's current records
+    pub fn verify_vaccination_status(
+        env: Env,
+        patient_id: Address,
+        cvx_code: String,
+        status_hash: BytesN<32>,
+    ) -> bool {
+        build_status(&env, &patient_id, &cvx_code).status_hash == status_hash
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    let admin: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if admin != *caller {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+fn patient_records(env: &Env, patient_id: &Address) -> Vec<VaccineRecord> {
+    let ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::PatientImmunizations(patient_id.clone()))
+        .unwrap_or(Vec::new(env));
+    let mut records = Vec::new(env);
+    for id in ids.iter() {
+        if let Some(record) = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ImmunizationRecord(id))
+        {
+            records.push_back(record);
+        }
+    }
+    records
+}
+
+/// Number of doses given for a CVX code and the date of the latest one
+fn doses_for(records: &Vec<VaccineRecord>, cvx_code: &String) -> (u32, u64) {
+    let mut count = 0;
+    let mut last = 0;
+    for record in records.iter() {
+        if record.cvx_code == *cvx_code {
+            count += 1;
+            last = last.max(record.administration_date);
+        }
+    }
+    (count, last)
+}
+
+fn has_active_exemption(env: &Env, patient_id: &Address, cvx_code: &String, now: u64) -> bool {
+    let exemptions: Vec<ExemptionRecord> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::Exemptions(patient_id.clone()))
+        .unwrap_or(Vec::new(env));
+    exemptions
+        .iter()
+        .any(|e| e.cvx_code == *cvx_code && e.expires_at.is_none_or(|expiry| expiry > now))
+}
+
+fn build_status(env: &Env, patient_id: &Address, cvx_code: &String) -> VaccinationStatus {
+    let records = patient_records(env, patient_id);
+    let (doses_received, last_dose_date) = doses_for(&records, cvx_code);
+    let doses_required = env
+        .storage()
+        .persistent()
+        .get::<_, VaccineSchedule>(&DataKey::Schedule(cvx_code.clone()))
+        .map(|schedule| schedule.doses.len())
+        .unwrap_or(0);
+    let exempt = has_active_exemption(env, patient_id, cvx_code, env.ledger().timestamp());
+    let series_complete = doses_required > 0 && doses_received >= doses_required;
+
+    let mut payload = patient_id.clone().to_xdr(env);
+    payload.append(&cvx_code.clone().to_xdr(env));
+    payload.append(&(doses_received, doses_required, last_dose_date, exempt).to_xdr(env));
+    let status_hash: BytesN<32> = env.crypto().sha256(&payload).into();
+
+    VaccinationStatus {
+        patient_id: patient_id.clone(),
+        cvx_code: cvx_code.clone(),
+        doses_received,
+        doses_required,
+        series_complete,
+        last_dose_date,
+        exempt,
+        status_hash,
+    }
 }
@@ -162,3 +162,155 @@ fn test_vaccine_series_and_due() {
     let due3 = client.check_due_vaccines(&patient_id, &1700000000);
     assert_eq!(due3.len(), 0);
 }
+
+fn dose(
+    env: &Env,
+    patient_id: &Address,
+    provider_id: &Address,
+    dose_number: u32,
+    date: u64,
+) -> VaccineRecord {
+    VaccineRecord {
+        patient_id: patient_id.clone(),
+        provider_id: provider_id.clone(),
+        vaccine_name: String::from_str(env, "MMR"),
+        cvx_code: String::from_str(env, "03"),
+        lot_number: String::from_str(env, "LOT_MMR"),
+        manufacturer: String::from_str(env, "MERCK"),
+        administration_date: date,
+        expiration_date: date + 31_536_000,
+        dose_number,
+        route: Symbol::new(env, "SC"),
+        site: Symbol::new(env, "ARM"),
+    }
+}
+
+#[test]
+fn test_schedule_forecast_and_exemption() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ImmunizationRegistry, ());
+    let client = ImmunizationRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    client.initialize(&admin);
+
+    let day = 86_400u64;
+    client.set_vaccine_schedule(
+        &admin,
+        &VaccineSchedule {
+            cvx_code: String::from_str(&env, "03"),
+            vaccine_name: String::from_str(&env, "MMR"),
+            doses: soroban_sdk::vec![
+                &env,
+                ScheduleDose {
+                    dose_number: 1,
+                    min_age_days: 365,
+                    min_interval_days: 0
+                },
+                ScheduleDose {
+                    dose_number: 2,
+                    min_age_days: 1460,
+                    min_interval_days: 28
+                },
+            ],
+            grace_period_days: 90,
+        },
+    );
+
+    let birth = 1_000 * day;
+
+    // Before the first birthday the first dose is upcoming
+    let forecast = client.forecast_doses(&patient_id, &birth, &(birth + 200 * day));
+    assert_eq!(forecast.len(), 1);
+    assert_eq!(forecast.get(0).unwrap().status, ForecastStatus::Upcoming);
+    assert_eq!(forecast.get(0).unwrap().due_date, birth + 365 * day);
+
+    // Well past the grace period it is overdue
+    let forecast = client.forecast_doses(&patient_id, &birth, &(birth + 500 * day));
+    assert_eq!(forecast.get(0).unwrap().status, ForecastStatus::Overdue);
+
+    client.record_immunization(&dose(&env, &patient_id, &provider_id, 1, birth + 370 * day));
+    let forecast = client.forecast_doses(&patient_id, &birth, &(birth + 1460 * day));
+    assert_eq!(forecast.get(0).unwrap().dose_number, 2);
+    assert_eq!(forecast.get(0).unwrap().status, ForecastStatus::Due);
+
+    // A medical exemption removes the vaccine from the forecast
+    client.record_exemption(
+        &patient_id,
+        &provider_id,
+        &String::from_str(&env, "03"),
+        &Symbol::new(&env, "MEDICAL"),
+        &String::from_str(&env, "Immunocompromised"),
+        &None,
+    );
+    assert!(client
+        .forecast_doses(&patient_id, &birth, &(birth + 1460 * day))
+        .is_empty());
+    assert_eq!(client.get_exemptions(&patient_id).len(), 1);
+
+    // Only the patient may claim a non-medical exemption
+    let res = client.try_record_exemption(
+        &patient_id,
+        &provider_id,
+        &String::from_str(&env, "03"),
+        &Symbol::new(&env, "RELIGIOUS"),
+        &String::from_str(&env, "NA"),
+        &None,
+    );
+    assert_eq!(res, Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_shared_vaccination_status_is_verifiable() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ImmunizationRegistry, ());
+    let client = ImmunizationRegistryClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let school = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_vaccine_schedule(
+        &admin,
+        &VaccineSchedule {
+            cvx_code: String::from_str(&env, "03"),
+            vaccine_name: String::from_str(&env, "MMR"),
+            doses: soroban_sdk::vec![
+                &env,
+                ScheduleDose {
+                    dose_number: 1,
+                    min_age_days: 365,
+                    min_interval_days: 0
+                },
+            ],
+            grace_period_days: 30,
+        },
+    );
+    client.record_immunization(&dose(&env, &patient_id, &provider_id, 1, 1690000000));
+
+    let code = String::from_str(&env, "03");
+    let res = client.try_get_vaccination_status(&patient_id, &school, &code);
+    assert_eq!(res, Err(Ok(Error::NotAuthorized)));
+
+    client.share_vaccination_status(&patient_id, &school, &(env.ledger().timestamp() + 1000));
+    let status = client.get_vaccination_status(&patient_id, &school, &code);
+    assert!(status.series_complete);
+    assert_eq!(status.doses_received, 1);
+    assert!(client.verify_vaccination_status(&patient_id, &code, &status.status_hash));
+
+    // A new dose changes the status, so the old hash no longer verifies
+    client.record_immunization(&dose(&env, &patient_id, &provider_id, 2, 1700000000));
+    assert!(!client.verify_vaccination_status(&patient_id, &code, &status.status_hash));
+
+    client.revoke_status_share(&patient_id, &school);
+    assert!(client
+        .try_get_vaccination_status(&patient_id, &school, &code)
+        .is_err());
+}
@@ -1,6 +1,4 @@
-#![no_std]
-
-use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, BytesN};
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, BytesN, Vec};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -10,6 +8,11 @@ pub enum DataKey {
     ImmunizationRecord(u64),
     AdverseEvents(u64), // List of AdverseEvent
     PatientVaccineSeries(Address), // List of VaccineSeries
+    Admin,
+    Schedule(String),               // VaccineSchedule keyed by CVX code
+    ScheduleCodes,                  // List of CVX codes with a configured schedule
+    Exemptions(Address),            // List of ExemptionRecord
+    StatusAccess(Address, Address), // (patient, viewer) -> StatusShare
 }
 
 #[contracterror]
@@ -19,6 +22,10 @@ pub enum Error {
     NotAuthorized = 1,
     RecordNotFound = 2,
     InvalidDoseNumber = 3,
+    AlreadyInitialized = 4,
+    NotInitialized = 5,
+    InvalidSchedule = 6,
+    InvalidExemption = 7,
 }
 
 #[contracttype]
@@ -53,3 +60,73 @@ pub struct VaccineSeries {
     pub doses_required: u32,
     pub schedule_hash: BytesN<32>,
 }
+
+/// One dose in a schedule. The dose becomes due at `min_age_days` after birth
+/// or `min_interval_days` after the previous dose, whichever is later.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduleDose {
+    pub dose_number: u32,
+    pub min_age_days: u32,
+    pub min_interval_days: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaccineSchedule {
+    pub cvx_code: String,
+    pub vaccine_name: String,
+    pub doses: Vec<ScheduleDose>,
+    pub grace_period_days: u32, // after the due date a dose is overdue
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ForecastStatus {
+    Upcoming,
+    Due,
+    Overdue,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DoseForecast {
+    pub cvx_code: String,
+    pub vaccine_name: String,
+    pub dose_number: u32,
+    pub due_date: u64,
+    pub status: ForecastStatus,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExemptionRecord {
+    pub cvx_code: String,
+    pub exemption_type: Symbol, // MEDICAL, RELIGIOUS, PERSONAL
+    pub reason: String,
+    pub issued_by: Address,
+    pub issued_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StatusShare {
+    pub granted_at: u64,
+    pub expires_at: u64,
+}
+
+/// Vaccination status for one vaccine; `status_hash` commits to the other
+/// fields so a verifier can check a presented status against the registry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VaccinationStatus {
+    pub patient_id: Address,
+    pub cvx_code: String,
+    pub doses_received: u32,
+    pub doses_required: u32,
+    pub series_complete: bool,
+    pub last_dose_date: u64,
+    pub exempt: bool,
+    pub status_hash: BytesN<32>,
+}
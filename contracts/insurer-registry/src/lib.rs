@@ -1,6 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractimpl, contracttype, symbol_short, Address, Env, String, Vec};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, String, Vec,
+};
 
 /// --------------------
 /// Insurer Structures
@@ -22,15 +24,35 @@ pub struct InsurerData {
 /// --------------------
 #[contracttype]
 pub enum DataKey {
+    Admin,
     Insurer(Address),
     ClaimsReviewers(Address), // Maps insurer wallet to list of approved reviewers
 }
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct InsurerRegistry;
 
 #[contractimpl]
 impl InsurerRegistry {
+    /// Set the admin allowed to upgrade the contract
+    ///
+    /// # Arguments
+    /// * `admin` - The admin address
+    ///
+    /// # Panics
+    /// Panics if the contract is already initialized
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+    }
+
     /// Register a new insurance company with comprehensive information
     ///
     /// # Arguments
@@ -293,6 +315,34 @@ impl InsurerRegistry {
         }
         false
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> u32 {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::migrate(&env, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Not initialized"));
+        if stored != *admin {
+            panic!("Unauthorized admin");
+        }
+    }
 }
 
 mod test;
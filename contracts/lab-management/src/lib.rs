@@ -1,10 +1,13 @@
 #![no_std]
 
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
-    panic_with_error,
+    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractevent,
+    contractimpl, contracttype, panic_with_error,
 };
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -12,6 +15,11 @@ pub enum Error {
     NotFound = 1,
     Unauthorized = 2,
     QCFieldFailed = 4,
+    InvalidState = 5,
+    InvalidInput = 6,
+    AlreadyAcknowledged = 7,
+    AlreadyInitialized = 8,
+    NotInitialized = 9,
 }
 
 #[contracttype]
@@ -32,9 +40,113 @@ pub struct LabOrder {
     pub patient_id: Address,
     pub lab_id: Option<Address>,
     pub test_panel: Vec<String>,
+    pub priority: Symbol,
     pub status: Symbol,
     pub results_hash: Option<BytesN<32>>,
     pub quality_control_passed: bool,
+    pub ordered_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpecimenCollection {
+    pub order_id: u64,
+    pub specimen_id: String,
+    pub specimen_type: Symbol,
+    pub collected_by: Address,
+    pub collected_at: u64,
+}
+
+/// Result value as posted by the lab; values and limits are scaled by 100
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ObservationInput {
+    pub code: String, // LOINC-style code
+    pub name: String,
+    pub value_x100: i64,
+    pub unit: String,
+    pub ref_low_x100: Option<i64>,
+    pub ref_high_x100: Option<i64>,
+    pub critical_low_x100: Option<i64>,
+    pub critical_high_x100: Option<i64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Observation {
+    pub order_id: u64,
+    pub code: String,
+    pub name: String,
+    pub value_x100: i64,
+    pub unit: String,
+    pub ref_low_x100: Option<i64>,
+    pub ref_high_x100: Option<i64>,
+    pub abnormal_flag: Symbol, // N, L, H, LL, HH
+    pub critical: bool,
+    pub resulted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalAlert {
+    pub alert_id: u64,
+    pub order_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub code: String,
+    pub value_x100: i64,
+    pub raised_at: u64,
+    pub acknowledged_by: Option<Address>,
+    pub acknowledged_at: Option<u64>,
+}
+
+#[contracttype]
+pub enum DataKey {
+    Admin,
+    Specimen(u64),
+    Observations(u64),
+    CriticalAlertCounter,
+    CriticalAlert(u64),
+    PendingAlerts(Address),
+    PatientOrders(Address),
+    ProviderOrders(Address),
+}
+
+#[contractevent]
+#[derive(Clone, Debug)]
+pub struct LabResultSubmitted {
+    #[topic]
+    pub patient_id: Address,
+    pub order_id: u64,
+    pub results: Vec<TestResult>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalValueFlagged {
+    #[topic]
+    pub order_id: u64,
+    pub test_code: String,
+    pub value: String,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalResultRaised {
+    #[topic]
+    pub provider_id: Address,
+    pub alert_id: u64,
+    pub order_id: u64,
+    pub code: String,
+    pub value_x100: i64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CriticalResultAcknowledged {
+    #[topic]
+    pub alert_id: u64,
+    pub acknowledged_by: Address,
 }
 
 #[contracttype]
@@ -51,6 +163,15 @@ pub struct LabManagementContract;
 
 #[contractimpl]
 impl LabManagementContract {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic_with_error!(&env, Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+    }
+
     pub fn order_lab_test(
         env: Env,
         provider_id: Address,
@@ -65,16 +186,20 @@ impl LabManagementContract {
             .unwrap_or(0);
 
         let order = LabOrder {
-            provider_id,
-            patient_id,
+            provider_id: provider_id.clone(),
+            patient_id: patient_id.clone(),
             lab_id: None,
             test_panel: req.test_panel,
+            priority: req.priority,
             status: Symbol::new(&env, "Ordered"),
             results_hash: None,
             quality_control_passed: false,
+            ordered_at: env.ledger().timestamp(),
         };
 
         env.storage().persistent().set(&id, &order);
+        append_id(&env, DataKey::PatientOrders(patient_id), id);
+        append_id(&env, DataKey::ProviderOrders(provider_id), id);
         env.storage()
             .instance()
             .set(&Symbol::new(&env, "LAB_ID"), &(id + 1));
@@ -112,14 +237,12 @@ impl LabManagementContract {
         order.status = Symbol::new(&env, "Completed");
 
         // Event for result submission (LOINC support)
-        env.events().publish(
-            (
-                Symbol::new(&env, "LAB"),
-                Symbol::new(&env, "RESULT"),
-                order.patient_id.clone(),
-            ),
-            results_summary,
-        );
+        LabResultSubmitted {
+            patient_id: order.patient_id.clone(),
+            order_id,
+            results: results_summary,
+        }
+        .publish(&env);
 
         env.storage().persistent().set(&order_id, &order);
     }
@@ -133,8 +256,324 @@ impl LabManagementContract {
     ) {
         lab_id.require_auth();
         // Event for critical alerting
-        env.events()
-            .publish((Symbol::new(&env, "CRITICAL"), order_id), (test_code, val));
+        CriticalValueFlagged {
+            order_id,
+            test_code,
+            value: val,
+        }
+        .publish(&env);
+    }
+
+    /// Record specimen collection for an order
+    pub fn record_specimen_collection(
+        env: Env,
+        order_id: u64,
+        collector: Address,
+        specimen_id: String,
+        specimen_type: Symbol,
+        collected_at: u64,
+    ) {
+        collector.require_auth();
+        let mut order = load_order(&env, order_id);
+
+        if order.status == Symbol::new(&env, "Completed") {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+        if collected_at > env.ledger().timestamp() {
+            panic_with_error!(&env, Error::InvalidInput);
+        }
+
+        let specimen = SpecimenCollection {
+            order_id,
+            specimen_id,
+            specimen_type,
+            collected_by: collector,
+            collected_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Specimen(order_id), &specimen);
+
+        order.status = Symbol::new(&env, "Collected");
+        env.storage().persistent().set(&order_id, &order);
     }
+
+    /// Post coded observations from the assigned lab. Abnormal flags are derived
+    /// from the reference and critical limits; critical results raise an alert
+    /// the ordering provider must acknowledge.
+    pub fn post_observations(
+        env: Env,
+        order_id: u64,
+        lab_id: Address,
+        observations: Vec<ObservationInput>,
+    ) -> Vec<u64> {
+        lab_id.require_auth();
+        let mut order = load_order(&env, order_id);
+
+        if order.lab_id != Some(lab_id) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if observations.is_empty() {
+            panic_with_error!(&env, Error::InvalidInput);
+        }
+
+        let now = env.ledger().timestamp();
+        let key = DataKey::Observations(order_id);
+        let mut stored: Vec<Observation> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let mut alert_ids = Vec::new(&env);
+
+        for input in observations.iter() {
+            let flag = abnormal_flag(&input);
+            let critical = flag == "LL" || flag == "HH";
+
+            if critical {
+                alert_ids.push_back(raise_critical_alert(&env, order_id, &order, &input));
+            }
+
+            stored.push_back(Observation {
+                order_id,
+                code: input.code,
+                name: input.name,
+                value_x100: input.value_x100,
+                unit: input.unit,
+                ref_low_x100: input.ref_low_x100,
+                ref_high_x100: input.ref_high_x100,
+                abnormal_flag: Symbol::new(&env, flag),
+                critical,
+                resulted_at: now,
+            });
+        }
+
+        env.storage().persistent().set(&key, &stored);
+        order.status = Symbol::new(&env, "Resulted");
+        env.storage().persistent().set(&order_id, &order);
+
+        alert_ids
+    }
+
+    /// Ordering provider acknowledges a critical result
+    pub fn acknowledge_critical_result(env: Env, alert_id: u64, provider_id: Address) {
+        provider_id.require_auth();
+
+        let key = DataKey::CriticalAlert(alert_id);
+        let mut alert: CriticalAlert = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotFound));
+        if alert.provider_id != provider_id {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if alert.acknowledged_by.is_some() {
+            panic_with_error!(&env, Error::AlreadyAcknowledged);
+        }
+
+        alert.acknowledged_by = Some(provider_id.clone());
+        alert.acknowledged_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&key, &alert);
+
+        let pending_key = DataKey::PendingAlerts(provider_id.clone());
+        let pending: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&pending_key)
+            .unwrap_or(Vec::new(&env));
+        let mut remaining = Vec::new(&env);
+        for id in pending.iter() {
+            if id != alert_id {
+                remaining.push_back(id);
+            }
+        }
+        env.storage().persistent().set(&pending_key, &remaining);
+
+        CriticalResultAcknowledged {
+            alert_id,
+            acknowledged_by: provider_id,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_critical_alert(env: Env, alert_id: u64) -> Option<CriticalAlert> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::CriticalAlert(alert_id))
+    }
+
+    /// Critical results still awaiting the provider's acknowledgment
+    pub fn get_unacknowledged_alerts(env: Env, provider_id: Address) -> Vec<CriticalAlert> {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PendingAlerts(provider_id))
+            .unwrap_or(Vec::new(&env));
+        let mut alerts = Vec::new(&env);
+        for id in ids.iter() {
+            if let Some(alert) = env.storage().persistent().get(&DataKey::CriticalAlert(id)) {
+                alerts.push_back(alert);
+            }
+        }
+        alerts
+    }
+
+    pub fn get_lab_order(env: Env, order_id: u64) -> LabOrder {
+        load_order(&env, order_id)
+    }
+
+    pub fn get_specimen(env: Env, order_id: u64) -> Option<SpecimenCollection> {
+        env.storage().persistent().get(&DataKey::Specimen(order_id))
+    }
+
+    /// Observations for an order, visible to the patient, ordering provider and assigned lab
+    pub fn get_order_results(env: Env, order_id: u64, requester: Address) -> Vec<Observation> {
+        requester.require_auth();
+        let order = load_order(&env, order_id);
+
+        let allowed = requester == order.patient_id
+            || requester == order.provider_id
+            || order.lab_id == Some(requester);
+        if !allowed {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        env.storage()
+            .persistent()
+            .get(&DataKey::Observations(order_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_patient_orders(env: Env, patient_id: Address, offset: u32, limit: u32) -> Vec<u64> {
+        let orders = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientOrders(patient_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
+    pub fn get_provider_orders(
+        env: Env,
+        provider_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let orders = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderOrders(provider_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&orders, offset, limit)
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        require_admin(&env, &admin);
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> u32 {
+        require_admin(&env, &admin);
+        shared_upgrade::migrate(&env, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotInitialized));
+    if stored != *admin {
+        panic_with_error!(env, Error::Unauthorized);
+    }
+}
+
+fn load_order(env: &Env, order_id: u64) -> LabOrder {
+    env.storage()
+        .persistent()
+        .get(&order_id)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotFound))
+}
+
+fn append_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+fn abnormal_flag(input: &ObservationInput) -> &'static str {
+    let value = input.value_x100;
+    if input.critical_low_x100.is_some_and(|limit| value < limit) {
+        "LL"
+    } else if input.critical_high_x100.is_some_and(|limit| value > limit) {
+        "HH"
+    } else if input.ref_low_x100.is_some_and(|limit| value < limit) {
+        "L"
+    } else if input.ref_high_x100.is_some_and(|limit| value > limit) {
+        "H"
+    } else {
+        "N"
+    }
+}
+
+fn raise_critical_alert(
+    env: &Env,
+    order_id: u64,
+    order: &LabOrder,
+    input: &ObservationInput,
+) -> u64 {
+    let alert_id = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::CriticalAlertCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage()
+        .instance()
+        .set(&DataKey::CriticalAlertCounter, &alert_id);
+
+    let alert = CriticalAlert {
+        alert_id,
+        order_id,
+        patient_id: order.patient_id.clone(),
+        provider_id: order.provider_id.clone(),
+        code: input.code.clone(),
+        value_x100: input.value_x100,
+        raised_at: env.ledger().timestamp(),
+        acknowledged_by: None,
+        acknowledged_at: None,
+    };
+    env.storage()
+        .persistent()
+        .set(&DataKey::CriticalAlert(alert_id), &alert);
+    append_id(
+        env,
+        DataKey::PendingAlerts(order.provider_id.clone()),
+        alert_id,
+    );
+
+    CriticalResultRaised {
+        provider_id: order.provider_id.clone(),
+        alert_id,
+        order_id,
+        code: input.code.clone(),
+        value_x100: input.value_x100,
+    }
+    .publish(env);
+
+    alert_id
 }
 mod test;
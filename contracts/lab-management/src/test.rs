@@ -107,3 +107,138 @@ fn test_fail_assign_nonexistent_order() {
     let lab = Address::generate(&env);
     client.assign_lab(&999, &lab, &0);
 }
+
+fn observation(env: &Env, code: &str, value_x100: i64) -> ObservationInput {
+    ObservationInput {
+        code: String::from_str(env, code),
+        name: String::from_str(env, "Potassium"),
+        value_x100,
+        unit: String::from_str(env, "mmol/L"),
+        ref_low_x100: Some(350),
+        ref_high_x100: Some(510),
+        critical_low_x100: Some(250),
+        critical_high_x100: Some(650),
+    }
+}
+
+#[test]
+fn test_coded_observations_and_critical_acknowledgment() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(LabManagementContract, ());
+    let client = LabManagementContractClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let lab = Address::generate(&env);
+
+    let req = OrderRequest {
+        test_panel: vec![&env, String::from_str(&env, "2823-3")],
+        priority: Symbol::new(&env, "ROUTINE"),
+        clinical_info_hash: BytesN::from_array(&env, &[1u8; 32]),
+        fasting_required: false,
+        collection_date: None,
+    };
+    let order_id = client.order_lab_test(&provider, &patient, &req);
+    assert_eq!(
+        client.get_patient_orders(&patient, &0, &10),
+        vec![&env, order_id]
+    );
+    assert_eq!(
+        client.get_provider_orders(&provider, &0, &10),
+        vec![&env, order_id]
+    );
+
+    client.assign_lab(&order_id, &lab, &3600);
+    client.record_specimen_collection(
+        &order_id,
+        &lab,
+        &String::from_str(&env, "SPC-1"),
+        &Symbol::new(&env, "serum"),
+        &0,
+    );
+    assert_eq!(
+        client.get_lab_order(&order_id).status,
+        Symbol::new(&env, "Collected")
+    );
+
+    // A different lab cannot post results
+    let other_lab = Address::generate(&env);
+    let denied = client.try_post_observations(
+        &order_id,
+        &other_lab,
+        &vec![&env, observation(&env, "2823-3", 420)],
+    );
+    assert!(denied.is_err());
+
+    let alerts = client.post_observations(
+        &order_id,
+        &lab,
+        &vec![
+            &env,
+            observation(&env, "2823-3", 540),
+            observation(&env, "2823-3", 690),
+        ],
+    );
+    assert_eq!(alerts.len(), 1);
+
+    let results = client.get_order_results(&order_id, &patient);
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results.get(0).unwrap().abnormal_flag,
+        Symbol::new(&env, "H")
+    );
+    assert_eq!(
+        results.get(1).unwrap().abnormal_flag,
+        Symbol::new(&env, "HH")
+    );
+    assert!(results.get(1).unwrap().critical);
+
+    let alert_id = alerts.get(0).unwrap();
+    assert_eq!(client.get_unacknowledged_alerts(&provider).len(), 1);
+    client.acknowledge_critical_result(&alert_id, &provider);
+    assert!(client.get_unacknowledged_alerts(&provider).is_empty());
+    assert_eq!(
+        client
+            .get_critical_alert(&alert_id)
+            .unwrap()
+            .acknowledged_by,
+        Some(provider.clone())
+    );
+
+    let again = client.try_acknowledge_critical_result(&alert_id, &provider);
+    assert!(again.is_err());
+
+    let stranger = Address::generate(&env);
+    assert!(client.try_get_order_results(&order_id, &stranger).is_err());
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(LabManagementContract, ());
+    let client = LabManagementContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_migrate(&admin),
+        Err(Ok(Error::NotInitialized.into()))
+    );
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized.into()))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(
+        client.try_migrate(&stranger),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized.into()))
+    );
+}
@@ -5,13 +5,28 @@ mod test;
 
 use soroban_sdk::{contract, contractimpl, Env, Address, String, Vec, BytesN};
 use types::{DataKey, Error, ServiceLine, ClaimStatus, ClaimRecord, DenialInfo};
+pub use types::{AgingReport, LedgerEntry, LedgerEntryKind, LineAdjudication};
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
 
 #[contract]
 pub struct MedicalClaimsSystem;
 
 #[contractimpl]
 impl MedicalClaimsSystem {
-    
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn submit_claim(
         env: Env,
         provider_id: Address,
@@ -25,6 +40,17 @@ impl MedicalClaimsSystem {
     ) -> Result<u64, Error> {
         provider_id.require_auth();
 
+        let mut line_total: i128 = 0;
+        for line in service_codes.iter() {
+            if line.charge_amount < 0 || line.quantity == 0 {
+                return Err(Error::InvalidAmount);
+            }
+            line_total += line.charge_amount * line.quantity as i128;
+        }
+        if total_amount <= 0 || total_amount != line_total {
+            return Err(Error::InvalidAmount);
+        }
+
         let count: u64 = env
             .storage()
             .instance()
@@ -47,6 +73,9 @@ impl MedicalClaimsSystem {
             approved_amount: None,
             patient_responsibility: None,
             appeal_level: 0,
+            payer: None,
+            insurer_balance: 0,
+            adjudicated_at: None,
         };
 
         env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
@@ -76,13 +105,11 @@ impl MedicalClaimsSystem {
 
         let mut claim: ClaimRecord = env.storage().persistent().get(&DataKey::Claim(claim_id)).ok_or(Error::ClaimNotFound)?;
 
-        if claim.status != ClaimStatus::Submitted && claim.status != ClaimStatus::Appealed {
-            return Err(Error::InvalidStateTransition);
+        if approved_amount < 0 || patient_responsibility < 0 || patient_responsibility > approved_amount {
+            return Err(Error::InvalidAmount);
         }
 
-        claim.status = ClaimStatus::Adjudicated;
-        claim.approved_amount = Some(approved_amount);
-        claim.patient_responsibility = Some(patient_responsibility);
+        apply_adjudication(&env, &mut claim, insurance_admin, approved_amount, patient_responsibility)?;
 
         env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
         env.storage().persistent().set(&DataKey::ApprovedLines(claim_id), &approved_lines);
@@ -126,7 +153,7 @@ impl MedicalClaimsSystem {
         env: Env,
         claim_id: u64,
         insurance_admin: Address,
-        _payment_amount: i128, // Currently ignored, just relying on record
+        payment_amount: i128,
         payment_date: u64,
         payment_reference: String,
     ) -> Result<(), Error> {
@@ -137,10 +164,18 @@ impl MedicalClaimsSystem {
         if claim.status != ClaimStatus::Adjudicated {
             return Err(Error::InvalidStateTransition);
         }
+        if claim.payer != Some(insurance_admin.clone()) {
+            return Err(Error::NotAuthorized);
+        }
+        if payment_amount < 0 || payment_amount > claim.insurer_balance {
+            return Err(Error::InvalidAmount);
+        }
 
+        claim.insurer_balance -= payment_amount;
         claim.status = ClaimStatus::Paid;
         env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
 
+        post_ledger_entry(&env, claim_id, LedgerEntryKind::InsurerPayment, payment_amount, insurance_admin, payment_reference.clone());
         env.storage().persistent().set(&DataKey::ClaimPayment(claim_id), &(payment_date, payment_reference));
 
         Ok(())
@@ -166,10 +201,16 @@ impl MedicalClaimsSystem {
             return Err(Error::InvalidStateTransition);
         }
 
+        if payment_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
         // Apply payment - simplified reconciliation
         let current_resp = claim.patient_responsibility.unwrap_or(0);
         let new_resp = current_resp - payment_amount;
         claim.patient_responsibility = Some(if new_resp < 0 { 0 } else { new_resp });
+        adjust_patient_balance(&env, &patient_id, claim.patient_responsibility.unwrap_or(0) - current_resp);
+        post_ledger_entry(&env, claim_id, LedgerEntryKind::PatientPayment, payment_amount, patient_id.clone(), String::from_str(&env, ""));
 
         if claim.status == ClaimStatus::Paid && claim.patient_responsibility.unwrap_or(0) == 0 {
             claim.status = ClaimStatus::Closed;
@@ -180,4 +221,313 @@ impl MedicalClaimsSystem {
 
         Ok(())
     }
+
+    /// Adjudicate line by line. Allowed, paid and denied amounts are recorded
+    /// per line; the patient owes whatever was allowed but not paid.
+    pub fn adjudicate_claim_lines(
+        env: Env,
+        claim_id: u64,
+        payer: Address,
+        lines: Vec<LineAdjudication>,
+    ) -> Result<(), Error> {
+        payer.require_auth();
+
+        let mut claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Claim(claim_id))
+            .ok_or(Error::ClaimNotFound)?;
+
+        let mut approved_amount: i128 = 0;
+        let mut paid_amount: i128 = 0;
+        let mut approved_lines: Vec<u64> = Vec::new(&env);
+        let mut denials: Vec<DenialInfo> = Vec::new(&env);
+
+        for adj in lines.iter() {
+            let line = claim
+                .service_codes
+                .get(adj.line_number.wrapping_sub(1))
+                .ok_or(Error::InvalidLine)?;
+            let charge = line.charge_amount * line.quantity as i128;
+
+            if adj.allowed_amount < 0
+                || adj.denied_amount < 0
+                || adj.paid_amount < 0
+                || adj.paid_amount > adj.allowed_amount
+                || adj.allowed_amount + adj.denied_amount > charge
+            {
+                return Err(Error::InvalidAmount);
+            }
+
+            approved_amount += adj.allowed_amount;
+            paid_amount += adj.paid_amount;
+            if adj.allowed_amount > 0 {
+                approved_lines.push_back(adj.line_number as u64);
+            }
+            if adj.denied_amount > 0 {
+                denials.push_back(DenialInfo {
+                    line_number: adj.line_number as u64,
+                    denial_code: adj.reason_codes.get(0).unwrap_or(String::from_str(&env, "")),
+                    denial_reason: String::from_str(&env, ""),
+                    is_appealable: true,
+                });
+            }
+        }
+
+        apply_adjudication(
+            &env,
+            &mut claim,
+            payer,
+            approved_amount,
+            approved_amount - paid_amount,
+        )?;
+
+        env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
+        env.storage()
+            .persistent()
+            .set(&DataKey::ApprovedLines(claim_id), &approved_lines);
+        env.storage()
+            .persistent()
+            .set(&DataKey::DenialInfos(claim_id), &denials);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LineAdjudications(claim_id), &lines);
+
+        Ok(())
+    }
+
+    /// Provider write-off against the patient's remaining responsibility
+    pub fn post_adjustment(
+        env: Env,
+        claim_id: u64,
+        provider_id: Address,
+        amount: i128,
+        reason_code: String,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        let mut claim: ClaimRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Claim(claim_id))
+            .ok_or(Error::ClaimNotFound)?;
+
+        if claim.provider_id != provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if claim.status != ClaimStatus::Adjudicated && claim.status != ClaimStatus::Paid {
+            return Err(Error::InvalidStateTransition);
+        }
+
+        let current_resp = claim.patient_responsibility.unwrap_or(0);
+        if amount <= 0 || amount > current_resp {
+            return Err(Error::InvalidAmount);
+        }
+
+        claim.patient_responsibility = Some(current_resp - amount);
+        adjust_patient_balance(&env, &claim.patient_id, -amount);
+        if claim.status == ClaimStatus::Paid && current_resp == amount {
+            claim.status = ClaimStatus::Closed;
+        }
+        env.storage().persistent().set(&DataKey::Claim(claim_id), &claim);
+
+        post_ledger_entry(
+            &env,
+            claim_id,
+            LedgerEntryKind::Adjustment,
+            amount,
+            provider_id,
+            reason_code,
+        );
+
+        Ok(())
+    }
+
+    pub fn get_claim(env: Env, claim_id: u64) -> Result<ClaimRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claim(claim_id))
+            .ok_or(Error::ClaimNotFound)
+    }
+
+    pub fn get_line_adjudications(env: Env, claim_id: u64) -> Vec<LineAdjudication> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LineAdjudications(claim_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_claim_ledger(env: Env, claim_id: u64, offset: u32, limit: u32) -> Vec<LedgerEntry> {
+        let ledger = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimLedger(claim_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&ledger, offset, limit)
+    }
+
+    /// Total patient responsibility still owed across all claims
+    pub fn get_patient_balance(env: Env, patient_id: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PatientBalance(patient_id))
+            .unwrap_or(0)
+    }
+
+    /// Provider receivables, aged from the date of service. Unadjudicated
+    /// claims count at their full charge.
+    pub fn get_provider_aging(env: Env, provider_id: Address, as_of: u64) -> AgingReport {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderClaims(provider_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut report = empty_aging();
+        for id in ids.iter() {
+            if let Some(claim) = env.storage().persistent().get::<_, ClaimRecord>(&DataKey::Claim(id)) {
+                let outstanding = match claim.status {
+                    ClaimStatus::Submitted | ClaimStatus::Appealed => claim.total_amount,
+                    ClaimStatus::Adjudicated | ClaimStatus::Paid => {
+                        claim.insurer_balance + claim.patient_responsibility.unwrap_or(0)
+                    }
+                    ClaimStatus::Closed => 0,
+                };
+                add_to_bucket(&mut report, outstanding, claim.service_date, as_of);
+            }
+        }
+        report
+    }
+
+    /// Patient responsibility, aged from the date the claim was adjudicated
+    pub fn get_patient_aging(env: Env, patient_id: Address, as_of: u64) -> AgingReport {
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientClaims(patient_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut report = empty_aging();
+        for id in ids.iter() {
+            if let Some(claim) = env.storage().persistent().get::<_, ClaimRecord>(&DataKey::Claim(id)) {
+                if let Some(billed_at) = claim.adjudicated_at {
+                    add_to_bucket(&mut report, claim.patient_responsibility.unwrap_or(0), billed_at, as_of);
+                }
+            }
+        }
+        report
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if stored != *admin {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+/// Record an adjudication outcome, keeping the patient's running balance in
+/// step when a claim is re-adjudicated after an appeal.
+fn apply_adjudication(
+    env: &Env,
+    claim: &mut ClaimRecord,
+    payer: Address,
+    approved_amount: i128,
+    patient_responsibility: i128,
+) -> Result<(), Error> {
+    if claim.status != ClaimStatus::Submitted && claim.status != ClaimStatus::Appealed {
+        return Err(Error::InvalidStateTransition);
+    }
+    if let Some(existing) = &claim.payer {
+        if *existing != payer {
+            return Err(Error::NotAuthorized);
+        }
+    }
+
+    let previous = claim.patient_responsibility.unwrap_or(0);
+    adjust_patient_balance(env, &claim.patient_id, patient_responsibility - previous);
+
+    claim.status = ClaimStatus::Adjudicated;
+    claim.approved_amount = Some(approved_amount);
+    claim.patient_responsibility = Some(patient_responsibility);
+    claim.insurer_balance = approved_amount - patient_responsibility;
+    claim.payer = Some(payer);
+    claim.adjudicated_at = Some(env.ledger().timestamp());
+    Ok(())
+}
+
+fn adjust_patient_balance(env: &Env, patient_id: &Address, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let key = DataKey::PatientBalance(patient_id.clone());
+    let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    env.storage().persistent().set(&key, &(balance + delta));
+}
+
+fn post_ledger_entry(
+    env: &Env,
+    claim_id: u64,
+    kind: LedgerEntryKind,
+    amount: i128,
+    posted_by: Address,
+    reference: String,
+) {
+    let key = DataKey::ClaimLedger(claim_id);
+    let mut entries: Vec<LedgerEntry> = env.storage().persistent().get(&key).unwrap_or(Vec::new(env));
+    entries.push_back(LedgerEntry {
+        kind,
+        amount,
+        posted_by,
+        posted_at: env.ledger().timestamp(),
+        reference,
+    });
+    env.storage().persistent().set(&key, &entries);
+}
+
+fn empty_aging() -> AgingReport {
+    AgingReport {
+        current: 0,
+        days_31_60: 0,
+        days_61_90: 0,
+        over_90: 0,
+        total: 0,
+    }
+}
+
+fn add_to_bucket(report: &mut AgingReport, amount: i128, since: u64, as_of: u64) {
+    if amount <= 0 {
+        return;
+    }
+    let days = as_of.saturating_sub(since) / SECONDS_PER_DAY;
+    match days {
+        0..=30 => report.current += amount,
+        31..=60 => report.days_31_60 += amount,
+        61..=90 => report.days_61_90 += amount,
+        _ => report.over_90 += amount,
+    }
+    report.total += amount;
 }
@@ -181,3 +181,211 @@ fn test_appeal_workflow() {
         &BytesN::from_array(&env, &[4; 32]),
     );
 }
+
+#[test]
+fn test_line_adjudication_ledger_and_aging() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MedicalClaimsSystem, ());
+    let client = MedicalClaimsSystemClient::new(&env, &contract_id);
+
+    let provider_id = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let payer = Address::generate(&env);
+
+    let mut services = Vec::new(&env);
+    services.push_back(ServiceLine {
+        procedure_code: String::from_str(&env, "99213"),
+        modifier: None,
+        quantity: 1,
+        charge_amount: 15000,
+        diagnosis_pointers: Vec::new(&env),
+    });
+    services.push_back(ServiceLine {
+        procedure_code: String::from_str(&env, "85025"),
+        modifier: None,
+        quantity: 2,
+        charge_amount: 5000,
+        diagnosis_pointers: Vec::new(&env),
+    });
+
+    // Total must match the sum of the lines
+    let bad = client.try_submit_claim(
+        &provider_id,
+        &patient_id,
+        &12345,
+        &0,
+        &services,
+        &Vec::new(&env),
+        &BytesN::from_array(&env, &[0; 32]),
+        &20000,
+    );
+    assert_eq!(bad, Err(Ok(Error::InvalidAmount)));
+
+    let claim_id = client.submit_claim(
+        &provider_id,
+        &patient_id,
+        &12345,
+        &0,
+        &services,
+        &Vec::new(&env),
+        &BytesN::from_array(&env, &[0; 32]),
+        &25000,
+    );
+
+    let day = 86_400u64;
+    let aging = client.get_provider_aging(&provider_id, &(10 * day));
+    assert_eq!(aging.current, 25000);
+
+    let mut lines = Vec::new(&env);
+    lines.push_back(LineAdjudication {
+        line_number: 1,
+        allowed_amount: 10000,
+        paid_amount: 8000,
+        denied_amount: 0,
+        reason_codes: Vec::new(&env),
+    });
+    let mut reasons = Vec::new(&env);
+    reasons.push_back(String::from_str(&env, "CO-97"));
+    lines.push_back(LineAdjudication {
+        line_number: 2,
+        allowed_amount: 0,
+        paid_amount: 0,
+        denied_amount: 10000,
+        reason_codes: reasons,
+    });
+    client.adjudicate_claim_lines(&claim_id, &payer, &lines);
+
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.approved_amount, Some(10000));
+    assert_eq!(claim.patient_responsibility, Some(2000));
+    assert_eq!(claim.insurer_balance, 8000);
+    assert_eq!(client.get_patient_balance(&patient_id), 2000);
+
+    // Only the adjudicating payer can pay, and not more than it owes
+    let other = Address::generate(&env);
+    let res =
+        client.try_process_payment(&claim_id, &other, &8000, &0, &String::from_str(&env, "X"));
+    assert_eq!(res, Err(Ok(Error::NotAuthorized)));
+    let res =
+        client.try_process_payment(&claim_id, &payer, &9000, &0, &String::from_str(&env, "X"));
+    assert_eq!(res, Err(Ok(Error::InvalidAmount)));
+
+    client.process_payment(
+        &claim_id,
+        &payer,
+        &8000,
+        &0,
+        &String::from_str(&env, "EFT-1"),
+    );
+
+    // 45 days after adjudication the patient's share sits in the 31-60 bucket
+    let aging = client.get_patient_aging(&patient_id, &(45 * day));
+    assert_eq!(aging.days_31_60, 2000);
+    assert_eq!(aging.total, 2000);
+
+    client.apply_patient_payment(&claim_id, &patient_id, &1500, &0);
+    client.post_adjustment(
+        &claim_id,
+        &provider_id,
+        &500,
+        &String::from_str(&env, "SMALL_BAL"),
+    );
+
+    let claim = client.get_claim(&claim_id);
+    assert_eq!(claim.status, ClaimStatus::Closed);
+    assert_eq!(client.get_patient_balance(&patient_id), 0);
+    assert_eq!(
+        client.get_provider_aging(&provider_id, &(45 * day)).total,
+        0
+    );
+
+    let ledger = client.get_claim_ledger(&claim_id, &0, &10);
+    assert_eq!(ledger.len(), 3);
+    assert_eq!(ledger.get(0).unwrap().kind, LedgerEntryKind::InsurerPayment);
+    assert_eq!(ledger.get(2).unwrap().kind, LedgerEntryKind::Adjustment);
+}
+
+#[test]
+fn test_readjudication_updates_patient_balance() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MedicalClaimsSystem, ());
+    let client = MedicalClaimsSystemClient::new(&env, &contract_id);
+
+    let provider_id = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let payer = Address::generate(&env);
+
+    let mut services = Vec::new(&env);
+    services.push_back(ServiceLine {
+        procedure_code: String::from_str(&env, "99214"),
+        modifier: None,
+        quantity: 1,
+        charge_amount: 25000,
+        diagnosis_pointers: Vec::new(&env),
+    });
+    let claim_id = client.submit_claim(
+        &provider_id,
+        &patient_id,
+        &12345,
+        &0,
+        &services,
+        &Vec::new(&env),
+        &BytesN::from_array(&env, &[1; 32]),
+        &25000,
+    );
+
+    client.adjudicate_claim(
+        &claim_id,
+        &payer,
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &20000,
+        &5000,
+    );
+    assert_eq!(client.get_patient_balance(&patient_id), 5000);
+
+    client.appeal_denial(
+        &claim_id,
+        &provider_id,
+        &1,
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+    client.adjudicate_claim(
+        &claim_id,
+        &payer,
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &25000,
+        &1000,
+    );
+    assert_eq!(client.get_patient_balance(&patient_id), 1000);
+    assert_eq!(client.get_claim(&claim_id).insurer_balance, 24000);
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MedicalClaimsSystem, ());
+    let client = MedicalClaimsSystemClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
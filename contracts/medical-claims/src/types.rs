@@ -1,5 +1,3 @@
-#![no_std]
-
 use soroban_sdk::{contracterror, contracttype, Address, String, BytesN, Vec};
 
 #[contracterror]
@@ -10,6 +8,10 @@ pub enum Error {
     ClaimNotFound = 2,
     InvalidAppealLevel = 3,
     InvalidStateTransition = 4,
+    InvalidAmount = 5,
+    InvalidLine = 6,
+    AlreadyInitialized = 7,
+    NotInitialized = 8,
 }
 
 #[contracttype]
@@ -57,11 +59,56 @@ pub struct ClaimRecord {
     pub approved_amount: Option<i128>,
     pub patient_responsibility: Option<i128>,
     pub appeal_level: u32,
+    pub payer: Option<Address>,
+    pub insurer_balance: i128,
+    pub adjudicated_at: Option<u64>,
+}
+
+/// Payer decision for a single service line (1-based `line_number`).
+/// The patient owes `allowed_amount - paid_amount` for the line.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LineAdjudication {
+    pub line_number: u32,
+    pub allowed_amount: i128,
+    pub paid_amount: i128,
+    pub denied_amount: i128,
+    pub reason_codes: Vec<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum LedgerEntryKind {
+    InsurerPayment,
+    PatientPayment,
+    Adjustment,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LedgerEntry {
+    pub kind: LedgerEntryKind,
+    pub amount: i128,
+    pub posted_by: Address,
+    pub posted_at: u64,
+    pub reference: String,
+}
+
+/// Outstanding balances bucketed by age in days.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AgingReport {
+    pub current: i128, // 0-30 days
+    pub days_31_60: i128,
+    pub days_61_90: i128,
+    pub over_90: i128,
+    pub total: i128,
 }
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    Admin,
     ClaimCounter,
     Claim(u64), // claim_id -> ClaimRecord
     DenialInfos(u64), // claim_id -> Vec<DenialInfo>
@@ -70,4 +117,7 @@ pub enum DataKey {
     PatientClaims(Address),  // patient_id -> Vec<u64>
     ClaimPayment(u64), // claim_id -> (u64, String) // payment_date, payment_reference
     PatientPayment(u64), // claim_id -> (u64, i128) // payment_date, payment_amount
+    LineAdjudications(u64), // claim_id -> Vec<LineAdjudication>
+    ClaimLedger(u64), // claim_id -> Vec<LedgerEntry>
+    PatientBalance(Address), // patient_id -> i128
 }
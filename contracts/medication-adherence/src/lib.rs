@@ -0,0 +1,305 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contract]
+pub struct MedicationAdherenceContract;
+
+#[contractimpl]
+impl MedicationAdherenceContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_pharmacy(env: Env, admin: Address, pharmacy_id: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Pharmacy(pharmacy_id), &true);
+        Ok(())
+    }
+
+    pub fn set_default_config(
+        env: Env,
+        admin: Address,
+        config: AdherenceConfig,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        validate_config(&config)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::DefaultConfig, &config);
+        Ok(())
+    }
+
+    /// Override gap and target settings for one drug class.
+    pub fn set_class_config(
+        env: Env,
+        admin: Address,
+        drug_class: Symbol,
+        config: AdherenceConfig,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        validate_config(&config)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ClassConfig(drug_class), &config);
+        Ok(())
+    }
+
+    pub fn get_config(env: Env, drug_class: Symbol) -> AdherenceConfig {
+        config_for(&env, &drug_class)
+    }
+
+    // -------------------------------------------------------------------
+    // Dispensing
+    // -------------------------------------------------------------------
+
+    /// Record a fill. A fill that arrives later than the previous supply's
+    /// run-out plus the class gap threshold emits `RefillGapDetected`.
+    pub fn record_dispense(
+        env: Env,
+        pharmacy_id: Address,
+        input: DispenseInput,
+    ) -> Result<(), Error> {
+        pharmacy_id.require_auth();
+        if !is_pharmacy(&env, &pharmacy_id) {
+            return Err(Error::PharmacyNotRegistered);
+        }
+        if input.days_supply == 0
+            || input.quantity == 0
+            || input.fill_date > env.ledger().timestamp()
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut records = dispenses(&env, &input.patient_id, &input.drug_class);
+        let fill_day = input.fill_date / SECONDS_PER_DAY;
+        let latest = records
+            .last()
+            .is_none_or(|last| last.fill_date <= input.fill_date);
+        if latest && !records.is_empty() {
+            let run_out = coverage_end(&records);
+            let threshold = u64::from(config_for(&env, &input.drug_class).gap_threshold_days);
+            if fill_day > run_out + threshold {
+                RefillGapDetected {
+                    patient_id: input.patient_id.clone(),
+                    drug_class: input.drug_class.clone(),
+                    gap_days: (fill_day - run_out) as u32,
+                    prescription_id: input.prescription_id,
+                }
+                .publish(&env);
+            }
+        }
+
+        let record = DispenseRecord {
+            prescription_id: input.prescription_id,
+            pharmacy_id,
+            patient_id: input.patient_id.clone(),
+            drug_class: input.drug_class.clone(),
+            ndc_code: input.ndc_code,
+            fill_date: input.fill_date,
+            days_supply: input.days_supply,
+            quantity: input.quantity,
+            recorded_at: env.ledger().timestamp(),
+        };
+        // Keep fills ordered by date so coverage can be walked in one pass.
+        let position = records
+            .iter()
+            .position(|r| r.fill_date > record.fill_date)
+            .map(|i| i as u32)
+            .unwrap_or(records.len());
+        records.insert(position, record);
+        save_dispenses(&env, &input.patient_id, &input.drug_class, &records);
+        add_patient_class(&env, &input.patient_id, &input.drug_class);
+        Ok(())
+    }
+
+    pub fn get_dispenses(
+        env: Env,
+        patient_id: Address,
+        drug_class: Symbol,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<DispenseRecord> {
+        let records = dispenses(&env, &patient_id, &drug_class);
+        shared_pagination::paginate(&records, offset, limit)
+    }
+
+    // -------------------------------------------------------------------
+    // Adherence
+    // -------------------------------------------------------------------
+
+    /// Proportion of days covered for one drug class between `period_start`
+    /// and `period_end` (inclusive). Early refills carry their supply forward.
+    pub fn get_adherence(
+        env: Env,
+        patient_id: Address,
+        drug_class: Symbol,
+        period_start: u64,
+        period_end: u64,
+    ) -> Result<AdherenceSummary, Error> {
+        if period_end < period_start {
+            return Err(Error::InvalidInput);
+        }
+        compute_summary(&env, &patient_id, &drug_class, period_start, period_end)
+    }
+
+    /// Summaries for every drug class filled within the period, for use by
+    /// care-management and analytics contracts.
+    pub fn get_patient_adherence(
+        env: Env,
+        patient_id: Address,
+        period_start: u64,
+        period_end: u64,
+    ) -> Vec<AdherenceSummary> {
+        let mut summaries = Vec::new(&env);
+        if period_end < period_start {
+            return summaries;
+        }
+        for drug_class in patient_classes(&env, &patient_id).iter() {
+            if let Ok(summary) =
+                compute_summary(&env, &patient_id, &drug_class, period_start, period_end)
+            {
+                summaries.push_back(summary);
+            }
+        }
+        summaries
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn validate_config(config: &AdherenceConfig) -> Result<(), Error> {
+    if config.pdc_target_bps == 0 || config.pdc_target_bps > 10_000 {
+        return Err(Error::InvalidInput);
+    }
+    Ok(())
+}
+
+/// First day (exclusive) not covered by any fill, carrying early refills forward.
+fn coverage_end(records: &Vec<DispenseRecord>) -> u64 {
+    let mut cursor = 0u64;
+    for r in records.iter() {
+        let start = (r.fill_date / SECONDS_PER_DAY).max(cursor);
+        cursor = start + u64::from(r.days_supply);
+    }
+    cursor
+}
+
+fn compute_summary(
+    env: &Env,
+    patient_id: &Address,
+    drug_class: &Symbol,
+    period_start: u64,
+    period_end: u64,
+) -> Result<AdherenceSummary, Error> {
+    let records = dispenses(env, patient_id, drug_class);
+    let end_day = period_end / SECONDS_PER_DAY + 1;
+    let first = records.first().ok_or(Error::NoDispenses)?;
+    let index_day = (period_start / SECONDS_PER_DAY).max(first.fill_date / SECONDS_PER_DAY);
+    if index_day >= end_day {
+        return Err(Error::NoDispenses);
+    }
+
+    let mut cursor = 0u64;
+    let mut covered = 0u64;
+    let mut longest_gap = 0u64;
+    let mut last_fill_date = 0u64;
+    for r in records.iter() {
+        let fill_day = r.fill_date / SECONDS_PER_DAY;
+        if fill_day >= end_day {
+            break;
+        }
+        last_fill_date = r.fill_date;
+        let seg_start = fill_day.max(cursor);
+        let seg_end = seg_start + u64::from(r.days_supply);
+
+        let gap_from = cursor.max(index_day);
+        if seg_start > gap_from {
+            longest_gap = longest_gap.max(seg_start.min(end_day) - gap_from);
+        }
+        let overlap_start = seg_start.max(index_day);
+        let overlap_end = seg_end.min(end_day);
+        if overlap_end > overlap_start {
+            covered += overlap_end - overlap_start;
+        }
+        cursor = seg_end;
+    }
+    let gap_from = cursor.max(index_day);
+    if end_day > gap_from {
+        longest_gap = longest_gap.max(end_day - gap_from);
+    }
+
+    let days_in_period = end_day - index_day;
+    let pdc_bps = (covered * 10_000 / days_in_period) as u32;
+    let config = config_for(env, drug_class);
+
+    Ok(AdherenceSummary {
+        patient_id: patient_id.clone(),
+        drug_class: drug_class.clone(),
+        period_start: index_day * SECONDS_PER_DAY,
+        period_end,
+        days_in_period: days_in_period as u32,
+        days_covered: covered as u32,
+        pdc_bps,
+        longest_gap_days: longest_gap as u32,
+        gap_flagged: longest_gap > u64::from(config.gap_threshold_days),
+        meets_target: pdc_bps >= config.pdc_target_bps,
+        last_fill_date,
+    })
+}
@@ -0,0 +1,77 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{AdherenceConfig, DataKey, DispenseRecord};
+
+const DEFAULT_GAP_THRESHOLD_DAYS: u32 = 30;
+const DEFAULT_PDC_TARGET_BPS: u32 = 8_000;
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_pharmacy(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Pharmacy(who.clone()))
+        .unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Configuration
+// -----------------------------------------------------------------------
+
+/// Class-specific settings, falling back to the default configuration.
+pub fn config_for(env: &Env, drug_class: &Symbol) -> AdherenceConfig {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ClassConfig(drug_class.clone()))
+        .or_else(|| env.storage().instance().get(&DataKey::DefaultConfig))
+        .unwrap_or(AdherenceConfig {
+            gap_threshold_days: DEFAULT_GAP_THRESHOLD_DAYS,
+            pdc_target_bps: DEFAULT_PDC_TARGET_BPS,
+        })
+}
+
+// -----------------------------------------------------------------------
+// Dispenses
+// -----------------------------------------------------------------------
+
+pub fn dispenses(env: &Env, patient_id: &Address, drug_class: &Symbol) -> Vec<DispenseRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Dispenses(patient_id.clone(), drug_class.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_dispenses(
+    env: &Env,
+    patient_id: &Address,
+    drug_class: &Symbol,
+    records: &Vec<DispenseRecord>,
+) {
+    env.storage().persistent().set(
+        &DataKey::Dispenses(patient_id.clone(), drug_class.clone()),
+        records,
+    );
+}
+
+pub fn patient_classes(env: &Env, patient_id: &Address) -> Vec<Symbol> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::PatientClasses(patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_patient_class(env: &Env, patient_id: &Address, drug_class: &Symbol) {
+    let mut classes = patient_classes(env, patient_id);
+    if !classes.contains(drug_class) {
+        classes.push_back(drug_class.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::PatientClasses(patient_id.clone()), &classes);
+    }
+}
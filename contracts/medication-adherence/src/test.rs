@@ -0,0 +1,267 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, String,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+const JAN_1: u64 = 20_000 * DAY;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    MedicationAdherenceContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(JAN_1 + 400 * DAY);
+    let contract_id = env.register(MedicationAdherenceContract, ());
+    let client = MedicationAdherenceContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let pharmacy = Address::generate(&env);
+    let patient = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_pharmacy(&admin, &pharmacy);
+
+    (env, admin, pharmacy, patient, client)
+}
+
+fn fill(
+    env: &Env,
+    client: &MedicationAdherenceContractClient,
+    pharmacy: &Address,
+    patient: &Address,
+    drug_class: Symbol,
+    day: u64,
+    days_supply: u32,
+) {
+    client.record_dispense(
+        pharmacy,
+        &DispenseInput {
+            prescription_id: 1,
+            patient_id: patient.clone(),
+            drug_class,
+            ndc_code: String::from_str(env, "00093-7212-98"),
+            fill_date: JAN_1 + day * DAY,
+            days_supply,
+            quantity: days_supply,
+        },
+    );
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_fully_covered_period() {
+    let (env, _, pharmacy, patient, client) = setup();
+    let statin = symbol_short!("statin");
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 0, 30);
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 30, 30);
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 60, 30);
+
+    let summary = client.get_adherence(&patient, &statin, &JAN_1, &(JAN_1 + 89 * DAY));
+    assert_eq!(summary.days_in_period, 90);
+    assert_eq!(summary.days_covered, 90);
+    assert_eq!(summary.pdc_bps, 10_000);
+    assert_eq!(summary.longest_gap_days, 0);
+    assert!(summary.meets_target && !summary.gap_flagged);
+}
+
+#[test]
+fn test_gap_lowers_pdc_and_is_flagged() {
+    let (env, admin, pharmacy, patient, client) = setup();
+    let ace = symbol_short!("acei");
+    client.set_class_config(
+        &admin,
+        &ace,
+        &AdherenceConfig {
+            gap_threshold_days: 14,
+            pdc_target_bps: 8_000,
+        },
+    );
+    fill(&env, &client, &pharmacy, &patient, ace.clone(), 0, 30);
+    fill(&env, &client, &pharmacy, &patient, ace.clone(), 50, 30);
+
+    let summary = client.get_adherence(&patient, &ace, &JAN_1, &(JAN_1 + 89 * DAY));
+    // Covered 0-29 and 50-79; gaps of 20 days and a trailing 10.
+    assert_eq!(summary.days_covered, 60);
+    assert_eq!(summary.pdc_bps, 6_666);
+    assert_eq!(summary.longest_gap_days, 20);
+    assert!(summary.gap_flagged);
+    assert!(!summary.meets_target);
+    assert_eq!(summary.last_fill_date, JAN_1 + 50 * DAY);
+}
+
+#[test]
+fn test_early_refills_carry_forward() {
+    let (env, _, pharmacy, patient, client) = setup();
+    let metformin = symbol_short!("biguan");
+    fill(&env, &client, &pharmacy, &patient, metformin.clone(), 0, 30);
+    // Refilled 5 days early: supply starts on day 30.
+    fill(
+        &env,
+        &client,
+        &pharmacy,
+        &patient,
+        metformin.clone(),
+        25,
+        30,
+    );
+
+    let summary = client.get_adherence(&patient, &metformin, &JAN_1, &(JAN_1 + 59 * DAY));
+    assert_eq!(summary.days_covered, 60);
+    assert_eq!(summary.longest_gap_days, 0);
+}
+
+#[test]
+fn test_late_refill_emits_gap_event() {
+    let (env, _, pharmacy, patient, client) = setup();
+    let statin = symbol_short!("statin");
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 0, 30);
+    let before = env.events().all().len();
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 45, 30);
+    assert_eq!(env.events().all().len(), before);
+
+    // Default threshold is 30 days; supply ran out on day 75.
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 120, 30);
+    assert_eq!(env.events().all().len(), before + 1);
+
+    let summaries = client.get_patient_adherence(&patient, &JAN_1, &(JAN_1 + 149 * DAY));
+    assert_eq!(summaries.len(), 1);
+    assert!(summaries.get(0).unwrap().gap_flagged);
+}
+
+#[test]
+fn test_dispense_validation() {
+    let (env, _, pharmacy, patient, client) = setup();
+    let other = Address::generate(&env);
+    let input = DispenseInput {
+        prescription_id: 7,
+        patient_id: patient.clone(),
+        drug_class: symbol_short!("statin"),
+        ndc_code: String::from_str(&env, "00093-7212-98"),
+        fill_date: JAN_1,
+        days_supply: 30,
+        quantity: 30,
+    };
+    assert_eq!(
+        client.try_record_dispense(&other, &input),
+        Err(Ok(Error::PharmacyNotRegistered))
+    );
+
+    let mut future = input.clone();
+    future.fill_date = JAN_1 + 500 * DAY;
+    assert_eq!(
+        client.try_record_dispense(&pharmacy, &future),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    assert_eq!(
+        client.try_get_adherence(&patient, &symbol_short!("statin"), &JAN_1, &(JAN_1 + DAY)),
+        Err(Ok(Error::NoDispenses))
+    );
+}
+
+#[test]
+fn test_configuration_requires_admin() {
+    let (env, admin, pharmacy, _, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let config = AdherenceConfig {
+        gap_threshold_days: 10,
+        pdc_target_bps: 9_000,
+    };
+    assert_eq!(
+        client.try_register_pharmacy(&pharmacy, &Address::generate(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_default_config(&pharmacy, &config),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_class_config(&pharmacy, &symbol_short!("statin"), &config),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_config_target_must_be_a_valid_rate() {
+    let (_, admin, _, _, client) = setup();
+    for pdc_target_bps in [0, 10_001] {
+        assert_eq!(
+            client.try_set_default_config(
+                &admin,
+                &AdherenceConfig {
+                    gap_threshold_days: 30,
+                    pdc_target_bps,
+                },
+            ),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+}
+
+#[test]
+fn test_empty_fills_and_periods_rejected() {
+    let (env, _, pharmacy, patient, client) = setup();
+    let statin = symbol_short!("statin");
+    let input = DispenseInput {
+        prescription_id: 3,
+        patient_id: patient.clone(),
+        drug_class: statin.clone(),
+        ndc_code: String::from_str(&env, "00093-7212-98"),
+        fill_date: JAN_1,
+        days_supply: 0,
+        quantity: 30,
+    };
+    assert_eq!(
+        client.try_record_dispense(&pharmacy, &input),
+        Err(Ok(Error::InvalidInput))
+    );
+    let mut no_quantity = input.clone();
+    no_quantity.days_supply = 30;
+    no_quantity.quantity = 0;
+    assert_eq!(
+        client.try_record_dispense(&pharmacy, &no_quantity),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    fill(&env, &client, &pharmacy, &patient, statin.clone(), 30, 30);
+    assert_eq!(
+        client.try_get_adherence(&patient, &statin, &(JAN_1 + DAY), &JAN_1),
+        Err(Ok(Error::InvalidInput))
+    );
+    // A window that closes before the first fill has nothing to measure
+    assert_eq!(
+        client.try_get_adherence(&patient, &statin, &JAN_1, &(JAN_1 + 10 * DAY)),
+        Err(Ok(Error::NoDispenses))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, pharmacy, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&pharmacy), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&pharmacy, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,112 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, String, Symbol};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    PharmacyNotRegistered = 4,
+    InvalidInput = 5,
+    NoDispenses = 6,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DispenseInput {
+    /// Prescription id in the prescription-management contract.
+    pub prescription_id: u64,
+    pub patient_id: Address,
+    pub drug_class: Symbol,
+    pub ndc_code: String,
+    pub fill_date: u64,
+    pub days_supply: u32,
+    pub quantity: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DispenseRecord {
+    pub prescription_id: u64,
+    pub pharmacy_id: Address,
+    pub patient_id: Address,
+    pub drug_class: Symbol,
+    pub ndc_code: String,
+    pub fill_date: u64,
+    pub days_supply: u32,
+    pub quantity: u32,
+    pub recorded_at: u64,
+}
+
+/// Gap and target settings, per drug class or as the default.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdherenceConfig {
+    /// Uncovered days beyond which a gap is flagged.
+    pub gap_threshold_days: u32,
+    /// PDC target in basis points (8000 = 80%).
+    pub pdc_target_bps: u32,
+}
+
+/// Proportion of days covered for one patient and drug class. The period
+/// starts at the later of `period_start` and the first fill (index date).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdherenceSummary {
+    pub patient_id: Address,
+    pub drug_class: Symbol,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub days_in_period: u32,
+    pub days_covered: u32,
+    pub pdc_bps: u32,
+    pub longest_gap_days: u32,
+    pub gap_flagged: bool,
+    pub meets_target: bool,
+    pub last_fill_date: u64,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+/// Raised when a fill arrives after the previous supply ran out by more than
+/// the configured threshold.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefillGapDetected {
+    #[topic]
+    pub patient_id: Address,
+    #[topic]
+    pub drug_class: Symbol,
+    pub gap_days: u32,
+    pub prescription_id: u64,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// pharmacy -> bool
+    Pharmacy(Address),
+    DefaultConfig,
+    /// drug_class -> AdherenceConfig
+    ClassConfig(Symbol),
+    /// (patient, drug_class) -> Vec<DispenseRecord>, ordered by fill date
+    Dispenses(Address, Symbol),
+    /// patient -> Vec<Symbol> (drug classes with dispenses)
+    PatientClasses(Address),
+}
@@ -1,7 +1,7 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, vec, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contracterror, contractevent, contractimpl, contracttype, vec, Address, BytesN, Env, String, Symbol, Vec,
 };
 
 #[contracterror]
@@ -11,8 +11,26 @@ pub enum Error {
     NotFound = 1,
     NotAuthorized = 2,
     RequiresExplicitConsent = 3,
+    InvalidMeasurementTool = 4,
+    InvalidMeasurementInterval = 5,
+    InvalidCoverageWindow = 6,
+    InvalidHoldType = 7,
+    InvalidHoldDuration = 8,
+    HoldNotActive = 9,
+    HearingNotFound = 10,
+    InvalidHearingOutcome = 11,
+    InvalidDisclosureScope = 12,
+    AlreadyInitialized = 13,
+    NotInitialized = 14,
 }
 
+const SECONDS_PER_DAY: u64 = 86_400;
+/// Days after the due date before an administration is reported overdue
+const MEASUREMENT_GRACE_DAYS: u64 = 7;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct MentalHealthAssessment {
@@ -57,6 +75,18 @@ pub struct TreatmentPlan {
     pub review_date: u64,
 }
 
+/// Fields of a new treatment plan; the patient and provider are passed alongside
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreatmentPlanInput {
+    pub diagnoses: Vec<String>,
+    pub treatment_goals: Vec<TreatmentGoal>,
+    pub interventions: Vec<String>,
+    pub frequency: String,
+    pub review_date: u64,
+    pub measurement_schedule: Vec<MeasurementSchedule>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct TherapySession {
@@ -114,8 +144,123 @@ pub struct Screening {
     pub screening_date: u64,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeasurementSchedule {
+    pub tool: Symbol, // PHQ9, GAD7
+    pub interval_days: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScheduledMeasurement {
+    pub plan_id: u64,
+    pub patient_id: Address,
+    pub tool: Symbol,
+    pub interval_days: u32,
+    pub last_administered: Option<u64>,
+    pub next_due: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MeasurementWorklistItem {
+    pub plan_id: u64,
+    pub patient_id: Address,
+    pub tool: Symbol,
+    pub due_date: u64,
+    pub status: Symbol, // due, overdue
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoverageShift {
+    pub facility_id: Address,
+    pub provider_id: Address,
+    pub start: u64,
+    pub end: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CrisisEvent {
+    pub event_id: u64,
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub reported_by: Address,
+    pub reported_at: u64,
+    pub severity: Symbol,
+    pub description: String,
+    /// On-call provider and shift window the event was routed to, if any coverage was active
+    pub routed_to: Option<Address>,
+    pub routed_shift_start: Option<u64>,
+    pub routed_shift_end: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CommitmentHearing {
+    pub hearing_date: u64,
+    pub outcome: Symbol, // pending, committed, released, continued
+    pub recorded_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegalHold {
+    pub hold_id: u64,
+    pub hospitalization_id: u64,
+    pub patient_id: Address,
+    pub facility_id: Address,
+    pub hold_type: Symbol, // emergency, temporary, extended, court_order
+    pub statutory_duration_hours: u32,
+    pub started_at: u64,
+    pub expires_at: u64,
+    pub hearings: Vec<CommitmentHearing>,
+    pub status: Symbol, // active, released, expired
+    pub released_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisclosureConsent {
+    pub patient_id: Address,
+    pub recipient_provider: Address,
+    pub scopes: Vec<Symbol>, // all, treatment_plan, assessments, sessions, substance_use
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+    pub revoked: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisclosureRecord {
+    pub disclosure_id: u64,
+    pub patient_id: Address,
+    pub recipient_provider: Address,
+    pub released_by: Address,
+    pub scope: Symbol,
+    pub summary_hash: BytesN<32>,
+    pub disclosed_at: u64,
+    /// Substance-use records may not be redisclosed by the recipient
+    pub redisclosure_prohibited: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreatmentSummaryReleased {
+    #[topic]
+    pub patient_id: Address,
+    #[topic]
+    pub recipient_provider: Address,
+    pub released_by: Address,
+    pub disclosure_id: u64,
+    pub scope: Symbol,
+}
+
 #[contracttype]
 pub enum DataKey {
+    Admin,
     AssessmentCounter,
     PlanCounter,
     HospitalizationCounter,
@@ -129,6 +274,18 @@ pub enum DataKey {
     Session(u64, u64),
     Symptom(Address, Symbol, u64),
     Outcomes(u64, u64),
+    MeasurementSchedule(u64),
+    PatientPlans(Address),
+    ProviderPlans(Address),
+    CrisisEventCounter,
+    CoverageRoster(Address),
+    CrisisEvent(u64),
+    LegalHoldCounter,
+    LegalHold(u64),
+    FacilityHolds(Address),
+    DisclosureCounter,
+    DisclosureConsent(Address, Address),
+    DisclosureLog(Address),
 }
 
 #[contract]
@@ -136,6 +293,16 @@ pub struct MentalHealthContract;
 
 #[contractimpl]
 impl MentalHealthContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn conduct_mental_health_assessment(
         env: Env,
         patient_id: Address,
@@ -173,7 +340,7 @@ impl MentalHealthContract {
         assessment_id: u64,
         total_score: u32,
         _item_scores: Vec<u32>,
-        _assessment_date: u64,
+        assessment_date: u64,
     ) -> Result<(), Error> {
         let mut assessment: MentalHealthAssessment = env
             .storage()
@@ -183,6 +350,7 @@ impl MentalHealthContract {
 
         assessment.phq9_score = Some(total_score);
         env.storage().persistent().set(&DataKey::Assessment(assessment_id), &assessment);
+        mark_measurement_administered(&env, &assessment.patient_id, &Symbol::new(&env, "PHQ9"), assessment_date);
 
         Ok(())
     }
@@ -192,7 +360,7 @@ impl MentalHealthContract {
         assessment_id: u64,
         total_score: u32,
         _item_scores: Vec<u32>,
-        _assessment_date: u64,
+        assessment_date: u64,
     ) -> Result<(), Error> {
         let mut assessment: MentalHealthAssessment = env
             .storage()
@@ -202,6 +370,7 @@ impl MentalHealthContract {
 
         assessment.gad7_score = Some(total_score);
         env.storage().persistent().set(&DataKey::Assessment(assessment_id), &assessment);
+        mark_measurement_administered(&env, &assessment.patient_id, &Symbol::new(&env, "GAD7"), assessment_date);
 
         Ok(())
     }
@@ -265,17 +434,49 @@ impl MentalHealthContract {
         env: Env,
         patient_id: Address,
         provider_id: Address,
-        diagnoses: Vec<String>,
-        treatment_goals: Vec<TreatmentGoal>,
-        interventions: Vec<String>,
-        frequency: String,
-        review_date: u64,
+        input: TreatmentPlanInput,
     ) -> Result<u64, Error> {
         provider_id.require_auth();
 
+        let TreatmentPlanInput {
+            diagnoses,
+            treatment_goals,
+            interventions,
+            frequency,
+            review_date,
+            measurement_schedule,
+        } = input;
+
+        let tools = [Symbol::new(&env, "PHQ9"), Symbol::new(&env, "GAD7")];
+        for entry in measurement_schedule.iter() {
+            if !tools.contains(&entry.tool) {
+                return Err(Error::InvalidMeasurementTool);
+            }
+            if entry.interval_days == 0 {
+                return Err(Error::InvalidMeasurementInterval);
+            }
+        }
+
         let mut count: u64 = env.storage().instance().get(&DataKey::PlanCounter).unwrap_or(0);
         count += 1;
 
+        // Baseline administrations are due as soon as the plan starts
+        let now = env.ledger().timestamp();
+        let mut scheduled: Vec<ScheduledMeasurement> = vec![&env];
+        for entry in measurement_schedule.iter() {
+            scheduled.push_back(ScheduledMeasurement {
+                plan_id: count,
+                patient_id: patient_id.clone(),
+                tool: entry.tool,
+                interval_days: entry.interval_days,
+                last_administered: None,
+                next_due: now,
+            });
+        }
+        env.storage().persistent().set(&DataKey::MeasurementSchedule(count), &scheduled);
+        push_id(&env, DataKey::PatientPlans(patient_id.clone()), count);
+        push_id(&env, DataKey::ProviderPlans(provider_id.clone()), count);
+
         let plan = TreatmentPlan {
             plan_id: count,
             patient_id,
@@ -367,6 +568,160 @@ impl MentalHealthContract {
         Ok(count)
     }
 
+    /// Restricted to the patient and the admitting facility
+    pub fn get_hospitalization(env: Env, hospitalization_id: u64, caller: Address) -> Result<Hospitalization, Error> {
+        caller.require_auth();
+
+        let hosp: Hospitalization =
+            env.storage().persistent().get(&DataKey::Hospitalization(hospitalization_id)).ok_or(Error::NotFound)?;
+        if caller != hosp.patient_id && caller != hosp.facility_id {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(hosp)
+    }
+
+    /// Place an involuntary hold on a hospitalization; the hold type becomes its legal status
+    pub fn place_legal_hold(
+        env: Env,
+        hospitalization_id: u64,
+        hold_type: Symbol,
+        statutory_duration_hours: u32,
+        started_at: u64,
+    ) -> Result<u64, Error> {
+        let mut hosp: Hospitalization = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Hospitalization(hospitalization_id))
+            .ok_or(Error::NotFound)?;
+        hosp.facility_id.require_auth();
+
+        let hold_types = ["emergency", "temporary", "extended", "court_order"];
+        if !hold_types.iter().any(|t| hold_type == Symbol::new(&env, t)) {
+            return Err(Error::InvalidHoldType);
+        }
+        if statutory_duration_hours == 0 {
+            return Err(Error::InvalidHoldDuration);
+        }
+
+        let mut count: u64 = env.storage().instance().get(&DataKey::LegalHoldCounter).unwrap_or(0);
+        count += 1;
+
+        let hold = LegalHold {
+            hold_id: count,
+            hospitalization_id,
+            patient_id: hosp.patient_id.clone(),
+            facility_id: hosp.facility_id.clone(),
+            hold_type: hold_type.clone(),
+            statutory_duration_hours,
+            started_at,
+            expires_at: started_at + statutory_duration_hours as u64 * 3_600,
+            hearings: vec![&env],
+            status: Symbol::new(&env, "active"),
+            released_at: None,
+        };
+
+        env.storage().persistent().set(&DataKey::LegalHold(count), &hold);
+        env.storage().instance().set(&DataKey::LegalHoldCounter, &count);
+        push_id(&env, DataKey::FacilityHolds(hosp.facility_id.clone()), count);
+
+        hosp.legal_status = hold_type;
+        env.storage().persistent().set(&DataKey::Hospitalization(hospitalization_id), &hosp);
+
+        Ok(count)
+    }
+
+    pub fn schedule_commitment_hearing(env: Env, hold_id: u64, hearing_date: u64) -> Result<u32, Error> {
+        let mut hold = load_active_hold(&env, hold_id)?;
+        hold.facility_id.require_auth();
+
+        hold.hearings.push_back(CommitmentHearing {
+            hearing_date,
+            outcome: Symbol::new(&env, "pending"),
+            recorded_at: None,
+        });
+        env.storage().persistent().set(&DataKey::LegalHold(hold_id), &hold);
+
+        Ok(hold.hearings.len() - 1)
+    }
+
+    /// Record a hearing outcome. A commitment order must carry the new expiration;
+    /// a release ends the hold.
+    pub fn record_hearing_outcome(
+        env: Env,
+        hold_id: u64,
+        hearing_index: u32,
+        outcome: Symbol,
+        new_expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        let mut hold = load_active_hold(&env, hold_id)?;
+        hold.facility_id.require_auth();
+
+        let mut hearing = hold.hearings.get(hearing_index).ok_or(Error::HearingNotFound)?;
+        let now = env.ledger().timestamp();
+
+        if outcome == Symbol::new(&env, "committed") {
+            hold.expires_at = new_expires_at.ok_or(Error::InvalidHearingOutcome)?;
+            hold.hold_type = Symbol::new(&env, "court_order");
+            set_legal_status(&env, hold.hospitalization_id, hold.hold_type.clone())?;
+        } else if outcome == Symbol::new(&env, "released") {
+            hold.status = Symbol::new(&env, "released");
+            hold.released_at = Some(now);
+            set_legal_status(&env, hold.hospitalization_id, Symbol::new(&env, "voluntary"))?;
+        } else if outcome != Symbol::new(&env, "continued") {
+            return Err(Error::InvalidHearingOutcome);
+        }
+
+        hearing.outcome = outcome;
+        hearing.recorded_at = Some(now);
+        hold.hearings.set(hearing_index, hearing);
+        env.storage().persistent().set(&DataKey::LegalHold(hold_id), &hold);
+
+        Ok(())
+    }
+
+    pub fn release_legal_hold(env: Env, hold_id: u64) -> Result<(), Error> {
+        let mut hold = load_active_hold(&env, hold_id)?;
+        hold.facility_id.require_auth();
+
+        hold.status = Symbol::new(&env, "released");
+        hold.released_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&DataKey::LegalHold(hold_id), &hold);
+        set_legal_status(&env, hold.hospitalization_id, Symbol::new(&env, "voluntary"))?;
+
+        Ok(())
+    }
+
+    /// Get a hold, reporting it as expired once its expiration has passed.
+    /// Restricted to the patient and the holding facility.
+    pub fn get_legal_hold(env: Env, hold_id: u64, caller: Address) -> Result<LegalHold, Error> {
+        caller.require_auth();
+
+        let hold: LegalHold = env.storage().persistent().get(&DataKey::LegalHold(hold_id)).ok_or(Error::NotFound)?;
+        if caller != hold.patient_id && caller != hold.facility_id {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(with_expiry(&env, hold))
+    }
+
+    /// Patients currently under an active hold at a facility. Restricted to the facility.
+    pub fn get_active_holds(env: Env, facility_id: Address) -> Vec<LegalHold> {
+        facility_id.require_auth();
+
+        let hold_ids: Vec<u64> = env.storage().persistent().get(&DataKey::FacilityHolds(facility_id)).unwrap_or(vec![&env]);
+        let active = Symbol::new(&env, "active");
+
+        let mut holds = vec![&env];
+        for hold_id in hold_ids.iter() {
+            if let Some(hold) = env.storage().persistent().get::<_, LegalHold>(&DataKey::LegalHold(hold_id)) {
+                let hold = with_expiry(&env, hold);
+                if hold.status == active {
+                    holds.push_back(hold);
+                }
+            }
+        }
+        holds
+    }
+
     pub fn request_substance_screening(
         env: Env,
         patient_id: Address,
@@ -414,6 +769,124 @@ impl MentalHealthContract {
         Ok(())
     }
 
+    /// Consent for a named recipient. A general `all` scope never covers substance-use
+    /// records; those must be consented to by name.
+    pub fn grant_disclosure_consent(
+        env: Env,
+        patient_id: Address,
+        recipient_provider: Address,
+        scopes: Vec<Symbol>,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        if scopes.is_empty() {
+            return Err(Error::InvalidDisclosureScope);
+        }
+        for scope in scopes.iter() {
+            if !is_disclosure_scope(&env, &scope) {
+                return Err(Error::InvalidDisclosureScope);
+            }
+        }
+
+        let consent = DisclosureConsent {
+            patient_id: patient_id.clone(),
+            recipient_provider: recipient_provider.clone(),
+            scopes,
+            granted_at: env.ledger().timestamp(),
+            expires_at,
+            revoked: false,
+        };
+        env.storage().persistent().set(&DataKey::DisclosureConsent(patient_id, recipient_provider), &consent);
+
+        Ok(())
+    }
+
+    pub fn revoke_disclosure_consent(env: Env, patient_id: Address, recipient_provider: Address) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let key = DataKey::DisclosureConsent(patient_id, recipient_provider);
+        let mut consent: DisclosureConsent = env.storage().persistent().get(&key).ok_or(Error::NotFound)?;
+        consent.revoked = true;
+        env.storage().persistent().set(&key, &consent);
+
+        Ok(())
+    }
+
+    /// Release a coordination-of-care summary to the recipient named in the patient's consent.
+    /// The release is made by the patient or a provider with a treatment plan for the patient.
+    pub fn release_treatment_summary(
+        env: Env,
+        patient_id: Address,
+        released_by: Address,
+        recipient_provider: Address,
+        scope: Symbol,
+        summary_hash: BytesN<32>,
+    ) -> Result<u64, Error> {
+        released_by.require_auth();
+
+        if released_by != patient_id && !is_treating_provider(&env, &patient_id, &released_by) {
+            return Err(Error::NotAuthorized);
+        }
+
+        if scope == Symbol::new(&env, "all") || !is_disclosure_scope(&env, &scope) {
+            return Err(Error::InvalidDisclosureScope);
+        }
+
+        let consent: DisclosureConsent = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DisclosureConsent(patient_id.clone(), recipient_provider.clone()))
+            .ok_or(Error::RequiresExplicitConsent)?;
+
+        let now = env.ledger().timestamp();
+        if consent.revoked || consent.expires_at.is_some_and(|expires_at| now >= expires_at) {
+            return Err(Error::RequiresExplicitConsent);
+        }
+
+        let substance_use = scope == Symbol::new(&env, "substance_use");
+        let covered = consent.scopes.contains(&scope) || (!substance_use && consent.scopes.contains(Symbol::new(&env, "all")));
+        if !covered {
+            return Err(Error::RequiresExplicitConsent);
+        }
+
+        let mut count: u64 = env.storage().instance().get(&DataKey::DisclosureCounter).unwrap_or(0);
+        count += 1;
+
+        let record = DisclosureRecord {
+            disclosure_id: count,
+            patient_id: patient_id.clone(),
+            recipient_provider: recipient_provider.clone(),
+            released_by: released_by.clone(),
+            scope: scope.clone(),
+            summary_hash,
+            disclosed_at: now,
+            redisclosure_prohibited: substance_use,
+        };
+
+        let key = DataKey::DisclosureLog(patient_id.clone());
+        let mut log: Vec<DisclosureRecord> = env.storage().persistent().get(&key).unwrap_or(vec![&env]);
+        log.push_back(record);
+        env.storage().persistent().set(&key, &log);
+        env.storage().instance().set(&DataKey::DisclosureCounter, &count);
+
+        TreatmentSummaryReleased {
+            patient_id,
+            recipient_provider,
+            released_by,
+            disclosure_id: count,
+            scope,
+        }
+        .publish(&env);
+
+        Ok(count)
+    }
+
+    pub fn get_disclosure_log(env: Env, patient_id: Address) -> Vec<DisclosureRecord> {
+        patient_id.require_auth();
+        env.storage().persistent().get(&DataKey::DisclosureLog(patient_id)).unwrap_or(vec![&env])
+    }
+
     pub fn set_enhanced_privacy_flag(
         env: Env,
         patient_id: Address,
@@ -424,6 +897,210 @@ impl MentalHealthContract {
         env.storage().persistent().set(&DataKey::PrivacyFlag(patient_id, record_type), &requires_explicit_consent);
         Ok(())
     }
+
+    pub fn register_coverage_shift(
+        env: Env,
+        facility_id: Address,
+        provider_id: Address,
+        start: u64,
+        end: u64,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+
+        if end <= start {
+            return Err(Error::InvalidCoverageWindow);
+        }
+
+        let key = DataKey::CoverageRoster(facility_id.clone());
+        let mut roster: Vec<CoverageShift> = env.storage().persistent().get(&key).unwrap_or(vec![&env]);
+        roster.push_back(CoverageShift { facility_id, provider_id, start, end });
+        env.storage().persistent().set(&key, &roster);
+
+        Ok(())
+    }
+
+    pub fn get_coverage_roster(env: Env, facility_id: Address) -> Vec<CoverageShift> {
+        env.storage().persistent().get(&DataKey::CoverageRoster(facility_id)).unwrap_or(vec![&env])
+    }
+
+    pub fn get_current_on_call(env: Env, facility_id: Address) -> Option<Address> {
+        current_shift(&env, &facility_id).map(|shift| shift.provider_id)
+    }
+
+    /// Record a crisis event, routed to whoever is on call at the facility right now
+    pub fn record_crisis_event(
+        env: Env,
+        patient_id: Address,
+        facility_id: Address,
+        reported_by: Address,
+        severity: Symbol,
+        description: String,
+    ) -> Result<u64, Error> {
+        reported_by.require_auth();
+
+        let mut count: u64 = env.storage().instance().get(&DataKey::CrisisEventCounter).unwrap_or(0);
+        count += 1;
+
+        let shift = current_shift(&env, &facility_id);
+        let event = CrisisEvent {
+            event_id: count,
+            patient_id,
+            facility_id,
+            reported_by,
+            reported_at: env.ledger().timestamp(),
+            severity,
+            description,
+            routed_to: shift.as_ref().map(|s| s.provider_id.clone()),
+            routed_shift_start: shift.as_ref().map(|s| s.start),
+            routed_shift_end: shift.map(|s| s.end),
+        };
+
+        env.storage().persistent().set(&DataKey::CrisisEvent(count), &event);
+        env.storage().instance().set(&DataKey::CrisisEventCounter, &count);
+
+        Ok(count)
+    }
+
+    /// Restricted to the patient, the facility, the reporter and the provider it was routed to
+    pub fn get_crisis_event(env: Env, event_id: u64, caller: Address) -> Result<CrisisEvent, Error> {
+        caller.require_auth();
+
+        let event: CrisisEvent = env.storage().persistent().get(&DataKey::CrisisEvent(event_id)).ok_or(Error::NotFound)?;
+        let routed_to = event.routed_to.as_ref() == Some(&caller);
+        if caller != event.patient_id && caller != event.facility_id && caller != event.reported_by && !routed_to {
+            return Err(Error::NotAuthorized);
+        }
+        Ok(event)
+    }
+
+    pub fn get_measurement_schedule(env: Env, plan_id: u64) -> Vec<ScheduledMeasurement> {
+        env.storage().persistent().get(&DataKey::MeasurementSchedule(plan_id)).unwrap_or(vec![&env])
+    }
+
+    /// Measurements due or overdue across a provider's treatment plans, as of now
+    pub fn get_measurement_worklist(env: Env, provider_id: Address) -> Vec<MeasurementWorklistItem> {
+        let now = env.ledger().timestamp();
+        let plan_ids: Vec<u64> = env.storage().persistent().get(&DataKey::ProviderPlans(provider_id)).unwrap_or(vec![&env]);
+
+        let mut worklist = vec![&env];
+        for plan_id in plan_ids.iter() {
+            for entry in Self::get_measurement_schedule(env.clone(), plan_id).iter() {
+                if entry.next_due > now {
+                    continue;
+                }
+                let overdue_at = entry.next_due + MEASUREMENT_GRACE_DAYS * SECONDS_PER_DAY;
+                let status = if now > overdue_at { Symbol::new(&env, "overdue") } else { Symbol::new(&env, "due") };
+                worklist.push_back(MeasurementWorklistItem {
+                    plan_id,
+                    patient_id: entry.patient_id,
+                    tool: entry.tool,
+                    due_date: entry.next_due,
+                    status,
+                });
+            }
+        }
+        worklist
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if stored != *admin {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+fn current_shift(env: &Env, facility_id: &Address) -> Option<CoverageShift> {
+    let now = env.ledger().timestamp();
+    let roster: Vec<CoverageShift> =
+        env.storage().persistent().get(&DataKey::CoverageRoster(facility_id.clone())).unwrap_or(vec![env]);
+    roster.iter().find(|shift| shift.start <= now && now < shift.end)
+}
+
+/// Whether the provider owns one of the patient's treatment plans
+fn is_treating_provider(env: &Env, patient_id: &Address, provider_id: &Address) -> bool {
+    let plan_ids: Vec<u64> = env.storage().persistent().get(&DataKey::PatientPlans(patient_id.clone())).unwrap_or(vec![env]);
+    plan_ids.iter().any(|plan_id| {
+        env.storage()
+            .persistent()
+            .get::<_, TreatmentPlan>(&DataKey::TreatmentPlan(plan_id))
+            .is_some_and(|plan| plan.provider_id == *provider_id)
+    })
+}
+
+fn is_disclosure_scope(env: &Env, scope: &Symbol) -> bool {
+    ["all", "treatment_plan", "assessments", "sessions", "substance_use"].iter().any(|s| *scope == Symbol::new(env, s))
+}
+
+fn set_legal_status(env: &Env, hospitalization_id: u64, legal_status: Symbol) -> Result<(), Error> {
+    let key = DataKey::Hospitalization(hospitalization_id);
+    let mut hosp: Hospitalization = env.storage().persistent().get(&key).ok_or(Error::NotFound)?;
+    hosp.legal_status = legal_status;
+    env.storage().persistent().set(&key, &hosp);
+    Ok(())
+}
+
+fn with_expiry(env: &Env, mut hold: LegalHold) -> LegalHold {
+    if hold.status == Symbol::new(env, "active") && env.ledger().timestamp() >= hold.expires_at {
+        hold.status = Symbol::new(env, "expired");
+    }
+    hold
+}
+
+fn load_active_hold(env: &Env, hold_id: u64) -> Result<LegalHold, Error> {
+    let hold: LegalHold = env.storage().persistent().get(&DataKey::LegalHold(hold_id)).ok_or(Error::NotFound)?;
+    let hold = with_expiry(env, hold);
+    if hold.status != Symbol::new(env, "active") {
+        return Err(Error::HoldNotActive);
+    }
+    Ok(hold)
+}
+
+fn push_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids: Vec<u64> = env.storage().persistent().get(&key).unwrap_or(vec![env]);
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+/// Roll the next due date forward on every plan for the patient that schedules this tool
+fn mark_measurement_administered(env: &Env, patient_id: &Address, tool: &Symbol, administered_at: u64) {
+    let plan_ids: Vec<u64> = env.storage().persistent().get(&DataKey::PatientPlans(patient_id.clone())).unwrap_or(vec![env]);
+    for plan_id in plan_ids.iter() {
+        let key = DataKey::MeasurementSchedule(plan_id);
+        let schedule: Vec<ScheduledMeasurement> = env.storage().persistent().get(&key).unwrap_or(vec![env]);
+        let mut updated = vec![env];
+        for mut entry in schedule.iter() {
+            if &entry.tool == tool {
+                entry.last_administered = Some(administered_at);
+                entry.next_due = administered_at + entry.interval_days as u64 * SECONDS_PER_DAY;
+            }
+            updated.push_back(entry);
+        }
+        env.storage().persistent().set(&key, &updated);
+    }
 }
 
 mod test;
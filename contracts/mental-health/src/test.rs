@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String, Symbol};
+use soroban_sdk::{testutils::{Address as _, Ledger as _}, vec, Address, BytesN, Env, String, Symbol};
 
 #[test]
 fn test_conduct_assessment_and_record_scores() {
@@ -74,11 +74,14 @@ fn test_treatment_plan_and_outcomes() {
     let plan_id = client.create_treatment_plan(
         &patient_id,
         &provider_id,
-        &diagnoses,
-        &goals,
-        &interventions,
-        &String::from_str(&env, "weekly"),
-        &1700000000,
+        &TreatmentPlanInput {
+            diagnoses,
+            treatment_goals: goals,
+            interventions,
+            frequency: String::from_str(&env, "weekly"),
+            review_date: 1700000000,
+            measurement_schedule: vec![&env],
+        },
     );
     assert_eq!(plan_id, 1);
 
@@ -106,6 +109,316 @@ fn test_treatment_plan_and_outcomes() {
     client.track_treatment_outcomes(&plan_id, &1690500000, &outcomes, &true);
 }
 
+#[test]
+fn test_measurement_based_care_worklist() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    let day = 86_400u64;
+
+    let schedule = vec![
+        &env,
+        MeasurementSchedule { tool: Symbol::new(&env, "PHQ9"), interval_days: 14 },
+        MeasurementSchedule { tool: Symbol::new(&env, "GAD7"), interval_days: 28 },
+    ];
+    let plan_id = client.create_treatment_plan(
+        &patient_id,
+        &provider_id,
+        &TreatmentPlanInput {
+            diagnoses: vec![&env, String::from_str(&env, "F41.1")],
+            treatment_goals: vec![&env],
+            interventions: vec![&env, String::from_str(&env, "CBT")],
+            frequency: String::from_str(&env, "weekly"),
+            review_date: 1_710_000_000,
+            measurement_schedule: schedule,
+        },
+    );
+
+    // Baseline administrations are due immediately
+    let worklist = client.get_measurement_worklist(&provider_id);
+    assert_eq!(worklist.len(), 2);
+    assert_eq!(worklist.get(0).unwrap().status, Symbol::new(&env, "due"));
+
+    let assessment_id = client.conduct_mental_health_assessment(
+        &patient_id,
+        &provider_id,
+        &1_700_000_000,
+        &Symbol::new(&env, "initial"),
+        &vec![&env],
+        &vec![&env],
+        &BytesN::from_array(&env, &[0; 32]),
+    );
+    client.record_phq9_score(&assessment_id, &14, &vec![&env], &1_700_000_000);
+    client.record_gad7_score(&assessment_id, &11, &vec![&env], &1_700_000_000);
+    assert!(client.get_measurement_worklist(&provider_id).is_empty());
+
+    let phq9 = client.get_measurement_schedule(&plan_id).get(0).unwrap();
+    assert_eq!(phq9.last_administered, Some(1_700_000_000));
+    assert_eq!(phq9.next_due, 1_700_000_000 + 14 * day);
+
+    // PHQ-9 comes due first and rolls overdue after the grace period
+    env.ledger().set_timestamp(1_700_000_000 + 15 * day);
+    let worklist = client.get_measurement_worklist(&provider_id);
+    assert_eq!(worklist.len(), 1);
+    assert_eq!(worklist.get(0).unwrap().tool, Symbol::new(&env, "PHQ9"));
+    assert_eq!(worklist.get(0).unwrap().status, Symbol::new(&env, "due"));
+
+    env.ledger().set_timestamp(1_700_000_000 + 29 * day);
+    let worklist = client.get_measurement_worklist(&provider_id);
+    assert_eq!(worklist.len(), 2);
+    assert_eq!(worklist.get(0).unwrap().status, Symbol::new(&env, "overdue"));
+    assert_eq!(worklist.get(1).unwrap().status, Symbol::new(&env, "due"));
+
+    let invalid = vec![&env, MeasurementSchedule { tool: Symbol::new(&env, "BDI"), interval_days: 14 }];
+    let result = client.try_create_treatment_plan(
+        &patient_id,
+        &provider_id,
+        &TreatmentPlanInput {
+            diagnoses: vec![&env],
+            treatment_goals: vec![&env],
+            interventions: vec![&env],
+            frequency: String::from_str(&env, "weekly"),
+            review_date: 1_710_000_000,
+            measurement_schedule: invalid,
+        },
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidMeasurementTool)));
+}
+
+#[test]
+fn test_on_call_roster_routes_crisis_events() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+
+    let facility_id = Address::generate(&env);
+    let day_provider = Address::generate(&env);
+    let night_provider = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let nurse = Address::generate(&env);
+
+    client.register_coverage_shift(&facility_id, &day_provider, &1_700_000_000, &1_700_043_200);
+    client.register_coverage_shift(&facility_id, &night_provider, &1_700_043_200, &1_700_086_400);
+    assert_eq!(client.get_coverage_roster(&facility_id).len(), 2);
+
+    let result = client.try_register_coverage_shift(&facility_id, &night_provider, &1_700_086_400, &1_700_086_400);
+    assert_eq!(result, Err(Ok(Error::InvalidCoverageWindow)));
+
+    assert_eq!(client.get_current_on_call(&facility_id), Some(day_provider));
+
+    // After-hours crisis is routed to the night coverage
+    env.ledger().set_timestamp(1_700_050_000);
+    let event_id = client.record_crisis_event(
+        &patient_id,
+        &facility_id,
+        &nurse,
+        &Symbol::new(&env, "high"),
+        &String::from_str(&env, "Patient expressing active suicidal ideation"),
+    );
+    let event = client.get_crisis_event(&event_id, &facility_id);
+    assert_eq!(event.reported_at, 1_700_050_000);
+    assert_eq!(event.routed_to, Some(night_provider));
+    assert_eq!(event.routed_shift_start, Some(1_700_043_200));
+    assert_eq!(event.routed_shift_end, Some(1_700_086_400));
+
+    // Gaps in coverage are recorded as unrouted
+    env.ledger().set_timestamp(1_700_090_000);
+    assert!(client.get_current_on_call(&facility_id).is_none());
+    let event_id = client.record_crisis_event(
+        &patient_id,
+        &facility_id,
+        &nurse,
+        &Symbol::new(&env, "moderate"),
+        &String::from_str(&env, "Escalating agitation"),
+    );
+    assert_eq!(client.get_crisis_event(&event_id, &facility_id).routed_to, None);
+
+    // Readable by the reporter and the patient, not by an unrelated provider
+    assert_eq!(client.get_crisis_event(&event_id, &nurse).reported_by, nurse);
+    assert_eq!(client.get_crisis_event(&event_id, &patient_id).patient_id, patient_id);
+    assert_eq!(client.try_get_crisis_event(&event_id, &Address::generate(&env)), Err(Ok(Error::NotAuthorized)));
+}
+
+#[test]
+fn test_legal_hold_lifecycle() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+
+    let facility_id = Address::generate(&env);
+    let patient_a = Address::generate(&env);
+    let patient_b = Address::generate(&env);
+    let hour = 3_600u64;
+
+    let hosp_a = client.document_hospitalization(
+        &patient_a,
+        &1_700_000_000,
+        &String::from_str(&env, "danger to self"),
+        &Symbol::new(&env, "voluntary"),
+        &facility_id,
+        &None,
+    );
+    let hosp_b = client.document_hospitalization(
+        &patient_b,
+        &1_700_000_000,
+        &String::from_str(&env, "grave disability"),
+        &Symbol::new(&env, "voluntary"),
+        &facility_id,
+        &None,
+    );
+
+    let result = client.try_place_legal_hold(&hosp_a, &Symbol::new(&env, "indefinite"), &72, &1_700_000_000);
+    assert_eq!(result, Err(Ok(Error::InvalidHoldType)));
+
+    let hold_a = client.place_legal_hold(&hosp_a, &Symbol::new(&env, "emergency"), &72, &1_700_000_000);
+    let hold_b = client.place_legal_hold(&hosp_b, &Symbol::new(&env, "emergency"), &72, &1_700_000_000);
+    let emergency = Symbol::new(&env, "emergency");
+    assert_eq!(client.get_hospitalization(&hosp_a, &patient_a).legal_status, emergency);
+    assert_eq!(client.get_legal_hold(&hold_a, &facility_id).expires_at, 1_700_000_000 + 72 * hour);
+    assert_eq!(client.get_active_holds(&facility_id).len(), 2);
+
+    // Court extends patient A's commitment
+    let hearing = client.schedule_commitment_hearing(&hold_a, &(1_700_000_000 + 48 * hour));
+    env.ledger().set_timestamp(1_700_000_000 + 48 * hour);
+    let result = client.try_record_hearing_outcome(&hold_a, &hearing, &Symbol::new(&env, "committed"), &None);
+    assert_eq!(result, Err(Ok(Error::InvalidHearingOutcome)));
+    client.record_hearing_outcome(
+        &hold_a,
+        &hearing,
+        &Symbol::new(&env, "committed"),
+        &Some(1_700_000_000 + 14 * 24 * hour),
+    );
+    let hold = client.get_legal_hold(&hold_a, &facility_id);
+    assert_eq!(hold.hold_type, Symbol::new(&env, "court_order"));
+    assert_eq!(client.get_hospitalization(&hosp_a, &facility_id).legal_status, Symbol::new(&env, "court_order"));
+    assert_eq!(hold.hearings.get(0).unwrap().outcome, Symbol::new(&env, "committed"));
+
+    // Patient B's hold lapses without a hearing
+    env.ledger().set_timestamp(1_700_000_000 + 72 * hour);
+    assert_eq!(client.get_legal_hold(&hold_b, &facility_id).status, Symbol::new(&env, "expired"));
+    let active = client.get_active_holds(&facility_id);
+    assert_eq!(active.len(), 1);
+    assert_eq!(active.get(0).unwrap().patient_id, patient_a);
+    let result = client.try_schedule_commitment_hearing(&hold_b, &(1_700_000_000 + 80 * hour));
+    assert_eq!(result, Err(Ok(Error::HoldNotActive)));
+
+    client.release_legal_hold(&hold_a);
+    assert!(client.get_active_holds(&facility_id).is_empty());
+    assert_eq!(client.get_legal_hold(&hold_a, &facility_id).status, Symbol::new(&env, "released"));
+    assert_eq!(client.get_hospitalization(&hosp_a, &patient_a).legal_status, Symbol::new(&env, "voluntary"));
+
+    // Other patients cannot read the hold or the hospitalization
+    assert_eq!(client.try_get_legal_hold(&hold_a, &patient_b), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(client.try_get_hospitalization(&hosp_a, &patient_b), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(client.get_legal_hold(&hold_a, &patient_a).patient_id, patient_a);
+}
+
+#[test]
+fn test_hearing_release_restores_voluntary_status() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+
+    let facility_id = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+
+    let hosp_id = client.document_hospitalization(
+        &patient_id,
+        &1_700_000_000,
+        &String::from_str(&env, "danger to self"),
+        &Symbol::new(&env, "voluntary"),
+        &facility_id,
+        &None,
+    );
+    let hold_id = client.place_legal_hold(&hosp_id, &Symbol::new(&env, "temporary"), &72, &1_700_000_000);
+    assert_eq!(client.get_hospitalization(&hosp_id, &facility_id).legal_status, Symbol::new(&env, "temporary"));
+
+    let hearing = client.schedule_commitment_hearing(&hold_id, &1_700_100_000);
+    client.record_hearing_outcome(&hold_id, &hearing, &Symbol::new(&env, "released"), &None);
+
+    assert_eq!(client.get_legal_hold(&hold_id, &patient_id).status, Symbol::new(&env, "released"));
+    assert_eq!(client.get_hospitalization(&hosp_id, &facility_id).legal_status, Symbol::new(&env, "voluntary"));
+}
+
+#[test]
+fn test_treatment_summary_disclosure_requires_consent() {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_700_000_000);
+
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let therapist = Address::generate(&env);
+    let pcp = Address::generate(&env);
+    let other_provider = Address::generate(&env);
+    let hash = BytesN::from_array(&env, &[3; 32]);
+    let substance_use = Symbol::new(&env, "substance_use");
+    let treatment_plan = Symbol::new(&env, "treatment_plan");
+
+    client.create_treatment_plan(
+        &patient_id,
+        &therapist,
+        &TreatmentPlanInput {
+            diagnoses: vec![&env, String::from_str(&env, "F10.20")],
+            treatment_goals: vec![&env],
+            interventions: vec![&env, String::from_str(&env, "MI")],
+            frequency: String::from_str(&env, "weekly"),
+            review_date: 1_710_000_000,
+            measurement_schedule: vec![&env],
+        },
+    );
+
+    let result = client.try_release_treatment_summary(&patient_id, &therapist, &pcp, &treatment_plan, &hash);
+    assert_eq!(result, Err(Ok(Error::RequiresExplicitConsent)));
+
+    client.grant_disclosure_consent(&patient_id, &pcp, &vec![&env, Symbol::new(&env, "all")], &Some(1_800_000_000));
+
+    // The recipient cannot release to itself; only the patient or a treating provider can
+    let result = client.try_release_treatment_summary(&patient_id, &pcp, &pcp, &treatment_plan, &hash);
+    assert_eq!(result, Err(Ok(Error::NotAuthorized)));
+    client.release_treatment_summary(&patient_id, &therapist, &pcp, &treatment_plan, &hash);
+
+    // A general consent does not reach substance-use records
+    let result = client.try_release_treatment_summary(&patient_id, &therapist, &pcp, &substance_use, &hash);
+    assert_eq!(result, Err(Ok(Error::RequiresExplicitConsent)));
+
+    client.grant_disclosure_consent(&patient_id, &pcp, &vec![&env, substance_use.clone()], &None);
+    client.release_treatment_summary(&patient_id, &patient_id, &pcp, &substance_use, &hash);
+
+    // Consent is recipient-specific
+    let result = client.try_release_treatment_summary(&patient_id, &therapist, &other_provider, &substance_use, &hash);
+    assert_eq!(result, Err(Ok(Error::RequiresExplicitConsent)));
+
+    client.revoke_disclosure_consent(&patient_id, &pcp);
+    let result = client.try_release_treatment_summary(&patient_id, &therapist, &pcp, &substance_use, &hash);
+    assert_eq!(result, Err(Ok(Error::RequiresExplicitConsent)));
+
+    let log = client.get_disclosure_log(&patient_id);
+    assert_eq!(log.len(), 2);
+    assert!(!log.get(0).unwrap().redisclosure_prohibited);
+    assert!(log.get(1).unwrap().redisclosure_prohibited);
+    assert_eq!(log.get(0).unwrap().released_by, therapist);
+    assert_eq!(log.get(1).unwrap().released_by, patient_id);
+    assert_eq!(log.get(1).unwrap().recipient_provider, pcp);
+}
+
 #[test]
 fn test_privacy_and_screening() {
     let env = Env::default();
@@ -237,3 +550,27 @@ fn test_assess_suicide_risk_unauth() {
         &hash,
     );
 }
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(MentalHealthContract, ());
+    let client = MentalHealthContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::NotAuthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
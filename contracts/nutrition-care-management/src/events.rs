@@ -0,0 +1,72 @@
+use soroban_sdk::{contractevent, Address, Symbol};
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FoodRestrictionRecorded {
+    #[topic]
+    pub assessment_id: u64,
+    pub patient_id: Address,
+    pub kind: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DietConflictsFlagged {
+    #[topic]
+    pub order_id: u64,
+    pub ordering_provider: Address,
+    pub conflict_count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DietConflictsAcknowledged {
+    #[topic]
+    pub order_id: u64,
+    pub provider_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountOrdered {
+    #[topic]
+    pub study_id: u64,
+    pub patient_id: Address,
+    pub ordering_provider: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountMealRecorded {
+    #[topic]
+    pub study_id: u64,
+    pub meal_time: u64,
+    pub calories: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountCompleted {
+    #[topic]
+    pub study_id: u64,
+    pub calorie_needs_met_pct: u32,
+    pub protein_needs_met_pct: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountSigned {
+    #[topic]
+    pub study_id: u64,
+    pub dietitian_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FollowUpDue {
+    #[topic]
+    pub care_plan_id: u64,
+    pub patient_id: Option<Address>,
+    pub dietitian_id: Address,
+    pub due_at: u64,
+}
@@ -1,11 +1,13 @@
 #![no_std]
 
+mod events;
 mod storage;
 mod types;
 
 #[cfg(test)]
 mod test;
 
+use events::*;
 use soroban_sdk::{
     contract, contractimpl, symbol_short, Address, BytesN, Env, String, Symbol, Vec,
 };
@@ -17,6 +19,21 @@ pub struct NutritionCareContract;
 
 #[contractimpl]
 impl NutritionCareContract {
+    // ------------------------------------------------------------------
+    // Initialization
+    // ------------------------------------------------------------------
+
+    /// Set the admin allowed to upgrade the contract and migrate its data.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if has_admin(&env) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        save_admin(&env, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     // ------------------------------------------------------------------
     // 1. conduct_nutrition_assessment
     // ------------------------------------------------------------------
@@ -58,6 +75,7 @@ impl NutritionCareContract {
 
         save_assessment(&env, &assessment);
         add_patient_assessment(&env, &patient_id, assessment_id);
+        add_dietitian_assessment(&env, &dietitian_id, assessment_id);
 
         env.events().publish(
             (Symbol::new(&env, "assessment_created"),),
@@ -186,6 +204,7 @@ impl NutritionCareContract {
         };
 
         save_care_plan(&env, &plan);
+        add_dietitian_care_plan(&env, &dietitian_id, care_plan_id);
 
         env.events().publish(
             (Symbol::new(&env, "care_plan_created"),),
@@ -230,6 +249,33 @@ impl NutritionCareContract {
         save_diet_order(&env, &order);
         add_patient_diet_order(&env, &patient_id, order_id);
 
+        // Preferences are informational; allergies and intolerances must be
+        // acknowledged by the ordering provider.
+        let mut restrictions = Vec::new(&env);
+        for r in load_food_restrictions(&env, &patient_id).iter() {
+            if r.kind != symbol_short!("pref") {
+                restrictions.push_back(r);
+            }
+        }
+        if !restrictions.is_empty() {
+            let count = restrictions.len();
+            save_diet_order_conflicts(
+                &env,
+                &DietOrderConflicts {
+                    order_id,
+                    restrictions,
+                    acknowledged_by: None,
+                    acknowledged_at: None,
+                },
+            );
+            DietConflictsFlagged {
+                order_id,
+                ordering_provider: ordering_provider.clone(),
+                conflict_count: count,
+            }
+            .publish(&env);
+        }
+
         env.events().publish(
             (Symbol::new(&env, "diet_order_placed"),),
             (order_id, patient_id, ordering_provider),
@@ -472,6 +518,363 @@ impl NutritionCareContract {
         Ok(())
     }
 
+    // ------------------------------------------------------------------
+    // 11. record_food_restriction
+    // ------------------------------------------------------------------
+
+    /// Record a food allergy, intolerance or preference against an
+    /// assessment, by the dietitian who conducted it.
+    ///
+    /// Valid kinds: `allergy`, `intoler`, `pref`.
+    /// Valid sources: `patient` (patient-reported) and `verified` (confirmed
+    /// in the allergy contract; requires `allergy_record_id`).
+    pub fn record_food_restriction(
+        env: Env,
+        assessment_id: u64,
+        dietitian_id: Address,
+        input: FoodRestrictionInput,
+    ) -> Result<(), Error> {
+        dietitian_id.require_auth();
+
+        let FoodRestrictionInput {
+            allergen,
+            kind,
+            reaction,
+            source,
+            allergy_record_id,
+        } = input;
+
+        let assessment = load_assessment(&env, assessment_id).ok_or(Error::AssessmentNotFound)?;
+        if assessment.dietitian_id != dietitian_id {
+            return Err(Error::Unauthorized);
+        }
+
+        let valid_kinds = [
+            symbol_short!("allergy"),
+            symbol_short!("intoler"),
+            symbol_short!("pref"),
+        ];
+        if !valid_kinds.contains(&kind) {
+            return Err(Error::InvalidRestrictionKind);
+        }
+
+        let verified = source == symbol_short!("verified");
+        if !verified && source != symbol_short!("patient") {
+            return Err(Error::InvalidRestrictionSource);
+        }
+        if verified != allergy_record_id.is_some() {
+            return Err(Error::InvalidRestrictionSource);
+        }
+
+        let restriction = FoodRestriction {
+            assessment_id,
+            patient_id: assessment.patient_id.clone(),
+            allergen,
+            kind: kind.clone(),
+            reaction,
+            source,
+            allergy_record_id,
+            recorded_by: dietitian_id,
+            recorded_at: env.ledger().timestamp(),
+        };
+
+        append_food_restriction(&env, &assessment.patient_id, &restriction);
+
+        FoodRestrictionRecorded {
+            assessment_id,
+            patient_id: assessment.patient_id,
+            kind,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // 12. acknowledge_diet_conflicts
+    // ------------------------------------------------------------------
+
+    /// Acknowledge the allergies and intolerances flagged on a diet order.
+    /// Only the ordering provider may acknowledge.
+    pub fn acknowledge_diet_conflicts(
+        env: Env,
+        order_id: u64,
+        provider_id: Address,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        let order = load_diet_order(&env, order_id).ok_or(Error::DietOrderNotFound)?;
+        if order.ordering_provider != provider_id {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut conflicts =
+            load_diet_order_conflicts(&env, order_id).ok_or(Error::NoDietConflicts)?;
+        if conflicts.acknowledged_by.is_some() {
+            return Err(Error::DietConflictsAlreadyAcknowledged);
+        }
+
+        conflicts.acknowledged_by = Some(provider_id.clone());
+        conflicts.acknowledged_at = Some(env.ledger().timestamp());
+        save_diet_order_conflicts(&env, &conflicts);
+
+        DietConflictsAcknowledged {
+            order_id,
+            provider_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // 13. order_calorie_count
+    // ------------------------------------------------------------------
+
+    /// Order a 72-hour calorie count starting at `window_start`. Intake is
+    /// later compared against the needs computed for `assessment_id`.
+    pub fn order_calorie_count(
+        env: Env,
+        patient_id: Address,
+        assessment_id: u64,
+        ordering_provider: Address,
+        window_start: u64,
+    ) -> Result<u64, Error> {
+        ordering_provider.require_auth();
+
+        let assessment = load_assessment(&env, assessment_id).ok_or(Error::AssessmentNotFound)?;
+        if assessment.patient_id != patient_id {
+            return Err(Error::AssessmentNotFound);
+        }
+
+        let study_id = next_calorie_count_id(&env);
+        let order = CalorieCountOrder {
+            study_id,
+            patient_id: patient_id.clone(),
+            assessment_id,
+            ordering_provider: ordering_provider.clone(),
+            window_start,
+            window_end: window_start + CALORIE_COUNT_WINDOW_SECS,
+            status: symbol_short!("ordered"),
+            ordered_at: env.ledger().timestamp(),
+        };
+
+        save_calorie_count(&env, &order);
+
+        CalorieCountOrdered {
+            study_id,
+            patient_id,
+            ordering_provider,
+        }
+        .publish(&env);
+
+        Ok(study_id)
+    }
+
+    // ------------------------------------------------------------------
+    // 14. record_calorie_count_meal
+    // ------------------------------------------------------------------
+
+    /// Record a meal's intake during a calorie count. The meal must fall
+    /// within the 72-hour window. Returns the calories consumed.
+    pub fn record_calorie_count_meal(
+        env: Env,
+        study_id: u64,
+        nurse_id: Address,
+        meal_time: u64,
+        meal_type: Symbol,
+        foods_consumed: Vec<FoodItem>,
+        percentage_consumed: u32,
+    ) -> Result<u32, Error> {
+        nurse_id.require_auth();
+
+        let order = load_calorie_count(&env, study_id).ok_or(Error::CalorieCountNotFound)?;
+        if order.status != symbol_short!("ordered") {
+            return Err(Error::InvalidCalorieCountStatus);
+        }
+        if meal_time < order.window_start || meal_time >= order.window_end {
+            return Err(Error::MealOutsideCountWindow);
+        }
+
+        let pct = percentage_consumed.min(100);
+        let mut calories = 0u32;
+        let mut protein_grams = 0u32;
+        for food in foods_consumed.iter() {
+            calories += food.calories;
+            protein_grams += food.protein_grams;
+        }
+        let calories = calories * pct / 100;
+        let protein_grams = protein_grams * pct / 100;
+
+        let meal = CalorieCountMeal {
+            recorded_by: nurse_id,
+            meal_time,
+            meal_type: meal_type.clone(),
+            foods_consumed: foods_consumed.clone(),
+            percentage_consumed: pct,
+            calories,
+            protein_grams,
+        };
+        append_calorie_count_meal(&env, study_id, &meal);
+
+        // The meal also belongs in the patient's regular intake history.
+        let record = FoodIntakeRecord {
+            patient_id: order.patient_id.clone(),
+            meal_date: meal_time,
+            meal_type,
+            foods_consumed,
+            percentage_consumed: pct,
+            recorded_at: env.ledger().timestamp(),
+        };
+        append_food_intake(&env, &order.patient_id, &record);
+
+        CalorieCountMealRecorded {
+            study_id,
+            meal_time,
+            calories,
+        }
+        .publish(&env);
+
+        Ok(calories)
+    }
+
+    // ------------------------------------------------------------------
+    // 15. complete_calorie_count
+    // ------------------------------------------------------------------
+
+    /// Close a calorie count once its window has ended and aggregate the
+    /// recorded intake against the assessment's computed needs.
+    pub fn complete_calorie_count(env: Env, study_id: u64) -> Result<CalorieCountSummary, Error> {
+        let mut order = load_calorie_count(&env, study_id).ok_or(Error::CalorieCountNotFound)?;
+        if order.status != symbol_short!("ordered") {
+            return Err(Error::InvalidCalorieCountStatus);
+        }
+        let now = env.ledger().timestamp();
+        if now < order.window_end {
+            return Err(Error::CalorieCountWindowOpen);
+        }
+
+        let needs = load_computed_needs(&env, order.assessment_id)
+            .ok_or(Error::AssessmentNotFound)?
+            .needs;
+
+        let meals = load_calorie_count_meals(&env, study_id);
+        let mut total_calories = 0u32;
+        let mut total_protein_grams = 0u32;
+        for meal in meals.iter() {
+            total_calories += meal.calories;
+            total_protein_grams += meal.protein_grams;
+        }
+        let avg_daily_calories = total_calories / CALORIE_COUNT_DAYS;
+        let avg_daily_protein_grams = total_protein_grams / CALORIE_COUNT_DAYS;
+
+        let summary = CalorieCountSummary {
+            study_id,
+            meals_recorded: meals.len(),
+            total_calories,
+            total_protein_grams,
+            avg_daily_calories,
+            avg_daily_protein_grams,
+            calories_needed_per_day: needs.calories_per_day,
+            protein_needed_grams: needs.protein_grams,
+            calorie_needs_met_pct: percent_of(avg_daily_calories, needs.calories_per_day),
+            protein_needs_met_pct: percent_of(avg_daily_protein_grams, needs.protein_grams),
+            completed_at: now,
+            signed_off_by: None,
+            signed_off_at: None,
+        };
+
+        save_calorie_count_summary(&env, &summary);
+        order.status = symbol_short!("completed");
+        save_calorie_count(&env, &order);
+
+        CalorieCountCompleted {
+            study_id,
+            calorie_needs_met_pct: summary.calorie_needs_met_pct,
+            protein_needs_met_pct: summary.protein_needs_met_pct,
+        }
+        .publish(&env);
+
+        Ok(summary)
+    }
+
+    // ------------------------------------------------------------------
+    // 16. sign_off_calorie_count
+    // ------------------------------------------------------------------
+
+    /// Sign off a completed calorie-count summary. Only the dietitian who
+    /// conducted the linked assessment may sign.
+    pub fn sign_off_calorie_count(
+        env: Env,
+        study_id: u64,
+        dietitian_id: Address,
+    ) -> Result<(), Error> {
+        dietitian_id.require_auth();
+
+        let mut order = load_calorie_count(&env, study_id).ok_or(Error::CalorieCountNotFound)?;
+        if order.status != symbol_short!("completed") {
+            return Err(Error::InvalidCalorieCountStatus);
+        }
+        let assessment =
+            load_assessment(&env, order.assessment_id).ok_or(Error::AssessmentNotFound)?;
+        if assessment.dietitian_id != dietitian_id {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut summary =
+            load_calorie_count_summary(&env, study_id).ok_or(Error::CalorieCountNotFound)?;
+        summary.signed_off_by = Some(dietitian_id.clone());
+        summary.signed_off_at = Some(env.ledger().timestamp());
+        save_calorie_count_summary(&env, &summary);
+
+        order.status = symbol_short!("signed");
+        save_calorie_count(&env, &order);
+
+        CalorieCountSigned {
+            study_id,
+            dietitian_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    // ------------------------------------------------------------------
+    // 17. flag_due_follow_ups
+    // ------------------------------------------------------------------
+
+    /// Emit a `FollowUpDue` event for each of a dietitian's active care
+    /// plans whose next follow-up has passed, and return those plan ids.
+    pub fn flag_due_follow_ups(env: Env, dietitian_id: Address) -> Vec<u64> {
+        dietitian_id.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut due = Vec::new(&env);
+        for care_plan_id in load_dietitian_care_plans(&env, &dietitian_id).iter() {
+            let Some(plan) = load_care_plan(&env, care_plan_id) else {
+                continue;
+            };
+            let Some(due_at) = follow_up_due_at(&env, &plan) else {
+                continue;
+            };
+            if due_at >= now {
+                continue;
+            }
+            let patient_id = load_assessment(&env, plan.assessment_id).map(|a| a.patient_id);
+
+            FollowUpDue {
+                care_plan_id,
+                patient_id,
+                dietitian_id: dietitian_id.clone(),
+                due_at,
+            }
+            .publish(&env);
+            due.push_back(care_plan_id);
+        }
+
+        due
+    }
+
     // ------------------------------------------------------------------
     // Query helpers
     // ------------------------------------------------------------------
@@ -502,13 +905,25 @@ impl NutritionCareContract {
     }
 
     /// Retrieve the food-intake history for a patient.
-    pub fn get_food_intake(env: Env, patient_id: Address) -> Vec<FoodIntakeRecord> {
-        load_food_intake(&env, &patient_id)
+    pub fn get_food_intake(
+        env: Env,
+        patient_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<FoodIntakeRecord> {
+        let intake = load_food_intake(&env, &patient_id);
+        shared_pagination::paginate(&intake, offset, limit)
     }
 
     /// Retrieve the weight-trend history for a patient.
-    pub fn get_weight_history(env: Env, patient_id: Address) -> Vec<WeightEntry> {
-        load_weight_history(&env, &patient_id)
+    pub fn get_weight_history(
+        env: Env,
+        patient_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<WeightEntry> {
+        let history = load_weight_history(&env, &patient_id);
+        shared_pagination::paginate(&history, offset, limit)
     }
 
     /// Retrieve a malnutrition screening result.
@@ -524,8 +939,185 @@ impl NutritionCareContract {
         load_supplements(&env, care_plan_id)
     }
 
+    /// Retrieve the food allergies, intolerances and preferences on file for
+    /// a patient.
+    pub fn get_food_restrictions(env: Env, patient_id: Address) -> Vec<FoodRestriction> {
+        load_food_restrictions(&env, &patient_id)
+    }
+
+    /// Retrieve the conflicts flagged on a diet order.
+    pub fn get_diet_order_conflicts(env: Env, order_id: u64) -> Result<DietOrderConflicts, Error> {
+        load_diet_order_conflicts(&env, order_id).ok_or(Error::NoDietConflicts)
+    }
+
+    /// Retrieve a calorie-count order.
+    pub fn get_calorie_count(env: Env, study_id: u64) -> Result<CalorieCountOrder, Error> {
+        load_calorie_count(&env, study_id).ok_or(Error::CalorieCountNotFound)
+    }
+
+    /// Retrieve the meals recorded during a calorie count.
+    pub fn get_calorie_count_meals(env: Env, study_id: u64) -> Vec<CalorieCountMeal> {
+        load_calorie_count_meals(&env, study_id)
+    }
+
+    /// Retrieve the completion summary of a calorie count.
+    pub fn get_calorie_count_summary(
+        env: Env,
+        study_id: u64,
+    ) -> Result<CalorieCountSummary, Error> {
+        load_calorie_count_summary(&env, study_id).ok_or(Error::CalorieCountNotFound)
+    }
+
+    /// Retrieve the assessments conducted by a dietitian.
+    pub fn get_dietitian_assessments(
+        env: Env,
+        dietitian_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<NutritionAssessment> {
+        let ids = load_dietitian_assessments(&env, &dietitian_id);
+        shared_pagination::paginate_map(&env, &ids, offset, limit, |id| load_assessment(&env, id))
+    }
+
+    /// Retrieve the care plans authored by a dietitian.
+    pub fn get_dietitian_care_plans(
+        env: Env,
+        dietitian_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<NutritionCarePlan> {
+        let ids = load_dietitian_care_plans(&env, &dietitian_id);
+        shared_pagination::paginate_map(&env, &ids, offset, limit, |id| load_care_plan(&env, id))
+    }
+
+    /// Summarise a dietitian's caseload: assessment and care plan counts,
+    /// active plans, and the active plans with an overdue follow-up.
+    pub fn get_dietitian_caseload(env: Env, dietitian_id: Address) -> DietitianCaseload {
+        let now = env.ledger().timestamp();
+        let plan_ids = load_dietitian_care_plans(&env, &dietitian_id);
+
+        let mut active_care_plans = 0u32;
+        let mut overdue_follow_up_plan_ids = Vec::new(&env);
+        for care_plan_id in plan_ids.iter() {
+            let Some(plan) = load_care_plan(&env, care_plan_id) else {
+                continue;
+            };
+            if !is_plan_active(&env, care_plan_id) {
+                continue;
+            }
+            active_care_plans += 1;
+            if let Some(due_at) = follow_up_due_at(&env, &plan) {
+                if due_at < now {
+                    overdue_follow_up_plan_ids.push_back(care_plan_id);
+                }
+            }
+        }
+
+        DietitianCaseload {
+            dietitian_id: dietitian_id.clone(),
+            total_assessments: load_dietitian_assessments(&env, &dietitian_id).len(),
+            total_care_plans: plan_ids.len(),
+            active_care_plans,
+            overdue_follow_up_plan_ids,
+        }
+    }
+
     /// Retrieve the latest outcome evaluation for a care plan.
     pub fn get_outcome_evaluation(env: Env, care_plan_id: u64) -> Result<OutcomeEvaluation, Error> {
         load_outcome_evaluation(&env, care_plan_id).ok_or(Error::CarePlanNotFound)
     }
+
+    // ------------------------------------------------------------------
+    // Upgrades
+    // ------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored = load_admin(env).ok_or(Error::NotInitialized)?;
+    if stored != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+// ----------------------------------------------------------------------
+// Calorie counts
+// ----------------------------------------------------------------------
+
+const CALORIE_COUNT_DAYS: u32 = 3;
+const CALORIE_COUNT_WINDOW_SECS: u64 = CALORIE_COUNT_DAYS as u64 * SECONDS_PER_DAY;
+
+/// `part` as a whole-number percentage of `whole` (0 when `whole` is 0).
+fn percent_of(part: u32, whole: u32) -> u32 {
+    if whole == 0 {
+        return 0;
+    }
+    ((part as u64 * 100) / whole as u64) as u32
+}
+
+// ----------------------------------------------------------------------
+// Follow-up scheduling
+// ----------------------------------------------------------------------
+
+/// Interval implied by a care plan's `follow_up_frequency`, or `None` for a
+/// frequency that does not map to a fixed schedule.
+fn follow_up_interval(env: &Env, frequency: &String) -> Option<u64> {
+    let days = if *frequency == String::from_str(env, "daily") {
+        1
+    } else if *frequency == String::from_str(env, "weekly") {
+        7
+    } else if *frequency == String::from_str(env, "biweekly") {
+        14
+    } else if *frequency == String::from_str(env, "monthly") {
+        30
+    } else if *frequency == String::from_str(env, "quarterly") {
+        90
+    } else {
+        return None;
+    };
+    Some(days * SECONDS_PER_DAY)
+}
+
+/// A plan stays active until an outcome evaluation discontinues care.
+fn is_plan_active(env: &Env, care_plan_id: u64) -> bool {
+    load_outcome_evaluation(env, care_plan_id)
+        .map(|ev| ev.continue_care)
+        .unwrap_or(true)
+}
+
+/// When an active plan's next follow-up is due: one interval after the
+/// latest documented intervention, or after plan creation if there is none.
+fn follow_up_due_at(env: &Env, plan: &NutritionCarePlan) -> Option<u64> {
+    if !is_plan_active(env, plan.care_plan_id) {
+        return None;
+    }
+    let interval = follow_up_interval(env, &plan.follow_up_frequency)?;
+    let last_contact = load_interventions(env, plan.care_plan_id)
+        .iter()
+        .map(|i| i.intervention_date)
+        .fold(plan.created_at, u64::max);
+    Some(last_contact.saturating_add(interval))
 }
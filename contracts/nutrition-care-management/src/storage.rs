@@ -3,11 +3,28 @@
 use soroban_sdk::{Address, Env, Vec};
 
 use crate::types::{
-    ComputedNeeds, DataKey, DietOrder, FoodIntakeRecord, MalnutritionScreening,
+    CalorieCountMeal, CalorieCountOrder, CalorieCountSummary, ComputedNeeds, DataKey, DietOrder,
+    DietOrderConflicts, FoodIntakeRecord, FoodRestriction, MalnutritionScreening,
     NutritionAssessment, NutritionCarePlan, NutritionIntervention, OutcomeEvaluation,
     SupplementRecommendation, WeightEntry,
 };
 
+// -----------------------------------------------------------------------
+// Admin
+// -----------------------------------------------------------------------
+
+pub fn has_admin(env: &Env) -> bool {
+    env.storage().instance().has(&DataKey::Admin)
+}
+
+pub fn save_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn load_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
 // -----------------------------------------------------------------------
 // Counter helpers
 // -----------------------------------------------------------------------
@@ -51,6 +68,19 @@ pub fn next_diet_order_id(env: &Env) -> u64 {
     next
 }
 
+pub fn next_calorie_count_id(env: &Env) -> u64 {
+    let id: u64 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::CalorieCountCounter)
+        .unwrap_or(0);
+    let next = id + 1;
+    env.storage()
+        .persistent()
+        .set(&DataKey::CalorieCountCounter, &next);
+    next
+}
+
 // -----------------------------------------------------------------------
 // NutritionAssessment
 // -----------------------------------------------------------------------
@@ -77,6 +107,21 @@ pub fn add_patient_assessment(env: &Env, patient_id: &Address, assessment_id: u6
         .set(&DataKey::PatientAssessments(patient_id.clone()), &ids);
 }
 
+pub fn add_dietitian_assessment(env: &Env, dietitian_id: &Address, assessment_id: u64) {
+    let mut ids: Vec<u64> = load_dietitian_assessments(env, dietitian_id);
+    ids.push_back(assessment_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::DietitianAssessments(dietitian_id.clone()), &ids);
+}
+
+pub fn load_dietitian_assessments(env: &Env, dietitian_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DietitianAssessments(dietitian_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
 // -----------------------------------------------------------------------
 // ComputedNeeds
 // -----------------------------------------------------------------------
@@ -107,6 +152,21 @@ pub fn load_care_plan(env: &Env, id: u64) -> Option<NutritionCarePlan> {
     env.storage().persistent().get(&DataKey::CarePlan(id))
 }
 
+pub fn add_dietitian_care_plan(env: &Env, dietitian_id: &Address, care_plan_id: u64) {
+    let mut ids: Vec<u64> = load_dietitian_care_plans(env, dietitian_id);
+    ids.push_back(care_plan_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::DietitianCarePlans(dietitian_id.clone()), &ids);
+}
+
+pub fn load_dietitian_care_plans(env: &Env, dietitian_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DietitianCarePlans(dietitian_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
 // -----------------------------------------------------------------------
 // DietOrder
 // -----------------------------------------------------------------------
@@ -133,6 +193,37 @@ pub fn add_patient_diet_order(env: &Env, patient_id: &Address, order_id: u64) {
         .set(&DataKey::PatientDietOrders(patient_id.clone()), &ids);
 }
 
+// -----------------------------------------------------------------------
+// FoodRestrictions / DietOrderConflicts
+// -----------------------------------------------------------------------
+
+pub fn append_food_restriction(env: &Env, patient_id: &Address, restriction: &FoodRestriction) {
+    let mut list = load_food_restrictions(env, patient_id);
+    list.push_back(restriction.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::FoodRestrictions(patient_id.clone()), &list);
+}
+
+pub fn load_food_restrictions(env: &Env, patient_id: &Address) -> Vec<FoodRestriction> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::FoodRestrictions(patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_diet_order_conflicts(env: &Env, conflicts: &DietOrderConflicts) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::DietOrderConflicts(conflicts.order_id), conflicts);
+}
+
+pub fn load_diet_order_conflicts(env: &Env, order_id: u64) -> Option<DietOrderConflicts> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::DietOrderConflicts(order_id))
+}
+
 // -----------------------------------------------------------------------
 // NutritionIntervention (list per care plan)
 // -----------------------------------------------------------------------
@@ -256,3 +347,46 @@ pub fn load_outcome_evaluation(env: &Env, care_plan_id: u64) -> Option<OutcomeEv
         .persistent()
         .get(&DataKey::OutcomeEvaluation(care_plan_id))
 }
+
+// -----------------------------------------------------------------------
+// CalorieCount
+// -----------------------------------------------------------------------
+
+pub fn save_calorie_count(env: &Env, order: &CalorieCountOrder) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CalorieCount(order.study_id), order);
+}
+
+pub fn load_calorie_count(env: &Env, study_id: u64) -> Option<CalorieCountOrder> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CalorieCount(study_id))
+}
+
+pub fn append_calorie_count_meal(env: &Env, study_id: u64, meal: &CalorieCountMeal) {
+    let mut list = load_calorie_count_meals(env, study_id);
+    list.push_back(meal.clone());
+    env.storage()
+        .persistent()
+        .set(&DataKey::CalorieCountMeals(study_id), &list);
+}
+
+pub fn load_calorie_count_meals(env: &Env, study_id: u64) -> Vec<CalorieCountMeal> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CalorieCountMeals(study_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_calorie_count_summary(env: &Env, summary: &CalorieCountSummary) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::CalorieCountSummary(summary.study_id), summary);
+}
+
+pub fn load_calorie_count_summary(env: &Env, study_id: u64) -> Option<CalorieCountSummary> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::CalorieCountSummary(study_id))
+}
@@ -2,7 +2,9 @@
 
 use super::*;
 use soroban_sdk::{
-    symbol_short, testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec,
+    symbol_short,
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, String, Symbol, Vec,
 };
 
 // -----------------------------------------------------------------------
@@ -509,7 +511,7 @@ fn test_track_food_intake_success() {
         &75u32,
     );
 
-    let records = client.get_food_intake(&patient);
+    let records = client.get_food_intake(&patient, &0, &10);
     assert_eq!(records.len(), 1);
     let r = records.get(0).unwrap();
     assert_eq!(r.percentage_consumed, 75);
@@ -530,7 +532,7 @@ fn test_track_food_intake_multiple_meals() {
         client.track_food_intake(&patient, &1_200_000u64, meal, &foods, &100u32);
     }
 
-    let records = client.get_food_intake(&patient);
+    let records = client.get_food_intake(&patient, &0, &10);
     assert_eq!(records.len(), 3);
 }
 
@@ -548,7 +550,7 @@ fn test_track_food_intake_empty_foods_list() {
         &0u32,
     );
 
-    let records = client.get_food_intake(&patient);
+    let records = client.get_food_intake(&patient, &0, &10);
     assert_eq!(records.len(), 1);
 }
 
@@ -568,7 +570,7 @@ fn test_monitor_weight_trend_success() {
         &symbol_short!("measured"),
     );
 
-    let history = client.get_weight_history(&patient);
+    let history = client.get_weight_history(&patient, &0, &10);
     assert_eq!(history.len(), 1);
     let entry = history.get(0).unwrap();
     assert_eq!(entry.weight_kg_x100, 7050);
@@ -585,7 +587,7 @@ fn test_monitor_weight_trend_multiple_entries() {
         client.monitor_weight_trend(&patient, date, w, &symbol_short!("measured"));
     }
 
-    let history = client.get_weight_history(&patient);
+    let history = client.get_weight_history(&patient, &0, &10);
     assert_eq!(history.len(), 3);
     assert_eq!(history.get(0).unwrap().weight_kg_x100, 7200);
     assert_eq!(history.get(2).unwrap().weight_kg_x100, 7100);
@@ -603,7 +605,7 @@ fn test_monitor_weight_patient_reported() {
         &symbol_short!("pt_rprtd"),
     );
 
-    let history = client.get_weight_history(&patient);
+    let history = client.get_weight_history(&patient, &0, &10);
     let entry = history.get(0).unwrap();
     assert_eq!(entry.method, symbol_short!("pt_rprtd"));
 }
@@ -837,6 +839,284 @@ fn test_evaluate_outcomes_care_plan_not_found() {
     assert!(result.is_err());
 }
 
+// -----------------------------------------------------------------------
+// Food restrictions
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_food_restrictions_flag_diet_order_conflicts() {
+    let (env, patient, dietitian, provider) = setup();
+    let client = register(&env);
+    let assessment_id = create_assessment(&env, &client, &patient, &dietitian);
+
+    client.record_food_restriction(
+        &assessment_id,
+        &dietitian,
+        &FoodRestrictionInput {
+            allergen: String::from_str(&env, "peanut"),
+            kind: symbol_short!("allergy"),
+            reaction: Some(String::from_str(&env, "anaphylaxis")),
+            source: symbol_short!("verified"),
+            allergy_record_id: Some(7u64),
+        },
+    );
+    client.record_food_restriction(
+        &assessment_id,
+        &dietitian,
+        &FoodRestrictionInput {
+            allergen: String::from_str(&env, "pork"),
+            kind: symbol_short!("pref"),
+            reaction: None,
+            source: symbol_short!("patient"),
+            allergy_record_id: None,
+        },
+    );
+    assert_eq!(client.get_food_restrictions(&patient).len(), 2);
+
+    let order_id = client.order_therapeutic_diet(
+        &patient,
+        &provider,
+        &symbol_short!("regular"),
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    let conflicts = client.get_diet_order_conflicts(&order_id);
+    assert_eq!(conflicts.restrictions.len(), 1);
+    assert_eq!(
+        conflicts.restrictions.get(0).unwrap().allergen,
+        String::from_str(&env, "peanut")
+    );
+    assert_eq!(conflicts.acknowledged_by, None);
+
+    let result = client.try_acknowledge_diet_conflicts(&order_id, &dietitian);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    client.acknowledge_diet_conflicts(&order_id, &provider);
+    assert_eq!(
+        client.get_diet_order_conflicts(&order_id).acknowledged_by,
+        Some(provider.clone())
+    );
+    let result = client.try_acknowledge_diet_conflicts(&order_id, &provider);
+    assert_eq!(result, Err(Ok(Error::DietConflictsAlreadyAcknowledged)));
+}
+
+#[test]
+fn test_food_restriction_validation() {
+    let (env, patient, dietitian, provider) = setup();
+    let client = register(&env);
+    let assessment_id = create_assessment(&env, &client, &patient, &dietitian);
+    let allergen = String::from_str(&env, "lactose");
+
+    let result = client.try_record_food_restriction(
+        &assessment_id,
+        &dietitian,
+        &FoodRestrictionInput {
+            allergen: allergen.clone(),
+            kind: symbol_short!("dislike"),
+            reaction: None,
+            source: symbol_short!("patient"),
+            allergy_record_id: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidRestrictionKind)));
+
+    let result = client.try_record_food_restriction(
+        &assessment_id,
+        &dietitian,
+        &FoodRestrictionInput {
+            allergen: allergen.clone(),
+            kind: symbol_short!("intoler"),
+            reaction: None,
+            source: symbol_short!("verified"),
+            allergy_record_id: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidRestrictionSource)));
+
+    let result = client.try_record_food_restriction(
+        &assessment_id,
+        &provider,
+        &FoodRestrictionInput {
+            allergen: allergen.clone(),
+            kind: symbol_short!("intoler"),
+            reaction: None,
+            source: symbol_short!("patient"),
+            allergy_record_id: None,
+        },
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let order_id = client.order_therapeutic_diet(
+        &patient,
+        &provider,
+        &symbol_short!("regular"),
+        &None,
+        &None,
+        &None,
+        &None,
+    );
+    let result = client.try_get_diet_order_conflicts(&order_id);
+    assert_eq!(result, Err(Ok(Error::NoDietConflicts)));
+}
+
+// -----------------------------------------------------------------------
+// Calorie counts
+// -----------------------------------------------------------------------
+
+fn food(env: &Env, name: &str, calories: u32, protein_grams: u32) -> Vec<FoodItem> {
+    let mut foods = Vec::new(env);
+    foods.push_back(FoodItem {
+        food_name: String::from_str(env, name),
+        portion_size: String::from_str(env, "1 serving"),
+        calories,
+        protein_grams,
+    });
+    foods
+}
+
+#[test]
+fn test_calorie_count_study_workflow() {
+    let (env, patient, dietitian, provider) = setup();
+    let client = register(&env);
+    let nurse = Address::generate(&env);
+    let assessment_id = create_assessment(&env, &client, &patient, &dietitian);
+    let needs = client.calculate_nutritional_needs(
+        &assessment_id,
+        &symbol_short!("moderate"),
+        &100i64,
+        &Vec::new(&env),
+    );
+
+    env.ledger().set_timestamp(10_000);
+    let study_id = client.order_calorie_count(&patient, &assessment_id, &provider, &10_000u64);
+    let order = client.get_calorie_count(&study_id);
+    assert_eq!(order.window_end, 10_000 + 3 * 86_400);
+    assert_eq!(order.status, symbol_short!("ordered"));
+
+    // Half of a 1,200 kcal / 40 g meal on each of the three days.
+    for day in 0..3u64 {
+        let eaten = client.record_calorie_count_meal(
+            &study_id,
+            &nurse,
+            &(10_000 + day * 86_400 + 3_600),
+            &symbol_short!("lunch"),
+            &food(&env, "stew", 1_200, 40),
+            &50u32,
+        );
+        assert_eq!(eaten, 600);
+    }
+    let result = client.try_record_calorie_count_meal(
+        &study_id,
+        &nurse,
+        &(10_000 + 3 * 86_400),
+        &symbol_short!("dinner"),
+        &food(&env, "soup", 300, 10),
+        &100u32,
+    );
+    assert_eq!(result, Err(Ok(Error::MealOutsideCountWindow)));
+    assert_eq!(client.get_food_intake(&patient, &0, &10).len(), 3);
+
+    let result = client.try_complete_calorie_count(&study_id);
+    assert_eq!(result, Err(Ok(Error::CalorieCountWindowOpen)));
+
+    env.ledger().set_timestamp(10_000 + 3 * 86_400);
+    let summary = client.complete_calorie_count(&study_id);
+    assert_eq!(summary.meals_recorded, 3);
+    assert_eq!(summary.total_calories, 1_800);
+    assert_eq!(summary.total_protein_grams, 60);
+    assert_eq!(summary.avg_daily_calories, 600);
+    assert_eq!(summary.calories_needed_per_day, needs.calories_per_day);
+    assert_eq!(
+        summary.calorie_needs_met_pct,
+        600 * 100 / needs.calories_per_day
+    );
+    assert_eq!(
+        summary.protein_needs_met_pct,
+        20 * 100 / needs.protein_grams
+    );
+
+    let result = client.try_sign_off_calorie_count(&study_id, &provider);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+    client.sign_off_calorie_count(&study_id, &dietitian);
+
+    let signed = client.get_calorie_count_summary(&study_id);
+    assert_eq!(signed.signed_off_by, Some(dietitian.clone()));
+    assert_eq!(
+        client.get_calorie_count(&study_id).status,
+        symbol_short!("signed")
+    );
+    let result = client.try_complete_calorie_count(&study_id);
+    assert_eq!(result, Err(Ok(Error::InvalidCalorieCountStatus)));
+}
+
+// -----------------------------------------------------------------------
+// Dietitian caseload
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_dietitian_caseload_and_follow_ups() {
+    let (env, patient, dietitian, _) = setup();
+    let client = register(&env);
+    let other_dietitian = Address::generate(&env);
+
+    env.ledger().set_timestamp(1_000);
+    let (_, weekly_plan) = create_plan(&env, &client, &patient, &dietitian);
+    let (_, closed_plan) = create_plan(&env, &client, &patient, &dietitian);
+    create_plan(&env, &client, &patient, &other_dietitian);
+
+    client.evaluate_nutrition_outcomes(
+        &closed_plan,
+        &2_000u64,
+        &0i64,
+        &Vec::new(&env),
+        &Vec::new(&env),
+        &false,
+    );
+
+    assert_eq!(
+        client.get_dietitian_assessments(&dietitian, &0, &10).len(),
+        2
+    );
+    let plans = client.get_dietitian_care_plans(&dietitian, &0, &10);
+    assert_eq!(plans.len(), 2);
+    assert_eq!(plans.get(0).unwrap().care_plan_id, weekly_plan);
+
+    // Six days in, the weekly follow-up is not yet due.
+    env.ledger().set_timestamp(1_000 + 6 * 86_400);
+    let caseload = client.get_dietitian_caseload(&dietitian);
+    assert_eq!(caseload.total_assessments, 2);
+    assert_eq!(caseload.total_care_plans, 2);
+    assert_eq!(caseload.active_care_plans, 1);
+    assert!(caseload.overdue_follow_up_plan_ids.is_empty());
+
+    env.ledger().set_timestamp(1_000 + 8 * 86_400);
+    let caseload = client.get_dietitian_caseload(&dietitian);
+    assert_eq!(caseload.overdue_follow_up_plan_ids.len(), 1);
+    assert_eq!(
+        caseload.overdue_follow_up_plan_ids.get(0).unwrap(),
+        weekly_plan
+    );
+
+    let due = client.flag_due_follow_ups(&dietitian);
+    assert_eq!(due.len(), 1);
+    assert_eq!(env.events().all().len(), 1);
+
+    // A session resets the follow-up clock.
+    client.document_nutrition_intervention(
+        &weekly_plan,
+        &(1_000 + 8 * 86_400),
+        &symbol_short!("counsel"),
+        &Vec::new(&env),
+        &30u32,
+        &symbol_short!("good"),
+    );
+    let caseload = client.get_dietitian_caseload(&dietitian);
+    assert!(caseload.overdue_follow_up_plan_ids.is_empty());
+    assert!(client.flag_due_follow_ups(&dietitian).is_empty());
+}
+
 // -----------------------------------------------------------------------
 // End-to-end workflow test
 // -----------------------------------------------------------------------
@@ -950,9 +1230,31 @@ fn test_full_nutrition_care_workflow() {
     assert_eq!(ev.weight_change_kg_x100, -80);
     assert!(ev.continue_care);
 
-    let history = client.get_weight_history(&patient);
+    let history = client.get_weight_history(&patient, &0, &10);
     assert_eq!(history.len(), 1);
 
     let supplements = client.get_supplements(&care_plan_id);
     assert_eq!(supplements.len(), 1);
 }
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _patient, _dietitian, _provider) = setup();
+    let client = register(&env);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -19,6 +19,16 @@ pub enum Error {
     AssessmentAlreadyHasMalnutritionRisk = 7,
     WeightRecordingFailed = 8,
     InvalidActivityLevel = 9,
+    InvalidRestrictionKind = 10,
+    InvalidRestrictionSource = 11,
+    NoDietConflicts = 12,
+    DietConflictsAlreadyAcknowledged = 13,
+    CalorieCountNotFound = 14,
+    MealOutsideCountWindow = 15,
+    CalorieCountWindowOpen = 16,
+    InvalidCalorieCountStatus = 17,
+    AlreadyInitialized = 18,
+    NotInitialized = 19,
 }
 
 // -----------------------------------------------------------------------
@@ -204,6 +214,112 @@ pub struct OutcomeEvaluation {
     pub evaluated_at: u64,
 }
 
+/// Fields supplied when recording a food allergy, intolerance or preference.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FoodRestrictionInput {
+    pub allergen: String,
+    /// allergy | intoler | pref
+    pub kind: Symbol,
+    pub reaction: Option<String>,
+    /// patient (patient-reported) | verified (confirmed in the allergy contract)
+    pub source: Symbol,
+    /// Allergy-contract record backing a `verified` entry.
+    pub allergy_record_id: Option<u64>,
+}
+
+/// A food allergy, intolerance or preference captured during an assessment.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FoodRestriction {
+    pub assessment_id: u64,
+    pub patient_id: Address,
+    pub allergen: String,
+    /// allergy | intoler | pref
+    pub kind: Symbol,
+    pub reaction: Option<String>,
+    /// patient (patient-reported) | verified (confirmed in the allergy contract)
+    pub source: Symbol,
+    /// Allergy-contract record backing a `verified` entry.
+    pub allergy_record_id: Option<u64>,
+    pub recorded_by: Address,
+    pub recorded_at: u64,
+}
+
+/// Allergies and intolerances on file when a diet order was placed, pending
+/// acknowledgment by the ordering provider.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DietOrderConflicts {
+    pub order_id: u64,
+    pub restrictions: Vec<FoodRestriction>,
+    pub acknowledged_by: Option<Address>,
+    pub acknowledged_at: Option<u64>,
+}
+
+/// A 72-hour calorie-count study ordered for a patient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountOrder {
+    pub study_id: u64,
+    pub patient_id: Address,
+    pub assessment_id: u64,
+    pub ordering_provider: Address,
+    pub window_start: u64,
+    pub window_end: u64,
+    /// ordered | completed | signed
+    pub status: Symbol,
+    pub ordered_at: u64,
+}
+
+/// A meal recorded by nursing during a calorie count.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountMeal {
+    pub recorded_by: Address,
+    pub meal_time: u64,
+    pub meal_type: Symbol,
+    pub foods_consumed: Vec<FoodItem>,
+    /// 0‑100 percent of the meal consumed.
+    pub percentage_consumed: u32,
+    /// Calories and protein actually consumed, scaled by `percentage_consumed`.
+    pub calories: u32,
+    pub protein_grams: u32,
+}
+
+/// Completion summary of a calorie count compared against computed needs.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CalorieCountSummary {
+    pub study_id: u64,
+    pub meals_recorded: u32,
+    pub total_calories: u32,
+    pub total_protein_grams: u32,
+    pub avg_daily_calories: u32,
+    pub avg_daily_protein_grams: u32,
+    pub calories_needed_per_day: u32,
+    pub protein_needed_grams: u32,
+    /// Average daily intake as a percentage of needs.
+    pub calorie_needs_met_pct: u32,
+    pub protein_needs_met_pct: u32,
+    pub completed_at: u64,
+    pub signed_off_by: Option<Address>,
+    pub signed_off_at: Option<u64>,
+}
+
+/// Workload view of a dietitian's assessments and care plans.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DietitianCaseload {
+    pub dietitian_id: Address,
+    pub total_assessments: u32,
+    pub total_care_plans: u32,
+    /// Plans without an outcome evaluation that discontinued care.
+    pub active_care_plans: u32,
+    /// Active plans whose next follow-up, per `follow_up_frequency`, has passed.
+    pub overdue_follow_up_plan_ids: Vec<u64>,
+}
+
 // -----------------------------------------------------------------------
 // Storage keys
 // -----------------------------------------------------------------------
@@ -211,12 +327,16 @@ pub struct OutcomeEvaluation {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    /// Contract administrator, allowed to upgrade and migrate.
+    Admin,
     /// Auto-increment counter for assessments.
     AssessmentCounter,
     /// Auto-increment counter for care plans.
     CarePlanCounter,
     /// Auto-increment counter for diet orders.
     DietOrderCounter,
+    /// Auto-increment counter for calorie-count studies.
+    CalorieCountCounter,
 
     /// assessment_id → NutritionAssessment
     Assessment(u64),
@@ -242,4 +362,18 @@ pub enum DataKey {
     PatientAssessments(Address),
     /// patient_id → Vec<u64> (diet order ids)
     PatientDietOrders(Address),
+    /// patient_id → Vec<FoodRestriction>
+    FoodRestrictions(Address),
+    /// order_id → DietOrderConflicts
+    DietOrderConflicts(u64),
+    /// study_id → CalorieCountOrder
+    CalorieCount(u64),
+    /// study_id → Vec<CalorieCountMeal>
+    CalorieCountMeals(u64),
+    /// study_id → CalorieCountSummary
+    CalorieCountSummary(u64),
+    /// dietitian_id → Vec<u64> (assessment ids)
+    DietitianAssessments(Address),
+    /// dietitian_id → Vec<u64> (care plan ids)
+    DietitianCarePlans(Address),
 }
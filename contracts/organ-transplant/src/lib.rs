@@ -0,0 +1,480 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct OrganTransplantContract;
+
+#[contractimpl]
+impl OrganTransplantContract {
+    // -------------------------------------------------------------------
+    // Administration and roles
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Grant the OPO coordinator role, required to view donors and record matches.
+    pub fn add_coordinator(env: Env, admin: Address, coordinator: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Coordinator(coordinator), &true);
+        Ok(())
+    }
+
+    pub fn remove_coordinator(env: Env, admin: Address, coordinator: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Coordinator(coordinator));
+        Ok(())
+    }
+
+    pub fn register_transplant_center(
+        env: Env,
+        admin: Address,
+        center_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        let center = TransplantCenter {
+            center_id: center_id.clone(),
+            name,
+            registered_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Center(center_id), &center);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Donor registry
+    // -------------------------------------------------------------------
+
+    /// Register (or update) the caller as a donor with the given consent scope.
+    pub fn register_donor(
+        env: Env,
+        donor_id: Address,
+        blood_type: BloodType,
+        consent: ConsentScope,
+    ) -> Result<(), Error> {
+        donor_id.require_auth();
+
+        if consent.organs.is_empty() && !consent.tissue {
+            return Err(Error::ConsentNotGiven);
+        }
+
+        save_donor(
+            &env,
+            &DonorRecord {
+                donor_id: donor_id.clone(),
+                blood_type,
+                consent,
+                registered_at: env.ledger().timestamp(),
+                active: true,
+            },
+        );
+
+        DonorRegistered {
+            donor_id,
+            blood_type,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn revoke_donor_consent(env: Env, donor_id: Address) -> Result<(), Error> {
+        donor_id.require_auth();
+        let mut donor = load_donor(&env, &donor_id).ok_or(Error::DonorNotFound)?;
+        donor.active = false;
+        save_donor(&env, &donor);
+        Ok(())
+    }
+
+    /// Donor records are visible to the donor and to OPO coordinators only.
+    pub fn get_donor(env: Env, donor_id: Address, caller: Address) -> Result<DonorRecord, Error> {
+        caller.require_auth();
+        if caller != donor_id && !is_coordinator(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        load_donor(&env, &donor_id).ok_or(Error::DonorNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Recipient waitlist
+    // -------------------------------------------------------------------
+
+    pub fn list_recipient(
+        env: Env,
+        center_id: Address,
+        request: WaitlistRequest,
+    ) -> Result<u64, Error> {
+        center_id.require_auth();
+        if !is_center(&env, &center_id) {
+            return Err(Error::CenterNotRegistered);
+        }
+
+        let now = env.ledger().timestamp();
+        let entry = WaitlistEntry {
+            entry_id: next_entry_id(&env),
+            patient_id: request.patient_id,
+            center_id: center_id.clone(),
+            organ: request.organ,
+            blood_type: request.blood_type,
+            urgency: request.urgency,
+            medical_score: request.medical_score,
+            status: WaitlistStatus::Active,
+            listed_at: now,
+            updated_at: now,
+        };
+
+        save_entry(&env, &entry);
+        add_to_organ_list(&env, entry.organ, entry.entry_id);
+        audit(&env, entry.entry_id, &center_id, "listed", entry.entry_id);
+        publish_entry(&env, &entry);
+
+        Ok(entry.entry_id)
+    }
+
+    /// Listing center updates a recipient's urgency and medical score.
+    pub fn update_recipient_urgency(
+        env: Env,
+        center_id: Address,
+        entry_id: u64,
+        urgency: Urgency,
+        medical_score: u32,
+    ) -> Result<(), Error> {
+        let mut entry = load_center_entry(&env, &center_id, entry_id)?;
+        if !matches!(
+            entry.status,
+            WaitlistStatus::Active | WaitlistStatus::OnHold
+        ) {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        entry.urgency = urgency;
+        entry.medical_score = medical_score;
+        entry.updated_at = env.ledger().timestamp();
+        save_entry(&env, &entry);
+        audit(&env, entry_id, &center_id, "urgency", medical_score as u64);
+        publish_entry(&env, &entry);
+
+        Ok(())
+    }
+
+    /// Listing center places a recipient on hold, reactivates, or removes them.
+    /// `Transplanted` is only reached by accepting a match.
+    pub fn update_waitlist_status(
+        env: Env,
+        center_id: Address,
+        entry_id: u64,
+        status: WaitlistStatus,
+    ) -> Result<(), Error> {
+        let mut entry = load_center_entry(&env, &center_id, entry_id)?;
+
+        let allowed = matches!(
+            (&entry.status, &status),
+            (WaitlistStatus::Active, WaitlistStatus::OnHold)
+                | (WaitlistStatus::OnHold, WaitlistStatus::Active)
+                | (WaitlistStatus::Active, WaitlistStatus::Removed)
+                | (WaitlistStatus::OnHold, WaitlistStatus::Removed)
+        );
+        if !allowed {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        entry.status = status;
+        entry.updated_at = env.ledger().timestamp();
+        save_entry(&env, &entry);
+        audit(&env, entry_id, &center_id, "status", entry_id);
+        publish_entry(&env, &entry);
+
+        Ok(())
+    }
+
+    /// Visible to the patient, the listing center and OPO coordinators.
+    pub fn get_waitlist_entry(
+        env: Env,
+        entry_id: u64,
+        caller: Address,
+    ) -> Result<WaitlistEntry, Error> {
+        caller.require_auth();
+        let entry = load_entry(&env, entry_id).ok_or(Error::EntryNotFound)?;
+        if caller != entry.patient_id && caller != entry.center_id && !is_coordinator(&env, &caller)
+        {
+            return Err(Error::Unauthorized);
+        }
+        Ok(entry)
+    }
+
+    // -------------------------------------------------------------------
+    // Matching
+    // -------------------------------------------------------------------
+
+    /// ABO-compatible active candidates for a donor organ, ordered by urgency,
+    /// then medical score (highest first), then time on the list.
+    pub fn find_candidates(
+        env: Env,
+        coordinator: Address,
+        donor_id: Address,
+        organ: OrganType,
+    ) -> Result<Vec<WaitlistEntry>, Error> {
+        require_coordinator(&env, &coordinator)?;
+        let donor = load_donor(&env, &donor_id).ok_or(Error::DonorNotFound)?;
+        if !donor.active || !donor.consent.organs.contains(organ) {
+            return Err(Error::ConsentNotGiven);
+        }
+
+        let mut candidates: Vec<WaitlistEntry> = Vec::new(&env);
+        for id in organ_list(&env, organ).iter() {
+            let entry = match load_entry(&env, id) {
+                Some(entry) => entry,
+                None => continue,
+            };
+            if entry.status != WaitlistStatus::Active
+                || !abo_compatible(donor.blood_type, entry.blood_type)
+            {
+                continue;
+            }
+
+            let mut pos = candidates.len();
+            for (i, existing) in candidates.iter().enumerate() {
+                if ranks_before(&entry, &existing) {
+                    pos = i as u32;
+                    break;
+                }
+            }
+            candidates.insert(pos, entry);
+        }
+
+        Ok(candidates)
+    }
+
+    /// Record an organ offer from a donor to a waitlisted recipient.
+    pub fn record_match(
+        env: Env,
+        coordinator: Address,
+        donor_id: Address,
+        entry_id: u64,
+    ) -> Result<u64, Error> {
+        require_coordinator(&env, &coordinator)?;
+
+        let donor = load_donor(&env, &donor_id).ok_or(Error::DonorNotFound)?;
+        let entry = load_entry(&env, entry_id).ok_or(Error::EntryNotFound)?;
+        if !donor.active || !donor.consent.organs.contains(entry.organ) {
+            return Err(Error::ConsentNotGiven);
+        }
+        if entry.status != WaitlistStatus::Active {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if !abo_compatible(donor.blood_type, entry.blood_type) {
+            return Err(Error::IncompatibleMatch);
+        }
+
+        let event = MatchEvent {
+            match_id: next_match_id(&env),
+            donor_id,
+            entry_id,
+            organ: entry.organ,
+            coordinator: coordinator.clone(),
+            status: MatchStatus::Offered,
+            recorded_at: env.ledger().timestamp(),
+            responded_at: None,
+            response_reason: None,
+        };
+        save_match(&env, &event);
+        audit(&env, entry_id, &coordinator, "offered", event.match_id);
+
+        MatchRecorded {
+            match_id: event.match_id,
+            entry_id,
+            status: MatchStatus::Offered,
+        }
+        .publish(&env);
+
+        Ok(event.match_id)
+    }
+
+    /// Listing center accepts or declines an offer. Acceptance marks the
+    /// recipient as transplanted.
+    pub fn respond_to_match(
+        env: Env,
+        center_id: Address,
+        match_id: u64,
+        accept: bool,
+        reason: Option<String>,
+    ) -> Result<(), Error> {
+        let mut event = load_match(&env, match_id).ok_or(Error::MatchNotFound)?;
+        let mut entry = load_center_entry(&env, &center_id, event.entry_id)?;
+        if event.status != MatchStatus::Offered {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let now = env.ledger().timestamp();
+        event.status = if accept {
+            MatchStatus::Accepted
+        } else {
+            MatchStatus::Declined
+        };
+        event.responded_at = Some(now);
+        event.response_reason = reason;
+        save_match(&env, &event);
+
+        if accept {
+            entry.status = WaitlistStatus::Transplanted;
+            entry.updated_at = now;
+            save_entry(&env, &entry);
+            publish_entry(&env, &entry);
+        }
+        let action = if accept { "accepted" } else { "declined" };
+        audit(&env, entry.entry_id, &center_id, action, match_id);
+
+        MatchRecorded {
+            match_id,
+            entry_id: entry.entry_id,
+            status: event.status,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_match(env: Env, match_id: u64, caller: Address) -> Result<MatchEvent, Error> {
+        caller.require_auth();
+        let event = load_match(&env, match_id).ok_or(Error::MatchNotFound)?;
+        let entry = load_entry(&env, event.entry_id).ok_or(Error::EntryNotFound)?;
+        if caller != entry.center_id && !is_coordinator(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(event)
+    }
+
+    /// Full history of actions on a waitlist entry, for coordinators, the
+    /// listing center and the administrator.
+    pub fn get_audit_trail(
+        env: Env,
+        caller: Address,
+        entry_id: u64,
+    ) -> Result<Vec<AuditEntry>, Error> {
+        caller.require_auth();
+        let entry = load_entry(&env, entry_id).ok_or(Error::EntryNotFound)?;
+        let is_admin = get_admin(&env) == Some(caller.clone());
+        if !is_admin && caller != entry.center_id && !is_coordinator(&env, &caller) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(load_audit(&env, entry_id))
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    let admin = get_admin(env).ok_or(Error::NotInitialized)?;
+    if admin != *caller {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_coordinator(env: &Env, caller: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    if !is_coordinator(env, caller) {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_center_entry(
+    env: &Env,
+    center_id: &Address,
+    entry_id: u64,
+) -> Result<WaitlistEntry, Error> {
+    center_id.require_auth();
+    let entry = load_entry(env, entry_id).ok_or(Error::EntryNotFound)?;
+    if entry.center_id != *center_id {
+        return Err(Error::Unauthorized);
+    }
+    Ok(entry)
+}
+
+fn publish_entry(env: &Env, entry: &WaitlistEntry) {
+    WaitlistUpdated {
+        entry_id: entry.entry_id,
+        status: entry.status.clone(),
+        urgency: entry.urgency,
+    }
+    .publish(env);
+}
+
+fn abo_compatible(donor: BloodType, recipient: BloodType) -> bool {
+    match donor {
+        BloodType::O => true,
+        BloodType::A => matches!(recipient, BloodType::A | BloodType::AB),
+        BloodType::B => matches!(recipient, BloodType::B | BloodType::AB),
+        BloodType::AB => recipient == BloodType::AB,
+    }
+}
+
+fn urgency_rank(urgency: Urgency) -> u32 {
+    match urgency {
+        Urgency::Emergent => 0,
+        Urgency::Urgent => 1,
+        Urgency::Routine => 2,
+    }
+}
+
+fn ranks_before(a: &WaitlistEntry, b: &WaitlistEntry) -> bool {
+    let (ua, ub) = (urgency_rank(a.urgency), urgency_rank(b.urgency));
+    if ua != ub {
+        return ua < ub;
+    }
+    if a.medical_score != b.medical_score {
+        return a.medical_score > b.medical_score;
+    }
+    a.listed_at < b.listed_at
+}
@@ -0,0 +1,131 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{AuditEntry, DataKey, DonorRecord, MatchEvent, OrganType, WaitlistEntry};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_coordinator(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Coordinator(who.clone()))
+        .unwrap_or(false)
+}
+
+pub fn is_center(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Center(who.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+pub fn next_entry_id(env: &Env) -> u64 {
+    next_id(env, DataKey::EntryCounter)
+}
+
+pub fn next_match_id(env: &Env) -> u64 {
+    next_id(env, DataKey::MatchCounter)
+}
+
+// -----------------------------------------------------------------------
+// Donors
+// -----------------------------------------------------------------------
+
+pub fn save_donor(env: &Env, donor: &DonorRecord) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Donor(donor.donor_id.clone()), donor);
+}
+
+pub fn load_donor(env: &Env, donor_id: &Address) -> Option<DonorRecord> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Donor(donor_id.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Waitlist
+// -----------------------------------------------------------------------
+
+pub fn save_entry(env: &Env, entry: &WaitlistEntry) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Entry(entry.entry_id), entry);
+}
+
+pub fn load_entry(env: &Env, entry_id: u64) -> Option<WaitlistEntry> {
+    env.storage().persistent().get(&DataKey::Entry(entry_id))
+}
+
+pub fn add_to_organ_list(env: &Env, organ: OrganType, entry_id: u64) {
+    let key = DataKey::OrganWaitlist(organ);
+    let mut ids: Vec<u64> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    ids.push_back(entry_id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn organ_list(env: &Env, organ: OrganType) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::OrganWaitlist(organ))
+        .unwrap_or(Vec::new(env))
+}
+
+// -----------------------------------------------------------------------
+// Matches
+// -----------------------------------------------------------------------
+
+pub fn save_match(env: &Env, event: &MatchEvent) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Match(event.match_id), event);
+}
+
+pub fn load_match(env: &Env, match_id: u64) -> Option<MatchEvent> {
+    env.storage().persistent().get(&DataKey::Match(match_id))
+}
+
+// -----------------------------------------------------------------------
+// Audit trail
+// -----------------------------------------------------------------------
+
+pub fn audit(env: &Env, entry_id: u64, actor: &Address, action: &str, reference_id: u64) {
+    let key = DataKey::Audit(entry_id);
+    let mut log: Vec<AuditEntry> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    log.push_back(AuditEntry {
+        actor: actor.clone(),
+        action: Symbol::new(env, action),
+        reference_id,
+        timestamp: env.ledger().timestamp(),
+    });
+    env.storage().persistent().set(&key, &log);
+}
+
+pub fn load_audit(env: &Env, entry_id: u64) -> Vec<AuditEntry> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Audit(entry_id))
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,414 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env, String};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    OrganTransplantContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(OrganTransplantContract, ());
+    let client = OrganTransplantContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let coordinator = Address::generate(&env);
+    let center = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_coordinator(&admin, &coordinator);
+    client.register_transplant_center(&admin, &center, &String::from_str(&env, "City Transplant"));
+
+    (env, admin, coordinator, center, client)
+}
+
+fn register_kidney_donor(
+    env: &Env,
+    client: &OrganTransplantContractClient,
+    blood_type: BloodType,
+) -> Address {
+    let donor = Address::generate(env);
+    client.register_donor(
+        &donor,
+        &blood_type,
+        &ConsentScope {
+            organs: vec![env, OrganType::Kidney, OrganType::Liver],
+            tissue: true,
+            research: false,
+            consent_hash: BytesN::from_array(env, &[1u8; 32]),
+        },
+    );
+    donor
+}
+
+fn list(
+    env: &Env,
+    client: &OrganTransplantContractClient,
+    center: &Address,
+    blood_type: BloodType,
+    urgency: Urgency,
+    score: u32,
+) -> u64 {
+    client.list_recipient(
+        center,
+        &WaitlistRequest {
+            patient_id: Address::generate(env),
+            organ: OrganType::Kidney,
+            blood_type,
+            urgency,
+            medical_score: score,
+        },
+    )
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_donor_registration_and_access() {
+    let (env, _, coordinator, _, client) = setup();
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+
+    let record = client.get_donor(&donor, &coordinator);
+    assert_eq!(record.blood_type, BloodType::O);
+    assert!(record.active);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_get_donor(&donor, &stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.revoke_donor_consent(&donor);
+    assert!(!client.get_donor(&donor, &donor).active);
+}
+
+#[test]
+fn test_candidates_ranked_and_abo_filtered() {
+    let (env, _, coordinator, center, client) = setup();
+    let donor = register_kidney_donor(&env, &client, BloodType::A);
+
+    let routine = list(&env, &client, &center, BloodType::A, Urgency::Routine, 90);
+    let urgent_low = list(&env, &client, &center, BloodType::AB, Urgency::Urgent, 10);
+    let urgent_high = list(&env, &client, &center, BloodType::A, Urgency::Urgent, 50);
+    let incompatible = list(&env, &client, &center, BloodType::B, Urgency::Emergent, 99);
+
+    let candidates = client.find_candidates(&coordinator, &donor, &OrganType::Kidney);
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates.get(0).unwrap().entry_id, urgent_high);
+    assert_eq!(candidates.get(1).unwrap().entry_id, urgent_low);
+    assert_eq!(candidates.get(2).unwrap().entry_id, routine);
+
+    assert_eq!(
+        client.try_record_match(&coordinator, &donor, &incompatible),
+        Err(Ok(Error::IncompatibleMatch))
+    );
+
+    // Organs outside the consent scope cannot be allocated
+    assert_eq!(
+        client.try_find_candidates(&coordinator, &donor, &OrganType::Heart),
+        Err(Ok(Error::ConsentNotGiven))
+    );
+}
+
+#[test]
+fn test_only_coordinators_record_matches() {
+    let (env, admin, coordinator, center, client) = setup();
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    let entry = list(&env, &client, &center, BloodType::B, Urgency::Urgent, 40);
+
+    assert_eq!(
+        client.try_record_match(&center, &donor, &entry),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.remove_coordinator(&admin, &coordinator);
+    assert_eq!(
+        client.try_record_match(&coordinator, &donor, &entry),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_match_acceptance_and_audit_trail() {
+    let (env, admin, coordinator, center, client) = setup();
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    let entry = list(&env, &client, &center, BloodType::B, Urgency::Routine, 40);
+
+    client.update_recipient_urgency(&center, &entry, &Urgency::Urgent, &70);
+
+    let declined = client.record_match(&coordinator, &donor, &entry);
+    client.respond_to_match(
+        &center,
+        &declined,
+        &false,
+        &Some(String::from_str(&env, "Organ quality")),
+    );
+    assert_eq!(
+        client.get_match(&declined, &coordinator).status,
+        MatchStatus::Declined
+    );
+
+    let accepted = client.record_match(&coordinator, &donor, &entry);
+    client.respond_to_match(&center, &accepted, &true, &None);
+
+    let record = client.get_waitlist_entry(&entry, &center);
+    assert_eq!(record.status, WaitlistStatus::Transplanted);
+    assert_eq!(record.urgency, Urgency::Urgent);
+
+    // A transplanted recipient can no longer receive offers or be reactivated
+    assert_eq!(
+        client.try_record_match(&coordinator, &donor, &entry),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_update_waitlist_status(&center, &entry, &WaitlistStatus::Active),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+
+    let trail = client.get_audit_trail(&admin, &entry);
+    assert_eq!(trail.len(), 6);
+    assert_eq!(trail.get(5).unwrap().reference_id, accepted);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_get_audit_trail(&stranger, &entry),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_status_updates_limited_to_listing_center() {
+    let (env, admin, coordinator, center, client) = setup();
+    let entry = list(&env, &client, &center, BloodType::O, Urgency::Routine, 10);
+
+    let other_center = Address::generate(&env);
+    client.register_transplant_center(
+        &admin,
+        &other_center,
+        &String::from_str(&env, "County General"),
+    );
+    assert_eq!(
+        client.try_update_waitlist_status(&other_center, &entry, &WaitlistStatus::OnHold),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.update_waitlist_status(&center, &entry, &WaitlistStatus::OnHold);
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    assert!(client
+        .find_candidates(&coordinator, &donor, &OrganType::Kidney)
+        .is_empty());
+
+    // Unregistered facilities cannot list recipients
+    let unregistered = Address::generate(&env);
+    let res = client.try_list_recipient(
+        &unregistered,
+        &WaitlistRequest {
+            patient_id: Address::generate(&env),
+            organ: OrganType::Liver,
+            blood_type: BloodType::A,
+            urgency: Urgency::Routine,
+            medical_score: 1,
+        },
+    );
+    assert_eq!(res, Err(Ok(Error::CenterNotRegistered)));
+}
+
+#[test]
+fn test_initialize_twice_fails() {
+    let (_, admin, _, _, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn test_role_management_requires_admin() {
+    let (env, _, coordinator, center, client) = setup();
+    let stranger = Address::generate(&env);
+
+    assert_eq!(
+        client.try_add_coordinator(&coordinator, &stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_remove_coordinator(&center, &coordinator),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_register_transplant_center(
+            &center,
+            &stranger,
+            &String::from_str(&env, "Rogue Clinic"),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_donor_consent_errors() {
+    let (env, _, coordinator, center, client) = setup();
+
+    let donor = Address::generate(&env);
+    let empty_consent = ConsentScope {
+        organs: vec![&env],
+        tissue: false,
+        research: true,
+        consent_hash: BytesN::from_array(&env, &[2u8; 32]),
+    };
+    assert_eq!(
+        client.try_register_donor(&donor, &BloodType::O, &empty_consent),
+        Err(Ok(Error::ConsentNotGiven))
+    );
+    assert_eq!(
+        client.try_revoke_donor_consent(&donor),
+        Err(Ok(Error::DonorNotFound))
+    );
+    assert_eq!(
+        client.try_get_donor(&donor, &coordinator),
+        Err(Ok(Error::DonorNotFound))
+    );
+
+    // Revoked donors drop out of allocation entirely
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    let entry = list(&env, &client, &center, BloodType::O, Urgency::Urgent, 50);
+    client.revoke_donor_consent(&donor);
+    assert_eq!(
+        client.try_find_candidates(&coordinator, &donor, &OrganType::Kidney),
+        Err(Ok(Error::ConsentNotGiven))
+    );
+    assert_eq!(
+        client.try_record_match(&coordinator, &donor, &entry),
+        Err(Ok(Error::ConsentNotGiven))
+    );
+}
+
+#[test]
+fn test_match_response_errors() {
+    let (env, admin, coordinator, center, client) = setup();
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    let entry = list(&env, &client, &center, BloodType::O, Urgency::Urgent, 50);
+    let match_id = client.record_match(&coordinator, &donor, &entry);
+
+    assert_eq!(
+        client.try_respond_to_match(&center, &99, &true, &None),
+        Err(Ok(Error::MatchNotFound))
+    );
+
+    let other_center = Address::generate(&env);
+    client.register_transplant_center(
+        &admin,
+        &other_center,
+        &String::from_str(&env, "County General"),
+    );
+    assert_eq!(
+        client.try_respond_to_match(&other_center, &match_id, &true, &None),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_get_match(&match_id, &other_center),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // An offer can only be answered once
+    client.respond_to_match(&center, &match_id, &false, &None);
+    assert_eq!(
+        client.try_respond_to_match(&center, &match_id, &true, &None),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_invalid_waitlist_transitions() {
+    let (env, _, coordinator, center, client) = setup();
+    let entry = list(&env, &client, &center, BloodType::A, Urgency::Routine, 20);
+
+    // Transplanted is reserved for accepted matches
+    assert_eq!(
+        client.try_update_waitlist_status(&center, &entry, &WaitlistStatus::Transplanted),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_update_waitlist_status(&center, &entry, &WaitlistStatus::Active),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+
+    client.update_waitlist_status(&center, &entry, &WaitlistStatus::Removed);
+    assert_eq!(
+        client.try_update_waitlist_status(&center, &entry, &WaitlistStatus::Active),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+    assert_eq!(
+        client.try_update_recipient_urgency(&center, &entry, &Urgency::Emergent, &90),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+
+    let donor = register_kidney_donor(&env, &client, BloodType::O);
+    assert_eq!(
+        client.try_record_match(&coordinator, &donor, &entry),
+        Err(Ok(Error::InvalidStatusTransition))
+    );
+}
+
+#[test]
+fn test_waitlist_entry_access() {
+    let (env, _, coordinator, center, client) = setup();
+    let patient = Address::generate(&env);
+    let entry = client.list_recipient(
+        &center,
+        &WaitlistRequest {
+            patient_id: patient.clone(),
+            organ: OrganType::Kidney,
+            blood_type: BloodType::B,
+            urgency: Urgency::Routine,
+            medical_score: 30,
+        },
+    );
+
+    assert_eq!(
+        client.get_waitlist_entry(&entry, &patient).patient_id,
+        patient
+    );
+    assert_eq!(
+        client.get_waitlist_entry(&entry, &coordinator).entry_id,
+        entry
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_get_waitlist_entry(&entry, &stranger),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_get_waitlist_entry(&99, &center),
+        Err(Ok(Error::EntryNotFound))
+    );
+    assert_eq!(
+        client.try_get_audit_trail(&coordinator, &99),
+        Err(Ok(Error::EntryNotFound))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, coordinator, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(
+        client.try_migrate(&coordinator),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_upgrade(&coordinator, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
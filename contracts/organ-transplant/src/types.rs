@@ -0,0 +1,216 @@
+use soroban_sdk::{
+    contracterror, contractevent, contracttype, Address, BytesN, String, Symbol, Vec,
+};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    CenterNotRegistered = 4,
+    DonorNotFound = 5,
+    ConsentNotGiven = 6,
+    EntryNotFound = 7,
+    MatchNotFound = 8,
+    InvalidStatusTransition = 9,
+    IncompatibleMatch = 10,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+/// ABO blood group, the compatibility gate for solid organ allocation.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BloodType {
+    O,
+    A,
+    B,
+    AB,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum OrganType {
+    Kidney,
+    Liver,
+    Heart,
+    Lung,
+    Pancreas,
+    Intestine,
+}
+
+/// Medical urgency of a waitlisted recipient, most urgent first.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Urgency {
+    Emergent,
+    Urgent,
+    Routine,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum WaitlistStatus {
+    /// Eligible to receive offers.
+    Active,
+    /// Temporarily not eligible for offers.
+    OnHold,
+    /// Received a transplant.
+    Transplanted,
+    /// Removed from the list (recovered, deceased, transferred).
+    Removed,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum MatchStatus {
+    Offered,
+    Accepted,
+    Declined,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+/// What a donor has agreed to donate.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentScope {
+    pub organs: Vec<OrganType>,
+    pub tissue: bool,
+    pub research: bool,
+    pub consent_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorRecord {
+    pub donor_id: Address,
+    pub blood_type: BloodType,
+    pub consent: ConsentScope,
+    pub registered_at: u64,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransplantCenter {
+    pub center_id: Address,
+    pub name: String,
+    pub registered_at: u64,
+}
+
+/// Input for listing a recipient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistRequest {
+    pub patient_id: Address,
+    pub organ: OrganType,
+    pub blood_type: BloodType,
+    pub urgency: Urgency,
+    pub medical_score: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistEntry {
+    pub entry_id: u64,
+    pub patient_id: Address,
+    pub center_id: Address,
+    pub organ: OrganType,
+    pub blood_type: BloodType,
+    pub urgency: Urgency,
+    pub medical_score: u32,
+    pub status: WaitlistStatus,
+    pub listed_at: u64,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchEvent {
+    pub match_id: u64,
+    pub donor_id: Address,
+    pub entry_id: u64,
+    pub organ: OrganType,
+    pub coordinator: Address,
+    pub status: MatchStatus,
+    pub recorded_at: u64,
+    pub responded_at: Option<u64>,
+    pub response_reason: Option<String>,
+}
+
+/// One action taken on a waitlist entry.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditEntry {
+    pub actor: Address,
+    pub action: Symbol,
+    pub reference_id: u64,
+    pub timestamp: u64,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DonorRegistered {
+    #[topic]
+    pub donor_id: Address,
+    pub blood_type: BloodType,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitlistUpdated {
+    #[topic]
+    pub entry_id: u64,
+    pub status: WaitlistStatus,
+    pub urgency: Urgency,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchRecorded {
+    #[topic]
+    pub match_id: u64,
+    pub entry_id: u64,
+    pub status: MatchStatus,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// coordinator -> bool
+    Coordinator(Address),
+    /// center_id -> TransplantCenter
+    Center(Address),
+    /// donor_id -> DonorRecord
+    Donor(Address),
+    EntryCounter,
+    /// entry_id -> WaitlistEntry
+    Entry(u64),
+    /// organ -> Vec<u64> (entry ids)
+    OrganWaitlist(OrganType),
+    MatchCounter,
+    /// match_id -> MatchEvent
+    Match(u64),
+    /// entry_id -> Vec<AuditEntry>
+    Audit(u64),
+}
@@ -0,0 +1,17 @@
+//! Client interface for the health-information-exchange contract, used to
+//! register imaging studies in the document registry when they are shared.
+
+use soroban_sdk::{contractclient, Address, BytesN, Env, Symbol};
+
+#[contractclient(name = "DocumentExchangeClient")]
+pub trait DocumentExchange {
+    fn register_exchanged_document(
+        env: Env,
+        integration: Address,
+        source: Address,
+        recipient: Address,
+        patient_id: Address,
+        document_type: Symbol,
+        content_hash: BytesN<32>,
+    ) -> u64;
+}
@@ -1,5 +1,7 @@
 #![no_std]
 
+mod document_exchange;
+mod patient_registry;
 mod storage;
 mod types;
 
@@ -12,11 +14,56 @@ use soroban_sdk::{
 use storage::*;
 use types::*;
 
+pub use document_exchange::{DocumentExchange, DocumentExchangeClient};
+pub use patient_registry::{PatientRegistry, PatientRegistryClient};
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct PacsContract;
 
 #[contractimpl]
 impl PacsContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if load_admin(&env).is_some() {
+            return Err(Error::AlreadyExists);
+        }
+        admin.require_auth();
+        save_admin(&env, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Configure the patient-registry contract used to validate new studies.
+    pub fn set_patient_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        strict_mode: bool,
+    ) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        save_patient_registry(
+            &env,
+            &PatientRegistryConfig {
+                registry,
+                strict_mode,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn get_patient_registry(env: Env) -> Option<PatientRegistryConfig> {
+        load_patient_registry(&env)
+    }
+
+    /// Configure the document registry that records studies shared via access grants.
+    pub fn set_document_exchange(env: Env, admin: Address, exchange: Address) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        save_document_exchange(&env, &exchange);
+        Ok(())
+    }
+
     /// Register a new DICOM imaging study and return its on-chain study_id.
     #[allow(clippy::too_many_arguments)]
     pub fn register_imaging_study(
@@ -37,6 +84,7 @@ impl PacsContract {
         if study_uid.len() == 0 || body_part.len() == 0 {
             return Err(Error::InvalidInput);
         }
+        require_registered_patient(&env, &patient_id)?;
 
         let study_id = next_study_id(&env);
 
@@ -224,6 +272,17 @@ impl PacsContract {
         grants.push_back(grant);
         save_access_list(&env, study_id, &grants);
 
+        if let Some(exchange) = load_document_exchange(&env) {
+            DocumentExchangeClient::new(&env, &exchange).register_exchanged_document(
+                &env.current_contract_address(),
+                &study.ordering_provider,
+                &viewer_id,
+                &patient_id,
+                &symbol_short!("imaging"),
+                &study.storage_location_hash,
+            );
+        }
+
         env.events().publish(
             (symbol_short!("acc_grant"), study_id),
             (patient_id, viewer_id),
@@ -357,15 +416,12 @@ impl PacsContract {
             study.patient_id == viewer_id || study.ordering_provider == viewer_id;
 
         if !is_owner {
-            let now = env.ledger().timestamp();
             let grants = load_access_list(&env, study_id);
             let mut allowed = false;
             for grant in grants.iter() {
                 if grant.viewer_id == viewer_id {
-                    if let Some(exp) = grant.expires_at {
-                        if now > exp {
-                            return Err(Error::AccessExpired);
-                        }
+                    if shared_access::is_expired(&env, grant.expires_at) {
+                        return Err(Error::AccessExpired);
                     }
                     allowed = true;
                     break;
@@ -402,7 +458,6 @@ impl PacsContract {
     ) -> Result<Vec<ImagingStudy>, Error> {
         requester.require_auth();
 
-        let now = env.ledger().timestamp();
         let study_ids = load_patient_studies(&env, &patient_id);
         let mut results: Vec<ImagingStudy> = Vec::new(&env);
 
@@ -417,13 +472,7 @@ impl PacsContract {
                     let grants = load_access_list(&env, sid);
                     for grant in grants.iter() {
                         if grant.viewer_id == requester {
-                            let active = match grant.expires_at {
-                                Some(exp) => now <= exp,
-                                None => true,
-                            };
-                            if active {
-                                allowed = true;
-                            }
+                            allowed = !shared_access::is_expired(&env, grant.expires_at);
                             break;
                         }
                     }
@@ -466,4 +515,32 @@ impl PacsContract {
 
         Ok(results)
     }
+
+    /// Extend the TTL of a study and its series, report, access list, view
+    /// log, QC review and anonymized UID. Open to any caller so archived
+    /// studies can be kept readable by whoever needs them.
+    pub fn extend_study_ttl(env: Env, study_id: u64) -> Result<(), Error> {
+        if !extend_study_entries(&env, study_id) {
+            return Err(Error::NotFound);
+        }
+        shared_ttl::extend_instance(&env, shared_ttl::INSTANCE);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        shared_access::require_admin(&env, &admin, &DataKey::Admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
 }
@@ -0,0 +1,9 @@
+//! Client interface for the patient-registry contract, used to verify that a
+//! patient is registered before imaging studies are registered against them.
+
+use soroban_sdk::{contractclient, Address, Env};
+
+#[contractclient(name = "PatientRegistryClient")]
+pub trait PatientRegistry {
+    fn is_patient_registered(env: Env, wallet: Address) -> bool;
+}
@@ -1,11 +1,10 @@
 use soroban_sdk::{Address, Env, String, Vec};
+use crate::patient_registry::PatientRegistryClient;
 use crate::types::{
-    AccessGrant, CdRecord, DataKey, ImagingReport, ImagingStudy, QcReview, SeriesInfo, ViewRecord,
+    AccessGrant, CdRecord, DataKey, Error, ImagingReport, ImagingStudy, PatientRegistryConfig,
+    QcReview, SeriesInfo, ViewRecord,
 };
 
-const BUMP_AMOUNT: u32 = 518400; // ~60 days in ledgers (assuming 5s ledger)
-const BUMP_THRESHOLD: u32 = 259200; // ~30 days
-
 pub fn next_study_id(env: &Env) -> u64 {
     let id: u64 = env
         .storage()
@@ -31,21 +30,41 @@ pub fn next_cd_id(env: &Env) -> u64 {
 pub fn save_study(env: &Env, study: &ImagingStudy) {
     let key = DataKey::Study(study.study_id);
     env.storage().persistent().set(&key, study);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn load_study(env: &Env, study_id: u64) -> Option<ImagingStudy> {
-    env.storage().persistent().get(&DataKey::Study(study_id))
+    let key = DataKey::Study(study_id);
+    let study = env.storage().persistent().get(&key);
+    if study.is_some() {
+        shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+    }
+    study
+}
+
+/// Extend a study and every entry keyed by its id with the archive policy.
+/// Returns false if the study does not exist.
+pub fn extend_study_entries(env: &Env, study_id: u64) -> bool {
+    if !shared_ttl::bump_persistent(env, &DataKey::Study(study_id), shared_ttl::ARCHIVE) {
+        return false;
+    }
+    for key in [
+        DataKey::SeriesList(study_id),
+        DataKey::Report(study_id),
+        DataKey::AccessList(study_id),
+        DataKey::ViewLog(study_id),
+        DataKey::QcReview(study_id),
+        DataKey::AnonymizedStudy(study_id),
+    ] {
+        shared_ttl::bump_persistent(env, &key, shared_ttl::ARCHIVE);
+    }
+    true
 }
 
 pub fn save_series(env: &Env, study_id: u64, series: &Vec<SeriesInfo>) {
     let key = DataKey::SeriesList(study_id);
     env.storage().persistent().set(&key, series);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn load_series(env: &Env, study_id: u64) -> Vec<SeriesInfo> {
@@ -58,9 +77,7 @@ pub fn load_series(env: &Env, study_id: u64) -> Vec<SeriesInfo> {
 pub fn save_report(env: &Env, report: &ImagingReport) {
     let key = DataKey::Report(report.study_id);
     env.storage().persistent().set(&key, report);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn load_report(env: &Env, study_id: u64) -> Option<ImagingReport> {
@@ -70,9 +87,7 @@ pub fn load_report(env: &Env, study_id: u64) -> Option<ImagingReport> {
 pub fn save_access_list(env: &Env, study_id: u64, grants: &Vec<AccessGrant>) {
     let key = DataKey::AccessList(study_id);
     env.storage().persistent().set(&key, grants);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn load_access_list(env: &Env, study_id: u64) -> Vec<AccessGrant> {
@@ -85,9 +100,7 @@ pub fn load_access_list(env: &Env, study_id: u64) -> Vec<AccessGrant> {
 pub fn save_patient_studies(env: &Env, patient_id: &Address, studies: &Vec<u64>) {
     let key = DataKey::PatientStudies(patient_id.clone());
     env.storage().persistent().set(&key, studies);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn load_patient_studies(env: &Env, patient_id: &Address) -> Vec<u64> {
@@ -106,31 +119,66 @@ pub fn append_view_log(env: &Env, study_id: u64, record: &ViewRecord) {
         .unwrap_or_else(|| Vec::new(env));
     logs.push_back(record.clone());
     env.storage().persistent().set(&key, &logs);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn save_qc_review(env: &Env, review: &QcReview) {
     let key = DataKey::QcReview(review.study_id);
     env.storage().persistent().set(&key, review);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn save_anonymized_uid(env: &Env, study_id: u64, uid: &String) {
     let key = DataKey::AnonymizedStudy(study_id);
     env.storage().persistent().set(&key, uid);
-    env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
 }
 
 pub fn save_cd_record(env: &Env, record: &CdRecord) {
     let key = DataKey::CdRecord(record.cd_id);
     env.storage().persistent().set(&key, record);
+    shared_ttl::extend_persistent(env, &key, shared_ttl::ACTIVE);
+}
+
+pub fn load_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn save_admin(env: &Env, admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+}
+
+pub fn load_patient_registry(env: &Env) -> Option<PatientRegistryConfig> {
+    env.storage().instance().get(&DataKey::PatientRegistry)
+}
+
+pub fn save_patient_registry(env: &Env, config: &PatientRegistryConfig) {
     env.storage()
-        .persistent()
-        .extend_ttl(&key, BUMP_THRESHOLD, BUMP_AMOUNT);
+        .instance()
+        .set(&DataKey::PatientRegistry, config);
+}
+
+pub fn load_document_exchange(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::DocumentExchange)
+}
+
+pub fn save_document_exchange(env: &Env, exchange: &Address) {
+    env.storage()
+        .instance()
+        .set(&DataKey::DocumentExchange, exchange);
+}
+
+/// In strict mode, verify the patient exists in the configured patient registry.
+pub fn require_registered_patient(env: &Env, patient_id: &Address) -> Result<(), Error> {
+    match load_patient_registry(env) {
+        Some(config) if config.strict_mode => {
+            let client = PatientRegistryClient::new(env, &config.registry);
+            if client.is_patient_registered(patient_id) {
+                Ok(())
+            } else {
+                Err(Error::PatientNotRegistered)
+            }
+        }
+        _ => Ok(()),
+    }
 }
@@ -1,9 +1,55 @@
 #![cfg(test)]
 
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{storage::Persistent as _, Address as _},
+    Address, BytesN, Env, String, Symbol, Vec,
+};
 
 use crate::{PacsContract, PacsContractClient};
-use crate::types::{ComparisonCriteria, ImagingFilters};
+use crate::types::{ComparisonCriteria, DataKey, Error, ImagingFilters};
+
+// Minimal stand-in for the patient-registry contract
+#[contract]
+struct MockPatientRegistry;
+
+#[contractimpl]
+impl MockPatientRegistry {
+    pub fn register_patient(env: Env, wallet: Address) {
+        env.storage().persistent().set(&wallet, &true);
+    }
+
+    pub fn is_patient_registered(env: Env, wallet: Address) -> bool {
+        env.storage().persistent().has(&wallet)
+    }
+}
+
+// Records the last document registered through the exchange hook
+#[contract]
+struct MockDocumentExchange;
+
+#[contractimpl]
+impl MockDocumentExchange {
+    pub fn register_exchanged_document(
+        env: Env,
+        _integration: Address,
+        source: Address,
+        recipient: Address,
+        _patient_id: Address,
+        document_type: Symbol,
+        _content_hash: BytesN<32>,
+    ) -> u64 {
+        env.storage().instance().set(
+            &Symbol::new(&env, "last"),
+            &(source, recipient, document_type),
+        );
+        1
+    }
+
+    pub fn last(env: Env) -> Option<(Address, Address, Symbol)> {
+        env.storage().instance().get(&Symbol::new(&env, "last"))
+    }
+}
 
 // ─── helpers ────────────────────────────────────────────────────────────────
 
@@ -369,3 +415,101 @@ fn search_studies_critical_findings_filter() {
     let results = client.search_imaging_studies(&patient, &patient, &filters);
     assert_eq!(results.len(), 1);
 }
+
+#[test]
+fn strict_mode_rejects_unregistered_patient() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, provider) = setup(&env);
+
+    let registry_id = env.register(MockPatientRegistry, ());
+    let registry = MockPatientRegistryClient::new(&env, &registry_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Lenient mode only records the registry
+    client.set_patient_registry(&admin, &registry_id, &false);
+    assert_eq!(register_ct_chest(&env, &client, &patient, &provider), 1);
+
+    client.set_patient_registry(&admin, &registry_id, &true);
+    let result = client.try_register_imaging_study(
+        &patient,
+        &provider,
+        &String::from_str(&env, "1.2.3"),
+        &Symbol::new(&env, "MR"),
+        &String::from_str(&env, "Knee"),
+        &1_700_000_000_u64,
+        &String::from_str(&env, "MR Knee"),
+        &1_u32,
+        &20_u32,
+        &dummy_hash(&env),
+    );
+    assert_eq!(result, Err(Ok(Error::PatientNotRegistered)));
+
+    registry.register_patient(&patient);
+    assert_eq!(register_ct_chest(&env, &client, &patient, &provider), 2);
+}
+
+#[test]
+fn set_patient_registry_requires_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, _, _) = setup(&env);
+
+    client.initialize(&Address::generate(&env));
+    let result = client.try_set_patient_registry(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &true,
+    );
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+}
+
+#[test]
+fn grant_access_registers_shared_study_with_document_exchange() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, provider) = setup(&env);
+
+    let exchange_id = env.register(MockDocumentExchange, ());
+    let exchange = MockDocumentExchangeClient::new(&env, &exchange_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_document_exchange(&admin, &exchange_id);
+
+    let study_id = register_ct_chest(&env, &client, &patient, &provider);
+    let viewer = Address::generate(&env);
+    client.grant_imaging_access(
+        &study_id,
+        &patient,
+        &viewer,
+        &Symbol::new(&env, "view"),
+        &None,
+    );
+    assert_eq!(
+        exchange.last(),
+        Some((provider, viewer, Symbol::new(&env, "imaging")))
+    );
+}
+
+#[test]
+fn extend_study_ttl_uses_archive_policy() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, patient, provider) = setup(&env);
+    let study_id = register_ct_chest(&env, &client, &patient, &provider);
+
+    let ttl = || {
+        env.as_contract(&client.address, || {
+            env.storage()
+                .persistent()
+                .get_ttl(&DataKey::Study(study_id))
+        })
+    };
+    assert_eq!(ttl(), shared_ttl::ACTIVE.extend_to);
+
+    client.extend_study_ttl(&study_id);
+    assert_eq!(ttl(), shared_ttl::ARCHIVE.extend_to);
+
+    assert_eq!(client.try_extend_study_ttl(&99), Err(Ok(Error::NotFound)));
+}
@@ -10,10 +10,23 @@ pub enum Error {
     AlreadyExists = 4,
     AccessExpired = 5,
     ReportAlreadyExists = 6,
+    PatientNotRegistered = 7,
+}
+
+impl From<shared_access::AccessError> for Error {
+    fn from(err: shared_access::AccessError) -> Self {
+        match err {
+            shared_access::AccessError::Expired => Error::AccessExpired,
+            _ => Error::Unauthorized,
+        }
+    }
 }
 
 #[contracttype]
 pub enum DataKey {
+    Admin,
+    PatientRegistry,
+    DocumentExchange,
     StudyCounter,
     CdCounter,
     Study(u64),
@@ -27,6 +40,14 @@ pub enum DataKey {
     CdRecord(u64),
 }
 
+/// Patient-registry hook; in strict mode unregistered patients are rejected
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientRegistryConfig {
+    pub registry: Address,
+    pub strict_mode: bool,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct ImagingStudy {
@@ -1,9 +1,13 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, symbol_short, Address, Bytes, Env, Map, String, Vec,
+    contract, contractevent, contractimpl, contracttype, symbol_short, Address, Bytes, BytesN, Env,
+    IntoVal, Map, String, Symbol, TryFromVal, Val, Vec,
 };
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 /// --------------------
 /// Patient Structures
 /// --------------------
@@ -13,6 +17,28 @@ pub struct PatientData {
     pub name: String,
     pub dob: u64,
     pub metadata: String, // IPFS / encrypted medical refs
+    pub version: u32,
+}
+
+/// Snapshot of PatientData before an update, with who changed which fields
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientVersion {
+    pub previous: PatientData,
+    pub changed_by: Address,
+    pub changed_fields: Vec<Symbol>,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientMerge {
+    pub duplicate: Address,
+    pub survivor: Address,
+    pub merged_by: Address,
+    pub approved_by: Address,
+    pub records_moved: u32,
+    pub timestamp: u64,
 }
 
 /// --------------------
@@ -27,16 +53,98 @@ pub struct DoctorData {
     pub verified: bool,
 }
 
+/// --------------------
+/// Attribution Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareRelationship {
+    pub patient: Address,
+    pub provider: Address,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderPanel {
+    pub provider: Address,
+    pub patients: Vec<Address>,
+    pub active_count: u32,
+}
+
+/// --------------------
+/// Consent Structures
+/// --------------------
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentGrant {
+    pub patient: Address,
+    pub grantee: Address,
+    pub scope: Symbol, // e.g. "research", "imaging_share", "mental_health"
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentGranted {
+    #[topic]
+    pub patient: Address,
+    #[topic]
+    pub scope: Symbol,
+    pub grantee: Address,
+    pub expires_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsentRevoked {
+    #[topic]
+    pub patient: Address,
+    #[topic]
+    pub scope: Symbol,
+    pub grantee: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributionStarted {
+    #[topic]
+    pub patient: Address,
+    #[topic]
+    pub provider: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttributionEnded {
+    #[topic]
+    pub patient: Address,
+    #[topic]
+    pub provider: Address,
+    pub ended_by: Address,
+}
+
 /// --------------------
 /// Storage Keys
 /// --------------------
 #[contracttype]
 pub enum DataKey {
+    Admin,
     Patient(Address),
     Doctor(Address),
     Institution(Address),
     MedicalRecords(Address),
     AuthorizedDoctors(Address),
+    Attribution(Address),
+    AttributionHistory(Address),
+    ProviderPanel(Address),
+    PatientHistory(Address),
+    MergedInto(Address),
+    MergedIdentities(Address),
+    Consent(Address, Symbol, Address),
+    PatientConsents(Address),
 }
 
 #[contracttype]
@@ -53,12 +161,26 @@ pub struct MedicalRegistry;
 
 #[contractimpl]
 impl MedicalRegistry {
+    // =====================================================
+    //                   INITIALIZATION
+    // =====================================================
+
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("Already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+    }
+
     // =====================================================
     //                    PATIENT LOGIC
     // =====================================================
 
     pub fn register_patient(env: Env, wallet: Address, name: String, dob: u64, metadata: String) {
         wallet.require_auth();
+        Self::require_not_merged(&env, &wallet);
 
         let key = DataKey::Patient(wallet.clone());
         if env.storage().persistent().has(&key) {
@@ -69,6 +191,7 @@ impl MedicalRegistry {
             name,
             dob,
             metadata,
+            version: 1,
         };
         env.storage().persistent().set(&key, &patient);
 
@@ -77,7 +200,24 @@ impl MedicalRegistry {
     }
 
     pub fn update_patient(env: Env, wallet: Address, metadata: String) {
-        wallet.require_auth();
+        Self::update_demographics(env, wallet.clone(), wallet, None, None, Some(metadata));
+    }
+
+    /// Versioned update of patient fields by the patient or one of their authorized doctors
+    pub fn update_demographics(
+        env: Env,
+        wallet: Address,
+        caller: Address,
+        name: Option<String>,
+        dob: Option<u64>,
+        metadata: Option<String>,
+    ) {
+        caller.require_auth();
+        Self::require_not_merged(&env, &wallet);
+
+        if caller != wallet && !Self::is_authorized_doctor(&env, &wallet, &caller) {
+            panic!("Unauthorized caller");
+        }
 
         let key = DataKey::Patient(wallet.clone());
         let mut patient: PatientData = env
@@ -85,16 +225,71 @@ impl MedicalRegistry {
             .persistent()
             .get(&key)
             .expect("Patient not found");
+        let previous = patient.clone();
+
+        let mut changed_fields = Vec::new(&env);
+        if let Some(name) = name {
+            if name != patient.name {
+                patient.name = name;
+                changed_fields.push_back(Symbol::new(&env, "name"));
+            }
+        }
+        if let Some(dob) = dob {
+            if dob != patient.dob {
+                patient.dob = dob;
+                changed_fields.push_back(Symbol::new(&env, "dob"));
+            }
+        }
+        if let Some(metadata) = metadata {
+            if metadata != patient.metadata {
+                patient.metadata = metadata;
+                changed_fields.push_back(Symbol::new(&env, "metadata"));
+            }
+        }
+        if changed_fields.is_empty() {
+            return;
+        }
 
-        patient.metadata = metadata;
+        patient.version += 1;
         env.storage().persistent().set(&key, &patient);
 
+        let history_key = DataKey::PatientHistory(wallet.clone());
+        let mut history: Vec<PatientVersion> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(PatientVersion {
+            previous,
+            changed_by: caller,
+            changed_fields,
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&history_key, &history);
+
         env.events()
             .publish((symbol_short!("upd_pat"), wallet), symbol_short!("success"));
     }
 
+    pub fn get_patient_history(
+        env: Env,
+        wallet: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<PatientVersion> {
+        let history = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientHistory(Self::resolve_patient(
+                &env, wallet,
+            )))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&history, offset, limit)
+    }
+
+    /// Lookups for merged duplicates resolve to the surviving record
     pub fn get_patient(env: Env, wallet: Address) -> PatientData {
-        let key = DataKey::Patient(wallet);
+        let key = DataKey::Patient(Self::resolve_patient(&env, wallet));
         env.storage()
             .persistent()
             .get(&key)
@@ -102,10 +297,392 @@ impl MedicalRegistry {
     }
 
     pub fn is_patient_registered(env: Env, wallet: Address) -> bool {
-        let key = DataKey::Patient(wallet);
+        let key = DataKey::Patient(Self::resolve_patient(&env, wallet));
         env.storage().persistent().has(&key)
     }
 
+    // =====================================================
+    //                 IDENTITY LINKING
+    // =====================================================
+
+    /// Merge a duplicate registration into a surviving record. The duplicate's
+    /// medical records, authorized doctors, consents, attribution and
+    /// demographics history move to the survivor and its lookups redirect there.
+    /// The institution and an approver sign; the approver is the surviving
+    /// patient or a verified doctor with access to both records.
+    pub fn merge_patient_records(
+        env: Env,
+        institution_wallet: Address,
+        approver: Address,
+        duplicate: Address,
+        survivor: Address,
+    ) {
+        institution_wallet.require_auth();
+        approver.require_auth();
+
+        let inst_key = DataKey::Institution(institution_wallet.clone());
+        if !env.storage().persistent().has(&inst_key) {
+            panic!("Unauthorized institution");
+        }
+        if approver != survivor && !Self::can_approve_merge(&env, &approver, &duplicate, &survivor)
+        {
+            panic!("Unauthorized approver");
+        }
+        if duplicate == survivor {
+            panic!("Cannot merge a record into itself");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::MergedInto(survivor.clone()))
+        {
+            panic!("Survivor has been merged");
+        }
+
+        let duplicate_key = DataKey::Patient(duplicate.clone());
+        if !env.storage().persistent().has(&duplicate_key) {
+            panic!("Patient not found");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Patient(survivor.clone()))
+        {
+            panic!("Patient not found");
+        }
+
+        let duplicate_records_key = DataKey::MedicalRecords(duplicate.clone());
+        let moved: Vec<MedicalRecord> = env
+            .storage()
+            .persistent()
+            .get(&duplicate_records_key)
+            .unwrap_or(Vec::new(&env));
+        let survivor_records_key = DataKey::MedicalRecords(survivor.clone());
+        let mut records: Vec<MedicalRecord> = env
+            .storage()
+            .persistent()
+            .get(&survivor_records_key)
+            .unwrap_or(Vec::new(&env));
+        records.append(&moved);
+        env.storage()
+            .persistent()
+            .set(&survivor_records_key, &records);
+        env.storage().persistent().remove(&duplicate_records_key);
+
+        // Doctors the duplicate authorized keep their access on the survivor
+        let duplicate_doctors_key = DataKey::AuthorizedDoctors(duplicate.clone());
+        let duplicate_doctors: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&duplicate_doctors_key)
+            .unwrap_or(Map::new(&env));
+        if !duplicate_doctors.is_empty() {
+            let survivor_doctors_key = DataKey::AuthorizedDoctors(survivor.clone());
+            let mut doctors: Map<Address, bool> = env
+                .storage()
+                .persistent()
+                .get(&survivor_doctors_key)
+                .unwrap_or(Map::new(&env));
+            for doctor in duplicate_doctors.keys().iter() {
+                doctors.set(doctor, true);
+            }
+            env.storage()
+                .persistent()
+                .set(&survivor_doctors_key, &doctors);
+        }
+        env.storage().persistent().remove(&duplicate_doctors_key);
+
+        Self::move_consents(&env, &duplicate, &survivor);
+
+        // The duplicate's active attribution is closed; its provider carries
+        // over to the survivor unless the survivor already has one
+        let active: Option<CareRelationship> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attribution(duplicate.clone()));
+        if let Some(relationship) = active {
+            let provider = relationship.provider.clone();
+            Self::close_attribution(&env, relationship, &institution_wallet);
+            if !env
+                .storage()
+                .persistent()
+                .has(&DataKey::Attribution(survivor.clone()))
+            {
+                Self::start_attribution(&env, &survivor, &provider);
+            }
+        }
+        Self::append_history::<CareRelationship>(
+            &env,
+            DataKey::AttributionHistory(duplicate.clone()),
+            DataKey::AttributionHistory(survivor.clone()),
+        );
+        Self::append_history::<PatientVersion>(
+            &env,
+            DataKey::PatientHistory(duplicate.clone()),
+            DataKey::PatientHistory(survivor.clone()),
+        );
+        env.storage().persistent().remove(&duplicate_key);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::MergedInto(duplicate.clone()), &survivor);
+
+        let merged_key = DataKey::MergedIdentities(survivor.clone());
+        let mut merged: Vec<PatientMerge> = env
+            .storage()
+            .persistent()
+            .get(&merged_key)
+            .unwrap_or(Vec::new(&env));
+        merged.push_back(PatientMerge {
+            duplicate,
+            survivor: survivor.clone(),
+            merged_by: institution_wallet,
+            approved_by: approver,
+            records_moved: moved.len(),
+            timestamp: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&merged_key, &merged);
+    }
+
+    /// Surviving address for a patient, or the address itself if never merged
+    pub fn get_canonical_patient(env: Env, wallet: Address) -> Address {
+        Self::resolve_patient(&env, wallet)
+    }
+
+    pub fn get_merged_identities(env: Env, survivor: Address) -> Vec<PatientMerge> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MergedIdentities(survivor))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    // =====================================================
+    //                    CONSENT HUB
+    // =====================================================
+
+    /// Record a named consent scope for a grantee, replacing any existing grant
+    pub fn grant_consent(
+        env: Env,
+        patient: Address,
+        grantee: Address,
+        scope: Symbol,
+        expires_at: Option<u64>,
+    ) {
+        patient.require_auth();
+
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Patient(patient.clone()))
+        {
+            panic!("Patient not found");
+        }
+        let now = env.ledger().timestamp();
+        if expires_at.is_some_and(|expiry| expiry <= now) {
+            panic!("Consent expiry must be in the future");
+        }
+
+        let grant = ConsentGrant {
+            patient: patient.clone(),
+            grantee: grantee.clone(),
+            scope: scope.clone(),
+            granted_at: now,
+            expires_at,
+        };
+        env.storage().persistent().set(
+            &DataKey::Consent(patient.clone(), scope.clone(), grantee.clone()),
+            &grant,
+        );
+
+        let index_key = DataKey::PatientConsents(patient.clone());
+        let mut index: Map<(Symbol, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Map::new(&env));
+        index.set((scope.clone(), grantee.clone()), true);
+        env.storage().persistent().set(&index_key, &index);
+
+        ConsentGranted {
+            patient,
+            scope,
+            grantee,
+            expires_at,
+        }
+        .publish(&env);
+    }
+
+    pub fn revoke_consent(env: Env, patient: Address, grantee: Address, scope: Symbol) {
+        patient.require_auth();
+
+        let key = DataKey::Consent(patient.clone(), scope.clone(), grantee.clone());
+        if !env.storage().persistent().has(&key) {
+            panic!("Consent not found");
+        }
+        env.storage().persistent().remove(&key);
+
+        let index_key = DataKey::PatientConsents(patient.clone());
+        let mut index: Map<(Symbol, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&index_key)
+            .unwrap_or(Map::new(&env));
+        index.remove((scope.clone(), grantee.clone()));
+        env.storage().persistent().set(&index_key, &index);
+
+        ConsentRevoked {
+            patient,
+            scope,
+            grantee,
+        }
+        .publish(&env);
+    }
+
+    /// Whether `patient` has an unexpired consent for `grantee` under `scope`.
+    /// Intended to be called cross-contract by other workspace contracts.
+    pub fn has_consent(env: Env, patient: Address, grantee: Address, scope: Symbol) -> bool {
+        let patient = Self::resolve_patient(&env, patient);
+        let grant: Option<ConsentGrant> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Consent(patient, scope, grantee));
+        let now = env.ledger().timestamp();
+        grant.is_some_and(|g| g.expires_at.is_none_or(|expiry| now < expiry))
+    }
+
+    /// Unexpired consents the patient has granted
+    pub fn get_patient_consents(env: Env, patient: Address) -> Vec<ConsentGrant> {
+        let patient = Self::resolve_patient(&env, patient);
+        let index: Map<(Symbol, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientConsents(patient.clone()))
+            .unwrap_or(Map::new(&env));
+        let now = env.ledger().timestamp();
+
+        let mut consents = Vec::new(&env);
+        for (scope, grantee) in index.keys().iter() {
+            let grant: Option<ConsentGrant> =
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::Consent(patient.clone(), scope, grantee));
+            if let Some(grant) = grant {
+                if grant.expires_at.is_none_or(|expiry| now < expiry) {
+                    consents.push_back(grant);
+                }
+            }
+        }
+        consents
+    }
+
+    /// Follow merge redirects to the surviving record; a survivor may itself
+    /// have been merged away later
+    fn resolve_patient(env: &Env, wallet: Address) -> Address {
+        let mut current = wallet;
+        while let Some(next) = env
+            .storage()
+            .persistent()
+            .get::<_, Address>(&DataKey::MergedInto(current.clone()))
+        {
+            current = next;
+        }
+        current
+    }
+
+    /// Writes must name the surviving record; a merged-away wallet is retired
+    fn require_not_merged(env: &Env, wallet: &Address) {
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::MergedInto(wallet.clone()))
+        {
+            panic!("Patient record has been merged");
+        }
+    }
+
+    /// A verified doctor already authorized on both records may approve a merge
+    fn can_approve_merge(
+        env: &Env,
+        doctor: &Address,
+        duplicate: &Address,
+        survivor: &Address,
+    ) -> bool {
+        let verified = env
+            .storage()
+            .persistent()
+            .get::<_, DoctorData>(&DataKey::Doctor(doctor.clone()))
+            .is_some_and(|d| d.verified);
+        verified
+            && Self::is_authorized_doctor(env, duplicate, doctor)
+            && Self::is_authorized_doctor(env, survivor, doctor)
+    }
+
+    /// Re-key the duplicate's consent grants to the survivor. A grant the
+    /// survivor already holds for the same scope and grantee is kept.
+    fn move_consents(env: &Env, duplicate: &Address, survivor: &Address) {
+        let duplicate_index_key = DataKey::PatientConsents(duplicate.clone());
+        let duplicate_index: Map<(Symbol, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&duplicate_index_key)
+            .unwrap_or(Map::new(env));
+        if duplicate_index.is_empty() {
+            return;
+        }
+
+        let survivor_index_key = DataKey::PatientConsents(survivor.clone());
+        let mut survivor_index: Map<(Symbol, Address), bool> = env
+            .storage()
+            .persistent()
+            .get(&survivor_index_key)
+            .unwrap_or(Map::new(env));
+        for (scope, grantee) in duplicate_index.keys().iter() {
+            let from = DataKey::Consent(duplicate.clone(), scope.clone(), grantee.clone());
+            let grant: Option<ConsentGrant> = env.storage().persistent().get(&from);
+            env.storage().persistent().remove(&from);
+            let Some(mut grant) = grant else {
+                continue;
+            };
+            if survivor_index.contains_key((scope.clone(), grantee.clone())) {
+                continue;
+            }
+            grant.patient = survivor.clone();
+            env.storage().persistent().set(
+                &DataKey::Consent(survivor.clone(), scope.clone(), grantee.clone()),
+                &grant,
+            );
+            survivor_index.set((scope, grantee), true);
+        }
+        env.storage()
+            .persistent()
+            .set(&survivor_index_key, &survivor_index);
+        env.storage().persistent().remove(&duplicate_index_key);
+    }
+
+    /// Move every entry of one history list onto the end of another
+    fn append_history<T>(env: &Env, from: DataKey, to: DataKey)
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        let moved: Option<Vec<T>> = env.storage().persistent().get(&from);
+        let Some(moved) = moved else {
+            return;
+        };
+        let mut history: Vec<T> = env.storage().persistent().get(&to).unwrap_or(Vec::new(env));
+        history.append(&moved);
+        env.storage().persistent().set(&to, &history);
+        env.storage().persistent().remove(&from);
+    }
+
+    fn is_authorized_doctor(env: &Env, patient: &Address, doctor: &Address) -> bool {
+        let access_map: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AuthorizedDoctors(patient.clone()))
+            .unwrap_or(Map::new(env));
+        access_map.contains_key(doctor.clone())
+    }
+
     // =====================================================
     //                    DOCTOR LOGIC
     // =====================================================
@@ -173,8 +750,9 @@ impl MedicalRegistry {
     //              INSTITUTION MANAGEMENT
     // =====================================================
 
-    pub fn register_institution(env: Env, institution_wallet: Address) {
-        institution_wallet.require_auth();
+    /// Institutions can verify doctors and merge records, so only the admin adds them
+    pub fn register_institution(env: Env, admin: Address, institution_wallet: Address) {
+        Self::require_admin(&env, &admin);
         let key = DataKey::Institution(institution_wallet);
         env.storage().persistent().set(&key, &true);
     }
@@ -185,6 +763,7 @@ impl MedicalRegistry {
 
     pub fn grant_access(env: Env, patient: Address, doctor: Address) {
         patient.require_auth();
+        Self::require_not_merged(&env, &patient);
 
         let key = DataKey::AuthorizedDoctors(patient.clone());
         let mut map: Map<Address, bool> = env
@@ -199,6 +778,7 @@ impl MedicalRegistry {
 
     pub fn revoke_access(env: Env, patient: Address, doctor: Address) {
         patient.require_auth();
+        Self::require_not_merged(&env, &patient);
 
         let key = DataKey::AuthorizedDoctors(patient.clone());
         let mut map: Map<Address, bool> = env
@@ -212,7 +792,7 @@ impl MedicalRegistry {
     }
 
     pub fn get_authorized_doctors(env: Env, patient: Address) -> Vec<Address> {
-        let key = DataKey::AuthorizedDoctors(patient);
+        let key = DataKey::AuthorizedDoctors(Self::resolve_patient(&env, patient));
         let map: Map<Address, bool> = env
             .storage()
             .persistent()
@@ -232,14 +812,8 @@ impl MedicalRegistry {
         doctor.require_auth();
 
         // Check access
-        let access_key = DataKey::AuthorizedDoctors(patient.clone());
-        let access_map: Map<Address, bool> = env
-            .storage()
-            .persistent()
-            .get(&access_key)
-            .unwrap_or(Map::new(&env));
-
-        if !access_map.contains_key(doctor.clone()) {
+        let patient = Self::resolve_patient(&env, patient);
+        if !Self::is_authorized_doctor(&env, &patient, &doctor) {
             panic!("Doctor not authorized");
         }
 
@@ -250,7 +824,7 @@ impl MedicalRegistry {
             timestamp: env.ledger().timestamp(),
         };
 
-        let records_key = DataKey::MedicalRecords(patient.clone());
+        let records_key = DataKey::MedicalRecords(patient);
         let mut records: Vec<MedicalRecord> = env
             .storage()
             .persistent()
@@ -261,12 +835,219 @@ impl MedicalRegistry {
         env.storage().persistent().set(&records_key, &records);
     }
 
-    pub fn get_medical_records(env: Env, patient: Address) -> Vec<MedicalRecord> {
-        let key = DataKey::MedicalRecords(patient);
-        env.storage()
+    pub fn get_medical_records(
+        env: Env,
+        patient: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<MedicalRecord> {
+        let key = DataKey::MedicalRecords(Self::resolve_patient(&env, patient));
+        let records = env
+            .storage()
             .persistent()
             .get(&key)
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&records, offset, limit)
+    }
+
+    // =====================================================
+    //              PROVIDER ATTRIBUTION
+    // =====================================================
+
+    /// Link a patient to a primary-care provider; both parties sign
+    pub fn link_primary_provider(env: Env, patient: Address, provider: Address) {
+        patient.require_auth();
+        provider.require_auth();
+        Self::require_not_merged(&env, &patient);
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Attribution(patient.clone()))
+        {
+            panic!("Patient already attributed");
+        }
+        Self::start_attribution(&env, &patient, &provider);
+    }
+
+    /// Move a patient to a new primary-care provider; patient and new provider sign
+    pub fn transfer_attribution(env: Env, patient: Address, new_provider: Address) {
+        patient.require_auth();
+        new_provider.require_auth();
+        Self::require_not_merged(&env, &patient);
+
+        let current: CareRelationship = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attribution(patient.clone()))
+            .expect("No active attribution");
+        if current.provider == new_provider {
+            panic!("Provider already attributed");
+        }
+
+        Self::close_attribution(&env, current, &patient);
+        Self::start_attribution(&env, &patient, &new_provider);
+    }
+
+    /// End the active relationship; either the patient or the provider may end it
+    pub fn end_relationship(env: Env, patient: Address, caller: Address) {
+        caller.require_auth();
+
+        let current: CareRelationship = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Attribution(patient.clone()))
+            .expect("No active attribution");
+        if caller != patient && caller != current.provider {
+            panic!("Unauthorized caller");
+        }
+
+        Self::close_attribution(&env, current, &caller);
+    }
+
+    pub fn get_primary_provider(env: Env, patient: Address) -> Option<CareRelationship> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attribution(Self::resolve_patient(&env, patient)))
+    }
+
+    pub fn get_attribution_history(
+        env: Env,
+        patient: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<CareRelationship> {
+        let history = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AttributionHistory(Self::resolve_patient(
+                &env, patient,
+            )))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&history, offset, limit)
+    }
+
+    pub fn get_provider_panel(env: Env, provider: Address) -> ProviderPanel {
+        let panel: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ProviderPanel(provider.clone()))
+            .unwrap_or(Map::new(&env));
+
+        ProviderPanel {
+            provider,
+            active_count: panel.len(),
+            patients: panel.keys(),
+        }
+    }
+
+    fn start_attribution(env: &Env, patient: &Address, provider: &Address) {
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Patient(patient.clone()))
+        {
+            panic!("Patient not found");
+        }
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Doctor(provider.clone()))
+        {
+            panic!("Doctor not found");
+        }
+
+        let relationship = CareRelationship {
+            patient: patient.clone(),
+            provider: provider.clone(),
+            started_at: env.ledger().timestamp(),
+            ended_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Attribution(patient.clone()), &relationship);
+
+        let panel_key = DataKey::ProviderPanel(provider.clone());
+        let mut panel: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&panel_key)
+            .unwrap_or(Map::new(env));
+        panel.set(patient.clone(), true);
+        env.storage().persistent().set(&panel_key, &panel);
+
+        AttributionStarted {
+            patient: patient.clone(),
+            provider: provider.clone(),
+        }
+        .publish(env);
+    }
+
+    fn close_attribution(env: &Env, mut relationship: CareRelationship, ended_by: &Address) {
+        let patient = relationship.patient.clone();
+        let provider = relationship.provider.clone();
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Attribution(patient.clone()));
+
+        let panel_key = DataKey::ProviderPanel(provider.clone());
+        let mut panel: Map<Address, bool> = env
+            .storage()
+            .persistent()
+            .get(&panel_key)
+            .unwrap_or(Map::new(env));
+        panel.remove(patient.clone());
+        env.storage().persistent().set(&panel_key, &panel);
+
+        relationship.ended_at = Some(env.ledger().timestamp());
+        let history_key = DataKey::AttributionHistory(patient.clone());
+        let mut history: Vec<CareRelationship> = env
+            .storage()
+            .persistent()
+            .get(&history_key)
+            .unwrap_or(Vec::new(env));
+        history.push_back(relationship);
+        env.storage().persistent().set(&history_key, &history);
+
+        AttributionEnded {
+            patient,
+            provider,
+            ended_by: ended_by.clone(),
+        }
+        .publish(env);
+    }
+
+    // =====================================================
+    //                      UPGRADES
+    // =====================================================
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> u32 {
+        Self::require_admin(&env, &admin);
+        shared_upgrade::migrate(&env, SCHEMA_VERSION)
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .unwrap_or_else(|| panic!("Not initialized"));
+        if stored != *admin {
+            panic!("Unauthorized admin");
+        }
     }
 }
 #[cfg(test)]
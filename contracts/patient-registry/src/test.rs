@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Bytes, Env, String};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, Bytes, Env, String, Symbol,
+};
 
 /// ------------------------------------------------
 /// PATIENT TESTS
@@ -48,6 +51,82 @@ fn test_update_patient() {
 
     let patient_data = client.get_patient(&patient_wallet);
     assert_eq!(patient_data.metadata, new_metadata);
+    assert_eq!(patient_data.version, 2);
+
+    let history = client.get_patient_history(&patient_wallet, &0, &10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().previous.metadata, initial_metadata);
+}
+
+#[test]
+fn test_update_demographics_by_authorized_doctor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.register_patient(
+        &patient,
+        &String::from_str(&env, "Jon Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    client.grant_access(&patient, &doctor);
+
+    let corrected = String::from_str(&env, "John Doe");
+    client.update_demographics(
+        &patient,
+        &doctor,
+        &Some(corrected.clone()),
+        &Some(631152000),
+        &None,
+    );
+
+    let patient_data = client.get_patient(&patient);
+    assert_eq!(patient_data.name, corrected);
+    assert_eq!(patient_data.version, 2);
+
+    // Only the name actually changed
+    let change = client
+        .get_patient_history(&patient, &0, &10)
+        .get(0)
+        .unwrap();
+    assert_eq!(change.changed_by, doctor);
+    assert_eq!(change.changed_fields.len(), 1);
+    assert_eq!(
+        change.changed_fields.get(0).unwrap(),
+        Symbol::new(&env, "name")
+    );
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized caller")]
+fn test_update_demographics_by_stranger_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    client.register_patient(
+        &patient,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    client.update_demographics(
+        &patient,
+        &Address::generate(&env),
+        &Some(String::from_str(&env, "Someone Else")),
+        &None,
+        &None,
+    );
 }
 
 #[test]
@@ -120,7 +199,9 @@ fn test_register_institution_and_verify_doctor() {
     client.register_doctor(&doctor_wallet, &name, &specialization, &cert_hash);
 
     // Register institution
-    client.register_institution(&institution_wallet);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution_wallet);
 
     // Verify doctor
     client.verify_doctor(&doctor_wallet, &institution_wallet);
@@ -168,7 +249,7 @@ fn test_grant_access_and_add_medical_record() {
     client.grant_access(&patient, &doctor);
     client.add_medical_record(&patient, &doctor, &hash, &desc);
 
-    let records = client.get_medical_records(&patient);
+    let records = client.get_medical_records(&patient, &0, &10);
     assert_eq!(records.len(), 1);
 
     let record = records.get(0).unwrap();
@@ -211,3 +292,551 @@ fn test_revoke_access() {
     let doctors = client.get_authorized_doctors(&patient);
     assert_eq!(doctors.len(), 0);
 }
+
+// ------------------------------------------------
+// PROVIDER ATTRIBUTION TESTS
+// ------------------------------------------------
+
+fn register_patient_and_doctor(
+    env: &Env,
+    client: &MedicalRegistryClient,
+    patient: &Address,
+    doctor: &Address,
+) {
+    client.register_patient(
+        patient,
+        &String::from_str(env, "Jane Doe"),
+        &631152000,
+        &String::from_str(env, "ipfs://data"),
+    );
+    client.register_doctor(
+        doctor,
+        &String::from_str(env, "Dr. Grey"),
+        &String::from_str(env, "Family Medicine"),
+        &Bytes::from_array(env, &[1]),
+    );
+}
+
+#[test]
+fn test_attribution_link_transfer_and_panel() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let second_doctor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &patient, &doctor);
+    client.register_doctor(
+        &second_doctor,
+        &String::from_str(&env, "Dr. Shepherd"),
+        &String::from_str(&env, "Internal Medicine"),
+        &Bytes::from_array(&env, &[2]),
+    );
+
+    client.link_primary_provider(&patient, &doctor);
+    assert_eq!(
+        client.get_primary_provider(&patient).unwrap().provider,
+        doctor
+    );
+    assert_eq!(client.get_provider_panel(&doctor).active_count, 1);
+
+    client.transfer_attribution(&patient, &second_doctor);
+    assert_eq!(client.get_provider_panel(&doctor).active_count, 0);
+    let panel = client.get_provider_panel(&second_doctor);
+    assert_eq!(panel.active_count, 1);
+    assert_eq!(panel.patients.get(0).unwrap(), patient);
+
+    client.end_relationship(&patient, &second_doctor);
+    assert!(client.get_primary_provider(&patient).is_none());
+    assert_eq!(client.get_provider_panel(&second_doctor).active_count, 0);
+
+    let history = client.get_attribution_history(&patient, &0, &10);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(0).unwrap().provider, doctor);
+    assert!(history.get(1).unwrap().ended_at.is_some());
+}
+
+#[test]
+#[should_panic(expected = "Patient already attributed")]
+fn test_double_attribution_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &patient, &doctor);
+    client.link_primary_provider(&patient, &doctor);
+    client.link_primary_provider(&patient, &doctor);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized caller")]
+fn test_third_party_cannot_end_relationship() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let doctor = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &patient, &doctor);
+    client.link_primary_provider(&patient, &doctor);
+    client.end_relationship(&patient, &Address::generate(&env));
+}
+
+/// ------------------------------------------------
+/// IDENTITY LINKING TESTS
+/// ------------------------------------------------
+
+#[test]
+fn test_merge_duplicate_patient_records() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    for wallet in [&survivor, &duplicate] {
+        client.register_patient(
+            wallet,
+            &String::from_str(&env, "Jane Doe"),
+            &631152000,
+            &String::from_str(&env, "ipfs://data"),
+        );
+    }
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.grant_access(&duplicate, &doctor);
+    client.add_medical_record(
+        &duplicate,
+        &doctor,
+        &Bytes::from_array(&env, &[4, 2]),
+        &String::from_str(&env, "Lipid panel"),
+    );
+
+    client.merge_patient_records(&institution, &survivor, &duplicate, &survivor);
+
+    assert_eq!(client.get_canonical_patient(&duplicate), survivor);
+    assert_eq!(
+        client.get_patient(&duplicate),
+        client.get_patient(&survivor)
+    );
+    assert_eq!(client.get_medical_records(&survivor, &0, &10).len(), 1);
+    assert_eq!(client.get_medical_records(&duplicate, &0, &10).len(), 1);
+
+    let merges = client.get_merged_identities(&survivor);
+    assert_eq!(merges.len(), 1);
+    assert_eq!(merges.get(0).unwrap().records_moved, 1);
+}
+
+#[test]
+fn test_merge_moves_access_attribution_and_history() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &duplicate, &doctor);
+    client.register_patient(
+        &survivor,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.grant_access(&duplicate, &doctor);
+    client.link_primary_provider(&duplicate, &doctor);
+    client.update_patient(&duplicate, &String::from_str(&env, "ipfs://updated"));
+
+    client.merge_patient_records(&institution, &survivor, &duplicate, &survivor);
+
+    // Access follows the survivor, including writes through the old address
+    assert_eq!(client.get_authorized_doctors(&survivor).len(), 1);
+    client.add_medical_record(
+        &duplicate,
+        &doctor,
+        &Bytes::from_array(&env, &[7]),
+        &String::from_str(&env, "Follow-up"),
+    );
+    assert_eq!(client.get_medical_records(&survivor, &0, &10).len(), 1);
+
+    // The provider panel lists the survivor instead of the duplicate
+    assert_eq!(
+        client.get_primary_provider(&survivor).unwrap().provider,
+        doctor
+    );
+    let panel = client.get_provider_panel(&doctor);
+    assert_eq!(panel.active_count, 1);
+    assert_eq!(panel.patients.get(0).unwrap(), survivor);
+
+    let attribution = client.get_attribution_history(&survivor, &0, &10);
+    assert_eq!(attribution.len(), 1);
+    assert_eq!(attribution.get(0).unwrap().patient, duplicate);
+    assert_eq!(client.get_patient_history(&survivor, &0, &10).len(), 1);
+    assert_eq!(client.get_patient_history(&duplicate, &0, &10).len(), 1);
+}
+
+#[test]
+fn test_chained_merges_resolve_to_final_survivor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+    let third = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    for wallet in [&first, &second, &third] {
+        client.register_patient(
+            wallet,
+            &String::from_str(&env, "Jane Doe"),
+            &631152000,
+            &String::from_str(&env, "ipfs://data"),
+        );
+    }
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+
+    client.merge_patient_records(&institution, &second, &first, &second);
+    client.merge_patient_records(&institution, &third, &second, &third);
+
+    assert_eq!(client.get_canonical_patient(&first), third);
+    assert!(client.is_patient_registered(&first));
+    assert_eq!(client.get_patient(&first), client.get_patient(&third));
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized institution")]
+fn test_merge_by_unregistered_institution_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    let survivor = Address::generate(&env);
+    client.merge_patient_records(
+        &Address::generate(&env),
+        &survivor,
+        &Address::generate(&env),
+        &survivor,
+    );
+}
+
+#[test]
+fn test_merge_approved_by_doctor_with_access_to_both_records() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &duplicate, &doctor);
+    client.register_patient(
+        &survivor,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.verify_doctor(&doctor, &institution);
+    client.grant_access(&duplicate, &doctor);
+    client.grant_access(&survivor, &doctor);
+
+    client.merge_patient_records(&institution, &doctor, &duplicate, &survivor);
+
+    let merge = client.get_merged_identities(&survivor).get(0).unwrap();
+    assert_eq!(merge.approved_by, doctor);
+    assert_eq!(merge.merged_by, institution);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized approver")]
+fn test_merge_approved_by_doctor_without_access_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let doctor = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    register_patient_and_doctor(&env, &client, &duplicate, &doctor);
+    client.register_patient(
+        &survivor,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.verify_doctor(&doctor, &institution);
+    client.grant_access(&duplicate, &doctor);
+
+    client.merge_patient_records(&institution, &doctor, &duplicate, &survivor);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized admin")]
+fn test_register_institution_by_stranger_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+
+    client.initialize(&Address::generate(&env));
+    let institution = Address::generate(&env);
+    client.register_institution(&institution, &institution);
+}
+
+#[test]
+#[should_panic(expected = "Patient record has been merged")]
+fn test_grant_access_on_merged_wallet_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    for wallet in [&survivor, &duplicate] {
+        client.register_patient(
+            wallet,
+            &String::from_str(&env, "Jane Doe"),
+            &631152000,
+            &String::from_str(&env, "ipfs://data"),
+        );
+    }
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.merge_patient_records(&institution, &survivor, &duplicate, &survivor);
+
+    client.grant_access(&duplicate, &Address::generate(&env));
+}
+
+#[test]
+#[should_panic(expected = "Patient record has been merged")]
+fn test_reregister_merged_wallet_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let institution = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    for wallet in [&survivor, &duplicate] {
+        client.register_patient(
+            wallet,
+            &String::from_str(&env, "Jane Doe"),
+            &631152000,
+            &String::from_str(&env, "ipfs://data"),
+        );
+    }
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.merge_patient_records(&institution, &survivor, &duplicate, &survivor);
+
+    client.register_patient(
+        &duplicate,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+}
+
+/// ------------------------------------------------
+/// CONSENT HUB TESTS
+/// ------------------------------------------------
+
+#[test]
+fn test_consent_grant_expiry_and_revoke() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+    let researcher = Address::generate(&env);
+    let imaging_center = Address::generate(&env);
+    let research = Symbol::new(&env, "research");
+    let imaging = Symbol::new(&env, "imaging_share");
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    client.register_patient(
+        &patient,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+
+    client.grant_consent(&patient, &researcher, &research, &Some(2_000));
+    client.grant_consent(&patient, &imaging_center, &imaging, &None);
+
+    assert!(client.has_consent(&patient, &researcher, &research));
+    assert!(!client.has_consent(&patient, &researcher, &imaging));
+    assert_eq!(client.get_patient_consents(&patient).len(), 2);
+
+    env.ledger().set_timestamp(2_000);
+    assert!(!client.has_consent(&patient, &researcher, &research));
+    assert_eq!(client.get_patient_consents(&patient).len(), 1);
+
+    client.revoke_consent(&patient, &imaging_center, &imaging);
+    assert!(!client.has_consent(&patient, &imaging_center, &imaging));
+    assert!(client.get_patient_consents(&patient).is_empty());
+}
+
+#[test]
+fn test_merge_carries_consents_to_survivor() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let survivor = Address::generate(&env);
+    let duplicate = Address::generate(&env);
+    let researcher = Address::generate(&env);
+    let institution = Address::generate(&env);
+    let research = Symbol::new(&env, "research");
+
+    env.mock_all_auths();
+
+    for wallet in [&survivor, &duplicate] {
+        client.register_patient(
+            wallet,
+            &String::from_str(&env, "Jane Doe"),
+            &631152000,
+            &String::from_str(&env, "ipfs://data"),
+        );
+    }
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_institution(&admin, &institution);
+    client.grant_consent(&duplicate, &researcher, &research, &None);
+
+    client.merge_patient_records(&institution, &survivor, &duplicate, &survivor);
+
+    assert!(client.has_consent(&survivor, &researcher, &research));
+    assert!(client.has_consent(&duplicate, &researcher, &research));
+    let consents = client.get_patient_consents(&survivor);
+    assert_eq!(consents.len(), 1);
+    assert_eq!(consents.get(0).unwrap().patient, survivor);
+
+    client.revoke_consent(&survivor, &researcher, &research);
+    assert!(!client.has_consent(&duplicate, &researcher, &research));
+}
+
+#[test]
+#[should_panic(expected = "Consent expiry must be in the future")]
+fn test_consent_with_past_expiry_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+
+    let patient = Address::generate(&env);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    client.register_patient(
+        &patient,
+        &String::from_str(&env, "Jane Doe"),
+        &631152000,
+        &String::from_str(&env, "ipfs://data"),
+    );
+    client.grant_consent(
+        &patient,
+        &Address::generate(&env),
+        &Symbol::new(&env, "research"),
+        &Some(500),
+    );
+}
+
+// ------------------------------------------------
+// UPGRADE TESTS
+// ------------------------------------------------
+
+#[test]
+fn test_initialize_records_schema_version() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    assert_eq!(client.schema_version(), 0);
+    client.initialize(&admin);
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+}
+
+#[test]
+#[should_panic(expected = "Already initialized")]
+fn test_initialize_twice_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.initialize(&admin);
+}
+
+#[test]
+#[should_panic(expected = "Unauthorized admin")]
+fn test_migrate_by_stranger_should_fail() {
+    let env = Env::default();
+    let contract_id = env.register(MedicalRegistry, ());
+    let client = MedicalRegistryClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    client.initialize(&Address::generate(&env));
+    client.migrate(&Address::generate(&env));
+}
@@ -1,9 +1,9 @@
 #![cfg(test)]
 
 use super::*;
-use crate::contract::{PatientVitalsContract, PatientVitalsContractClient};
-use crate::types::{AlertThresholds, DeviceReading, Range, VitalSigns, VitalStatistics};
-use soroban_sdk::{testutils::Address as _, Address, Env, String, Symbol, Vec};
+use crate::contract::{PatientVitalsContract, PatientVitalsContractClient, SCHEMA_VERSION};
+use crate::types::{AlertThresholds, DeviceReading, Error, Range, VitalSigns, VitalStatistics};
+use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
 
 #[test]
 fn test_record_vital_signs() {
@@ -170,3 +170,28 @@ fn test_calculate_vital_statistics() {
     assert_eq!(stats.max_value, 90);
     assert_eq!(stats.average_value, 85);
 }
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PatientVitalsContract, ());
+    let client = PatientVitalsContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    assert_eq!(client.try_migrate(&admin), Err(Ok(Error::NotInitialized)));
+    client.initialize(&admin);
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&stranger), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -7,6 +7,8 @@ pub enum Error {
     Unauthorized = 1,
     NotFound = 2,
     InvalidParameter = 3,
+    AlreadyInitialized = 4,
+    NotInitialized = 5,
 }
 
 #[contracttype]
@@ -50,6 +52,7 @@ pub struct VitalStatistics {
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
+    Admin,
     VitalsHistory(Address),            // map to Vec<VitalReading>
     MonitoringParams(Address, Symbol), // map to MonitoringParameters
     DeviceReg(Address, String),        // map to DeviceRegistration
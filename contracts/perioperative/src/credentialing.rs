@@ -0,0 +1,16 @@
+use soroban_sdk::{contractclient, Address, Env, String, Symbol};
+
+/// Subset of the healthcare-credentialing interface used to report operative
+/// cases as clinical activity for privilege review.
+#[contractclient(name = "CredentialingClient")]
+pub trait Credentialing {
+    fn track_clinical_activity(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+        procedure_code: String,
+        outcome: Symbol,
+        complications: bool,
+        activity_date: u64,
+    );
+}
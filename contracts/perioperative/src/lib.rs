@@ -0,0 +1,448 @@
+#![no_std]
+
+pub mod credentialing;
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use credentialing::CredentialingClient;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct PerioperativeContract;
+
+#[contractimpl]
+impl PerioperativeContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(
+        env: Env,
+        admin: Address,
+        facility_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Facility(facility_id), &name);
+        Ok(())
+    }
+
+    /// Closed cases are reported to this credentialing contract as clinical activity.
+    pub fn set_credentialing_contract(
+        env: Env,
+        admin: Address,
+        contract_id: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Credentialing, &contract_id);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Scheduling
+    // -------------------------------------------------------------------
+
+    pub fn request_case(
+        env: Env,
+        surgeon_id: Address,
+        facility_id: Address,
+        request: CaseRequest,
+    ) -> Result<u64, Error> {
+        surgeon_id.require_auth();
+        if !is_facility(&env, &facility_id) {
+            return Err(Error::FacilityNotRegistered);
+        }
+        if request.estimated_minutes == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let case = SurgicalCase {
+            case_id: next_case_id(&env),
+            facility_id,
+            patient_id: request.patient_id,
+            surgeon_id: surgeon_id.clone(),
+            procedure_code: request.procedure_code,
+            anesthesia: request.anesthesia,
+            estimated_minutes: request.estimated_minutes,
+            requested_at: env.ledger().timestamp(),
+            room: None,
+            scheduled_start: 0,
+            status: CaseStatus::Requested,
+            started_at: 0,
+            completed_at: 0,
+            operative_record_hash: None,
+        };
+        save_case(&env, &case);
+        push_id(&env, DataKey::SurgeonCases(surgeon_id), case.case_id);
+        publish_status(&env, &case);
+
+        Ok(case.case_id)
+    }
+
+    /// Book an operating room. Rejected if it overlaps another active case in the room.
+    pub fn schedule_case(
+        env: Env,
+        facility_id: Address,
+        case_id: u64,
+        room: Symbol,
+        scheduled_start: u64,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+        let mut case = load_case(&env, case_id).ok_or(Error::CaseNotFound)?;
+        if case.facility_id != facility_id {
+            return Err(Error::Unauthorized);
+        }
+        if case.status != CaseStatus::Requested && case.status != CaseStatus::Scheduled {
+            return Err(Error::InvalidState);
+        }
+        if scheduled_start < env.ledger().timestamp() {
+            return Err(Error::InvalidInput);
+        }
+
+        let end = case_end(scheduled_start, case.estimated_minutes);
+        for other_id in room_cases(&env, &facility_id, &room).iter() {
+            if other_id == case_id {
+                continue;
+            }
+            if let Some(other) = load_case(&env, other_id) {
+                let booked = matches!(other.status, CaseStatus::Scheduled | CaseStatus::InProgress)
+                    && other.room == Some(room.clone());
+                if booked
+                    && scheduled_start < case_end(other.scheduled_start, other.estimated_minutes)
+                    && other.scheduled_start < end
+                {
+                    return Err(Error::RoomConflict);
+                }
+            }
+        }
+
+        if case.room != Some(room.clone()) {
+            push_id(&env, DataKey::RoomCases(facility_id, room.clone()), case_id);
+        }
+        case.room = Some(room);
+        case.scheduled_start = scheduled_start;
+        case.status = CaseStatus::Scheduled;
+        save_case(&env, &case);
+        publish_status(&env, &case);
+        Ok(())
+    }
+
+    /// Cancel a case that has not started. Either the surgeon or the facility may cancel.
+    pub fn cancel_case(env: Env, caller: Address, case_id: u64) -> Result<(), Error> {
+        caller.require_auth();
+        let mut case = load_case(&env, case_id).ok_or(Error::CaseNotFound)?;
+        if caller != case.surgeon_id && caller != case.facility_id {
+            return Err(Error::Unauthorized);
+        }
+        if case.status != CaseStatus::Requested && case.status != CaseStatus::Scheduled {
+            return Err(Error::InvalidState);
+        }
+        case.status = CaseStatus::Cancelled;
+        save_case(&env, &case);
+        publish_status(&env, &case);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Day of surgery
+    // -------------------------------------------------------------------
+
+    pub fn attest_preop_checklist(
+        env: Env,
+        clinician: Address,
+        case_id: u64,
+        consent_verified: bool,
+        site_marked: bool,
+        antibiotics_given: bool,
+    ) -> Result<(), Error> {
+        clinician.require_auth();
+        let case = load_case(&env, case_id).ok_or(Error::CaseNotFound)?;
+        if case.status != CaseStatus::Scheduled {
+            return Err(Error::InvalidState);
+        }
+        let checklist = PreOpChecklist {
+            consent_verified,
+            site_marked,
+            antibiotics_given,
+            attested_by: clinician,
+            attested_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Checklist(case_id), &checklist);
+        Ok(())
+    }
+
+    /// Start the case. Every pre-op checklist item must have been confirmed.
+    pub fn start_case(env: Env, surgeon_id: Address, case_id: u64) -> Result<(), Error> {
+        let mut case = load_surgeon_case(&env, &surgeon_id, case_id)?;
+        if case.status != CaseStatus::Scheduled {
+            return Err(Error::InvalidState);
+        }
+        let checklist: PreOpChecklist = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Checklist(case_id))
+            .ok_or(Error::ChecklistIncomplete)?;
+        if !(checklist.consent_verified && checklist.site_marked && checklist.antibiotics_given) {
+            return Err(Error::ChecklistIncomplete);
+        }
+
+        case.status = CaseStatus::InProgress;
+        case.started_at = env.ledger().timestamp();
+        save_case(&env, &case);
+        publish_status(&env, &case);
+        Ok(())
+    }
+
+    pub fn record_operative_record(
+        env: Env,
+        surgeon_id: Address,
+        case_id: u64,
+        record_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut case = load_surgeon_case(&env, &surgeon_id, case_id)?;
+        if case.status != CaseStatus::InProgress && case.status != CaseStatus::Completed {
+            return Err(Error::InvalidState);
+        }
+        case.operative_record_hash = Some(record_hash);
+        save_case(&env, &case);
+        Ok(())
+    }
+
+    /// Sponge, sharps and instrument counts. Attested by someone other than
+    /// the operating surgeon.
+    pub fn attest_counts(
+        env: Env,
+        clinician: Address,
+        case_id: u64,
+        counts_correct: bool,
+    ) -> Result<(), Error> {
+        clinician.require_auth();
+        let case = load_case(&env, case_id).ok_or(Error::CaseNotFound)?;
+        if clinician == case.surgeon_id {
+            return Err(Error::Unauthorized);
+        }
+        if case.status != CaseStatus::InProgress {
+            return Err(Error::InvalidState);
+        }
+        let attestation = CountAttestation {
+            counts_correct,
+            attested_by: clinician,
+            attested_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Counts(case_id), &attestation);
+        Ok(())
+    }
+
+    /// End the case. Requires the operative record and a counts-correct attestation.
+    pub fn complete_case(env: Env, surgeon_id: Address, case_id: u64) -> Result<(), Error> {
+        let mut case = load_surgeon_case(&env, &surgeon_id, case_id)?;
+        if case.status != CaseStatus::InProgress {
+            return Err(Error::InvalidState);
+        }
+        if case.operative_record_hash.is_none() {
+            return Err(Error::OperativeRecordMissing);
+        }
+        let counts: Option<CountAttestation> =
+            env.storage().persistent().get(&DataKey::Counts(case_id));
+        if !counts.is_some_and(|c| c.counts_correct) {
+            return Err(Error::CountsNotReconciled);
+        }
+
+        case.status = CaseStatus::Completed;
+        case.completed_at = env.ledger().timestamp();
+        save_case(&env, &case);
+        publish_status(&env, &case);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Post-op
+    // -------------------------------------------------------------------
+
+    pub fn record_complication(
+        env: Env,
+        reporter: Address,
+        case_id: u64,
+        code: String,
+        severity: Symbol,
+        description: String,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+        let case = load_case(&env, case_id).ok_or(Error::CaseNotFound)?;
+        if reporter != case.surgeon_id && reporter != case.facility_id {
+            return Err(Error::Unauthorized);
+        }
+        if case.status != CaseStatus::InProgress && case.status != CaseStatus::Completed {
+            return Err(Error::InvalidState);
+        }
+
+        let key = DataKey::Complications(case_id);
+        let mut list: Vec<Complication> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        list.push_back(Complication {
+            code,
+            severity: severity.clone(),
+            description,
+            reported_by: reporter,
+            reported_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &list);
+
+        ComplicationReported { case_id, severity }.publish(&env);
+        Ok(())
+    }
+
+    /// Close the post-op follow-up window and report the case, with whether
+    /// any complication was recorded, to credentialing as clinical activity.
+    pub fn close_case(
+        env: Env,
+        surgeon_id: Address,
+        case_id: u64,
+        outcome: Symbol,
+    ) -> Result<(), Error> {
+        let mut case = load_surgeon_case(&env, &surgeon_id, case_id)?;
+        if case.status != CaseStatus::Completed {
+            return Err(Error::InvalidState);
+        }
+        case.status = CaseStatus::Closed;
+        save_case(&env, &case);
+        publish_status(&env, &case);
+
+        let credentialing: Option<Address> = env.storage().instance().get(&DataKey::Credentialing);
+        if let Some(contract_id) = credentialing {
+            let had_complications = !Self::get_complications(env.clone(), case_id).is_empty();
+            CredentialingClient::new(&env, &contract_id).track_clinical_activity(
+                &case.surgeon_id,
+                &case.facility_id,
+                &case.procedure_code,
+                &outcome,
+                &had_complications,
+                &case.completed_at,
+            );
+        }
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Queries
+    // -------------------------------------------------------------------
+
+    pub fn get_case(env: Env, case_id: u64) -> Result<SurgicalCase, Error> {
+        load_case(&env, case_id).ok_or(Error::CaseNotFound)
+    }
+
+    pub fn get_checklist(env: Env, case_id: u64) -> Option<PreOpChecklist> {
+        env.storage().persistent().get(&DataKey::Checklist(case_id))
+    }
+
+    pub fn get_count_attestation(env: Env, case_id: u64) -> Option<CountAttestation> {
+        env.storage().persistent().get(&DataKey::Counts(case_id))
+    }
+
+    pub fn get_complications(env: Env, case_id: u64) -> Vec<Complication> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Complications(case_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_surgeon_cases(env: Env, surgeon_id: Address, offset: u32, limit: u32) -> Vec<u64> {
+        let cases = id_list(&env, &DataKey::SurgeonCases(surgeon_id));
+        shared_pagination::paginate(&cases, offset, limit)
+    }
+
+    pub fn get_room_schedule(env: Env, facility_id: Address, room: Symbol) -> Vec<SurgicalCase> {
+        let mut cases = Vec::new(&env);
+        for id in room_cases(&env, &facility_id, &room).iter() {
+            if let Some(case) = load_case(&env, id) {
+                if case.status == CaseStatus::Scheduled && case.room == Some(room.clone()) {
+                    cases.push_back(case);
+                }
+            }
+        }
+        cases
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_surgeon_case(env: &Env, surgeon_id: &Address, case_id: u64) -> Result<SurgicalCase, Error> {
+    surgeon_id.require_auth();
+    let case = load_case(env, case_id).ok_or(Error::CaseNotFound)?;
+    if case.surgeon_id != *surgeon_id {
+        return Err(Error::Unauthorized);
+    }
+    Ok(case)
+}
+
+fn case_end(start: u64, estimated_minutes: u32) -> u64 {
+    start.saturating_add(u64::from(estimated_minutes) * 60)
+}
+
+fn publish_status(env: &Env, case: &SurgicalCase) {
+    CaseStatusChanged {
+        case_id: case.case_id,
+        status: case.status,
+    }
+    .publish(env);
+}
@@ -0,0 +1,56 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{DataKey, SurgicalCase};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_facility(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Facility(who.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Cases
+// -----------------------------------------------------------------------
+
+pub fn next_case_id(env: &Env) -> u64 {
+    let next = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::CaseCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage().instance().set(&DataKey::CaseCounter, &next);
+    next
+}
+
+pub fn save_case(env: &Env, case: &SurgicalCase) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Case(case.case_id), case);
+}
+
+pub fn load_case(env: &Env, case_id: u64) -> Option<SurgicalCase> {
+    env.storage().persistent().get(&DataKey::Case(case_id))
+}
+
+pub fn id_list(env: &Env, key: &DataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+pub fn push_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids = id_list(env, &key);
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
+
+pub fn room_cases(env: &Env, facility_id: &Address, room: &Symbol) -> Vec<u64> {
+    id_list(env, &DataKey::RoomCases(facility_id.clone(), room.clone()))
+}
@@ -0,0 +1,437 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, BytesN, Env, String,
+};
+
+mod mock_credentialing {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, String, Symbol};
+
+    #[contract]
+    pub struct MockCredentialing;
+
+    #[contractimpl]
+    impl MockCredentialing {
+        pub fn track_clinical_activity(
+            env: Env,
+            provider_id: Address,
+            _facility_id: Address,
+            _procedure_code: String,
+            outcome: Symbol,
+            complications: bool,
+            _activity_date: u64,
+        ) {
+            env.storage().instance().set(
+                &symbol_short!("last"),
+                &(provider_id, outcome, complications),
+            );
+        }
+
+        pub fn last(env: Env) -> Option<(Address, Symbol, bool)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const HOUR: u64 = 3_600;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    Address,
+    PerioperativeContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000 * HOUR);
+    let contract_id = env.register(PerioperativeContract, ());
+    let client = PerioperativeContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    let surgeon = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(&admin, &facility, &String::from_str(&env, "Main OR"));
+
+    (env, admin, facility, surgeon, nurse, client)
+}
+
+fn request(
+    env: &Env,
+    client: &PerioperativeContractClient,
+    surgeon: &Address,
+    facility: &Address,
+    minutes: u32,
+) -> u64 {
+    client.request_case(
+        surgeon,
+        facility,
+        &CaseRequest {
+            patient_id: Address::generate(env),
+            procedure_code: String::from_str(env, "47562"),
+            anesthesia: AnesthesiaType::General,
+            estimated_minutes: minutes,
+        },
+    )
+}
+
+fn scheduled_case(
+    env: &Env,
+    client: &PerioperativeContractClient,
+    surgeon: &Address,
+    facility: &Address,
+    start: u64,
+) -> u64 {
+    let case_id = request(env, client, surgeon, facility, 120);
+    client.schedule_case(facility, &case_id, &symbol_short!("OR1"), &start);
+    case_id
+}
+
+fn run_case(
+    env: &Env,
+    client: &PerioperativeContractClient,
+    surgeon: &Address,
+    nurse: &Address,
+    case_id: u64,
+) {
+    client.attest_preop_checklist(nurse, &case_id, &true, &true, &true);
+    client.start_case(surgeon, &case_id);
+    client.record_operative_record(surgeon, &case_id, &BytesN::from_array(env, &[7u8; 32]));
+    client.attest_counts(nurse, &case_id, &true);
+    client.complete_case(surgeon, &case_id);
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_case_lifecycle() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    run_case(&env, &client, &surgeon, &nurse, case_id);
+
+    let case = client.get_case(&case_id);
+    assert_eq!(case.status, CaseStatus::Completed);
+    assert!(case.operative_record_hash.is_some());
+    assert_eq!(client.get_surgeon_cases(&surgeon, &0, &10).len(), 1);
+
+    client.close_case(&surgeon, &case_id, &symbol_short!("success"));
+    assert_eq!(client.get_case(&case_id).status, CaseStatus::Closed);
+}
+
+#[test]
+fn test_room_conflicts() {
+    let (env, _, facility, surgeon, _, client) = setup();
+    let first = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    let second = request(&env, &client, &surgeon, &facility, 60);
+
+    assert_eq!(
+        client.try_schedule_case(&facility, &second, &symbol_short!("OR1"), &(1_003 * HOUR)),
+        Err(Ok(Error::RoomConflict))
+    );
+    client.schedule_case(&facility, &second, &symbol_short!("OR1"), &(1_004 * HOUR));
+    assert_eq!(
+        client
+            .get_room_schedule(&facility, &symbol_short!("OR1"))
+            .len(),
+        2
+    );
+
+    // Cancelling frees the slot.
+    client.cancel_case(&surgeon, &first);
+    let third = request(&env, &client, &surgeon, &facility, 60);
+    client.schedule_case(&facility, &third, &symbol_short!("OR1"), &(1_002 * HOUR));
+}
+
+#[test]
+fn test_checklist_required_before_start() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    assert_eq!(
+        client.try_start_case(&surgeon, &case_id),
+        Err(Ok(Error::ChecklistIncomplete))
+    );
+
+    client.attest_preop_checklist(&nurse, &case_id, &true, &false, &true);
+    assert_eq!(
+        client.try_start_case(&surgeon, &case_id),
+        Err(Ok(Error::ChecklistIncomplete))
+    );
+
+    client.attest_preop_checklist(&nurse, &case_id, &true, &true, &true);
+    client.start_case(&surgeon, &case_id);
+}
+
+#[test]
+fn test_completion_requires_record_and_correct_counts() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    client.attest_preop_checklist(&nurse, &case_id, &true, &true, &true);
+    client.start_case(&surgeon, &case_id);
+
+    assert_eq!(
+        client.try_complete_case(&surgeon, &case_id),
+        Err(Ok(Error::OperativeRecordMissing))
+    );
+    client.record_operative_record(&surgeon, &case_id, &BytesN::from_array(&env, &[1u8; 32]));
+
+    // The surgeon cannot attest their own counts.
+    assert_eq!(
+        client.try_attest_counts(&surgeon, &case_id, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.attest_counts(&nurse, &case_id, &false);
+    assert_eq!(
+        client.try_complete_case(&surgeon, &case_id),
+        Err(Ok(Error::CountsNotReconciled))
+    );
+    client.attest_counts(&nurse, &case_id, &true);
+    client.complete_case(&surgeon, &case_id);
+}
+
+#[test]
+fn test_closed_case_feeds_credentialing_with_complications() {
+    let (env, admin, facility, surgeon, nurse, client) = setup();
+    let credentialing_id = env.register(mock_credentialing::MockCredentialing, ());
+    client.set_credentialing_contract(&admin, &credentialing_id);
+
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    run_case(&env, &client, &surgeon, &nurse, case_id);
+    client.record_complication(
+        &surgeon,
+        &case_id,
+        &String::from_str(&env, "SSI"),
+        &symbol_short!("moderate"),
+        &String::from_str(&env, "Superficial incisional infection on day 6"),
+    );
+    assert_eq!(client.get_complications(&case_id).len(), 1);
+
+    client.close_case(&surgeon, &case_id, &symbol_short!("success"));
+    let mock = mock_credentialing::MockCredentialingClient::new(&env, &credentialing_id);
+    assert_eq!(
+        mock.last(),
+        Some((surgeon.clone(), symbol_short!("success"), true))
+    );
+    assert_eq!(
+        client.try_record_complication(
+            &surgeon,
+            &case_id,
+            &String::from_str(&env, "SSI"),
+            &symbol_short!("mild"),
+            &String::from_str(&env, "late"),
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_administration_requires_admin() {
+    let (env, admin, facility, surgeon, _, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&facility, &other, &String::from_str(&env, "Annex OR")),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_credentialing_contract(&surgeon, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_request_and_schedule_validation() {
+    let (env, admin, facility, surgeon, _, client) = setup();
+
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_request_case(
+            &surgeon,
+            &unregistered,
+            &CaseRequest {
+                patient_id: Address::generate(&env),
+                procedure_code: String::from_str(&env, "47562"),
+                anesthesia: AnesthesiaType::General,
+                estimated_minutes: 90,
+            },
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+    assert_eq!(
+        client.try_request_case(
+            &surgeon,
+            &facility,
+            &CaseRequest {
+                patient_id: Address::generate(&env),
+                procedure_code: String::from_str(&env, "47562"),
+                anesthesia: AnesthesiaType::General,
+                estimated_minutes: 0,
+            },
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    let case_id = request(&env, &client, &surgeon, &facility, 60);
+    assert_eq!(
+        client.try_schedule_case(&facility, &case_id, &symbol_short!("OR1"), &(999 * HOUR)),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    // Only the facility the case was requested at can book its rooms
+    let other_facility = Address::generate(&env);
+    client.register_facility(&admin, &other_facility, &String::from_str(&env, "Annex OR"));
+    assert_eq!(
+        client.try_schedule_case(
+            &other_facility,
+            &case_id,
+            &symbol_short!("OR1"),
+            &(1_002 * HOUR),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_schedule_case(&facility, &99, &symbol_short!("OR1"), &(1_002 * HOUR)),
+        Err(Ok(Error::CaseNotFound))
+    );
+    assert_eq!(client.try_get_case(&99), Err(Ok(Error::CaseNotFound)));
+}
+
+#[test]
+fn test_cancellation_rules() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_case(&stranger, &case_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.cancel_case(&facility, &case_id);
+    assert_eq!(client.get_case(&case_id).status, CaseStatus::Cancelled);
+    assert_eq!(
+        client.try_schedule_case(&facility, &case_id, &symbol_short!("OR2"), &(1_002 * HOUR)),
+        Err(Ok(Error::InvalidState))
+    );
+
+    // Started cases can no longer be cancelled
+    let started = scheduled_case(&env, &client, &surgeon, &facility, 1_005 * HOUR);
+    client.attest_preop_checklist(&nurse, &started, &true, &true, &true);
+    client.start_case(&surgeon, &started);
+    assert_eq!(
+        client.try_cancel_case(&surgeon, &started),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_only_operating_surgeon_drives_case() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    client.attest_preop_checklist(&nurse, &case_id, &true, &true, &true);
+
+    let other_surgeon = Address::generate(&env);
+    assert_eq!(
+        client.try_start_case(&other_surgeon, &case_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.start_case(&surgeon, &case_id);
+    assert_eq!(
+        client.try_record_operative_record(
+            &other_surgeon,
+            &case_id,
+            &BytesN::from_array(&env, &[3u8; 32]),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_complete_case(&other_surgeon, &case_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_record_complication(
+            &other_surgeon,
+            &case_id,
+            &String::from_str(&env, "SSI"),
+            &symbol_short!("mild"),
+            &String::from_str(&env, "Not the operating surgeon"),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_out_of_order_transitions() {
+    let (env, _, facility, surgeon, nurse, client) = setup();
+
+    // Checklists apply to scheduled cases only
+    let unscheduled = request(&env, &client, &surgeon, &facility, 60);
+    assert_eq!(
+        client.try_attest_preop_checklist(&nurse, &unscheduled, &true, &true, &true),
+        Err(Ok(Error::InvalidState))
+    );
+
+    let case_id = scheduled_case(&env, &client, &surgeon, &facility, 1_002 * HOUR);
+    assert_eq!(
+        client.try_attest_counts(&nurse, &case_id, &true),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_record_operative_record(
+            &surgeon,
+            &case_id,
+            &BytesN::from_array(&env, &[3u8; 32]),
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_complete_case(&surgeon, &case_id),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_close_case(&surgeon, &case_id, &symbol_short!("success")),
+        Err(Ok(Error::InvalidState))
+    );
+    assert_eq!(
+        client.try_record_complication(
+            &surgeon,
+            &case_id,
+            &String::from_str(&env, "SSI"),
+            &symbol_short!("mild"),
+            &String::from_str(&env, "Before incision"),
+        ),
+        Err(Ok(Error::InvalidState))
+    );
+
+    run_case(&env, &client, &surgeon, &nurse, case_id);
+    assert_eq!(
+        client.try_start_case(&surgeon, &case_id),
+        Err(Ok(Error::InvalidState))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, facility, _, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&facility), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&facility, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,158 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, BytesN, String, Symbol};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    FacilityNotRegistered = 4,
+    CaseNotFound = 5,
+    InvalidState = 6,
+    RoomConflict = 7,
+    ChecklistIncomplete = 8,
+    OperativeRecordMissing = 9,
+    CountsNotReconciled = 10,
+    InvalidInput = 11,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AnesthesiaType {
+    General,
+    Regional,
+    MonitoredAnesthesiaCare,
+    Sedation,
+    Local,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CaseStatus {
+    Requested,
+    Scheduled,
+    InProgress,
+    /// Out of the room; post-op complications may still be reported.
+    Completed,
+    /// Follow-up period over; the case has been reported to credentialing.
+    Closed,
+    Cancelled,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseRequest {
+    pub patient_id: Address,
+    pub procedure_code: String,
+    pub anesthesia: AnesthesiaType,
+    pub estimated_minutes: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurgicalCase {
+    pub case_id: u64,
+    pub facility_id: Address,
+    pub patient_id: Address,
+    pub surgeon_id: Address,
+    pub procedure_code: String,
+    pub anesthesia: AnesthesiaType,
+    pub estimated_minutes: u32,
+    pub requested_at: u64,
+    pub room: Option<Symbol>,
+    pub scheduled_start: u64,
+    pub status: CaseStatus,
+    pub started_at: u64,
+    pub completed_at: u64,
+    /// Hash of the off-chain intraoperative record.
+    pub operative_record_hash: Option<BytesN<32>>,
+}
+
+/// Sign-in/time-out checks that must all be confirmed before incision.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PreOpChecklist {
+    pub consent_verified: bool,
+    pub site_marked: bool,
+    pub antibiotics_given: bool,
+    pub attested_by: Address,
+    pub attested_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CountAttestation {
+    pub counts_correct: bool,
+    pub attested_by: Address,
+    pub attested_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Complication {
+    pub code: String,
+    pub severity: Symbol,
+    pub description: String,
+    pub reported_by: Address,
+    pub reported_at: u64,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CaseStatusChanged {
+    #[topic]
+    pub case_id: u64,
+    pub status: CaseStatus,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ComplicationReported {
+    #[topic]
+    pub case_id: u64,
+    pub severity: Symbol,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// Address of the healthcare-credentialing contract
+    Credentialing,
+    /// facility_id -> name
+    Facility(Address),
+    CaseCounter,
+    /// case_id -> SurgicalCase
+    Case(u64),
+    /// (facility_id, room) -> Vec<u64> (case ids)
+    RoomCases(Address, Symbol),
+    /// surgeon -> Vec<u64> (case ids)
+    SurgeonCases(Address),
+    /// case_id -> PreOpChecklist
+    Checklist(u64),
+    /// case_id -> CountAttestation
+    Counts(u64),
+    /// case_id -> Vec<Complication>
+    Complications(u64),
+}
@@ -0,0 +1,208 @@
+use soroban_sdk::{contractevent, Address, Symbol};
+
+use crate::{GbsStatus, GrowthAlertKind, MandatedScreenType, PregnancyOutcome};
+
+// Every state-changing entrypoint publishes one of these. The first topic is
+// the record id where one exists, otherwise the patient.
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientRegistryConfigured {
+    #[topic]
+    pub registry: Address,
+    pub strict_mode: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PregnancyRecordCreated {
+    #[topic]
+    pub pregnancy_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub edd: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PregnancyClosed {
+    #[topic]
+    pub pregnancy_id: u64,
+    pub provider_id: Address,
+    pub outcome: PregnancyOutcome,
+    pub gestational_age_weeks: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalVisitRecorded {
+    #[topic]
+    pub pregnancy_id: u64,
+    pub visit_id: u64,
+    pub gestational_age_weeks: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalVisitScheduleSet {
+    #[topic]
+    pub admin: Address,
+    pub first_visit_week: u32,
+    pub final_visit_week: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalCareEnrolled {
+    #[topic]
+    pub pregnancy_id: u64,
+    #[topic]
+    pub facility_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalScreeningRecorded {
+    #[topic]
+    pub pregnancy_id: u64,
+    pub screening_id: u64,
+    pub screening_type: Symbol,
+    pub abnormal: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UltrasoundRecorded {
+    #[topic]
+    pub pregnancy_id: u64,
+    pub ultrasound_id: u64,
+    pub gestational_age: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LaborAdmissionDocumented {
+    #[topic]
+    pub labor_id: u64,
+    #[topic]
+    pub pregnancy_id: u64,
+    pub admission_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GbsStatusRecorded {
+    #[topic]
+    pub labor_id: u64,
+    #[topic]
+    pub facility_id: Address,
+    pub gbs_status: GbsStatus,
+    pub gestational_age_weeks: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntrapartumAntibioticsRecorded {
+    #[topic]
+    pub labor_id: u64,
+    pub started_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AntenatalSteroidsRecorded {
+    #[topic]
+    pub labor_id: u64,
+    pub given_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DeliveryRecorded {
+    #[topic]
+    pub delivery_id: u64,
+    #[topic]
+    pub pregnancy_id: u64,
+    pub delivery_method: Symbol,
+    pub delivering_provider: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewbornRecorded {
+    #[topic]
+    pub delivery_id: u64,
+    #[topic]
+    pub newborn_id: Address,
+    pub gestational_age_weeks: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewbornScreeningRecorded {
+    #[topic]
+    pub newborn_id: Address,
+    pub screening_id: u64,
+    pub screening_type: Symbol,
+    pub requires_followup: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PediatricGrowthTracked {
+    #[topic]
+    pub growth_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub age_months: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrowthAlertRaised {
+    #[topic]
+    pub patient_id: Address,
+    pub growth_id: u64,
+    pub kind: GrowthAlertKind,
+    pub percentile_x100: i64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DevelopmentalMilestoneRecorded {
+    #[topic]
+    pub patient_id: Address,
+    pub age_months: u32,
+    pub milestone_category: Symbol,
+    pub concern_count: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WellChildVisitTracked {
+    #[topic]
+    pub patient_id: Address,
+    pub visit_date: u64,
+    pub age_months: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MandatedScreensRequired {
+    #[topic]
+    pub newborn_id: Address,
+    #[topic]
+    pub facility_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MandatedScreenFailed {
+    #[topic]
+    pub newborn_id: Address,
+    #[topic]
+    pub facility_id: Address,
+    pub screen_type: MandatedScreenType,
+    pub referral_specialty: Symbol,
+}
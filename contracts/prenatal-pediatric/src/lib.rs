@@ -5,6 +5,44 @@ use soroban_sdk::{
     String, Symbol, Vec,
 };
 
+mod events;
+mod patient_registry;
+
+pub use events::*;
+pub use patient_registry::{PatientRegistry, PatientRegistryClient};
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+/// CCHD pulse oximetry is due before discharge, taken here as 48 hours.
+const CCHD_SCREEN_WINDOW_SECS: u64 = 48 * 60 * 60;
+/// Hearing screening is due by one month of age.
+const HEARING_SCREEN_WINDOW_SECS: u64 = 30 * 24 * 60 * 60;
+/// Intrapartum GBS prophylaxis counts as adequate when started at least
+/// four hours before delivery.
+const GBS_PROPHYLAXIS_LEAD_SECS: u64 = 4 * 60 * 60;
+/// Below this gestational age labor is preterm.
+const TERM_WEEKS: u32 = 37;
+/// Antenatal corticosteroids are indicated for preterm labor in this range.
+const STEROID_MIN_WEEKS: u32 = 24;
+const STEROID_MAX_WEEKS: u32 = 34;
+const WEEK_SECS: u64 = 7 * 24 * 60 * 60;
+/// Gestational age at the EDD.
+const EDD_WEEKS: u64 = 40;
+/// Prenatal care begun after the fourth month is inadequate regardless of
+/// how many visits follow.
+const CARE_INITIATION_MAX_WEEKS: u32 = 16;
+/// Share of expected visits attended (bps) for adequate and intermediate care.
+const ADEQUATE_VISITS_BPS: u32 = 8_000;
+const INTERMEDIATE_VISITS_BPS: u32 = 5_000;
+/// Major weight-for-age percentile lines (×100). Falling across two of
+/// them between measurements is flagged as weight faltering.
+const MAJOR_PERCENTILE_LINES: [i64; 7] = [500, 1000, 2500, 5000, 7500, 9000, 9500];
+/// BMI at or above this percentile (×100) is flagged as obesity.
+const BMI_OBESITY_PERCENTILE_X100: i64 = 9500;
+/// BMI-for-age percentiles apply from two years of age.
+const BMI_SCREEN_MIN_MONTHS: u32 = 24;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -13,6 +51,8 @@ pub enum Error {
     Unauthorized = 2,
     InvalidData = 3,
     AlreadyExists = 4,
+    PatientNotRegistered = 5,
+    PregnancyClosed = 6,
 }
 
 #[contracttype]
@@ -30,6 +70,27 @@ pub struct PregnancyRecord {
     pub outcome: Option<Symbol>,
 }
 
+/// How a pregnancy ended other than by a recorded delivery.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PregnancyOutcome {
+    Miscarriage,
+    Termination,
+    Ectopic,
+    Stillbirth,
+}
+
+/// Count of pregnancies a provider has closed, by outcome.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct PregnancyOutcomeStats {
+    pub delivered: u32,
+    pub miscarriage: u32,
+    pub termination: u32,
+    pub ectopic: u32,
+    pub stillbirth: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PrenatalVisit {
@@ -80,6 +141,100 @@ pub struct LaborRecord {
     pub cervical_effacement: u32,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GbsStatus {
+    Positive,
+    Negative,
+    Unknown,
+}
+
+/// Quality measures for a labor admission, kept alongside the labor record.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LaborQualityRecord {
+    pub labor_id: u64,
+    pub facility_id: Address,
+    pub gbs_status: GbsStatus,
+    pub gestational_age_weeks: u32,
+    pub antibiotics_started_at: Option<u64>,
+    pub steroids_given_at: Option<u64>,
+}
+
+/// Prophylaxis compliance across a facility's labors. Rates are in basis
+/// points and are zero when nothing was indicated.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProphylaxisCompliance {
+    pub gbs_indicated: u32,
+    pub gbs_adequate: u32,
+    pub gbs_compliance_bps: u32,
+    pub steroids_indicated: u32,
+    pub steroids_given: u32,
+    pub steroid_compliance_bps: u32,
+}
+
+/// Visits fall due every `every_weeks` until gestational week `until_week`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitInterval {
+    pub until_week: u32,
+    pub every_weeks: u32,
+}
+
+/// Expected prenatal visits: one at `first_visit_week`, then each interval
+/// in turn. The default is every 4 weeks until 28, every 2 until 36, then
+/// weekly until 40.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalVisitSchedule {
+    pub first_visit_week: u32,
+    pub intervals: Vec<VisitInterval>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PrenatalCareAdequacy {
+    NotYetDue,
+    Inadequate,
+    Intermediate,
+    Adequate,
+}
+
+/// How a pregnancy's recorded visits compare with the schedule up to its
+/// current gestational age, or the age it ended at. An expected visit is
+/// attended when a visit falls after the previous expected week and no
+/// later than its own.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrenatalVisitAdherence {
+    pub pregnancy_id: u64,
+    pub gestational_age_weeks: u32,
+    pub first_visit_week: Option<u32>,
+    pub visits_recorded: u32,
+    pub expected_visits: u32,
+    pub attended_visits: u32,
+    pub missed_weeks: Vec<u32>,
+    pub adherence_bps: u32,
+    pub adequacy: PrenatalCareAdequacy,
+}
+
+/// Adequacy of prenatal care across a facility's enrolled pregnancies.
+/// Pregnancies with no visit due yet are counted as `not_yet_due` only.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FacilityPrenatalCareStats {
+    pub pregnancies: u32,
+    pub not_yet_due: u32,
+    pub adequate: u32,
+    pub intermediate: u32,
+    pub inadequate: u32,
+    pub expected_visits: u32,
+    pub attended_visits: u32,
+    pub adherence_bps: u32,
+    pub adequate_care_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DeliveryRecord {
@@ -121,6 +276,42 @@ pub struct NewbornScreening {
     pub requires_followup: bool,
 }
 
+/// Screens every newborn must receive within a statutory window.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MandatedScreenType {
+    Hearing,
+    Cchd,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MandatedScreenStatus {
+    Pending,
+    Passed,
+    /// Failed and flagged for an audiology or cardiology referral.
+    ReferralRequired,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MandatedScreen {
+    pub screen_type: MandatedScreenType,
+    pub due_by: u64,
+    pub status: MandatedScreenStatus,
+    pub screening_id: Option<u64>,
+    pub completed_at: Option<u64>,
+    pub completed_in_window: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct NewbornMandatedScreens {
+    pub newborn_id: Address,
+    pub facility_id: Address,
+    pub screens: Vec<MandatedScreen>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PediatricMeasurements {
@@ -140,6 +331,29 @@ pub struct PediatricGrowthRecord {
     pub measurements: PediatricMeasurements,
 }
 
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum GrowthAlertKind {
+    WeightFaltering,
+    BmiObesity,
+}
+
+/// Raised by growth surveillance in `track_pediatric_growth`. For weight
+/// faltering `prior_growth_id` and `prior_percentile_x100` refer to the
+/// measurement the trajectory was compared against.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GrowthAlert {
+    pub patient_id: Address,
+    pub growth_id: u64,
+    pub kind: GrowthAlertKind,
+    pub percentile_x100: i64,
+    pub prior_growth_id: Option<u64>,
+    pub prior_percentile_x100: Option<i64>,
+    pub lines_crossed: u32,
+    pub raised_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DevelopmentalMilestone {
@@ -171,8 +385,18 @@ pub struct GrowthPercentiles {
     pub bmi_percentile_x100: i64,
 }
 
+/// Patient-registry hook; in strict mode unregistered patients are rejected
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientRegistryConfig {
+    pub registry: Address,
+    pub strict_mode: bool,
+}
+
 #[contracttype]
 pub enum DataKey {
+    Admin,
+    PatientRegistry,
     Pregnancy(u64),
     PrenatalVisit(u64),
     PrenatalScreening(u64),
@@ -183,8 +407,19 @@ pub enum DataKey {
     NewbornScreening(u64),
     Growth(u64),
     GrowthByAge(Address, u32),
+    PatientGrowth(Address),
+    GrowthAlerts(Address),
     Milestone(Address, u32),
     WellChildVisit(Address, u64),
+    MandatedScreens(Address),
+    FacilityNewborns(Address),
+    ProviderOutcomeStats(Address),
+    LaborQuality(u64),
+    LaborDeliveredAt(u64),
+    FacilityLabors(Address),
+    PrenatalVisitSchedule,
+    PregnancyEndWeek(u64),
+    FacilityPregnancies(Address),
 }
 
 #[contract]
@@ -192,6 +427,48 @@ pub struct MaternalChildHealthContract;
 
 #[contractimpl]
 impl MaternalChildHealthContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyExists);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn set_patient_registry(
+        env: Env,
+        admin: Address,
+        registry: Address,
+        strict_mode: bool,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if stored != Some(admin) {
+            return Err(Error::Unauthorized);
+        }
+
+        env.storage().instance().set(
+            &DataKey::PatientRegistry,
+            &PatientRegistryConfig {
+                registry: registry.clone(),
+                strict_mode,
+            },
+        );
+
+        PatientRegistryConfigured {
+            registry,
+            strict_mode,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_patient_registry(env: Env) -> Option<PatientRegistryConfig> {
+        env.storage().instance().get(&DataKey::PatientRegistry)
+    }
+
     pub fn create_pregnancy_record(
         env: Env,
         patient_id: Address,
@@ -207,12 +484,13 @@ impl MaternalChildHealthContract {
         if lmp_date >= estimated_due_date || para > gravida {
             return Err(Error::InvalidData);
         }
+        Self::require_registered_patient(&env, &patient_id)?;
 
         let pregnancy_id = Self::next_id(&env, symbol_short!("preg_ctr"));
         let record = PregnancyRecord {
             pregnancy_id,
-            patient_id,
-            provider_id,
+            patient_id: patient_id.clone(),
+            provider_id: provider_id.clone(),
             lmp_date,
             edd: estimated_due_date,
             gravida,
@@ -226,6 +504,13 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::Pregnancy(pregnancy_id), &record);
 
+        PregnancyRecordCreated {
+            pregnancy_id,
+            patient_id,
+            provider_id,
+            edd: estimated_due_date,
+        }
+        .publish(&env);
         Ok(pregnancy_id)
     }
 
@@ -240,7 +525,7 @@ impl MaternalChildHealthContract {
         fetal_heart_rate: Option<u32>,
         visit_notes_hash: BytesN<32>,
     ) -> Result<(), Error> {
-        let mut pregnancy = Self::get_pregnancy(&env, pregnancy_id)?;
+        let mut pregnancy = Self::get_open_pregnancy(&env, pregnancy_id)?;
 
         if gestational_age_weeks > 45 || weight_kg_x100 <= 0 {
             return Err(Error::InvalidData);
@@ -268,6 +553,12 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::Pregnancy(pregnancy_id), &pregnancy);
 
+        PrenatalVisitRecorded {
+            pregnancy_id,
+            visit_id,
+            gestational_age_weeks,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -285,7 +576,7 @@ impl MaternalChildHealthContract {
         let screening = PrenatalScreening {
             screening_id,
             pregnancy_id,
-            screening_type,
+            screening_type: screening_type.clone(),
             test_date,
             results_hash,
             abnormal,
@@ -295,6 +586,13 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::PrenatalScreening(screening_id), &screening);
 
+        PrenatalScreeningRecorded {
+            pregnancy_id,
+            screening_id,
+            screening_type,
+            abnormal,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -308,7 +606,7 @@ impl MaternalChildHealthContract {
         placental_location: String,
         findings_hash: BytesN<32>,
     ) -> Result<(), Error> {
-        let _ = Self::get_pregnancy(&env, pregnancy_id)?;
+        let _ = Self::get_open_pregnancy(&env, pregnancy_id)?;
 
         if gestational_age > 45 {
             return Err(Error::InvalidData);
@@ -330,6 +628,12 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::Ultrasound(ultrasound_id), &ultrasound);
 
+        UltrasoundRecorded {
+            pregnancy_id,
+            ultrasound_id,
+            gestational_age,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -361,9 +665,312 @@ impl MaternalChildHealthContract {
 
         env.storage().persistent().set(&DataKey::Labor(labor_id), &labor);
 
+        LaborAdmissionDocumented {
+            labor_id,
+            pregnancy_id,
+            admission_date,
+        }
+        .publish(&env);
         Ok(labor_id)
     }
 
+    /// Record the GBS status at labor admission together with the
+    /// gestational age, which decides whether prophylaxis or antenatal
+    /// steroids are indicated. This starts quality tracking for the labor
+    /// at the facility.
+    pub fn record_gbs_status(
+        env: Env,
+        labor_id: u64,
+        facility_id: Address,
+        gbs_status: GbsStatus,
+        gestational_age_weeks: u32,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+
+        if !env.storage().persistent().has(&DataKey::Labor(labor_id)) {
+            return Err(Error::NotFound);
+        }
+        if gestational_age_weeks > 45 {
+            return Err(Error::InvalidData);
+        }
+
+        let key = DataKey::LaborQuality(labor_id);
+        let existing: Option<LaborQualityRecord> = env.storage().persistent().get(&key);
+        let record = match existing {
+            Some(mut record) => {
+                if record.facility_id != facility_id {
+                    return Err(Error::Unauthorized);
+                }
+                record.gbs_status = gbs_status;
+                record.gestational_age_weeks = gestational_age_weeks;
+                record
+            }
+            None => {
+                let facility_key = DataKey::FacilityLabors(facility_id.clone());
+                let mut labors: Vec<u64> = env
+                    .storage()
+                    .persistent()
+                    .get(&facility_key)
+                    .unwrap_or(Vec::new(&env));
+                labors.push_back(labor_id);
+                env.storage().persistent().set(&facility_key, &labors);
+
+                LaborQualityRecord {
+                    labor_id,
+                    facility_id: facility_id.clone(),
+                    gbs_status,
+                    gestational_age_weeks,
+                    antibiotics_started_at: None,
+                    steroids_given_at: None,
+                }
+            }
+        };
+        env.storage().persistent().set(&key, &record);
+
+        GbsStatusRecorded {
+            labor_id,
+            facility_id,
+            gbs_status,
+            gestational_age_weeks,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn record_intrapartum_antibiotics(
+        env: Env,
+        labor_id: u64,
+        facility_id: Address,
+        started_at: u64,
+    ) -> Result<(), Error> {
+        let mut record = Self::get_labor_quality_for(&env, labor_id, &facility_id)?;
+        record.antibiotics_started_at = Some(started_at);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LaborQuality(labor_id), &record);
+
+        IntrapartumAntibioticsRecorded {
+            labor_id,
+            started_at,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Only preterm labors take antenatal corticosteroids.
+    pub fn record_antenatal_steroids(
+        env: Env,
+        labor_id: u64,
+        facility_id: Address,
+        given_at: u64,
+    ) -> Result<(), Error> {
+        let mut record = Self::get_labor_quality_for(&env, labor_id, &facility_id)?;
+        if record.gestational_age_weeks >= TERM_WEEKS {
+            return Err(Error::InvalidData);
+        }
+        record.steroids_given_at = Some(given_at);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LaborQuality(labor_id), &record);
+
+        AntenatalSteroidsRecorded { labor_id, given_at }.publish(&env);
+        Ok(())
+    }
+
+    pub fn get_labor_quality(env: Env, labor_id: u64) -> Result<LaborQualityRecord, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::LaborQuality(labor_id))
+            .ok_or(Error::NotFound)
+    }
+
+    /// GBS prophylaxis is indicated for GBS-positive labors, and for preterm
+    /// labors of unknown status; it is adequate when started at least four
+    /// hours before delivery, so only delivered labors count. Antenatal
+    /// steroids are indicated for labors between 24 and 34 weeks.
+    pub fn get_prophylaxis_compliance(env: Env, facility_id: Address) -> ProphylaxisCompliance {
+        let labors: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FacilityLabors(facility_id))
+            .unwrap_or(Vec::new(&env));
+
+        let mut gbs_indicated = 0u32;
+        let mut gbs_adequate = 0u32;
+        let mut steroids_indicated = 0u32;
+        let mut steroids_given = 0u32;
+        for labor_id in labors.iter() {
+            let record: LaborQualityRecord = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::LaborQuality(labor_id))
+            {
+                Some(record) => record,
+                None => continue,
+            };
+
+            let preterm = record.gestational_age_weeks < TERM_WEEKS;
+            let gbs_needed = record.gbs_status == GbsStatus::Positive
+                || (record.gbs_status == GbsStatus::Unknown && preterm);
+            let delivered_at: Option<u64> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::LaborDeliveredAt(labor_id));
+            if let (true, Some(delivered_at)) = (gbs_needed, delivered_at) {
+                gbs_indicated += 1;
+                let latest_start = delivered_at.saturating_sub(GBS_PROPHYLAXIS_LEAD_SECS);
+                if record.antibiotics_started_at.is_some_and(|started| started <= latest_start) {
+                    gbs_adequate += 1;
+                }
+            }
+
+            if (STEROID_MIN_WEEKS..STEROID_MAX_WEEKS).contains(&record.gestational_age_weeks) {
+                steroids_indicated += 1;
+                if record.steroids_given_at.is_some() {
+                    steroids_given += 1;
+                }
+            }
+        }
+
+        ProphylaxisCompliance {
+            gbs_indicated,
+            gbs_adequate,
+            gbs_compliance_bps: Self::rate_bps(gbs_adequate, gbs_indicated),
+            steroids_indicated,
+            steroids_given,
+            steroid_compliance_bps: Self::rate_bps(steroids_given, steroids_indicated),
+        }
+    }
+
+    /// Replace the prenatal visit schedule. Intervals must end at strictly
+    /// increasing weeks, the first after `first_visit_week`.
+    pub fn set_prenatal_visit_schedule(
+        env: Env,
+        admin: Address,
+        schedule: PrenatalVisitSchedule,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+
+        if schedule.first_visit_week == 0 || schedule.intervals.is_empty() {
+            return Err(Error::InvalidData);
+        }
+        let mut previous_week = schedule.first_visit_week;
+        for interval in schedule.intervals.iter() {
+            if interval.every_weeks == 0
+                || interval.until_week <= previous_week
+                || interval.until_week > 45
+            {
+                return Err(Error::InvalidData);
+            }
+            previous_week = interval.until_week;
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PrenatalVisitSchedule, &schedule);
+
+        PrenatalVisitScheduleSet {
+            admin,
+            first_visit_week: schedule.first_visit_week,
+            final_visit_week: previous_week,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_prenatal_visit_schedule(env: Env) -> PrenatalVisitSchedule {
+        env.storage()
+            .instance()
+            .get(&DataKey::PrenatalVisitSchedule)
+            .unwrap_or_else(|| Self::default_visit_schedule(&env))
+    }
+
+    /// Count the pregnancy towards the facility's adequacy-of-care statistics.
+    pub fn enroll_prenatal_care(
+        env: Env,
+        pregnancy_id: u64,
+        facility_id: Address,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+        Self::get_pregnancy(&env, pregnancy_id)?;
+
+        let facility_key = DataKey::FacilityPregnancies(facility_id.clone());
+        let mut pregnancies: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&facility_key)
+            .unwrap_or(Vec::new(&env));
+        if pregnancies.contains(pregnancy_id) {
+            return Err(Error::AlreadyExists);
+        }
+        pregnancies.push_back(pregnancy_id);
+        env.storage().persistent().set(&facility_key, &pregnancies);
+
+        PrenatalCareEnrolled {
+            pregnancy_id,
+            facility_id,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_prenatal_visit_adherence(
+        env: Env,
+        pregnancy_id: u64,
+    ) -> Result<PrenatalVisitAdherence, Error> {
+        let pregnancy = Self::get_pregnancy(&env, pregnancy_id)?;
+        let schedule = Self::get_prenatal_visit_schedule(env.clone());
+        Ok(Self::visit_adherence(&env, &pregnancy, &schedule))
+    }
+
+    pub fn get_facility_prenatal_care_stats(
+        env: Env,
+        facility_id: Address,
+    ) -> FacilityPrenatalCareStats {
+        let pregnancies: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FacilityPregnancies(facility_id))
+            .unwrap_or(Vec::new(&env));
+        let schedule = Self::get_prenatal_visit_schedule(env.clone());
+
+        let mut stats = FacilityPrenatalCareStats {
+            pregnancies: pregnancies.len(),
+            not_yet_due: 0,
+            adequate: 0,
+            intermediate: 0,
+            inadequate: 0,
+            expected_visits: 0,
+            attended_visits: 0,
+            adherence_bps: 0,
+            adequate_care_bps: 0,
+        };
+        for pregnancy_id in pregnancies.iter() {
+            let pregnancy = match env
+                .storage()
+                .persistent()
+                .get(&DataKey::Pregnancy(pregnancy_id))
+            {
+                Some(pregnancy) => pregnancy,
+                None => continue,
+            };
+            let adherence = Self::visit_adherence(&env, &pregnancy, &schedule);
+            stats.expected_visits += adherence.expected_visits;
+            stats.attended_visits += adherence.attended_visits;
+            match adherence.adequacy {
+                PrenatalCareAdequacy::NotYetDue => stats.not_yet_due += 1,
+                PrenatalCareAdequacy::Inadequate => stats.inadequate += 1,
+                PrenatalCareAdequacy::Intermediate => stats.intermediate += 1,
+                PrenatalCareAdequacy::Adequate => stats.adequate += 1,
+            }
+        }
+        stats.adherence_bps = Self::rate_bps(stats.attended_visits, stats.expected_visits);
+        stats.adequate_care_bps = Self::rate_bps(
+            stats.adequate,
+            stats.adequate + stats.intermediate + stats.inadequate,
+        );
+        stats
+    }
+
     pub fn record_delivery(
         env: Env,
         labor_id: u64,
@@ -387,28 +994,110 @@ impl MaternalChildHealthContract {
             delivery_id,
             pregnancy_id: labor.pregnancy_id,
             delivery_datetime,
-            delivery_method,
+            delivery_method: delivery_method.clone(),
             presentation,
             newborn_ids: Vec::new(&env),
             complications,
             blood_loss_ml,
-            delivering_provider,
+            delivering_provider: delivering_provider.clone(),
             mother_outcome: symbol_short!("stable"),
         };
 
         env.storage()
             .persistent()
             .set(&DataKey::Delivery(delivery_id), &delivery);
+        env.storage()
+            .persistent()
+            .set(&DataKey::LaborDeliveredAt(labor_id), &delivery_datetime);
 
         let mut pregnancy = Self::get_pregnancy(&env, labor.pregnancy_id)?;
+        if pregnancy.outcome.is_none() {
+            Self::update_outcome_stats(&env, &pregnancy.provider_id, |stats| stats.delivered += 1);
+            env.storage().persistent().set(
+                &DataKey::PregnancyEndWeek(labor.pregnancy_id),
+                &Self::gestational_weeks_at(&pregnancy, delivery_datetime),
+            );
+        }
         pregnancy.outcome = Some(symbol_short!("delivrd"));
         env.storage()
             .persistent()
             .set(&DataKey::Pregnancy(labor.pregnancy_id), &pregnancy);
 
+        DeliveryRecorded {
+            delivery_id,
+            pregnancy_id: labor.pregnancy_id,
+            delivery_method,
+            delivering_provider,
+        }
+        .publish(&env);
         Ok(delivery_id)
     }
 
+    /// Close a pregnancy that ended without a delivery. The gestational age
+    /// must fit the outcome: miscarriage and ectopic before 20 weeks,
+    /// stillbirth from 20 weeks. No further visits or ultrasounds can be
+    /// recorded once it is closed.
+    pub fn close_pregnancy(
+        env: Env,
+        pregnancy_id: u64,
+        provider_id: Address,
+        outcome: PregnancyOutcome,
+        gestational_age_weeks: u32,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        let mut pregnancy = Self::get_open_pregnancy(&env, pregnancy_id)?;
+        if pregnancy.provider_id != provider_id {
+            return Err(Error::Unauthorized);
+        }
+
+        let valid_age = match outcome {
+            PregnancyOutcome::Miscarriage | PregnancyOutcome::Ectopic => gestational_age_weeks < 20,
+            PregnancyOutcome::Stillbirth => (20..=45).contains(&gestational_age_weeks),
+            PregnancyOutcome::Termination => gestational_age_weeks <= 45,
+        };
+        if !valid_age {
+            return Err(Error::InvalidData);
+        }
+
+        pregnancy.outcome = Some(match outcome {
+            PregnancyOutcome::Miscarriage => symbol_short!("miscarr"),
+            PregnancyOutcome::Termination => symbol_short!("terminatd"),
+            PregnancyOutcome::Ectopic => symbol_short!("ectopic"),
+            PregnancyOutcome::Stillbirth => symbol_short!("stillbrth"),
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::Pregnancy(pregnancy_id), &pregnancy);
+        env.storage().persistent().set(
+            &DataKey::PregnancyEndWeek(pregnancy_id),
+            &gestational_age_weeks,
+        );
+
+        Self::update_outcome_stats(&env, &provider_id, |stats| match outcome {
+            PregnancyOutcome::Miscarriage => stats.miscarriage += 1,
+            PregnancyOutcome::Termination => stats.termination += 1,
+            PregnancyOutcome::Ectopic => stats.ectopic += 1,
+            PregnancyOutcome::Stillbirth => stats.stillbirth += 1,
+        });
+
+        PregnancyClosed {
+            pregnancy_id,
+            provider_id,
+            outcome,
+            gestational_age_weeks,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_provider_outcome_stats(env: Env, provider_id: Address) -> PregnancyOutcomeStats {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ProviderOutcomeStats(provider_id))
+            .unwrap_or_default()
+    }
+
     pub fn record_newborn(
         env: Env,
         delivery_id: u64,
@@ -456,6 +1145,12 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::Delivery(delivery_id), &delivery);
 
+        NewbornRecorded {
+            delivery_id,
+            newborn_id: newborn_id.clone(),
+            gestational_age_weeks,
+        }
+        .publish(&env);
         Ok(newborn_id)
     }
 
@@ -476,8 +1171,8 @@ impl MaternalChildHealthContract {
         let screening_id = Self::next_id(&env, symbol_short!("nbs_ctr"));
         let screening = NewbornScreening {
             screening_id,
-            newborn_id,
-            screening_type,
+            newborn_id: newborn_id.clone(),
+            screening_type: screening_type.clone(),
             test_date,
             result,
             requires_followup,
@@ -487,9 +1182,120 @@ impl MaternalChildHealthContract {
             .persistent()
             .set(&DataKey::NewbornScreening(screening_id), &screening);
 
+        Self::complete_mandated_screen(
+            &env,
+            &newborn_id,
+            &screening_type,
+            &screening,
+        );
+
+        NewbornScreeningRecorded {
+            newborn_id,
+            screening_id,
+            screening_type,
+            requires_followup,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Mark the newborn as owing the mandated hearing and CCHD screens,
+    /// each due within its statutory window from birth. Screenings recorded
+    /// afterwards with type `hearing` or `cchd` complete them; anything other
+    /// than a `pass` without follow-up flags the newborn for referral.
+    pub fn require_mandated_screens(
+        env: Env,
+        facility_id: Address,
+        newborn_id: Address,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+
+        let newborn: NewbornRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Newborn(newborn_id.clone()))
+            .ok_or(Error::NotFound)?;
+        let key = DataKey::MandatedScreens(newborn_id.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(Error::AlreadyExists);
+        }
+
+        let mut screens = Vec::new(&env);
+        for (screen_type, window) in [
+            (MandatedScreenType::Hearing, HEARING_SCREEN_WINDOW_SECS),
+            (MandatedScreenType::Cchd, CCHD_SCREEN_WINDOW_SECS),
+        ] {
+            screens.push_back(MandatedScreen {
+                screen_type,
+                due_by: newborn.birth_datetime.saturating_add(window),
+                status: MandatedScreenStatus::Pending,
+                screening_id: None,
+                completed_at: None,
+                completed_in_window: false,
+            });
+        }
+        env.storage().persistent().set(
+            &key,
+            &NewbornMandatedScreens {
+                newborn_id: newborn_id.clone(),
+                facility_id: facility_id.clone(),
+                screens,
+            },
+        );
+
+        let facility_key = DataKey::FacilityNewborns(facility_id.clone());
+        let mut newborns: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&facility_key)
+            .unwrap_or(Vec::new(&env));
+        newborns.push_back(newborn_id.clone());
+        env.storage().persistent().set(&facility_key, &newborns);
+
+        MandatedScreensRequired {
+            newborn_id,
+            facility_id,
+        }
+        .publish(&env);
         Ok(())
     }
 
+    pub fn get_mandated_screens(
+        env: Env,
+        newborn_id: Address,
+    ) -> Result<NewbornMandatedScreens, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MandatedScreens(newborn_id))
+            .ok_or(Error::NotFound)
+    }
+
+    /// One page of the facility's newborns, keeping only those with a
+    /// mandated screen still pending.
+    pub fn get_incomplete_mandated_screens(
+        env: Env,
+        facility_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<NewbornMandatedScreens> {
+        let newborns: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FacilityNewborns(facility_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate_map(&env, &newborns, offset, limit, |newborn_id| {
+            let record: NewbornMandatedScreens = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MandatedScreens(newborn_id))?;
+            record
+                .screens
+                .iter()
+                .any(|screen| screen.status == MandatedScreenStatus::Pending)
+                .then_some(record)
+        })
+    }
+
     pub fn track_pediatric_growth(
         env: Env,
         patient_id: Address,
@@ -503,6 +1309,7 @@ impl MaternalChildHealthContract {
         if age_months > 228 || weight_kg_x100 <= 0 || height_cm_x100 <= 0 || bmi_x100 <= 0 {
             return Err(Error::InvalidData);
         }
+        Self::require_registered_patient(&env, &patient_id)?;
 
         let measurements = PediatricMeasurements {
             weight_kg_x100,
@@ -520,14 +1327,40 @@ impl MaternalChildHealthContract {
             measurements,
         };
 
+        let prior = Self::prior_growth(&env, &patient_id, age_months);
+
         env.storage().persistent().set(&DataKey::Growth(growth_id), &growth);
-        env.storage()
+        env.storage().persistent().set(
+            &DataKey::GrowthByAge(patient_id.clone(), age_months),
+            &growth_id,
+        );
+        let growth_key = DataKey::PatientGrowth(patient_id.clone());
+        let mut growth_ids: Vec<u64> = env
+            .storage()
             .persistent()
-            .set(&DataKey::GrowthByAge(patient_id, age_months), &growth_id);
+            .get(&growth_key)
+            .unwrap_or(Vec::new(&env));
+        growth_ids.push_back(growth_id);
+        env.storage().persistent().set(&growth_key, &growth_ids);
+
+        PediatricGrowthTracked {
+            growth_id,
+            patient_id: patient_id.clone(),
+            age_months,
+        }
+        .publish(&env);
 
+        Self::run_growth_surveillance(&env, &growth, prior);
         Ok(())
     }
 
+    pub fn get_growth_alerts(env: Env, patient_id: Address) -> Vec<GrowthAlert> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::GrowthAlerts(patient_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn record_developmental_milestone(
         env: Env,
         patient_id: Address,
@@ -540,20 +1373,29 @@ impl MaternalChildHealthContract {
         if age_months > 228 {
             return Err(Error::InvalidData);
         }
+        Self::require_registered_patient(&env, &patient_id)?;
 
+        let concern_count = concerns.len();
         let record = DevelopmentalMilestone {
             patient_id: patient_id.clone(),
             assessment_date,
             age_months,
-            milestone_category,
+            milestone_category: milestone_category.clone(),
             milestones_met,
             concerns,
         };
 
         env.storage()
             .persistent()
-            .set(&DataKey::Milestone(patient_id, age_months), &record);
+            .set(&DataKey::Milestone(patient_id.clone(), age_months), &record);
 
+        DevelopmentalMilestoneRecorded {
+            patient_id,
+            age_months,
+            milestone_category,
+            concern_count,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -569,6 +1411,7 @@ impl MaternalChildHealthContract {
         if age_months > 228 {
             return Err(Error::InvalidData);
         }
+        Self::require_registered_patient(&env, &patient_id)?;
 
         let visit = WellChildVisit {
             patient_id: patient_id.clone(),
@@ -579,10 +1422,17 @@ impl MaternalChildHealthContract {
             anticipatory_guidance_hash,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::WellChildVisit(patient_id, visit_date), &visit);
+        env.storage().persistent().set(
+            &DataKey::WellChildVisit(patient_id.clone(), visit_date),
+            &visit,
+        );
 
+        WellChildVisitTracked {
+            patient_id,
+            visit_date,
+            age_months,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -601,25 +1451,11 @@ impl MaternalChildHealthContract {
             return Err(Error::InvalidData);
         }
 
-        let expected_weight = Self::expected_weight_kg_x100(age_months, &sex);
-        let expected_height = Self::expected_height_cm_x100(age_months, &sex);
-        let expected_hc = Self::expected_head_circumference_cm_x100(age_months, &sex);
-        let expected_bmi = Self::expected_bmi_x100(age_months, &sex);
-
-        let weight_percentile_x100 = Self::estimate_percentile(measurements.weight_kg_x100, expected_weight, 120);
-        let height_percentile_x100 =
-            Self::estimate_percentile(measurements.height_cm_x100, expected_height, 300);
-        let bmi_percentile_x100 = Self::estimate_percentile(measurements.bmi_x100, expected_bmi, 120);
-        let head_circ_pct_x100 = measurements
-            .head_circumference_cm_x100
-            .map(|hc| Self::estimate_percentile(hc, expected_hc, 180));
-
-        Ok(GrowthPercentiles {
-            weight_percentile_x100,
-            height_percentile_x100,
-            head_circ_pct_x100,
-            bmi_percentile_x100,
-        })
+        Ok(Self::growth_percentiles(
+            Some(&sex),
+            age_months,
+            &measurements,
+        ))
     }
 
     pub fn get_pregnancy_record(env: Env, pregnancy_id: u64) -> Result<PregnancyRecord, Error> {
@@ -684,6 +1520,32 @@ impl MaternalChildHealthContract {
             .ok_or(Error::NotFound)
     }
 
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Option<Address> = env.storage().instance().get(&DataKey::Admin);
+        if stored.as_ref() != Some(admin) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
     fn get_pregnancy(env: &Env, pregnancy_id: u64) -> Result<PregnancyRecord, Error> {
         env.storage()
             .persistent()
@@ -691,6 +1553,235 @@ impl MaternalChildHealthContract {
             .ok_or(Error::NotFound)
     }
 
+    fn get_labor_quality_for(
+        env: &Env,
+        labor_id: u64,
+        facility_id: &Address,
+    ) -> Result<LaborQualityRecord, Error> {
+        facility_id.require_auth();
+        let record: LaborQualityRecord = env
+            .storage()
+            .persistent()
+            .get(&DataKey::LaborQuality(labor_id))
+            .ok_or(Error::NotFound)?;
+        if record.facility_id != *facility_id {
+            return Err(Error::Unauthorized);
+        }
+        Ok(record)
+    }
+
+    fn default_visit_schedule(env: &Env) -> PrenatalVisitSchedule {
+        PrenatalVisitSchedule {
+            first_visit_week: 8,
+            intervals: Vec::from_array(
+                env,
+                [
+                    VisitInterval {
+                        until_week: 28,
+                        every_weeks: 4,
+                    },
+                    VisitInterval {
+                        until_week: 36,
+                        every_weeks: 2,
+                    },
+                    VisitInterval {
+                        until_week: 40,
+                        every_weeks: 1,
+                    },
+                ],
+            ),
+        }
+    }
+
+    /// Gestational age is dated from the EDD, which reflects any ultrasound
+    /// correction of the LMP date.
+    fn gestational_weeks_at(pregnancy: &PregnancyRecord, timestamp: u64) -> u32 {
+        let conception_start = pregnancy.edd.saturating_sub(EDD_WEEKS * WEEK_SECS);
+        (timestamp.saturating_sub(conception_start) / WEEK_SECS) as u32
+    }
+
+    fn expected_visit_weeks(env: &Env, schedule: &PrenatalVisitSchedule) -> Vec<u32> {
+        let mut weeks = Vec::new(env);
+        let mut week = schedule.first_visit_week;
+        weeks.push_back(week);
+        for interval in schedule.intervals.iter() {
+            while week < interval.until_week {
+                week = (week + interval.every_weeks).min(interval.until_week);
+                weeks.push_back(week);
+            }
+        }
+        weeks
+    }
+
+    /// An expected visit is due once its week has passed
+    fn visit_adherence(
+        env: &Env,
+        pregnancy: &PregnancyRecord,
+        schedule: &PrenatalVisitSchedule,
+    ) -> PrenatalVisitAdherence {
+        let gestational_age_weeks = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PregnancyEndWeek(pregnancy.pregnancy_id))
+            .unwrap_or_else(|| Self::gestational_weeks_at(pregnancy, env.ledger().timestamp()));
+
+        let mut visit_weeks: Vec<u32> = Vec::new(env);
+        for visit_id in pregnancy.prenatal_visits.iter() {
+            let visit: Option<PrenatalVisit> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PrenatalVisit(visit_id));
+            if let Some(visit) = visit {
+                visit_weeks.push_back(visit.gestational_age_weeks);
+            }
+        }
+        let first_visit_week = visit_weeks.iter().min();
+
+        let mut expected_visits = 0u32;
+        let mut attended_visits = 0u32;
+        let mut missed_weeks = Vec::new(env);
+        let mut window_start = 0u32;
+        for week in Self::expected_visit_weeks(env, schedule).iter() {
+            if week >= gestational_age_weeks {
+                break;
+            }
+            expected_visits += 1;
+            if visit_weeks
+                .iter()
+                .any(|visit_week| visit_week > window_start && visit_week <= week)
+            {
+                attended_visits += 1;
+            } else {
+                missed_weeks.push_back(week);
+            }
+            window_start = week;
+        }
+
+        let adherence_bps = Self::rate_bps(attended_visits, expected_visits);
+        let late_start = match first_visit_week {
+            Some(first) => first > CARE_INITIATION_MAX_WEEKS,
+            None => gestational_age_weeks > CARE_INITIATION_MAX_WEEKS,
+        };
+        let adequacy = if expected_visits == 0 {
+            PrenatalCareAdequacy::NotYetDue
+        } else if late_start || adherence_bps < INTERMEDIATE_VISITS_BPS {
+            PrenatalCareAdequacy::Inadequate
+        } else if adherence_bps < ADEQUATE_VISITS_BPS {
+            PrenatalCareAdequacy::Intermediate
+        } else {
+            PrenatalCareAdequacy::Adequate
+        };
+
+        PrenatalVisitAdherence {
+            pregnancy_id: pregnancy.pregnancy_id,
+            gestational_age_weeks,
+            first_visit_week,
+            visits_recorded: visit_weeks.len(),
+            expected_visits,
+            attended_visits,
+            missed_weeks,
+            adherence_bps,
+            adequacy,
+        }
+    }
+
+    fn rate_bps(count: u32, total: u32) -> u32 {
+        if total == 0 {
+            0
+        } else {
+            (u64::from(count) * 10_000 / u64::from(total)) as u32
+        }
+    }
+
+    fn get_open_pregnancy(env: &Env, pregnancy_id: u64) -> Result<PregnancyRecord, Error> {
+        let pregnancy = Self::get_pregnancy(env, pregnancy_id)?;
+        if pregnancy.outcome.is_some() {
+            return Err(Error::PregnancyClosed);
+        }
+        Ok(pregnancy)
+    }
+
+    fn update_outcome_stats(
+        env: &Env,
+        provider_id: &Address,
+        update: impl FnOnce(&mut PregnancyOutcomeStats),
+    ) {
+        let key = DataKey::ProviderOutcomeStats(provider_id.clone());
+        let mut stats: PregnancyOutcomeStats = env.storage().persistent().get(&key).unwrap_or_default();
+        update(&mut stats);
+        env.storage().persistent().set(&key, &stats);
+    }
+
+    fn require_registered_patient(env: &Env, patient_id: &Address) -> Result<(), Error> {
+        let config: Option<PatientRegistryConfig> =
+            env.storage().instance().get(&DataKey::PatientRegistry);
+        match config {
+            Some(config) if config.strict_mode => {
+                let client = PatientRegistryClient::new(env, &config.registry);
+                if client.is_patient_registered(patient_id) {
+                    Ok(())
+                } else {
+                    Err(Error::PatientNotRegistered)
+                }
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Apply a recorded screening to the newborn's mandated screen of the
+    /// same type, if one is owed. A rescreen replaces the earlier result.
+    fn complete_mandated_screen(
+        env: &Env,
+        newborn_id: &Address,
+        screening_type: &Symbol,
+        screening: &NewbornScreening,
+    ) {
+        let screen_type = if *screening_type == symbol_short!("hearing") {
+            MandatedScreenType::Hearing
+        } else if *screening_type == symbol_short!("cchd") {
+            MandatedScreenType::Cchd
+        } else {
+            return;
+        };
+        let key = DataKey::MandatedScreens(newborn_id.clone());
+        let mut record: NewbornMandatedScreens = match env.storage().persistent().get(&key) {
+            Some(record) => record,
+            None => return,
+        };
+
+        let passed = screening.result == symbol_short!("pass") && !screening.requires_followup;
+        for i in 0..record.screens.len() {
+            let mut screen = record.screens.get_unchecked(i);
+            if screen.screen_type != screen_type {
+                continue;
+            }
+            screen.status = if passed {
+                MandatedScreenStatus::Passed
+            } else {
+                MandatedScreenStatus::ReferralRequired
+            };
+            screen.screening_id = Some(screening.screening_id);
+            screen.completed_at = Some(screening.test_date);
+            screen.completed_in_window = screening.test_date <= screen.due_by;
+            record.screens.set(i, screen);
+        }
+        env.storage().persistent().set(&key, &record);
+
+        if !passed {
+            let referral_specialty = match screen_type {
+                MandatedScreenType::Hearing => Symbol::new(env, "audiology"),
+                MandatedScreenType::Cchd => Symbol::new(env, "cardiology"),
+            };
+            MandatedScreenFailed {
+                newborn_id: newborn_id.clone(),
+                facility_id: record.facility_id,
+                screen_type,
+                referral_specialty,
+            }
+            .publish(env);
+        }
+    }
+
     fn next_id(env: &Env, counter_key: Symbol) -> u64 {
         let next = env.storage().instance().get(&counter_key).unwrap_or(0u64) + 1;
         env.storage().instance().set(&counter_key, &next);
@@ -707,6 +1798,152 @@ impl MaternalChildHealthContract {
         env.deployer().with_current_contract(salt).deployed_address()
     }
 
+    /// Percentiles against the sex-specific reference, or the midpoint of
+    /// the two references when the sex is unknown.
+    fn growth_percentiles(
+        sex: Option<&Symbol>,
+        age_months: u32,
+        measurements: &PediatricMeasurements,
+    ) -> GrowthPercentiles {
+        let expected = |reference: fn(u32, &Symbol) -> i64| match sex {
+            Some(sex) => reference(age_months, sex),
+            None => {
+                (reference(age_months, &symbol_short!("male"))
+                    + reference(age_months, &symbol_short!("female")))
+                    / 2
+            }
+        };
+        let expected_weight = expected(Self::expected_weight_kg_x100);
+        let expected_height = expected(Self::expected_height_cm_x100);
+        let expected_hc = expected(Self::expected_head_circumference_cm_x100);
+        let expected_bmi = expected(Self::expected_bmi_x100);
+
+        let weight_percentile_x100 = Self::estimate_percentile(measurements.weight_kg_x100, expected_weight, 120);
+        let height_percentile_x100 =
+            Self::estimate_percentile(measurements.height_cm_x100, expected_height, 300);
+        let bmi_percentile_x100 = Self::estimate_percentile(measurements.bmi_x100, expected_bmi, 120);
+        let head_circ_pct_x100 = measurements
+            .head_circumference_cm_x100
+            .map(|hc| Self::estimate_percentile(hc, expected_hc, 180));
+
+        GrowthPercentiles {
+            weight_percentile_x100,
+            height_percentile_x100,
+            head_circ_pct_x100,
+            bmi_percentile_x100,
+        }
+    }
+
+    /// The patient's latest measurement taken at a younger age
+    fn prior_growth(
+        env: &Env,
+        patient_id: &Address,
+        age_months: u32,
+    ) -> Option<PediatricGrowthRecord> {
+        let growth_ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientGrowth(patient_id.clone()))
+            .unwrap_or(Vec::new(env));
+        let mut prior: Option<PediatricGrowthRecord> = None;
+        for growth_id in growth_ids.iter() {
+            let record: PediatricGrowthRecord =
+                match env.storage().persistent().get(&DataKey::Growth(growth_id)) {
+                    Some(record) => record,
+                    None => continue,
+                };
+            let is_later = match &prior {
+                Some(best) => record.age_months >= best.age_months,
+                None => true,
+            };
+            if record.age_months < age_months && is_later {
+                prior = Some(record);
+            }
+        }
+        prior
+    }
+
+    /// Flag weight faltering (two major percentile lines crossed downward
+    /// since the prior measurement) and BMI at or above the 95th percentile.
+    /// Recorded newborns are scored against their own sex's reference.
+    fn run_growth_surveillance(
+        env: &Env,
+        growth: &PediatricGrowthRecord,
+        prior: Option<PediatricGrowthRecord>,
+    ) {
+        let sex = env
+            .storage()
+            .persistent()
+            .get::<_, NewbornRecord>(&DataKey::Newborn(growth.patient_id.clone()))
+            .map(|newborn| newborn.sex);
+        let current =
+            Self::growth_percentiles(sex.as_ref(), growth.age_months, &growth.measurements);
+
+        if let Some(prior) = prior {
+            let prior_percentile =
+                Self::growth_percentiles(sex.as_ref(), prior.age_months, &prior.measurements)
+                    .weight_percentile_x100;
+            let lines_crossed = MAJOR_PERCENTILE_LINES
+                .iter()
+                .filter(|line| {
+                    prior_percentile >= **line && current.weight_percentile_x100 < **line
+                })
+                .count() as u32;
+            if lines_crossed >= 2 {
+                Self::raise_growth_alert(
+                    env,
+                    GrowthAlert {
+                        patient_id: growth.patient_id.clone(),
+                        growth_id: growth.growth_id,
+                        kind: GrowthAlertKind::WeightFaltering,
+                        percentile_x100: current.weight_percentile_x100,
+                        prior_growth_id: Some(prior.growth_id),
+                        prior_percentile_x100: Some(prior_percentile),
+                        lines_crossed,
+                        raised_at: env.ledger().timestamp(),
+                    },
+                );
+            }
+        }
+
+        if growth.age_months >= BMI_SCREEN_MIN_MONTHS
+            && current.bmi_percentile_x100 >= BMI_OBESITY_PERCENTILE_X100
+        {
+            Self::raise_growth_alert(
+                env,
+                GrowthAlert {
+                    patient_id: growth.patient_id.clone(),
+                    growth_id: growth.growth_id,
+                    kind: GrowthAlertKind::BmiObesity,
+                    percentile_x100: current.bmi_percentile_x100,
+                    prior_growth_id: None,
+                    prior_percentile_x100: None,
+                    lines_crossed: 0,
+                    raised_at: env.ledger().timestamp(),
+                },
+            );
+        }
+    }
+
+    fn raise_growth_alert(env: &Env, alert: GrowthAlert) {
+        let key = DataKey::GrowthAlerts(alert.patient_id.clone());
+        let mut alerts: Vec<GrowthAlert> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(env));
+        alerts.push_back(alert.clone());
+        env.storage().persistent().set(&key, &alerts);
+
+        GrowthAlertRaised {
+            patient_id: alert.patient_id,
+            growth_id: alert.growth_id,
+            kind: alert.kind,
+            percentile_x100: alert.percentile_x100,
+        }
+        .publish(env);
+    }
+
     fn estimate_percentile(value: i64, expected: i64, sd: i64) -> i64 {
         let delta = i128::from(value) - i128::from(expected);
         let score = 5000i128 + (delta * 2000i128) / i128::from(sd);
@@ -2,9 +2,26 @@
 
 use super::*;
 use soroban_sdk::{
-    testutils::Address as _, vec, Address, BytesN, Env, String, Symbol,
+    contract, contractimpl,
+    testutils::{Address as _, Events, Ledger},
+    vec, Address, BytesN, Env, String, Symbol,
 };
 
+// Minimal stand-in for the patient-registry contract
+#[contract]
+struct MockPatientRegistry;
+
+#[contractimpl]
+impl MockPatientRegistry {
+    pub fn register_patient(env: Env, wallet: Address) {
+        env.storage().persistent().set(&wallet, &true);
+    }
+
+    pub fn is_patient_registered(env: Env, wallet: Address) -> bool {
+        env.storage().persistent().has(&wallet)
+    }
+}
+
 fn setup() -> (Env, MaternalChildHealthContractClient<'static>) {
     let env = Env::default();
     env.mock_all_auths();
@@ -35,6 +52,7 @@ fn test_create_pregnancy_record() {
     let (patient, provider, id) = seed_pregnancy(&env, &client);
 
     assert_eq!(id, 1);
+    assert_eq!(env.events().all().len(), 1);
     let record = client.get_pregnancy_record(&id);
     assert_eq!(record.patient_id, patient);
     assert_eq!(record.provider_id, provider);
@@ -229,6 +247,352 @@ fn test_newborn_screening_success() {
     );
 }
 
+#[test]
+fn test_close_pregnancy_outcomes_and_stats() {
+    let (env, client) = setup();
+    let (_patient, provider, miscarried) = seed_pregnancy(&env, &client);
+    let (_patient, _provider, stillborn) = seed_pregnancy(&env, &client);
+
+    // Gestational age has to fit the outcome.
+    assert_eq!(
+        client.try_close_pregnancy(&miscarried, &provider, &PregnancyOutcome::Miscarriage, &24),
+        Err(Ok(Error::InvalidData))
+    );
+    assert_eq!(
+        client.try_close_pregnancy(&miscarried, &Address::generate(&env), &PregnancyOutcome::Miscarriage, &10),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.close_pregnancy(&miscarried, &provider, &PregnancyOutcome::Miscarriage, &10);
+    assert_eq!(
+        client.get_pregnancy_record(&miscarried).outcome,
+        Some(symbol_short!("miscarr"))
+    );
+
+    // A closed pregnancy takes no further visits, ultrasounds or closures.
+    assert_eq!(
+        client.try_record_prenatal_visit(
+            &miscarried,
+            &1_701_000_000,
+            &12,
+            &6500,
+            &String::from_str(&env, "110/70"),
+            &None,
+            &None,
+            &BytesN::from_array(&env, &[1; 32]),
+        ),
+        Err(Ok(Error::PregnancyClosed))
+    );
+    assert_eq!(
+        client.try_record_ultrasound(
+            &miscarried,
+            &1_701_000_000,
+            &12,
+            &None,
+            &Symbol::new(&env, "normal"),
+            &String::from_str(&env, "anterior"),
+            &BytesN::from_array(&env, &[2; 32]),
+        ),
+        Err(Ok(Error::PregnancyClosed))
+    );
+    assert_eq!(
+        client.try_close_pregnancy(&miscarried, &provider, &PregnancyOutcome::Termination, &10),
+        Err(Ok(Error::PregnancyClosed))
+    );
+
+    let stillborn_provider = client.get_pregnancy_record(&stillborn).provider_id;
+    client.close_pregnancy(&stillborn, &stillborn_provider, &PregnancyOutcome::Stillbirth, &32);
+
+    let stats = client.get_provider_outcome_stats(&provider);
+    assert_eq!(stats.miscarriage, 1);
+    assert_eq!(stats.stillbirth, 0);
+    assert_eq!(client.get_provider_outcome_stats(&stillborn_provider).stillbirth, 1);
+}
+
+#[test]
+fn test_gbs_and_steroid_prophylaxis_compliance() {
+    let (env, client) = setup();
+    let (_patient, provider, pregnancy_id) = seed_pregnancy(&env, &client);
+    let facility = Address::generate(&env);
+
+    let admit = |admission_date: u64| {
+        client.document_labor_admission(
+            &pregnancy_id,
+            &admission_date,
+            &true,
+            &Symbol::new(&env, "intact"),
+            &4,
+            &80,
+        )
+    };
+    let deliver = |labor_id: u64, delivered_at: u64| {
+        client.record_delivery(
+            &labor_id,
+            &delivered_at,
+            &Symbol::new(&env, "vaginal"),
+            &Symbol::new(&env, "vertex"),
+            &vec![&env],
+            &300,
+            &provider,
+        );
+    };
+
+    // GBS positive at term, antibiotics six hours before delivery.
+    let adequate = admit(1_724_900_000);
+    client.record_gbs_status(&adequate, &facility, &GbsStatus::Positive, &39);
+    client.record_intrapartum_antibiotics(&adequate, &facility, &1_724_900_000);
+    deliver(adequate, 1_724_921_600);
+
+    // Preterm with unknown status: antibiotics an hour before delivery, and
+    // steroids given.
+    let late = admit(1_724_950_000);
+    client.record_gbs_status(&late, &facility, &GbsStatus::Unknown, &30);
+    client.record_intrapartum_antibiotics(&late, &facility, &1_724_950_000);
+    client.record_antenatal_steroids(&late, &facility, &1_724_950_100);
+    deliver(late, 1_724_953_600);
+
+    // Preterm GBS negative without steroids, still in labor.
+    let no_steroids = admit(1_725_000_000);
+    client.record_gbs_status(&no_steroids, &facility, &GbsStatus::Negative, &32);
+
+    // Steroids are only for preterm labor, and only the tracking facility
+    // can add to the record.
+    assert_eq!(
+        client.try_record_antenatal_steroids(&adequate, &facility, &1_724_900_100),
+        Err(Ok(Error::InvalidData))
+    );
+    assert_eq!(
+        client.try_record_intrapartum_antibiotics(&no_steroids, &Address::generate(&env), &1_725_000_100),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    let compliance = client.get_prophylaxis_compliance(&facility);
+    assert_eq!(compliance.gbs_indicated, 2);
+    assert_eq!(compliance.gbs_adequate, 1);
+    assert_eq!(compliance.gbs_compliance_bps, 5_000);
+    assert_eq!(compliance.steroids_indicated, 2);
+    assert_eq!(compliance.steroids_given, 1);
+    assert_eq!(compliance.steroid_compliance_bps, 5_000);
+}
+
+// Gestational week 0 for pregnancies seeded with an EDD of 1_725_000_000
+const SEED_WEEK_ZERO: u64 = 1_725_000_000 - 40 * WEEK_SECS;
+
+fn record_visit_at_week(
+    env: &Env,
+    client: &MaternalChildHealthContractClient<'static>,
+    pregnancy_id: u64,
+    week: u32,
+) {
+    client.record_prenatal_visit(
+        &pregnancy_id,
+        &(SEED_WEEK_ZERO + u64::from(week) * WEEK_SECS),
+        &week,
+        &6_850,
+        &String::from_str(env, "118/74"),
+        &None,
+        &None,
+        &BytesN::from_array(env, &[11u8; 32]),
+    );
+}
+
+#[test]
+fn test_prenatal_visit_adherence() {
+    let (env, client) = setup();
+    let (_patient, _provider, pregnancy_id) = seed_pregnancy(&env, &client);
+    env.ledger().set_timestamp(SEED_WEEK_ZERO + 21 * WEEK_SECS);
+
+    for week in [8, 12, 20] {
+        record_visit_at_week(&env, &client, pregnancy_id, week);
+    }
+
+    let adherence = client.get_prenatal_visit_adherence(&pregnancy_id);
+    assert_eq!(adherence.gestational_age_weeks, 21);
+    assert_eq!(adherence.first_visit_week, Some(8));
+    assert_eq!(adherence.visits_recorded, 3);
+    assert_eq!(adherence.expected_visits, 4);
+    assert_eq!(adherence.attended_visits, 3);
+    assert_eq!(adherence.missed_weeks, vec![&env, 16]);
+    assert_eq!(adherence.adherence_bps, 7_500);
+    assert_eq!(adherence.adequacy, PrenatalCareAdequacy::Intermediate);
+}
+
+#[test]
+fn test_facility_prenatal_care_stats() {
+    let (env, client) = setup();
+    let facility = Address::generate(&env);
+    env.ledger().set_timestamp(SEED_WEEK_ZERO + 21 * WEEK_SECS);
+
+    let (_, _, intermediate) = seed_pregnancy(&env, &client);
+    for week in [8, 12, 20] {
+        record_visit_at_week(&env, &client, intermediate, week);
+    }
+
+    // Ended at 22 weeks, so later visits are never due
+    let (_, provider, adequate) = seed_pregnancy(&env, &client);
+    for week in [8, 12, 16, 20] {
+        record_visit_at_week(&env, &client, adequate, week);
+    }
+    client.close_pregnancy(&adequate, &provider, &PregnancyOutcome::Stillbirth, &22);
+
+    // Care begun at 18 weeks
+    let (_, _, inadequate) = seed_pregnancy(&env, &client);
+    record_visit_at_week(&env, &client, inadequate, 18);
+
+    // Five weeks along, nothing due yet
+    let early = client.create_pregnancy_record(
+        &Address::generate(&env),
+        &Address::generate(&env),
+        &(env.ledger().timestamp() - 5 * WEEK_SECS),
+        &(env.ledger().timestamp() + 35 * WEEK_SECS),
+        &1,
+        &0,
+        &vec![&env],
+    );
+
+    for pregnancy_id in [intermediate, adequate, inadequate, early] {
+        client.enroll_prenatal_care(&pregnancy_id, &facility);
+    }
+    assert_eq!(
+        client.try_enroll_prenatal_care(&adequate, &facility),
+        Err(Ok(Error::AlreadyExists))
+    );
+    assert_eq!(
+        client.get_prenatal_visit_adherence(&inadequate).adequacy,
+        PrenatalCareAdequacy::Inadequate
+    );
+
+    let stats = client.get_facility_prenatal_care_stats(&facility);
+    assert_eq!(stats.pregnancies, 4);
+    assert_eq!(stats.not_yet_due, 1);
+    assert_eq!(stats.adequate, 1);
+    assert_eq!(stats.intermediate, 1);
+    assert_eq!(stats.inadequate, 1);
+    assert_eq!(stats.expected_visits, 12);
+    assert_eq!(stats.attended_visits, 8);
+    assert_eq!(stats.adherence_bps, 6_666);
+    assert_eq!(stats.adequate_care_bps, 3_333);
+}
+
+#[test]
+fn test_configure_prenatal_visit_schedule() {
+    let (env, client) = setup();
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let default = client.get_prenatal_visit_schedule();
+    assert_eq!(default.first_visit_week, 8);
+    assert_eq!(default.intervals.len(), 3);
+
+    let schedule = PrenatalVisitSchedule {
+        first_visit_week: 10,
+        intervals: vec![
+            &env,
+            VisitInterval {
+                until_week: 30,
+                every_weeks: 10,
+            },
+        ],
+    };
+    let result = client.try_set_prenatal_visit_schedule(&Address::generate(&env), &schedule);
+    assert_eq!(result, Err(Ok(Error::Unauthorized)));
+
+    let bad = PrenatalVisitSchedule {
+        first_visit_week: 10,
+        intervals: vec![
+            &env,
+            VisitInterval {
+                until_week: 8,
+                every_weeks: 2,
+            },
+        ],
+    };
+    let result = client.try_set_prenatal_visit_schedule(&admin, &bad);
+    assert_eq!(result, Err(Ok(Error::InvalidData)));
+
+    client.set_prenatal_visit_schedule(&admin, &schedule);
+    assert_eq!(env.events().all().len(), 1);
+    assert_eq!(client.get_prenatal_visit_schedule(), schedule);
+
+    let (_patient, _provider, pregnancy_id) = seed_pregnancy(&env, &client);
+    env.ledger().set_timestamp(SEED_WEEK_ZERO + 21 * WEEK_SECS);
+    for week in [10, 20] {
+        record_visit_at_week(&env, &client, pregnancy_id, week);
+    }
+    let adherence = client.get_prenatal_visit_adherence(&pregnancy_id);
+    assert_eq!(adherence.expected_visits, 2);
+    assert_eq!(adherence.attended_visits, 2);
+    assert_eq!(adherence.adequacy, PrenatalCareAdequacy::Adequate);
+}
+
+#[test]
+fn test_mandated_screens_track_windows_and_flag_failures() {
+    let (env, client) = setup();
+    let (_patient, provider, pregnancy_id) = seed_pregnancy(&env, &client);
+    let facility = Address::generate(&env);
+
+    let labor_id = client.document_labor_admission(
+        &pregnancy_id,
+        &1_724_900_000,
+        &true,
+        &Symbol::new(&env, "intact"),
+        &5,
+        &85,
+    );
+    let delivery_id = client.record_delivery(
+        &labor_id,
+        &1_725_000_000,
+        &Symbol::new(&env, "vaginal"),
+        &Symbol::new(&env, "vertex"),
+        &vec![&env],
+        &275,
+        &provider,
+    );
+    let first = client.record_newborn(
+        &delivery_id, &1_725_000_000, &symbol_short!("female"), &3100, &50, &34, &8, &9, &39,
+    );
+    let second = client.record_newborn(
+        &delivery_id, &1_725_000_000, &symbol_short!("male"), &3300, &51, &35, &8, &9, &39,
+    );
+    client.require_mandated_screens(&facility, &first);
+    client.require_mandated_screens(&facility, &second);
+    assert_eq!(
+        client.try_require_mandated_screens(&facility, &first),
+        Err(Ok(Error::AlreadyExists))
+    );
+    assert_eq!(client.get_incomplete_mandated_screens(&facility, &0, &10).len(), 2);
+
+    // Hearing passes in time; CCHD is done after the 48 hour window and fails.
+    client.record_newborn_screening(
+        &first,
+        &Symbol::new(&env, "hearing"),
+        &1_725_010_000,
+        &Symbol::new(&env, "pass"),
+        &false,
+    );
+    client.record_newborn_screening(
+        &first,
+        &Symbol::new(&env, "cchd"),
+        &1_725_200_000,
+        &Symbol::new(&env, "fail"),
+        &true,
+    );
+    // The failed screen also raises the referral flag.
+    assert_eq!(env.events().all().len(), 2);
+
+    let screens = client.get_mandated_screens(&first).screens;
+    let hearing = screens.get(0).unwrap();
+    assert_eq!(hearing.status, MandatedScreenStatus::Passed);
+    assert!(hearing.completed_in_window);
+    let cchd = screens.get(1).unwrap();
+    assert_eq!(cchd.status, MandatedScreenStatus::ReferralRequired);
+    assert_eq!(cchd.completed_at, Some(1_725_200_000));
+    assert!(!cchd.completed_in_window);
+
+    let incomplete = client.get_incomplete_mandated_screens(&facility, &0, &10);
+    assert_eq!(incomplete.len(), 1);
+    assert_eq!(incomplete.get(0).unwrap().newborn_id, second);
+}
+
 #[test]
 fn test_pediatric_growth_milestones_well_child() {
     let (env, client) = setup();
@@ -354,6 +718,59 @@ fn test_calculate_growth_percentiles() {
     assert!(bad.is_err());
 }
 
+#[test]
+fn test_growth_weight_faltering_alert() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+
+    // On the 50th weight percentile at 6 months
+    client.track_pediatric_growth(&patient, &1_730_000_000, &6, &720, &6600, &None, &1680);
+    assert_eq!(client.get_growth_alerts(&patient).len(), 0);
+
+    // Small dip at 7 months crosses only the 50th line
+    client.track_pediatric_growth(&patient, &1_732_600_000, &7, &730, &6850, &None, &1680);
+    assert_eq!(client.get_growth_alerts(&patient).len(), 0);
+
+    // Below the 10th percentile by 9 months: 25th and 10th lines crossed
+    client.track_pediatric_growth(&patient, &1_737_800_000, &9, &640, &7350, &None, &1680);
+    assert_eq!(env.events().all().len(), 2);
+
+    let alerts = client.get_growth_alerts(&patient);
+    assert_eq!(alerts.len(), 1);
+    let alert = alerts.get(0).unwrap();
+    assert_eq!(alert.kind, GrowthAlertKind::WeightFaltering);
+    assert_eq!(
+        alert.growth_id,
+        client.get_growth_record(&patient, &9).growth_id
+    );
+    assert_eq!(
+        alert.prior_growth_id,
+        Some(client.get_growth_record(&patient, &7).growth_id)
+    );
+    assert_eq!(alert.lines_crossed, 2);
+    assert!(alert.percentile_x100 < 1000);
+}
+
+#[test]
+fn test_growth_bmi_obesity_alert() {
+    let (env, client) = setup();
+    let patient = Address::generate(&env);
+
+    // BMI percentiles are not applied before two years of age
+    client.track_pediatric_growth(&patient, &1_730_000_000, &12, &980, &7550, &None, &2000);
+    assert_eq!(client.get_growth_alerts(&patient).len(), 0);
+
+    client.track_pediatric_growth(&patient, &1_760_000_000, &36, &1680, &9400, &None, &2000);
+    assert_eq!(env.events().all().len(), 2);
+
+    let alerts = client.get_growth_alerts(&patient);
+    assert_eq!(alerts.len(), 1);
+    let alert = alerts.get(0).unwrap();
+    assert_eq!(alert.kind, GrowthAlertKind::BmiObesity);
+    assert!(alert.percentile_x100 >= 9500);
+    assert_eq!(alert.prior_growth_id, None);
+}
+
 #[test]
 fn test_nonexistent_getters_fail() {
     let (env, client) = setup();
@@ -369,3 +786,50 @@ fn test_nonexistent_getters_fail() {
     assert!(res3.is_err());
     assert!(res_growth.is_err());
 }
+
+#[test]
+fn test_strict_mode_requires_registered_patient() {
+    let (env, client) = setup();
+    let registry_id = env.register(MockPatientRegistry, ());
+    let registry = MockPatientRegistryClient::new(&env, &registry_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_patient_registry(&admin, &registry_id, &true);
+
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let result = client.try_create_pregnancy_record(
+        &patient,
+        &provider,
+        &1_700_000_000,
+        &1_725_000_000,
+        &1,
+        &0,
+        &vec![&env],
+    );
+    assert_eq!(result, Err(Ok(Error::PatientNotRegistered)));
+
+    let child = Address::generate(&env);
+    let growth = client.try_track_pediatric_growth(
+        &child,
+        &1_700_000_000,
+        &12,
+        &950,
+        &7_500,
+        &None,
+        &1_690,
+    );
+    assert_eq!(growth, Err(Ok(Error::PatientNotRegistered)));
+
+    registry.register_patient(&patient);
+    let id = client.create_pregnancy_record(
+        &patient,
+        &provider,
+        &1_700_000_000,
+        &1_725_000_000,
+        &1,
+        &0,
+        &vec![&env],
+    );
+    assert_eq!(client.get_pregnancy_record(&id).patient_id, patient);
+}
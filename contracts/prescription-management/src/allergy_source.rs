@@ -0,0 +1,26 @@
+//! Client interface for the allergy-management contract, used to screen new
+//! prescriptions against the patient's recorded drug allergies.
+//!
+//! `AllergyInteraction` mirrors allergy-management's type so values decode
+//! across the contract boundary; keep it in sync with that contract.
+
+use soroban_sdk::{Address, Env, String, Symbol, Vec, contractclient, contracttype};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AllergyInteraction {
+    pub allergy_id: u64,
+    pub allergen: String,
+    pub severity: Symbol,
+    pub reaction_type: Vec<String>,
+    pub interaction_type: Symbol,
+}
+
+#[contractclient(name = "AllergySourceClient")]
+pub trait AllergySource {
+    fn check_drug_allergy_interaction(
+        env: Env,
+        patient_id: Address,
+        drug_name: String,
+    ) -> Vec<AllergyInteraction>;
+}
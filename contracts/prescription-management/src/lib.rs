@@ -1,10 +1,17 @@
 #![no_std]
 
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractimpl, contracttype,
-    panic_with_error,
+    Address, BytesN, Env, String, Symbol, Vec, contract, contracterror, contractevent,
+    contractimpl, contracttype, panic_with_error,
 };
 
+mod allergy_source;
+
+pub use allergy_source::{AllergyInteraction, AllergySource, AllergySourceClient};
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -17,6 +24,10 @@ pub enum Error {
     InvalidSeverity = 6,
     InteractionNotFound = 7,
     MissingOverrideReason = 8,
+    InvalidState = 9,
+    NoRefillsRemaining = 10,
+    ControlledSubstanceRestriction = 11,
+    AllergyConflict = 12,
 }
 
 #[contracttype]
@@ -74,6 +85,12 @@ pub enum DataKey {
     PatientConditions(Address),
     MedicationContraindications(String),
     InteractionOverride(u64, Address),
+    Admin,
+    AllergySource,
+    ControlledPrescriber(Address),
+    DispenseHistory(u64),
+    RefillRequestCounter,
+    RefillRequest(u64),
 }
 
 #[contracttype]
@@ -83,6 +100,7 @@ pub enum PrescriptionStatus {
     Dispensed,
     Expired,
     Transferred,
+    Cancelled,
 }
 
 #[contracttype]
@@ -91,13 +109,72 @@ pub struct Prescription {
     pub provider_id: Address,
     pub patient_id: Address,
     pub medication_name: String,
+    pub ndc_code: String,
+    pub dosage: String,
+    pub route: Symbol,
     pub quantity: u32,
+    pub days_supply: u32,
     pub refills_remaining: u32,
+    pub fills: u32,
     pub is_controlled: bool,
+    pub schedule: Option<u32>,
+    pub substitution_allowed: bool,
     pub current_pharmacy: Option<Address>,
     pub status: PrescriptionStatus,
     pub valid_until: u64,
-    // Add additional fields here as needed
+    pub created_at: u64,
+    pub allergy_override_reason: Option<String>,
+    pub cancel_reason: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DispenseEvent {
+    pub prescription_id: u64,
+    pub pharmacy_id: Address,
+    pub quantity: u32,
+    pub lot: String,
+    pub fill_number: u32,
+    pub dispensed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum RefillStatus {
+    Pending,
+    Approved,
+    Denied,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefillRequest {
+    pub request_id: u64,
+    pub prescription_id: u64,
+    pub requested_by: Address,
+    pub requested_at: u64,
+    pub status: RefillStatus,
+    pub refills_granted: u32,
+    pub decided_at: Option<u64>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrescriptionDispensed {
+    #[topic]
+    pub prescription_id: u64,
+    pub pharmacy_id: Address,
+    pub quantity: u32,
+    pub fill_number: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PrescriptionCancelled {
+    #[topic]
+    pub prescription_id: u64,
+    pub provider_id: Address,
+    pub reason: String,
 }
 
 // Struct to bypass the 10-parameter limit
@@ -106,14 +183,16 @@ pub struct IssueRequest {
     pub medication_name: String,
     pub ndc_code: String,
     pub dosage: String,
+    pub route: Symbol,
     pub quantity: u32,
     pub days_supply: u32,
     pub refills_allowed: u32,
     pub instructions_hash: BytesN<32>,
     pub is_controlled: bool,
-    pub schedule: Option<u32>,
+    pub schedule: Option<u32>, // DEA schedule 2-5 for controlled substances
     pub valid_until: u64,
     pub substitution_allowed: bool,
+    pub allergy_override_reason: Option<String>,
 }
 
 #[contract]
@@ -121,6 +200,42 @@ pub struct PrescriptionContract;
 
 #[contractimpl]
 impl PrescriptionContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyExists);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Allow a provider to prescribe controlled substances
+    pub fn authorize_controlled_prescriber(
+        env: Env,
+        admin: Address,
+        provider_id: Address,
+        authorized: bool,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        let key = DataKey::ControlledPrescriber(provider_id);
+        if authorized {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        Ok(())
+    }
+
+    /// Screen new prescriptions against an allergy-management contract
+    pub fn set_allergy_source(env: Env, admin: Address, source: Address) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::AllergySource, &source);
+        Ok(())
+    }
+
     pub fn issue_prescription(
         env: Env,
         provider_id: Address,
@@ -129,6 +244,36 @@ impl PrescriptionContract {
     ) -> u64 {
         provider_id.require_auth();
 
+        if req.quantity == 0 {
+            panic_with_error!(&env, Error::InvalidPrescription);
+        }
+        if req.is_controlled {
+            let valid_schedule = matches!(req.schedule, Some(2..=5));
+            let authorized = env
+                .storage()
+                .persistent()
+                .has(&DataKey::ControlledPrescriber(provider_id.clone()));
+            if !valid_schedule || !authorized {
+                panic_with_error!(&env, Error::ControlledSubstanceRestriction);
+            }
+            // Schedule II prescriptions cannot carry refills
+            if req.schedule == Some(2) && req.refills_allowed > 0 {
+                panic_with_error!(&env, Error::ControlledSubstanceRestriction);
+            }
+        }
+
+        if let Some(source) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::AllergySource)
+        {
+            let interactions = AllergySourceClient::new(&env, &source)
+                .check_drug_allergy_interaction(&patient_id, &req.medication_name);
+            if !interactions.is_empty() && req.allergy_override_reason.is_none() {
+                panic_with_error!(&env, Error::AllergyConflict);
+            }
+        }
+
         let id = env
             .storage()
             .instance()
@@ -139,12 +284,22 @@ impl PrescriptionContract {
             provider_id,
             patient_id,
             medication_name: req.medication_name,
+            ndc_code: req.ndc_code,
+            dosage: req.dosage,
+            route: req.route,
             quantity: req.quantity,
+            days_supply: req.days_supply,
             refills_remaining: req.refills_allowed,
+            fills: 0,
             is_controlled: req.is_controlled,
+            schedule: req.schedule,
+            substitution_allowed: req.substitution_allowed,
             current_pharmacy: None,
             status: PrescriptionStatus::Active,
             valid_until: req.valid_until,
+            created_at: env.ledger().timestamp(),
+            allergy_override_reason: req.allergy_override_reason,
+            cancel_reason: None,
         };
 
         env.storage().persistent().set(&id, &prescription);
@@ -155,29 +310,106 @@ impl PrescriptionContract {
         id
     }
 
+    pub fn get_prescription(env: Env, prescription_id: u64) -> Prescription {
+        load_prescription(&env, prescription_id)
+    }
+
+    /// Patient routes the prescription to a pharmacy before the first fill
+    pub fn assign_pharmacy(env: Env, prescription_id: u64, pharmacy_id: Address) {
+        let mut p = load_prescription(&env, prescription_id);
+        p.patient_id.require_auth();
+
+        if p.status != PrescriptionStatus::Active || p.fills > 0 {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+
+        p.current_pharmacy = Some(pharmacy_id);
+        env.storage().persistent().set(&prescription_id, &p);
+    }
+
+    /// Dispense a fill. The first fill uses the original order; later fills
+    /// consume a refill. Controlled substances also require the patient's signature.
     pub fn dispense_prescription(
         env: Env,
         prescription_id: u64,
         pharmacy_id: Address,
-        _quantity: u32,
-        _lot: String,
+        quantity: u32,
+        lot: String,
     ) {
         pharmacy_id.require_auth();
 
-        let mut p: Prescription = env
-            .storage()
-            .persistent()
-            .get(&prescription_id)
-            .expect("Prescription not found");
+        let mut p = load_prescription(&env, prescription_id);
 
         if env.ledger().timestamp() > p.valid_until {
             panic_with_error!(&env, Error::Expired);
         }
+        if matches!(
+            p.status,
+            PrescriptionStatus::Cancelled | PrescriptionStatus::Expired
+        ) {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+        if p.current_pharmacy
+            .as_ref()
+            .is_some_and(|a| *a != pharmacy_id)
+        {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if quantity == 0 || quantity > p.quantity {
+            panic_with_error!(&env, Error::InvalidPrescription);
+        }
+        if p.is_controlled {
+            p.patient_id.require_auth();
+        }
+        if p.fills > 0 {
+            if p.refills_remaining == 0 {
+                panic_with_error!(&env, Error::NoRefillsRemaining);
+            }
+            p.refills_remaining -= 1;
+        }
 
+        p.fills += 1;
         p.status = PrescriptionStatus::Dispensed;
-        p.current_pharmacy = Some(pharmacy_id);
-
+        p.current_pharmacy = Some(pharmacy_id.clone());
         env.storage().persistent().set(&prescription_id, &p);
+
+        let key = DataKey::DispenseHistory(prescription_id);
+        let mut history: Vec<DispenseEvent> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(DispenseEvent {
+            prescription_id,
+            pharmacy_id: pharmacy_id.clone(),
+            quantity,
+            lot,
+            fill_number: p.fills,
+            dispensed_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&key, &history);
+
+        PrescriptionDispensed {
+            prescription_id,
+            pharmacy_id,
+            quantity,
+            fill_number: p.fills,
+        }
+        .publish(&env);
+    }
+
+    pub fn get_dispense_history(
+        env: Env,
+        prescription_id: u64,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<DispenseEvent> {
+        let history = env
+            .storage()
+            .persistent()
+            .get(&DataKey::DispenseHistory(prescription_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&history, offset, limit)
     }
 
     pub fn transfer_prescription(
@@ -188,7 +420,14 @@ impl PrescriptionContract {
     ) {
         from_pharmacy.require_auth();
 
-        let mut p: Prescription = env.storage().persistent().get(&prescription_id).unwrap();
+        let mut p = load_prescription(&env, prescription_id);
+
+        if p.current_pharmacy != Some(from_pharmacy) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if p.schedule == Some(2) {
+            panic_with_error!(&env, Error::ControlledSubstanceRestriction);
+        }
 
         p.current_pharmacy = Some(to_pharmacy);
         p.status = PrescriptionStatus::Transferred;
@@ -196,6 +435,125 @@ impl PrescriptionContract {
         env.storage().persistent().set(&prescription_id, &p);
     }
 
+    /// Patient or their pharmacy asks the prescriber for more refills
+    pub fn request_refill(env: Env, prescription_id: u64, requester: Address) -> u64 {
+        requester.require_auth();
+
+        let p = load_prescription(&env, prescription_id);
+        if requester != p.patient_id && p.current_pharmacy != Some(requester.clone()) {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if p.status == PrescriptionStatus::Cancelled {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+
+        let request_id = env
+            .storage()
+            .instance()
+            .get::<_, u64>(&DataKey::RefillRequestCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::RefillRequestCounter, &request_id);
+
+        let request = RefillRequest {
+            request_id,
+            prescription_id,
+            requested_by: requester,
+            requested_at: env.ledger().timestamp(),
+            status: RefillStatus::Pending,
+            refills_granted: 0,
+            decided_at: None,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefillRequest(request_id), &request);
+
+        request_id
+    }
+
+    /// Prescriber decides a pending refill request; approval adds `refills` fills
+    pub fn respond_refill_request(
+        env: Env,
+        request_id: u64,
+        provider_id: Address,
+        approve: bool,
+        refills: u32,
+    ) {
+        provider_id.require_auth();
+
+        let key = DataKey::RefillRequest(request_id);
+        let mut request: RefillRequest = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| panic_with_error!(&env, Error::NotFound));
+        if request.status != RefillStatus::Pending {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+
+        let mut p = load_prescription(&env, request.prescription_id);
+        if p.provider_id != provider_id {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+
+        if approve {
+            if refills == 0 {
+                panic_with_error!(&env, Error::InvalidPrescription);
+            }
+            if p.schedule == Some(2) {
+                panic_with_error!(&env, Error::ControlledSubstanceRestriction);
+            }
+            p.refills_remaining += refills;
+            env.storage().persistent().set(&request.prescription_id, &p);
+            request.status = RefillStatus::Approved;
+            request.refills_granted = refills;
+        } else {
+            request.status = RefillStatus::Denied;
+        }
+
+        request.decided_at = Some(env.ledger().timestamp());
+        env.storage().persistent().set(&key, &request);
+    }
+
+    pub fn get_refill_request(env: Env, request_id: u64) -> Option<RefillRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefillRequest(request_id))
+    }
+
+    pub fn cancel_prescription(
+        env: Env,
+        prescription_id: u64,
+        provider_id: Address,
+        reason: String,
+    ) {
+        provider_id.require_auth();
+
+        let mut p = load_prescription(&env, prescription_id);
+        if p.provider_id != provider_id {
+            panic_with_error!(&env, Error::Unauthorized);
+        }
+        if matches!(
+            p.status,
+            PrescriptionStatus::Cancelled | PrescriptionStatus::Expired
+        ) {
+            panic_with_error!(&env, Error::InvalidState);
+        }
+
+        p.status = PrescriptionStatus::Cancelled;
+        p.cancel_reason = Some(reason.clone());
+        env.storage().persistent().set(&prescription_id, &p);
+
+        PrescriptionCancelled {
+            prescription_id,
+            provider_id,
+            reason,
+        }
+        .publish(&env);
+    }
+
     pub fn register_medication(
         env: Env,
         ndc_code: String,
@@ -487,6 +845,43 @@ impl PrescriptionContract {
         );
         Ok(())
     }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn load_prescription(env: &Env, prescription_id: u64) -> Prescription {
+    env.storage()
+        .persistent()
+        .get(&prescription_id)
+        .unwrap_or_else(|| panic_with_error!(env, Error::NotFound))
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::Unauthorized)?;
+    if stored != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
 }
 
 fn is_valid_severity(env: &Env, severity: &Symbol) -> bool {
@@ -3,11 +3,59 @@
 use super::*;
 // Note the inclusion of 'Ledger' and 'Address' as traits here
 use soroban_sdk::{
-    Address, BytesN, Env, String, Symbol,
+    Address, BytesN, Env, String, Symbol, Vec, contract, contractimpl,
     testutils::{Address as _, Ledger as _},
     vec,
 };
 
+// Stand-in for allergy-management that flags every drug named "Penicillin"
+#[contract]
+struct MockAllergySource;
+
+#[contractimpl]
+impl MockAllergySource {
+    pub fn check_drug_allergy_interaction(
+        env: Env,
+        _patient_id: Address,
+        drug_name: String,
+    ) -> Vec<AllergyInteraction> {
+        let mut interactions = Vec::new(&env);
+        if drug_name == String::from_str(&env, "Penicillin") {
+            interactions.push_back(AllergyInteraction {
+                allergy_id: 1,
+                allergen: drug_name,
+                severity: Symbol::new(&env, "severe"),
+                reaction_type: Vec::new(&env),
+                interaction_type: Symbol::new(&env, "direct"),
+            });
+        }
+        interactions
+    }
+}
+
+// Panicking entrypoints surface contract errors as `soroban_sdk::Error`
+fn contract_error(error: Error) -> soroban_sdk::Error {
+    soroban_sdk::Error::from_contract_error(error as u32)
+}
+
+fn issue_request(env: &Env, medication: &str, refills: u32, schedule: Option<u32>) -> IssueRequest {
+    IssueRequest {
+        medication_name: String::from_str(env, medication),
+        ndc_code: String::from_str(env, "0000-0000-00"),
+        dosage: String::from_str(env, "10mg"),
+        route: Symbol::new(env, "oral"),
+        quantity: 30,
+        days_supply: 30,
+        refills_allowed: refills,
+        instructions_hash: BytesN::from_array(env, &[0u8; 32]),
+        is_controlled: schedule.is_some(),
+        schedule,
+        valid_until: 100_000,
+        substitution_allowed: true,
+        allergy_override_reason: None,
+    }
+}
+
 #[test]
 fn test_prescription_lifecycle() {
     let env = Env::default();
@@ -25,6 +73,7 @@ fn test_prescription_lifecycle() {
         medication_name: String::from_str(&env, "Amoxicillin"),
         ndc_code: String::from_str(&env, "0501-1234-01"),
         dosage: String::from_str(&env, "500mg"),
+        route: Symbol::new(&env, "oral"),
         quantity: 30,
         days_supply: 10,
         refills_allowed: 2,
@@ -33,6 +82,7 @@ fn test_prescription_lifecycle() {
         schedule: None,
         valid_until: 1000,
         substitution_allowed: true,
+        allergy_override_reason: None,
     };
 
     let prescription_id = client.issue_prescription(&provider, &patient, &request);
@@ -68,6 +118,7 @@ fn test_fail_expired_prescription() {
         medication_name: String::from_str(&env, "Advil"),
         ndc_code: String::from_str(&env, "123"),
         dosage: String::from_str(&env, "200mg"),
+        route: Symbol::new(&env, "oral"),
         quantity: 10,
         days_supply: 5,
         refills_allowed: 0,
@@ -76,6 +127,7 @@ fn test_fail_expired_prescription() {
         schedule: None,
         valid_until: 500,
         substitution_allowed: true,
+        allergy_override_reason: None,
     };
 
     let id = client.issue_prescription(&provider, &patient, &request);
@@ -294,3 +346,168 @@ fn test_invalid_severity_rejected() {
 
     assert_eq!(result, Err(Ok(Error::InvalidSeverity)));
 }
+
+#[test]
+fn test_refill_request_and_dispense_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PrescriptionContract, ());
+    let client = PrescriptionContractClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let pharmacy = Address::generate(&env);
+
+    let id = client.issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Lisinopril", 1, None),
+    );
+    client.assign_pharmacy(&id, &pharmacy);
+
+    // Another pharmacy cannot fill a routed prescription
+    let other = Address::generate(&env);
+    let result = client.try_dispense_prescription(&id, &other, &30, &String::from_str(&env, "L0"));
+    assert!(result.is_err());
+
+    client.dispense_prescription(&id, &pharmacy, &30, &String::from_str(&env, "L1"));
+    client.dispense_prescription(&id, &pharmacy, &30, &String::from_str(&env, "L2"));
+    assert_eq!(client.get_prescription(&id).refills_remaining, 0);
+
+    let exhausted =
+        client.try_dispense_prescription(&id, &pharmacy, &30, &String::from_str(&env, "L3"));
+    assert_eq!(
+        exhausted,
+        Err(Ok(contract_error(Error::NoRefillsRemaining)))
+    );
+
+    let request_id = client.request_refill(&id, &pharmacy);
+    client.respond_refill_request(&request_id, &provider, &true, &2);
+    let request = client.get_refill_request(&request_id).unwrap();
+    assert_eq!(request.status, RefillStatus::Approved);
+    assert_eq!(client.get_prescription(&id).refills_remaining, 2);
+
+    client.dispense_prescription(&id, &pharmacy, &30, &String::from_str(&env, "L3"));
+    let history = client.get_dispense_history(&id, &0, &10);
+    assert_eq!(history.len(), 3);
+    assert_eq!(history.get(2).unwrap().fill_number, 3);
+}
+
+#[test]
+fn test_cancelled_prescription_cannot_be_dispensed() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PrescriptionContract, ());
+    let client = PrescriptionContractClient::new(&env, &contract_id);
+
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let pharmacy = Address::generate(&env);
+
+    let id = client.issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Metformin", 0, None),
+    );
+
+    let stranger = Address::generate(&env);
+    let result = client.try_cancel_prescription(&id, &stranger, &String::from_str(&env, "error"));
+    assert_eq!(result, Err(Ok(contract_error(Error::Unauthorized))));
+
+    client.cancel_prescription(&id, &provider, &String::from_str(&env, "Duplicate therapy"));
+    assert_eq!(
+        client.get_prescription(&id).status,
+        PrescriptionStatus::Cancelled
+    );
+
+    let result =
+        client.try_dispense_prescription(&id, &pharmacy, &30, &String::from_str(&env, "L1"));
+    assert_eq!(result, Err(Ok(contract_error(Error::InvalidState))));
+}
+
+#[test]
+fn test_controlled_substance_restrictions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PrescriptionContract, ());
+    let client = PrescriptionContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    client.initialize(&admin);
+
+    // Prescriber must be authorized for controlled substances
+    let unauthorized = client.try_issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Oxycodone", 0, Some(2)),
+    );
+    assert_eq!(
+        unauthorized,
+        Err(Ok(contract_error(Error::ControlledSubstanceRestriction)))
+    );
+
+    client.authorize_controlled_prescriber(&admin, &provider, &true);
+
+    // Schedule II cannot carry refills
+    let with_refills = client.try_issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Oxycodone", 1, Some(2)),
+    );
+    assert_eq!(
+        with_refills,
+        Err(Ok(contract_error(Error::ControlledSubstanceRestriction)))
+    );
+
+    let id = client.issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Oxycodone", 0, Some(2)),
+    );
+    let request_id = client.request_refill(&id, &patient);
+    let approve = client.try_respond_refill_request(&request_id, &provider, &true, &1);
+    assert_eq!(
+        approve,
+        Err(Ok(contract_error(Error::ControlledSubstanceRestriction)))
+    );
+}
+
+#[test]
+fn test_allergy_source_blocks_conflicting_prescription() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(PrescriptionContract, ());
+    let client = PrescriptionContractClient::new(&env, &contract_id);
+    let source_id = env.register(MockAllergySource, ());
+
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_allergy_source(&admin, &source_id);
+
+    let mut request = issue_request(&env, "Penicillin", 0, None);
+    let blocked = client.try_issue_prescription(&provider, &patient, &request);
+    assert_eq!(blocked, Err(Ok(contract_error(Error::AllergyConflict))));
+
+    request.allergy_override_reason = Some(String::from_str(&env, "Desensitized in clinic"));
+    let id = client.issue_prescription(&provider, &patient, &request);
+    assert!(
+        client
+            .get_prescription(&id)
+            .allergy_override_reason
+            .is_some()
+    );
+
+    client.issue_prescription(
+        &provider,
+        &patient,
+        &issue_request(&env, "Azithromycin", 0, None),
+    );
+}
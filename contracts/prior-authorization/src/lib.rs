@@ -10,6 +10,9 @@ use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol,
 use storage::*;
 use types::*;
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 const MAX_APPEAL_LEVEL: u32 = 3;
 
 #[contract]
@@ -17,6 +20,100 @@ pub struct PriorAuthorizationContract;
 
 #[contractimpl]
 impl PriorAuthorizationContract {
+    /// Set the administrator who registers payers.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if load_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Register a payer. Only the administrator may do this.
+    pub fn register_payer(
+        env: Env,
+        admin: Address,
+        payer_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if load_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+
+        save_payer(
+            &env,
+            &Payer {
+                payer_id: payer_id.clone(),
+                name,
+                active: true,
+                registered_at: env.ledger().timestamp(),
+            },
+        );
+
+        PayerRegistered { payer_id }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Create or update a coverage policy issued by the calling payer.
+    pub fn register_coverage_policy(
+        env: Env,
+        payer_id: Address,
+        policy: CoveragePolicy,
+    ) -> Result<(), Error> {
+        payer_id.require_auth();
+
+        let payer = load_payer(&env, &payer_id).ok_or(Error::PayerNotFound)?;
+        if !payer.active || policy.payer_id != payer_id {
+            return Err(Error::Unauthorized);
+        }
+        if let Some(existing) = load_policy(&env, policy.policy_id) {
+            if existing.payer_id != payer_id {
+                return Err(Error::Unauthorized);
+            }
+        }
+
+        save_policy(&env, &policy);
+
+        PolicyRegistered {
+            policy_id: policy.policy_id,
+            payer_id,
+            patient_id: policy.patient_id,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_coverage_policy(env: Env, policy_id: u64) -> Option<CoveragePolicy> {
+        load_policy(&env, policy_id)
+    }
+
+    /// Check whether a patient is covered for a service under a policy and
+    /// whether that service needs a prior authorization.
+    pub fn check_eligibility(
+        env: Env,
+        policy_id: u64,
+        patient_id: Address,
+        service_code: String,
+    ) -> Result<EligibilityResult, Error> {
+        let policy = load_policy(&env, policy_id).ok_or(Error::PolicyNotFound)?;
+
+        let eligible = policy_in_force(&env, &policy) && policy.patient_id == patient_id;
+        let service_covered = eligible && policy.covered_services.contains(&service_code);
+
+        Ok(EligibilityResult {
+            policy_id,
+            eligible,
+            service_covered,
+            prior_auth_required: service_covered
+                && policy.prior_auth_services.contains(&service_code),
+        })
+    }
+
     /// Submit a new prior authorization request.
     pub fn submit_prior_authorization(
         env: Env,
@@ -32,6 +129,16 @@ impl PriorAuthorizationContract {
     ) -> Result<u64, Error> {
         provider_id.require_auth();
 
+        let policy = load_policy(&env, policy_id).ok_or(Error::PolicyNotFound)?;
+        if !policy_in_force(&env, &policy) || policy.patient_id != patient_id {
+            return Err(Error::PolicyInactive);
+        }
+        for code in service_codes.iter() {
+            if !policy.covered_services.contains(&code) {
+                return Err(Error::ServiceNotCovered);
+            }
+        }
+
         let auth_request_id = next_auth_id(&env);
 
         let req = AuthorizationRequest {
@@ -105,7 +212,9 @@ impl PriorAuthorizationContract {
 
     /// Review an authorization request and record a decision.
     ///
-    /// Valid decisions: `approved`, `denied`, `more_info_needed`.
+    /// Valid decisions: `approved`, `denied`, `pended`, `more_info_needed`.
+    /// Only the payer that issued the request's policy may review it, and
+    /// approvals must carry a unit count and a well-formed validity window.
     pub fn review_authorization(
         env: Env,
         auth_request_id: u64,
@@ -121,11 +230,17 @@ impl PriorAuthorizationContract {
         let mut req = load_auth_request(&env, auth_request_id)
             .ok_or(Error::AuthRequestNotFound)?;
 
-        // Only Submitted, UnderReview, or MoreInfoNeeded can be reviewed
+        let policy = load_policy(&env, req.policy_id).ok_or(Error::PolicyNotFound)?;
+        if policy.payer_id != reviewer_id {
+            return Err(Error::Unauthorized);
+        }
+
+        // Only requests still awaiting a decision can be reviewed
         match req.status {
             AuthStatus::Submitted
             | AuthStatus::UnderReview
             | AuthStatus::MoreInfoNeeded
+            | AuthStatus::Pended
             | AuthStatus::PeerToPeerScheduled => {}
             _ => return Err(Error::InvalidStatusTransition),
         }
@@ -133,8 +248,17 @@ impl PriorAuthorizationContract {
         let approved_sym = Symbol::new(&env, "approved");
         let denied_sym = Symbol::new(&env, "denied");
         let more_info_sym = Symbol::new(&env, "more_info_needed");
+        let pended_sym = Symbol::new(&env, "pended");
 
         if decision == approved_sym {
+            if approved_units.unwrap_or(0) == 0 {
+                return Err(Error::InvalidDecision);
+            }
+            if let (Some(from), Some(until)) = (valid_from, valid_until) {
+                if from >= until {
+                    return Err(Error::InvalidDecision);
+                }
+            }
             req.status = AuthStatus::Approved;
             req.approved_units = approved_units;
             req.valid_from = valid_from;
@@ -145,6 +269,8 @@ impl PriorAuthorizationContract {
             req.decision_date = Some(env.ledger().timestamp());
         } else if decision == more_info_sym {
             req.status = AuthStatus::MoreInfoNeeded;
+        } else if decision == pended_sym {
+            req.status = AuthStatus::Pended;
         } else {
             return Err(Error::InvalidDecision);
         }
@@ -155,7 +281,7 @@ impl PriorAuthorizationContract {
 
         env.events().publish(
             (Symbol::new(&env, "auth_reviewed"),),
-            (auth_request_id, decision, reviewer_id),
+            (auth_request_id, decision, reviewer_id, review_notes),
         );
 
         Ok(())
@@ -322,7 +448,10 @@ impl PriorAuthorizationContract {
 
         // Only unresolved requests can be expedited
         match req.status {
-            AuthStatus::Submitted | AuthStatus::UnderReview | AuthStatus::MoreInfoNeeded => {}
+            AuthStatus::Submitted
+            | AuthStatus::UnderReview
+            | AuthStatus::MoreInfoNeeded
+            | AuthStatus::Pended => {}
             _ => return Err(Error::InvalidStatusTransition),
         }
 
@@ -460,4 +589,66 @@ impl PriorAuthorizationContract {
             decision_date: req.decision_date,
         })
     }
-}
\ No newline at end of file
+
+    /// Check that an authorization currently allows `units` of `service_code`
+    /// for the patient. Intended for other contracts to call before
+    /// scheduling a service.
+    pub fn verify_authorization(
+        env: Env,
+        auth_request_id: u64,
+        patient_id: Address,
+        service_code: String,
+        units: u32,
+    ) -> bool {
+        let req = match load_auth_request(&env, auth_request_id) {
+            Some(req) => req,
+            None => return false,
+        };
+        if !matches!(req.status, AuthStatus::Approved)
+            || req.patient_id != patient_id
+            || !req.service_codes.contains(&service_code)
+        {
+            return false;
+        }
+
+        let now = env.ledger().timestamp();
+        if req.valid_from.is_some_and(|from| now < from)
+            || req.valid_until.is_some_and(|until| now > until)
+        {
+            return false;
+        }
+
+        let approved = req.approved_units.unwrap_or(0);
+        req.units_used + units <= approved
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if load_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn policy_in_force(env: &Env, policy: &CoveragePolicy) -> bool {
+    let now = env.ledger().timestamp();
+    policy.active && policy.effective_from <= now && now <= policy.effective_until
+}
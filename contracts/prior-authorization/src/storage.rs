@@ -1,10 +1,8 @@
-#![no_std]
-
 use soroban_sdk::{Address, Env, Vec};
 
 use crate::types::{
-    Appeal, AuthorizationRequest, DataKey, ExtensionRequest, PeerToPeerRequest,
-    SupportingDocument, UsageRecord,
+    Appeal, AuthorizationRequest, CoveragePolicy, DataKey, ExtensionRequest, Payer,
+    PeerToPeerRequest, SupportingDocument, UsageRecord,
 };
 
 // -----------------------------------------------------------------------
@@ -37,6 +35,36 @@ pub fn next_appeal_id(env: &Env) -> u64 {
     next
 }
 
+// -----------------------------------------------------------------------
+// Payers and coverage policies
+// -----------------------------------------------------------------------
+
+pub fn load_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn save_payer(env: &Env, payer: &Payer) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Payer(payer.payer_id.clone()), payer);
+}
+
+pub fn load_payer(env: &Env, payer_id: &Address) -> Option<Payer> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Payer(payer_id.clone()))
+}
+
+pub fn save_policy(env: &Env, policy: &CoveragePolicy) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Policy(policy.policy_id), policy);
+}
+
+pub fn load_policy(env: &Env, policy_id: u64) -> Option<CoveragePolicy> {
+    env.storage().persistent().get(&DataKey::Policy(policy_id))
+}
+
 // -----------------------------------------------------------------------
 // AuthorizationRequest
 // -----------------------------------------------------------------------
@@ -1,6 +1,6 @@
-#![no_std]
-
-use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Symbol, Vec};
+use soroban_sdk::{
+    contracterror, contractevent, contracttype, Address, BytesN, String, Symbol, Vec,
+};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -18,6 +18,12 @@ pub enum Error {
     AuthorizationExpired = 10,
     ExceedsApprovedUnits = 11,
     PeerToPeerAlreadyScheduled = 12,
+    AlreadyInitialized = 13,
+    NotInitialized = 14,
+    PayerNotFound = 15,
+    PolicyNotFound = 16,
+    PolicyInactive = 17,
+    ServiceNotCovered = 18,
 }
 
 /// Lifecycle status of a prior authorization request.
@@ -30,6 +36,8 @@ pub enum AuthStatus {
     UnderReview,
     /// Reviewer requested additional information.
     MoreInfoNeeded,
+    /// Payer has pended the request for a later decision.
+    Pended,
     /// Peer-to-peer review has been scheduled.
     PeerToPeerScheduled,
     /// Authorization approved.
@@ -42,6 +50,59 @@ pub enum AuthStatus {
     Expired,
 }
 
+/// A registered payer (insurer) able to hold coverage policies.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Payer {
+    pub payer_id: Address,
+    pub name: String,
+    pub active: bool,
+    pub registered_at: u64,
+}
+
+/// A patient's coverage under a payer.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CoveragePolicy {
+    pub policy_id: u64,
+    pub payer_id: Address,
+    pub patient_id: Address,
+    pub plan_name: String,
+    /// Service codes the policy covers.
+    pub covered_services: Vec<String>,
+    /// Covered service codes that need a prior authorization.
+    pub prior_auth_services: Vec<String>,
+    pub effective_from: u64,
+    pub effective_until: u64,
+    pub active: bool,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PayerRegistered {
+    #[topic]
+    pub payer_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyRegistered {
+    #[topic]
+    pub policy_id: u64,
+    pub payer_id: Address,
+    pub patient_id: Address,
+}
+
+/// Result of an eligibility check for one service.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EligibilityResult {
+    pub policy_id: u64,
+    pub eligible: bool,
+    pub service_covered: bool,
+    pub prior_auth_required: bool,
+}
+
 /// Core authorization request record.
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -146,6 +207,12 @@ pub struct UsageRecord {
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
+    /// Contract administrator, manages payer registration.
+    Admin,
+    /// payer_id -> Payer
+    Payer(Address),
+    /// policy_id -> CoveragePolicy
+    Policy(u64),
     /// Auto-increment counter for auth requests.
     AuthCounter,
     /// Auto-increment counter for appeals.
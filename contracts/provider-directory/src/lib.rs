@@ -0,0 +1,267 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct ProviderDirectoryContract;
+
+#[contractimpl]
+impl ProviderDirectoryContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(
+        env: Env,
+        admin: Address,
+        facility_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if get_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Facility(facility_id), &name);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Listings
+    // -------------------------------------------------------------------
+
+    /// Create or replace the provider's own directory listing.
+    pub fn publish_listing(
+        env: Env,
+        provider_id: Address,
+        input: ListingInput,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        if input.specialties.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+
+        if let Some(previous) = load_listing(&env, &provider_id) {
+            for specialty in previous.specialties.iter() {
+                if !input.specialties.contains(&specialty) {
+                    unindex_specialty(&env, &specialty, &provider_id);
+                }
+            }
+        }
+        for specialty in input.specialties.iter() {
+            index_specialty(&env, &specialty, &provider_id);
+        }
+
+        save_listing(
+            &env,
+            &ProviderListing {
+                provider_id,
+                display_name: input.display_name,
+                specialties: input.specialties,
+                languages: input.languages,
+                accepting_new_patients: input.accepting_new_patients,
+                next_available_at: input.next_available_at,
+                active: true,
+                updated_at: env.ledger().timestamp(),
+            },
+        );
+        Ok(())
+    }
+
+    pub fn set_availability(
+        env: Env,
+        provider_id: Address,
+        accepting_new_patients: bool,
+        next_available_at: u64,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut listing = load_listing(&env, &provider_id).ok_or(Error::ListingNotFound)?;
+        listing.accepting_new_patients = accepting_new_patients;
+        listing.next_available_at = next_available_at;
+        listing.updated_at = env.ledger().timestamp();
+        save_listing(&env, &listing);
+        Ok(())
+    }
+
+    /// Hide the listing from search and specialty checks. Publishing again restores it.
+    pub fn withdraw_listing(env: Env, provider_id: Address) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut listing = load_listing(&env, &provider_id).ok_or(Error::ListingNotFound)?;
+        listing.active = false;
+        listing.updated_at = env.ledger().timestamp();
+        save_listing(&env, &listing);
+        Ok(())
+    }
+
+    pub fn get_listing(env: Env, provider_id: Address) -> Result<ProviderListing, Error> {
+        load_listing(&env, &provider_id).ok_or(Error::ListingNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Affiliations
+    // -------------------------------------------------------------------
+
+    pub fn request_affiliation(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        if !is_facility(&env, &facility_id) {
+            return Err(Error::FacilityNotRegistered);
+        }
+        if load_affiliation(&env, &provider_id, &facility_id).is_none() {
+            let mut facilities = provider_facilities(&env, &provider_id);
+            facilities.push_back(facility_id.clone());
+            env.storage().persistent().set(
+                &DataKey::ProviderFacilities(provider_id.clone()),
+                &facilities,
+            );
+        }
+        save_affiliation(
+            &env,
+            &Affiliation {
+                provider_id,
+                facility_id,
+                attested: false,
+                requested_at: env.ledger().timestamp(),
+                attested_at: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// The facility confirms (or, with `attested = false`, withdraws) an affiliation.
+    pub fn attest_affiliation(
+        env: Env,
+        facility_id: Address,
+        provider_id: Address,
+        attested: bool,
+    ) -> Result<(), Error> {
+        facility_id.require_auth();
+        if !is_facility(&env, &facility_id) {
+            return Err(Error::FacilityNotRegistered);
+        }
+        let mut affiliation =
+            load_affiliation(&env, &provider_id, &facility_id).ok_or(Error::AffiliationNotFound)?;
+        affiliation.attested = attested;
+        affiliation.attested_at = if attested {
+            env.ledger().timestamp()
+        } else {
+            0
+        };
+        save_affiliation(&env, &affiliation);
+        Ok(())
+    }
+
+    pub fn get_affiliations(env: Env, provider_id: Address) -> Vec<Affiliation> {
+        let mut affiliations = Vec::new(&env);
+        for facility_id in provider_facilities(&env, &provider_id).iter() {
+            if let Some(affiliation) = load_affiliation(&env, &provider_id, &facility_id) {
+                affiliations.push_back(affiliation);
+            }
+        }
+        affiliations
+    }
+
+    // -------------------------------------------------------------------
+    // Search
+    // -------------------------------------------------------------------
+
+    /// Active providers listing `specialty`, optionally filtered by spoken
+    /// language, attested affiliation with `facility_id`, and whether they
+    /// accept new patients.
+    pub fn find_providers(
+        env: Env,
+        specialty: Symbol,
+        language: Option<Symbol>,
+        facility_id: Option<Address>,
+        accepting_only: bool,
+    ) -> Vec<ProviderListing> {
+        let mut results = Vec::new(&env);
+        for provider_id in specialty_index(&env, &specialty).iter() {
+            let Some(listing) = load_listing(&env, &provider_id) else {
+                continue;
+            };
+            if !listing.active || (accepting_only && !listing.accepting_new_patients) {
+                continue;
+            }
+            if let Some(language) = &language {
+                if !listing.languages.contains(language) {
+                    continue;
+                }
+            }
+            if let Some(facility_id) = &facility_id {
+                let affiliated =
+                    load_affiliation(&env, &provider_id, facility_id).is_some_and(|a| a.attested);
+                if !affiliated {
+                    continue;
+                }
+            }
+            results.push_back(listing);
+        }
+        results
+    }
+
+    /// Whether the provider has an active listing under `specialty`.
+    /// Used by the referral contract to validate receiving providers.
+    pub fn is_listed_for_specialty(env: Env, provider_id: Address, specialty: Symbol) -> bool {
+        load_listing(&env, &provider_id)
+            .is_some_and(|listing| listing.active && listing.specialties.contains(&specialty))
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
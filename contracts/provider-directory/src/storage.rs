@@ -0,0 +1,92 @@
+use soroban_sdk::{Address, Env, Symbol, Vec};
+
+use crate::types::{Affiliation, DataKey, ProviderListing};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_facility(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Facility(who.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Listings
+// -----------------------------------------------------------------------
+
+pub fn save_listing(env: &Env, listing: &ProviderListing) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Listing(listing.provider_id.clone()), listing);
+}
+
+pub fn load_listing(env: &Env, provider_id: &Address) -> Option<ProviderListing> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Listing(provider_id.clone()))
+}
+
+pub fn specialty_index(env: &Env, specialty: &Symbol) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SpecialtyIndex(specialty.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn index_specialty(env: &Env, specialty: &Symbol, provider_id: &Address) {
+    let mut providers = specialty_index(env, specialty);
+    if !providers.contains(provider_id) {
+        providers.push_back(provider_id.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::SpecialtyIndex(specialty.clone()), &providers);
+    }
+}
+
+pub fn unindex_specialty(env: &Env, specialty: &Symbol, provider_id: &Address) {
+    let mut providers = specialty_index(env, specialty);
+    if let Some(i) = providers.first_index_of(provider_id) {
+        providers.remove(i);
+        env.storage()
+            .persistent()
+            .set(&DataKey::SpecialtyIndex(specialty.clone()), &providers);
+    }
+}
+
+// -----------------------------------------------------------------------
+// Affiliations
+// -----------------------------------------------------------------------
+
+pub fn save_affiliation(env: &Env, affiliation: &Affiliation) {
+    env.storage().persistent().set(
+        &DataKey::Affiliation(
+            affiliation.provider_id.clone(),
+            affiliation.facility_id.clone(),
+        ),
+        affiliation,
+    );
+}
+
+pub fn load_affiliation(
+    env: &Env,
+    provider_id: &Address,
+    facility_id: &Address,
+) -> Option<Affiliation> {
+    env.storage().persistent().get(&DataKey::Affiliation(
+        provider_id.clone(),
+        facility_id.clone(),
+    ))
+}
+
+pub fn provider_facilities(env: &Env, provider_id: &Address) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProviderFacilities(provider_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
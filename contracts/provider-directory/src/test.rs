@@ -0,0 +1,304 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{symbol_short, testutils::Address as _, vec, Address, BytesN, Env, String};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    ProviderDirectoryContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(ProviderDirectoryContract, ());
+    let client = ProviderDirectoryContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(&admin, &facility, &String::from_str(&env, "Mercy General"));
+
+    (env, admin, facility, client)
+}
+
+fn list(
+    env: &Env,
+    client: &ProviderDirectoryContractClient,
+    specialties: Vec<Symbol>,
+    languages: Vec<Symbol>,
+    accepting: bool,
+) -> Address {
+    let provider = Address::generate(env);
+    client.publish_listing(
+        &provider,
+        &ListingInput {
+            display_name: String::from_str(env, "Dr. Example"),
+            specialties,
+            languages,
+            accepting_new_patients: accepting,
+            next_available_at: 0,
+        },
+    );
+    provider
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_search_by_specialty_language_and_accepting() {
+    let (env, _, _, client) = setup();
+    let cardio = symbol_short!("cardio");
+    let en = symbol_short!("en");
+    let es = symbol_short!("es");
+
+    list(
+        &env,
+        &client,
+        vec![&env, cardio.clone()],
+        vec![&env, en.clone()],
+        true,
+    );
+    let bilingual = list(
+        &env,
+        &client,
+        vec![&env, cardio.clone(), symbol_short!("im")],
+        vec![&env, en.clone(), es.clone()],
+        true,
+    );
+    list(
+        &env,
+        &client,
+        vec![&env, cardio.clone()],
+        vec![&env, es.clone()],
+        false,
+    );
+
+    assert_eq!(
+        client.find_providers(&cardio, &None, &None, &false).len(),
+        3
+    );
+    assert_eq!(client.find_providers(&cardio, &None, &None, &true).len(), 2);
+    let spanish = client.find_providers(&cardio, &Some(es), &None, &true);
+    assert_eq!(spanish.len(), 1);
+    assert_eq!(spanish.get(0).unwrap().provider_id, bilingual);
+}
+
+#[test]
+fn test_republishing_updates_specialty_index() {
+    let (env, _, _, client) = setup();
+    let provider = list(
+        &env,
+        &client,
+        vec![&env, symbol_short!("derm")],
+        vec![&env, symbol_short!("en")],
+        true,
+    );
+    assert!(client.is_listed_for_specialty(&provider, &symbol_short!("derm")));
+
+    client.publish_listing(
+        &provider,
+        &ListingInput {
+            display_name: String::from_str(&env, "Dr. Example"),
+            specialties: vec![&env, symbol_short!("allergy")],
+            languages: vec![&env, symbol_short!("en")],
+            accepting_new_patients: true,
+            next_available_at: 0,
+        },
+    );
+    assert!(!client.is_listed_for_specialty(&provider, &symbol_short!("derm")));
+    assert_eq!(
+        client
+            .find_providers(&symbol_short!("derm"), &None, &None, &false)
+            .len(),
+        0
+    );
+
+    client.withdraw_listing(&provider);
+    assert!(!client.is_listed_for_specialty(&provider, &symbol_short!("allergy")));
+}
+
+#[test]
+fn test_affiliation_requires_facility_attestation() {
+    let (env, _, facility, client) = setup();
+    let ortho = symbol_short!("ortho");
+    let provider = list(&env, &client, vec![&env, ortho.clone()], vec![&env], true);
+    let at_facility = Some(facility.clone());
+
+    client.request_affiliation(&provider, &facility);
+    assert!(!client.get_affiliations(&provider).get(0).unwrap().attested);
+    assert_eq!(
+        client
+            .find_providers(&ortho, &None, &at_facility, &false)
+            .len(),
+        0
+    );
+
+    client.attest_affiliation(&facility, &provider, &true);
+    assert_eq!(
+        client
+            .find_providers(&ortho, &None, &at_facility, &false)
+            .len(),
+        1
+    );
+
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_request_affiliation(&provider, &unregistered),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+}
+
+#[test]
+fn test_availability_updates() {
+    let (env, _, _, client) = setup();
+    let provider = list(
+        &env,
+        &client,
+        vec![&env, symbol_short!("peds")],
+        vec![&env],
+        true,
+    );
+    client.set_availability(&provider, &false, &1_900_000_000);
+
+    let listing = client.get_listing(&provider);
+    assert!(!listing.accepting_new_patients);
+    assert_eq!(listing.next_available_at, 1_900_000_000);
+
+    let unknown = Address::generate(&env);
+    assert_eq!(
+        client.try_set_availability(&unknown, &true, &0),
+        Err(Ok(Error::ListingNotFound))
+    );
+}
+
+#[test]
+fn test_listing_requires_specialty() {
+    let (env, _, _, client) = setup();
+    let provider = Address::generate(&env);
+    assert_eq!(
+        client.try_publish_listing(
+            &provider,
+            &ListingInput {
+                display_name: String::from_str(&env, "Dr. Empty"),
+                specialties: vec![&env],
+                languages: vec![&env],
+                accepting_new_patients: true,
+                next_available_at: 0,
+            },
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_facility_registration_requires_admin() {
+    let (env, admin, facility, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&facility, &other, &String::from_str(&env, "Rogue Clinic")),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // The rejected facility cannot attest affiliations either
+    let provider = list(
+        &env,
+        &client,
+        vec![&env, symbol_short!("gi")],
+        vec![&env],
+        true,
+    );
+    assert_eq!(
+        client.try_attest_affiliation(&other, &provider, &true),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+}
+
+#[test]
+fn test_attestation_requires_pending_request() {
+    let (env, admin, facility, client) = setup();
+    let provider = list(
+        &env,
+        &client,
+        vec![&env, symbol_short!("gi")],
+        vec![&env],
+        true,
+    );
+
+    assert_eq!(
+        client.try_attest_affiliation(&facility, &provider, &true),
+        Err(Ok(Error::AffiliationNotFound))
+    );
+
+    // A request to one facility cannot be attested by another
+    let other_facility = Address::generate(&env);
+    client.register_facility(
+        &admin,
+        &other_facility,
+        &String::from_str(&env, "St. Luke's"),
+    );
+    client.request_affiliation(&provider, &facility);
+    assert_eq!(
+        client.try_attest_affiliation(&other_facility, &provider, &true),
+        Err(Ok(Error::AffiliationNotFound))
+    );
+
+    // Withdrawing an attestation drops the provider from facility search
+    let at_facility = Some(facility.clone());
+    client.attest_affiliation(&facility, &provider, &true);
+    client.attest_affiliation(&facility, &provider, &false);
+    assert!(client
+        .find_providers(&symbol_short!("gi"), &None, &at_facility, &false)
+        .is_empty());
+}
+
+#[test]
+fn test_withdrawn_and_missing_listings() {
+    let (env, _, _, client) = setup();
+    let provider = list(
+        &env,
+        &client,
+        vec![&env, symbol_short!("neuro")],
+        vec![&env],
+        true,
+    );
+
+    client.withdraw_listing(&provider);
+    assert!(!client.get_listing(&provider).active);
+    assert!(client
+        .find_providers(&symbol_short!("neuro"), &None, &None, &false)
+        .is_empty());
+
+    let unknown = Address::generate(&env);
+    assert_eq!(
+        client.try_withdraw_listing(&unknown),
+        Err(Ok(Error::ListingNotFound))
+    );
+    assert_eq!(
+        client.try_get_listing(&unknown),
+        Err(Ok(Error::ListingNotFound))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, facility, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&facility), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&facility, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
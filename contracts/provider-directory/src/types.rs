@@ -0,0 +1,77 @@
+use soroban_sdk::{contracterror, contracttype, Address, String, Symbol, Vec};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    ListingNotFound = 4,
+    FacilityNotRegistered = 5,
+    AffiliationNotFound = 6,
+    InvalidInput = 7,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ListingInput {
+    pub display_name: String,
+    pub specialties: Vec<Symbol>,
+    pub languages: Vec<Symbol>,
+    pub accepting_new_patients: bool,
+    /// Earliest open appointment slot; 0 when not published.
+    pub next_available_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderListing {
+    pub provider_id: Address,
+    pub display_name: String,
+    pub specialties: Vec<Symbol>,
+    pub languages: Vec<Symbol>,
+    pub accepting_new_patients: bool,
+    pub next_available_at: u64,
+    pub active: bool,
+    pub updated_at: u64,
+}
+
+/// A provider's claimed affiliation; only counts once the facility attests it.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Affiliation {
+    pub provider_id: Address,
+    pub facility_id: Address,
+    pub attested: bool,
+    pub requested_at: u64,
+    pub attested_at: u64,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// facility_id -> name
+    Facility(Address),
+    /// provider -> ProviderListing
+    Listing(Address),
+    /// specialty -> Vec<Address> (providers listing it)
+    SpecialtyIndex(Symbol),
+    /// (provider, facility) -> Affiliation
+    Affiliation(Address, Address),
+    /// provider -> Vec<Address> (facility ids)
+    ProviderFacilities(Address),
+}
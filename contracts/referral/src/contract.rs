@@ -1,11 +1,74 @@
-use crate::types::{DataKey, Error, Referral, ReferralStatus};
+use crate::directory::DirectoryClient;
+use crate::document_exchange::DocumentExchangeClient;
+use crate::types::{
+    AttachmentRequest, CareSummaryRequested, DataKey, Error, PatientReferralUpdated, Referral,
+    ReferralAcknowledged, ReferralAttachment, ReferralItemAttached, ReferralStatus,
+};
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct ReferralContract;
 
 #[contractimpl]
 impl ReferralContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Once set, new referrals must target a provider listed in the
+    /// directory under the requested specialty.
+    pub fn set_provider_directory(
+        env: Env,
+        admin: Address,
+        directory: Address,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::ProviderDirectory, &directory);
+        Ok(())
+    }
+
+    /// Once set, shared care summaries are registered in the document exchange.
+    pub fn set_document_exchange(env: Env, admin: Address, exchange: Address) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::DocumentExchange, &exchange);
+        Ok(())
+    }
+
+    /// Set the attachments a referral to `specialty` must carry before it can
+    /// be accepted, e.g. "labs", "imaging", "med list".
+    pub fn set_required_attachments(
+        env: Env,
+        admin: Address,
+        specialty: Symbol,
+        items: Vec<String>,
+    ) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::RequiredAttachments(specialty), &items);
+        Ok(())
+    }
+
+    pub fn get_required_attachments(env: Env, specialty: Symbol) -> Vec<String> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RequiredAttachments(specialty))
+            .unwrap_or(Vec::new(&env))
+    }
+
     pub fn create_referral(
         env: Env,
         referring_provider: Address,
@@ -19,6 +82,15 @@ impl ReferralContract {
     ) -> Result<u64, Error> {
         referring_provider.require_auth();
 
+        let directory: Option<Address> = env.storage().instance().get(&DataKey::ProviderDirectory);
+        if let Some(directory) = directory {
+            if !DirectoryClient::new(&env, &directory)
+                .is_listed_for_specialty(&referred_to, &specialty)
+            {
+                return Err(Error::ProviderNotListed);
+            }
+        }
+
         let referral_id: u64 = env
             .storage()
             .instance()
@@ -47,6 +119,17 @@ impl ReferralContract {
             .instance()
             .set(&DataKey::ReferralCount, &referral_id);
 
+        let patient_key = DataKey::PatientReferrals(referral.patient_id.clone());
+        let mut patient_referrals: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&patient_key)
+            .unwrap_or(Vec::new(&env));
+        patient_referrals.push_back(referral_id);
+        env.storage()
+            .persistent()
+            .set(&patient_key, &patient_referrals);
+
         // Emit events for extended data that is not stored in the state struct to save space
         env.events().publish(
             (Symbol::new(&env, "referral_created"), referral_id),
@@ -78,6 +161,10 @@ impl ReferralContract {
             return Err(Error::InvalidStatusTransition);
         }
 
+        if !Self::missing_attachments(&env, &referral).is_empty() {
+            return Err(Error::MissingRequiredAttachments);
+        }
+
         referral.status = ReferralStatus::Accepted;
         referral.accepted_at = Some(env.ledger().timestamp());
 
@@ -88,6 +175,7 @@ impl ReferralContract {
             (Symbol::new(&env, "referral_accepted"), referral_id),
             estimated_appointment_date,
         );
+        Self::notify_patient(&env, &referral);
 
         Ok(())
     }
@@ -166,6 +254,15 @@ impl ReferralContract {
             return Err(Error::InvalidStatusTransition);
         };
 
+        if new_status == ReferralStatus::Scheduled
+            && !env
+                .storage()
+                .persistent()
+                .has(&DataKey::PatientAcknowledgment(referral_id))
+        {
+            return Err(Error::PatientNotAcknowledged);
+        }
+
         referral.status = new_status;
         env.storage()
             .persistent()
@@ -174,6 +271,11 @@ impl ReferralContract {
             (Symbol::new(&env, "referral_status_updated"), referral_id),
             (status, status_note),
         );
+        if referral.status == ReferralStatus::Scheduled
+            || referral.status == ReferralStatus::Completed
+        {
+            Self::notify_patient(&env, &referral);
+        }
 
         Ok(())
     }
@@ -220,6 +322,7 @@ impl ReferralContract {
                 followup_required,
             ),
         );
+        Self::notify_patient(&env, &referral);
 
         Ok(())
     }
@@ -245,6 +348,23 @@ impl ReferralContract {
             return Err(Error::NotAuthorized);
         }
 
+        let exchange: Option<Address> = env.storage().instance().get(&DataKey::DocumentExchange);
+        if let Some(exchange) = exchange {
+            let recipient = if from_provider == referral.referring_provider {
+                referral.receiving_provider.clone()
+            } else {
+                referral.referring_provider.clone()
+            };
+            DocumentExchangeClient::new(&env, &exchange).register_exchanged_document(
+                &env.current_contract_address(),
+                &from_provider,
+                &recipient,
+                &referral.patient_id,
+                &summary_type,
+                &summary_hash,
+            );
+        }
+
         env.events().publish(
             (Symbol::new(&env, "care_summary_shared"), referral_id),
             (from_provider, summary_type, summary_hash),
@@ -273,11 +393,252 @@ impl ReferralContract {
             return Err(Error::NotAuthorized);
         }
 
-        env.events().publish(
-            (Symbol::new(&env, "care_summary_requested"), referral_id),
-            (requesting_provider, information_needed),
+        CareSummaryRequested {
+            referral_id,
+            requesting_provider,
+            information_needed,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// The patient confirms they are willing to be scheduled. A referral
+    /// cannot move to Scheduled until this has been recorded.
+    pub fn acknowledge_referral(
+        env: Env,
+        referral_id: u64,
+        patient_id: Address,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let referral: Referral = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Referral(referral_id))
+            .ok_or(Error::ReferralNotFound)?;
+
+        if referral.patient_id != patient_id {
+            return Err(Error::NotAuthorized);
+        }
+
+        if referral.status != ReferralStatus::Pending && referral.status != ReferralStatus::Accepted
+        {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let acknowledged_at = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &DataKey::PatientAcknowledgment(referral_id),
+            &acknowledged_at,
+        );
+        ReferralAcknowledged {
+            referral_id,
+            patient_id,
+            acknowledged_at,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// When the patient confirmed they are willing to be scheduled, if they have.
+    pub fn get_patient_acknowledgment(env: Env, referral_id: u64) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PatientAcknowledgment(referral_id))
+    }
+
+    /// One page of the patient's own referrals, oldest first.
+    pub fn get_patient_referrals(
+        env: Env,
+        patient_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<Referral> {
+        patient_id.require_auth();
+
+        let ids: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientReferrals(patient_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate_map(&env, &ids, offset, limit, |id| {
+            env.storage().persistent().get(&DataKey::Referral(id))
+        })
+    }
+
+    /// Supply a checklist item for the referral. Only the referring provider
+    /// can attach items; a later attachment of the same item replaces the
+    /// earlier one.
+    pub fn attach_referral_item(
+        env: Env,
+        referral_id: u64,
+        referring_provider: Address,
+        item: String,
+        document_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        referring_provider.require_auth();
+
+        let referral: Referral = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Referral(referral_id))
+            .ok_or(Error::ReferralNotFound)?;
+
+        if referral.referring_provider != referring_provider {
+            return Err(Error::NotAuthorized);
+        }
+
+        match referral.status {
+            ReferralStatus::Declined | ReferralStatus::Completed | ReferralStatus::Cancelled => {
+                return Err(Error::InvalidStatusTransition)
+            }
+            _ => {}
+        }
+
+        let mut attachments = Self::get_referral_attachments(env.clone(), referral_id);
+        if let Some(idx) = attachments.iter().position(|a| a.item == item) {
+            attachments.remove(idx as u32);
+        }
+        attachments.push_back(ReferralAttachment {
+            item: item.clone(),
+            document_hash: document_hash.clone(),
+            provided_at: env.ledger().timestamp(),
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReferralAttachments(referral_id), &attachments);
+
+        ReferralItemAttached {
+            referral_id,
+            item,
+            document_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_referral_attachments(env: Env, referral_id: u64) -> Vec<ReferralAttachment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferralAttachments(referral_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Required items for the referral's specialty that have not been attached.
+    pub fn get_missing_attachments(env: Env, referral_id: u64) -> Result<Vec<String>, Error> {
+        let referral: Referral = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Referral(referral_id))
+            .ok_or(Error::ReferralNotFound)?;
+        Ok(Self::missing_attachments(&env, &referral))
+    }
+
+    /// Formally ask the referring provider for the missing checklist items.
+    /// The request is recorded and sent as a care summary request listing
+    /// the items. Returns the items requested.
+    pub fn request_missing_attachments(
+        env: Env,
+        referral_id: u64,
+        receiving_provider: Address,
+    ) -> Result<Vec<String>, Error> {
+        receiving_provider.require_auth();
+
+        let referral: Referral = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Referral(referral_id))
+            .ok_or(Error::ReferralNotFound)?;
+
+        if referral.receiving_provider != receiving_provider {
+            return Err(Error::NotAuthorized);
+        }
+
+        if referral.status != ReferralStatus::Pending {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let missing = Self::missing_attachments(&env, &referral);
+        if missing.is_empty() {
+            return Ok(missing);
+        }
+
+        env.storage().persistent().set(
+            &DataKey::AttachmentRequest(referral_id),
+            &AttachmentRequest {
+                requested_by: receiving_provider.clone(),
+                items: missing.clone(),
+                requested_at: env.ledger().timestamp(),
+            },
         );
+        CareSummaryRequested {
+            referral_id,
+            requesting_provider: receiving_provider,
+            information_needed: missing.clone(),
+        }
+        .publish(&env);
 
+        Ok(missing)
+    }
+
+    pub fn get_attachment_request(env: Env, referral_id: u64) -> Option<AttachmentRequest> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AttachmentRequest(referral_id))
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn missing_attachments(env: &Env, referral: &Referral) -> Vec<String> {
+        let required = Self::get_required_attachments(env.clone(), referral.specialty.clone());
+        let attachments = Self::get_referral_attachments(env.clone(), referral.referral_id);
+
+        let mut missing = Vec::new(env);
+        for item in required.iter() {
+            if !attachments.iter().any(|a| a.item == item) {
+                missing.push_back(item);
+            }
+        }
+        missing
+    }
+
+    fn notify_patient(env: &Env, referral: &Referral) {
+        PatientReferralUpdated {
+            patient_id: referral.patient_id.clone(),
+            referral_id: referral.referral_id,
+            status: referral.status.clone(),
+        }
+        .publish(env);
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::NotAuthorized);
+        }
         Ok(())
     }
 }
@@ -0,0 +1,8 @@
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Subset of the provider-directory interface used to check that a
+/// receiving provider is listed under the requested specialty.
+#[contractclient(name = "DirectoryClient")]
+pub trait Directory {
+    fn is_listed_for_specialty(env: Env, provider_id: Address, specialty: Symbol) -> bool;
+}
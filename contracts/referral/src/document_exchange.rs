@@ -0,0 +1,16 @@
+use soroban_sdk::{contractclient, Address, BytesN, Env, Symbol};
+
+/// Subset of the health-information-exchange interface used to register
+/// care summaries shared between referral parties.
+#[contractclient(name = "DocumentExchangeClient")]
+pub trait DocumentExchange {
+    fn register_exchanged_document(
+        env: Env,
+        integration: Address,
+        source: Address,
+        recipient: Address,
+        patient_id: Address,
+        document_type: Symbol,
+        content_hash: BytesN<32>,
+    ) -> u64;
+}
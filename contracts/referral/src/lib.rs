@@ -1,5 +1,7 @@
 #![no_std]
 
 pub mod contract;
+pub mod directory;
+pub mod document_exchange;
 pub mod test;
 pub mod types;
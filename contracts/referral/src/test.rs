@@ -1,7 +1,7 @@
 #![cfg(test)]
 
 use crate::contract::{ReferralContract, ReferralContractClient};
-use crate::types::Error;
+use crate::types::{Error, ReferralStatus};
 use soroban_sdk::{
     testutils::{Address as _, Events},
     Address, BytesN, Env, String, Symbol, Vec,
@@ -108,6 +108,7 @@ fn test_decline_and_update_status() {
         &Vec::new(&env),
     );
 
+    client.acknowledge_referral(&referral_id2, &patient_id);
     client.update_referral_status(
         &referral_id2,
         &referred_to,
@@ -144,3 +145,287 @@ fn test_auth_failures() {
     let res = client.try_accept_referral(&referral_id, &wrong_provider, &None);
     assert!(res.is_err()); // NotAuthorized
 }
+
+mod mock_directory {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct MockDirectory;
+
+    #[contractimpl]
+    impl MockDirectory {
+        pub fn list(env: Env, provider_id: Address, specialty: Symbol) {
+            env.storage()
+                .instance()
+                .set(&(provider_id, specialty), &true);
+        }
+
+        pub fn is_listed_for_specialty(env: Env, provider_id: Address, specialty: Symbol) -> bool {
+            env.storage()
+                .instance()
+                .get(&(provider_id, specialty))
+                .unwrap_or(false)
+        }
+    }
+}
+
+#[test]
+fn test_receiving_provider_must_be_listed_for_specialty() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ReferralContract, ());
+    let client = ReferralContractClient::new(&env, &contract_id);
+    let directory_id = env.register(mock_directory::MockDirectory, ());
+    let directory = mock_directory::MockDirectoryClient::new(&env, &directory_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_provider_directory(&admin, &directory_id);
+
+    let referring_provider = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let cardiologist = Address::generate(&env);
+    let cardio = Symbol::new(&env, "Cardio");
+    directory.list(&cardiologist, &cardio);
+
+    let res = client.try_create_referral(
+        &referring_provider,
+        &patient_id,
+        &cardiologist,
+        &Symbol::new(&env, "Ortho"),
+        &String::from_str(&env, "Knee pain"),
+        &Symbol::new(&env, "Routine"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &Vec::new(&env),
+    );
+    assert_eq!(res, Err(Ok(Error::ProviderNotListed)));
+
+    let referral_id = client.create_referral(
+        &referring_provider,
+        &patient_id,
+        &cardiologist,
+        &cardio,
+        &String::from_str(&env, "Heart palpitations"),
+        &Symbol::new(&env, "Urgent"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &Vec::new(&env),
+    );
+    assert_eq!(referral_id, 1);
+}
+
+mod mock_exchange {
+    use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, Symbol};
+
+    #[contract]
+    pub struct MockExchange;
+
+    #[contractimpl]
+    impl MockExchange {
+        pub fn register_exchanged_document(
+            env: Env,
+            _integration: Address,
+            source: Address,
+            recipient: Address,
+            _patient_id: Address,
+            document_type: Symbol,
+            _content_hash: BytesN<32>,
+        ) -> u64 {
+            env.storage().instance().set(
+                &Symbol::new(&env, "last"),
+                &(source, recipient, document_type),
+            );
+            1
+        }
+
+        pub fn last(env: Env) -> Option<(Address, Address, Symbol)> {
+            env.storage().instance().get(&Symbol::new(&env, "last"))
+        }
+    }
+}
+
+#[test]
+fn test_shared_care_summary_registered_with_exchange() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ReferralContract, ());
+    let client = ReferralContractClient::new(&env, &contract_id);
+    let exchange_id = env.register(mock_exchange::MockExchange, ());
+    let exchange = mock_exchange::MockExchangeClient::new(&env, &exchange_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_document_exchange(&admin, &exchange_id);
+
+    let referring_provider = Address::generate(&env);
+    let referred_to = Address::generate(&env);
+    let referral_id = client.create_referral(
+        &referring_provider,
+        &Address::generate(&env),
+        &referred_to,
+        &Symbol::new(&env, "Ortho"),
+        &String::from_str(&env, "Knee pain"),
+        &Symbol::new(&env, "Routine"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &Vec::new(&env),
+    );
+
+    let summary_type = Symbol::new(&env, "ConsultNote");
+    client.share_care_summary(
+        &referral_id,
+        &referred_to,
+        &summary_type,
+        &BytesN::from_array(&env, &[5; 32]),
+    );
+    assert_eq!(
+        exchange.last(),
+        Some((referred_to, referring_provider, summary_type))
+    );
+}
+
+#[test]
+fn test_acceptance_requires_checklist_attachments() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ReferralContract, ());
+    let client = ReferralContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let specialty = Symbol::new(&env, "Cardio");
+    let labs = String::from_str(&env, "labs");
+    let med_list = String::from_str(&env, "med list");
+    client.set_required_attachments(
+        &admin,
+        &specialty,
+        &Vec::from_array(&env, [labs.clone(), med_list.clone()]),
+    );
+
+    let referring_provider = Address::generate(&env);
+    let referred_to = Address::generate(&env);
+    let referral_id = client.create_referral(
+        &referring_provider,
+        &Address::generate(&env),
+        &referred_to,
+        &specialty,
+        &String::from_str(&env, "Heart palpitations"),
+        &Symbol::new(&env, "Urgent"),
+        &BytesN::from_array(&env, &[1; 32]),
+        &Vec::new(&env),
+    );
+
+    client.attach_referral_item(
+        &referral_id,
+        &referring_provider,
+        &labs,
+        &BytesN::from_array(&env, &[2; 32]),
+    );
+    assert_eq!(
+        client.try_attach_referral_item(
+            &referral_id,
+            &referred_to,
+            &med_list,
+            &BytesN::from_array(&env, &[3; 32]),
+        ),
+        Err(Ok(Error::NotAuthorized))
+    );
+    assert_eq!(
+        client.try_accept_referral(&referral_id, &referred_to, &None),
+        Err(Ok(Error::MissingRequiredAttachments))
+    );
+
+    // The receiver formally asks for what is missing.
+    let requested = client.request_missing_attachments(&referral_id, &referred_to);
+    assert_eq!(requested, Vec::from_array(&env, [med_list.clone()]));
+    assert_eq!(env.events().all().len(), 1);
+    let request = client.get_attachment_request(&referral_id).unwrap();
+    assert_eq!(request.requested_by, referred_to);
+    assert_eq!(request.items, requested);
+
+    client.attach_referral_item(
+        &referral_id,
+        &referring_provider,
+        &med_list,
+        &BytesN::from_array(&env, &[3; 32]),
+    );
+    // Re-attaching an item replaces it.
+    client.attach_referral_item(
+        &referral_id,
+        &referring_provider,
+        &labs,
+        &BytesN::from_array(&env, &[4; 32]),
+    );
+    let attachments = client.get_referral_attachments(&referral_id);
+    assert_eq!(attachments.len(), 2);
+    assert_eq!(
+        attachments.get(1).unwrap().document_hash,
+        BytesN::from_array(&env, &[4; 32])
+    );
+    assert!(client.get_missing_attachments(&referral_id).is_empty());
+
+    client.accept_referral(&referral_id, &referred_to, &None);
+}
+
+#[test]
+fn test_patient_acknowledges_and_tracks_referrals() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(ReferralContract, ());
+    let client = ReferralContractClient::new(&env, &contract_id);
+
+    let referring_provider = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let referred_to = Address::generate(&env);
+    let mut referral_ids = Vec::new(&env);
+    for _ in 0..3 {
+        referral_ids.push_back(client.create_referral(
+            &referring_provider,
+            &patient_id,
+            &referred_to,
+            &Symbol::new(&env, "Ortho"),
+            &String::from_str(&env, "Knee pain"),
+            &Symbol::new(&env, "Routine"),
+            &BytesN::from_array(&env, &[1; 32]),
+            &Vec::new(&env),
+        ));
+    }
+    let referral_id = referral_ids.get(0).unwrap();
+
+    let page = client.get_patient_referrals(&patient_id, &1, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(
+        page.get(0).unwrap().referral_id,
+        referral_ids.get(1).unwrap()
+    );
+    assert!(client
+        .get_patient_referrals(&Address::generate(&env), &0, &10)
+        .is_empty());
+
+    client.accept_referral(&referral_id, &referred_to, &None);
+    assert_eq!(env.events().all().len(), 2);
+
+    let scheduled = Symbol::new(&env, "Scheduled");
+    assert_eq!(
+        client.try_update_referral_status(&referral_id, &referred_to, &scheduled, &None),
+        Err(Ok(Error::PatientNotAcknowledged))
+    );
+    assert_eq!(
+        client.try_acknowledge_referral(&referral_id, &referring_provider),
+        Err(Ok(Error::NotAuthorized))
+    );
+
+    client.acknowledge_referral(&referral_id, &patient_id);
+    assert!(client.get_patient_acknowledgment(&referral_id).is_some());
+    client.update_referral_status(&referral_id, &referred_to, &scheduled, &None);
+    assert_eq!(env.events().all().len(), 2);
+
+    let referral = client
+        .get_patient_referrals(&patient_id, &0, &1)
+        .get(0)
+        .unwrap();
+    assert_eq!(referral.status, ReferralStatus::Scheduled);
+}
@@ -1,4 +1,6 @@
-use soroban_sdk::{contracterror, contracttype, Address, String, Symbol};
+use soroban_sdk::{
+    contracterror, contractevent, contracttype, Address, BytesN, String, Symbol, Vec,
+};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -7,6 +9,11 @@ pub enum Error {
     NotAuthorized = 1,
     ReferralNotFound = 2,
     InvalidStatusTransition = 3,
+    AlreadyInitialized = 4,
+    NotInitialized = 5,
+    ProviderNotListed = 6,
+    MissingRequiredAttachments = 7,
+    PatientNotAcknowledged = 8,
 }
 
 #[contracttype]
@@ -37,9 +44,77 @@ pub struct Referral {
     pub completed_at: Option<u64>,
 }
 
+/// A checklist item the referring provider has supplied, e.g. "labs".
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralAttachment {
+    pub item: String,
+    pub document_hash: BytesN<32>,
+    pub provided_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralItemAttached {
+    #[topic]
+    pub referral_id: u64,
+    pub item: String,
+    pub document_hash: BytesN<32>,
+}
+
+/// A provider on the referral asked for care-summary items, either directly
+/// or as the missing attachments on the checklist.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CareSummaryRequested {
+    #[topic]
+    pub referral_id: u64,
+    pub requesting_provider: Address,
+    pub information_needed: Vec<String>,
+}
+
+/// Published for the patient whenever their referral is accepted,
+/// scheduled or completed, so patient apps can follow it by topic.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientReferralUpdated {
+    #[topic]
+    pub patient_id: Address,
+    #[topic]
+    pub referral_id: u64,
+    pub status: ReferralStatus,
+}
+
+/// The patient confirmed they are willing to be scheduled.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralAcknowledged {
+    #[topic]
+    pub referral_id: u64,
+    pub patient_id: Address,
+    pub acknowledged_at: u64,
+}
+
+/// The receiving provider's formal request for missing checklist items.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AttachmentRequest {
+    pub requested_by: Address,
+    pub items: Vec<String>,
+    pub requested_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Referral(u64),
     ReferralCount,
+    Admin,
+    ProviderDirectory,
+    DocumentExchange,
+    RequiredAttachments(Symbol),
+    ReferralAttachments(u64),
+    AttachmentRequest(u64),
+    PatientReferrals(Address),
+    PatientAcknowledgment(u64),
 }
@@ -0,0 +1,160 @@
+use soroban_sdk::{contractevent, Address, BytesN, String, Symbol};
+
+use crate::AppointmentStatus;
+
+// Every state-changing entrypoint publishes one of these, with the
+// evaluation, plan, authorization or batch id as the first topic.
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EvaluationConducted {
+    #[topic]
+    pub evaluation_id: u64,
+    #[topic]
+    pub patient_id: Address,
+    pub therapist_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RangeOfMotionAssessed {
+    #[topic]
+    pub evaluation_id: u64,
+    pub joint: String,
+    pub degrees: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StrengthAssessed {
+    #[topic]
+    pub evaluation_id: u64,
+    pub muscle_group: String,
+    pub side: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BalanceMobilityAssessed {
+    #[topic]
+    pub evaluation_id: u64,
+    pub test_type: Symbol,
+    pub fall_risk: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TreatmentPlanCreated {
+    #[topic]
+    pub plan_id: u64,
+    pub evaluation_id: u64,
+    pub therapist_id: Address,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapySessionDocumented {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub session_date: u64,
+    pub session_duration_minutes: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionBillingRecorded {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub session_index: u32,
+    pub total_units: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillingExportCommitted {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub export_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PainLevelTracked {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub measurement_date: u64,
+    pub pain_score: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunctionalOutcomeMeasured {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub outcome_tool: Symbol,
+    pub score: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapyAuthorizationRequested {
+    #[topic]
+    pub auth_id: u64,
+    pub treatment_plan_id: u64,
+    pub requested_visits: u32,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProgressNoteDocumented {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub note_date: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapyDischarged {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub discharge_date: u64,
+    pub discharge_reason: Symbol,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeRegistryBatchCommitted {
+    #[topic]
+    pub batch_id: u64,
+    pub episode_count: u32,
+    pub commitment_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapyAppointmentScheduled {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub appointment_id: u64,
+    pub scheduled_for: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapyAppointmentUpdated {
+    #[topic]
+    pub treatment_plan_id: u64,
+    pub appointment_id: u64,
+    pub status: AppointmentStatus,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuthorizationAtRisk {
+    #[topic]
+    pub auth_id: u64,
+    pub treatment_plan_id: u64,
+    pub no_shows: u32,
+}
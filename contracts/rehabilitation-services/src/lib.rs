@@ -1,9 +1,21 @@
 #![no_std]
 
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+    contract, contractimpl, contracttype, xdr::ToXdr, Address, BytesN, Env, String, Symbol, Vec,
 };
 
+/// No-shows on a plan at which its authorization is flagged as at risk.
+const NO_SHOW_AUTHORIZATION_THRESHOLD: u32 = 3;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+mod events;
+mod reporting;
+
+pub use events::*;
+pub use reporting::{EpisodeOutcomeSummary, OutcomeRegistryBatch};
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct RehabGoal {
@@ -90,6 +102,90 @@ pub struct TherapySession {
     pub homework_assigned: Option<String>,
 }
 
+/// A CPT-style code billed for a session. Timed codes carry the minutes
+/// spent on them; untimed codes bill their units as given.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillingCodeEntry {
+    pub code: String,
+    pub timed: bool,
+    pub minutes: u32,
+    pub units: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionBilling {
+    pub session_index: u32,
+    pub session_date: u64,
+    pub codes: Vec<BillingCodeEntry>,
+    pub total_timed_minutes: u32,
+    pub total_units: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CodeUnits {
+    pub code: String,
+    pub units: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlanBillingSummary {
+    pub treatment_plan_id: u64,
+    pub sessions_billed: u32,
+    pub total_timed_minutes: u32,
+    pub total_units: u32,
+    pub units_by_code: Vec<CodeUnits>,
+}
+
+/// Commitment to the billing records of one period, so an export sent to
+/// a payer can be checked against the chain.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BillingExportCommitment {
+    pub treatment_plan_id: u64,
+    pub period_start: u64,
+    pub period_end: u64,
+    pub sessions: u32,
+    pub total_units: u32,
+    pub export_hash: BytesN<32>,
+    pub committed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AppointmentStatus {
+    Scheduled,
+    Completed,
+    CancelledByPatient,
+    NoShow,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TherapyAppointment {
+    pub appointment_id: u64,
+    pub treatment_plan_id: u64,
+    pub scheduled_for: u64,
+    pub status: AppointmentStatus,
+    pub updated_at: u64,
+}
+
+/// A patient's appointment outcomes across all plans. Rates are in basis
+/// points of resolved (no longer scheduled) appointments.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AttendanceStats {
+    pub scheduled: u32,
+    pub completed: u32,
+    pub cancelled: u32,
+    pub no_shows: u32,
+    pub cancellation_rate_bps: u32,
+    pub no_show_rate_bps: u32,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct PainMeasurement {
@@ -141,6 +237,7 @@ pub struct DischargeRecord {
 
 #[contracttype]
 pub enum DataKey {
+    Admin,
     EvaluationCounter,
     Evaluation(u64),
     ROMAssessments(u64),
@@ -155,6 +252,17 @@ pub enum DataKey {
     Authorization(u64),
     ProgressNotes(u64),
     Discharge(u64),
+    SessionBilling(u64, u32),
+    BilledSessions(u64),
+    BillingExport(u64, u64),
+    OutcomeBatchCounter,
+    OutcomeBatch(u64),
+    AppointmentCounter,
+    Appointment(u64),
+    PlanAppointments(u64),
+    PlanNoShows(u64),
+    PlanAuthorization(u64),
+    PatientAttendance(Address),
 }
 
 #[contracttype]
@@ -165,6 +273,8 @@ pub enum Error {
     Unauthorized = 2,
     InvalidInput = 3,
     AlreadyExists = 4,
+    AlreadyInitialized = 5,
+    NotInitialized = 6,
 }
 
 impl From<Error> for soroban_sdk::Error {
@@ -192,6 +302,16 @@ pub struct RehabilitationServicesContract;
 
 #[contractimpl]
 impl RehabilitationServicesContract {
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
     pub fn conduct_pt_evaluation(
         env: Env,
         patient_id: Address,
@@ -214,8 +334,8 @@ impl RehabilitationServicesContract {
 
         let evaluation = PTEvaluation {
             evaluation_id: eval_id,
-            patient_id,
-            therapist_id,
+            patient_id: patient_id.clone(),
+            therapist_id: therapist_id.clone(),
             evaluation_date,
             diagnosis,
             chief_complaint,
@@ -231,6 +351,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::EvaluationCounter, &eval_id);
 
+        EvaluationConducted {
+            evaluation_id: eval_id,
+            patient_id,
+            therapist_id,
+        }
+        .publish(&env);
+
         Ok(eval_id)
     }
 
@@ -252,7 +379,7 @@ impl RehabilitationServicesContract {
         eval.therapist_id.require_auth();
 
         let assessment = ROMAssessment {
-            joint,
+            joint: joint.clone(),
             movement,
             degrees,
             pain_level,
@@ -270,6 +397,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::ROMAssessments(evaluation_id), &assessments);
 
+        RangeOfMotionAssessed {
+            evaluation_id,
+            joint,
+            degrees,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -289,9 +423,9 @@ impl RehabilitationServicesContract {
         eval.therapist_id.require_auth();
 
         let assessment = StrengthAssessment {
-            muscle_group,
+            muscle_group: muscle_group.clone(),
             manual_muscle_test_grade,
-            side,
+            side: side.clone(),
         };
 
         let mut assessments: Vec<StrengthAssessment> = env
@@ -305,6 +439,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::StrengthAssessments(evaluation_id), &assessments);
 
+        StrengthAssessed {
+            evaluation_id,
+            muscle_group,
+            side,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -324,9 +465,9 @@ impl RehabilitationServicesContract {
         eval.therapist_id.require_auth();
 
         let assessment = BalanceMobilityAssessment {
-            test_type,
+            test_type: test_type.clone(),
             score,
-            fall_risk,
+            fall_risk: fall_risk.clone(),
         };
 
         let mut assessments: Vec<BalanceMobilityAssessment> = env
@@ -343,6 +484,13 @@ impl RehabilitationServicesContract {
                 &assessments,
             );
 
+        BalanceMobilityAssessed {
+            evaluation_id,
+            test_type,
+            fall_risk,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -375,7 +523,7 @@ impl RehabilitationServicesContract {
         let plan = RehabTreatmentPlan {
             plan_id,
             evaluation_id,
-            therapist_id,
+            therapist_id: therapist_id.clone(),
             stg_goals,
             ltg_goals,
             interventions,
@@ -391,6 +539,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::TreatmentPlanCounter, &plan_id);
 
+        TreatmentPlanCreated {
+            plan_id,
+            evaluation_id,
+            therapist_id,
+        }
+        .publish(&env);
+
         Ok(plan_id)
     }
 
@@ -430,9 +585,362 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::TherapySessions(treatment_plan_id), &sessions);
 
+        TherapySessionDocumented {
+            treatment_plan_id,
+            session_date,
+            session_duration_minutes,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
+    /// Attach billing codes to a documented session, identified by its
+    /// position in the plan's session list. Timed units must match the
+    /// 8-minute rule applied to the total timed minutes, which cannot exceed
+    /// the session length.
+    pub fn record_session_billing(
+        env: Env,
+        treatment_plan_id: u64,
+        session_index: u32,
+        codes: Vec<BillingCodeEntry>,
+    ) -> Result<(), Error> {
+        let plan: RehabTreatmentPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreatmentPlan(treatment_plan_id))
+            .ok_or(Error::NotFound)?;
+
+        plan.therapist_id.require_auth();
+
+        let sessions: Vec<TherapySession> = env
+            .storage()
+            .instance()
+            .get(&DataKey::TherapySessions(treatment_plan_id))
+            .unwrap_or(Vec::new(&env));
+        let session = sessions.get(session_index).ok_or(Error::NotFound)?;
+
+        let key = DataKey::SessionBilling(treatment_plan_id, session_index);
+        if env.storage().instance().has(&key) {
+            return Err(Error::AlreadyExists);
+        }
+        if codes.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut total_timed_minutes = 0u32;
+        let mut timed_units = 0u32;
+        let mut total_units = 0u32;
+        for entry in codes.iter() {
+            if entry.units == 0 {
+                return Err(Error::InvalidInput);
+            }
+            if entry.timed {
+                total_timed_minutes += entry.minutes;
+                timed_units += entry.units;
+            }
+            total_units += entry.units;
+        }
+
+        if total_timed_minutes > session.session_duration_minutes
+            || timed_units != Self::timed_units_for(total_timed_minutes)
+        {
+            return Err(Error::InvalidInput);
+        }
+
+        let billing = SessionBilling {
+            session_index,
+            session_date: session.session_date,
+            codes,
+            total_timed_minutes,
+            total_units,
+        };
+        env.storage().instance().set(&key, &billing);
+
+        let mut billed: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::BilledSessions(treatment_plan_id))
+            .unwrap_or(Vec::new(&env));
+        billed.push_back(session_index);
+        env.storage()
+            .instance()
+            .set(&DataKey::BilledSessions(treatment_plan_id), &billed);
+
+        SessionBillingRecorded {
+            treatment_plan_id,
+            session_index,
+            total_units,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    pub fn get_session_billing(
+        env: Env,
+        treatment_plan_id: u64,
+        session_index: u32,
+    ) -> Result<SessionBilling, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::SessionBilling(treatment_plan_id, session_index))
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn get_plan_billing_summary(env: Env, treatment_plan_id: u64) -> PlanBillingSummary {
+        let billings = Self::plan_billings(&env, treatment_plan_id, 0, u64::MAX);
+
+        let mut total_timed_minutes = 0u32;
+        let mut total_units = 0u32;
+        let mut units_by_code: Vec<CodeUnits> = Vec::new(&env);
+        for billing in billings.iter() {
+            total_timed_minutes += billing.total_timed_minutes;
+            total_units += billing.total_units;
+            for entry in billing.codes.iter() {
+                let existing = units_by_code.iter().position(|c| c.code == entry.code);
+                match existing {
+                    Some(i) => {
+                        let mut code_units = units_by_code.get_unchecked(i as u32);
+                        code_units.units += entry.units;
+                        units_by_code.set(i as u32, code_units);
+                    }
+                    None => units_by_code.push_back(CodeUnits {
+                        code: entry.code,
+                        units: entry.units,
+                    }),
+                }
+            }
+        }
+
+        PlanBillingSummary {
+            treatment_plan_id,
+            sessions_billed: billings.len(),
+            total_timed_minutes,
+            total_units,
+            units_by_code,
+        }
+    }
+
+    /// Commit to the plan's billing for sessions dated within the period.
+    /// The hash covers the XDR encoding of those billing records in session
+    /// order.
+    pub fn commit_billing_export(
+        env: Env,
+        treatment_plan_id: u64,
+        period_start: u64,
+        period_end: u64,
+    ) -> Result<BytesN<32>, Error> {
+        let plan: RehabTreatmentPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreatmentPlan(treatment_plan_id))
+            .ok_or(Error::NotFound)?;
+
+        plan.therapist_id.require_auth();
+
+        if period_start > period_end {
+            return Err(Error::InvalidInput);
+        }
+        let key = DataKey::BillingExport(treatment_plan_id, period_start);
+        if env.storage().instance().has(&key) {
+            return Err(Error::AlreadyExists);
+        }
+
+        let billings = Self::plan_billings(&env, treatment_plan_id, period_start, period_end);
+        let total_units = billings.iter().map(|b| b.total_units).sum();
+        let export_hash: BytesN<32> = env.crypto().sha256(&billings.clone().to_xdr(&env)).into();
+
+        let commitment = BillingExportCommitment {
+            treatment_plan_id,
+            period_start,
+            period_end,
+            sessions: billings.len(),
+            total_units,
+            export_hash: export_hash.clone(),
+            committed_at: env.ledger().timestamp(),
+        };
+        env.storage().instance().set(&key, &commitment);
+
+        BillingExportCommitted {
+            treatment_plan_id,
+            period_start,
+            period_end,
+            export_hash: export_hash.clone(),
+        }
+        .publish(&env);
+
+        Ok(export_hash)
+    }
+
+    pub fn get_billing_export(
+        env: Env,
+        treatment_plan_id: u64,
+        period_start: u64,
+    ) -> Result<BillingExportCommitment, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::BillingExport(treatment_plan_id, period_start))
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn schedule_therapy_appointment(
+        env: Env,
+        treatment_plan_id: u64,
+        scheduled_for: u64,
+    ) -> Result<u64, Error> {
+        let plan: RehabTreatmentPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreatmentPlan(treatment_plan_id))
+            .ok_or(Error::NotFound)?;
+
+        plan.therapist_id.require_auth();
+
+        let appointment_id = env
+            .storage()
+            .instance()
+            .get(&DataKey::AppointmentCounter)
+            .unwrap_or(0u64)
+            + 1;
+
+        let appointment = TherapyAppointment {
+            appointment_id,
+            treatment_plan_id,
+            scheduled_for,
+            status: AppointmentStatus::Scheduled,
+            updated_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::Appointment(appointment_id), &appointment);
+        env.storage()
+            .instance()
+            .set(&DataKey::AppointmentCounter, &appointment_id);
+
+        let mut appointments: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlanAppointments(treatment_plan_id))
+            .unwrap_or(Vec::new(&env));
+        appointments.push_back(appointment_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::PlanAppointments(treatment_plan_id), &appointments);
+
+        let patient_id = Self::plan_patient(&env, &plan)?;
+        Self::update_attendance(&env, &patient_id, |stats| stats.scheduled += 1);
+
+        TherapyAppointmentScheduled {
+            treatment_plan_id,
+            appointment_id,
+            scheduled_for,
+        }
+        .publish(&env);
+
+        Ok(appointment_id)
+    }
+
+    /// Resolve a scheduled appointment. Reaching the no-show threshold on a
+    /// plan that has an authorization publishes `AuthorizationAtRisk`.
+    pub fn update_appointment_status(
+        env: Env,
+        appointment_id: u64,
+        status: AppointmentStatus,
+    ) -> Result<(), Error> {
+        let mut appointment: TherapyAppointment = env
+            .storage()
+            .instance()
+            .get(&DataKey::Appointment(appointment_id))
+            .ok_or(Error::NotFound)?;
+        let plan: RehabTreatmentPlan = env
+            .storage()
+            .instance()
+            .get(&DataKey::TreatmentPlan(appointment.treatment_plan_id))
+            .ok_or(Error::NotFound)?;
+
+        plan.therapist_id.require_auth();
+
+        if appointment.status != AppointmentStatus::Scheduled || status == AppointmentStatus::Scheduled {
+            return Err(Error::InvalidInput);
+        }
+
+        appointment.status = status;
+        appointment.updated_at = env.ledger().timestamp();
+        env.storage()
+            .instance()
+            .set(&DataKey::Appointment(appointment_id), &appointment);
+
+        let patient_id = Self::plan_patient(&env, &plan)?;
+        Self::update_attendance(&env, &patient_id, |stats| match status {
+            AppointmentStatus::Completed => stats.completed += 1,
+            AppointmentStatus::CancelledByPatient => stats.cancelled += 1,
+            AppointmentStatus::NoShow => stats.no_shows += 1,
+            AppointmentStatus::Scheduled => {}
+        });
+
+        TherapyAppointmentUpdated {
+            treatment_plan_id: appointment.treatment_plan_id,
+            appointment_id,
+            status,
+        }
+        .publish(&env);
+
+        if status == AppointmentStatus::NoShow {
+            let treatment_plan_id = appointment.treatment_plan_id;
+            let no_shows: u32 = env
+                .storage()
+                .instance()
+                .get(&DataKey::PlanNoShows(treatment_plan_id))
+                .unwrap_or(0)
+                + 1;
+            env.storage()
+                .instance()
+                .set(&DataKey::PlanNoShows(treatment_plan_id), &no_shows);
+
+            let auth_id: Option<u64> = env
+                .storage()
+                .instance()
+                .get(&DataKey::PlanAuthorization(treatment_plan_id));
+            if let (true, Some(auth_id)) = (no_shows == NO_SHOW_AUTHORIZATION_THRESHOLD, auth_id) {
+                AuthorizationAtRisk {
+                    auth_id,
+                    treatment_plan_id,
+                    no_shows,
+                }
+                .publish(&env);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn get_appointment(env: Env, appointment_id: u64) -> Result<TherapyAppointment, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Appointment(appointment_id))
+            .ok_or(Error::NotFound)
+    }
+
+    pub fn get_plan_appointments(env: Env, treatment_plan_id: u64, offset: u32, limit: u32) -> Vec<TherapyAppointment> {
+        let appointments: Vec<u64> = env
+            .storage()
+            .instance()
+            .get(&DataKey::PlanAppointments(treatment_plan_id))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate_map(&env, &appointments, offset, limit, |appointment_id| {
+            env.storage().instance().get(&DataKey::Appointment(appointment_id))
+        })
+    }
+
+    pub fn get_patient_attendance(env: Env, patient_id: Address) -> AttendanceStats {
+        env.storage()
+            .instance()
+            .get(&DataKey::PatientAttendance(patient_id))
+            .unwrap_or_default()
+    }
+
     pub fn track_pain_level(
         env: Env,
         treatment_plan_id: u64,
@@ -469,6 +977,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::PainMeasurements(treatment_plan_id), &measurements);
 
+        PainLevelTracked {
+            treatment_plan_id,
+            measurement_date,
+            pain_score,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -490,7 +1005,7 @@ impl RehabilitationServicesContract {
 
         let outcome = FunctionalOutcome {
             measurement_date,
-            outcome_tool,
+            outcome_tool: outcome_tool.clone(),
             score,
             minimal_detectable_change,
         };
@@ -506,6 +1021,13 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::FunctionalOutcomes(treatment_plan_id), &outcomes);
 
+        FunctionalOutcomeMeasured {
+            treatment_plan_id,
+            outcome_tool,
+            score,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -544,6 +1066,16 @@ impl RehabilitationServicesContract {
         env.storage()
             .instance()
             .set(&DataKey::AuthorizationCounter, &auth_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::PlanAuthorization(treatment_plan_id), &auth_id);
+
+        TherapyAuthorizationRequested {
+            auth_id,
+            treatment_plan_id,
+            requested_visits,
+        }
+        .publish(&env);
 
         Ok(auth_id)
     }
@@ -584,6 +1116,12 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::ProgressNotes(treatment_plan_id), &notes);
 
+        ProgressNoteDocumented {
+            treatment_plan_id,
+            note_date,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
@@ -606,7 +1144,7 @@ impl RehabilitationServicesContract {
 
         let discharge = DischargeRecord {
             discharge_date,
-            discharge_reason,
+            discharge_reason: discharge_reason.clone(),
             goals_met,
             final_outcomes_hash,
             home_exercise_program_hash,
@@ -616,9 +1154,109 @@ impl RehabilitationServicesContract {
             .instance()
             .set(&DataKey::Discharge(treatment_plan_id), &discharge);
 
+        TherapyDischarged {
+            treatment_plan_id,
+            discharge_date,
+            discharge_reason,
+        }
+        .publish(&env);
+
         Ok(())
     }
 
+    /// Assemble de-identified outcome summaries for discharged episodes
+    /// into a registry batch, scored on `outcome_tool`, and commit to it.
+    /// The submitter must be the treating therapist on every plan.
+    pub fn export_outcome_registry_batch(
+        env: Env,
+        submitter: Address,
+        treatment_plan_ids: Vec<u64>,
+        outcome_tool: Symbol,
+    ) -> Result<OutcomeRegistryBatch, Error> {
+        submitter.require_auth();
+
+        if treatment_plan_ids.is_empty() {
+            return Err(Error::InvalidInput);
+        }
+
+        let mut episodes = Vec::new(&env);
+        for treatment_plan_id in treatment_plan_ids.iter() {
+            let plan: RehabTreatmentPlan = env
+                .storage()
+                .instance()
+                .get(&DataKey::TreatmentPlan(treatment_plan_id))
+                .ok_or(Error::NotFound)?;
+            if plan.therapist_id != submitter {
+                return Err(Error::Unauthorized);
+            }
+            let discharge: DischargeRecord = env
+                .storage()
+                .instance()
+                .get(&DataKey::Discharge(treatment_plan_id))
+                .ok_or(Error::NotFound)?;
+            let outcomes: Vec<FunctionalOutcome> = env
+                .storage()
+                .instance()
+                .get(&DataKey::FunctionalOutcomes(treatment_plan_id))
+                .unwrap_or(Vec::new(&env));
+            let sessions: Vec<TherapySession> = env
+                .storage()
+                .instance()
+                .get(&DataKey::TherapySessions(treatment_plan_id))
+                .unwrap_or(Vec::new(&env));
+
+            let summary = reporting::episode_summary(
+                episodes.len(),
+                &plan,
+                &outcomes,
+                sessions.len(),
+                &discharge,
+                &outcome_tool,
+            )
+            .ok_or(Error::InvalidInput)?;
+            episodes.push_back(summary);
+        }
+
+        let batch_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::OutcomeBatchCounter)
+            .unwrap_or(0)
+            + 1;
+        let commitment_hash = reporting::commitment_hash(&env, &episodes);
+        let batch = OutcomeRegistryBatch {
+            batch_id,
+            submitter,
+            outcome_tool,
+            episodes,
+            commitment_hash: commitment_hash.clone(),
+            created_at: env.ledger().timestamp(),
+        };
+
+        env.storage()
+            .instance()
+            .set(&DataKey::OutcomeBatchCounter, &batch_id);
+        env.storage()
+            .instance()
+            .set(&DataKey::OutcomeBatch(batch_id), &batch);
+
+        OutcomeRegistryBatchCommitted {
+            batch_id,
+            episode_count: batch.episodes.len(),
+            commitment_hash,
+        }
+        .publish(&env);
+
+        Ok(batch)
+    }
+
+    pub fn get_outcome_registry_batch(env: Env, batch_id: u64) -> Result<OutcomeRegistryBatch, Error> {
+        env.storage()
+            .instance()
+            .get(&DataKey::OutcomeBatch(batch_id))
+            .ok_or(Error::NotFound)
+    }
+
     // Query functions
     pub fn get_evaluation(env: Env, evaluation_id: u64) -> Result<PTEvaluation, Error> {
         env.storage()
@@ -648,32 +1286,40 @@ impl RehabilitationServicesContract {
             .unwrap_or(Vec::new(&env))
     }
 
-    pub fn get_therapy_sessions(env: Env, treatment_plan_id: u64) -> Vec<TherapySession> {
-        env.storage()
+    pub fn get_therapy_sessions(env: Env, treatment_plan_id: u64, offset: u32, limit: u32) -> Vec<TherapySession> {
+        let items = env
+            .storage()
             .instance()
             .get(&DataKey::TherapySessions(treatment_plan_id))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
-    pub fn get_pain_measurements(env: Env, treatment_plan_id: u64) -> Vec<PainMeasurement> {
-        env.storage()
+    pub fn get_pain_measurements(env: Env, treatment_plan_id: u64, offset: u32, limit: u32) -> Vec<PainMeasurement> {
+        let items = env
+            .storage()
             .instance()
             .get(&DataKey::PainMeasurements(treatment_plan_id))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
-    pub fn get_functional_outcomes(env: Env, treatment_plan_id: u64) -> Vec<FunctionalOutcome> {
-        env.storage()
+    pub fn get_functional_outcomes(env: Env, treatment_plan_id: u64, offset: u32, limit: u32) -> Vec<FunctionalOutcome> {
+        let items = env
+            .storage()
             .instance()
             .get(&DataKey::FunctionalOutcomes(treatment_plan_id))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
-    pub fn get_progress_notes(env: Env, treatment_plan_id: u64) -> Vec<ProgressNote> {
-        env.storage()
+    pub fn get_progress_notes(env: Env, treatment_plan_id: u64, offset: u32, limit: u32) -> Vec<ProgressNote> {
+        let items = env
+            .storage()
             .instance()
             .get(&DataKey::ProgressNotes(treatment_plan_id))
-            .unwrap_or(Vec::new(&env))
+            .unwrap_or(Vec::new(&env));
+        shared_pagination::paginate(&items, offset, limit)
     }
 
     pub fn get_discharge_record(env: Env, treatment_plan_id: u64) -> Result<DischargeRecord, Error> {
@@ -689,6 +1335,90 @@ impl RehabilitationServicesContract {
             .get(&DataKey::BalanceMobilityAssessments(evaluation_id))
             .unwrap_or(Vec::new(&env))
     }
+
+    fn plan_patient(env: &Env, plan: &RehabTreatmentPlan) -> Result<Address, Error> {
+        let evaluation: PTEvaluation = env
+            .storage()
+            .instance()
+            .get(&DataKey::Evaluation(plan.evaluation_id))
+            .ok_or(Error::NotFound)?;
+        Ok(evaluation.patient_id)
+    }
+
+    fn update_attendance(env: &Env, patient_id: &Address, update: impl FnOnce(&mut AttendanceStats)) {
+        let key = DataKey::PatientAttendance(patient_id.clone());
+        let mut stats: AttendanceStats = env.storage().instance().get(&key).unwrap_or_default();
+        update(&mut stats);
+
+        let resolved = stats.completed + stats.cancelled + stats.no_shows;
+        stats.cancellation_rate_bps = (stats.cancelled * 10_000).checked_div(resolved).unwrap_or(0);
+        stats.no_show_rate_bps = (stats.no_shows * 10_000).checked_div(resolved).unwrap_or(0);
+        env.storage().instance().set(&key, &stats);
+    }
+
+    /// Units allowed for the total timed minutes under the 8-minute rule:
+    /// one unit per full 15 minutes, plus one more for a remainder of 8 or
+    /// more.
+    fn timed_units_for(total_timed_minutes: u32) -> u32 {
+        let units = total_timed_minutes / 15;
+        if total_timed_minutes % 15 >= 8 {
+            units + 1
+        } else {
+            units
+        }
+    }
+
+    fn plan_billings(env: &Env, treatment_plan_id: u64, from: u64, to: u64) -> Vec<SessionBilling> {
+        let billed: Vec<u32> = env
+            .storage()
+            .instance()
+            .get(&DataKey::BilledSessions(treatment_plan_id))
+            .unwrap_or(Vec::new(env));
+
+        let mut billings = Vec::new(env);
+        for session_index in billed.iter() {
+            let billing: Option<SessionBilling> = env
+                .storage()
+                .instance()
+                .get(&DataKey::SessionBilling(treatment_plan_id, session_index));
+            if let Some(billing) = billing {
+                if billing.session_date >= from && billing.session_date <= to {
+                    billings.push_back(billing);
+                }
+            }
+        }
+        billings
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        Self::require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        Self::require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+
+    fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != *admin {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
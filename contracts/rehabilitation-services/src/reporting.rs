@@ -0,0 +1,64 @@
+use soroban_sdk::{contracttype, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{DischargeRecord, FunctionalOutcome, RehabTreatmentPlan};
+
+/// Outcome summary of one discharged episode of care, stripped of patient,
+/// therapist and plan identifiers. `episode_ref` is the episode's position
+/// in its batch.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EpisodeOutcomeSummary {
+    pub episode_ref: u32,
+    pub outcome_tool: Symbol,
+    pub start_score: u32,
+    pub end_score: u32,
+    pub score_change: i64,
+    pub visits_used: u32,
+    pub goals_total: u32,
+    pub goals_met: u32,
+    pub discharge_reason: Symbol,
+}
+
+/// A de-identified batch of episode summaries prepared for an outcome
+/// registry. `commitment_hash` is the SHA-256 of the XDR-encoded episodes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OutcomeRegistryBatch {
+    pub batch_id: u64,
+    pub submitter: Address,
+    pub outcome_tool: Symbol,
+    pub episodes: Vec<EpisodeOutcomeSummary>,
+    pub commitment_hash: BytesN<32>,
+    pub created_at: u64,
+}
+
+/// Summarise an episode on `outcome_tool`, scoring from its first to its
+/// last measurement with that tool. `None` if the tool was never used.
+pub fn episode_summary(
+    episode_ref: u32,
+    plan: &RehabTreatmentPlan,
+    outcomes: &Vec<FunctionalOutcome>,
+    visits_used: u32,
+    discharge: &DischargeRecord,
+    outcome_tool: &Symbol,
+) -> Option<EpisodeOutcomeSummary> {
+    let mut scores = outcomes.iter().filter(|o| o.outcome_tool == *outcome_tool);
+    let start_score = scores.next()?.score;
+    let end_score = scores.last().map_or(start_score, |o| o.score);
+
+    Some(EpisodeOutcomeSummary {
+        episode_ref,
+        outcome_tool: outcome_tool.clone(),
+        start_score,
+        end_score,
+        score_change: i64::from(end_score) - i64::from(start_score),
+        visits_used,
+        goals_total: plan.stg_goals.len() + plan.ltg_goals.len(),
+        goals_met: discharge.goals_met.len(),
+        discharge_reason: discharge.discharge_reason.clone(),
+    })
+}
+
+pub fn commitment_hash(env: &Env, episodes: &Vec<EpisodeOutcomeSummary>) -> BytesN<32> {
+    env.crypto().sha256(&episodes.clone().to_xdr(env)).into()
+}
@@ -1,7 +1,10 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Symbol, Vec};
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    Address, BytesN, Env, String, Symbol, Vec,
+};
 
 fn create_test_env() -> (Env, Address, Address) {
     let env = Env::default();
@@ -33,6 +36,7 @@ fn test_conduct_pt_evaluation() {
     );
 
     assert_eq!(eval_id, 1);
+    assert_eq!(env.events().all().len(), 1);
 
     let evaluation = client.get_evaluation(&eval_id);
     assert_eq!(evaluation.patient_id, patient);
@@ -269,7 +273,7 @@ fn test_document_therapy_session() {
         &Some(String::from_str(&env, "Home exercises")),
     );
 
-    let sessions = client.get_therapy_sessions(&plan_id);
+    let sessions = client.get_therapy_sessions(&plan_id, &0, &10);
     assert_eq!(sessions.len(), 1);
     assert_eq!(sessions.get(0).unwrap().session_duration_minutes, 45);
 }
@@ -336,7 +340,7 @@ fn test_track_pain_level() {
         &quality,
     );
 
-    let pain_measurements = client.get_pain_measurements(&plan_id);
+    let pain_measurements = client.get_pain_measurements(&plan_id, &0, &10);
     assert_eq!(pain_measurements.len(), 1);
     assert_eq!(pain_measurements.get(0).unwrap().pain_score, 6);
 }
@@ -400,7 +404,7 @@ fn test_measure_functional_outcome() {
         &true,
     );
 
-    let outcomes = client.get_functional_outcomes(&plan_id);
+    let outcomes = client.get_functional_outcomes(&plan_id, &0, &10);
     assert_eq!(outcomes.len(), 1);
     assert_eq!(outcomes.get(0).unwrap().score, 25);
 }
@@ -526,7 +530,7 @@ fn test_document_progress_note() {
         &plan_mods,
     );
 
-    let notes = client.get_progress_notes(&plan_id);
+    let notes = client.get_progress_notes(&plan_id, &0, &10);
     assert_eq!(notes.len(), 1);
 }
 
@@ -706,12 +710,311 @@ fn test_complete_rehab_workflow() {
     let rom_assessments = client.get_rom_assessments(&eval_id);
     assert_eq!(rom_assessments.len(), 1);
 
-    let sessions = client.get_therapy_sessions(&plan_id);
+    let sessions = client.get_therapy_sessions(&plan_id, &0, &10);
     assert_eq!(sessions.len(), 1);
 
-    let pain_measurements = client.get_pain_measurements(&plan_id);
+    let pain_measurements = client.get_pain_measurements(&plan_id, &0, &10);
     assert_eq!(pain_measurements.len(), 1);
 
-    let outcomes = client.get_functional_outcomes(&plan_id);
+    let outcomes = client.get_functional_outcomes(&plan_id, &0, &10);
     assert_eq!(outcomes.len(), 1);
 }
+
+#[test]
+fn test_session_billing_and_export_commitment() {
+    let (env, patient, therapist) = create_test_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RehabilitationServicesContract, ());
+    let client = RehabilitationServicesContractClient::new(&env, &contract_id);
+
+    let eval_id = client.conduct_pt_evaluation(
+        &patient,
+        &therapist,
+        &1000u64,
+        &String::from_str(&env, "Injury"),
+        &String::from_str(&env, "Pain"),
+        &Vec::from_array(&env, [String::from_str(&env, "Limited")]),
+        &String::from_str(&env, "Active"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    let goal = RehabGoal {
+        goal_id: 1,
+        goal_type: Symbol::new(&env, "stg"),
+        goal_description: String::from_str(&env, "Goal"),
+        target_date: 2000u64,
+        measurement_method: String::from_str(&env, "Method"),
+        achieved: false,
+    };
+    let intervention = TherapyIntervention {
+        intervention_type: Symbol::new(&env, "exercise"),
+        description: String::from_str(&env, "Exercise"),
+        sets: Some(3),
+        reps: Some(10),
+        duration: None,
+        resistance: None,
+    };
+    let plan_id = client.create_rehab_treatment_plan(
+        &eval_id,
+        &therapist,
+        &Vec::from_array(&env, [goal.clone()]),
+        &Vec::from_array(&env, [goal]),
+        &Vec::from_array(&env, [intervention.clone()]),
+        &String::from_str(&env, "3x/week"),
+        &8u32,
+        &Symbol::new(&env, "good"),
+    );
+    for session_date in [1500u64, 1600u64] {
+        client.document_therapy_session(
+            &plan_id,
+            &session_date,
+            &Vec::from_array(&env, [intervention.clone()]),
+            &45u32,
+            &String::from_str(&env, "Tolerated well"),
+            &None,
+        );
+    }
+
+    let code = |code: &str, timed: bool, minutes: u32, units: u32| BillingCodeEntry {
+        code: String::from_str(&env, code),
+        timed,
+        minutes,
+        units,
+    };
+
+    // 23 timed minutes bill two units under the 8-minute rule, not one.
+    let under_billed = Vec::from_array(&env, [code("97110", true, 23, 1)]);
+    assert!(client.try_record_session_billing(&plan_id, &0, &under_billed).is_err());
+    // Timed minutes cannot exceed the 45 minute session.
+    let too_long = Vec::from_array(&env, [code("97110", true, 50, 3)]);
+    assert!(client.try_record_session_billing(&plan_id, &0, &too_long).is_err());
+
+    client.record_session_billing(
+        &plan_id,
+        &0,
+        &Vec::from_array(&env, [code("97110", true, 23, 2), code("97161", false, 0, 1)]),
+    );
+    client.record_session_billing(
+        &plan_id,
+        &1,
+        &Vec::from_array(&env, [code("97110", true, 30, 2), code("97140", true, 8, 1)]),
+    );
+    assert!(client
+        .try_record_session_billing(&plan_id, &1, &Vec::from_array(&env, [code("97110", true, 15, 1)]))
+        .is_err());
+    assert!(client
+        .try_record_session_billing(&plan_id, &2, &Vec::from_array(&env, [code("97110", true, 15, 1)]))
+        .is_err());
+
+    let summary = client.get_plan_billing_summary(&plan_id);
+    assert_eq!(summary.sessions_billed, 2);
+    assert_eq!(summary.total_timed_minutes, 61);
+    assert_eq!(summary.total_units, 6);
+    assert_eq!(summary.units_by_code.len(), 3);
+    assert_eq!(summary.units_by_code.get(0).unwrap().units, 4);
+
+    let hash = client.commit_billing_export(&plan_id, &1000u64, &1550u64);
+    let commitment = client.get_billing_export(&plan_id, &1000u64);
+    assert_eq!(commitment.export_hash, hash);
+    assert_eq!(commitment.sessions, 1);
+    assert_eq!(commitment.total_units, 3);
+    assert!(client.try_commit_billing_export(&plan_id, &1000u64, &1550u64).is_err());
+}
+
+#[test]
+fn test_outcome_registry_batch_export() {
+    let (env, patient, therapist) = create_test_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RehabilitationServicesContract, ());
+    let client = RehabilitationServicesContractClient::new(&env, &contract_id);
+
+    let eval_id = client.conduct_pt_evaluation(
+        &patient,
+        &therapist,
+        &1000u64,
+        &String::from_str(&env, "Injury"),
+        &String::from_str(&env, "Pain"),
+        &Vec::from_array(&env, [String::from_str(&env, "Limited")]),
+        &String::from_str(&env, "Active"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    let goal = |goal_id: u64| RehabGoal {
+        goal_id,
+        goal_type: Symbol::new(&env, "stg"),
+        goal_description: String::from_str(&env, "Goal"),
+        target_date: 2000u64,
+        measurement_method: String::from_str(&env, "Method"),
+        achieved: false,
+    };
+    let intervention = TherapyIntervention {
+        intervention_type: Symbol::new(&env, "exercise"),
+        description: String::from_str(&env, "Exercise"),
+        sets: Some(3),
+        reps: Some(10),
+        duration: None,
+        resistance: None,
+    };
+    let create_plan = || {
+        client.create_rehab_treatment_plan(
+            &eval_id,
+            &therapist,
+            &Vec::from_array(&env, [goal(1), goal(2)]),
+            &Vec::from_array(&env, [goal(3)]),
+            &Vec::from_array(&env, [intervention.clone()]),
+            &String::from_str(&env, "3x/week"),
+            &8u32,
+            &Symbol::new(&env, "good"),
+        )
+    };
+    let plan_id = create_plan();
+    let open_plan_id = create_plan();
+
+    let lefs = Symbol::new(&env, "lefs");
+    client.measure_functional_outcome(&plan_id, &1100u64, &lefs, &32u32, &false);
+    client.measure_functional_outcome(&plan_id, &1200u64, &Symbol::new(&env, "nprs"), &6u32, &false);
+    client.measure_functional_outcome(&plan_id, &4900u64, &lefs, &61u32, &true);
+    for session_date in [1500u64, 2500u64, 3500u64] {
+        client.document_therapy_session(
+            &plan_id,
+            &session_date,
+            &Vec::from_array(&env, [intervention.clone()]),
+            &45u32,
+            &String::from_str(&env, "Tolerated well"),
+            &None,
+        );
+    }
+    client.discharge_from_therapy(
+        &plan_id,
+        &5000u64,
+        &Symbol::new(&env, "goals_met"),
+        &Vec::from_array(&env, [1u64, 3u64]),
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &BytesN::from_array(&env, &[4u8; 32]),
+    );
+
+    // Episodes must be discharged, scored on the tool, and the submitter's own.
+    let both = Vec::from_array(&env, [plan_id, open_plan_id]);
+    assert!(client.try_export_outcome_registry_batch(&therapist, &both, &lefs).is_err());
+    let discharged = Vec::from_array(&env, [plan_id]);
+    assert!(client
+        .try_export_outcome_registry_batch(&therapist, &discharged, &Symbol::new(&env, "odi"))
+        .is_err());
+    assert!(client
+        .try_export_outcome_registry_batch(&Address::generate(&env), &discharged, &lefs)
+        .is_err());
+
+    let batch = client.export_outcome_registry_batch(&therapist, &discharged, &lefs);
+    assert_eq!(env.events().all().len(), 1);
+    assert_eq!(batch.batch_id, 1);
+    let episode = batch.episodes.get(0).unwrap();
+    assert_eq!(episode.episode_ref, 0);
+    assert_eq!(episode.start_score, 32);
+    assert_eq!(episode.end_score, 61);
+    assert_eq!(episode.score_change, 29);
+    assert_eq!(episode.visits_used, 3);
+    assert_eq!(episode.goals_total, 3);
+    assert_eq!(episode.goals_met, 2);
+    assert_eq!(client.get_outcome_registry_batch(&1), batch);
+}
+
+#[test]
+fn test_appointment_no_shows_put_authorization_at_risk() {
+    let (env, patient, therapist) = create_test_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RehabilitationServicesContract, ());
+    let client = RehabilitationServicesContractClient::new(&env, &contract_id);
+
+    let eval_id = client.conduct_pt_evaluation(
+        &patient,
+        &therapist,
+        &1000u64,
+        &String::from_str(&env, "Injury"),
+        &String::from_str(&env, "Pain"),
+        &Vec::from_array(&env, [String::from_str(&env, "Limited")]),
+        &String::from_str(&env, "Active"),
+        &BytesN::from_array(&env, &[1u8; 32]),
+    );
+    let goal = RehabGoal {
+        goal_id: 1,
+        goal_type: Symbol::new(&env, "stg"),
+        goal_description: String::from_str(&env, "Goal"),
+        target_date: 2000u64,
+        measurement_method: String::from_str(&env, "Method"),
+        achieved: false,
+    };
+    let intervention = TherapyIntervention {
+        intervention_type: Symbol::new(&env, "exercise"),
+        description: String::from_str(&env, "Exercise"),
+        sets: Some(3),
+        reps: Some(10),
+        duration: None,
+        resistance: None,
+    };
+    let plan_id = client.create_rehab_treatment_plan(
+        &eval_id,
+        &therapist,
+        &Vec::from_array(&env, [goal.clone()]),
+        &Vec::from_array(&env, [goal]),
+        &Vec::from_array(&env, [intervention]),
+        &String::from_str(&env, "3x/week"),
+        &8u32,
+        &Symbol::new(&env, "good"),
+    );
+    client.request_therapy_authorization(&plan_id, &12u32, &BytesN::from_array(&env, &[2u8; 32]));
+
+    let mut appointments = Vec::new(&env);
+    for day in 0..6u64 {
+        appointments.push_back(client.schedule_therapy_appointment(&plan_id, &(2000 + day * 86_400)));
+    }
+    client.update_appointment_status(&appointments.get(0).unwrap(), &AppointmentStatus::Completed);
+    client.update_appointment_status(&appointments.get(1).unwrap(), &AppointmentStatus::CancelledByPatient);
+    client.update_appointment_status(&appointments.get(2).unwrap(), &AppointmentStatus::NoShow);
+    client.update_appointment_status(&appointments.get(3).unwrap(), &AppointmentStatus::NoShow);
+    assert_eq!(env.events().all().len(), 1);
+
+    // The third no-show flags the authorization.
+    client.update_appointment_status(&appointments.get(4).unwrap(), &AppointmentStatus::NoShow);
+    assert_eq!(env.events().all().len(), 2);
+    assert_eq!(client.get_appointment(&appointments.get(4).unwrap()).status, AppointmentStatus::NoShow);
+
+    // Only scheduled appointments can be resolved, and not back to scheduled.
+    assert!(client
+        .try_update_appointment_status(&appointments.get(0).unwrap(), &AppointmentStatus::NoShow)
+        .is_err());
+    assert!(client
+        .try_update_appointment_status(&appointments.get(5).unwrap(), &AppointmentStatus::Scheduled)
+        .is_err());
+
+    let stats = client.get_patient_attendance(&patient);
+    assert_eq!(stats.scheduled, 6);
+    assert_eq!(stats.completed, 1);
+    assert_eq!(stats.cancelled, 1);
+    assert_eq!(stats.no_shows, 3);
+    assert_eq!(stats.cancellation_rate_bps, 2_000);
+    assert_eq!(stats.no_show_rate_bps, 6_000);
+
+    let page = client.get_plan_appointments(&plan_id, &4, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().status, AppointmentStatus::Scheduled);
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, admin, stranger) = create_test_env();
+    env.mock_all_auths();
+
+    let contract_id = env.register(RehabilitationServicesContract, ());
+    let client = RehabilitationServicesContractClient::new(&env, &contract_id);
+
+    assert!(client.try_migrate(&admin).is_err());
+    client.initialize(&admin);
+    assert!(client.try_initialize(&admin).is_err());
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert!(client.try_migrate(&stranger).is_err());
+    assert!(client
+        .try_upgrade(&stranger, &BytesN::from_array(&env, &[0u8; 32]))
+        .is_err());
+}
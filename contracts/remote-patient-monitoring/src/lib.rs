@@ -0,0 +1,406 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+const SECONDS_PER_DAY: u64 = 86_400;
+
+#[contract]
+pub struct RemotePatientMonitoringContract;
+
+#[contractimpl]
+impl RemotePatientMonitoringContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Authorize a provider to run RPM episodes and receive alerts.
+    pub fn add_provider(env: Env, admin: Address, provider_id: Address) -> Result<(), Error> {
+        admin.require_auth();
+        if get_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Provider(provider_id), &true);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Enrollment
+    // -------------------------------------------------------------------
+
+    /// Open an RPM episode. The patient consents by co-signing.
+    pub fn enroll_patient(
+        env: Env,
+        provider_id: Address,
+        patient_id: Address,
+        ack_sla_seconds: u64,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        patient_id.require_auth();
+        if !is_provider(&env, &provider_id) {
+            return Err(Error::Unauthorized);
+        }
+        if ack_sla_seconds == 0 {
+            return Err(Error::InvalidInput);
+        }
+        if load_enrollment(&env, &patient_id).is_some_and(|e| e.ended_at == 0) {
+            return Err(Error::AlreadyEnrolled);
+        }
+
+        save_enrollment(
+            &env,
+            &Enrollment {
+                patient_id,
+                provider_id,
+                enrolled_at: env.ledger().timestamp(),
+                ended_at: 0,
+                ack_sla_seconds,
+                days_transmitted: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn end_enrollment(env: Env, caller: Address, patient_id: Address) -> Result<(), Error> {
+        caller.require_auth();
+        let mut enrollment = load_open_enrollment(&env, &patient_id)?;
+        if caller != enrollment.provider_id && caller != patient_id {
+            return Err(Error::Unauthorized);
+        }
+        enrollment.ended_at = env.ledger().timestamp();
+        save_enrollment(&env, &enrollment);
+        Ok(())
+    }
+
+    pub fn get_enrollment(env: Env, patient_id: Address) -> Result<Enrollment, Error> {
+        load_enrollment(&env, &patient_id).ok_or(Error::NotEnrolled)
+    }
+
+    /// Replace the patient's alert thresholds.
+    pub fn set_thresholds(
+        env: Env,
+        provider_id: Address,
+        patient_id: Address,
+        thresholds: Vec<Threshold>,
+    ) -> Result<(), Error> {
+        require_responsible_provider(&env, &provider_id, &patient_id)?;
+        for t in thresholds.iter() {
+            if t.high_x100 != 0 && t.low_x100 > t.high_x100 {
+                return Err(Error::InvalidInput);
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Thresholds(patient_id), &thresholds);
+        Ok(())
+    }
+
+    pub fn get_thresholds(env: Env, patient_id: Address) -> Vec<Threshold> {
+        thresholds(&env, &patient_id)
+    }
+
+    // -------------------------------------------------------------------
+    // Devices
+    // -------------------------------------------------------------------
+
+    pub fn register_device(
+        env: Env,
+        provider_id: Address,
+        patient_id: Address,
+        device_id: Address,
+        device_type: Symbol,
+        serial_number: String,
+    ) -> Result<(), Error> {
+        require_responsible_provider(&env, &provider_id, &patient_id)?;
+        save_device(
+            &env,
+            &Device {
+                device_id,
+                patient_id,
+                device_type,
+                serial_number,
+                registered_at: env.ledger().timestamp(),
+                active: true,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn deactivate_device(
+        env: Env,
+        provider_id: Address,
+        device_id: Address,
+    ) -> Result<(), Error> {
+        let mut device = load_device(&env, &device_id).ok_or(Error::DeviceNotFound)?;
+        require_responsible_provider(&env, &provider_id, &device.patient_id)?;
+        device.active = false;
+        save_device(&env, &device);
+        Ok(())
+    }
+
+    pub fn get_device(env: Env, device_id: Address) -> Result<Device, Error> {
+        load_device(&env, &device_id).ok_or(Error::DeviceNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Observations and alerts
+    // -------------------------------------------------------------------
+
+    /// Submit one day's summary from a registered device. Each key value is
+    /// checked against the patient's thresholds and every breach raises an
+    /// alert routed to the responsible provider. Returns the new alert ids.
+    pub fn submit_daily_summary(
+        env: Env,
+        device_id: Address,
+        day: u64,
+        batch_hash: BytesN<32>,
+        values: Vec<KeyValue>,
+    ) -> Result<Vec<u64>, Error> {
+        device_id.require_auth();
+        let device = load_device(&env, &device_id).ok_or(Error::DeviceNotFound)?;
+        if !device.active {
+            return Err(Error::DeviceInactive);
+        }
+        let patient_id = device.patient_id.clone();
+        let mut enrollment = load_open_enrollment(&env, &patient_id)?;
+
+        let now = env.ledger().timestamp();
+        if day < enrollment.enrolled_at / SECONDS_PER_DAY || day > now / SECONDS_PER_DAY {
+            return Err(Error::InvalidInput);
+        }
+        let summary_key = DataKey::Summary(patient_id.clone(), day);
+        if env.storage().persistent().has(&summary_key) {
+            return Err(Error::DuplicateSummary);
+        }
+
+        env.storage().persistent().set(
+            &summary_key,
+            &DailySummary {
+                patient_id: patient_id.clone(),
+                device_id,
+                day,
+                batch_hash,
+                values: values.clone(),
+                submitted_at: now,
+            },
+        );
+        enrollment.days_transmitted += 1;
+        save_enrollment(&env, &enrollment);
+
+        let rules = thresholds(&env, &patient_id);
+        let mut raised = Vec::new(&env);
+        for kv in values.iter() {
+            let Some(rule) = rules.iter().find(|r| r.metric == kv.metric) else {
+                continue;
+            };
+            let breach = if rule.low_x100 != 0 && kv.value_x100 < rule.low_x100 {
+                BreachKind::Low
+            } else if rule.high_x100 != 0 && kv.value_x100 > rule.high_x100 {
+                BreachKind::High
+            } else {
+                continue;
+            };
+
+            let alert = Alert {
+                alert_id: next_alert_id(&env),
+                patient_id: patient_id.clone(),
+                provider_id: enrollment.provider_id.clone(),
+                metric: kv.metric.clone(),
+                value_x100: kv.value_x100,
+                breach,
+                raised_at: now,
+                ack_due_at: now.saturating_add(enrollment.ack_sla_seconds),
+                acknowledged_at: 0,
+                note: None,
+            };
+            save_alert(&env, &alert);
+            add_provider_alert(&env, &alert.provider_id, alert.alert_id);
+            raised.push_back(alert.alert_id);
+
+            ThresholdBreached {
+                provider_id: alert.provider_id,
+                patient_id: alert.patient_id,
+                alert_id: alert.alert_id,
+                metric: alert.metric,
+                value_x100: alert.value_x100,
+                ack_due_at: alert.ack_due_at,
+            }
+            .publish(&env);
+        }
+        Ok(raised)
+    }
+
+    pub fn get_daily_summary(env: Env, patient_id: Address, day: u64) -> Option<DailySummary> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Summary(patient_id, day))
+    }
+
+    pub fn acknowledge_alert(
+        env: Env,
+        provider_id: Address,
+        alert_id: u64,
+        note: String,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+        let mut alert = load_alert(&env, alert_id).ok_or(Error::AlertNotFound)?;
+        if alert.provider_id != provider_id {
+            return Err(Error::Unauthorized);
+        }
+        if alert.acknowledged_at != 0 {
+            return Err(Error::AlreadyAcknowledged);
+        }
+        alert.acknowledged_at = env.ledger().timestamp();
+        alert.note = Some(note);
+        save_alert(&env, &alert);
+
+        AlertAcknowledged {
+            alert_id,
+            provider_id,
+            within_sla: alert.acknowledged_at <= alert.ack_due_at,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    pub fn get_alert(env: Env, alert_id: u64) -> Result<Alert, Error> {
+        load_alert(&env, alert_id).ok_or(Error::AlertNotFound)
+    }
+
+    /// Unacknowledged alerts for a provider, oldest first.
+    pub fn get_open_alerts(env: Env, provider_id: Address) -> Vec<Alert> {
+        let mut open = Vec::new(&env);
+        for id in provider_alerts(&env, &provider_id).iter() {
+            if let Some(alert) = load_alert(&env, id) {
+                if alert.acknowledged_at == 0 {
+                    open.push_back(alert);
+                }
+            }
+        }
+        open
+    }
+
+    pub fn get_sla_stats(env: Env, provider_id: Address) -> SlaStats {
+        let now = env.ledger().timestamp();
+        let mut stats = SlaStats {
+            total: 0,
+            acknowledged: 0,
+            acknowledged_within_sla: 0,
+            open_overdue: 0,
+        };
+        for id in provider_alerts(&env, &provider_id).iter() {
+            let Some(alert) = load_alert(&env, id) else {
+                continue;
+            };
+            stats.total += 1;
+            if alert.acknowledged_at != 0 {
+                stats.acknowledged += 1;
+                if alert.acknowledged_at <= alert.ack_due_at {
+                    stats.acknowledged_within_sla += 1;
+                }
+            } else if now > alert.ack_due_at {
+                stats.open_overdue += 1;
+            }
+        }
+        stats
+    }
+
+    // -------------------------------------------------------------------
+    // Adherence
+    // -------------------------------------------------------------------
+
+    /// Days with a transmitted summary over days enrolled in the current
+    /// (or most recent) episode. A partial day counts as enrolled.
+    pub fn get_adherence(env: Env, patient_id: Address) -> Result<AdherenceStats, Error> {
+        let enrollment = load_enrollment(&env, &patient_id).ok_or(Error::NotEnrolled)?;
+        let end = if enrollment.ended_at == 0 {
+            env.ledger().timestamp()
+        } else {
+            enrollment.ended_at
+        };
+        let days_enrolled =
+            (end / SECONDS_PER_DAY - enrollment.enrolled_at / SECONDS_PER_DAY + 1) as u32;
+        let adherence_bps = enrollment.days_transmitted.saturating_mul(10_000) / days_enrolled;
+
+        Ok(AdherenceStats {
+            days_enrolled,
+            days_transmitted: enrollment.days_transmitted,
+            adherence_bps,
+        })
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn load_open_enrollment(env: &Env, patient_id: &Address) -> Result<Enrollment, Error> {
+    match load_enrollment(env, patient_id) {
+        Some(enrollment) if enrollment.ended_at == 0 => Ok(enrollment),
+        _ => Err(Error::NotEnrolled),
+    }
+}
+
+fn require_responsible_provider(
+    env: &Env,
+    provider_id: &Address,
+    patient_id: &Address,
+) -> Result<(), Error> {
+    provider_id.require_auth();
+    let enrollment = load_open_enrollment(env, patient_id)?;
+    if enrollment.provider_id != *provider_id {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
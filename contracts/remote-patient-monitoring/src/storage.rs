@@ -0,0 +1,94 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::{Alert, DataKey, Device, Enrollment, Threshold};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_provider(env: &Env, who: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Provider(who.clone()))
+        .unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Enrollment and devices
+// -----------------------------------------------------------------------
+
+pub fn save_enrollment(env: &Env, enrollment: &Enrollment) {
+    env.storage().persistent().set(
+        &DataKey::Enrollment(enrollment.patient_id.clone()),
+        enrollment,
+    );
+}
+
+pub fn load_enrollment(env: &Env, patient_id: &Address) -> Option<Enrollment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Enrollment(patient_id.clone()))
+}
+
+pub fn save_device(env: &Env, device: &Device) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Device(device.device_id.clone()), device);
+}
+
+pub fn load_device(env: &Env, device_id: &Address) -> Option<Device> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Device(device_id.clone()))
+}
+
+pub fn thresholds(env: &Env, patient_id: &Address) -> Vec<Threshold> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Thresholds(patient_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+// -----------------------------------------------------------------------
+// Alerts
+// -----------------------------------------------------------------------
+
+pub fn next_alert_id(env: &Env) -> u64 {
+    let next = env
+        .storage()
+        .instance()
+        .get::<_, u64>(&DataKey::AlertCounter)
+        .unwrap_or(0)
+        + 1;
+    env.storage().instance().set(&DataKey::AlertCounter, &next);
+    next
+}
+
+pub fn save_alert(env: &Env, alert: &Alert) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Alert(alert.alert_id), alert);
+}
+
+pub fn load_alert(env: &Env, alert_id: u64) -> Option<Alert> {
+    env.storage().persistent().get(&DataKey::Alert(alert_id))
+}
+
+pub fn provider_alerts(env: &Env, provider_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ProviderAlerts(provider_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_provider_alert(env: &Env, provider_id: &Address, alert_id: u64) {
+    let mut ids = provider_alerts(env, provider_id);
+    ids.push_back(alert_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::ProviderAlerts(provider_id.clone()), &ids);
+}
@@ -0,0 +1,360 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger},
+    vec, Address, BytesN, Env, String,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+const START_DAY: u64 = 20_000;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    Address,
+    RemotePatientMonitoringContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START_DAY * DAY + 3_600);
+    let contract_id = env.register(RemotePatientMonitoringContract, ());
+    let client = RemotePatientMonitoringContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let provider = Address::generate(&env);
+    let patient = Address::generate(&env);
+    let device = Address::generate(&env);
+    client.initialize(&admin);
+    client.add_provider(&admin, &provider);
+    client.enroll_patient(&provider, &patient, &(4 * 3_600));
+    client.register_device(
+        &provider,
+        &patient,
+        &device,
+        &symbol_short!("bp_cuff"),
+        &String::from_str(&env, "BP-00912"),
+    );
+    client.set_thresholds(
+        &provider,
+        &patient,
+        &vec![
+            &env,
+            Threshold {
+                metric: symbol_short!("sbp"),
+                low_x100: 9_000,
+                high_x100: 16_000,
+            },
+            Threshold {
+                metric: symbol_short!("weight"),
+                low_x100: 0,
+                high_x100: 9_500,
+            },
+        ],
+    );
+
+    (env, admin, provider, patient, device, client)
+}
+
+fn submit(
+    env: &Env,
+    client: &RemotePatientMonitoringContractClient,
+    device: &Address,
+    day: u64,
+    sbp_x100: i64,
+) -> Vec<u64> {
+    client.submit_daily_summary(
+        device,
+        &day,
+        &BytesN::from_array(env, &[day as u8; 32]),
+        &vec![
+            env,
+            KeyValue {
+                metric: symbol_short!("sbp"),
+                value_x100: sbp_x100,
+            },
+            KeyValue {
+                metric: symbol_short!("weight"),
+                value_x100: 8_800,
+            },
+        ],
+    )
+}
+
+fn advance_days(env: &Env, days: u64) {
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + days * DAY);
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_breach_raises_alert_for_responsible_provider() {
+    let (env, _, provider, patient, device, client) = setup();
+    assert_eq!(submit(&env, &client, &device, START_DAY, 13_200).len(), 0);
+
+    advance_days(&env, 1);
+    let raised = submit(&env, &client, &device, START_DAY + 1, 17_400);
+    assert_eq!(raised.len(), 1);
+
+    let alert = client.get_alert(&raised.get(0).unwrap());
+    assert_eq!(alert.provider_id, provider);
+    assert_eq!(alert.breach, BreachKind::High);
+    assert_eq!(alert.metric, symbol_short!("sbp"));
+    assert_eq!(client.get_open_alerts(&provider).len(), 1);
+    assert!(client
+        .get_daily_summary(&patient, &(START_DAY + 1))
+        .is_some());
+}
+
+#[test]
+fn test_alert_acknowledgment_sla() {
+    let (env, _, provider, _, device, client) = setup();
+    let first = submit(&env, &client, &device, START_DAY, 8_000)
+        .get(0)
+        .unwrap();
+    advance_days(&env, 1);
+    let second = submit(&env, &client, &device, START_DAY + 1, 17_000)
+        .get(0)
+        .unwrap();
+
+    // Second alert acknowledged promptly; the first is now overdue.
+    client.acknowledge_alert(
+        &provider,
+        &second,
+        &String::from_str(&env, "Called patient, meds adjusted"),
+    );
+    let stats = client.get_sla_stats(&provider);
+    assert_eq!(stats.total, 2);
+    assert_eq!(stats.acknowledged_within_sla, 1);
+    assert_eq!(stats.open_overdue, 1);
+
+    client.acknowledge_alert(&provider, &first, &String::from_str(&env, "Late"));
+    let stats = client.get_sla_stats(&provider);
+    assert_eq!(stats.acknowledged, 2);
+    assert_eq!(stats.acknowledged_within_sla, 1);
+    assert_eq!(
+        client.try_acknowledge_alert(&provider, &first, &String::from_str(&env, "again")),
+        Err(Ok(Error::AlreadyAcknowledged))
+    );
+}
+
+#[test]
+fn test_adherence_counts_transmitted_days() {
+    let (env, _, _, patient, device, client) = setup();
+    submit(&env, &client, &device, START_DAY, 12_000);
+    advance_days(&env, 3);
+    submit(&env, &client, &device, START_DAY + 2, 12_000);
+    submit(&env, &client, &device, START_DAY + 3, 12_000);
+
+    let stats = client.get_adherence(&patient);
+    assert_eq!(stats.days_enrolled, 4);
+    assert_eq!(stats.days_transmitted, 3);
+    assert_eq!(stats.adherence_bps, 7_500);
+}
+
+#[test]
+fn test_summary_validation() {
+    let (env, _, provider, _, device, client) = setup();
+    submit(&env, &client, &device, START_DAY, 12_000);
+    assert_eq!(
+        client.try_submit_daily_summary(
+            &device,
+            &START_DAY,
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &Vec::new(&env),
+        ),
+        Err(Ok(Error::DuplicateSummary))
+    );
+    // Future days and days before enrollment are rejected.
+    for day in [START_DAY + 1, START_DAY - 1] {
+        assert_eq!(
+            client.try_submit_daily_summary(
+                &device,
+                &day,
+                &BytesN::from_array(&env, &[9u8; 32]),
+                &Vec::new(&env),
+            ),
+            Err(Ok(Error::InvalidInput))
+        );
+    }
+
+    client.deactivate_device(&provider, &device);
+    advance_days(&env, 1);
+    assert_eq!(
+        client.try_submit_daily_summary(
+            &device,
+            &(START_DAY + 1),
+            &BytesN::from_array(&env, &[9u8; 32]),
+            &Vec::new(&env),
+        ),
+        Err(Ok(Error::DeviceInactive))
+    );
+}
+
+#[test]
+fn test_enrollment_rules() {
+    let (env, _, provider, patient, device, client) = setup();
+    assert_eq!(
+        client.try_enroll_patient(&provider, &patient, &3_600),
+        Err(Ok(Error::AlreadyEnrolled))
+    );
+
+    let unlisted = Address::generate(&env);
+    let new_patient = Address::generate(&env);
+    assert_eq!(
+        client.try_enroll_patient(&unlisted, &new_patient, &3_600),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.end_enrollment(&patient, &patient);
+    assert_eq!(
+        client.try_submit_daily_summary(
+            &device,
+            &START_DAY,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &Vec::new(&env),
+        ),
+        Err(Ok(Error::NotEnrolled))
+    );
+    client.enroll_patient(&provider, &patient, &3_600);
+}
+
+#[test]
+fn test_only_responsible_provider_manages_patient() {
+    let (env, admin, provider, patient, device, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+    let other_provider = Address::generate(&env);
+    assert_eq!(
+        client.try_add_provider(&provider, &other_provider),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    // A registered provider still cannot touch another provider's patient
+    client.add_provider(&admin, &other_provider);
+    assert_eq!(
+        client.try_set_thresholds(&other_provider, &patient, &Vec::new(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_register_device(
+            &other_provider,
+            &patient,
+            &Address::generate(&env),
+            &symbol_short!("scale"),
+            &String::from_str(&env, "SC-1"),
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_deactivate_device(&other_provider, &device),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_end_enrollment(&other_provider, &patient),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_input_validation() {
+    let (env, _, provider, patient, _, client) = setup();
+
+    let new_patient = Address::generate(&env);
+    assert_eq!(
+        client.try_enroll_patient(&provider, &new_patient, &0),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_thresholds(
+            &provider,
+            &patient,
+            &vec![
+                &env,
+                Threshold {
+                    metric: symbol_short!("sbp"),
+                    low_x100: 16_000,
+                    high_x100: 9_000,
+                },
+            ],
+        ),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_set_thresholds(&provider, &new_patient, &Vec::new(&env)),
+        Err(Ok(Error::NotEnrolled))
+    );
+}
+
+#[test]
+fn test_alert_and_lookup_errors() {
+    let (env, admin, provider, patient, device, client) = setup();
+    let alert_id = submit(&env, &client, &device, START_DAY, 17_000)
+        .get(0)
+        .unwrap();
+
+    let other_provider = Address::generate(&env);
+    client.add_provider(&admin, &other_provider);
+    assert_eq!(
+        client.try_acknowledge_alert(&other_provider, &alert_id, &String::from_str(&env, "ok")),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_acknowledge_alert(&provider, &99, &String::from_str(&env, "ok")),
+        Err(Ok(Error::AlertNotFound))
+    );
+    assert_eq!(client.try_get_alert(&99), Err(Ok(Error::AlertNotFound)));
+
+    let unknown_device = Address::generate(&env);
+    assert_eq!(
+        client.try_submit_daily_summary(
+            &unknown_device,
+            &START_DAY,
+            &BytesN::from_array(&env, &[1u8; 32]),
+            &Vec::new(&env),
+        ),
+        Err(Ok(Error::DeviceNotFound))
+    );
+    assert_eq!(
+        client.try_deactivate_device(&provider, &unknown_device),
+        Err(Ok(Error::DeviceNotFound))
+    );
+    assert_eq!(
+        client.try_get_device(&unknown_device),
+        Err(Ok(Error::DeviceNotFound))
+    );
+
+    client.end_enrollment(&provider, &patient);
+    assert_eq!(
+        client.try_end_enrollment(&provider, &patient),
+        Err(Ok(Error::NotEnrolled))
+    );
+    assert_eq!(
+        client.try_get_adherence(&other_provider),
+        Err(Ok(Error::NotEnrolled))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, provider, _, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&provider), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&provider, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
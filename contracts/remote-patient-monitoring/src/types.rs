@@ -0,0 +1,180 @@
+use soroban_sdk::{
+    contracterror, contractevent, contracttype, Address, BytesN, String, Symbol, Vec,
+};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    NotEnrolled = 4,
+    AlreadyEnrolled = 5,
+    DeviceNotFound = 6,
+    DeviceInactive = 7,
+    DuplicateSummary = 8,
+    AlertNotFound = 9,
+    AlreadyAcknowledged = 10,
+    InvalidInput = 11,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BreachKind {
+    Low,
+    High,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+/// An RPM episode. `provider_id` receives every alert raised for the patient.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Enrollment {
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub enrolled_at: u64,
+    /// 0 while the episode is open.
+    pub ended_at: u64,
+    /// Seconds the provider has to acknowledge an alert.
+    pub ack_sla_seconds: u64,
+    pub days_transmitted: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Device {
+    pub device_id: Address,
+    pub patient_id: Address,
+    pub device_type: Symbol,
+    pub serial_number: String,
+    pub registered_at: u64,
+    pub active: bool,
+}
+
+/// Alert bounds for one metric, values ×100. A zero bound is not checked.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Threshold {
+    pub metric: Symbol,
+    pub low_x100: i64,
+    pub high_x100: i64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeyValue {
+    pub metric: Symbol,
+    pub value_x100: i64,
+}
+
+/// One day of device data: the hash of the full off-chain batch plus the
+/// key values used for alerting.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DailySummary {
+    pub patient_id: Address,
+    pub device_id: Address,
+    /// Days since the Unix epoch (UTC).
+    pub day: u64,
+    pub batch_hash: BytesN<32>,
+    pub values: Vec<KeyValue>,
+    pub submitted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alert {
+    pub alert_id: u64,
+    pub patient_id: Address,
+    pub provider_id: Address,
+    pub metric: Symbol,
+    pub value_x100: i64,
+    pub breach: BreachKind,
+    pub raised_at: u64,
+    pub ack_due_at: u64,
+    /// 0 until acknowledged.
+    pub acknowledged_at: u64,
+    pub note: Option<String>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SlaStats {
+    pub total: u32,
+    pub acknowledged: u32,
+    pub acknowledged_within_sla: u32,
+    pub open_overdue: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AdherenceStats {
+    pub days_enrolled: u32,
+    pub days_transmitted: u32,
+    /// days_transmitted / days_enrolled in basis points.
+    pub adherence_bps: u32,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+/// Routed to the responsible provider via the `provider_id` topic.
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ThresholdBreached {
+    #[topic]
+    pub provider_id: Address,
+    #[topic]
+    pub patient_id: Address,
+    pub alert_id: u64,
+    pub metric: Symbol,
+    pub value_x100: i64,
+    pub ack_due_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AlertAcknowledged {
+    #[topic]
+    pub alert_id: u64,
+    pub provider_id: Address,
+    pub within_sla: bool,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// provider -> bool
+    Provider(Address),
+    /// patient -> Enrollment (latest episode)
+    Enrollment(Address),
+    /// device_id -> Device
+    Device(Address),
+    /// patient -> Vec<Threshold>
+    Thresholds(Address),
+    /// (patient, day) -> DailySummary
+    Summary(Address, u64),
+    AlertCounter,
+    /// alert_id -> Alert
+    Alert(u64),
+    /// provider -> Vec<u64> (alert ids)
+    ProviderAlerts(Address),
+}
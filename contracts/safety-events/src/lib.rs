@@ -0,0 +1,490 @@
+#![no_std]
+
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct SafetyEventsContract;
+
+#[contractimpl]
+impl SafetyEventsContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(
+        env: Env,
+        admin: Address,
+        facility_id: Address,
+        safety_officer: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        if get_admin(&env).ok_or(Error::NotInitialized)? != admin {
+            return Err(Error::Unauthorized);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::SafetyOfficer(facility_id), &safety_officer);
+        Ok(())
+    }
+
+    pub fn add_reporter(
+        env: Env,
+        safety_officer: Address,
+        facility_id: Address,
+        staff: Address,
+    ) -> Result<(), Error> {
+        require_safety_officer(&env, &safety_officer, &facility_id)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reporter(facility_id, staff), &true);
+        Ok(())
+    }
+
+    /// Record patient-days for a period, the denominator for event rates.
+    pub fn record_patient_days(
+        env: Env,
+        safety_officer: Address,
+        facility_id: Address,
+        period_start: u64,
+        patient_days: u32,
+    ) -> Result<(), Error> {
+        require_safety_officer(&env, &safety_officer, &facility_id)?;
+        let key = DataKey::PatientDays(facility_id);
+        let mut entries: Vec<PatientDaysEntry> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        entries.push_back(PatientDaysEntry {
+            period_start,
+            patient_days,
+        });
+        env.storage().persistent().set(&key, &entries);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Reporting
+    // -------------------------------------------------------------------
+
+    /// File an incident. In anonymous mode the reporter still authorizes
+    /// the call but their address is not stored on the report.
+    pub fn file_report(env: Env, reporter: Address, input: IncidentInput) -> Result<u64, Error> {
+        reporter.require_auth();
+        if safety_officer(&env, &input.facility_id).is_none() {
+            return Err(Error::FacilityNotRegistered);
+        }
+        if !is_reporter(&env, &input.facility_id, &reporter) {
+            return Err(Error::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if input.occurred_at > now {
+            return Err(Error::InvalidInput);
+        }
+
+        let report = IncidentReport {
+            id: next_id(&env, DataKey::ReportCounter),
+            facility_id: input.facility_id.clone(),
+            event_type: input.event_type,
+            severity: input.severity,
+            harm_level: input.harm_level,
+            occurred_at: input.occurred_at,
+            reported_at: now,
+            location: input.location,
+            patient_id: input.patient_id,
+            involved_provider: input.involved_provider.clone(),
+            description_hash: input.description_hash,
+            reporter: if input.anonymous {
+                None
+            } else {
+                Some(reporter)
+            },
+            status: ReportStatus::Open,
+            rca_id: 0,
+            closed_at: 0,
+        };
+        save_report(&env, &report);
+        push_id(&env, DataKey::FacilityReports(input.facility_id), report.id);
+        if let Some(provider) = input.involved_provider {
+            push_id(&env, DataKey::ProviderReports(provider), report.id);
+        }
+
+        IncidentFiled {
+            facility_id: report.facility_id.clone(),
+            incident_id: report.id,
+            event_type: report.event_type,
+            harm_level: report.harm_level,
+        }
+        .publish(&env);
+
+        Ok(report.id)
+    }
+
+    pub fn get_report(env: Env, incident_id: u64) -> Result<IncidentReport, Error> {
+        load_report(&env, incident_id).ok_or(Error::ReportNotFound)
+    }
+
+    pub fn get_facility_reports(
+        env: Env,
+        facility_id: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<u64> {
+        let reports = id_list(&env, &DataKey::FacilityReports(facility_id));
+        shared_pagination::paginate(&reports, offset, limit)
+    }
+
+    // -------------------------------------------------------------------
+    // Root cause analysis
+    // -------------------------------------------------------------------
+
+    pub fn assign_rca(
+        env: Env,
+        safety_officer: Address,
+        incident_id: u64,
+        lead: Address,
+        due_date: u64,
+    ) -> Result<u64, Error> {
+        let mut report = load_report(&env, incident_id).ok_or(Error::ReportNotFound)?;
+        require_safety_officer(&env, &safety_officer, &report.facility_id)?;
+        if report.status != ReportStatus::Open || report.rca_id != 0 {
+            return Err(Error::InvalidStatus);
+        }
+        let now = env.ledger().timestamp();
+        if due_date <= now {
+            return Err(Error::InvalidInput);
+        }
+
+        let rca = RootCauseAnalysis {
+            id: next_id(&env, DataKey::RcaCounter),
+            incident_id,
+            lead,
+            assigned_at: now,
+            due_date,
+            findings_hash: None,
+            completed_at: 0,
+        };
+        save_rca(&env, &rca);
+        report.rca_id = rca.id;
+        report.status = ReportStatus::RcaInProgress;
+        save_report(&env, &report);
+        Ok(rca.id)
+    }
+
+    pub fn complete_rca(
+        env: Env,
+        lead: Address,
+        rca_id: u64,
+        findings_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        lead.require_auth();
+        let mut rca = load_rca(&env, rca_id).ok_or(Error::RcaNotFound)?;
+        if rca.lead != lead {
+            return Err(Error::Unauthorized);
+        }
+        if rca.completed_at != 0 {
+            return Err(Error::InvalidStatus);
+        }
+        rca.findings_hash = Some(findings_hash);
+        rca.completed_at = env.ledger().timestamp();
+        save_rca(&env, &rca);
+        Ok(())
+    }
+
+    pub fn get_rca(env: Env, rca_id: u64) -> Result<RootCauseAnalysis, Error> {
+        load_rca(&env, rca_id).ok_or(Error::RcaNotFound)
+    }
+
+    // -------------------------------------------------------------------
+    // Corrective actions
+    // -------------------------------------------------------------------
+
+    pub fn add_corrective_action(
+        env: Env,
+        safety_officer: Address,
+        incident_id: u64,
+        owner: Address,
+        description: String,
+        due_date: u64,
+    ) -> Result<u64, Error> {
+        let report = load_report(&env, incident_id).ok_or(Error::ReportNotFound)?;
+        require_safety_officer(&env, &safety_officer, &report.facility_id)?;
+        if report.status == ReportStatus::Closed {
+            return Err(Error::InvalidStatus);
+        }
+        if due_date <= env.ledger().timestamp() {
+            return Err(Error::InvalidInput);
+        }
+
+        let action = CorrectiveAction {
+            id: next_id(&env, DataKey::ActionCounter),
+            incident_id,
+            owner: owner.clone(),
+            description,
+            due_date,
+            status: ActionStatus::Open,
+            completed_at: 0,
+        };
+        save_action(&env, &action);
+        push_id(&env, DataKey::IncidentActions(incident_id), action.id);
+
+        CorrectiveActionAssigned {
+            owner,
+            incident_id,
+            action_id: action.id,
+            due_date,
+        }
+        .publish(&env);
+
+        Ok(action.id)
+    }
+
+    pub fn complete_corrective_action(
+        env: Env,
+        owner: Address,
+        action_id: u64,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+        let mut action = load_action(&env, action_id).ok_or(Error::ActionNotFound)?;
+        if action.owner != owner {
+            return Err(Error::Unauthorized);
+        }
+        if action.status != ActionStatus::Open {
+            return Err(Error::InvalidStatus);
+        }
+        action.status = ActionStatus::Completed;
+        action.completed_at = env.ledger().timestamp();
+        save_action(&env, &action);
+        Ok(())
+    }
+
+    pub fn get_corrective_actions(env: Env, incident_id: u64) -> Vec<CorrectiveAction> {
+        let mut actions = Vec::new(&env);
+        for id in id_list(&env, &DataKey::IncidentActions(incident_id)).iter() {
+            if let Some(action) = load_action(&env, id) {
+                actions.push_back(action);
+            }
+        }
+        actions
+    }
+
+    /// Open corrective actions past their due date across a facility.
+    pub fn get_overdue_actions(env: Env, facility_id: Address) -> Vec<CorrectiveAction> {
+        let now = env.ledger().timestamp();
+        let mut overdue = Vec::new(&env);
+        for incident_id in id_list(&env, &DataKey::FacilityReports(facility_id)).iter() {
+            for id in id_list(&env, &DataKey::IncidentActions(incident_id)).iter() {
+                if let Some(action) = load_action(&env, id) {
+                    if action.status == ActionStatus::Open && action.due_date < now {
+                        overdue.push_back(action);
+                    }
+                }
+            }
+        }
+        overdue
+    }
+
+    /// Close a report once any RCA is complete and all actions are done.
+    pub fn close_report(env: Env, safety_officer: Address, incident_id: u64) -> Result<(), Error> {
+        let mut report = load_report(&env, incident_id).ok_or(Error::ReportNotFound)?;
+        require_safety_officer(&env, &safety_officer, &report.facility_id)?;
+        if report.status == ReportStatus::Closed {
+            return Err(Error::InvalidStatus);
+        }
+        if report.rca_id != 0 {
+            let rca = load_rca(&env, report.rca_id).ok_or(Error::RcaNotFound)?;
+            if rca.completed_at == 0 {
+                return Err(Error::RcaIncomplete);
+            }
+        }
+        for id in id_list(&env, &DataKey::IncidentActions(incident_id)).iter() {
+            if let Some(action) = load_action(&env, id) {
+                if action.status == ActionStatus::Open {
+                    return Err(Error::OpenActionsRemain);
+                }
+            }
+        }
+
+        report.status = ReportStatus::Closed;
+        report.closed_at = env.ledger().timestamp();
+        save_report(&env, &report);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Queries
+    // -------------------------------------------------------------------
+
+    /// Event counts and rates for incidents that occurred in `[from, to]`.
+    pub fn get_facility_event_rate(
+        env: Env,
+        facility_id: Address,
+        from: u64,
+        to: u64,
+    ) -> Result<FacilityEventRate, Error> {
+        if to < from {
+            return Err(Error::InvalidInput);
+        }
+        let mut rate = FacilityEventRate {
+            facility_id: facility_id.clone(),
+            from,
+            to,
+            total_events: 0,
+            falls: 0,
+            medication_errors: 0,
+            pressure_injuries: 0,
+            harm_events: 0,
+            patient_days: 0,
+            events_per_1000_days_x100: 0,
+            harm_per_1000_days_x100: 0,
+        };
+
+        for id in id_list(&env, &DataKey::FacilityReports(facility_id.clone())).iter() {
+            let Some(report) = load_report(&env, id) else {
+                continue;
+            };
+            if report.occurred_at < from || report.occurred_at > to {
+                continue;
+            }
+            rate.total_events += 1;
+            match report.event_type {
+                EventType::Fall => rate.falls += 1,
+                EventType::MedicationError => rate.medication_errors += 1,
+                EventType::PressureInjury => rate.pressure_injuries += 1,
+                EventType::Other => {}
+            }
+            if report.harm_level > HarmLevel::NoHarm {
+                rate.harm_events += 1;
+            }
+        }
+
+        let entries: Vec<PatientDaysEntry> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PatientDays(facility_id))
+            .unwrap_or(Vec::new(&env));
+        for entry in entries.iter() {
+            if entry.period_start >= from && entry.period_start <= to {
+                rate.patient_days += entry.patient_days;
+            }
+        }
+        if rate.patient_days > 0 {
+            let days = u64::from(rate.patient_days);
+            rate.events_per_1000_days_x100 = (u64::from(rate.total_events) * 100_000 / days) as u32;
+            rate.harm_per_1000_days_x100 = (u64::from(rate.harm_events) * 100_000 / days) as u32;
+        }
+
+        Ok(rate)
+    }
+
+    /// Incidents naming `provider_id` that occurred in `[from, to]`, with
+    /// the state of their corrective actions.
+    pub fn get_provider_safety_summary(
+        env: Env,
+        provider_id: Address,
+        from: u64,
+        to: u64,
+    ) -> ProviderSafetySummary {
+        let now = env.ledger().timestamp();
+        let mut summary = ProviderSafetySummary {
+            provider_id: provider_id.clone(),
+            from,
+            to,
+            incidents: 0,
+            harm_events: 0,
+            severe_harm_events: 0,
+            open_actions: 0,
+            overdue_actions: 0,
+        };
+
+        for id in id_list(&env, &DataKey::ProviderReports(provider_id)).iter() {
+            let Some(report) = load_report(&env, id) else {
+                continue;
+            };
+            if report.occurred_at < from || report.occurred_at > to {
+                continue;
+            }
+            summary.incidents += 1;
+            if report.harm_level > HarmLevel::NoHarm {
+                summary.harm_events += 1;
+            }
+            if report.harm_level >= HarmLevel::Severe {
+                summary.severe_harm_events += 1;
+            }
+            for action_id in id_list(&env, &DataKey::IncidentActions(id)).iter() {
+                if let Some(action) = load_action(&env, action_id) {
+                    if action.status == ActionStatus::Open {
+                        summary.open_actions += 1;
+                        if action.due_date < now {
+                            summary.overdue_actions += 1;
+                        }
+                    }
+                }
+            }
+        }
+        summary
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_safety_officer(env: &Env, caller: &Address, facility_id: &Address) -> Result<(), Error> {
+    caller.require_auth();
+    let officer = safety_officer(env, facility_id).ok_or(Error::FacilityNotRegistered)?;
+    if officer != *caller {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::{CorrectiveAction, DataKey, IncidentReport, RootCauseAnalysis};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn safety_officer(env: &Env, facility_id: &Address) -> Option<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SafetyOfficer(facility_id.clone()))
+}
+
+pub fn is_reporter(env: &Env, facility_id: &Address, staff: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Reporter(facility_id.clone(), staff.clone()))
+        .unwrap_or(false)
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+pub fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+pub fn load_report(env: &Env, id: u64) -> Option<IncidentReport> {
+    env.storage().persistent().get(&DataKey::Report(id))
+}
+
+pub fn save_report(env: &Env, report: &IncidentReport) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Report(report.id), report);
+}
+
+pub fn load_rca(env: &Env, id: u64) -> Option<RootCauseAnalysis> {
+    env.storage().persistent().get(&DataKey::Rca(id))
+}
+
+pub fn save_rca(env: &Env, rca: &RootCauseAnalysis) {
+    env.storage().persistent().set(&DataKey::Rca(rca.id), rca);
+}
+
+pub fn load_action(env: &Env, id: u64) -> Option<CorrectiveAction> {
+    env.storage().persistent().get(&DataKey::Action(id))
+}
+
+pub fn save_action(env: &Env, action: &CorrectiveAction) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Action(action.id), action);
+}
+
+// -----------------------------------------------------------------------
+// Indexes
+// -----------------------------------------------------------------------
+
+pub fn id_list(env: &Env, key: &DataKey) -> Vec<u64> {
+    env.storage().persistent().get(key).unwrap_or(Vec::new(env))
+}
+
+pub fn push_id(env: &Env, key: DataKey, id: u64) {
+    let mut ids = id_list(env, &key);
+    ids.push_back(id);
+    env.storage().persistent().set(&key, &ids);
+}
@@ -0,0 +1,387 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Events, Ledger},
+    Address, BytesN, Env, String,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY: u64 = 86_400;
+const NOW: u64 = 1_700_000_000;
+
+fn setup() -> (
+    Env,
+    Address,
+    Address,
+    Address,
+    Address,
+    SafetyEventsContractClient<'static>,
+) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(NOW);
+    let contract_id = env.register(SafetyEventsContract, ());
+    let client = SafetyEventsContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    let officer = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(&admin, &facility, &officer);
+    client.add_reporter(&officer, &facility, &nurse);
+
+    (env, admin, facility, officer, nurse, client)
+}
+
+fn incident(
+    env: &Env,
+    facility: &Address,
+    event_type: EventType,
+    harm_level: HarmLevel,
+) -> IncidentInput {
+    IncidentInput {
+        facility_id: facility.clone(),
+        event_type,
+        severity: Severity::Moderate,
+        harm_level,
+        occurred_at: NOW - DAY,
+        location: String::from_str(env, "4 West"),
+        patient_id: Some(Address::generate(env)),
+        involved_provider: None,
+        description_hash: BytesN::from_array(env, &[7u8; 32]),
+        anonymous: false,
+    }
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_file_report_and_anonymous_mode() {
+    let (env, _, facility, _, nurse, client) = setup();
+    let named = client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::Mild),
+    );
+    assert_eq!(env.events().all().len(), 1);
+
+    let mut input = incident(
+        &env,
+        &facility,
+        EventType::MedicationError,
+        HarmLevel::NoHarm,
+    );
+    input.anonymous = true;
+    let anonymous = client.file_report(&nurse, &input);
+
+    assert_eq!(client.get_report(&named).reporter, Some(nurse.clone()));
+    assert_eq!(client.get_report(&anonymous).reporter, None);
+    assert_eq!(client.get_facility_reports(&facility, &0, &10).len(), 2);
+
+    let outsider = Address::generate(&env);
+    assert_eq!(
+        client.try_file_report(&outsider, &input),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_rca_and_actions_gate_closure() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    let id = client.file_report(
+        &nurse,
+        &incident(
+            &env,
+            &facility,
+            EventType::PressureInjury,
+            HarmLevel::Moderate,
+        ),
+    );
+    let lead = Address::generate(&env);
+    let rca_id = client.assign_rca(&officer, &id, &lead, &(NOW + 30 * DAY));
+    assert_eq!(client.get_report(&id).status, ReportStatus::RcaInProgress);
+    let action_id = client.add_corrective_action(
+        &officer,
+        &id,
+        &nurse,
+        &String::from_str(&env, "Turning schedule audit"),
+        &(NOW + 14 * DAY),
+    );
+
+    assert_eq!(
+        client.try_close_report(&officer, &id),
+        Err(Ok(Error::RcaIncomplete))
+    );
+    client.complete_rca(&lead, &rca_id, &BytesN::from_array(&env, &[1u8; 32]));
+    assert_eq!(
+        client.try_close_report(&officer, &id),
+        Err(Ok(Error::OpenActionsRemain))
+    );
+
+    client.complete_corrective_action(&nurse, &action_id);
+    client.close_report(&officer, &id);
+    let report = client.get_report(&id);
+    assert_eq!(report.status, ReportStatus::Closed);
+    assert_eq!(report.closed_at, NOW);
+}
+
+#[test]
+fn test_overdue_actions() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    let id = client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::NoHarm),
+    );
+    let owner = Address::generate(&env);
+    let first = client.add_corrective_action(
+        &officer,
+        &id,
+        &owner,
+        &String::from_str(&env, "Bed alarm check"),
+        &(NOW + 2 * DAY),
+    );
+    client.add_corrective_action(
+        &officer,
+        &id,
+        &owner,
+        &String::from_str(&env, "Signage"),
+        &(NOW + 10 * DAY),
+    );
+
+    env.ledger().set_timestamp(NOW + 5 * DAY);
+    let overdue = client.get_overdue_actions(&facility);
+    assert_eq!(overdue.len(), 1);
+    assert_eq!(overdue.get(0).unwrap().id, first);
+}
+
+#[test]
+fn test_facility_event_rate() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::Mild),
+    );
+    client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::NoHarm),
+    );
+    client.file_report(
+        &nurse,
+        &incident(
+            &env,
+            &facility,
+            EventType::MedicationError,
+            HarmLevel::Severe,
+        ),
+    );
+    client.record_patient_days(&officer, &facility, &(NOW - 10 * DAY), &2_000);
+
+    let rate = client.get_facility_event_rate(&facility, &(NOW - 30 * DAY), &NOW);
+    assert_eq!(rate.total_events, 3);
+    assert_eq!(rate.falls, 2);
+    assert_eq!(rate.medication_errors, 1);
+    assert_eq!(rate.harm_events, 2);
+    assert_eq!(rate.patient_days, 2_000);
+    // 3 events per 2,000 days = 1.50 per 1,000.
+    assert_eq!(rate.events_per_1000_days_x100, 150);
+    assert_eq!(rate.harm_per_1000_days_x100, 100);
+}
+
+#[test]
+fn test_provider_safety_summary() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    let provider = Address::generate(&env);
+    let mut input = incident(
+        &env,
+        &facility,
+        EventType::MedicationError,
+        HarmLevel::Severe,
+    );
+    input.involved_provider = Some(provider.clone());
+    let id = client.file_report(&nurse, &input);
+    client.add_corrective_action(
+        &officer,
+        &id,
+        &provider,
+        &String::from_str(&env, "Double-check training"),
+        &(NOW + DAY),
+    );
+    client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::Mild),
+    );
+
+    env.ledger().set_timestamp(NOW + 3 * DAY);
+    let summary = client.get_provider_safety_summary(&provider, &(NOW - 30 * DAY), &NOW);
+    assert_eq!(summary.incidents, 1);
+    assert_eq!(summary.harm_events, 1);
+    assert_eq!(summary.severe_harm_events, 1);
+    assert_eq!(summary.open_actions, 1);
+    assert_eq!(summary.overdue_actions, 1);
+}
+
+#[test]
+fn test_facility_roles_enforced() {
+    let (env, admin, facility, officer, nurse, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&officer, &other, &officer),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_reporter(&nurse, &facility, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_reporter(&officer, &other, &nurse),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+    assert_eq!(
+        client.try_record_patient_days(&nurse, &facility, &NOW, &100),
+        Err(Ok(Error::Unauthorized))
+    );
+}
+
+#[test]
+fn test_report_validation() {
+    let (env, _, facility, _, nurse, client) = setup();
+
+    let mut future = incident(&env, &facility, EventType::Fall, HarmLevel::NoHarm);
+    future.occurred_at = NOW + DAY;
+    assert_eq!(
+        client.try_file_report(&nurse, &future),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    let unregistered = Address::generate(&env);
+    assert_eq!(
+        client.try_file_report(
+            &nurse,
+            &incident(&env, &unregistered, EventType::Fall, HarmLevel::NoHarm),
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+    assert_eq!(client.try_get_report(&99), Err(Ok(Error::ReportNotFound)));
+    assert_eq!(
+        client.try_get_facility_event_rate(&facility, &NOW, &(NOW - DAY)),
+        Err(Ok(Error::InvalidInput))
+    );
+}
+
+#[test]
+fn test_rca_errors() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    let id = client.file_report(
+        &nurse,
+        &incident(
+            &env,
+            &facility,
+            EventType::MedicationError,
+            HarmLevel::Moderate,
+        ),
+    );
+    let lead = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_rca(&nurse, &id, &lead, &(NOW + DAY)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_assign_rca(&officer, &id, &lead, &NOW),
+        Err(Ok(Error::InvalidInput))
+    );
+    assert_eq!(
+        client.try_assign_rca(&officer, &99, &lead, &(NOW + DAY)),
+        Err(Ok(Error::ReportNotFound))
+    );
+
+    let rca_id = client.assign_rca(&officer, &id, &lead, &(NOW + DAY));
+    assert_eq!(
+        client.try_assign_rca(&officer, &id, &lead, &(NOW + DAY)),
+        Err(Ok(Error::InvalidStatus))
+    );
+
+    let findings = BytesN::from_array(&env, &[2u8; 32]);
+    assert_eq!(
+        client.try_complete_rca(&officer, &rca_id, &findings),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.complete_rca(&lead, &rca_id, &findings);
+    assert_eq!(
+        client.try_complete_rca(&lead, &rca_id, &findings),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(client.try_get_rca(&99), Err(Ok(Error::RcaNotFound)));
+}
+
+#[test]
+fn test_corrective_action_errors_and_closed_reports() {
+    let (env, _, facility, officer, nurse, client) = setup();
+    let id = client.file_report(
+        &nurse,
+        &incident(&env, &facility, EventType::Fall, HarmLevel::Mild),
+    );
+    let description = String::from_str(&env, "Hourly rounding");
+
+    assert_eq!(
+        client.try_add_corrective_action(&nurse, &id, &nurse, &description, &(NOW + DAY)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_add_corrective_action(&officer, &id, &nurse, &description, &NOW),
+        Err(Ok(Error::InvalidInput))
+    );
+
+    let action_id = client.add_corrective_action(&officer, &id, &nurse, &description, &(NOW + DAY));
+    assert_eq!(
+        client.try_complete_corrective_action(&officer, &action_id),
+        Err(Ok(Error::Unauthorized))
+    );
+    client.complete_corrective_action(&nurse, &action_id);
+    assert_eq!(
+        client.try_complete_corrective_action(&nurse, &action_id),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_complete_corrective_action(&nurse, &99),
+        Err(Ok(Error::ActionNotFound))
+    );
+
+    // Closed reports accept no further work
+    client.close_report(&officer, &id);
+    assert_eq!(
+        client.try_close_report(&officer, &id),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_add_corrective_action(&officer, &id, &nurse, &description, &(NOW + DAY)),
+        Err(Ok(Error::InvalidStatus))
+    );
+    assert_eq!(
+        client.try_assign_rca(&officer, &id, &nurse, &(NOW + DAY)),
+        Err(Ok(Error::InvalidStatus))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, _, officer, _, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&officer), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&officer, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
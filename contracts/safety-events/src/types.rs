@@ -0,0 +1,230 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, BytesN, String};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    FacilityNotRegistered = 4,
+    ReportNotFound = 5,
+    RcaNotFound = 6,
+    ActionNotFound = 7,
+    InvalidInput = 8,
+    InvalidStatus = 9,
+    RcaIncomplete = 10,
+    OpenActionsRemain = 11,
+}
+
+// -----------------------------------------------------------------------
+// Enums
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum EventType {
+    Fall,
+    MedicationError,
+    PressureInjury,
+    Other,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum Severity {
+    Low,
+    Moderate,
+    High,
+    Critical,
+}
+
+/// Harm to the patient, ordered from least to most serious.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord)]
+pub enum HarmLevel {
+    NoHarm,
+    Mild,
+    Moderate,
+    Severe,
+    Death,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ReportStatus {
+    Open,
+    RcaInProgress,
+    Closed,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ActionStatus {
+    Open,
+    Completed,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncidentInput {
+    pub facility_id: Address,
+    pub event_type: EventType,
+    pub severity: Severity,
+    pub harm_level: HarmLevel,
+    pub occurred_at: u64,
+    pub location: String,
+    pub patient_id: Option<Address>,
+    pub involved_provider: Option<Address>,
+    /// Hash of the narrative held off-chain.
+    pub description_hash: BytesN<32>,
+    /// When set, the reporter's address is not stored with the report.
+    pub anonymous: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncidentReport {
+    pub id: u64,
+    pub facility_id: Address,
+    pub event_type: EventType,
+    pub severity: Severity,
+    pub harm_level: HarmLevel,
+    pub occurred_at: u64,
+    pub reported_at: u64,
+    pub location: String,
+    pub patient_id: Option<Address>,
+    pub involved_provider: Option<Address>,
+    pub description_hash: BytesN<32>,
+    pub reporter: Option<Address>,
+    pub status: ReportStatus,
+    /// 0 until a root cause analysis is assigned.
+    pub rca_id: u64,
+    pub closed_at: u64,
+}
+
+/// Root cause analysis task for one incident.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RootCauseAnalysis {
+    pub id: u64,
+    pub incident_id: u64,
+    pub lead: Address,
+    pub assigned_at: u64,
+    pub due_date: u64,
+    pub findings_hash: Option<BytesN<32>>,
+    /// 0 while the analysis is in progress.
+    pub completed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CorrectiveAction {
+    pub id: u64,
+    pub incident_id: u64,
+    pub owner: Address,
+    pub description: String,
+    pub due_date: u64,
+    pub status: ActionStatus,
+    pub completed_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PatientDaysEntry {
+    pub period_start: u64,
+    pub patient_days: u32,
+}
+
+/// Event counts for a facility over a period. Rates are per 1,000
+/// patient-days, scaled by 100.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FacilityEventRate {
+    pub facility_id: Address,
+    pub from: u64,
+    pub to: u64,
+    pub total_events: u32,
+    pub falls: u32,
+    pub medication_errors: u32,
+    pub pressure_injuries: u32,
+    /// Events with harm above `NoHarm`.
+    pub harm_events: u32,
+    pub patient_days: u32,
+    pub events_per_1000_days_x100: u32,
+    pub harm_per_1000_days_x100: u32,
+}
+
+/// Safety figures for one provider, for use by scorecard contracts.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ProviderSafetySummary {
+    pub provider_id: Address,
+    pub from: u64,
+    pub to: u64,
+    pub incidents: u32,
+    pub harm_events: u32,
+    pub severe_harm_events: u32,
+    pub open_actions: u32,
+    pub overdue_actions: u32,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IncidentFiled {
+    #[topic]
+    pub facility_id: Address,
+    pub incident_id: u64,
+    pub event_type: EventType,
+    pub harm_level: HarmLevel,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CorrectiveActionAssigned {
+    #[topic]
+    pub owner: Address,
+    pub incident_id: u64,
+    pub action_id: u64,
+    pub due_date: u64,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// Facility -> safety officer address.
+    SafetyOfficer(Address),
+    /// (facility, staff) -> may file reports.
+    Reporter(Address, Address),
+    Report(u64),
+    Rca(u64),
+    Action(u64),
+    /// Facility -> incident ids.
+    FacilityReports(Address),
+    /// Involved provider -> incident ids.
+    ProviderReports(Address),
+    /// Incident -> corrective action ids.
+    IncidentActions(u64),
+    /// Facility -> reported patient-days by period.
+    PatientDays(Address),
+    ReportCounter,
+    RcaCounter,
+    ActionCounter,
+}
@@ -0,0 +1,44 @@
+use soroban_sdk::{Address, Env};
+
+use crate::{check_grant, AccessError, AccessKey, Grant};
+
+// -----------------------------------------------------------------------
+// Owner-to-grantee access
+// -----------------------------------------------------------------------
+
+/// Record that `owner` (typically a patient) lets `grantee` read their
+/// data. The caller is responsible for authorizing `owner`.
+pub fn grant_access(env: &Env, owner: &Address, grantee: &Address, expires_at: Option<u64>) {
+    let grant = Grant {
+        granted_by: owner.clone(),
+        granted_at: env.ledger().timestamp(),
+        expires_at,
+    };
+    env.storage()
+        .persistent()
+        .set(&AccessKey::AclGrant(owner.clone(), grantee.clone()), &grant);
+}
+
+pub fn revoke_access(env: &Env, owner: &Address, grantee: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&AccessKey::AclGrant(owner.clone(), grantee.clone()));
+}
+
+pub fn get_access(env: &Env, owner: &Address, grantee: &Address) -> Option<Grant> {
+    env.storage()
+        .persistent()
+        .get(&AccessKey::AclGrant(owner.clone(), grantee.clone()))
+}
+
+/// Owners always have access to their own data.
+pub fn check_access(env: &Env, owner: &Address, grantee: &Address) -> Result<(), AccessError> {
+    if owner == grantee {
+        return Ok(());
+    }
+    check_grant(env, get_access(env, owner, grantee), AccessError::NoGrant).map(|_| ())
+}
+
+pub fn has_access(env: &Env, owner: &Address, grantee: &Address) -> bool {
+    check_access(env, owner, grantee).is_ok()
+}
@@ -0,0 +1,107 @@
+#![no_std]
+
+//! Access-control building blocks shared by the healthcare contracts.
+//!
+//! Every helper works on the calling contract's own storage through the
+//! `Env` it is given, so the library adds no contract of its own. Entries
+//! are stored under [`AccessKey`] so they cannot clash with a contract's
+//! `DataKey` variants.
+
+mod grants;
+mod roles;
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contracttype, Address, Env, IntoVal, Symbol, Val};
+
+pub use grants::*;
+pub use roles::*;
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+/// Reasons an access check fails. Contracts convert this into their own
+/// `#[contracterror]` type, usually with a `From` impl.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum AccessError {
+    /// No admin has been stored under the given key.
+    NotInitialized,
+    /// The caller is not the stored admin.
+    NotAdmin,
+    /// The caller does not hold the required role.
+    MissingRole,
+    /// No grant exists from the owner to the caller.
+    NoGrant,
+    /// A role or grant exists but its expiry has passed.
+    Expired,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+/// A role or access grant. `expires_at` of `None` never expires.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Grant {
+    pub granted_by: Address,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum AccessKey {
+    /// (role, holder) -> Grant
+    AclRole(Symbol, Address),
+    /// (owner, grantee) -> Grant
+    AclGrant(Address, Address),
+}
+
+// -----------------------------------------------------------------------
+// Checks
+// -----------------------------------------------------------------------
+
+/// Require `admin` to authorize the call and match the address the contract
+/// keeps in instance storage under `admin_key`.
+pub fn require_admin<K>(env: &Env, admin: &Address, admin_key: &K) -> Result<(), AccessError>
+where
+    K: IntoVal<Env, Val>,
+{
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(admin_key)
+        .ok_or(AccessError::NotInitialized)?;
+    if stored != *admin {
+        return Err(AccessError::NotAdmin);
+    }
+    Ok(())
+}
+
+/// `true` once the ledger time is past `expires_at`.
+pub fn is_expired(env: &Env, expires_at: Option<u64>) -> bool {
+    match expires_at {
+        Some(at) => env.ledger().timestamp() > at,
+        None => false,
+    }
+}
+
+pub(crate) fn check_grant(
+    env: &Env,
+    grant: Option<Grant>,
+    missing: AccessError,
+) -> Result<Grant, AccessError> {
+    let grant = grant.ok_or(missing)?;
+    if is_expired(env, grant.expires_at) {
+        return Err(AccessError::Expired);
+    }
+    Ok(grant)
+}
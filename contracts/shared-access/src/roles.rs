@@ -0,0 +1,53 @@
+use soroban_sdk::{Address, Env, Symbol};
+
+use crate::{check_grant, AccessError, AccessKey, Grant};
+
+// -----------------------------------------------------------------------
+// Role registry
+// -----------------------------------------------------------------------
+
+/// Give `holder` a role, replacing any earlier grant of the same role.
+/// The caller is responsible for authorizing `granted_by`.
+pub fn grant_role(
+    env: &Env,
+    role: &Symbol,
+    holder: &Address,
+    granted_by: &Address,
+    expires_at: Option<u64>,
+) {
+    let grant = Grant {
+        granted_by: granted_by.clone(),
+        granted_at: env.ledger().timestamp(),
+        expires_at,
+    };
+    env.storage()
+        .persistent()
+        .set(&AccessKey::AclRole(role.clone(), holder.clone()), &grant);
+}
+
+pub fn revoke_role(env: &Env, role: &Symbol, holder: &Address) {
+    env.storage()
+        .persistent()
+        .remove(&AccessKey::AclRole(role.clone(), holder.clone()));
+}
+
+/// The stored role grant, whether or not it has expired.
+pub fn get_role(env: &Env, role: &Symbol, holder: &Address) -> Option<Grant> {
+    env.storage()
+        .persistent()
+        .get(&AccessKey::AclRole(role.clone(), holder.clone()))
+}
+
+pub fn check_role(env: &Env, role: &Symbol, holder: &Address) -> Result<(), AccessError> {
+    check_grant(env, get_role(env, role, holder), AccessError::MissingRole).map(|_| ())
+}
+
+pub fn has_role(env: &Env, role: &Symbol, holder: &Address) -> bool {
+    check_role(env, role, holder).is_ok()
+}
+
+/// Require `holder` to authorize the call and hold an unexpired `role`.
+pub fn require_role(env: &Env, role: &Symbol, holder: &Address) -> Result<(), AccessError> {
+    holder.require_auth();
+    check_role(env, role, holder)
+}
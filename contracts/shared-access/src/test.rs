@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, symbol_short,
+    testutils::{Address as _, Ledger},
+    Address, Env, Symbol,
+};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+/// Empty contract that gives the helpers a storage context.
+#[contract]
+struct Harness;
+
+fn setup() -> (Env, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let contract_id = env.register(Harness, ());
+    (env, contract_id)
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_require_admin() {
+    let (env, contract_id) = setup();
+    let admin = Address::generate(&env);
+    let other = Address::generate(&env);
+    let key = symbol_short!("Admin");
+
+    // Each check authorizes the caller, so each runs in its own frame.
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            require_admin(&env, &admin, &key),
+            Err(AccessError::NotInitialized)
+        );
+    });
+    env.as_contract(&contract_id, || {
+        env.storage().instance().set(&key, &admin);
+        assert_eq!(require_admin(&env, &admin, &key), Ok(()));
+    });
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            require_admin(&env, &other, &key),
+            Err(AccessError::NotAdmin)
+        );
+    });
+}
+
+#[test]
+fn test_role_grant_revoke_and_expiry() {
+    let (env, contract_id) = setup();
+    let admin = Address::generate(&env);
+    let nurse = Address::generate(&env);
+    let role = Symbol::new(&env, "triage");
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(
+            check_role(&env, &role, &nurse),
+            Err(AccessError::MissingRole)
+        );
+
+        grant_role(&env, &role, &nurse, &admin, Some(2_000));
+        assert!(has_role(&env, &role, &nurse));
+        assert_eq!(require_role(&env, &role, &nurse), Ok(()));
+        assert_eq!(get_role(&env, &role, &nurse).unwrap().granted_by, admin);
+
+        env.ledger().set_timestamp(2_001);
+        assert_eq!(check_role(&env, &role, &nurse), Err(AccessError::Expired));
+
+        grant_role(&env, &role, &nurse, &admin, None);
+        assert!(has_role(&env, &role, &nurse));
+        revoke_role(&env, &role, &nurse);
+        assert!(!has_role(&env, &role, &nurse));
+    });
+}
+
+#[test]
+fn test_owner_grants() {
+    let (env, contract_id) = setup();
+    let patient = Address::generate(&env);
+    let provider = Address::generate(&env);
+
+    env.as_contract(&contract_id, || {
+        assert!(has_access(&env, &patient, &patient));
+        assert_eq!(
+            check_access(&env, &patient, &provider),
+            Err(AccessError::NoGrant)
+        );
+
+        grant_access(&env, &patient, &provider, Some(1_500));
+        assert!(has_access(&env, &patient, &provider));
+        // Grants are one-directional.
+        assert!(!has_access(&env, &provider, &patient));
+
+        env.ledger().set_timestamp(1_501);
+        assert_eq!(
+            check_access(&env, &patient, &provider),
+            Err(AccessError::Expired)
+        );
+
+        revoke_access(&env, &patient, &provider);
+        assert_eq!(get_access(&env, &patient, &provider), None);
+    });
+}
+
+#[test]
+fn test_is_expired() {
+    let (env, _) = setup();
+    assert!(!is_expired(&env, None));
+    assert!(!is_expired(&env, Some(1_000)));
+    assert!(is_expired(&env, Some(999)));
+}
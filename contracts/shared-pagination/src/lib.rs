@@ -0,0 +1,52 @@
+#![no_std]
+
+//! Offset/limit pagination shared by the healthcare contracts' list getters.
+//!
+//! Getters take `offset` and `limit` and return at most [`MAX_PAGE_SIZE`]
+//! items, so a long history can be read a page at a time instead of in one
+//! response that grows without bound. An `offset` past the end yields an
+//! empty page.
+
+#[cfg(test)]
+mod test;
+
+use core::ops::Range;
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val, Vec};
+
+/// Largest page any getter returns, whatever `limit` the caller asks for.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+/// The index range of the requested page within a list of `len` items.
+pub fn page_range(len: u32, offset: u32, limit: u32) -> Range<u32> {
+    let start = offset.min(len);
+    let end = start.saturating_add(limit.min(MAX_PAGE_SIZE)).min(len);
+    start..end
+}
+
+/// One page of `items`.
+pub fn paginate<T>(items: &Vec<T>, offset: u32, limit: u32) -> Vec<T> {
+    items.slice(page_range(items.len(), offset, limit))
+}
+
+/// One page of an index of keys, each resolved through `load`. Only the
+/// keys on the page are loaded; keys that no longer resolve are skipped.
+pub fn paginate_map<K, T, F>(
+    env: &Env,
+    keys: &Vec<K>,
+    offset: u32,
+    limit: u32,
+    mut load: F,
+) -> Vec<T>
+where
+    K: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    F: FnMut(K) -> Option<T>,
+{
+    let mut page = Vec::new(env);
+    for i in page_range(keys.len(), offset, limit) {
+        if let Some(item) = load(keys.get_unchecked(i)) {
+            page.push_back(item);
+        }
+    }
+    page
+}
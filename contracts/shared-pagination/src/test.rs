@@ -0,0 +1,56 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{Env, Vec};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn numbers(env: &Env, count: u32) -> Vec<u32> {
+    let mut items = Vec::new(env);
+    for i in 0..count {
+        items.push_back(i);
+    }
+    items
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_page_range_clamps() {
+    assert_eq!(page_range(10, 0, 3), 0..3);
+    assert_eq!(page_range(10, 8, 3), 8..10);
+    assert_eq!(page_range(10, 12, 3), 10..10);
+    assert_eq!(page_range(10, 0, 0), 0..0);
+    assert_eq!(page_range(500, 0, u32::MAX), 0..MAX_PAGE_SIZE);
+    assert_eq!(
+        page_range(u32::MAX, u32::MAX - 1, 10),
+        u32::MAX - 1..u32::MAX
+    );
+}
+
+#[test]
+fn test_paginate_walks_pages() {
+    let env = Env::default();
+    let items = numbers(&env, 7);
+
+    assert_eq!(paginate(&items, 0, 3), numbers(&env, 3));
+    let last = paginate(&items, 6, 3);
+    assert_eq!(last.len(), 1);
+    assert_eq!(last.get(0), Some(6));
+    assert!(paginate(&items, 7, 3).is_empty());
+}
+
+#[test]
+fn test_paginate_map_skips_missing() {
+    let env = Env::default();
+    let keys = numbers(&env, 6);
+
+    let evens = paginate_map(&env, &keys, 1, 4, |k| (k % 2 == 0).then_some(k * 10));
+    assert_eq!(evens.len(), 2);
+    assert_eq!(evens.get(0), Some(20));
+    assert_eq!(evens.get(1), Some(40));
+}
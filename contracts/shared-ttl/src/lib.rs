@@ -0,0 +1,81 @@
+#![no_std]
+
+//! Time-to-live policy for contract storage, shared by the healthcare
+//! contracts.
+//!
+//! Soroban archives persistent entries whose TTL runs out. Contracts call
+//! these helpers from their load/save paths so records that are in use stay
+//! live, and expose explicit bump entrypoints for closed records that are
+//! kept for audit but rarely touched.
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{Env, IntoVal, Val};
+
+/// Ledgers closed per day, assuming five-second ledgers.
+pub const LEDGERS_PER_DAY: u32 = 17_280;
+
+// -----------------------------------------------------------------------
+// Policies
+// -----------------------------------------------------------------------
+
+/// When an entry gets extended (`threshold`) and to how many ledgers from
+/// now (`extend_to`).
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct TtlPolicy {
+    pub threshold: u32,
+    pub extend_to: u32,
+}
+
+impl TtlPolicy {
+    pub const fn days(threshold_days: u32, extend_to_days: u32) -> Self {
+        TtlPolicy {
+            threshold: threshold_days * LEDGERS_PER_DAY,
+            extend_to: extend_to_days * LEDGERS_PER_DAY,
+        }
+    }
+}
+
+/// Records read or written as part of normal operation.
+pub const ACTIVE: TtlPolicy = TtlPolicy::days(30, 60);
+
+/// Closed records kept for audit, extended on request.
+pub const ARCHIVE: TtlPolicy = TtlPolicy::days(90, 180);
+
+/// The contract instance: admin, configuration and counters.
+pub const INSTANCE: TtlPolicy = TtlPolicy::days(30, 60);
+
+// -----------------------------------------------------------------------
+// Extension
+// -----------------------------------------------------------------------
+
+/// Extend a persistent entry the caller knows exists, e.g. right after
+/// writing it.
+pub fn extend_persistent<K>(env: &Env, key: &K, policy: TtlPolicy)
+where
+    K: IntoVal<Env, Val>,
+{
+    env.storage()
+        .persistent()
+        .extend_ttl(key, policy.threshold, policy.extend_to);
+}
+
+/// Extend a persistent entry if it exists. Returns whether it did.
+pub fn bump_persistent<K>(env: &Env, key: &K, policy: TtlPolicy) -> bool
+where
+    K: IntoVal<Env, Val>,
+{
+    if !env.storage().persistent().has(key) {
+        return false;
+    }
+    extend_persistent(env, key, policy);
+    true
+}
+
+/// Extend the contract instance and its code.
+pub fn extend_instance(env: &Env, policy: TtlPolicy) {
+    env.storage()
+        .instance()
+        .extend_ttl(policy.threshold, policy.extend_to);
+}
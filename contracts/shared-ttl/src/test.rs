@@ -0,0 +1,47 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{contract, symbol_short, testutils::storage::Persistent as _, Env};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+/// Empty contract that gives the helpers a storage context.
+#[contract]
+struct Harness;
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_policy_days() {
+    let policy = TtlPolicy::days(1, 2);
+    assert_eq!(policy.threshold, LEDGERS_PER_DAY);
+    assert_eq!(policy.extend_to, 2 * LEDGERS_PER_DAY);
+}
+
+#[test]
+fn test_extend_and_bump_persistent() {
+    let env = Env::default();
+    let contract_id = env.register(Harness, ());
+    let key = symbol_short!("record");
+
+    env.as_contract(&contract_id, || {
+        assert!(!bump_persistent(&env, &key, ACTIVE));
+
+        env.storage().persistent().set(&key, &1u32);
+        extend_persistent(&env, &key, ACTIVE);
+        assert_eq!(env.storage().persistent().get_ttl(&key), ACTIVE.extend_to);
+
+        assert!(bump_persistent(&env, &key, ARCHIVE));
+        assert_eq!(env.storage().persistent().get_ttl(&key), ARCHIVE.extend_to);
+
+        // Above the threshold, a bump leaves the TTL alone.
+        assert!(bump_persistent(&env, &key, ACTIVE));
+        assert_eq!(env.storage().persistent().get_ttl(&key), ARCHIVE.extend_to);
+
+        extend_instance(&env, INSTANCE);
+    });
+}
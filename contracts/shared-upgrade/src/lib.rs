@@ -0,0 +1,75 @@
+#![no_std]
+
+//! WASM upgrade and storage schema-version helpers shared by the healthcare
+//! contracts.
+//!
+//! Each contract keeps its own `SCHEMA_VERSION` constant, records it at
+//! `initialize`, and exposes admin-gated `upgrade` and `migrate`
+//! entrypoints built on these helpers, plus a public `schema_version`.
+//! Contracts deployed before versioning existed read back as version 0.
+//!
+//! Bump a contract's `SCHEMA_VERSION` whenever one of its stored types
+//! changes shape, and add the matching data migration to its `migrate`
+//! entrypoint, keyed on the version `migrate` returns. To ship new code the
+//! admin calls `upgrade` with the uploaded WASM hash, then `migrate` on the
+//! new code.
+
+#[cfg(test)]
+mod test;
+
+use soroban_sdk::{contracttype, BytesN, Env};
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum UpgradeKey {
+    /// u32 schema version of the data the contract has stored.
+    SchemaVersion,
+}
+
+// -----------------------------------------------------------------------
+// Schema version
+// -----------------------------------------------------------------------
+
+/// The schema version of the stored data, or 0 if none was ever recorded.
+pub fn schema_version(env: &Env) -> u32 {
+    env.storage()
+        .instance()
+        .get(&UpgradeKey::SchemaVersion)
+        .unwrap_or(0)
+}
+
+/// Record `version` unconditionally. Call from `initialize`, where there is
+/// no older data to migrate.
+pub fn init_schema_version(env: &Env, version: u32) {
+    env.storage()
+        .instance()
+        .set(&UpgradeKey::SchemaVersion, &version);
+}
+
+/// Move the stored schema version up to `target` and return the version it
+/// was at, so the caller knows which data migrations it has just run.
+///
+/// Panics if the stored data is newer than `target`; that means an older
+/// WASM was installed over newer data and migrating would lose information.
+pub fn migrate(env: &Env, target: u32) -> u32 {
+    let from = schema_version(env);
+    if from > target {
+        panic!("stored schema is newer than this contract version");
+    }
+    init_schema_version(env, target);
+    from
+}
+
+// -----------------------------------------------------------------------
+// Upgrade
+// -----------------------------------------------------------------------
+
+/// Replace the running contract's WASM. The caller is responsible for the
+/// admin check; storage is untouched until `migrate` runs on the new code.
+pub fn upgrade(env: &Env, new_wasm_hash: BytesN<32>) {
+    env.deployer().update_current_contract_wasm(new_wasm_hash);
+}
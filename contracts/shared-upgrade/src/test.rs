@@ -0,0 +1,46 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{contract, Env};
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+/// Empty contract that gives the helpers a storage context.
+#[contract]
+struct Harness;
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_schema_version_defaults_and_migrates() {
+    let env = Env::default();
+    let contract_id = env.register(Harness, ());
+
+    env.as_contract(&contract_id, || {
+        assert_eq!(schema_version(&env), 0);
+
+        assert_eq!(migrate(&env, 1), 0);
+        assert_eq!(schema_version(&env), 1);
+
+        // Re-running at the same version is a no-op.
+        assert_eq!(migrate(&env, 1), 1);
+        assert_eq!(migrate(&env, 3), 1);
+        assert_eq!(schema_version(&env), 3);
+    });
+}
+
+#[test]
+#[should_panic(expected = "stored schema is newer")]
+fn test_migrate_rejects_downgrade() {
+    let env = Env::default();
+    let contract_id = env.register(Harness, ());
+
+    env.as_contract(&contract_id, || {
+        init_schema_version(&env, 2);
+        migrate(&env, 1);
+    });
+}
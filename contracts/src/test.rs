@@ -2,10 +2,10 @@
 mod test {
     use crate::{
         AppointmentScheduling, AppointmentSchedulingClient, AppointmentStatus, HealthcareRegistry,
-        HealthcareRegistryClient,
+        HealthcareRegistryClient, SCHEMA_VERSION,
     };
 
-    use soroban_sdk::{testutils::Address as _, Address, Env, String, Vec};
+    use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, String, Vec};
 
     fn setup_test(env: &Env) -> (HealthcareRegistryClient<'static>, Address, Address) {
         // Updated from register_contract to register
@@ -15,6 +15,7 @@ mod test {
         let admin = Address::generate(env);
         let institution = Address::generate(env);
 
+        env.mock_all_auths();
         client.init(&admin);
 
         (client, admin, institution)
@@ -109,6 +110,32 @@ mod test {
         assert_eq!(data.metadata, new_meta);
     }
 
+    #[test]
+    fn test_init_records_schema_version() {
+        let env = Env::default();
+        let (client, _, _) = setup_test(&env);
+
+        assert_eq!(client.schema_version(), SCHEMA_VERSION);
+    }
+
+    #[test]
+    #[should_panic(expected = "Already initialized")]
+    fn test_init_twice_fails() {
+        let env = Env::default();
+        let (client, _, _) = setup_test(&env);
+
+        client.init(&Address::generate(&env));
+    }
+
+    #[test]
+    #[should_panic(expected = "Unauthorized admin")]
+    fn test_upgrade_by_non_admin_fails() {
+        let env = Env::default();
+        let (client, _, inst_addr) = setup_test(&env);
+
+        client.upgrade(&inst_addr, &BytesN::from_array(&env, &[0u8; 32]));
+    }
+
     // Appointment Scheduling Tests
     #[test]
     fn test_create_appointment() {
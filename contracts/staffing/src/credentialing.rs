@@ -0,0 +1,13 @@
+use soroban_sdk::{contractclient, Address, Env, Symbol};
+
+/// Subset of the healthcare-credentialing interface used to confirm that a
+/// provider may work a unit's service line before a shift is assigned.
+#[contractclient(name = "CredentialingClient")]
+pub trait Credentialing {
+    fn has_active_privilege(
+        env: Env,
+        provider_id: Address,
+        facility_id: Address,
+        privilege_category: Symbol,
+    ) -> bool;
+}
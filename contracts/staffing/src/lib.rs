@@ -0,0 +1,331 @@
+#![no_std]
+
+pub mod credentialing;
+mod storage;
+mod types;
+
+#[cfg(test)]
+mod test;
+
+use credentialing::CredentialingClient;
+use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
+use storage::*;
+pub use types::*;
+
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
+#[contract]
+pub struct StaffingContract;
+
+#[contractimpl]
+impl StaffingContract {
+    // -------------------------------------------------------------------
+    // Administration
+    // -------------------------------------------------------------------
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if get_admin(&env).is_some() {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    pub fn register_facility(
+        env: Env,
+        admin: Address,
+        facility_id: Address,
+        name: String,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Facility(facility_id), &name);
+        Ok(())
+    }
+
+    /// Units with `verify_privileges` set check assignments against this
+    /// credentialing contract.
+    pub fn set_credentialing_contract(
+        env: Env,
+        admin: Address,
+        contract_id: Address,
+    ) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::Credentialing, &contract_id);
+        Ok(())
+    }
+
+    // -------------------------------------------------------------------
+    // Units and shifts
+    // -------------------------------------------------------------------
+
+    pub fn create_unit(
+        env: Env,
+        facility_id: Address,
+        name: String,
+        service_line: Symbol,
+        min_staff: u32,
+        verify_privileges: bool,
+    ) -> Result<u64, Error> {
+        require_facility(&env, &facility_id)?;
+        let unit = StaffUnit {
+            unit_id: next_id(&env, DataKey::UnitCounter),
+            facility_id,
+            name,
+            service_line,
+            min_staff,
+            verify_privileges,
+        };
+        save_unit(&env, &unit);
+        Ok(unit.unit_id)
+    }
+
+    pub fn get_unit(env: Env, unit_id: u64) -> Result<StaffUnit, Error> {
+        load_unit(&env, unit_id).ok_or(Error::UnitNotFound)
+    }
+
+    pub fn create_shift(env: Env, facility_id: Address, input: ShiftInput) -> Result<u64, Error> {
+        require_facility(&env, &facility_id)?;
+        let unit = load_unit(&env, input.unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.facility_id != facility_id {
+            return Err(Error::Unauthorized);
+        }
+        if input.ends_at <= input.starts_at || input.capacity == 0 {
+            return Err(Error::InvalidInput);
+        }
+
+        let shift = Shift {
+            shift_id: next_id(&env, DataKey::ShiftCounter),
+            unit_id: input.unit_id,
+            facility_id,
+            role: input.role,
+            starts_at: input.starts_at,
+            ends_at: input.ends_at,
+            capacity: input.capacity,
+            assigned: 0,
+            cancelled: false,
+        };
+        save_shift(&env, &shift);
+        add_unit_shift(&env, shift.unit_id, shift.shift_id);
+        Ok(shift.shift_id)
+    }
+
+    /// Cancel a shift and release everyone assigned to it.
+    pub fn cancel_shift(env: Env, facility_id: Address, shift_id: u64) -> Result<(), Error> {
+        let mut shift = load_facility_shift(&env, &facility_id, shift_id)?;
+        if shift.cancelled {
+            return Err(Error::ShiftCancelled);
+        }
+        for assignment in shift_assignments(&env, shift_id).iter() {
+            remove_staff_shift(&env, &assignment.staff_id, shift_id);
+        }
+        save_shift_assignments(&env, shift_id, &Vec::new(&env));
+        shift.assigned = 0;
+        shift.cancelled = true;
+        save_shift(&env, &shift);
+        Ok(())
+    }
+
+    pub fn get_shift(env: Env, shift_id: u64) -> Result<Shift, Error> {
+        load_shift(&env, shift_id).ok_or(Error::ShiftNotFound)
+    }
+
+    /// Shifts on a unit starting within `[from, to]`.
+    pub fn get_unit_shifts(env: Env, unit_id: u64, from: u64, to: u64) -> Vec<Shift> {
+        let mut shifts = Vec::new(&env);
+        for id in unit_shifts(&env, unit_id).iter() {
+            if let Some(shift) = load_shift(&env, id) {
+                if shift.starts_at >= from && shift.starts_at <= to {
+                    shifts.push_back(shift);
+                }
+            }
+        }
+        shifts
+    }
+
+    /// Active shifts starting within `[from, to]` with fewer assigned staff
+    /// than the unit minimum.
+    pub fn get_understaffed_shifts(
+        env: Env,
+        unit_id: u64,
+        from: u64,
+        to: u64,
+    ) -> Result<Vec<Shift>, Error> {
+        let unit = load_unit(&env, unit_id).ok_or(Error::UnitNotFound)?;
+        let mut shifts = Vec::new(&env);
+        for shift in Self::get_unit_shifts(env.clone(), unit_id, from, to).iter() {
+            if !shift.cancelled && shift.assigned < unit.min_staff {
+                shifts.push_back(shift);
+            }
+        }
+        Ok(shifts)
+    }
+
+    // -------------------------------------------------------------------
+    // Assignments
+    // -------------------------------------------------------------------
+
+    /// Assign a staff member to a shift. On units that verify privileges the
+    /// assignment is only accepted if healthcare-credentialing reports an
+    /// active, unsuspended privilege for the unit's service line.
+    pub fn assign_staff(
+        env: Env,
+        facility_id: Address,
+        shift_id: u64,
+        staff_id: Address,
+    ) -> Result<Assignment, Error> {
+        let mut shift = load_facility_shift(&env, &facility_id, shift_id)?;
+        if shift.cancelled {
+            return Err(Error::ShiftCancelled);
+        }
+        if shift.assigned >= shift.capacity {
+            return Err(Error::ShiftFull);
+        }
+
+        let mut staff_shift_ids = staff_shifts(&env, &staff_id);
+        for id in staff_shift_ids.iter() {
+            if id == shift_id {
+                return Err(Error::AlreadyAssigned);
+            }
+            if let Some(other) = load_shift(&env, id) {
+                if other.starts_at < shift.ends_at && shift.starts_at < other.ends_at {
+                    return Err(Error::ScheduleConflict);
+                }
+            }
+        }
+
+        let unit = load_unit(&env, shift.unit_id).ok_or(Error::UnitNotFound)?;
+        if unit.verify_privileges {
+            let credentialing: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Credentialing)
+                .ok_or(Error::CredentialingNotConfigured)?;
+            let active = CredentialingClient::new(&env, &credentialing).has_active_privilege(
+                &staff_id,
+                &facility_id,
+                &unit.service_line,
+            );
+            if !active {
+                return Err(Error::PrivilegesNotActive);
+            }
+        }
+
+        let assignment = Assignment {
+            shift_id,
+            staff_id: staff_id.clone(),
+            assigned_at: env.ledger().timestamp(),
+            privileges_verified: unit.verify_privileges,
+        };
+        let mut assignments = shift_assignments(&env, shift_id);
+        assignments.push_back(assignment.clone());
+        save_shift_assignments(&env, shift_id, &assignments);
+        staff_shift_ids.push_back(shift_id);
+        save_staff_shifts(&env, &staff_id, &staff_shift_ids);
+        shift.assigned += 1;
+        save_shift(&env, &shift);
+
+        StaffAssigned {
+            staff_id,
+            shift_id,
+            unit_id: shift.unit_id,
+            privileges_verified: assignment.privileges_verified,
+        }
+        .publish(&env);
+
+        Ok(assignment)
+    }
+
+    pub fn unassign_staff(
+        env: Env,
+        facility_id: Address,
+        shift_id: u64,
+        staff_id: Address,
+    ) -> Result<(), Error> {
+        let mut shift = load_facility_shift(&env, &facility_id, shift_id)?;
+        let mut assignments = shift_assignments(&env, shift_id);
+        let index = assignments
+            .iter()
+            .position(|a| a.staff_id == staff_id)
+            .ok_or(Error::NotAssigned)?;
+        assignments.remove(index as u32);
+        save_shift_assignments(&env, shift_id, &assignments);
+        remove_staff_shift(&env, &staff_id, shift_id);
+        shift.assigned -= 1;
+        save_shift(&env, &shift);
+        Ok(())
+    }
+
+    pub fn get_shift_assignments(env: Env, shift_id: u64) -> Vec<Assignment> {
+        shift_assignments(&env, shift_id)
+    }
+
+    pub fn get_staff_shifts(env: Env, staff_id: Address, offset: u32, limit: u32) -> Vec<Shift> {
+        let ids = staff_shifts(&env, &staff_id);
+        shared_pagination::paginate_map(&env, &ids, offset, limit, |id| load_shift(&env, id))
+    }
+
+    // -------------------------------------------------------------------
+    // Upgrades
+    // -------------------------------------------------------------------
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    if get_admin(env).ok_or(Error::NotInitialized)? != *admin {
+        return Err(Error::Unauthorized);
+    }
+    Ok(())
+}
+
+fn require_facility(env: &Env, facility_id: &Address) -> Result<(), Error> {
+    facility_id.require_auth();
+    if !is_facility(env, facility_id) {
+        return Err(Error::FacilityNotRegistered);
+    }
+    Ok(())
+}
+
+fn load_facility_shift(env: &Env, facility_id: &Address, shift_id: u64) -> Result<Shift, Error> {
+    require_facility(env, facility_id)?;
+    let shift = load_shift(env, shift_id).ok_or(Error::ShiftNotFound)?;
+    if shift.facility_id != *facility_id {
+        return Err(Error::Unauthorized);
+    }
+    Ok(shift)
+}
+
+fn remove_staff_shift(env: &Env, staff_id: &Address, shift_id: u64) {
+    let mut ids = staff_shifts(env, staff_id);
+    if let Some(index) = ids.first_index_of(shift_id) {
+        ids.remove(index);
+        save_staff_shifts(env, staff_id, &ids);
+    }
+}
@@ -0,0 +1,96 @@
+use soroban_sdk::{Address, Env, Vec};
+
+use crate::types::{Assignment, DataKey, Shift, StaffUnit};
+
+// -----------------------------------------------------------------------
+// Roles
+// -----------------------------------------------------------------------
+
+pub fn get_admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+pub fn is_facility(env: &Env, facility_id: &Address) -> bool {
+    env.storage()
+        .persistent()
+        .has(&DataKey::Facility(facility_id.clone()))
+}
+
+// -----------------------------------------------------------------------
+// Counters
+// -----------------------------------------------------------------------
+
+pub fn next_id(env: &Env, key: DataKey) -> u64 {
+    let next = env.storage().instance().get::<_, u64>(&key).unwrap_or(0) + 1;
+    env.storage().instance().set(&key, &next);
+    next
+}
+
+// -----------------------------------------------------------------------
+// Units and shifts
+// -----------------------------------------------------------------------
+
+pub fn load_unit(env: &Env, unit_id: u64) -> Option<StaffUnit> {
+    env.storage().persistent().get(&DataKey::Unit(unit_id))
+}
+
+pub fn save_unit(env: &Env, unit: &StaffUnit) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Unit(unit.unit_id), unit);
+}
+
+pub fn load_shift(env: &Env, shift_id: u64) -> Option<Shift> {
+    env.storage().persistent().get(&DataKey::Shift(shift_id))
+}
+
+pub fn save_shift(env: &Env, shift: &Shift) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::Shift(shift.shift_id), shift);
+}
+
+pub fn unit_shifts(env: &Env, unit_id: u64) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnitShifts(unit_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn add_unit_shift(env: &Env, unit_id: u64, shift_id: u64) {
+    let mut ids = unit_shifts(env, unit_id);
+    ids.push_back(shift_id);
+    env.storage()
+        .persistent()
+        .set(&DataKey::UnitShifts(unit_id), &ids);
+}
+
+// -----------------------------------------------------------------------
+// Assignments
+// -----------------------------------------------------------------------
+
+pub fn shift_assignments(env: &Env, shift_id: u64) -> Vec<Assignment> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ShiftAssignments(shift_id))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_shift_assignments(env: &Env, shift_id: u64, assignments: &Vec<Assignment>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::ShiftAssignments(shift_id), assignments);
+}
+
+pub fn staff_shifts(env: &Env, staff_id: &Address) -> Vec<u64> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::StaffShifts(staff_id.clone()))
+        .unwrap_or(Vec::new(env))
+}
+
+pub fn save_staff_shifts(env: &Env, staff_id: &Address, ids: &Vec<u64>) {
+    env.storage()
+        .persistent()
+        .set(&DataKey::StaffShifts(staff_id.clone()), ids);
+}
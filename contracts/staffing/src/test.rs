@@ -0,0 +1,336 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events},
+    Address, BytesN, Env, String,
+};
+
+mod mock_credentialing {
+    use soroban_sdk::{contract, contractimpl, Address, Env, Symbol};
+
+    #[contract]
+    pub struct MockCredentialing;
+
+    #[contractimpl]
+    impl MockCredentialing {
+        pub fn grant(env: Env, provider_id: Address, privilege_category: Symbol) {
+            env.storage()
+                .persistent()
+                .set(&(provider_id, privilege_category), &true);
+        }
+
+        pub fn has_active_privilege(
+            env: Env,
+            provider_id: Address,
+            _facility_id: Address,
+            privilege_category: Symbol,
+        ) -> bool {
+            env.storage()
+                .persistent()
+                .get(&(provider_id, privilege_category))
+                .unwrap_or(false)
+        }
+    }
+}
+
+// -----------------------------------------------------------------------
+// Helpers
+// -----------------------------------------------------------------------
+
+const DAY_START: u64 = 1_700_000_000;
+const HOURS_12: u64 = 12 * 3_600;
+
+fn setup() -> (Env, Address, Address, StaffingContractClient<'static>) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register(StaffingContract, ());
+    let client = StaffingContractClient::new(&env, &contract_id);
+
+    let admin = Address::generate(&env);
+    let facility = Address::generate(&env);
+    client.initialize(&admin);
+    client.register_facility(&admin, &facility, &String::from_str(&env, "General"));
+
+    (env, admin, facility, client)
+}
+
+fn unit(
+    env: &Env,
+    client: &StaffingContractClient,
+    facility: &Address,
+    verify_privileges: bool,
+) -> u64 {
+    client.create_unit(
+        facility,
+        &String::from_str(env, "ICU"),
+        &symbol_short!("icu"),
+        &2,
+        &verify_privileges,
+    )
+}
+
+fn shift(
+    client: &StaffingContractClient,
+    facility: &Address,
+    unit_id: u64,
+    starts_at: u64,
+    capacity: u32,
+) -> u64 {
+    client.create_shift(
+        facility,
+        &ShiftInput {
+            unit_id,
+            role: symbol_short!("rn"),
+            starts_at,
+            ends_at: starts_at + HOURS_12,
+            capacity,
+        },
+    )
+}
+
+// -----------------------------------------------------------------------
+// Tests
+// -----------------------------------------------------------------------
+
+#[test]
+fn test_assign_without_verification() {
+    let (env, _, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+    let shift_id = shift(&client, &facility, unit_id, DAY_START, 2);
+    let nurse = Address::generate(&env);
+
+    let assignment = client.assign_staff(&facility, &shift_id, &nurse);
+    assert!(!assignment.privileges_verified);
+    assert_eq!(env.events().all().len(), 1);
+    assert_eq!(client.get_shift(&shift_id).assigned, 1);
+    assert_eq!(client.get_staff_shifts(&nurse, &0, &10).len(), 1);
+    assert_eq!(
+        client.try_assign_staff(&facility, &shift_id, &nurse),
+        Err(Ok(Error::AlreadyAssigned))
+    );
+}
+
+#[test]
+fn test_capacity_and_overlap() {
+    let (env, _, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+    let day = shift(&client, &facility, unit_id, DAY_START, 1);
+    let overlapping = shift(&client, &facility, unit_id, DAY_START + HOURS_12 / 2, 2);
+    let night = shift(&client, &facility, unit_id, DAY_START + HOURS_12, 2);
+    let nurse = Address::generate(&env);
+
+    client.assign_staff(&facility, &day, &nurse);
+    assert_eq!(
+        client.try_assign_staff(&facility, &day, &Address::generate(&env)),
+        Err(Ok(Error::ShiftFull))
+    );
+    assert_eq!(
+        client.try_assign_staff(&facility, &overlapping, &nurse),
+        Err(Ok(Error::ScheduleConflict))
+    );
+    // Back-to-back shifts do not overlap.
+    client.assign_staff(&facility, &night, &nurse);
+}
+
+#[test]
+fn test_privilege_check_via_credentialing() {
+    let (env, admin, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, true);
+    let shift_id = shift(&client, &facility, unit_id, DAY_START, 2);
+    let doctor = Address::generate(&env);
+
+    assert_eq!(
+        client.try_assign_staff(&facility, &shift_id, &doctor),
+        Err(Ok(Error::CredentialingNotConfigured))
+    );
+
+    let credentialing = env.register(mock_credentialing::MockCredentialing, ());
+    client.set_credentialing_contract(&admin, &credentialing);
+    assert_eq!(
+        client.try_assign_staff(&facility, &shift_id, &doctor),
+        Err(Ok(Error::PrivilegesNotActive))
+    );
+
+    mock_credentialing::MockCredentialingClient::new(&env, &credentialing)
+        .grant(&doctor, &symbol_short!("icu"));
+    let assignment = client.assign_staff(&facility, &shift_id, &doctor);
+    assert!(assignment.privileges_verified);
+}
+
+#[test]
+fn test_understaffed_and_unassign() {
+    let (env, _, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+    let first = shift(&client, &facility, unit_id, DAY_START, 3);
+    let second = shift(&client, &facility, unit_id, DAY_START + HOURS_12, 3);
+    let a = Address::generate(&env);
+    let b = Address::generate(&env);
+    client.assign_staff(&facility, &first, &a);
+    client.assign_staff(&facility, &first, &b);
+
+    let short = client.get_understaffed_shifts(&unit_id, &DAY_START, &(DAY_START + 2 * HOURS_12));
+    assert_eq!(short.len(), 1);
+    assert_eq!(short.get(0).unwrap().shift_id, second);
+
+    client.unassign_staff(&facility, &first, &b);
+    assert_eq!(client.get_shift_assignments(&first).len(), 1);
+    assert_eq!(client.get_staff_shifts(&b, &0, &10).len(), 0);
+    assert_eq!(
+        client.try_unassign_staff(&facility, &first, &b),
+        Err(Ok(Error::NotAssigned))
+    );
+}
+
+#[test]
+fn test_cancel_shift_releases_staff() {
+    let (env, _, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+    let shift_id = shift(&client, &facility, unit_id, DAY_START, 2);
+    let nurse = Address::generate(&env);
+    client.assign_staff(&facility, &shift_id, &nurse);
+
+    client.cancel_shift(&facility, &shift_id);
+    let cancelled = client.get_shift(&shift_id);
+    assert!(cancelled.cancelled);
+    assert_eq!(cancelled.assigned, 0);
+    assert_eq!(client.get_staff_shifts(&nurse, &0, &10).len(), 0);
+    assert_eq!(
+        client.try_assign_staff(&facility, &shift_id, &nurse),
+        Err(Ok(Error::ShiftCancelled))
+    );
+
+    let other_facility = Address::generate(&env);
+    assert_eq!(
+        client.try_cancel_shift(&other_facility, &shift_id),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+}
+
+#[test]
+fn test_administration_requires_admin() {
+    let (env, admin, facility, client) = setup();
+    assert_eq!(
+        client.try_initialize(&admin),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+
+    let other = Address::generate(&env);
+    assert_eq!(
+        client.try_register_facility(&facility, &other, &String::from_str(&env, "Annex")),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_credentialing_contract(&facility, &other),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_create_unit(
+            &other,
+            &String::from_str(&env, "ED"),
+            &symbol_short!("ed"),
+            &1,
+            &false,
+        ),
+        Err(Ok(Error::FacilityNotRegistered))
+    );
+}
+
+#[test]
+fn test_shift_validation() {
+    let (env, _, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+
+    let input = ShiftInput {
+        unit_id,
+        role: symbol_short!("rn"),
+        starts_at: DAY_START,
+        ends_at: DAY_START,
+        capacity: 2,
+    };
+    assert_eq!(
+        client.try_create_shift(&facility, &input),
+        Err(Ok(Error::InvalidInput))
+    );
+    let mut no_capacity = input.clone();
+    no_capacity.ends_at = DAY_START + HOURS_12;
+    no_capacity.capacity = 0;
+    assert_eq!(
+        client.try_create_shift(&facility, &no_capacity),
+        Err(Ok(Error::InvalidInput))
+    );
+    let mut unknown_unit = no_capacity.clone();
+    unknown_unit.unit_id = 99;
+    unknown_unit.capacity = 2;
+    assert_eq!(
+        client.try_create_shift(&facility, &unknown_unit),
+        Err(Ok(Error::UnitNotFound))
+    );
+
+    assert_eq!(client.try_get_unit(&99), Err(Ok(Error::UnitNotFound)));
+    assert_eq!(client.try_get_shift(&99), Err(Ok(Error::ShiftNotFound)));
+    assert_eq!(
+        client.try_get_understaffed_shifts(&99, &DAY_START, &(DAY_START + HOURS_12)),
+        Err(Ok(Error::UnitNotFound))
+    );
+    assert_eq!(
+        client.try_assign_staff(&facility, &99, &Address::generate(&env)),
+        Err(Ok(Error::ShiftNotFound))
+    );
+}
+
+#[test]
+fn test_shifts_scoped_to_owning_facility() {
+    let (env, admin, facility, client) = setup();
+    let unit_id = unit(&env, &client, &facility, false);
+    let shift_id = shift(&client, &facility, unit_id, DAY_START, 2);
+    let nurse = Address::generate(&env);
+    client.assign_staff(&facility, &shift_id, &nurse);
+
+    let other_facility = Address::generate(&env);
+    client.register_facility(&admin, &other_facility, &String::from_str(&env, "Annex"));
+    assert_eq!(
+        client.try_create_shift(
+            &other_facility,
+            &ShiftInput {
+                unit_id,
+                role: symbol_short!("rn"),
+                starts_at: DAY_START,
+                ends_at: DAY_START + HOURS_12,
+                capacity: 1,
+            },
+        ),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_assign_staff(&other_facility, &shift_id, &Address::generate(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_unassign_staff(&other_facility, &shift_id, &nurse),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        client.try_cancel_shift(&other_facility, &shift_id),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    client.cancel_shift(&facility, &shift_id);
+    assert_eq!(
+        client.try_cancel_shift(&facility, &shift_id),
+        Err(Ok(Error::ShiftCancelled))
+    );
+}
+
+#[test]
+fn test_upgrade_and_migrate_require_admin() {
+    let (env, _, facility, client) = setup();
+    assert_eq!(client.schema_version(), SCHEMA_VERSION);
+
+    assert_eq!(client.try_migrate(&facility), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        client.try_upgrade(&facility, &BytesN::from_array(&env, &[0u8; 32])),
+        Err(Ok(Error::Unauthorized))
+    );
+}
@@ -0,0 +1,115 @@
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, String, Symbol};
+
+// -----------------------------------------------------------------------
+// Error types
+// -----------------------------------------------------------------------
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    Unauthorized = 1,
+    NotInitialized = 2,
+    AlreadyInitialized = 3,
+    FacilityNotRegistered = 4,
+    UnitNotFound = 5,
+    ShiftNotFound = 6,
+    InvalidInput = 7,
+    ShiftCancelled = 8,
+    ShiftFull = 9,
+    AlreadyAssigned = 10,
+    NotAssigned = 11,
+    ScheduleConflict = 12,
+    CredentialingNotConfigured = 13,
+    PrivilegesNotActive = 14,
+}
+
+// -----------------------------------------------------------------------
+// Records
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffUnit {
+    pub unit_id: u64,
+    pub facility_id: Address,
+    pub name: String,
+    /// Privilege category checked in healthcare-credentialing.
+    pub service_line: Symbol,
+    /// Minimum headcount per shift.
+    pub min_staff: u32,
+    /// When set, assignments require active privileges for `service_line`.
+    pub verify_privileges: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ShiftInput {
+    pub unit_id: u64,
+    pub role: Symbol,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub capacity: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Shift {
+    pub shift_id: u64,
+    pub unit_id: u64,
+    pub facility_id: Address,
+    pub role: Symbol,
+    pub starts_at: u64,
+    pub ends_at: u64,
+    pub capacity: u32,
+    pub assigned: u32,
+    pub cancelled: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Assignment {
+    pub shift_id: u64,
+    pub staff_id: Address,
+    pub assigned_at: u64,
+    /// True when privileges were confirmed with healthcare-credentialing.
+    pub privileges_verified: bool,
+}
+
+// -----------------------------------------------------------------------
+// Events
+// -----------------------------------------------------------------------
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StaffAssigned {
+    #[topic]
+    pub staff_id: Address,
+    pub shift_id: u64,
+    pub unit_id: u64,
+    pub privileges_verified: bool,
+}
+
+// -----------------------------------------------------------------------
+// Storage keys
+// -----------------------------------------------------------------------
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    /// Address of the healthcare-credentialing contract
+    Credentialing,
+    /// Facility -> display name.
+    Facility(Address),
+    Unit(u64),
+    Shift(u64),
+    /// Unit -> shift ids.
+    UnitShifts(u64),
+    /// Shift -> assignments.
+    ShiftAssignments(u64),
+    /// Staff member -> shift ids.
+    StaffShifts(Address),
+    UnitCounter,
+    ShiftCounter,
+}
@@ -0,0 +1,15 @@
+use soroban_sdk::{contractclient, BytesN, Env, Symbol};
+
+/// Subset of the healthcare-analytics interface used to publish anonymized
+/// visit satisfaction scores.
+#[contractclient(name = "AnalyticsClient")]
+pub trait Analytics {
+    fn record_metric(
+        env: Env,
+        metric_type: Symbol,
+        value: i128,
+        category: Symbol,
+        timestamp: u64,
+        metadata_hash: Option<BytesN<32>>,
+    );
+}
@@ -1,8 +1,13 @@
+use crate::analytics::AnalyticsClient;
 use crate::types::{
-    DataKey, EligibilityResult, Error, PrescriptionRequest, VirtualVisit, VisitStatus,
+    DataKey, EligibilityResult, Error, PrescriptionRequest, SatisfactionRating, SessionAttestation,
+    TelehealthConsent, VirtualVisit, VisitOutcomeRecorded, VisitStatus, VisitSummaryRecorded,
 };
 use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, String, Symbol, Vec};
 
+/// See `shared_upgrade` for when to bump this.
+const SCHEMA_VERSION: u32 = 1;
+
 #[contract]
 pub struct TelemedicineContract;
 
@@ -39,6 +44,10 @@ impl TelemedicineContract {
             session_end: None,
             patient_location: String::from_str(&env, ""), // Default empty, updated at start
             consent_documented: consent_obtained,
+            patient_joined_at: None,
+            duration_minutes: None,
+            no_show_party: None,
+            summary_hash: None,
         };
 
         env.storage()
@@ -81,6 +90,10 @@ impl TelemedicineContract {
 
         // Let's assume validation happened via verify_telemedicine_eligibility before calling
 
+        if !visit.consent_documented && !has_active_consent(&env, &visit.patient_id) {
+            return Err(Error::ConsentRequired);
+        }
+
         visit.status = VisitStatus::InProgress;
         visit.session_start = Some(session_start_time);
         visit.patient_location = patient_location_state;
@@ -88,6 +101,7 @@ impl TelemedicineContract {
         env.storage()
             .persistent()
             .set(&DataKey::VirtualVisit(visit_id), &visit);
+        add_attestation(&env, visit_id, provider_id, "START", session_start_time);
 
         // Mock a simple session token
         let token = String::from_str(&env, "SESSION_TOKEN_123");
@@ -149,12 +163,20 @@ impl TelemedicineContract {
             return Err(Error::InvalidStatusTransition);
         }
 
+        // The attested duration must agree with the recorded start and end
+        let start = visit.session_start.unwrap_or(0);
+        if session_end_time <= start || (session_end_time - start) / 60 != session_duration as u64 {
+            return Err(Error::InvalidSessionTime);
+        }
+
         visit.status = VisitStatus::Completed;
         visit.session_end = Some(session_end_time);
+        visit.duration_minutes = Some(session_duration);
 
         env.storage()
             .persistent()
             .set(&DataKey::VirtualVisit(visit_id), &visit);
+        add_attestation(&env, visit_id, provider_id, "END", session_end_time);
         env.events().publish(
             (Symbol::new(&env, "session_ended"), visit_id),
             session_duration,
@@ -247,4 +269,333 @@ impl TelemedicineContract {
 
         Ok(rx_id)
     }
+
+    pub fn initialize(env: Env, admin: Address) -> Result<(), Error> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(Error::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        shared_upgrade::init_schema_version(&env, SCHEMA_VERSION);
+        Ok(())
+    }
+
+    /// Point satisfaction capture at a healthcare-analytics contract
+    pub fn set_analytics_contract(
+        env: Env,
+        admin: Address,
+        analytics: Address,
+    ) -> Result<(), Error> {
+        admin.require_auth();
+        let stored: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::NotInitialized)?;
+        if stored != admin {
+            return Err(Error::NotAuthorized);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::AnalyticsContract, &analytics);
+        Ok(())
+    }
+
+    /// Standing telehealth consent, used when a visit was booked without one
+    pub fn record_telehealth_consent(
+        env: Env,
+        patient_id: Address,
+        consent_hash: BytesN<32>,
+        expires_at: Option<u64>,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let consent = TelehealthConsent {
+            patient_id: patient_id.clone(),
+            consent_hash,
+            granted_at: env.ledger().timestamp(),
+            expires_at,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::TelehealthConsent(patient_id), &consent);
+        Ok(())
+    }
+
+    pub fn revoke_telehealth_consent(env: Env, patient_id: Address) {
+        patient_id.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::TelehealthConsent(patient_id));
+    }
+
+    pub fn has_telehealth_consent(env: Env, patient_id: Address) -> bool {
+        has_active_consent(&env, &patient_id)
+    }
+
+    /// Patient attests to joining an in-progress session
+    pub fn attest_session_join(
+        env: Env,
+        visit_id: u64,
+        patient_id: Address,
+        joined_at: u64,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let mut visit = load_visit(&env, visit_id)?;
+        if visit.patient_id != patient_id {
+            return Err(Error::NotAuthorized);
+        }
+        if visit.status != VisitStatus::InProgress {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if joined_at < visit.session_start.unwrap_or(0) {
+            return Err(Error::InvalidSessionTime);
+        }
+
+        visit.patient_joined_at = Some(joined_at);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VirtualVisit(visit_id), &visit);
+        add_attestation(&env, visit_id, patient_id, "JOIN", joined_at);
+
+        Ok(())
+    }
+
+    /// Either party reports that the other did not attend. Only possible once
+    /// the scheduled time has passed and the session never started.
+    pub fn record_no_show(env: Env, visit_id: u64, reporter: Address) -> Result<(), Error> {
+        reporter.require_auth();
+
+        let mut visit = load_visit(&env, visit_id)?;
+        let absent = if reporter == visit.provider_id {
+            visit.patient_id.clone()
+        } else if reporter == visit.patient_id {
+            visit.provider_id.clone()
+        } else {
+            return Err(Error::NotAuthorized);
+        };
+        if visit.status != VisitStatus::Scheduled || env.ledger().timestamp() < visit.scheduled_time
+        {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        visit.status = VisitStatus::NoShow;
+        visit.no_show_party = Some(absent);
+        env.storage()
+            .persistent()
+            .set(&DataKey::VirtualVisit(visit_id), &visit);
+
+        VisitOutcomeRecorded {
+            visit_id,
+            status: VisitStatus::NoShow,
+            reported_by: reporter,
+            detail: None,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Close a visit that could not be held or finished for technical reasons
+    pub fn record_technical_failure(
+        env: Env,
+        visit_id: u64,
+        reporter: Address,
+        issue_description: String,
+    ) -> Result<(), Error> {
+        reporter.require_auth();
+
+        let mut visit = load_visit(&env, visit_id)?;
+        if visit.provider_id != reporter && visit.patient_id != reporter {
+            return Err(Error::NotAuthorized);
+        }
+        if visit.status != VisitStatus::Scheduled && visit.status != VisitStatus::InProgress {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        let now = env.ledger().timestamp();
+        if visit.status == VisitStatus::InProgress {
+            visit.session_end = Some(now);
+        }
+        visit.status = VisitStatus::TechnicalFailure;
+        env.storage()
+            .persistent()
+            .set(&DataKey::VirtualVisit(visit_id), &visit);
+
+        VisitOutcomeRecorded {
+            visit_id,
+            status: VisitStatus::TechnicalFailure,
+            reported_by: reporter,
+            detail: Some(issue_description),
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Attach the hash of the post-visit summary shared with the patient
+    pub fn record_visit_summary(
+        env: Env,
+        visit_id: u64,
+        provider_id: Address,
+        summary_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        provider_id.require_auth();
+
+        let mut visit = load_visit(&env, visit_id)?;
+        if visit.provider_id != provider_id {
+            return Err(Error::NotAuthorized);
+        }
+        if visit.status != VisitStatus::Completed {
+            return Err(Error::InvalidStatusTransition);
+        }
+
+        visit.summary_hash = Some(summary_hash.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::VirtualVisit(visit_id), &visit);
+
+        VisitSummaryRecorded {
+            visit_id,
+            summary_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Patient rates a completed visit (1-5). The score is forwarded without
+    /// patient identifiers to the configured analytics contract.
+    pub fn submit_visit_satisfaction(
+        env: Env,
+        visit_id: u64,
+        patient_id: Address,
+        score: u32,
+    ) -> Result<(), Error> {
+        patient_id.require_auth();
+
+        let visit = load_visit(&env, visit_id)?;
+        if visit.patient_id != patient_id {
+            return Err(Error::NotAuthorized);
+        }
+        if visit.status != VisitStatus::Completed {
+            return Err(Error::InvalidStatusTransition);
+        }
+        if !(1..=5).contains(&score) {
+            return Err(Error::InvalidRating);
+        }
+        let key = DataKey::Satisfaction(visit_id);
+        if env.storage().persistent().has(&key) {
+            return Err(Error::AlreadyRated);
+        }
+
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &key,
+            &SatisfactionRating {
+                visit_id,
+                score,
+                rated_at: now,
+            },
+        );
+
+        if let Some(analytics) = env
+            .storage()
+            .instance()
+            .get::<_, Address>(&DataKey::AnalyticsContract)
+        {
+            AnalyticsClient::new(&env, &analytics).record_metric(
+                &Symbol::new(&env, "tele_satisfaction"),
+                &(score as i128),
+                &visit.visit_type,
+                &now,
+                &None,
+            );
+        }
+
+        Ok(())
+    }
+
+    pub fn get_visit(env: Env, visit_id: u64) -> Result<VirtualVisit, Error> {
+        load_visit(&env, visit_id)
+    }
+
+    pub fn get_session_attestations(env: Env, visit_id: u64) -> Vec<SessionAttestation> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Attestations(visit_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_visit_satisfaction(env: Env, visit_id: u64) -> Option<SatisfactionRating> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Satisfaction(visit_id))
+    }
+
+    /// Admin only; see `shared_upgrade::upgrade`.
+    pub fn upgrade(env: Env, admin: Address, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        require_admin(&env, &admin)?;
+        shared_upgrade::upgrade(&env, new_wasm_hash);
+        Ok(())
+    }
+
+    /// Admin only; see `shared_upgrade::migrate`.
+    pub fn migrate(env: Env, admin: Address) -> Result<u32, Error> {
+        require_admin(&env, &admin)?;
+        Ok(shared_upgrade::migrate(&env, SCHEMA_VERSION))
+    }
+
+    pub fn schema_version(env: Env) -> u32 {
+        shared_upgrade::schema_version(&env)
+    }
+}
+
+fn require_admin(env: &Env, admin: &Address) -> Result<(), Error> {
+    admin.require_auth();
+    let stored: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::Admin)
+        .ok_or(Error::NotInitialized)?;
+    if stored != *admin {
+        return Err(Error::NotAuthorized);
+    }
+    Ok(())
+}
+
+fn load_visit(env: &Env, visit_id: u64) -> Result<VirtualVisit, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::VirtualVisit(visit_id))
+        .ok_or(Error::VisitNotFound)
+}
+
+fn has_active_consent(env: &Env, patient_id: &Address) -> bool {
+    let consent: Option<TelehealthConsent> = env
+        .storage()
+        .persistent()
+        .get(&DataKey::TelehealthConsent(patient_id.clone()));
+    match consent {
+        Some(consent) => consent
+            .expires_at
+            .is_none_or(|expiry| expiry > env.ledger().timestamp()),
+        None => false,
+    }
+}
+
+fn add_attestation(env: &Env, visit_id: u64, attested_by: Address, event: &str, timestamp: u64) {
+    let key = DataKey::Attestations(visit_id);
+    let mut attestations: Vec<SessionAttestation> = env
+        .storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or(Vec::new(env));
+    attestations.push_back(SessionAttestation {
+        attested_by,
+        event: Symbol::new(env, event),
+        timestamp,
+    });
+    env.storage().persistent().set(&key, &attestations);
 }
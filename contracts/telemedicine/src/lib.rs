@@ -1,5 +1,6 @@
 #![no_std]
 
+pub mod analytics;
 pub mod contract;
 pub mod test;
 pub mod types;
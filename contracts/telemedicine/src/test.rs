@@ -3,7 +3,7 @@
 use crate::contract::{TelemedicineContract, TelemedicineContractClient};
 use crate::types::{EligibilityResult, Error, PrescriptionRequest, VisitStatus};
 use soroban_sdk::{
-    testutils::{Address as _, Events},
+    testutils::{Address as _, Ledger},
     Address, BytesN, Env, String, Symbol, Vec,
 };
 
@@ -147,3 +147,170 @@ fn test_auth_and_eligibility_failures() {
         client.try_prescribe_during_visit(&visit_id, &provider_id, &wrong_patient, &rx_request);
     assert!(rx_res.is_err());
 }
+
+mod mock_analytics {
+    use soroban_sdk::{contract, contractimpl, symbol_short, BytesN, Env, Symbol};
+
+    #[contract]
+    pub struct MockAnalytics;
+
+    #[contractimpl]
+    impl MockAnalytics {
+        pub fn record_metric(
+            env: Env,
+            metric_type: Symbol,
+            value: i128,
+            _category: Symbol,
+            _timestamp: u64,
+            _metadata_hash: Option<BytesN<32>>,
+        ) {
+            env.storage()
+                .instance()
+                .set(&symbol_short!("last"), &(metric_type, value));
+        }
+
+        pub fn last(env: Env) -> Option<(Symbol, i128)> {
+            env.storage().instance().get(&symbol_short!("last"))
+        }
+    }
+}
+
+#[test]
+fn test_session_attestations_summary_and_satisfaction() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TelemedicineContract, ());
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+    let analytics_id = env.register(mock_analytics::MockAnalytics, ());
+    let analytics = mock_analytics::MockAnalyticsClient::new(&env, &analytics_id);
+
+    let admin = Address::generate(&env);
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+    client.initialize(&admin);
+    client.set_analytics_contract(&admin, &analytics_id);
+
+    // Booked without consent: the session cannot start until consent exists
+    let visit_id = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700000000,
+        &Symbol::new(&env, "FollowUp"),
+        &15,
+        &Symbol::new(&env, "ZoomHD"),
+        &false,
+    );
+    let res = client.try_start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000000,
+        &String::from_str(&env, "NY"),
+    );
+    assert_eq!(res, Err(Ok(Error::ConsentRequired)));
+
+    client.record_telehealth_consent(&patient_id, &BytesN::from_array(&env, &[7; 32]), &None);
+    assert!(client.has_telehealth_consent(&patient_id));
+    client.start_virtual_session(
+        &visit_id,
+        &provider_id,
+        &1700000000,
+        &String::from_str(&env, "NY"),
+    );
+    client.attest_session_join(&visit_id, &patient_id, &1700000030);
+
+    // Duration must match the attested start and end
+    let res = client.try_end_virtual_session(&visit_id, &provider_id, &1700000900, &20);
+    assert_eq!(res, Err(Ok(Error::InvalidSessionTime)));
+    client.end_virtual_session(&visit_id, &provider_id, &1700000900, &15);
+
+    let attestations = client.get_session_attestations(&visit_id);
+    assert_eq!(attestations.len(), 3);
+    assert_eq!(
+        attestations.get(1).unwrap().event,
+        Symbol::new(&env, "JOIN")
+    );
+
+    let summary = BytesN::from_array(&env, &[9; 32]);
+    client.record_visit_summary(&visit_id, &provider_id, &summary);
+    let visit = client.get_visit(&visit_id);
+    assert_eq!(visit.summary_hash, Some(summary));
+    assert_eq!(visit.duration_minutes, Some(15));
+
+    assert_eq!(
+        client.try_submit_visit_satisfaction(&visit_id, &patient_id, &6),
+        Err(Ok(Error::InvalidRating))
+    );
+    client.submit_visit_satisfaction(&visit_id, &patient_id, &4);
+    assert_eq!(
+        analytics.last(),
+        Some((Symbol::new(&env, "tele_satisfaction"), 4))
+    );
+    assert_eq!(
+        client.try_submit_visit_satisfaction(&visit_id, &patient_id, &5),
+        Err(Ok(Error::AlreadyRated))
+    );
+}
+
+#[test]
+fn test_no_show_and_technical_failure() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(TelemedicineContract, ());
+    let client = TelemedicineContractClient::new(&env, &contract_id);
+
+    let patient_id = Address::generate(&env);
+    let provider_id = Address::generate(&env);
+
+    let visit_id = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700000000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "ZoomHD"),
+        &true,
+    );
+
+    // Too early to call a no-show
+    env.ledger().set_timestamp(1699999000);
+    assert!(client.try_record_no_show(&visit_id, &provider_id).is_err());
+
+    env.ledger().set_timestamp(1700001000);
+    client.record_no_show(&visit_id, &provider_id);
+    let visit = client.get_visit(&visit_id);
+    assert_eq!(visit.status, VisitStatus::NoShow);
+    assert_eq!(visit.no_show_party, Some(patient_id.clone()));
+
+    let second = client.schedule_virtual_visit(
+        &patient_id,
+        &provider_id,
+        &1700002000,
+        &Symbol::new(&env, "Consult"),
+        &30,
+        &Symbol::new(&env, "ZoomHD"),
+        &true,
+    );
+    client.start_virtual_session(
+        &second,
+        &provider_id,
+        &1700002000,
+        &String::from_str(&env, "NY"),
+    );
+    env.ledger().set_timestamp(1700002300);
+    client.record_technical_failure(
+        &second,
+        &patient_id,
+        &String::from_str(&env, "Video dropped repeatedly"),
+    );
+    let visit = client.get_visit(&second);
+    assert_eq!(visit.status, VisitStatus::TechnicalFailure);
+    assert_eq!(visit.session_end, Some(1700002300));
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_record_technical_failure(&second, &stranger, &String::from_str(&env, "x")),
+        Err(Ok(Error::NotAuthorized))
+    );
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contracterror, contracttype, Address, BytesN, String, Symbol};
+use soroban_sdk::{contracterror, contractevent, contracttype, Address, BytesN, String, Symbol};
 
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -8,6 +8,12 @@ pub enum Error {
     VisitNotFound = 2,
     InvalidStatusTransition = 3,
     IneligibleLocation = 4,
+    AlreadyInitialized = 5,
+    NotInitialized = 6,
+    ConsentRequired = 7,
+    InvalidSessionTime = 8,
+    InvalidRating = 9,
+    AlreadyRated = 10,
 }
 
 #[contracttype]
@@ -17,6 +23,8 @@ pub enum VisitStatus {
     InProgress,
     Completed,
     Cancelled,
+    NoShow,
+    TechnicalFailure,
 }
 
 #[contracttype]
@@ -33,6 +41,54 @@ pub struct VirtualVisit {
     pub session_end: Option<u64>,
     pub patient_location: String,
     pub consent_documented: bool,
+    pub patient_joined_at: Option<u64>,
+    pub duration_minutes: Option<u32>,
+    pub no_show_party: Option<Address>,
+    pub summary_hash: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TelehealthConsent {
+    pub patient_id: Address,
+    pub consent_hash: BytesN<32>,
+    pub granted_at: u64,
+    pub expires_at: Option<u64>,
+}
+
+/// A party's signed record of a session event (START, JOIN or END).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionAttestation {
+    pub attested_by: Address,
+    pub event: Symbol,
+    pub timestamp: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SatisfactionRating {
+    pub visit_id: u64,
+    pub score: u32,
+    pub rated_at: u64,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitOutcomeRecorded {
+    #[topic]
+    pub visit_id: u64,
+    pub status: VisitStatus,
+    pub reported_by: Address,
+    pub detail: Option<String>,
+}
+
+#[contractevent]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VisitSummaryRecorded {
+    #[topic]
+    pub visit_id: u64,
+    pub summary_hash: BytesN<32>,
 }
 
 #[contracttype]
@@ -56,4 +112,9 @@ pub struct PrescriptionRequest {
 pub enum DataKey {
     VirtualVisit(u64),
     VisitCount,
+    Admin,
+    AnalyticsContract,
+    TelehealthConsent(Address),
+    Attestations(u64),
+    Satisfaction(u64),
 }